@@ -0,0 +1,146 @@
+//! Application assembly — routes plus the middleware stack, built from a
+//! [`ServerConfig`].
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use axum::Router;
+use axum::extract::DefaultBodyLimit;
+use axum::http::HeaderName;
+use tower_http::compression::predicate::{Predicate, SizeAbove};
+use tower_http::compression::{CompressionLayer, DefaultPredicate};
+use tower_http::request_id::{MakeRequestUuid, PropagateRequestIdLayer, SetRequestIdLayer};
+use tower_http::trace::TraceLayer;
+
+use ares_client::{EventPublisher, Snapshots, model_pricing};
+use ares_core::budget::BudgetGuard;
+use ares_core::circuit_breaker::CircuitBreakerRegistry;
+use ares_core::error::AppError;
+use ares_db::Database;
+
+use crate::config::ServerConfig;
+use crate::metrics;
+use crate::middleware;
+use crate::rate_limit::{self, RateLimiter};
+use crate::routes;
+use crate::shutdown::{self, Shutdown};
+use crate::state::AppState;
+use crate::telemetry;
+
+/// How often stale rate-limit entries are dropped.
+const RATE_LIMIT_CLEANUP_INTERVAL: Duration = Duration::from_secs(60);
+
+/// Responses smaller than this are sent uncompressed; below roughly one
+/// packet, compression costs more than it saves.
+const COMPRESSION_MIN_SIZE: u16 = 1024;
+
+/// Build the full application router: routes, rate limiting, draining,
+/// body limits, metrics, access logging, response compression, tracing,
+/// request IDs, and CORS.
+///
+/// Once `shutdown` is draining, API requests are answered with 503, and
+/// scrapes are tracked in it while they run; see
+/// [`shutdown::serve_with_drain`].
+///
+/// `/metrics` is included unless `config.metrics_addr` is set, in which case
+/// the caller serves [`metrics::router`] on that address. Spawns the
+/// rate-limiter cleanup task, so it must run inside a Tokio runtime.
+pub fn build_app(
+    config: ServerConfig,
+    db: Database,
+    shutdown: Shutdown,
+) -> Result<Router, AppError> {
+    let limiter = Arc::new(RateLimiter::new(&config.rate_limit)?);
+    let cleanup_limiter = limiter.clone();
+    tokio::spawn(async move {
+        loop {
+            tokio::time::sleep(RATE_LIMIT_CLEANUP_INTERVAL).await;
+            tracing::debug!(
+                "Rate limiter storage size: {} (cleaning up)",
+                cleanup_limiter.len()
+            );
+            cleanup_limiter.retain_recent();
+        }
+    });
+
+    let cors = config.cors.layer()?;
+    let metrics_router = match config.metrics_addr {
+        Some(_) => None,
+        None => Some(metrics::router(metrics::install_recorder()?)),
+    };
+
+    let events = EventPublisher::new(config.nats_url.as_deref())?;
+    let snapshots = Snapshots::new(config.snapshot_url.as_deref())?;
+    let budget = BudgetGuard::new(db.usage_repo(), config.budget).with_pricing(model_pricing::cost);
+
+    let state = Arc::new(AppState {
+        db,
+        admin_token: config.admin_token,
+        schemas_dir: config.schemas_dir,
+        proxy_config: config.proxy_config,
+        random_ua: config.random_ua,
+        browser: config.browser,
+        stealth: config.stealth,
+        tls_backend: config.tls_backend,
+        llm_api_key: config.llm_api_key,
+        llm_probe: config.llm_probe,
+        max_fetch_timeout_secs: config.max_fetch_timeout_secs,
+        max_llm_timeout_secs: config.max_llm_timeout_secs,
+        max_verify_goldens: config.max_verify_goldens,
+        events,
+        snapshots,
+        snapshot_kinds: config.snapshot_kinds,
+        store_content: config.store_content,
+        scrapes: shutdown.scrapes().clone(),
+        budget,
+        dedup_window: config.dedup_window,
+        dedup_reuse: config.dedup_reuse,
+        circuit_breakers: CircuitBreakerRegistry::default(),
+        rate_limiter: limiter,
+    });
+
+    let limits = routes::BodyLimits {
+        scrape: config.scrape_body_limit,
+        schema: config.schema_body_limit,
+    };
+    // Routes with a limit of their own override this default one.
+    let mut app = routes::router(state.clone(), limits)
+        .layer(axum::middleware::from_fn_with_state(
+            state,
+            rate_limit::rate_limit,
+        ))
+        .layer(axum::middleware::from_fn_with_state(
+            shutdown,
+            shutdown::reject_while_draining,
+        ))
+        .layer(DefaultBodyLimit::max(config.body_limit))
+        .layer(axum::middleware::from_fn(metrics::track_http))
+        .layer(middleware::request_logger());
+
+    // `/metrics` is merged after the layers above, so scrapes are neither
+    // rate-limited, logged, nor counted as API traffic.
+    if let Some(metrics_router) = metrics_router {
+        app = app.merge(metrics_router);
+    }
+
+    // gzip or brotli, as negotiated via `Accept-Encoding`.
+    let compression = CompressionLayer::new()
+        .gzip(true)
+        .br(true)
+        .compress_when(DefaultPredicate::new().and(SizeAbove::new(COMPRESSION_MIN_SIZE)));
+
+    // The request ID is assigned outside the trace layer so the request span
+    // can record it, and echoed back on the response.
+    let request_id = HeaderName::from_static(telemetry::REQUEST_ID_HEADER);
+
+    Ok(app
+        .layer(compression)
+        .layer(
+            TraceLayer::new_for_http()
+                .make_span_with(telemetry::make_span)
+                .on_response(telemetry::on_response),
+        )
+        .layer(PropagateRequestIdLayer::new(request_id.clone()))
+        .layer(SetRequestIdLayer::new(request_id, MakeRequestUuid))
+        .layer(cors))
+}
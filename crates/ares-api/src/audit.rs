@@ -0,0 +1,73 @@
+//! Recording administrative actions in the audit log.
+//!
+//! Handlers that change schemas, jobs, API keys, or circuit breakers take an
+//! [`Audit`] extractor and call [`Audit::record`] once the action succeeded.
+//! Recording is best-effort: a failed write is logged and never fails the
+//! request.
+
+use std::convert::Infallible;
+use std::sync::Arc;
+
+use axum::extract::FromRequestParts;
+use axum::http::request::Parts;
+
+use ares_core::audit::{AuditAction, NewAuditEntry, redact_summary, token_fingerprint};
+
+use crate::auth::AuthContext;
+use crate::state::AppState;
+use crate::telemetry::REQUEST_ID_HEADER;
+
+/// Who made the current request, ready to record what they did.
+pub struct Audit {
+    state: Arc<AppState>,
+    actor: Option<AuthContext>,
+    request_id: Option<String>,
+}
+
+impl FromRequestParts<Arc<AppState>> for Audit {
+    type Rejection = Infallible;
+
+    async fn from_request_parts(
+        parts: &mut Parts,
+        state: &Arc<AppState>,
+    ) -> Result<Self, Self::Rejection> {
+        Ok(Self {
+            state: state.clone(),
+            actor: parts.extensions.get::<AuthContext>().cloned(),
+            request_id: parts
+                .headers
+                .get(REQUEST_ID_HEADER)
+                .and_then(|v| v.to_str().ok())
+                .map(str::to_string),
+        })
+    }
+}
+
+impl Audit {
+    /// Record `action` on `resource_id`. `summary` is redacted before it is
+    /// stored.
+    pub async fn record(
+        &self,
+        action: AuditAction,
+        resource_id: Option<String>,
+        summary: serde_json::Value,
+    ) {
+        let api_key_id = self.actor.as_ref().and_then(|ctx| ctx.key_id);
+        let token_fingerprint = match api_key_id {
+            Some(_) => None,
+            None => self.state.admin_token.as_deref().map(token_fingerprint),
+        };
+        let entry = NewAuditEntry {
+            api_key_id,
+            token_fingerprint,
+            action,
+            resource_id,
+            request_id: self.request_id.clone(),
+            summary: redact_summary(summary),
+        };
+
+        if let Err(e) = self.state.db.audit_repo().insert(&entry).await {
+            tracing::warn!(%action, resource_id = ?entry.resource_id, "Failed to record audit entry: {e}");
+        }
+    }
+}
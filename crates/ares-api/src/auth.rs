@@ -1,28 +1,150 @@
 //! Authentication middleware for protecting admin endpoints.
+//!
+//! Callers authenticate with `Authorization: Bearer <token>`, where the token
+//! is either the bootstrap `ARES_ADMIN_TOKEN` (full access) or a scoped key
+//! from the `api_keys` table. Clients that can't set a bearer token may send
+//! the same token as `X-Api-Key: <token>` or, least preferred, as the
+//! `api_key` query parameter. Individual routes then demand a scope via
+//! [`RequireScope`].
 
+use std::fmt;
 use std::sync::Arc;
 
-use axum::extract::State;
+use axum::extract::{Query, State};
 use axum::http::{self, Request, StatusCode};
-use axum::middleware::Next;
+use axum::middleware::{self, Next};
 use axum::response::{IntoResponse, Response};
+use axum::routing::MethodRouter;
+use serde::Deserialize;
 use subtle::ConstantTimeEq;
+use uuid::Uuid;
+
+use ares_core::api_key::{ApiKeyScope, hash_api_key};
 
 use crate::dto::ErrorResponse;
+use crate::error::ApiError;
 use crate::state::AppState;
 
-/// Middleware that validates `Authorization: Bearer <token>` against the configured admin token.
+/// Identity of an authenticated caller, stored in request extensions by
+/// [`require_api_key`].
+#[derive(Debug, Clone)]
+pub struct AuthContext {
+    /// Database key ID, or `None` for the bootstrap `ARES_ADMIN_TOKEN`.
+    pub key_id: Option<Uuid>,
+    pub scopes: Vec<ApiKeyScope>,
+}
+
+impl AuthContext {
+    /// Whether the caller holds `scope` (`admin` grants everything).
+    pub fn has_scope(&self, scope: ApiKeyScope) -> bool {
+        self.scopes
+            .iter()
+            .any(|s| *s == scope || *s == ApiKeyScope::Admin)
+    }
+}
+
+/// Header carrying the API key for clients that can't send a bearer token.
+pub const API_KEY_HEADER: &str = "x-api-key";
+
+/// Where a request's API key was found.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CredentialSource {
+    /// `Authorization: Bearer <token>`.
+    Bearer,
+    /// `X-Api-Key: <token>`.
+    ApiKeyHeader,
+    /// The `api_key` query parameter.
+    QueryParam,
+}
+
+impl fmt::Display for CredentialSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Bearer => "bearer",
+            Self::ApiKeyHeader => "x-api-key",
+            Self::QueryParam => "query",
+        })
+    }
+}
+
+#[derive(Deserialize)]
+struct ApiKeyQuery {
+    api_key: Option<String>,
+}
+
+/// The token presented with `request` and where it came from, trying
+/// `Authorization: Bearer`, then `X-Api-Key`, then the `api_key` query
+/// parameter. Empty values are skipped.
+pub fn request_credential<B>(request: &Request<B>) -> Option<(CredentialSource, String)> {
+    let headers = request.headers();
+    let bearer = headers
+        .get(http::header::AUTHORIZATION)
+        .and_then(|v| v.to_str().ok())
+        .and_then(|header| header.split_once(' '))
+        .filter(|(scheme, token)| scheme.eq_ignore_ascii_case("bearer") && !token.is_empty())
+        .map(|(_, token)| (CredentialSource::Bearer, token.to_string()));
+    let header = || {
+        headers
+            .get(API_KEY_HEADER)
+            .and_then(|v| v.to_str().ok())
+            .map(str::trim)
+            .filter(|token| !token.is_empty())
+            .map(|token| (CredentialSource::ApiKeyHeader, token.to_string()))
+    };
+    let query = || {
+        Query::<ApiKeyQuery>::try_from_uri(request.uri())
+            .ok()
+            .and_then(|Query(query)| query.api_key)
+            .filter(|token| !token.is_empty())
+            .map(|token| (CredentialSource::QueryParam, token))
+    };
+
+    bearer.or_else(header).or_else(query)
+}
+
+/// Middleware that validates the request's API key (see
+/// [`request_credential`]) against the configured admin token and the
+/// stored API keys.
 ///
-/// - If no admin token is configured, returns 403 Forbidden (admin endpoints disabled).
-/// - If the token is missing or invalid, returns 401 Unauthorized.
+/// - If the token matches `ARES_ADMIN_TOKEN` or an active API key, the request
+///   proceeds with an [`AuthContext`] extension.
+/// - If no admin token is configured and the token matches no key, returns
+///   403 Forbidden (admin endpoints disabled).
+/// - Otherwise, returns 401 Unauthorized.
 pub async fn require_api_key(
     State(state): State<Arc<AppState>>,
-    request: Request<axum::body::Body>,
+    mut request: Request<axum::body::Body>,
     next: Next,
 ) -> Response {
-    let expected_token = match &state.admin_token {
-        Some(token) => token,
-        None => {
+    let credential = request_credential(&request);
+    if let Some((CredentialSource::QueryParam, _)) = &credential {
+        tracing::warn!(
+            "API key sent in the `api_key` query parameter; URLs end up in proxy logs and \
+             browser history, so prefer the Authorization or X-Api-Key header"
+        );
+    }
+
+    // The rate limiter has usually authenticated the caller already.
+    let known = request.extensions().get::<AuthContext>().cloned();
+    let context = match (known, &credential) {
+        (Some(context), _) => Some(context),
+        (None, Some((source, token))) => match authenticate(&state, token).await {
+            Ok(Some(context)) => {
+                tracing::debug!(
+                    auth_method = %source,
+                    key_id = ?context.key_id,
+                    "Authenticated request"
+                );
+                Some(context)
+            }
+            Ok(None) => None,
+            Err(e) => return e.into_response(),
+        },
+        (None, None) => None,
+    };
+
+    let Some(context) = context else {
+        if state.admin_token.is_none() {
             let body = ErrorResponse {
                 error: "forbidden".to_string(),
                 message: "Admin endpoints are disabled (no ARES_ADMIN_TOKEN configured)"
@@ -30,33 +152,91 @@ pub async fn require_api_key(
             };
             return (StatusCode::FORBIDDEN, axum::Json(body)).into_response();
         }
+        let body = ErrorResponse {
+            error: "unauthorized".to_string(),
+            message: "Missing or invalid API key. Expected: Authorization: Bearer <api_key> \
+                      or X-Api-Key: <api_key>"
+                .to_string(),
+        };
+        return (StatusCode::UNAUTHORIZED, axum::Json(body)).into_response();
     };
 
-    let auth_header = request
-        .headers()
-        .get(http::header::AUTHORIZATION)
-        .and_then(|v| v.to_str().ok());
-
-    let authenticated = match auth_header {
-        Some(header) => {
-            if let Some((scheme, token)) = header.split_once(' ') {
-                scheme.eq_ignore_ascii_case("bearer")
-                    && bool::from(token.as_bytes().ct_eq(expected_token.as_bytes()))
-            } else {
-                false
-            }
-        }
-        None => false,
+    request.extensions_mut().insert(context);
+    next.run(request).await
+}
+
+/// Resolve a token to the caller's identity, if it is valid.
+pub(crate) async fn authenticate(
+    state: &AppState,
+    token: &str,
+) -> Result<Option<AuthContext>, ApiError> {
+    if let Some(expected) = &state.admin_token
+        && bool::from(token.as_bytes().ct_eq(expected.as_bytes()))
+    {
+        return Ok(Some(AuthContext {
+            key_id: None,
+            scopes: vec![ApiKeyScope::Admin],
+        }));
+    }
+
+    let hash = hash_api_key(token);
+    let repo = state.db.api_key_repo();
+    let Some(key) = repo.find_active_by_hash(&hash).await? else {
+        return Ok(None);
     };
+    if !bool::from(key.key_hash.as_bytes().ct_eq(hash.as_bytes())) {
+        return Ok(None);
+    }
+
+    // Usage tracking is best-effort and must not delay the request.
+    let key_id = key.id;
+    tokio::spawn(async move {
+        if let Err(e) = repo.touch_last_used(key_id).await {
+            tracing::warn!(%key_id, "Failed to record API key usage: {e}");
+        }
+    });
+
+    Ok(Some(AuthContext {
+        key_id: Some(key.id),
+        scopes: key.scopes,
+    }))
+}
 
-    if !authenticated {
+/// Middleware that rejects callers lacking the given scope with 403.
+///
+/// Must run inside [`require_api_key`]; apply it per route with
+/// [`RequireScope::require_scope`].
+pub async fn require_scope(
+    State(scope): State<ApiKeyScope>,
+    request: Request<axum::body::Body>,
+    next: Next,
+) -> Response {
+    let allowed = request
+        .extensions()
+        .get::<AuthContext>()
+        .is_some_and(|ctx| ctx.has_scope(scope));
+
+    if !allowed {
         let body = ErrorResponse {
-            error: "unauthorized".to_string(),
-            message: "Missing or invalid Authorization header. Expected: Bearer <api_key>"
-                .to_string(),
+            error: "insufficient_scope".to_string(),
+            message: format!("This endpoint requires the '{scope}' scope"),
         };
-        return (StatusCode::UNAUTHORIZED, axum::Json(body)).into_response();
+        return (StatusCode::FORBIDDEN, axum::Json(body)).into_response();
     }
 
     next.run(request).await
 }
+
+/// Extension trait for attaching a scope requirement to a route.
+pub trait RequireScope {
+    fn require_scope(self, scope: ApiKeyScope) -> Self;
+}
+
+impl<S> RequireScope for MethodRouter<S>
+where
+    S: Clone + Send + Sync + 'static,
+{
+    fn require_scope(self, scope: ApiKeyScope) -> Self {
+        self.route_layer(middleware::from_fn_with_state(scope, require_scope))
+    }
+}
@@ -0,0 +1,1262 @@
+//! Server configuration — every `ARES_*` setting the server reads, parsed and
+//! validated in one place.
+//!
+//! [`ServerConfig::from_env`] reports every invalid variable at once rather
+//! than stopping at the first, so a misconfigured deployment can be fixed in
+//! one pass. Tests and embedders start from [`ServerConfig::new`] and adjust
+//! it with the `with_*` builders.
+
+use std::fmt;
+use std::net::SocketAddr;
+use std::path::PathBuf;
+use std::str::FromStr;
+use std::time::Duration;
+
+use axum::http::{HeaderName, HeaderValue, Method, header};
+use tower_http::cors::{AllowHeaders, AllowMethods, AllowOrigin, CorsLayer};
+
+use ares_client::{EventPublisher, Snapshots};
+use ares_core::budget::BudgetLimits;
+use ares_core::error::AppError;
+use ares_core::proxy::{ProxyConfig, ProxyEntry, RotationStrategy, TlsBackend};
+use ares_core::schema::SchemaResolver;
+use ares_core::snapshot::{SnapshotKind, parse_snapshot_kinds};
+use ares_db::DatabaseConfig;
+
+use crate::rate_limit::{RateLimitConfig, RateLimitTier, RateLimiter};
+use crate::telemetry;
+
+/// Default port when `ARES_SERVER_PORT` is unset.
+pub const DEFAULT_PORT: u16 = 3000;
+
+/// Default maximum request body size (2 MB), for routes without a limit of
+/// their own.
+pub const DEFAULT_BODY_LIMIT: usize = 2 * 1024 * 1024;
+
+/// Default maximum body size for `POST /v1/scrape` (2 MB).
+pub const DEFAULT_SCRAPE_BODY_LIMIT: usize = 2 * 1024 * 1024;
+
+/// Default maximum body size for schema uploads (1 MB).
+pub const DEFAULT_SCHEMA_BODY_LIMIT: usize = 1024 * 1024;
+
+/// Default upper bound on a per-request `fetch_timeout_secs`.
+pub const DEFAULT_MAX_FETCH_TIMEOUT_SECS: u64 = 120;
+
+/// Default upper bound on a per-request `llm_timeout_secs`.
+pub const DEFAULT_MAX_LLM_TIMEOUT_SECS: u64 = 600;
+
+/// Default upper bound on the golden pages one `POST /v1/verify` re-extracts.
+pub const DEFAULT_MAX_VERIFY_GOLDENS: usize = 50;
+
+/// Default time in-flight requests get to finish after a shutdown signal.
+pub const DEFAULT_DRAIN_TIMEOUT_SECS: u64 = 60;
+
+const DEFAULT_RATE_LIMIT_BURST: u32 = 30;
+const DEFAULT_RATE_LIMIT_PERIOD_SECS: u64 = 1;
+
+/// Methods allowed in cross-origin requests from listed origins unless
+/// `ARES_CORS_METHODS` is set: every method the API routes.
+pub const DEFAULT_CORS_METHODS: [Method; 5] = [
+    Method::GET,
+    Method::POST,
+    Method::PUT,
+    Method::PATCH,
+    Method::DELETE,
+];
+
+/// Request headers allowed in cross-origin requests from listed origins
+/// unless `ARES_CORS_HEADERS` is set: what the API's own clients send.
+pub static DEFAULT_CORS_HEADERS: [HeaderName; 4] = [
+    header::AUTHORIZATION,
+    header::CONTENT_TYPE,
+    header::IF_NONE_MATCH,
+    HeaderName::from_static(crate::auth::API_KEY_HEADER),
+];
+
+/// Response headers cross-origin scripts may read: the ETag for conditional
+/// requests, the rate limiter's `Retry-After`, and the request ID.
+pub static CORS_EXPOSE_HEADERS: [HeaderName; 3] = [
+    header::ETAG,
+    header::RETRY_AFTER,
+    HeaderName::from_static(crate::telemetry::REQUEST_ID_HEADER),
+];
+
+/// Which origins may make cross-origin requests, from `ARES_CORS_ORIGIN`.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub enum CorsOrigins {
+    /// No CORS headers; browsers block cross-origin requests.
+    #[default]
+    Disabled,
+    /// Any origin (`*`).
+    Any,
+    /// An explicit list of allowed origins.
+    List(Vec<HeaderValue>),
+}
+
+impl CorsOrigins {
+    /// Parse `*` or a comma-separated origin list; an empty list disables
+    /// CORS. Every origin must be written as browsers send it in `Origin`
+    /// (e.g. `https://app.example:8443`), since anything else never
+    /// matches; all invalid origins are reported together.
+    pub fn parse(spec: &str) -> Result<Self, String> {
+        let spec = spec.trim();
+        if spec == "*" {
+            return Ok(Self::Any);
+        }
+        let mut origins = Vec::new();
+        let mut invalid = Vec::new();
+        for origin in spec.split(',').map(str::trim).filter(|o| !o.is_empty()) {
+            match parse_origin(origin) {
+                Ok(origin) => origins.push(origin),
+                Err(e) => invalid.push(e),
+            }
+        }
+        if !invalid.is_empty() {
+            return Err(invalid.join("; "));
+        }
+        Ok(if origins.is_empty() {
+            Self::Disabled
+        } else {
+            Self::List(origins)
+        })
+    }
+}
+
+/// Check that `origin` is an `http(s)://host[:port]` origin in the form a
+/// browser sends: lowercase host, no path, no default port.
+fn parse_origin(origin: &str) -> Result<HeaderValue, String> {
+    let url = url::Url::parse(origin).map_err(|e| format!("'{origin}' is not a URL ({e})"))?;
+    if !matches!(url.scheme(), "http" | "https") {
+        return Err(format!("'{origin}' must use http or https"));
+    }
+    let expected = url.origin().ascii_serialization();
+    if origin != expected {
+        return Err(format!(
+            "'{origin}' is not an origin (did you mean '{expected}'?)"
+        ));
+    }
+    origin
+        .parse()
+        .map_err(|_| format!("'{origin}' is not a valid header value"))
+}
+
+/// `disabled`, `*`, or the comma-separated origin list.
+impl fmt::Display for CorsOrigins {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Disabled => f.write_str("disabled"),
+            Self::Any => f.write_str("*"),
+            Self::List(origins) => {
+                let origins: Vec<_> = origins
+                    .iter()
+                    .map(|o| o.to_str().unwrap_or("<invalid>"))
+                    .collect();
+                f.write_str(&origins.join(","))
+            }
+        }
+    }
+}
+
+/// Parse `*` (`None`: anything) or a non-empty comma-separated list.
+fn parse_cors_list<T>(
+    spec: &str,
+    what: &str,
+    parse: impl Fn(&str) -> Option<T>,
+) -> Result<Option<Vec<T>>, String> {
+    if spec.trim() == "*" {
+        return Ok(None);
+    }
+    let mut items = Vec::new();
+    let mut invalid = Vec::new();
+    for item in spec.split(',').map(str::trim).filter(|i| !i.is_empty()) {
+        match parse(item) {
+            Some(value) => items.push(value),
+            None => invalid.push(format!("'{item}' is not a valid {what}")),
+        }
+    }
+    if !invalid.is_empty() {
+        return Err(invalid.join("; "));
+    }
+    if items.is_empty() {
+        return Err(format!("expected `*` or at least one {what}"));
+    }
+    Ok(Some(items))
+}
+
+/// Cross-origin policy (`ARES_CORS_*`).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CorsConfig {
+    /// Allowed origins (`ARES_CORS_ORIGIN`).
+    pub origins: CorsOrigins,
+    /// Allowed methods (`ARES_CORS_METHODS`); `None` allows any.
+    pub methods: Option<Vec<Method>>,
+    /// Allowed request headers (`ARES_CORS_HEADERS`); `None` allows any.
+    pub headers: Option<Vec<HeaderName>>,
+    /// Let browsers send cookies and `Authorization` cross-origin
+    /// (`ARES_CORS_ALLOW_CREDENTIALS`).
+    pub allow_credentials: bool,
+    /// How long browsers may cache a preflight response
+    /// (`ARES_CORS_MAX_AGE`, in seconds); `None` leaves it to the browser.
+    pub max_age: Option<Duration>,
+}
+
+impl Default for CorsConfig {
+    fn default() -> Self {
+        Self::new(CorsOrigins::Disabled)
+    }
+}
+
+impl CorsConfig {
+    /// The default methods and headers for `origins`, without credentials:
+    /// any for `*`, which can't be combined with credentials anyway, and
+    /// [`DEFAULT_CORS_METHODS`] and [`DEFAULT_CORS_HEADERS`] otherwise.
+    pub fn new(origins: CorsOrigins) -> Self {
+        let any = origins == CorsOrigins::Any;
+        Self {
+            origins,
+            methods: (!any).then(|| DEFAULT_CORS_METHODS.to_vec()),
+            headers: (!any).then(|| DEFAULT_CORS_HEADERS.to_vec()),
+            allow_credentials: false,
+            max_age: None,
+        }
+    }
+
+    /// Parse an `ARES_CORS_METHODS` value: `*` or a comma-separated list,
+    /// case-insensitive.
+    pub fn parse_methods(spec: &str) -> Result<Option<Vec<Method>>, String> {
+        parse_cors_list(spec, "HTTP method", |m| {
+            Method::from_bytes(m.to_ascii_uppercase().as_bytes()).ok()
+        })
+    }
+
+    /// Parse an `ARES_CORS_HEADERS` value: `*` or a comma-separated list of
+    /// header names.
+    pub fn parse_headers(spec: &str) -> Result<Option<Vec<HeaderName>>, String> {
+        parse_cors_list(spec, "header name", |h| {
+            HeaderName::from_bytes(h.as_bytes()).ok()
+        })
+    }
+
+    pub fn with_methods(mut self, methods: Option<Vec<Method>>) -> Self {
+        self.methods = methods;
+        self
+    }
+
+    pub fn with_headers(mut self, headers: Option<Vec<HeaderName>>) -> Self {
+        self.headers = headers;
+        self
+    }
+
+    pub fn with_credentials(mut self, allow: bool) -> Self {
+        self.allow_credentials = allow;
+        self
+    }
+
+    pub fn with_max_age(mut self, max_age: Duration) -> Self {
+        self.max_age = Some(max_age);
+        self
+    }
+
+    /// Browsers ignore credentialed responses that answer with `*`, so
+    /// credentials need an explicit origin list, method list and header
+    /// list.
+    pub fn validate(&self) -> Result<(), AppError> {
+        if !self.allow_credentials {
+            return Ok(());
+        }
+        let wildcards: Vec<_> = [
+            ("ARES_CORS_ORIGIN", self.origins == CorsOrigins::Any),
+            ("ARES_CORS_METHODS", self.methods.is_none()),
+            ("ARES_CORS_HEADERS", self.headers.is_none()),
+        ]
+        .into_iter()
+        .filter_map(|(key, wildcard)| wildcard.then_some(key))
+        .collect();
+        if wildcards.is_empty() {
+            Ok(())
+        } else {
+            Err(AppError::ConfigError(format!(
+                "ARES_CORS_ALLOW_CREDENTIALS cannot be combined with `*` in {}",
+                wildcards.join(", ")
+            )))
+        }
+    }
+
+    /// The `tower_http` layer enforcing this policy. Fails where
+    /// [`validate`](Self::validate) does.
+    pub fn layer(&self) -> Result<CorsLayer, AppError> {
+        self.validate()?;
+        let layer = match &self.origins {
+            CorsOrigins::Disabled => return Ok(CorsLayer::new()),
+            CorsOrigins::Any => CorsLayer::new().allow_origin(AllowOrigin::any()),
+            CorsOrigins::List(origins) => {
+                CorsLayer::new().allow_origin(AllowOrigin::list(origins.iter().cloned()))
+            }
+        };
+        let layer = layer
+            .expose_headers(CORS_EXPOSE_HEADERS.clone())
+            .allow_methods(match &self.methods {
+                None => AllowMethods::any(),
+                Some(methods) => AllowMethods::list(methods.iter().cloned()),
+            })
+            .allow_headers(match &self.headers {
+                None => AllowHeaders::any(),
+                Some(headers) => AllowHeaders::list(headers.iter().cloned()),
+            })
+            .allow_credentials(self.allow_credentials);
+        Ok(match self.max_age {
+            Some(max_age) => layer.max_age(max_age),
+            None => layer,
+        })
+    }
+}
+
+/// Outcome of [`ServerConfig::check_schemas_dir`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SchemasDirStatus {
+    /// The directory exists and was listed.
+    Ready { schemas: usize },
+    /// The directory was missing and has been created.
+    Created,
+    /// The directory is missing; schema endpoints fail until it exists.
+    Missing,
+}
+
+/// Everything needed to build and run the server.
+#[derive(Debug)]
+pub struct ServerConfig {
+    /// Address the API listens on (`0.0.0.0:$ARES_SERVER_PORT`).
+    pub bind_addr: SocketAddr,
+    /// Bootstrap admin token (`ARES_ADMIN_TOKEN`); `None` disables it.
+    pub admin_token: Option<String>,
+    /// Schemas directory (`ARES_SCHEMAS_DIR`).
+    pub schemas_dir: PathBuf,
+    /// Create the schemas directory at startup when it is missing
+    /// (`ARES_SCHEMAS_CREATE_ON_STARTUP`).
+    pub create_schemas_dir: bool,
+    /// Inbound rate limits (`ARES_RATE_LIMIT_*`).
+    pub rate_limit: RateLimitConfig,
+    /// Cross-origin policy (`ARES_CORS_*`).
+    pub cors: CorsConfig,
+    /// Maximum request body size in bytes for routes without a limit of
+    /// their own (`ARES_BODY_SIZE_LIMIT`).
+    pub body_limit: usize,
+    /// Maximum body size in bytes for scrapes and schema validation runs,
+    /// which carry page HTML (`ARES_SCRAPE_BODY_SIZE_LIMIT`).
+    pub scrape_body_limit: usize,
+    /// Maximum body size in bytes for schema uploads
+    /// (`ARES_SCHEMA_BODY_SIZE_LIMIT`).
+    pub schema_body_limit: usize,
+    /// Database connection settings (`DATABASE_*`).
+    pub database: DatabaseConfig,
+    /// Apply pending migrations at startup (`ARES_AUTO_MIGRATE`); when
+    /// `false`, startup fails instead while any are pending.
+    pub auto_migrate: bool,
+    /// Separate address for `/metrics` (`ARES_METRICS_ADDR`); `None` serves
+    /// it on the API port.
+    pub metrics_addr: Option<SocketAddr>,
+    /// Outbound proxy rotation (`ARES_PROXY`, `ARES_PROXY_FILE`,
+    /// `ARES_PROXY_ROTATION`).
+    pub proxy_config: Option<ProxyConfig>,
+    /// Rotate User-Agent headers (`ARES_RANDOM_UA`).
+    pub random_ua: bool,
+    /// Fetch with a headless browser (`ARES_BROWSER`).
+    pub browser: bool,
+    /// Browser stealth mode (`ARES_STEALTH`).
+    pub stealth: bool,
+    /// TLS backend for outbound fetches (`ARES_TLS_BACKEND`).
+    pub tls_backend: TlsBackend,
+    /// API key for the LLM provider (`ARES_API_KEY`); scrapes with a cloud
+    /// provider fail without one.
+    pub llm_api_key: Option<String>,
+    /// Probe the LLM endpoint in readiness checks (`ARES_HEALTH_LLM_PROBE`).
+    pub llm_probe: bool,
+    /// Largest `fetch_timeout_secs` a request may ask for
+    /// (`ARES_MAX_FETCH_TIMEOUT_SECS`).
+    pub max_fetch_timeout_secs: u64,
+    /// Largest `llm_timeout_secs` a request may ask for
+    /// (`ARES_MAX_LLM_TIMEOUT_SECS`).
+    pub max_llm_timeout_secs: u64,
+    /// Most golden pages one `POST /v1/verify` may re-extract
+    /// (`ARES_VERIFY_MAX_GOLDENS`); schemas with more are rejected.
+    pub max_verify_goldens: usize,
+    /// How long in-flight requests may run after a shutdown signal
+    /// (`ARES_DRAIN_TIMEOUT`, in seconds).
+    pub drain_timeout: Duration,
+    /// NATS server extraction events are published to (`ARES_NATS_URL`,
+    /// needs the `events-nats` feature); `None` publishes nothing.
+    pub nats_url: Option<String>,
+    /// Where raw HTML and Markdown snapshots are archived
+    /// (`ARES_SNAPSHOT_URL`: `file:///path`, or `s3://bucket/prefix` with the
+    /// `s3` feature); `None` archives nothing.
+    pub snapshot_url: Option<String>,
+    /// Which snapshots are archived (`ARES_SNAPSHOT_KINDS`, default
+    /// `html,markdown`).
+    pub snapshot_kinds: Vec<SnapshotKind>,
+    /// Keep the cleaned content of saved extractions, served at
+    /// `/v1/extractions/{id}/raw-content` (`ARES_STORE_CONTENT`).
+    pub store_content: bool,
+    /// Daily caps on LLM calls made by scrapes (`ARES_BUDGET_MAX_CALLS`,
+    /// `ARES_BUDGET_MAX_TOKENS`, `ARES_BUDGET_MAX_COST_USD`).
+    pub budget: BudgetLimits,
+    /// Mark scrapes whose content another URL had within this window as
+    /// duplicates of it (`ARES_DEDUP_WINDOW_HOURS`); `None` disables the
+    /// check.
+    pub dedup_window: Option<Duration>,
+    /// Skip the LLM call for duplicates and copy the original's data
+    /// (`ARES_DEDUP_REUSE`, requires a dedup window).
+    pub dedup_reuse: bool,
+}
+
+impl ServerConfig {
+    /// Defaults for everything except the database.
+    pub fn new(database: DatabaseConfig) -> Self {
+        let tier = RateLimitTier::new(
+            DEFAULT_RATE_LIMIT_BURST,
+            Duration::from_secs(DEFAULT_RATE_LIMIT_PERIOD_SECS),
+        );
+        Self {
+            bind_addr: SocketAddr::from(([0, 0, 0, 0], DEFAULT_PORT)),
+            admin_token: None,
+            schemas_dir: PathBuf::from("schemas"),
+            create_schemas_dir: false,
+            rate_limit: RateLimitConfig::new(tier, tier),
+            cors: CorsConfig::default(),
+            body_limit: DEFAULT_BODY_LIMIT,
+            scrape_body_limit: DEFAULT_SCRAPE_BODY_LIMIT,
+            schema_body_limit: DEFAULT_SCHEMA_BODY_LIMIT,
+            database,
+            auto_migrate: true,
+            metrics_addr: None,
+            proxy_config: None,
+            random_ua: false,
+            browser: false,
+            stealth: false,
+            tls_backend: TlsBackend::default(),
+            llm_api_key: None,
+            llm_probe: false,
+            max_fetch_timeout_secs: DEFAULT_MAX_FETCH_TIMEOUT_SECS,
+            max_llm_timeout_secs: DEFAULT_MAX_LLM_TIMEOUT_SECS,
+            max_verify_goldens: DEFAULT_MAX_VERIFY_GOLDENS,
+            drain_timeout: Duration::from_secs(DEFAULT_DRAIN_TIMEOUT_SECS),
+            nats_url: None,
+            snapshot_url: None,
+            snapshot_kinds: SnapshotKind::ALL.to_vec(),
+            store_content: false,
+            budget: BudgetLimits::default(),
+            dedup_window: None,
+            dedup_reuse: false,
+        }
+    }
+
+    /// Read and validate the configuration from environment variables.
+    pub fn from_env() -> Result<Self, AppError> {
+        Self::from_lookup(|key| std::env::var(key).ok())
+    }
+
+    /// Like [`ServerConfig::from_env`], reading variables through `get`.
+    ///
+    /// All invalid variables are reported together in one `ConfigError`.
+    pub fn from_lookup(get: impl Fn(&str) -> Option<String>) -> Result<Self, AppError> {
+        let mut env = EnvReader {
+            get: &get,
+            errors: Vec::new(),
+        };
+
+        let database = DatabaseConfig::from_lookup(&get).map_err(|e| env.report(e));
+
+        let port: u16 = env.parse("ARES_SERVER_PORT", DEFAULT_PORT);
+        let metrics_addr = env.parse_opt::<SocketAddr>("ARES_METRICS_ADDR");
+
+        let burst_size = env.parse("ARES_RATE_LIMIT_BURST", DEFAULT_RATE_LIMIT_BURST);
+        let per_second = env.parse("ARES_RATE_LIMIT_RPS", DEFAULT_RATE_LIMIT_PERIOD_SECS);
+        let anon_burst_size = env.parse("ARES_RATE_LIMIT_ANON_BURST", burst_size);
+        let anon_per_second = env.parse("ARES_RATE_LIMIT_ANON_RPS", per_second);
+        let mut rate_limit = RateLimitConfig::new(
+            RateLimitTier::new(burst_size, Duration::from_secs(per_second)),
+            RateLimitTier::new(anon_burst_size, Duration::from_secs(anon_per_second)),
+        );
+        if let Some(spec) = env.string("ARES_RATE_LIMIT_KEY_OVERRIDES") {
+            match RateLimitConfig::parse_key_overrides(&spec) {
+                Ok(overrides) => rate_limit.key_overrides = overrides,
+                Err(e) => env.report(e),
+            }
+        }
+        if let Err(e) = RateLimiter::new(&rate_limit) {
+            env.report(e);
+        }
+
+        let tls_backend = env.parse_with("ARES_TLS_BACKEND", TlsBackend::default());
+        let rotation = env.parse_with("ARES_PROXY_ROTATION", RotationStrategy::default());
+        let proxy_config = env.proxy_config(rotation);
+
+        let admin_token = env.string("ARES_ADMIN_TOKEN");
+        let schemas_dir = env
+            .string("ARES_SCHEMAS_DIR")
+            .map_or_else(|| PathBuf::from("schemas"), PathBuf::from);
+        let cors = env.cors();
+        let body_limit = env.parse("ARES_BODY_SIZE_LIMIT", DEFAULT_BODY_LIMIT);
+        let scrape_body_limit = env.parse("ARES_SCRAPE_BODY_SIZE_LIMIT", DEFAULT_SCRAPE_BODY_LIMIT);
+        let schema_body_limit = env.parse("ARES_SCHEMA_BODY_SIZE_LIMIT", DEFAULT_SCHEMA_BODY_LIMIT);
+        let auto_migrate = env.parse("ARES_AUTO_MIGRATE", true);
+        let max_fetch_timeout_secs = env.parse(
+            "ARES_MAX_FETCH_TIMEOUT_SECS",
+            DEFAULT_MAX_FETCH_TIMEOUT_SECS,
+        );
+        let max_llm_timeout_secs =
+            env.parse("ARES_MAX_LLM_TIMEOUT_SECS", DEFAULT_MAX_LLM_TIMEOUT_SECS);
+        let max_verify_goldens = env.parse("ARES_VERIFY_MAX_GOLDENS", DEFAULT_MAX_VERIFY_GOLDENS);
+        let drain_timeout_secs = env.parse("ARES_DRAIN_TIMEOUT", DEFAULT_DRAIN_TIMEOUT_SECS);
+        let nats_url = env.string("ARES_NATS_URL");
+        if let Err(e) = EventPublisher::new(nats_url.as_deref()) {
+            env.report(e);
+        }
+        let snapshot_url = env.string("ARES_SNAPSHOT_URL");
+        if let Err(e) = Snapshots::new(snapshot_url.as_deref()) {
+            env.report(e);
+        }
+        let snapshot_kinds = match env.string("ARES_SNAPSHOT_KINDS") {
+            None => SnapshotKind::ALL.to_vec(),
+            Some(spec) => parse_snapshot_kinds(&spec).unwrap_or_else(|e| {
+                env.errors.push(format!("Invalid ARES_SNAPSHOT_KINDS: {e}"));
+                Vec::new()
+            }),
+        };
+
+        let budget = BudgetLimits {
+            max_calls: env.parse_opt("ARES_BUDGET_MAX_CALLS"),
+            max_tokens: env.parse_opt("ARES_BUDGET_MAX_TOKENS"),
+            max_cost_usd: env.parse_opt("ARES_BUDGET_MAX_COST_USD"),
+        };
+
+        let dedup_window = match env.parse_opt::<u64>("ARES_DEDUP_WINDOW_HOURS") {
+            Some(0) => {
+                env.errors
+                    .push("ARES_DEDUP_WINDOW_HOURS must be positive; unset it to disable".into());
+                None
+            }
+            hours => hours.map(|hours| Duration::from_secs(hours.saturating_mul(3600))),
+        };
+        let dedup_reuse = env.flag("ARES_DEDUP_REUSE");
+        if dedup_reuse && dedup_window.is_none() {
+            env.errors
+                .push("ARES_DEDUP_REUSE requires ARES_DEDUP_WINDOW_HOURS".into());
+        }
+
+        match database {
+            Ok(database) if env.errors.is_empty() => Ok(Self {
+                bind_addr: SocketAddr::from(([0, 0, 0, 0], port)),
+                admin_token,
+                schemas_dir,
+                create_schemas_dir: env.flag("ARES_SCHEMAS_CREATE_ON_STARTUP"),
+                rate_limit,
+                cors,
+                body_limit,
+                scrape_body_limit,
+                schema_body_limit,
+                database,
+                auto_migrate,
+                metrics_addr,
+                proxy_config,
+                random_ua: env.flag("ARES_RANDOM_UA"),
+                browser: env.flag("ARES_BROWSER"),
+                stealth: env.flag("ARES_STEALTH"),
+                tls_backend,
+                llm_api_key: env.string("ARES_API_KEY"),
+                llm_probe: env.flag("ARES_HEALTH_LLM_PROBE"),
+                max_fetch_timeout_secs,
+                max_llm_timeout_secs,
+                max_verify_goldens,
+                drain_timeout: Duration::from_secs(drain_timeout_secs),
+                nats_url,
+                snapshot_url,
+                snapshot_kinds,
+                store_content: env.flag("ARES_STORE_CONTENT"),
+                budget,
+                dedup_window,
+                dedup_reuse,
+            }),
+            _ => Err(env.into_error()),
+        }
+    }
+
+    /// Startup check of the schemas directory.
+    ///
+    /// A missing directory is created when `create_schemas_dir` is set and
+    /// otherwise only reported, since the server still works without schema
+    /// endpoints. A directory that exists but can't be listed is an error.
+    pub fn check_schemas_dir(&self) -> Result<SchemasDirStatus, AppError> {
+        let dir = &self.schemas_dir;
+        if !dir.exists() {
+            if !self.create_schemas_dir {
+                return Ok(SchemasDirStatus::Missing);
+            }
+            std::fs::create_dir_all(dir).map_err(|e| {
+                AppError::ConfigError(format!(
+                    "Failed to create ARES_SCHEMAS_DIR '{}': {e}",
+                    dir.display()
+                ))
+            })?;
+            return Ok(SchemasDirStatus::Created);
+        }
+
+        std::fs::read_dir(dir).map_err(|e| {
+            AppError::ConfigError(format!(
+                "ARES_SCHEMAS_DIR '{}' is not readable: {e}",
+                dir.display()
+            ))
+        })?;
+        let schemas = SchemaResolver::new(dir).list_schemas()?;
+        Ok(SchemasDirStatus::Ready {
+            schemas: schemas.len(),
+        })
+    }
+
+    pub fn with_bind_addr(mut self, addr: SocketAddr) -> Self {
+        self.bind_addr = addr;
+        self
+    }
+
+    pub fn with_admin_token(mut self, token: impl Into<String>) -> Self {
+        self.admin_token = Some(token.into());
+        self
+    }
+
+    pub fn with_schemas_dir(mut self, dir: impl Into<PathBuf>) -> Self {
+        self.schemas_dir = dir.into();
+        self
+    }
+
+    pub fn with_create_schemas_dir(mut self, create: bool) -> Self {
+        self.create_schemas_dir = create;
+        self
+    }
+
+    pub fn with_rate_limit(mut self, rate_limit: RateLimitConfig) -> Self {
+        self.rate_limit = rate_limit;
+        self
+    }
+
+    pub fn with_cors(mut self, cors: CorsConfig) -> Self {
+        self.cors = cors;
+        self
+    }
+
+    pub fn with_body_limit(mut self, bytes: usize) -> Self {
+        self.body_limit = bytes;
+        self
+    }
+
+    pub fn with_scrape_body_limit(mut self, bytes: usize) -> Self {
+        self.scrape_body_limit = bytes;
+        self
+    }
+
+    pub fn with_schema_body_limit(mut self, bytes: usize) -> Self {
+        self.schema_body_limit = bytes;
+        self
+    }
+
+    pub fn with_metrics_addr(mut self, addr: SocketAddr) -> Self {
+        self.metrics_addr = Some(addr);
+        self
+    }
+
+    pub fn with_llm_api_key(mut self, key: impl Into<String>) -> Self {
+        self.llm_api_key = Some(key.into());
+        self
+    }
+
+    pub fn with_llm_probe(mut self, enabled: bool) -> Self {
+        self.llm_probe = enabled;
+        self
+    }
+
+    pub fn with_max_timeouts(mut self, fetch_secs: u64, llm_secs: u64) -> Self {
+        self.max_fetch_timeout_secs = fetch_secs;
+        self.max_llm_timeout_secs = llm_secs;
+        self
+    }
+
+    pub fn with_drain_timeout(mut self, timeout: Duration) -> Self {
+        self.drain_timeout = timeout;
+        self
+    }
+
+    pub fn with_nats_url(mut self, url: impl Into<String>) -> Self {
+        self.nats_url = Some(url.into());
+        self
+    }
+
+    pub fn with_snapshots(mut self, url: impl Into<String>, kinds: &[SnapshotKind]) -> Self {
+        self.snapshot_url = Some(url.into());
+        self.snapshot_kinds = kinds.to_vec();
+        self
+    }
+
+    pub fn with_store_content(mut self, store: bool) -> Self {
+        self.store_content = store;
+        self
+    }
+
+    pub fn with_max_verify_goldens(mut self, max: usize) -> Self {
+        self.max_verify_goldens = max;
+        self
+    }
+
+    pub fn with_budget(mut self, budget: BudgetLimits) -> Self {
+        self.budget = budget;
+        self
+    }
+
+    pub fn with_dedup(mut self, window: Duration, reuse: bool) -> Self {
+        self.dedup_window = Some(window);
+        self.dedup_reuse = reuse;
+        self
+    }
+}
+
+/// Log the effective configuration as one INFO event with a field per
+/// setting, so log aggregators can index them. The database password is
+/// masked and the admin token is reported only as enabled or not.
+pub fn log_startup_config(config: &ServerConfig) {
+    let rate_limit = &config.rate_limit;
+    tracing::info!(
+        port = config.bind_addr.port(),
+        schemas_dir = %config.schemas_dir.display(),
+        rate_limit_rps = rate_limit.authenticated.replenish_period.as_secs(),
+        rate_limit_burst = rate_limit.authenticated.burst_size,
+        anon_rate_limit_rps = rate_limit.anonymous.replenish_period.as_secs(),
+        anon_rate_limit_burst = rate_limit.anonymous.burst_size,
+        rate_limit_key_overrides = rate_limit.key_overrides.len(),
+        body_limit = config.body_limit,
+        scrape_body_limit = config.scrape_body_limit,
+        schema_body_limit = config.schema_body_limit,
+        cors_origin = %config.cors.origins,
+        cors_allow_credentials = config.cors.allow_credentials,
+        database_url = %config.database.redacted_url(),
+        database_max_connections = config.database.max_connections,
+        auto_migrate = config.auto_migrate,
+        drain_timeout_secs = config.drain_timeout.as_secs(),
+        max_verify_goldens = config.max_verify_goldens,
+        nats_events_enabled = config.nats_url.is_some(),
+        snapshots_enabled = config.snapshot_url.is_some(),
+        store_content = config.store_content,
+        budget_max_calls = ?config.budget.max_calls,
+        budget_max_tokens = ?config.budget.max_tokens,
+        budget_max_cost_usd = ?config.budget.max_cost_usd,
+        dedup_window_hours = ?config.dedup_window.map(|window| window.as_secs() / 3600),
+        dedup_reuse = config.dedup_reuse,
+        admin_auth_enabled = config.admin_token.is_some(),
+        otel_endpoint = telemetry::otel_endpoint(),
+        "Server configuration"
+    );
+    if let CorsOrigins::List(origins) = &config.cors.origins {
+        for origin in origins {
+            tracing::info!(
+                origin = origin.to_str().unwrap_or("<invalid>"),
+                "CORS origin allowed"
+            );
+        }
+    }
+}
+
+/// Reads variables through a lookup function, collecting parse errors
+/// instead of failing on the first.
+struct EnvReader<'a, F> {
+    get: &'a F,
+    errors: Vec<String>,
+}
+
+impl<F: Fn(&str) -> Option<String>> EnvReader<'_, F> {
+    fn string(&self, key: &str) -> Option<String> {
+        (self.get)(key)
+    }
+
+    /// `true` for `true` or `1`; anything else (or unset) is `false`.
+    fn flag(&self, key: &str) -> bool {
+        self.string(key).is_some_and(|v| v == "true" || v == "1")
+    }
+
+    fn parse<T: FromStr>(&mut self, key: &str, default: T) -> T {
+        self.parse_opt(key).unwrap_or(default)
+    }
+
+    fn parse_opt<T: FromStr>(&mut self, key: &str) -> Option<T> {
+        let raw = self.string(key)?;
+        match raw.trim().parse() {
+            Ok(value) => Some(value),
+            Err(_) => {
+                self.errors.push(format!("Invalid {key} '{raw}'"));
+                None
+            }
+        }
+    }
+
+    /// Parse a type whose `FromStr` error is a descriptive `String`.
+    fn parse_with<T: FromStr<Err = String>>(&mut self, key: &str, default: T) -> T {
+        match self.string(key) {
+            None => default,
+            Some(raw) => raw.parse().unwrap_or_else(|e| {
+                self.errors.push(format!("Invalid {key}: {e}"));
+                default
+            }),
+        }
+    }
+
+    /// The cross-origin policy from `ARES_CORS_*`, reporting invalid
+    /// values and combinations.
+    fn cors(&mut self) -> CorsConfig {
+        let origins = self.parse_cors("ARES_CORS_ORIGIN", CorsOrigins::parse);
+        let mut cors = CorsConfig::new(origins.unwrap_or_default());
+        if let Some(methods) = self.parse_cors("ARES_CORS_METHODS", CorsConfig::parse_methods) {
+            cors.methods = methods;
+        }
+        if let Some(headers) = self.parse_cors("ARES_CORS_HEADERS", CorsConfig::parse_headers) {
+            cors.headers = headers;
+        }
+        cors.allow_credentials = self.flag("ARES_CORS_ALLOW_CREDENTIALS");
+        cors.max_age = self.parse_opt("ARES_CORS_MAX_AGE").map(Duration::from_secs);
+        if let Err(e) = cors.validate() {
+            self.report(e);
+        }
+        cors
+    }
+
+    /// `key` parsed by `parse`; `None` when unset or invalid.
+    fn parse_cors<T>(&mut self, key: &str, parse: impl Fn(&str) -> Result<T, String>) -> Option<T> {
+        let spec = self.string(key)?;
+        parse(&spec)
+            .map_err(|e| self.errors.push(format!("Invalid {key}: {e}")))
+            .ok()
+    }
+
+    /// Proxy entries from `ARES_PROXY` and each line of `ARES_PROXY_FILE`.
+    fn proxy_config(&mut self, rotation: RotationStrategy) -> Option<ProxyConfig> {
+        let mut entries = Vec::new();
+
+        if let Some(url) = self.string("ARES_PROXY") {
+            entries.push(ProxyEntry::new(url));
+        }
+
+        if let Some(path) = self.string("ARES_PROXY_FILE") {
+            match std::fs::read_to_string(&path) {
+                Ok(content) => entries.extend(
+                    content
+                        .lines()
+                        .map(str::trim)
+                        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+                        .map(ProxyEntry::new),
+                ),
+                Err(e) => self
+                    .errors
+                    .push(format!("Failed to read ARES_PROXY_FILE '{path}': {e}")),
+            }
+        }
+
+        (!entries.is_empty()).then(|| ProxyConfig::new(entries, rotation))
+    }
+
+    fn report(&mut self, error: AppError) {
+        match error {
+            AppError::ConfigError(msg) => self.errors.push(msg),
+            other => self.errors.push(other.to_string()),
+        }
+    }
+
+    fn into_error(self) -> AppError {
+        AppError::ConfigError(format!(
+            "Invalid server configuration:\n  - {}",
+            self.errors.join("\n  - ")
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use super::*;
+
+    fn from_vars(vars: &[(&str, &str)]) -> Result<ServerConfig, AppError> {
+        let vars: HashMap<String, String> = vars
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect();
+        ServerConfig::from_lookup(|key| vars.get(key).cloned())
+    }
+
+    #[test]
+    fn defaults_with_only_database_url() {
+        let config = from_vars(&[("DATABASE_URL", "postgres://localhost/ares")]).unwrap();
+
+        assert_eq!(config.bind_addr, SocketAddr::from(([0, 0, 0, 0], 3000)));
+        assert_eq!(config.admin_token, None);
+        assert_eq!(config.llm_api_key, None);
+        assert_eq!(config.schemas_dir, PathBuf::from("schemas"));
+        assert_eq!(config.body_limit, DEFAULT_BODY_LIMIT);
+        assert_eq!(config.scrape_body_limit, DEFAULT_SCRAPE_BODY_LIMIT);
+        assert_eq!(config.schema_body_limit, DEFAULT_SCHEMA_BODY_LIMIT);
+        assert_eq!(config.cors, CorsConfig::default());
+        assert_eq!(config.metrics_addr, None);
+        assert_eq!(config.nats_url, None);
+        assert_eq!(config.snapshot_url, None);
+        assert_eq!(config.snapshot_kinds, SnapshotKind::ALL);
+        assert!(config.budget.is_unlimited());
+        assert_eq!(config.dedup_window, None);
+        assert_eq!(
+            config.rate_limit.authenticated,
+            RateLimitTier::new(30, Duration::from_secs(1))
+        );
+        assert_eq!(config.rate_limit.anonymous, config.rate_limit.authenticated);
+        assert!(config.proxy_config.is_none());
+        assert!(!config.browser && !config.llm_probe);
+        assert!(config.auto_migrate);
+        assert_eq!(
+            config.drain_timeout,
+            Duration::from_secs(DEFAULT_DRAIN_TIMEOUT_SECS)
+        );
+        assert_eq!(config.max_verify_goldens, DEFAULT_MAX_VERIFY_GOLDENS);
+        assert_eq!(config.database.url, "postgres://localhost/ares");
+    }
+
+    #[test]
+    fn parses_overrides() {
+        let config = from_vars(&[
+            ("DATABASE_URL", "postgres://localhost/ares"),
+            ("ARES_SERVER_PORT", "8080"),
+            ("ARES_ADMIN_TOKEN", "secret"),
+            ("ARES_API_KEY", "sk-test"),
+            ("ARES_SCHEMAS_DIR", "/srv/schemas"),
+            ("ARES_SCHEMAS_CREATE_ON_STARTUP", "true"),
+            ("ARES_RATE_LIMIT_BURST", "100"),
+            ("ARES_RATE_LIMIT_ANON_RPS", "5"),
+            ("ARES_BODY_SIZE_LIMIT", "1024"),
+            ("ARES_SCRAPE_BODY_SIZE_LIMIT", "2048"),
+            ("ARES_SCHEMA_BODY_SIZE_LIMIT", "4096"),
+            ("ARES_CORS_ORIGIN", "https://a.example, https://b.example"),
+            ("ARES_METRICS_ADDR", "127.0.0.1:9090"),
+            ("ARES_HEALTH_LLM_PROBE", "true"),
+            ("ARES_PROXY", "http://proxy:8080"),
+            ("ARES_MAX_FETCH_TIMEOUT_SECS", "30"),
+            ("ARES_AUTO_MIGRATE", "false"),
+            ("ARES_DRAIN_TIMEOUT", "5"),
+            ("ARES_VERIFY_MAX_GOLDENS", "10"),
+            ("ARES_STORE_CONTENT", "true"),
+            ("ARES_BUDGET_MAX_CALLS", "5000"),
+            ("ARES_BUDGET_MAX_COST_USD", "25.5"),
+        ])
+        .unwrap();
+
+        assert_eq!(config.bind_addr.port(), 8080);
+        assert_eq!(config.admin_token.as_deref(), Some("secret"));
+        assert_eq!(config.llm_api_key.as_deref(), Some("sk-test"));
+        assert_eq!(config.schemas_dir, PathBuf::from("/srv/schemas"));
+        assert!(config.create_schemas_dir);
+        assert_eq!(
+            config.rate_limit.anonymous,
+            RateLimitTier::new(100, Duration::from_secs(5))
+        );
+        assert_eq!(config.body_limit, 1024);
+        assert_eq!(config.scrape_body_limit, 2048);
+        assert_eq!(config.schema_body_limit, 4096);
+        assert_eq!(
+            config.cors.origins,
+            CorsOrigins::List(vec![
+                HeaderValue::from_static("https://a.example"),
+                HeaderValue::from_static("https://b.example"),
+            ])
+        );
+        assert_eq!(config.metrics_addr, Some("127.0.0.1:9090".parse().unwrap()));
+        assert!(config.llm_probe);
+        assert!(config.proxy_config.is_some());
+        assert_eq!(config.max_fetch_timeout_secs, 30);
+        assert_eq!(config.max_llm_timeout_secs, DEFAULT_MAX_LLM_TIMEOUT_SECS);
+        assert!(!config.auto_migrate);
+        assert_eq!(config.drain_timeout, Duration::from_secs(5));
+        assert_eq!(config.max_verify_goldens, 10);
+        assert!(config.store_content);
+        assert_eq!(
+            config.budget,
+            BudgetLimits::default()
+                .with_max_calls(5000)
+                .with_max_cost_usd(25.5)
+        );
+    }
+
+    #[test]
+    fn nats_url_needs_the_events_nats_feature() {
+        let result = from_vars(&[
+            ("DATABASE_URL", "postgres://localhost/ares"),
+            ("ARES_NATS_URL", "nats://localhost:4222"),
+        ]);
+        if cfg!(feature = "events-nats") {
+            assert_eq!(
+                result.unwrap().nats_url.as_deref(),
+                Some("nats://localhost:4222")
+            );
+        } else {
+            let err = result.unwrap_err().to_string();
+            assert!(err.contains("events-nats"), "{err}");
+        }
+    }
+
+    #[test]
+    fn parses_snapshot_settings() {
+        let config = from_vars(&[
+            ("DATABASE_URL", "postgres://localhost/ares"),
+            ("ARES_SNAPSHOT_URL", "file:///var/lib/ares/snapshots"),
+            ("ARES_SNAPSHOT_KINDS", "markdown"),
+        ])
+        .unwrap();
+        assert_eq!(
+            config.snapshot_url.as_deref(),
+            Some("file:///var/lib/ares/snapshots")
+        );
+        assert_eq!(config.snapshot_kinds, [SnapshotKind::Markdown]);
+
+        let err = from_vars(&[
+            ("DATABASE_URL", "postgres://localhost/ares"),
+            ("ARES_SNAPSHOT_URL", "ftp://archive/ares"),
+            ("ARES_SNAPSHOT_KINDS", "html,pdf"),
+        ])
+        .unwrap_err()
+        .to_string();
+        assert!(err.contains("ftp://archive/ares"), "{err}");
+        assert!(err.contains("ARES_SNAPSHOT_KINDS"), "{err}");
+    }
+
+    #[test]
+    fn parses_dedup_settings() {
+        let config = from_vars(&[
+            ("DATABASE_URL", "postgres://localhost/ares"),
+            ("ARES_DEDUP_WINDOW_HOURS", "24"),
+            ("ARES_DEDUP_REUSE", "true"),
+        ])
+        .unwrap();
+        assert_eq!(config.dedup_window, Some(Duration::from_secs(24 * 3600)));
+        assert!(config.dedup_reuse);
+
+        let err = from_vars(&[
+            ("DATABASE_URL", "postgres://localhost/ares"),
+            ("ARES_DEDUP_WINDOW_HOURS", "0"),
+        ])
+        .unwrap_err()
+        .to_string();
+        assert!(err.contains("ARES_DEDUP_WINDOW_HOURS"), "{err}");
+
+        let err = from_vars(&[
+            ("DATABASE_URL", "postgres://localhost/ares"),
+            ("ARES_DEDUP_REUSE", "true"),
+        ])
+        .unwrap_err()
+        .to_string();
+        assert!(err.contains("ARES_DEDUP_REUSE"), "{err}");
+    }
+
+    #[test]
+    fn reports_all_errors_at_once() {
+        let err = from_vars(&[
+            ("ARES_SERVER_PORT", "eighty"),
+            ("ARES_RATE_LIMIT_BURST", "0"),
+            ("ARES_BODY_SIZE_LIMIT", "-1"),
+            ("ARES_METRICS_ADDR", "localhost"),
+            ("ARES_TLS_BACKEND", "boringssl"),
+            ("ARES_RATE_LIMIT_KEY_OVERRIDES", "nope"),
+        ])
+        .unwrap_err()
+        .to_string();
+
+        for needle in [
+            "DATABASE_URL",
+            "ARES_SERVER_PORT",
+            "burst size",
+            "ARES_BODY_SIZE_LIMIT",
+            "ARES_METRICS_ADDR",
+            "ARES_TLS_BACKEND",
+            "rate limit override",
+        ] {
+            assert!(err.contains(needle), "missing {needle:?} in: {err}");
+        }
+    }
+
+    #[test]
+    fn cors_parse() {
+        assert_eq!(CorsOrigins::parse("*"), Ok(CorsOrigins::Any));
+        assert_eq!(
+            CorsOrigins::parse("https://a.example, http://localhost:3000,"),
+            Ok(CorsOrigins::List(vec![
+                HeaderValue::from_static("https://a.example"),
+                HeaderValue::from_static("http://localhost:3000"),
+            ]))
+        );
+        assert_eq!(CorsOrigins::parse(" "), Ok(CorsOrigins::Disabled));
+    }
+
+    #[test]
+    fn cors_parse_rejects_invalid_origins() {
+        let err =
+            CorsOrigins::parse("htps://a.example, https://ok.example, a.example").unwrap_err();
+        assert!(
+            err.contains("'htps://a.example' must use http or https"),
+            "{err}"
+        );
+        assert!(err.contains("'a.example' is not a URL"), "{err}");
+        assert!(!err.contains("ok.example"), "{err}");
+
+        for (origin, expected) in [
+            ("https://a.example/", "https://a.example"),
+            ("https://A.example", "https://a.example"),
+            ("https://a.example:443", "https://a.example"),
+        ] {
+            let err = CorsOrigins::parse(origin).unwrap_err();
+            assert!(err.contains(&format!("did you mean '{expected}'")), "{err}");
+        }
+
+        let err = from_vars(&[
+            ("DATABASE_URL", "postgres://localhost/ares"),
+            ("ARES_CORS_ORIGIN", "htps://a.example"),
+        ])
+        .unwrap_err()
+        .to_string();
+        assert!(err.contains("Invalid ARES_CORS_ORIGIN"), "{err}");
+    }
+
+    #[test]
+    fn parses_cors_methods_headers_and_credentials() {
+        let config = from_vars(&[
+            ("DATABASE_URL", "postgres://localhost/ares"),
+            ("ARES_CORS_ORIGIN", "https://app.example"),
+            ("ARES_CORS_METHODS", "get, put"),
+            ("ARES_CORS_HEADERS", "Authorization, X-Trace"),
+            ("ARES_CORS_ALLOW_CREDENTIALS", "true"),
+            ("ARES_CORS_MAX_AGE", "600"),
+        ])
+        .unwrap();
+        assert_eq!(config.cors.methods, Some(vec![Method::GET, Method::PUT]));
+        assert_eq!(
+            config.cors.headers,
+            Some(vec![
+                header::AUTHORIZATION,
+                HeaderName::from_static("x-trace")
+            ])
+        );
+        assert!(config.cors.allow_credentials);
+        assert_eq!(config.cors.max_age, Some(Duration::from_secs(600)));
+
+        let defaults = CorsConfig::default();
+        assert_eq!(defaults.methods.unwrap(), DEFAULT_CORS_METHODS);
+        assert_eq!(defaults.headers.unwrap(), DEFAULT_CORS_HEADERS);
+        let any = CorsConfig::new(CorsOrigins::Any);
+        assert_eq!((any.methods, any.headers), (None, None));
+        assert_eq!(CorsConfig::parse_methods("*"), Ok(None));
+        assert!(CorsConfig::parse_methods(" , ").is_err());
+        assert!(CorsConfig::parse_headers("x trace").is_err());
+    }
+
+    #[test]
+    fn cors_credentials_reject_wildcards() {
+        let err = from_vars(&[
+            ("DATABASE_URL", "postgres://localhost/ares"),
+            ("ARES_CORS_ORIGIN", "*"),
+            ("ARES_CORS_HEADERS", "*"),
+            ("ARES_CORS_ALLOW_CREDENTIALS", "true"),
+        ])
+        .unwrap_err()
+        .to_string();
+        assert!(
+            err.contains("ARES_CORS_ORIGIN, ARES_CORS_METHODS, ARES_CORS_HEADERS"),
+            "{err}"
+        );
+
+        let cors = CorsConfig::new(CorsOrigins::Any).with_credentials(true);
+        assert!(cors.layer().is_err());
+        assert!(cors.with_credentials(false).layer().is_ok());
+    }
+
+    #[test]
+    fn schemas_dir_check() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let dir = tmp.path().join("schemas");
+        let config = ServerConfig::new(DatabaseConfig::new("postgres://localhost/ares"))
+            .with_schemas_dir(&dir);
+
+        assert_eq!(
+            config.check_schemas_dir().unwrap(),
+            SchemasDirStatus::Missing
+        );
+        assert!(!dir.exists());
+
+        let config = config.with_create_schemas_dir(true);
+        assert_eq!(
+            config.check_schemas_dir().unwrap(),
+            SchemasDirStatus::Created
+        );
+        assert_eq!(
+            config.check_schemas_dir().unwrap(),
+            SchemasDirStatus::Ready { schemas: 0 }
+        );
+
+        // Exists but can't be listed as a directory.
+        let file = tmp.path().join("not-a-dir");
+        std::fs::write(&file, "").unwrap();
+        let err = config
+            .with_schemas_dir(&file)
+            .check_schemas_dir()
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("not readable"), "{err}");
+    }
+
+    #[test]
+    fn builder_overrides_defaults() {
+        let config = ServerConfig::new(DatabaseConfig::new("postgres://localhost/ares"))
+            .with_admin_token("t")
+            .with_schemas_dir("/tmp/schemas")
+            .with_body_limit(10);
+
+        assert_eq!(config.admin_token.as_deref(), Some("t"));
+        assert_eq!(config.schemas_dir, PathBuf::from("/tmp/schemas"));
+        assert_eq!(config.body_limit, 10);
+        assert_eq!(config.bind_addr.port(), DEFAULT_PORT);
+    }
+
+    #[test]
+    fn startup_config_is_logged_without_secrets() {
+        use ares_core::testutil::CapturedLogs;
+
+        let config = from_vars(&[
+            ("DATABASE_URL", "postgres://ares:hunter22@db:5432/ares"),
+            ("DATABASE_MAX_CONNECTIONS", "12"),
+            ("ARES_ADMIN_TOKEN", "admin-secret"),
+            ("ARES_CORS_ORIGIN", "https://a.example, https://b.example"),
+            ("ARES_RATE_LIMIT_BURST", "50"),
+        ])
+        .unwrap();
+        let captured = CapturedLogs::default();
+        let subscriber = tracing_subscriber::fmt()
+            .json()
+            .with_writer(captured.clone())
+            .finish();
+        tracing::subscriber::with_default(subscriber, || log_startup_config(&config));
+
+        let output = captured.output();
+        assert!(!output.contains("hunter22"), "{output}");
+        assert!(!output.contains("admin-secret"), "{output}");
+        let line: serde_json::Value = serde_json::from_str(&output).unwrap();
+        let fields = &line["fields"];
+        assert_eq!(line["level"], "INFO");
+        assert_eq!(fields["port"], 3000);
+        assert_eq!(fields["schemas_dir"], "schemas");
+        assert_eq!(fields["rate_limit_rps"], 1);
+        assert_eq!(fields["rate_limit_burst"], 50);
+        assert_eq!(fields["body_limit"], DEFAULT_BODY_LIMIT);
+        assert_eq!(fields["cors_origin"], "https://a.example,https://b.example");
+        assert_eq!(
+            fields["database_url"],
+            "postgres://ares:[REDACTED]@db:5432/ares"
+        );
+        assert_eq!(fields["database_max_connections"], 12);
+        assert_eq!(fields["admin_auth_enabled"], true);
+    }
+}
@@ -1,14 +1,72 @@
-use chrono::{DateTime, Utc};
+use std::collections::BTreeMap;
+
+use chrono::{DateTime, NaiveDate, Utc};
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
-use ares_core::job::ScrapeJob;
-use ares_core::models::Extraction;
+use ares_core::api_key::ApiKey;
+use ares_core::audit::AuditEntry;
+use ares_core::budget::{BudgetLimits, DailyUsage};
+use ares_core::circuit_breaker::CircuitBreakerRecord;
+use ares_core::diff::JsonChange;
+use ares_core::event_log::WorkerEventRecord;
+use ares_core::fetch::{FetchAuth, FetchOptions};
+use ares_core::job::{JobStatus, ScrapeJob};
+use ares_core::models::{Extraction, HistoryEntry, format_age};
+use ares_core::schema::SchemaEntry;
+use ares_core::snapshot::SnapshotKind;
+use ares_core::verify::{GoldenCheck, VerifyReport};
+use ares_db::DomainStats;
 
 // ---------------------------------------------------------------------------
 // Jobs
 // ---------------------------------------------------------------------------
 
+/// A job's place in the queue. Query parameters accept any case.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, utoipa::ToSchema)]
+#[serde(rename_all = "lowercase", try_from = "String")]
+pub enum JobStatusDto {
+    Pending,
+    Running,
+    Completed,
+    Failed,
+    Cancelled,
+}
+
+/// Parsed by [`JobStatus`]'s `FromStr`, so invalid values are reported with
+/// the valid ones, as the CLI does.
+impl TryFrom<String> for JobStatusDto {
+    type Error = String;
+
+    fn try_from(s: String) -> Result<Self, Self::Error> {
+        s.parse::<JobStatus>().map(Self::from)
+    }
+}
+
+impl From<JobStatus> for JobStatusDto {
+    fn from(status: JobStatus) -> Self {
+        match status {
+            JobStatus::Pending => Self::Pending,
+            JobStatus::Running => Self::Running,
+            JobStatus::Completed => Self::Completed,
+            JobStatus::Failed => Self::Failed,
+            JobStatus::Cancelled => Self::Cancelled,
+        }
+    }
+}
+
+impl From<JobStatusDto> for JobStatus {
+    fn from(status: JobStatusDto) -> Self {
+        match status {
+            JobStatusDto::Pending => Self::Pending,
+            JobStatusDto::Running => Self::Running,
+            JobStatusDto::Completed => Self::Completed,
+            JobStatusDto::Failed => Self::Failed,
+            JobStatusDto::Cancelled => Self::Cancelled,
+        }
+    }
+}
+
 #[derive(Debug, Deserialize, utoipa::ToSchema)]
 pub struct CreateJobRequest {
     pub url: String,
@@ -17,12 +75,89 @@ pub struct CreateJobRequest {
     pub model: String,
     pub base_url: String,
     pub max_retries: Option<u32>,
+    /// http(s) webhook told when this job's extracted data changes, in place
+    /// of the worker's; never returned by the API
+    pub notify_url: Option<String>,
+    /// Labels for listing or deleting the job with others, e.g. a run ID
+    pub tags: Option<Vec<String>>,
+    /// Stored on the job and honoured by the worker that runs it
+    #[serde(flatten)]
+    pub fetch: FetchOptionsRequest,
 }
 
 #[derive(Debug, Serialize, utoipa::ToSchema)]
 pub struct CreateJobResponse {
     pub job_id: Uuid,
-    pub status: String,
+    pub status: JobStatusDto,
+}
+
+#[derive(Debug, Deserialize, utoipa::ToSchema)]
+pub struct CreateJobsFromSitemapRequest {
+    /// sitemap.xml or sitemap index URL; gzipped sitemaps are supported
+    pub sitemap_url: String,
+    pub schema_name: String,
+    pub schema: serde_json::Value,
+    pub model: String,
+    pub base_url: String,
+    pub max_retries: Option<u32>,
+    /// Only queue URLs matching at least one of these regexes
+    #[serde(default)]
+    pub include: Vec<String>,
+    /// Never queue URLs matching any of these regexes
+    #[serde(default)]
+    pub exclude: Vec<String>,
+    /// Queue at most this many URLs (default and maximum: 10000)
+    pub max_urls: Option<usize>,
+    /// Skip URLs whose sitemap `lastmod` is not newer than their latest
+    /// extraction with `schema_name` (default: false)
+    #[serde(default)]
+    pub skip_unmodified: bool,
+    /// http(s) webhook told when a job's extracted data changes, in place of
+    /// the worker's; never returned by the API
+    pub notify_url: Option<String>,
+    /// Labels set on every created job
+    pub tags: Option<Vec<String>>,
+    /// Stored on every job and honoured by the worker that runs it
+    #[serde(flatten)]
+    pub fetch: FetchOptionsRequest,
+}
+
+#[derive(Debug, Serialize, utoipa::ToSchema)]
+pub struct CreateJobsFromSitemapResponse {
+    /// IDs of the created jobs, in sitemap order
+    pub job_ids: Vec<Uuid>,
+    /// URLs skipped by `skip_unmodified`
+    pub skipped: usize,
+}
+
+#[derive(Debug, Deserialize, utoipa::ToSchema)]
+pub struct UpdateJobSchemaRequest {
+    /// Replacement JSON Schema the job will extract with
+    pub schema: serde_json::Value,
+}
+
+#[derive(Debug, Deserialize, utoipa::ToSchema)]
+pub struct BulkCancelRequest {
+    /// Jobs to cancel. Completed, cancelled, and unknown jobs are skipped.
+    pub job_ids: Vec<Uuid>,
+}
+
+#[derive(Debug, Serialize, utoipa::ToSchema)]
+pub struct BulkCancelResponse {
+    /// Number of jobs actually cancelled
+    pub cancelled: u64,
+}
+
+#[derive(Debug, Deserialize, utoipa::IntoParams)]
+pub struct DeleteJobsQuery {
+    /// Delete the finished jobs with this tag
+    pub tag: String,
+}
+
+#[derive(Debug, Serialize, utoipa::ToSchema)]
+pub struct DeleteJobsResponse {
+    /// Number of jobs deleted; pending and running jobs are kept
+    pub deleted: u64,
 }
 
 #[derive(Debug, Serialize, utoipa::ToSchema)]
@@ -33,7 +168,7 @@ pub struct JobResponse {
     pub schema: serde_json::Value,
     pub model: String,
     pub base_url: String,
-    pub status: String,
+    pub status: JobStatusDto,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
     pub started_at: Option<DateTime<Utc>>,
@@ -48,6 +183,10 @@ pub struct JobResponse {
     pub parent_job_id: Option<Uuid>,
     pub depth: u32,
     pub max_depth: u32,
+    pub max_pages: u32,
+    pub allowed_domains: Vec<String>,
+    pub tags: Vec<String>,
+    pub fetch_options: FetchOptionsResponse,
 }
 
 impl From<ScrapeJob> for JobResponse {
@@ -59,7 +198,7 @@ impl From<ScrapeJob> for JobResponse {
             schema: job.schema,
             model: job.model,
             base_url: job.base_url,
-            status: job.status.to_string(),
+            status: job.status.into(),
             created_at: job.created_at,
             updated_at: job.updated_at,
             started_at: job.started_at,
@@ -74,13 +213,114 @@ impl From<ScrapeJob> for JobResponse {
             parent_job_id: job.parent_job_id,
             depth: job.depth,
             max_depth: job.max_depth,
+            max_pages: job.max_pages,
+            allowed_domains: job.allowed_domains,
+            tags: job.tags,
+            fetch_options: job.fetch_options.into(),
+        }
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Fetch options
+// ---------------------------------------------------------------------------
+
+/// Per-request fetch options, accepted inline on scrape and job requests.
+#[derive(Debug, Default, Deserialize, utoipa::ToSchema)]
+pub struct FetchOptionsRequest {
+    /// Render the page in a headless browser (default: false)
+    pub use_browser: Option<bool>,
+    /// Fetch timeout in seconds, at most `ARES_MAX_FETCH_TIMEOUT_SECS`
+    pub fetch_timeout_secs: Option<u64>,
+    /// LLM call timeout in seconds, at most `ARES_MAX_LLM_TIMEOUT_SECS`
+    pub llm_timeout_secs: Option<u64>,
+    /// CSS selector; only matching elements are sent to the LLM
+    pub content_selector: Option<String>,
+    /// CSS selector the browser waits for before capture (requires `use_browser`)
+    pub wait_selector: Option<String>,
+    /// Extra request headers sent with the page fetch
+    pub headers: Option<BTreeMap<String, String>>,
+    /// Credentials sent with the page fetch; never returned by the API
+    pub auth: Option<FetchAuthRequest>,
+}
+
+/// Credentials for fetching a protected page.
+#[derive(Clone, Deserialize, utoipa::ToSchema)]
+#[serde(tag = "type", rename_all = "lowercase")]
+pub enum FetchAuthRequest {
+    /// HTTP Basic authentication
+    Basic { user: String, pass: String },
+    /// `Authorization: Bearer <token>`
+    Bearer { token: String },
+    /// A custom header, e.g. `X-Api-Key`
+    Header { name: String, value: String },
+}
+
+impl From<FetchAuthRequest> for FetchAuth {
+    fn from(req: FetchAuthRequest) -> Self {
+        match req {
+            FetchAuthRequest::Basic { user, pass } => Self::Basic { user, pass },
+            FetchAuthRequest::Bearer { token } => Self::Bearer { token },
+            FetchAuthRequest::Header { name, value } => Self::Header { name, value },
+        }
+    }
+}
+
+/// Redacted like [`FetchAuth`], so request bodies can be logged.
+impl std::fmt::Debug for FetchAuthRequest {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        std::fmt::Debug::fmt(&FetchAuth::from(self.clone()), f)
+    }
+}
+
+impl From<FetchOptionsRequest> for FetchOptions {
+    fn from(req: FetchOptionsRequest) -> Self {
+        Self {
+            use_browser: req.use_browser.unwrap_or(false),
+            fetch_timeout_secs: req.fetch_timeout_secs,
+            llm_timeout_secs: req.llm_timeout_secs,
+            content_selector: req.content_selector,
+            wait_selector: req.wait_selector,
+            headers: req.headers.unwrap_or_default(),
+            auth: req.auth.map(FetchAuth::from),
+        }
+    }
+}
+
+#[derive(Debug, Serialize, utoipa::ToSchema)]
+pub struct FetchOptionsResponse {
+    pub use_browser: bool,
+    pub fetch_timeout_secs: Option<u64>,
+    pub llm_timeout_secs: Option<u64>,
+    pub content_selector: Option<String>,
+    pub wait_selector: Option<String>,
+    pub headers: BTreeMap<String, String>,
+    /// Kind of credentials sent with the fetch (`basic`, `bearer`, or
+    /// `header`); the credentials themselves are never returned
+    pub auth_type: Option<String>,
+}
+
+impl From<FetchOptions> for FetchOptionsResponse {
+    fn from(options: FetchOptions) -> Self {
+        Self {
+            use_browser: options.use_browser,
+            fetch_timeout_secs: options.fetch_timeout_secs,
+            llm_timeout_secs: options.llm_timeout_secs,
+            content_selector: options.content_selector,
+            wait_selector: options.wait_selector,
+            headers: options.headers,
+            auth_type: options.auth.map(|auth| auth.kind().to_string()),
         }
     }
 }
 
 #[derive(Debug, Deserialize, utoipa::IntoParams)]
 pub struct ListJobsQuery {
-    pub status: Option<String>,
+    pub status: Option<JobStatusDto>,
+    /// Only jobs claimed by this worker
+    pub worker_id: Option<String>,
+    /// Only jobs with this tag
+    pub tag: Option<String>,
     pub limit: Option<usize>,
     pub offset: Option<usize>,
 }
@@ -93,6 +333,47 @@ pub struct JobListResponse {
     pub offset: usize,
 }
 
+#[derive(Debug, Serialize, utoipa::ToSchema)]
+pub struct PendingCountResponse {
+    pub count: i64,
+}
+
+#[derive(Debug, Deserialize, utoipa::IntoParams)]
+pub struct JobEventsQuery {
+    /// Most recent events to return (default 50, max 500)
+    pub limit: Option<usize>,
+}
+
+#[derive(Debug, Serialize, utoipa::ToSchema)]
+pub struct WorkerEventResponse {
+    pub id: i64,
+    pub worker_id: String,
+    pub job_id: Option<Uuid>,
+    /// "job_claimed", "job_started", "job_completed", or "job_failed"
+    pub event_type: String,
+    pub detail: serde_json::Value,
+    pub created_at: DateTime<Utc>,
+}
+
+impl From<WorkerEventRecord> for WorkerEventResponse {
+    fn from(event: WorkerEventRecord) -> Self {
+        Self {
+            id: event.id,
+            worker_id: event.worker_id,
+            job_id: event.job_id,
+            event_type: event.event_type,
+            detail: event.detail,
+            created_at: event.created_at,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, utoipa::ToSchema)]
+pub struct JobEventsResponse {
+    /// Oldest first
+    pub events: Vec<WorkerEventResponse>,
+}
+
 // ---------------------------------------------------------------------------
 // Extractions
 // ---------------------------------------------------------------------------
@@ -101,10 +382,48 @@ pub struct JobListResponse {
 pub struct ExtractionHistoryQuery {
     pub url: String,
     pub schema_name: String,
+    /// Only extractions whose content was detected in this language (ISO
+    /// 639-3, e.g. `eng`)
+    pub language: Option<String>,
     pub limit: Option<usize>,
     pub offset: Option<usize>,
 }
 
+#[derive(Debug, Deserialize, utoipa::IntoParams)]
+pub struct LatestExtractionQuery {
+    pub url: String,
+    pub schema_name: String,
+}
+
+#[derive(Debug, Deserialize, utoipa::IntoParams)]
+pub struct SnapshotQuery {
+    /// Which snapshot to return: `html` or `markdown`
+    #[param(value_type = String, example = "html")]
+    pub kind: SnapshotKind,
+}
+
+/// Selects the extractions to delete; exactly one field must be set.
+#[derive(Debug, Deserialize, utoipa::IntoParams)]
+pub struct DeleteExtractionsQuery {
+    /// Delete every extraction of this URL
+    pub url: Option<String>,
+    /// Delete every extraction made with this schema, in any version
+    pub schema_name: Option<String>,
+    /// Apply each extraction's `x-ares-retention` policy: delete expired
+    /// extractions, or null out their scrub fields
+    #[serde(default)]
+    pub expired: bool,
+}
+
+#[derive(Debug, Serialize, utoipa::ToSchema)]
+pub struct DeleteExtractionsResponse {
+    /// Number of extractions deleted
+    pub deleted: u64,
+    /// Number of expired extractions whose scrub fields were nulled out
+    /// (only with `expired=true`)
+    pub scrubbed: u64,
+}
+
 #[derive(Debug, Serialize, utoipa::ToSchema)]
 pub struct ExtractionResponse {
     pub id: Uuid,
@@ -112,22 +431,82 @@ pub struct ExtractionResponse {
     pub schema_name: String,
     pub extracted_data: serde_json::Value,
     pub content_hash: String,
+    /// 64-bit simhash of the cleaned content, for rows saved since it was added
+    pub content_simhash: Option<String>,
     pub data_hash: String,
+    /// Model that answered (may be more specific than the one requested)
     pub model: String,
+    /// LLM provider used (e.g. `openai`, `anthropic`, `local`)
+    pub provider: String,
+    /// SHA-256 of the effective extraction prompt; compare runs only when it matches
+    pub prompt_hash: Option<String>,
+    /// SHA-256 of the JSON schema the data was extracted with, for rows saved since it was added
+    pub schema_hash: Option<String>,
+    /// Schema version, when known
+    pub schema_version: Option<String>,
+    /// Extractor-call latency in ms (absent for cache-served results)
+    pub latency_ms: Option<i64>,
+    pub prompt_tokens: Option<i32>,
+    pub completion_tokens: Option<i32>,
+    /// Extraction of another URL this one duplicates (same schema and
+    /// cleaned content)
+    pub duplicate_of: Option<Uuid>,
+    /// Language of the cleaned content (ISO 639-3, e.g. `eng`), when detected
+    pub detected_language: Option<String>,
+    /// Known-good result that `POST /v1/verify` checks re-extractions against
+    pub golden: bool,
+    /// When an `x-ares-retention` policy scrubbed fields out of the data
+    pub scrubbed_at: Option<DateTime<Utc>>,
     pub created_at: DateTime<Utc>,
+    /// Time since `created_at`, e.g. `3h ago`
+    pub age_display: String,
+    /// In history listings, whether the data differs from the previous
+    /// extraction of the URL and schema (always true for the first one)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub changed: Option<bool>,
+    /// In history listings, the previous extraction of the URL and schema
+    /// (absent for the first one)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub previous_extraction_id: Option<Uuid>,
+}
+
+impl From<HistoryEntry> for ExtractionResponse {
+    fn from(entry: HistoryEntry) -> Self {
+        Self {
+            changed: Some(entry.changed),
+            previous_extraction_id: entry.previous_extraction_id,
+            ..Self::from(entry.extraction)
+        }
+    }
 }
 
 impl From<Extraction> for ExtractionResponse {
     fn from(e: Extraction) -> Self {
+        let age_display = format_age(e.age());
         Self {
             id: e.id,
             url: e.url,
             schema_name: e.schema_name,
             extracted_data: e.extracted_data,
             content_hash: e.content_hash,
+            content_simhash: e.content_simhash,
             data_hash: e.data_hash,
             model: e.model,
+            provider: e.provider,
+            prompt_hash: e.prompt_hash,
+            schema_hash: e.schema_hash,
+            schema_version: e.schema_version,
+            latency_ms: e.latency_ms,
+            prompt_tokens: e.prompt_tokens,
+            completion_tokens: e.completion_tokens,
+            duplicate_of: e.duplicate_of,
+            detected_language: e.detected_language,
+            golden: e.golden,
+            scrubbed_at: e.scrubbed_at,
             created_at: e.created_at,
+            age_display,
+            changed: None,
+            previous_extraction_id: None,
         }
     }
 }
@@ -152,8 +531,35 @@ pub struct CrawlResultsResponse {
 
 #[derive(Debug, Serialize, utoipa::ToSchema)]
 pub struct HealthResponse {
+    /// `healthy`, `degraded` (a non-critical dependency failed), or
+    /// `unhealthy` (database unreachable or migrations pending).
     pub status: &'static str,
+    /// Database connectivity: `ok` or `error`.
     pub database: &'static str,
+    /// Whether every bundled database migration has been applied.
+    pub migrations_applied: bool,
+    pub dependencies: Vec<DependencyHealth>,
+}
+
+#[derive(Debug, Serialize, utoipa::ToSchema)]
+pub struct DependencyHealth {
+    /// `database`, `migrations`, `schemas_dir`, or `llm`.
+    pub name: &'static str,
+    /// `ok` or `error`.
+    pub status: &'static str,
+    pub latency_ms: u64,
+    pub error: Option<String>,
+}
+
+impl DependencyHealth {
+    pub fn is_ok(&self) -> bool {
+        self.error.is_none()
+    }
+}
+
+#[derive(Debug, Serialize, utoipa::ToSchema)]
+pub struct LivenessResponse {
+    pub status: &'static str,
 }
 
 // ---------------------------------------------------------------------------
@@ -162,8 +568,13 @@ pub struct HealthResponse {
 
 #[derive(Debug, Deserialize, utoipa::ToSchema)]
 pub struct ScrapeRequest {
-    /// Target URL to scrape
+    /// Target URL to scrape. With `html`, only the key the extraction is
+    /// stored and compared under.
     pub url: String,
+    /// Pre-fetched HTML to clean and extract instead of fetching `url`.
+    /// Can't be combined with `use_browser`, `fetch_timeout_secs`, or
+    /// `wait_selector`.
+    pub html: Option<String>,
     /// JSON Schema definition for extraction
     pub schema: serde_json::Value,
     /// Schema name for storage
@@ -176,15 +587,32 @@ pub struct ScrapeRequest {
     pub base_url: Option<String>,
     /// Persist result to database (default: true)
     pub save: Option<bool>,
+    /// User-Agent of the browser: "desktop", "mobile", "googlebot", or any
+    /// string. Requires the page to be rendered in the browser
+    /// (`use_browser`, or a server running with `ARES_BROWSER`).
+    pub user_agent: Option<String>,
+    /// Treat the page as unchanged, skipping the LLM, when its content's
+    /// simhash differs from the previous extraction's in fewer than this
+    /// many of 64 bits (1–64). Requires `save`.
+    pub content_similarity_threshold: Option<u32>,
+    #[serde(flatten)]
+    pub fetch: FetchOptionsRequest,
 }
 
 #[derive(Debug, Serialize, utoipa::ToSchema)]
 pub struct ScrapeResponse {
     pub extracted_data: serde_json::Value,
     pub content_hash: String,
+    /// 64-bit simhash of the cleaned content, as 16 hex digits
+    pub content_simhash: String,
     pub data_hash: String,
     pub changed: bool,
     pub extraction_id: Option<Uuid>,
+    /// Extraction of another URL with the same cleaned content, when the
+    /// server dedups across URLs (`ARES_DEDUP_WINDOW_HOURS`)
+    pub duplicate_of: Option<Uuid>,
+    /// Language of the cleaned content (ISO 639-3, e.g. `eng`), when detected
+    pub detected_language: Option<String>,
 }
 
 // ---------------------------------------------------------------------------
@@ -196,11 +624,80 @@ pub struct SchemaListResponse {
     pub schemas: Vec<SchemaEntryResponse>,
 }
 
+/// Summary counts for `GET /v1/stats`.
+#[derive(Debug, Serialize, utoipa::ToSchema)]
+pub struct StatsResponse {
+    /// Registered schemas.
+    pub schemas: usize,
+    /// Versions across all registered schemas.
+    pub schema_versions: usize,
+}
+
+#[derive(Debug, Deserialize, utoipa::IntoParams)]
+pub struct DomainStatsQuery {
+    /// Start of the window (RFC 3339); defaults to 24 hours ago
+    pub since: Option<DateTime<Utc>>,
+}
+
+/// Per-domain job outcomes for `GET /v1/stats/domains`.
+#[derive(Debug, Serialize, utoipa::ToSchema)]
+pub struct DomainStatsResponse {
+    pub since: DateTime<Utc>,
+    /// Domains with activity in the window, most failed jobs first
+    pub domains: Vec<DomainStatsEntryResponse>,
+}
+
+#[derive(Debug, Serialize, utoipa::ToSchema)]
+pub struct DomainStatsEntryResponse {
+    /// `scheme://host:port`
+    pub domain: String,
+    pub jobs_succeeded: i64,
+    /// Permanently failed jobs; retried attempts are not counted
+    pub jobs_failed: i64,
+    /// Failed share of finished jobs, absent when none finished
+    pub failure_rate: Option<f64>,
+    /// Mean claim-to-completion time of successful jobs
+    pub avg_duration_secs: Option<f64>,
+    /// Last completed job, even if before the window
+    pub last_success_at: Option<DateTime<Utc>>,
+    pub extractions: i64,
+}
+
+impl From<DomainStats> for DomainStatsEntryResponse {
+    fn from(s: DomainStats) -> Self {
+        Self {
+            failure_rate: s.failure_rate(),
+            domain: s.domain,
+            jobs_succeeded: s.jobs_succeeded,
+            jobs_failed: s.jobs_failed,
+            avg_duration_secs: s.avg_duration_secs,
+            last_success_at: s.last_success_at,
+            extractions: s.extractions,
+        }
+    }
+}
+
 #[derive(Debug, Serialize, utoipa::ToSchema)]
 pub struct SchemaEntryResponse {
     pub name: String,
     pub latest_version: String,
     pub versions: Vec<String>,
+    /// Creation time of the oldest version file, if the file system records it.
+    pub created_at: Option<DateTime<Utc>>,
+    /// Last modification time across the version files.
+    pub updated_at: Option<DateTime<Utc>>,
+}
+
+impl From<SchemaEntry> for SchemaEntryResponse {
+    fn from(e: SchemaEntry) -> Self {
+        Self {
+            name: e.name,
+            latest_version: e.latest_version,
+            versions: e.versions,
+            created_at: e.created_at.map(DateTime::from),
+            updated_at: e.updated_at.map(DateTime::from),
+        }
+    }
 }
 
 #[derive(Debug, Serialize, utoipa::ToSchema)]
@@ -232,6 +729,94 @@ pub struct CreateSchemaResponse {
     pub version: String,
 }
 
+#[derive(Debug, Deserialize, utoipa::ToSchema)]
+pub struct SchemaValidationTestRequest {
+    /// Sample HTML to run through the extraction pipeline
+    pub html: String,
+    /// URL the sample HTML was taken from (used for logging only)
+    pub url: String,
+}
+
+#[derive(Debug, Serialize, utoipa::ToSchema)]
+pub struct SchemaValidationTestResponse {
+    /// Cleaned Markdown that was sent to the LLM
+    pub markdown: String,
+    /// Raw LLM extraction result
+    pub extracted_data: serde_json::Value,
+    /// Whether `extracted_data` conforms to the schema
+    pub valid: bool,
+    /// Schema validation failures (empty when `valid` is true)
+    pub errors: Vec<String>,
+}
+
+// ---------------------------------------------------------------------------
+// Verify
+// ---------------------------------------------------------------------------
+
+#[derive(Debug, Deserialize, utoipa::ToSchema)]
+pub struct VerifyRequest {
+    /// Schema reference (`name@version`, or `name` for the latest version);
+    /// its golden extractions are re-extracted
+    pub schema: String,
+    /// LLM model override (falls back to ARES_MODEL env)
+    pub model: Option<String>,
+    /// LLM provider: "openai" (default), "anthropic", or native "local"
+    pub provider: Option<String>,
+    /// API base URL override (falls back to ARES_BASE_URL env, then the provider default)
+    pub base_url: Option<String>,
+}
+
+#[derive(Debug, Serialize, utoipa::ToSchema)]
+pub struct VerifyResponse {
+    /// Resolved schema reference, e.g. `product@1.0.0`
+    pub schema_name: String,
+    /// Whether every golden page matched (true when there are none)
+    pub passed: bool,
+    pub total: usize,
+    pub failed: usize,
+    pub results: Vec<VerifyResultResponse>,
+}
+
+impl From<VerifyReport> for VerifyResponse {
+    fn from(report: VerifyReport) -> Self {
+        Self {
+            passed: report.passed(),
+            total: report.checks.len(),
+            failed: report.failed(),
+            schema_name: report.schema_name,
+            results: report.checks.into_iter().map(Into::into).collect(),
+        }
+    }
+}
+
+#[derive(Debug, Serialize, utoipa::ToSchema)]
+pub struct VerifyResultResponse {
+    pub url: String,
+    /// Golden extraction the new result was compared with
+    pub golden_id: Uuid,
+    pub passed: bool,
+    /// Whether the page was replayed from its HTML snapshot instead of fetched
+    pub replayed: bool,
+    /// Differences from the golden data to the new data
+    #[schema(value_type = Vec<Object>)]
+    pub changes: Vec<JsonChange>,
+    /// Why the page couldn't be re-extracted
+    pub error: Option<String>,
+}
+
+impl From<GoldenCheck> for VerifyResultResponse {
+    fn from(check: GoldenCheck) -> Self {
+        Self {
+            passed: check.passed(),
+            url: check.url,
+            golden_id: check.golden_id,
+            replayed: check.replayed,
+            changes: check.changes,
+            error: check.error,
+        }
+    }
+}
+
 // ---------------------------------------------------------------------------
 // Errors
 // ---------------------------------------------------------------------------
@@ -242,6 +827,17 @@ pub struct ErrorResponse {
     pub message: String,
 }
 
+/// Body of a 422 for a document that failed validation in several ways.
+#[derive(Debug, Serialize, utoipa::ToSchema)]
+pub struct ValidationErrorResponse {
+    /// Always `validation_error`.
+    pub error: String,
+    /// All failures joined into one line, as in [`ErrorResponse`].
+    pub message: String,
+    /// Each failure on its own.
+    pub messages: Vec<String>,
+}
+
 // ---------------------------------------------------------------------------
 // Crawl
 // ---------------------------------------------------------------------------
@@ -273,3 +869,203 @@ pub struct CrawlStatusResponse {
     pub completed_jobs: usize,
     pub failed_jobs: usize,
 }
+
+// ---------------------------------------------------------------------------
+// API keys
+// ---------------------------------------------------------------------------
+
+#[derive(Debug, Deserialize, utoipa::ToSchema)]
+pub struct CreateApiKeyRequest {
+    /// Human-readable name of the consumer (e.g., "ingest-service")
+    pub name: String,
+    /// Granted scopes: "scrape", "jobs:write", "schemas:write", "admin"
+    pub scopes: Vec<String>,
+}
+
+#[derive(Debug, Serialize, utoipa::ToSchema)]
+pub struct CreateApiKeyResponse {
+    pub id: Uuid,
+    pub name: String,
+    pub scopes: Vec<String>,
+    /// The key value. Shown only once — store it now.
+    pub key: String,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Serialize, utoipa::ToSchema)]
+pub struct ApiKeyResponse {
+    pub id: Uuid,
+    pub name: String,
+    pub scopes: Vec<String>,
+    pub created_at: DateTime<Utc>,
+    pub revoked_at: Option<DateTime<Utc>>,
+    pub last_used_at: Option<DateTime<Utc>>,
+}
+
+impl From<ApiKey> for ApiKeyResponse {
+    fn from(key: ApiKey) -> Self {
+        Self {
+            id: key.id,
+            name: key.name,
+            scopes: key.scopes.iter().map(|s| s.to_string()).collect(),
+            created_at: key.created_at,
+            revoked_at: key.revoked_at,
+            last_used_at: key.last_used_at,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, utoipa::IntoParams)]
+pub struct ListApiKeysQuery {
+    /// Include revoked keys (default: false)
+    pub include_revoked: Option<bool>,
+}
+
+#[derive(Debug, Serialize, utoipa::ToSchema)]
+pub struct ApiKeyListResponse {
+    pub api_keys: Vec<ApiKeyResponse>,
+}
+
+// ---------------------------------------------------------------------------
+// Circuit breakers
+// ---------------------------------------------------------------------------
+
+#[derive(Debug, Serialize, utoipa::ToSchema)]
+pub struct CircuitBreakerResponse {
+    pub name: String,
+    /// "closed", "open", or "half-open"
+    pub state: String,
+    pub failure_count: u32,
+    pub success_count: u32,
+    pub last_error: Option<String>,
+    /// Seconds until an open breaker starts probing again
+    pub retry_after_secs: Option<u64>,
+    /// When a worker last reported this breaker
+    pub updated_at: DateTime<Utc>,
+    pub reset_requested_at: Option<DateTime<Utc>>,
+}
+
+impl From<CircuitBreakerRecord> for CircuitBreakerResponse {
+    fn from(record: CircuitBreakerRecord) -> Self {
+        let stats = record.stats;
+        Self {
+            name: stats.name,
+            state: stats.state.to_string(),
+            failure_count: stats.failure_count,
+            success_count: stats.success_count,
+            last_error: stats.last_error,
+            retry_after_secs: stats.time_until_half_open.map(|d| d.as_secs()),
+            updated_at: record.updated_at,
+            reset_requested_at: record.reset_requested_at,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, utoipa::ToSchema)]
+pub struct CircuitBreakerListResponse {
+    pub circuit_breakers: Vec<CircuitBreakerResponse>,
+}
+
+// ---------------------------------------------------------------------------
+// Audit log
+// ---------------------------------------------------------------------------
+
+#[derive(Debug, Deserialize, utoipa::IntoParams)]
+pub struct AuditLogQuery {
+    /// Only entries recorded at or after this time (RFC 3339)
+    pub since: Option<DateTime<Utc>>,
+    /// Only entries for this action, e.g. "schema.create" or "job.cancel"
+    pub action: Option<String>,
+    pub limit: Option<usize>,
+    pub offset: Option<usize>,
+}
+
+#[derive(Debug, Serialize, utoipa::ToSchema)]
+pub struct AuditEntryResponse {
+    pub id: i64,
+    /// The acting API key; absent when the bootstrap admin token was used
+    pub api_key_id: Option<Uuid>,
+    /// Fingerprint of the bootstrap admin token, when it was used
+    pub token_fingerprint: Option<String>,
+    /// e.g. "schema.create", "job.cancel", "api_key.revoke"
+    pub action: String,
+    /// "schema", "job", "extraction", "api_key", or "circuit_breaker"
+    pub resource_type: String,
+    pub resource_id: Option<String>,
+    pub request_id: Option<String>,
+    /// Redacted summary of the request
+    pub summary: serde_json::Value,
+    pub created_at: DateTime<Utc>,
+}
+
+impl From<AuditEntry> for AuditEntryResponse {
+    fn from(entry: AuditEntry) -> Self {
+        Self {
+            id: entry.id,
+            api_key_id: entry.api_key_id,
+            token_fingerprint: entry.token_fingerprint,
+            action: entry.action,
+            resource_type: entry.resource_type,
+            resource_id: entry.resource_id,
+            request_id: entry.request_id,
+            summary: entry.summary,
+            created_at: entry.created_at,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, utoipa::ToSchema)]
+pub struct AuditLogResponse {
+    /// Newest first
+    pub entries: Vec<AuditEntryResponse>,
+    pub total: usize,
+    pub limit: usize,
+    pub offset: usize,
+}
+
+// ---------------------------------------------------------------------------
+// Admin: LLM usage
+// ---------------------------------------------------------------------------
+
+/// Today's LLM usage (UTC) against the daily budget.
+#[derive(Debug, Serialize, utoipa::ToSchema)]
+pub struct UsageResponse {
+    pub day: NaiveDate,
+    /// LLM calls made today, successful or not
+    pub llm_calls: u64,
+    /// Prompt plus completion tokens
+    pub tokens: u64,
+    /// Estimated spend; models without a known price count as free
+    pub cost_usd: f64,
+    pub limits: BudgetLimitsResponse,
+    /// Which cap is used up, if any
+    pub exceeded: Option<String>,
+    /// When today's counters reset (next midnight UTC)
+    pub resets_at: DateTime<Utc>,
+}
+
+/// Daily caps; `null` means uncapped.
+#[derive(Debug, Serialize, utoipa::ToSchema)]
+pub struct BudgetLimitsResponse {
+    pub max_calls: Option<u64>,
+    pub max_tokens: Option<u64>,
+    pub max_cost_usd: Option<f64>,
+}
+
+impl UsageResponse {
+    pub fn new(usage: DailyUsage, limits: &BudgetLimits, resets_at: DateTime<Utc>) -> Self {
+        Self {
+            day: usage.day,
+            llm_calls: usage.llm_calls,
+            tokens: usage.tokens,
+            cost_usd: usage.cost_usd,
+            limits: BudgetLimitsResponse {
+                max_calls: limits.max_calls,
+                max_tokens: limits.max_tokens,
+                max_cost_usd: limits.max_cost_usd,
+            },
+            exceeded: limits.exceeded_by(&usage),
+            resets_at,
+        }
+    }
+}
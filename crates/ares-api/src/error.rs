@@ -1,9 +1,9 @@
-use axum::http::StatusCode;
+use axum::http::{HeaderValue, StatusCode, header};
 use axum::response::{IntoResponse, Response};
 
 use ares_core::error::AppError;
 
-use crate::dto::ErrorResponse;
+use crate::dto::{ErrorResponse, ValidationErrorResponse};
 
 /// Wrapper so we can implement `IntoResponse` for `AppError`.
 pub struct ApiError(pub AppError);
@@ -16,6 +16,16 @@ impl From<AppError> for ApiError {
 
 impl IntoResponse for ApiError {
     fn into_response(self) -> Response {
+        // Every failure is listed separately so clients can show them all.
+        if let AppError::ValidationError(messages) = self.0 {
+            let body = ValidationErrorResponse {
+                error: "validation_error".to_string(),
+                message: messages.join("; "),
+                messages,
+            };
+            return (StatusCode::UNPROCESSABLE_ENTITY, axum::Json(body)).into_response();
+        }
+
         let (status, error_type) = match &self.0 {
             AppError::SchemaValidationError(_) | AppError::SchemaError(_) => {
                 (StatusCode::BAD_REQUEST, "validation_error")
@@ -28,12 +38,20 @@ impl IntoResponse for ApiError {
                 StatusCode::UNPROCESSABLE_ENTITY,
                 "extraction_validation_error",
             ),
+            // The target site refused us; retrying the same request won't help.
+            AppError::Blocked { .. } => (StatusCode::UNPROCESSABLE_ENTITY, "fetch_blocked"),
             AppError::SchemaNotFound { .. } => (StatusCode::NOT_FOUND, "not_found"),
+            AppError::Conflict(_) => (StatusCode::CONFLICT, "conflict"),
             AppError::SerializationError(_) => (StatusCode::BAD_REQUEST, "serialization_error"),
             AppError::DatabaseError(_) => (StatusCode::INTERNAL_SERVER_ERROR, "database_error"),
             AppError::ConfigError(_) => (StatusCode::INTERNAL_SERVER_ERROR, "config_error"),
             AppError::RateLimitExceeded => (StatusCode::TOO_MANY_REQUESTS, "rate_limit_exceeded"),
+            AppError::BudgetExceeded { .. } => (StatusCode::TOO_MANY_REQUESTS, "budget_exceeded"),
             AppError::Timeout(_) => (StatusCode::GATEWAY_TIMEOUT, "timeout"),
+            // The upstream LLM is down or overloaded; the client may retry later.
+            AppError::LlmError {
+                retryable: true, ..
+            } => (StatusCode::SERVICE_UNAVAILABLE, "llm_unavailable"),
             _ => (StatusCode::INTERNAL_SERVER_ERROR, "internal_error"),
         };
 
@@ -41,7 +59,15 @@ impl IntoResponse for ApiError {
             error: error_type.to_string(),
             message: self.0.to_string(),
         };
+        let mut response = (status, axum::Json(body)).into_response();
 
-        (status, axum::Json(body)).into_response()
+        // Tell clients when the daily budget resets.
+        if let AppError::BudgetExceeded { resets_at, .. } = &self.0 {
+            let secs = (*resets_at - chrono::Utc::now()).num_seconds().max(1);
+            response
+                .headers_mut()
+                .insert(header::RETRY_AFTER, HeaderValue::from(secs));
+        }
+        response
     }
 }
@@ -0,0 +1,112 @@
+//! Weak ETags and `If-None-Match` handling for read endpoints.
+//!
+//! ETags are derived from what identifies a representation — data hashes,
+//! `updated_at` timestamps — rather than from the serialized body, so they
+//! are cheap to compute and stable across serializer changes. They are weak
+//! (`W/"..."`) because compressed and uncompressed bodies share one tag.
+
+use axum::http::header::{ETAG, IF_NONE_MATCH};
+use axum::http::{HeaderMap, HeaderValue, StatusCode};
+use axum::response::{IntoResponse, Response};
+
+use ares_core::compute_hash;
+
+/// Hex digits of the SHA-256 digest kept in the tag.
+const ETAG_HEX_LEN: usize = 32;
+
+/// Build a weak ETag from the parts that identify a representation.
+pub fn weak_etag<I, S>(parts: I) -> HeaderValue
+where
+    I: IntoIterator<Item = S>,
+    S: AsRef<str>,
+{
+    let mut input = String::new();
+    for part in parts {
+        input.push_str(part.as_ref());
+        input.push('\0');
+    }
+    let digest = compute_hash(input);
+    HeaderValue::from_str(&format!("W/\"{}\"", &digest[..ETAG_HEX_LEN]))
+        .expect("hex digest is a valid header value")
+}
+
+/// Whether the request's `If-None-Match` matches `etag` (weak comparison).
+pub fn is_fresh(headers: &HeaderMap, etag: &HeaderValue) -> bool {
+    let Ok(etag) = etag.to_str() else {
+        return false;
+    };
+    headers
+        .get_all(IF_NONE_MATCH)
+        .iter()
+        .filter_map(|value| value.to_str().ok())
+        .flat_map(|value| value.split(','))
+        .map(str::trim)
+        .any(|candidate| candidate == "*" || opaque(candidate) == opaque(etag))
+}
+
+/// `304 Not Modified` if the client already holds `etag`, otherwise `body`.
+/// Both carry the `ETag` header.
+pub fn conditional(headers: &HeaderMap, etag: HeaderValue, body: impl IntoResponse) -> Response {
+    let mut response = if is_fresh(headers, &etag) {
+        StatusCode::NOT_MODIFIED.into_response()
+    } else {
+        body.into_response()
+    };
+    response.headers_mut().insert(ETAG, etag);
+    response
+}
+
+fn opaque(tag: &str) -> &str {
+    tag.strip_prefix("W/").unwrap_or(tag)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn if_none_match(value: &str) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        headers.insert(IF_NONE_MATCH, HeaderValue::from_str(value).unwrap());
+        headers
+    }
+
+    #[test]
+    fn etag_is_weak_and_depends_on_every_part() {
+        let tag = weak_etag(["job", "2026-01-01T00:00:00Z"]);
+        assert!(tag.to_str().unwrap().starts_with("W/\""));
+        assert_eq!(tag, weak_etag(["job", "2026-01-01T00:00:00Z"]));
+        assert_ne!(tag, weak_etag(["job", "2026-01-01T00:00:01Z"]));
+        // Part boundaries matter.
+        assert_ne!(weak_etag(["ab", "c"]), weak_etag(["a", "bc"]));
+    }
+
+    #[test]
+    fn if_none_match_uses_weak_comparison() {
+        let tag = weak_etag(["x"]);
+        let opaque = tag.to_str().unwrap().trim_start_matches("W/").to_string();
+
+        assert!(is_fresh(&if_none_match(tag.to_str().unwrap()), &tag));
+        assert!(is_fresh(&if_none_match(&opaque), &tag));
+        assert!(is_fresh(
+            &if_none_match(&format!("\"other\", {opaque}")),
+            &tag
+        ));
+        assert!(is_fresh(&if_none_match("*"), &tag));
+        assert!(!is_fresh(&if_none_match("W/\"other\""), &tag));
+        assert!(!is_fresh(&HeaderMap::new(), &tag));
+    }
+
+    #[test]
+    fn conditional_returns_304_with_etag() {
+        let tag = weak_etag(["x"]);
+        let headers = if_none_match(tag.to_str().unwrap());
+
+        let response = conditional(&headers, tag.clone(), "body");
+        assert_eq!(response.status(), StatusCode::NOT_MODIFIED);
+        assert_eq!(response.headers()[ETAG], tag);
+
+        let response = conditional(&HeaderMap::new(), tag.clone(), "body");
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(response.headers()[ETAG], tag);
+    }
+}
@@ -1,8 +1,20 @@
 //! REST API server — routes, authentication, DTOs, and OpenAPI documentation.
 
+pub mod app;
+pub mod audit;
 pub mod auth;
+pub mod config;
 pub mod dto;
 pub mod error;
+pub mod etag;
+pub mod metrics;
+pub mod middleware;
 pub mod openapi;
+pub mod rate_limit;
 pub mod routes;
+pub mod shutdown;
 pub mod state;
+pub mod telemetry;
+
+pub use app::build_app;
+pub use config::ServerConfig;
@@ -0,0 +1,302 @@
+//! Prometheus metrics — recorder setup, HTTP middleware, and background sampling.
+//!
+//! Metrics are recorded through the [`metrics`] facade and rendered in the
+//! Prometheus exposition format by [`router`]'s `GET /metrics`. The recorder
+//! is installed once per process with [`install_recorder`]; until then every
+//! metric call is a no-op.
+
+use std::sync::OnceLock;
+use std::time::{Duration, Instant};
+
+use axum::Router;
+use axum::extract::{MatchedPath, Request, State};
+use axum::http::{StatusCode, header};
+use axum::middleware::Next;
+use axum::response::{IntoResponse, Response};
+use axum::routing::get;
+use metrics_exporter_prometheus::{
+    Matcher, PrometheusBuilder, PrometheusHandle, PrometheusRecorder,
+};
+use tokio::task::JoinHandle;
+
+use ares_core::error::AppError;
+use ares_core::job::JobStatus;
+use ares_core::job_queue::JobQueue;
+use ares_db::Database;
+
+pub const HTTP_REQUESTS_TOTAL: &str = "ares_http_requests_total";
+pub const HTTP_REQUEST_DURATION: &str = "ares_http_request_duration_seconds";
+pub const SCRAPE_DURATION: &str = "ares_scrape_duration_seconds";
+pub const DB_POOL_CONNECTIONS: &str = "ares_db_pool_connections";
+pub const DB_POOL_IDLE: &str = "ares_db_pool_idle_connections";
+pub const DB_POOL_ACQUIRE_DURATION: &str = "ares_db_pool_acquire_duration_seconds";
+pub const JOBS: &str = "ares_jobs";
+pub const JOBS_PENDING: &str = "ares_jobs_pending";
+pub const EVENTS_DROPPED: &str = "ares_events_dropped_total";
+
+/// Content type of the Prometheus text exposition format.
+pub const CONTENT_TYPE: &str = "text/plain; version=0.0.4; charset=utf-8";
+
+/// Default histogram buckets (seconds), sized for HTTP and DB latencies.
+const LATENCY_BUCKETS: &[f64] = &[
+    0.005, 0.01, 0.025, 0.05, 0.1, 0.25, 0.5, 1.0, 2.5, 5.0, 10.0,
+];
+
+/// Buckets for the scrape pipeline, which includes a fetch and an LLM call.
+const SCRAPE_BUCKETS: &[f64] = &[0.5, 1.0, 2.5, 5.0, 10.0, 20.0, 30.0, 60.0, 120.0];
+
+const JOB_STATUSES: [JobStatus; 5] = [
+    JobStatus::Pending,
+    JobStatus::Running,
+    JobStatus::Completed,
+    JobStatus::Failed,
+    JobStatus::Cancelled,
+];
+
+static HANDLE: OnceLock<PrometheusHandle> = OnceLock::new();
+
+/// Build a Prometheus recorder with the Ares histogram buckets.
+///
+/// The recorder is not installed; use [`install_recorder`] for the global one.
+pub fn build_recorder() -> PrometheusRecorder {
+    PrometheusBuilder::new()
+        .set_buckets(LATENCY_BUCKETS)
+        .and_then(|b| {
+            b.set_buckets_for_metric(Matcher::Full(SCRAPE_DURATION.into()), SCRAPE_BUCKETS)
+        })
+        .expect("bucket lists are non-empty")
+        .build_recorder()
+}
+
+/// Install the process-wide recorder and return a handle for rendering.
+///
+/// Idempotent: later calls return the handle from the first call.
+pub fn install_recorder() -> Result<PrometheusHandle, AppError> {
+    let mut result = Ok(());
+    let handle = HANDLE.get_or_init(|| {
+        let recorder = build_recorder();
+        let handle = recorder.handle();
+        if let Err(e) = metrics::set_global_recorder(recorder) {
+            result = Err(AppError::ConfigError(format!(
+                "Failed to install metrics recorder: {e}"
+            )));
+        }
+        describe_metrics();
+        handle
+    });
+    result.map(|()| handle.clone())
+}
+
+fn describe_metrics() {
+    metrics::describe_counter!(
+        HTTP_REQUESTS_TOTAL,
+        "HTTP requests by method, route, and status"
+    );
+    metrics::describe_histogram!(
+        HTTP_REQUEST_DURATION,
+        metrics::Unit::Seconds,
+        "HTTP request latency by method, route, and status"
+    );
+    metrics::describe_histogram!(
+        SCRAPE_DURATION,
+        metrics::Unit::Seconds,
+        "Duration of the synchronous POST /v1/scrape pipeline by outcome"
+    );
+    metrics::describe_gauge!(DB_POOL_CONNECTIONS, "Open database pool connections");
+    metrics::describe_gauge!(DB_POOL_IDLE, "Idle database pool connections");
+    metrics::describe_histogram!(
+        DB_POOL_ACQUIRE_DURATION,
+        metrics::Unit::Seconds,
+        "Time to acquire a pooled database connection, probed by the sampler"
+    );
+    metrics::describe_gauge!(JOBS, "Scrape jobs by status");
+    metrics::describe_gauge!(JOBS_PENDING, "Scrape jobs waiting to be claimed");
+    metrics::describe_counter!(
+        EVENTS_DROPPED,
+        "Extraction events that could not be published to the event bus"
+    );
+}
+
+/// Router serving `GET /metrics`. Unauthenticated — merge it into the main
+/// app or serve it on a separate, internal-only port.
+pub fn router(handle: PrometheusHandle) -> Router {
+    Router::new()
+        .route("/metrics", get(render))
+        .with_state(handle)
+}
+
+async fn render(State(handle): State<PrometheusHandle>) -> impl IntoResponse {
+    (
+        StatusCode::OK,
+        [(header::CONTENT_TYPE, CONTENT_TYPE)],
+        handle.render(),
+    )
+}
+
+/// Middleware recording request counts and latencies.
+///
+/// Routes are labelled by their matched pattern (e.g. `/v1/jobs/{id}`) so
+/// label cardinality stays bounded; unmatched paths are labelled `unmatched`.
+pub async fn track_http(request: Request, next: Next) -> Response {
+    let method = request.method().clone();
+    let route = request
+        .extensions()
+        .get::<MatchedPath>()
+        .map_or("unmatched", MatchedPath::as_str)
+        .to_string();
+
+    let start = Instant::now();
+    let response = next.run(request).await;
+    record_http_request(method.as_str(), &route, response.status(), start.elapsed());
+
+    response
+}
+
+/// Record one handled HTTP request.
+pub fn record_http_request(method: &str, route: &str, status: StatusCode, elapsed: Duration) {
+    let labels = [
+        ("method", method.to_string()),
+        ("route", route.to_string()),
+        ("status", status.as_str().to_string()),
+    ];
+    metrics::counter!(HTTP_REQUESTS_TOTAL, &labels).increment(1);
+    metrics::histogram!(HTTP_REQUEST_DURATION, &labels).record(elapsed.as_secs_f64());
+}
+
+/// Record one run of the synchronous scrape pipeline.
+pub fn record_scrape(elapsed: Duration, success: bool) {
+    let outcome = if success { "success" } else { "error" };
+    metrics::histogram!(SCRAPE_DURATION, "outcome" => outcome).record(elapsed.as_secs_f64());
+}
+
+/// Record the publisher's running count of dropped extraction events.
+pub fn record_events_dropped(total: u64) {
+    metrics::counter!(EVENTS_DROPPED).absolute(total);
+}
+
+/// Record database pool occupancy.
+pub fn record_pool_stats(connections: u32, idle: usize) {
+    metrics::gauge!(DB_POOL_CONNECTIONS).set(connections as f64);
+    metrics::gauge!(DB_POOL_IDLE).set(idle as f64);
+}
+
+/// Record the job count for one status.
+pub fn record_job_count(status: JobStatus, count: i64) {
+    metrics::gauge!(JOBS, "status" => status.as_str()).set(count as f64);
+    if status == JobStatus::Pending {
+        metrics::gauge!(JOBS_PENDING).set(count as f64);
+    }
+}
+
+/// Spawn a task that samples pool and job-queue state every `interval` and
+/// runs recorder upkeep.
+pub fn spawn_sampler(db: Database, handle: PrometheusHandle, interval: Duration) -> JoinHandle<()> {
+    tokio::spawn(async move {
+        let mut ticker = tokio::time::interval(interval);
+        loop {
+            ticker.tick().await;
+            sample(&db).await;
+            handle.run_upkeep();
+        }
+    })
+}
+
+/// Take one sample of pool stats, acquire latency, and job counts.
+pub async fn sample(db: &Database) {
+    let pool = db.pool();
+    record_pool_stats(pool.size(), pool.num_idle());
+
+    let start = Instant::now();
+    match pool.acquire().await {
+        Ok(conn) => {
+            metrics::histogram!(DB_POOL_ACQUIRE_DURATION).record(start.elapsed().as_secs_f64());
+            drop(conn);
+        }
+        Err(e) => tracing::warn!("Metrics sampler could not acquire a connection: {e}"),
+    }
+
+    let repo = db.job_repo();
+    for status in JOB_STATUSES {
+        match repo.count_by_status(status).await {
+            Ok(count) => record_job_count(status, count),
+            Err(e) => tracing::warn!("Metrics sampler could not count {status} jobs: {e}"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn render_with(f: impl FnOnce()) -> String {
+        let recorder = build_recorder();
+        let handle = recorder.handle();
+        metrics::with_local_recorder(&recorder, f);
+        handle.render()
+    }
+
+    #[test]
+    fn http_requests_are_counted_per_route_and_status() {
+        let out = render_with(|| {
+            record_http_request(
+                "GET",
+                "/v1/jobs/{id}",
+                StatusCode::OK,
+                Duration::from_millis(3),
+            );
+            record_http_request(
+                "GET",
+                "/v1/jobs/{id}",
+                StatusCode::OK,
+                Duration::from_millis(4),
+            );
+            record_http_request(
+                "GET",
+                "/v1/jobs/{id}",
+                StatusCode::NOT_FOUND,
+                Duration::ZERO,
+            );
+        });
+
+        assert!(out.contains(
+            r#"ares_http_requests_total{method="GET",route="/v1/jobs/{id}",status="200"} 2"#
+        ));
+        assert!(out.contains(
+            r#"ares_http_requests_total{method="GET",route="/v1/jobs/{id}",status="404"} 1"#
+        ));
+        assert!(out.contains(
+            r#"ares_http_request_duration_seconds_bucket{method="GET",route="/v1/jobs/{id}",status="200",le="0.005"} 2"#
+        ));
+    }
+
+    #[test]
+    fn scrape_durations_use_scrape_buckets() {
+        let out = render_with(|| record_scrape(Duration::from_secs(3), true));
+
+        assert!(out.contains(r#"ares_scrape_duration_seconds_bucket{outcome="success",le="5"} 1"#));
+        assert!(
+            out.contains(r#"ares_scrape_duration_seconds_bucket{outcome="success",le="2.5"} 0"#)
+        );
+        assert!(!out.contains(r#"le="0.005""#));
+    }
+
+    #[test]
+    fn job_counts_set_status_gauges() {
+        let out = render_with(|| {
+            record_job_count(JobStatus::Pending, 7);
+            record_job_count(JobStatus::Failed, 2);
+        });
+
+        assert!(out.contains(r#"ares_jobs{status="pending"} 7"#));
+        assert!(out.contains(r#"ares_jobs{status="failed"} 2"#));
+        assert!(out.contains("ares_jobs_pending 7"));
+    }
+
+    #[test]
+    fn pool_stats_set_gauges() {
+        let out = render_with(|| record_pool_stats(4, 3));
+
+        assert!(out.contains("ares_db_pool_connections 4"));
+        assert!(out.contains("ares_db_pool_idle_connections 3"));
+    }
+}
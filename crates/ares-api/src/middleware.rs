@@ -0,0 +1,153 @@
+//! Access log — one `tracing` line per request.
+//!
+//! `TraceLayer` wraps each request in a span, but only logs at debug level
+//! and spreads a request over several events. [`RequestLoggerLayer`] writes a
+//! single INFO line once the response is ready, e.g.
+//! `GET /v1/jobs → 200 (12ms)`, with `method`, `path`, `status`,
+//! `duration_ms`, and (when the request-ID layer ran first) `request_id` as
+//! fields for log search.
+
+use std::future::Future;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+use std::time::Instant;
+
+use axum::extract::Request;
+use axum::response::Response;
+use tower::{Layer, Service};
+
+use crate::telemetry::REQUEST_ID_HEADER;
+
+/// The access log layer; see the [module docs](self).
+pub fn request_logger() -> RequestLoggerLayer {
+    RequestLoggerLayer
+}
+
+/// [`Layer`] wrapping a service in [`RequestLogger`].
+#[derive(Debug, Clone, Copy, Default)]
+pub struct RequestLoggerLayer;
+
+impl<S> Layer<S> for RequestLoggerLayer {
+    type Service = RequestLogger<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        RequestLogger { inner }
+    }
+}
+
+/// Service logging each request it passes to `inner`.
+#[derive(Debug, Clone)]
+pub struct RequestLogger<S> {
+    inner: S,
+}
+
+impl<S> Service<Request> for RequestLogger<S>
+where
+    S: Service<Request, Response = Response> + Send + 'static,
+    S::Future: Send + 'static,
+{
+    type Response = S::Response;
+    type Error = S::Error;
+    type Future = Pin<Box<dyn Future<Output = Result<Self::Response, Self::Error>> + Send>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, request: Request) -> Self::Future {
+        let method = request.method().clone();
+        let path = request.uri().path().to_string();
+        let request_id = request
+            .headers()
+            .get(REQUEST_ID_HEADER)
+            .and_then(|v| v.to_str().ok())
+            .map(str::to_string);
+        let started = Instant::now();
+        let response = self.inner.call(request);
+
+        Box::pin(async move {
+            let response = response.await?;
+            let status = response.status().as_u16();
+            let duration_ms = started.elapsed().as_millis() as u64;
+            tracing::info!(
+                %method,
+                path,
+                status,
+                duration_ms,
+                request_id,
+                "{method} {path} → {status} ({duration_ms}ms)"
+            );
+            Ok(response)
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use axum::Router;
+    use axum::body::Body;
+    use axum::http::StatusCode;
+    use axum::routing::get;
+    use tower::ServiceExt;
+
+    use ares_core::testutil::CapturedLogs;
+
+    use super::*;
+
+    /// Send `request` through a logged router and return the JSON log lines.
+    async fn log_lines(request: Request) -> Vec<serde_json::Value> {
+        let captured = CapturedLogs::default();
+        let subscriber = tracing_subscriber::fmt()
+            .json()
+            .with_writer(captured.clone())
+            .finish();
+        let _guard = tracing::subscriber::set_default(subscriber);
+
+        let app = Router::new()
+            .route("/v1/jobs", get(|| async { "[]" }))
+            .layer(request_logger());
+        app.oneshot(request).await.unwrap();
+
+        captured
+            .lines()
+            .iter()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn logs_one_line_per_request() {
+        let request = Request::builder()
+            .uri("/v1/jobs?status=pending")
+            .header(REQUEST_ID_HEADER, "req-1")
+            .body(Body::empty())
+            .unwrap();
+        let lines = log_lines(request).await;
+
+        assert_eq!(lines.len(), 1, "{lines:?}");
+        let fields = &lines[0]["fields"];
+        assert_eq!(lines[0]["level"], "INFO");
+        assert_eq!(fields["method"], "GET");
+        assert_eq!(fields["path"], "/v1/jobs");
+        assert_eq!(fields["status"], 200);
+        assert!(fields["duration_ms"].is_u64());
+        assert_eq!(fields["request_id"], "req-1");
+        let message = fields["message"].as_str().unwrap();
+        assert!(message.starts_with("GET /v1/jobs → 200 ("), "{message}");
+        assert!(message.ends_with("ms)"), "{message}");
+    }
+
+    #[tokio::test]
+    async fn logs_error_statuses_without_a_request_id() {
+        let request = Request::builder()
+            .uri("/missing")
+            .body(Body::empty())
+            .unwrap();
+        let lines = log_lines(request).await;
+
+        let fields = &lines[0]["fields"];
+        assert_eq!(fields["status"], StatusCode::NOT_FOUND.as_u16());
+        assert_eq!(fields["path"], "/missing");
+        assert!(fields.get("request_id").is_none(), "{fields}");
+    }
+}
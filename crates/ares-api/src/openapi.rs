@@ -10,41 +10,103 @@ use utoipa::OpenApi;
     paths(
         crate::routes::scrape,
         crate::routes::create_job,
+        crate::routes::create_jobs_from_sitemap,
         crate::routes::list_jobs,
+        crate::routes::pending_job_count,
+        crate::routes::running_jobs_by_worker,
         crate::routes::get_job,
         crate::routes::cancel_job,
+        crate::routes::bulk_cancel_jobs,
+        crate::routes::delete_jobs,
         crate::routes::retry_job,
+        crate::routes::update_job_schema,
+        crate::routes::get_job_events,
         crate::routes::get_extractions,
+        crate::routes::get_latest_extraction,
+        crate::routes::get_extraction_snapshot,
+        crate::routes::get_extraction_raw_content,
+        crate::routes::delete_extractions,
+        crate::routes::mark_golden,
+        crate::routes::unmark_golden,
+        crate::routes::verify,
         crate::routes::list_schemas,
+        crate::routes::stats,
+        crate::routes::domain_stats,
         crate::routes::get_schema,
         crate::routes::create_schema,
         crate::routes::update_schema_version,
         crate::routes::delete_schema_version,
+        crate::routes::set_latest_schema_version,
+        crate::routes::validate_schema_sample,
         crate::routes::start_crawl,
         crate::routes::get_crawl_status,
         crate::routes::get_crawl_results,
+        crate::routes::create_api_key,
+        crate::routes::list_api_keys,
+        crate::routes::revoke_api_key,
+        crate::routes::list_circuit_breakers,
+        crate::routes::reset_circuit_breaker,
+        crate::routes::list_audit_log,
+        crate::routes::get_usage,
         crate::routes::health,
+        crate::routes::livez,
+        crate::routes::readyz,
     ),
     components(schemas(
         crate::dto::ScrapeRequest,
         crate::dto::ScrapeResponse,
         crate::dto::CreateJobRequest,
         crate::dto::CreateJobResponse,
+        crate::dto::JobStatusDto,
+        crate::dto::CreateJobsFromSitemapRequest,
+        crate::dto::CreateJobsFromSitemapResponse,
+        crate::dto::UpdateJobSchemaRequest,
+        crate::dto::BulkCancelRequest,
+        crate::dto::BulkCancelResponse,
+        crate::dto::DeleteJobsResponse,
         crate::dto::JobResponse,
+        crate::dto::FetchOptionsRequest,
+        crate::dto::FetchAuthRequest,
+        crate::dto::FetchOptionsResponse,
         crate::dto::JobListResponse,
+        crate::dto::PendingCountResponse,
+        crate::dto::WorkerEventResponse,
+        crate::dto::JobEventsResponse,
         crate::dto::ExtractionResponse,
         crate::dto::ExtractionHistoryResponse,
+        crate::dto::DeleteExtractionsResponse,
         crate::dto::SchemaListResponse,
         crate::dto::SchemaEntryResponse,
+        crate::dto::StatsResponse,
+        crate::dto::DomainStatsResponse,
+        crate::dto::DomainStatsEntryResponse,
         crate::dto::SchemaDetailResponse,
         crate::dto::CreateSchemaRequest,
         crate::dto::CreateSchemaResponse,
         crate::dto::UpdateSchemaRequest,
+        crate::dto::SchemaValidationTestRequest,
+        crate::dto::SchemaValidationTestResponse,
+        crate::dto::VerifyRequest,
+        crate::dto::VerifyResponse,
+        crate::dto::VerifyResultResponse,
         crate::dto::HealthResponse,
+        crate::dto::DependencyHealth,
+        crate::dto::LivenessResponse,
         crate::dto::ErrorResponse,
+        crate::dto::ValidationErrorResponse,
         crate::dto::CrawlRequest,
         crate::dto::CrawlResponse,
         crate::dto::CrawlStatusResponse,
+        crate::dto::CreateApiKeyRequest,
+        crate::dto::CreateApiKeyResponse,
+        crate::dto::ApiKeyResponse,
+        crate::dto::ApiKeyListResponse,
+        crate::dto::CircuitBreakerResponse,
+        crate::dto::CircuitBreakerListResponse,
+        crate::dto::AuditEntryResponse,
+        crate::dto::AuditLogResponse,
+        crate::dto::UsageResponse,
+        crate::dto::BudgetLimitsResponse,
     )),
     tags(
         (name = "scrape", description = "One-shot data extraction"),
@@ -52,6 +114,7 @@ use utoipa::OpenApi;
         (name = "extractions", description = "Extraction history"),
         (name = "schemas", description = "Schema management"),
         (name = "crawl", description = "Recursive crawl management"),
+        (name = "admin", description = "API key and circuit breaker management"),
         (name = "system", description = "Health and system status"),
     ),
     modifiers(&SecurityAddon)
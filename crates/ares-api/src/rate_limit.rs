@@ -0,0 +1,328 @@
+//! Inbound rate limiting keyed on the caller's API key, falling back to peer IP.
+//!
+//! Internal callers typically share a NAT gateway, so a purely per-IP budget
+//! lets one noisy service starve everyone behind the same address. Requests
+//! carrying a valid API key (`Authorization: Bearer`, `X-Api-Key`, or the
+//! `api_key` query parameter) are therefore limited per key (keyed by its
+//! SHA-256 hash, so raw tokens never sit in limiter memory). Anonymous
+//! requests, and requests whose token doesn't authenticate, are limited per
+//! peer IP, so random tokens can't buy fresh budgets.
+
+use std::collections::HashMap;
+use std::net::{IpAddr, Ipv4Addr, SocketAddr};
+use std::num::NonZeroU32;
+use std::sync::Arc;
+use std::time::Duration;
+
+use axum::extract::{ConnectInfo, State};
+use axum::http::{self, HeaderValue, Request, StatusCode};
+use axum::middleware::Next;
+use axum::response::{IntoResponse, Response};
+use governor::clock::{Clock, DefaultClock};
+use governor::{DefaultDirectRateLimiter, DefaultKeyedRateLimiter, Quota};
+
+use ares_core::compute_hash;
+use ares_core::error::AppError;
+
+use crate::auth::{authenticate, request_credential};
+use crate::dto::ErrorResponse;
+use crate::state::AppState;
+
+/// Identity a request is rate-limited under.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum RateLimitKey {
+    /// SHA-256 hex digest of the bearer token.
+    ApiKey(String),
+    /// Peer IP address (unauthenticated requests).
+    Ip(IpAddr),
+}
+
+impl RateLimitKey {
+    /// The key for a request authenticated with `token`.
+    pub fn api_key(token: &str) -> Self {
+        RateLimitKey::ApiKey(compute_hash(token))
+    }
+
+    /// The key for a request without a valid API key: the peer IP from
+    /// axum's `ConnectInfo`.
+    ///
+    /// Requests without connection info (e.g. in-process test harnesses)
+    /// share the `0.0.0.0` bucket.
+    pub fn peer<B>(request: &Request<B>) -> Self {
+        RateLimitKey::Ip(
+            request
+                .extensions()
+                .get::<ConnectInfo<SocketAddr>>()
+                .map(|ci| ci.0.ip())
+                .unwrap_or(IpAddr::V4(Ipv4Addr::UNSPECIFIED)),
+        )
+    }
+}
+
+/// A burst/replenish budget applied to each key in a tier.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RateLimitTier {
+    /// Maximum number of requests allowed in a burst.
+    pub burst_size: u32,
+    /// Interval after which one request of the burst is replenished.
+    pub replenish_period: Duration,
+}
+
+impl RateLimitTier {
+    pub fn new(burst_size: u32, replenish_period: Duration) -> Self {
+        Self {
+            burst_size,
+            replenish_period,
+        }
+    }
+
+    fn quota(&self) -> Result<Quota, AppError> {
+        let burst = NonZeroU32::new(self.burst_size)
+            .ok_or_else(|| AppError::ConfigError("Rate limit burst size must be >= 1".into()))?;
+        Quota::with_period(self.replenish_period)
+            .map(|q| q.allow_burst(burst))
+            .ok_or_else(|| AppError::ConfigError("Rate limit period must be non-zero".into()))
+    }
+}
+
+/// Rate limit budgets for authenticated callers, anonymous callers, and
+/// individual API keys.
+#[derive(Debug, Clone)]
+pub struct RateLimitConfig {
+    /// Default budget for each distinct bearer token.
+    pub authenticated: RateLimitTier,
+    /// Budget for each peer IP on requests without a bearer token.
+    pub anonymous: RateLimitTier,
+    /// Per-key budgets, keyed by the SHA-256 hex digest of the token.
+    pub key_overrides: HashMap<String, RateLimitTier>,
+}
+
+impl RateLimitConfig {
+    pub fn new(authenticated: RateLimitTier, anonymous: RateLimitTier) -> Self {
+        Self {
+            authenticated,
+            anonymous,
+            key_overrides: HashMap::new(),
+        }
+    }
+
+    /// Give one API key (identified by the SHA-256 hex digest of the token)
+    /// its own budget instead of the authenticated default.
+    pub fn with_key_override(mut self, key_hash: impl Into<String>, tier: RateLimitTier) -> Self {
+        self.key_overrides
+            .insert(key_hash.into().to_ascii_lowercase(), tier);
+        self
+    }
+
+    /// Parse per-key overrides in the form
+    /// `<sha256>=<burst>/<period_secs>,<sha256>=<burst>/<period_secs>`.
+    pub fn parse_key_overrides(spec: &str) -> Result<HashMap<String, RateLimitTier>, AppError> {
+        let mut overrides = HashMap::new();
+        for entry in spec.split(',').map(str::trim).filter(|e| !e.is_empty()) {
+            let invalid = || {
+                AppError::ConfigError(format!(
+                    "Invalid rate limit override '{entry}'. Expected <sha256>=<burst>/<period_secs>"
+                ))
+            };
+            let (hash, budget) = entry.split_once('=').ok_or_else(invalid)?;
+            let (burst, period) = budget.split_once('/').ok_or_else(invalid)?;
+            let hash = hash.trim().to_ascii_lowercase();
+            if hash.len() != 64 || !hash.chars().all(|c| c.is_ascii_hexdigit()) {
+                return Err(invalid());
+            }
+            let burst: u32 = burst.trim().parse().map_err(|_| invalid())?;
+            let period: u64 = period.trim().parse().map_err(|_| invalid())?;
+            overrides.insert(hash, RateLimitTier::new(burst, Duration::from_secs(period)));
+        }
+        Ok(overrides)
+    }
+}
+
+/// Keyed rate limiter state shared by the [`rate_limit`] middleware.
+pub struct RateLimiter {
+    authenticated: DefaultKeyedRateLimiter<String>,
+    anonymous: DefaultKeyedRateLimiter<IpAddr>,
+    overrides: HashMap<String, DefaultDirectRateLimiter>,
+}
+
+impl RateLimiter {
+    pub fn new(config: &RateLimitConfig) -> Result<Self, AppError> {
+        let overrides = config
+            .key_overrides
+            .iter()
+            .map(|(hash, tier)| {
+                Ok((
+                    hash.clone(),
+                    DefaultDirectRateLimiter::direct(tier.quota()?),
+                ))
+            })
+            .collect::<Result<_, AppError>>()?;
+
+        Ok(Self {
+            authenticated: DefaultKeyedRateLimiter::keyed(config.authenticated.quota()?),
+            anonymous: DefaultKeyedRateLimiter::keyed(config.anonymous.quota()?),
+            overrides,
+        })
+    }
+
+    /// Consume one request from the key's budget.
+    ///
+    /// Returns the time to wait before the next request would be allowed
+    /// when the budget is exhausted.
+    pub fn check(&self, key: &RateLimitKey) -> Result<(), Duration> {
+        let outcome = match key {
+            RateLimitKey::ApiKey(hash) => match self.overrides.get(hash) {
+                Some(limiter) => limiter.check(),
+                None => self.authenticated.check_key(hash),
+            },
+            RateLimitKey::Ip(ip) => self.anonymous.check_key(ip),
+        };
+        outcome.map_err(|not_until| not_until.wait_time_from(DefaultClock::default().now()))
+    }
+
+    /// Number of keys currently tracked by the keyed limiters.
+    pub fn len(&self) -> usize {
+        self.authenticated.len() + self.anonymous.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Drop state for keys whose budget has fully replenished.
+    pub fn retain_recent(&self) {
+        self.authenticated.retain_recent();
+        self.anonymous.retain_recent();
+    }
+}
+
+/// Middleware that enforces the [`AppState::rate_limiter`] budget for each
+/// request.
+///
+/// The request's token (see [`request_credential`]) is authenticated first;
+/// only a valid key is limited under its own budget, and its
+/// [`AuthContext`](crate::auth::AuthContext) is passed on so
+/// [`require_api_key`](crate::auth::require_api_key) needn't look it up again.
+/// Everything else is limited per peer IP.
+///
+/// Exhausted budgets return 429 Too Many Requests with a `Retry-After` header
+/// (whole seconds, rounded up) and the standard JSON error body.
+pub async fn rate_limit(
+    State(state): State<Arc<AppState>>,
+    mut request: Request<axum::body::Body>,
+    next: Next,
+) -> Response {
+    let context = match request_credential(&request) {
+        Some((_, token)) => match authenticate(&state, &token).await {
+            Ok(context) => context.map(|context| (RateLimitKey::api_key(&token), context)),
+            Err(e) => {
+                tracing::warn!(error = %e.0, "API key not checked for rate limiting");
+                None
+            }
+        },
+        None => None,
+    };
+    let key = match &context {
+        Some((key, _)) => key.clone(),
+        None => RateLimitKey::peer(&request),
+    };
+
+    if let Err(wait) = state.rate_limiter.check(&key) {
+        let retry_after = wait.as_secs() + u64::from(wait.subsec_nanos() > 0);
+        let retry_after = retry_after.max(1);
+        let body = ErrorResponse {
+            error: "rate_limit_exceeded".to_string(),
+            message: format!("Too many requests. Retry after {retry_after} seconds"),
+        };
+        let mut response = (StatusCode::TOO_MANY_REQUESTS, axum::Json(body)).into_response();
+        response
+            .headers_mut()
+            .insert(http::header::RETRY_AFTER, HeaderValue::from(retry_after));
+        return response;
+    }
+
+    if let Some((_, context)) = context {
+        request.extensions_mut().insert(context);
+    }
+    next.run(request).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tier(burst: u32) -> RateLimitTier {
+        RateLimitTier::new(burst, Duration::from_secs(60))
+    }
+
+    #[test]
+    fn api_key_is_keyed_by_hash() {
+        assert_eq!(
+            RateLimitKey::api_key("secret"),
+            RateLimitKey::ApiKey(compute_hash("secret"))
+        );
+    }
+
+    #[test]
+    fn peer_key_is_the_connecting_ip() {
+        let mut req = Request::builder().uri("/v1/jobs").body(()).unwrap();
+        assert_eq!(
+            RateLimitKey::peer(&req),
+            RateLimitKey::Ip(IpAddr::V4(Ipv4Addr::UNSPECIFIED))
+        );
+        req.extensions_mut()
+            .insert(ConnectInfo(SocketAddr::from(([10, 0, 0, 7], 1234))));
+        assert_eq!(
+            RateLimitKey::peer(&req),
+            RateLimitKey::Ip(IpAddr::from([10, 0, 0, 7]))
+        );
+    }
+
+    #[test]
+    fn keys_have_independent_budgets() {
+        let limiter = RateLimiter::new(&RateLimitConfig::new(tier(1), tier(1))).unwrap();
+        let a = RateLimitKey::ApiKey(compute_hash("a"));
+        let b = RateLimitKey::ApiKey(compute_hash("b"));
+
+        assert!(limiter.check(&a).is_ok());
+        assert!(limiter.check(&a).is_err());
+        assert!(limiter.check(&b).is_ok());
+    }
+
+    #[test]
+    fn key_override_replaces_authenticated_tier() {
+        let hash = compute_hash("big-customer");
+        let config = RateLimitConfig::new(tier(1), tier(1)).with_key_override(&hash, tier(3));
+        let limiter = RateLimiter::new(&config).unwrap();
+        let key = RateLimitKey::ApiKey(hash);
+
+        for _ in 0..3 {
+            assert!(limiter.check(&key).is_ok());
+        }
+        assert!(limiter.check(&key).is_err());
+    }
+
+    #[test]
+    fn parse_key_overrides_accepts_valid_spec() {
+        let hash = compute_hash("k");
+        let overrides = RateLimitConfig::parse_key_overrides(&format!("{hash}=100/2, ")).unwrap();
+        assert_eq!(
+            overrides.get(&hash),
+            Some(&RateLimitTier::new(100, Duration::from_secs(2)))
+        );
+    }
+
+    #[test]
+    fn parse_key_overrides_rejects_malformed_entries() {
+        assert!(RateLimitConfig::parse_key_overrides("abc=1/1").is_err());
+        assert!(RateLimitConfig::parse_key_overrides(&compute_hash("k")).is_err());
+        assert!(
+            RateLimitConfig::parse_key_overrides(&format!("{}=x/1", compute_hash("k"))).is_err()
+        );
+    }
+
+    #[test]
+    fn zero_burst_is_a_config_error() {
+        assert!(RateLimiter::new(&RateLimitConfig::new(tier(0), tier(1))).is_err());
+    }
+}
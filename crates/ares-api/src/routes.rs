@@ -1,54 +1,193 @@
 use std::sync::Arc;
 
 use axum::Router;
-use axum::extract::{Path, Query, State};
-use axum::http::StatusCode;
+use axum::extract::rejection::QueryRejection;
+use axum::extract::{DefaultBodyLimit, Path, Query, State};
+use axum::http::{HeaderMap, StatusCode, header};
 use axum::middleware;
 use axum::response::IntoResponse;
-use axum::routing::{delete, get, post, put};
+use axum::routing::{delete, get, patch, post, put};
 use utoipa::OpenApi;
 use utoipa_swagger_ui::SwaggerUi;
 use uuid::Uuid;
 
-use ares_client::{HtmdCleaner, Provider, ProviderExtractor, ReqwestFetcher};
-use ares_core::job::CreateScrapeJobRequest;
+use ares_client::sitemap::DEFAULT_MAX_SITEMAP_URLS;
+use ares_client::{HtmdCleaner, Provider, ProviderExtractor, ReqwestFetcher, SitemapLoader};
+use ares_core::api_key::ApiKeyScope;
+use ares_core::audit::{AuditAction, schema_summary};
+use ares_core::budget::{BudgetedExtractor, next_reset};
+use ares_core::circuit_breaker::{CircuitBreaker, CircuitBreakingExtractor};
+use ares_core::fetch::{FetchAuth, FetchOptions};
+use ares_core::job::{CreateScrapeJobRequest, JobStatus};
 use ares_core::job_queue::JobQueue;
 use ares_core::models::ScrapeResult;
-use ares_core::traits::Fetcher;
-use ares_core::{NullStore, SchemaResolver, ScrapeService};
+use ares_core::snapshot::SnapshotKind;
+use ares_core::traits::{Cleaner, Extractor, Fetcher, SnapshotStore};
+use ares_core::{NullStore, SchemaResolver, ScrapeServiceBuilder};
 
-use crate::auth::require_api_key;
+use crate::audit::Audit;
+use crate::auth::{RequireScope, require_api_key};
 use crate::dto::{
-    CrawlRequest, CrawlResponse, CrawlResultsResponse, CrawlStatusResponse, CreateJobRequest,
-    CreateJobResponse, CreateSchemaRequest, CreateSchemaResponse, ExtractionHistoryQuery,
-    ExtractionHistoryResponse, ExtractionResponse, HealthResponse, JobListResponse, JobResponse,
-    ListJobsQuery, SchemaDetailResponse, SchemaEntryResponse, SchemaListResponse, ScrapeRequest,
-    ScrapeResponse, UpdateSchemaRequest,
+    ApiKeyListResponse, ApiKeyResponse, AuditEntryResponse, AuditLogQuery, AuditLogResponse,
+    BulkCancelRequest, BulkCancelResponse, CircuitBreakerListResponse, CircuitBreakerResponse,
+    CrawlRequest, CrawlResponse, CrawlResultsResponse, CrawlStatusResponse, CreateApiKeyRequest,
+    CreateApiKeyResponse, CreateJobRequest, CreateJobResponse, CreateJobsFromSitemapRequest,
+    CreateJobsFromSitemapResponse, CreateSchemaRequest, CreateSchemaResponse,
+    DeleteExtractionsQuery, DeleteExtractionsResponse, DeleteJobsQuery, DeleteJobsResponse,
+    DependencyHealth, DomainStatsEntryResponse, DomainStatsQuery, DomainStatsResponse,
+    ExtractionHistoryQuery, ExtractionHistoryResponse, ExtractionResponse, HealthResponse,
+    JobEventsQuery, JobEventsResponse, JobListResponse, JobResponse, LatestExtractionQuery,
+    ListApiKeysQuery, ListJobsQuery, LivenessResponse, PendingCountResponse, SchemaDetailResponse,
+    SchemaEntryResponse, SchemaListResponse, SchemaValidationTestRequest,
+    SchemaValidationTestResponse, ScrapeRequest, ScrapeResponse, SnapshotQuery, StatsResponse,
+    UpdateJobSchemaRequest, UpdateSchemaRequest, UsageResponse, VerifyRequest, VerifyResponse,
+    WorkerEventResponse,
 };
 use crate::error::ApiError;
+use crate::etag::{conditional, weak_etag};
 use crate::openapi::ApiDoc;
 use crate::state::AppState;
 
+/// Request body limits, in bytes, for routes that differ from the server
+/// default.
+#[derive(Debug, Clone, Copy)]
+pub struct BodyLimits {
+    /// Scrapes and schema validation runs, which carry page HTML.
+    pub scrape: usize,
+    /// Schema uploads.
+    pub schema: usize,
+}
+
 /// Build the full router with all routes and middleware.
-pub fn router(state: Arc<AppState>) -> Router {
+pub fn router(state: Arc<AppState>, limits: BodyLimits) -> Router {
+    let scrape_limit = DefaultBodyLimit::max(limits.scrape);
+    let schema_limit = DefaultBodyLimit::max(limits.schema);
     let api = Router::new()
-        .route("/v1/scrape", post(scrape))
-        .route("/v1/jobs", post(create_job))
+        .route(
+            "/v1/scrape",
+            post(scrape)
+                .layer(scrape_limit)
+                .require_scope(ApiKeyScope::Scrape),
+        )
+        .route(
+            "/v1/jobs",
+            post(create_job).require_scope(ApiKeyScope::JobsWrite),
+        )
         .route("/v1/jobs", get(list_jobs))
+        .route(
+            "/v1/jobs",
+            delete(delete_jobs).require_scope(ApiKeyScope::JobsWrite),
+        )
         .route("/v1/jobs/{id}", get(get_job))
-        .route("/v1/jobs/{id}", delete(cancel_job))
-        .route("/v1/jobs/{id}/retry", post(retry_job))
-        .route("/v1/crawl", post(start_crawl))
+        .route(
+            "/v1/jobs/{id}",
+            delete(cancel_job).require_scope(ApiKeyScope::JobsWrite),
+        )
+        .route(
+            "/v1/jobs/from-sitemap",
+            post(create_jobs_from_sitemap).require_scope(ApiKeyScope::JobsWrite),
+        )
+        .route(
+            "/v1/jobs/bulk-cancel",
+            post(bulk_cancel_jobs).require_scope(ApiKeyScope::JobsWrite),
+        )
+        .route(
+            "/v1/jobs/{id}/retry",
+            post(retry_job).require_scope(ApiKeyScope::JobsWrite),
+        )
+        .route(
+            "/v1/jobs/{id}/schema",
+            patch(update_job_schema).require_scope(ApiKeyScope::JobsWrite),
+        )
+        .route("/v1/jobs/{id}/events/history", get(get_job_events))
+        .route("/v1/jobs/running/by-worker", get(running_jobs_by_worker))
+        .route(
+            "/v1/crawl",
+            post(start_crawl).require_scope(ApiKeyScope::JobsWrite),
+        )
         .route("/v1/crawl/{id}", get(get_crawl_status))
         .route("/v1/crawl/{id}/results", get(get_crawl_results))
         .route("/v1/extractions", get(get_extractions))
+        .route("/v1/extractions/latest", get(get_latest_extraction))
+        .route(
+            "/v1/extractions/{id}/snapshot",
+            get(get_extraction_snapshot),
+        )
+        .route(
+            "/v1/extractions/{id}/raw-content",
+            get(get_extraction_raw_content),
+        )
+        .route(
+            "/v1/extractions",
+            delete(delete_extractions).require_scope(ApiKeyScope::Admin),
+        )
+        .route(
+            "/v1/extractions/{id}/golden",
+            post(mark_golden)
+                .delete(unmark_golden)
+                .require_scope(ApiKeyScope::SchemasWrite),
+        )
+        .route(
+            "/v1/verify",
+            post(verify).require_scope(ApiKeyScope::Scrape),
+        )
         .route("/v1/schemas", get(list_schemas))
-        .route("/v1/schemas", post(create_schema))
+        .route("/v1/stats", get(stats))
+        .route("/v1/stats/domains", get(domain_stats))
+        .route(
+            "/v1/schemas",
+            post(create_schema)
+                .layer(schema_limit)
+                .require_scope(ApiKeyScope::SchemasWrite),
+        )
         .route("/v1/schemas/{name}/{version}", get(get_schema))
-        .route("/v1/schemas/{name}/{version}", put(update_schema_version))
         .route(
             "/v1/schemas/{name}/{version}",
-            delete(delete_schema_version),
+            put(update_schema_version)
+                .layer(schema_limit)
+                .require_scope(ApiKeyScope::SchemasWrite),
+        )
+        .route(
+            "/v1/schemas/{name}/{version}",
+            delete(delete_schema_version).require_scope(ApiKeyScope::SchemasWrite),
+        )
+        .route(
+            "/v1/schemas/{name}/{version}/latest",
+            post(set_latest_schema_version).require_scope(ApiKeyScope::SchemasWrite),
+        )
+        .route(
+            "/v1/schemas/{name}/{version}/validate",
+            post(validate_schema_sample)
+                .layer(scrape_limit)
+                .require_scope(ApiKeyScope::Scrape),
+        )
+        .route(
+            "/v1/admin/api-keys",
+            post(create_api_key).require_scope(ApiKeyScope::Admin),
+        )
+        .route(
+            "/v1/admin/api-keys",
+            get(list_api_keys).require_scope(ApiKeyScope::Admin),
+        )
+        .route(
+            "/v1/admin/api-keys/{id}",
+            delete(revoke_api_key).require_scope(ApiKeyScope::Admin),
+        )
+        .route(
+            "/v1/admin/audit",
+            get(list_audit_log).require_scope(ApiKeyScope::Admin),
+        )
+        .route(
+            "/v1/admin/usage",
+            get(get_usage).require_scope(ApiKeyScope::Admin),
+        )
+        .route(
+            "/v1/admin/circuit-breakers",
+            get(list_circuit_breakers).require_scope(ApiKeyScope::Admin),
+        )
+        .route(
+            "/v1/admin/circuit-breakers/{name}/reset",
+            post(reset_circuit_breaker).require_scope(ApiKeyScope::Admin),
         )
         .layer(middleware::from_fn_with_state(
             state.clone(),
@@ -57,6 +196,9 @@ pub fn router(state: Arc<AppState>) -> Router {
 
     let public = Router::new()
         .route("/health", get(health))
+        .route("/livez", get(livez))
+        .route("/readyz", get(readyz))
+        .route("/v1/jobs/pending/count", get(pending_job_count))
         .merge(SwaggerUi::new("/swagger-ui").url("/api-docs/openapi.json", ApiDoc::openapi()));
 
     public.merge(api).with_state(state)
@@ -73,79 +215,189 @@ pub fn router(state: Arc<AppState>) -> Router {
     responses(
         (status = 200, description = "Extraction result", body = ScrapeResponse),
         (status = 400, description = "Bad request", body = crate::dto::ErrorResponse),
+        (status = 422, description = "Invalid JSON Schema (every violation listed), or the site served an anti-bot block (`fetch_blocked`)", body = crate::dto::ValidationErrorResponse),
         (status = 401, description = "Unauthorized"),
+        (status = 413, description = "Request body (including `html`) exceeds the body limit"),
+        (status = 429, description = "Rate limited (by this server or the upstream LLM), or the daily LLM budget is used up (`budget_exceeded`, with `Retry-After`)", body = crate::dto::ErrorResponse),
+        (status = 503, description = "Upstream LLM unavailable, or its circuit breaker is open after repeated failures; retry later", body = crate::dto::ErrorResponse),
     ),
     security(("bearer" = [])),
     tag = "scrape"
 )]
 pub async fn scrape(
     State(state): State<Arc<AppState>>,
-    axum::Json(body): axum::Json<ScrapeRequest>,
+    axum::Json(mut body): axum::Json<ScrapeRequest>,
 ) -> Result<impl IntoResponse, ApiError> {
-    // Resolve LLM config from request body or environment. Native local
-    // inference has no upstream credential, while the route itself remains
-    // protected by the separate ARES_ADMIN_TOKEN middleware.
-    let provider_name = body
-        .provider
-        .clone()
-        .unwrap_or_else(|| std::env::var("ARES_PROVIDER").unwrap_or_else(|_| "openai".to_string()));
-    let provider = Provider::parse(&provider_name).map_err(|_| {
-        ares_core::AppError::InvalidInput(format!(
-            "Invalid provider '{provider_name}': expected 'openai', 'anthropic', or 'local'"
-        ))
-    })?;
-    let api_key = upstream_api_key(provider, std::env::var("ARES_API_KEY").ok())?;
+    let fetch_options = FetchOptions::from(std::mem::take(&mut body.fetch));
+    validate_fetch_options(&state, &fetch_options)?;
+    if body.html.is_some() && fetch_options.affects_fetch() {
+        return Err(ares_core::AppError::InvalidInput(
+            "html can't be combined with use_browser, fetch_timeout_secs, wait_selector, headers, or auth"
+                .to_string(),
+        )
+        .into());
+    }
+    if fetch_options.use_browser && !cfg!(feature = "browser") {
+        return Err(ares_core::AppError::InvalidInput(
+            "use_browser requires a server built with the `browser` feature".to_string(),
+        )
+        .into());
+    }
 
-    let model = body.model.clone().unwrap_or_else(|| {
-        std::env::var("ARES_MODEL").unwrap_or_else(|_| "gpt-4o-mini".to_string())
-    });
+    if body.url.trim().is_empty() {
+        return Err(ares_core::AppError::InvalidInput("url must not be empty".to_string()).into());
+    }
+    if body
+        .html
+        .as_ref()
+        .is_some_and(|html| html.trim().is_empty())
+    {
+        return Err(ares_core::AppError::InvalidInput(
+            "html must not be empty when set".to_string(),
+        )
+        .into());
+    }
 
-    let base_url = body
-        .base_url
-        .clone()
-        .or_else(|| std::env::var("ARES_BASE_URL").ok())
-        .unwrap_or_else(|| provider.default_base_url().to_string());
+    // Caller-supplied HTML never reaches the fetcher, so it skips the browser.
+    let use_browser = (state.browser || fetch_options.use_browser) && body.html.is_none();
+    if body.user_agent.is_some() && !use_browser {
+        return Err(ares_core::AppError::InvalidInput(
+            "user_agent requires use_browser".to_string(),
+        )
+        .into());
+    }
 
     let save = body.save.unwrap_or(true);
+    if let Some(threshold) = body.content_similarity_threshold {
+        if !(1..=64).contains(&threshold) {
+            return Err(ares_core::AppError::InvalidInput(
+                "content_similarity_threshold must be between 1 and 64".to_string(),
+            )
+            .into());
+        }
+        if !save {
+            return Err(ares_core::AppError::InvalidInput(
+                "content_similarity_threshold requires save".to_string(),
+            )
+            .into());
+        }
+    }
+
+    let (extractor, model, base_url) = build_extractor(
+        state.llm_api_key.as_deref(),
+        body.provider.clone(),
+        body.model.clone(),
+        body.base_url.clone(),
+        fetch_options.llm_timeout(),
+    )?;
 
     // Validate schema
     ares_core::validate_schema(&body.schema)?;
 
+    // While this LLM endpoint is failing, reject at once rather than wait
+    // out the timeout on every request.
+    let breaker = state.circuit_breakers.get_or_create(&base_url);
     let cleaner = HtmdCleaner::new();
-    // A missing `anthropic` build feature surfaces as ConfigError from `build`;
-    // that's a client asking for an unsupported provider, so treat it as 400.
-    let extractor = ProviderExtractor::build(provider, &api_key, &model, &base_url, None, None)
-        .map_err(|e| match e {
-            ares_core::AppError::ConfigError(msg) => ares_core::AppError::InvalidInput(msg),
-            other => other,
-        })?;
-
-    // Build fetcher — browser or reqwest, with optional proxy + UA + stealth
-    let result = if state.browser {
-        let fetcher = create_browser_fetcher(&state).await?;
-        run_scrape(fetcher, cleaner, extractor, &state, &body, &model, save).await?
+
+    // Build fetcher — browser or reqwest, with optional proxy + UA + stealth.
+    let _scrape = state.scrapes.track(&body.url);
+    let start = std::time::Instant::now();
+    let result = if use_browser {
+        let fetcher = create_browser_fetcher(&state, body.user_agent.as_deref()).await?;
+        run_scrape(
+            fetcher,
+            cleaner,
+            extractor,
+            breaker,
+            &state,
+            &body,
+            fetch_options,
+            &model,
+            save,
+        )
+        .await
     } else {
         let fetcher = create_reqwest_fetcher(&state)?;
-        run_scrape(fetcher, cleaner, extractor, &state, &body, &model, save).await?
+        run_scrape(
+            fetcher,
+            cleaner,
+            extractor,
+            breaker,
+            &state,
+            &body,
+            fetch_options,
+            &model,
+            save,
+        )
+        .await
     };
+    crate::metrics::record_scrape(start.elapsed(), result.is_ok());
+    crate::metrics::record_events_dropped(state.events.dropped_events());
+    let result = result?;
 
     let response = ScrapeResponse {
         extracted_data: result.extracted_data,
         content_hash: result.content_hash,
+        content_simhash: result.content_simhash,
         data_hash: result.data_hash,
         changed: result.changed,
         extraction_id: result.extraction_id,
+        duplicate_of: result.duplicate_of,
+        detected_language: result.detected_language,
     };
 
     Ok(axum::Json(response))
 }
 
-fn upstream_api_key(provider: Provider, configured: Option<String>) -> Result<String, ApiError> {
+/// Build an extractor from per-request overrides, falling back to the
+/// `ARES_PROVIDER` / `ARES_MODEL` / `ARES_BASE_URL` environment. Cloud
+/// providers authenticate with the server's configured `api_key`. Returns the
+/// extractor together with the resolved model name and base URL.
+fn build_extractor(
+    api_key: Option<&str>,
+    provider: Option<String>,
+    model: Option<String>,
+    base_url: Option<String>,
+    llm_timeout: Option<std::time::Duration>,
+) -> Result<(ProviderExtractor, String, String), ApiError> {
+    // Native local inference has no upstream credential, while the route itself
+    // remains protected by the separate ARES_ADMIN_TOKEN middleware.
+    let provider_name = provider
+        .unwrap_or_else(|| std::env::var("ARES_PROVIDER").unwrap_or_else(|_| "openai".to_string()));
+    let provider = Provider::parse(&provider_name).map_err(|_| {
+        ares_core::AppError::InvalidInput(format!(
+            "Invalid provider '{provider_name}': expected 'openai', 'anthropic', or 'local'"
+        ))
+    })?;
+    let api_key = upstream_api_key(provider, api_key)?;
+
+    let model = model.unwrap_or_else(|| {
+        std::env::var("ARES_MODEL").unwrap_or_else(|_| "gpt-4o-mini".to_string())
+    });
+
+    let base_url = base_url
+        .or_else(|| std::env::var("ARES_BASE_URL").ok())
+        .unwrap_or_else(|| provider.default_base_url().to_string());
+
+    // A missing `anthropic` build feature surfaces as ConfigError from `build`;
+    // that's a client asking for an unsupported provider, so treat it as 400.
+    let extractor =
+        ProviderExtractor::build(provider, &api_key, &model, &base_url, llm_timeout, None)
+            .map_err(|e| match e {
+                ares_core::AppError::ConfigError(msg) => ares_core::AppError::InvalidInput(msg),
+                other => other,
+            })?;
+
+    Ok((extractor, model, base_url))
+}
+
+fn upstream_api_key(provider: Provider, configured: Option<&str>) -> Result<String, ApiError> {
     if provider == Provider::Local {
         return Ok(String::new());
     }
     configured
         .filter(|key| !key.trim().is_empty())
+        .map(str::to_string)
         .ok_or_else(|| {
             ares_core::AppError::ConfigError(
                 "ARES_API_KEY must be set for cloud-provider scrape endpoints".to_string(),
@@ -154,6 +406,82 @@ fn upstream_api_key(provider: Provider, configured: Option<String>) -> Result<St
         })
 }
 
+/// A job's `notify_url` must be an absolute http(s) URL.
+fn validate_notify_url(url: Option<&str>) -> Result<(), ApiError> {
+    let Some(url) = url else {
+        return Ok(());
+    };
+    match url::Url::parse(url) {
+        Ok(parsed) if matches!(parsed.scheme(), "http" | "https") => Ok(()),
+        _ => Err(ares_core::AppError::InvalidInput(
+            "notify_url must be an http or https URL".to_string(),
+        )
+        .into()),
+    }
+}
+
+/// Check per-request fetch options against the server's limits: timeouts
+/// within `1..=max`, parseable selectors, valid header names and values
+/// (including the ones `auth` sends), and `wait_selector` only with
+/// `use_browser`.
+fn validate_fetch_options(state: &AppState, options: &FetchOptions) -> Result<(), ApiError> {
+    let invalid = |msg: String| -> ApiError { ares_core::AppError::InvalidInput(msg).into() };
+
+    for (field, value, max) in [
+        (
+            "fetch_timeout_secs",
+            options.fetch_timeout_secs,
+            state.max_fetch_timeout_secs,
+        ),
+        (
+            "llm_timeout_secs",
+            options.llm_timeout_secs,
+            state.max_llm_timeout_secs,
+        ),
+    ] {
+        if let Some(secs) = value
+            && !(1..=max).contains(&secs)
+        {
+            return Err(invalid(format!("{field} must be between 1 and {max}")));
+        }
+    }
+    for selector in [&options.content_selector, &options.wait_selector]
+        .into_iter()
+        .flatten()
+    {
+        ares_client::cleaner::parse_selector(selector)?;
+    }
+    for (name, value) in &options.headers {
+        if axum::http::HeaderName::from_bytes(name.as_bytes()).is_err() {
+            return Err(invalid(format!("invalid header name '{name}'")));
+        }
+        if axum::http::HeaderValue::from_str(value).is_err() {
+            return Err(invalid(format!("invalid value for header '{name}'")));
+        }
+    }
+    match &options.auth {
+        Some(FetchAuth::Basic { user, .. }) if user.contains(':') => {
+            return Err(invalid("auth user must not contain ':'".to_string()));
+        }
+        Some(FetchAuth::Bearer { token }) if axum::http::HeaderValue::from_str(token).is_err() => {
+            return Err(invalid("invalid auth token".to_string()));
+        }
+        Some(FetchAuth::Header { name, value }) => {
+            if axum::http::HeaderName::from_bytes(name.as_bytes()).is_err() {
+                return Err(invalid(format!("invalid auth header name '{name}'")));
+            }
+            if axum::http::HeaderValue::from_str(value).is_err() {
+                return Err(invalid(format!("invalid value for auth header '{name}'")));
+            }
+        }
+        _ => {}
+    }
+    if options.wait_selector.is_some() && !options.use_browser {
+        return Err(invalid("wait_selector requires use_browser".to_string()));
+    }
+    Ok(())
+}
+
 /// Build a `ReqwestFetcher` with server-level proxy + UA + TLS config.
 fn create_reqwest_fetcher(state: &AppState) -> Result<ReqwestFetcher, ares_core::AppError> {
     let mut fetcher = ReqwestFetcher::new()?.with_tls_backend(state.tls_backend)?;
@@ -166,10 +494,12 @@ fn create_reqwest_fetcher(state: &AppState) -> Result<ReqwestFetcher, ares_core:
     Ok(fetcher)
 }
 
-/// Build a `BrowserFetcher` with server-level proxy + stealth config.
+/// Build a `BrowserFetcher` with server-level proxy + stealth config, and the
+/// request's User-Agent (a preset name or any string), if any.
 #[cfg(feature = "browser")]
 async fn create_browser_fetcher(
     state: &AppState,
+    user_agent: Option<&str>,
 ) -> Result<ares_client::BrowserFetcher, ares_core::AppError> {
     let proxy_url = state
         .proxy_config
@@ -183,11 +513,17 @@ async fn create_browser_fetcher(
     if state.stealth {
         fetcher = fetcher.with_stealth(ares_core::stealth::StealthConfig::full());
     }
+    if let Some(user_agent) = user_agent {
+        fetcher = fetcher.with_user_agent(ares_client::UserAgent::from(user_agent).as_str());
+    }
     Ok(fetcher)
 }
 
 #[cfg(not(feature = "browser"))]
-async fn create_browser_fetcher(_state: &AppState) -> Result<ReqwestFetcher, ares_core::AppError> {
+async fn create_browser_fetcher(
+    _state: &AppState,
+    _user_agent: Option<&str>,
+) -> Result<ReqwestFetcher, ares_core::AppError> {
     Err(ares_core::AppError::ConfigError(
         "ARES_BROWSER=true requires the `browser` feature. \
          Rebuild with: cargo build --features browser"
@@ -196,31 +532,63 @@ async fn create_browser_fetcher(_state: &AppState) -> Result<ReqwestFetcher, are
 }
 
 /// Run the scrape pipeline with any fetcher type.
+#[allow(clippy::too_many_arguments)]
 async fn run_scrape<F: Fetcher>(
     fetcher: F,
     cleaner: HtmdCleaner,
     extractor: ProviderExtractor,
+    breaker: CircuitBreaker,
     state: &AppState,
     body: &ScrapeRequest,
+    fetch_options: FetchOptions,
     model: &str,
     save: bool,
 ) -> Result<ScrapeResult, ares_core::AppError> {
     let provider = extractor.provider_name();
+    let extractor = CircuitBreakingExtractor::new(
+        BudgetedExtractor::new(extractor, state.budget.clone(), model),
+        breaker,
+    );
+    let builder = ScrapeServiceBuilder::new(fetcher, cleaner, extractor)
+        .with_model(model)
+        .with_provider(provider)
+        .with_fetch_options(fetch_options);
     if save {
-        let repo = state.db.extraction_repo();
-        let service =
-            ScrapeService::with_store(fetcher, cleaner, extractor, repo, model.to_string())
-                .with_provider(provider);
-        service
-            .scrape(&body.url, &body.schema, &body.schema_name)
-            .await
+        let service = builder
+            .with_store(state.db.extraction_repo())
+            .with_publisher(state.events.clone())
+            .with_snapshots(state.snapshots.clone(), &state.snapshot_kinds)
+            .with_store_content(state.store_content)
+            .with_dedup_window(state.dedup_window)
+            .with_reuse_duplicates(state.dedup_reuse)
+            .with_content_similarity_threshold(body.content_similarity_threshold)
+            .build()?;
+        match &body.html {
+            Some(html) => {
+                service
+                    .scrape_html(html, &body.url, &body.schema, &body.schema_name)
+                    .await
+            }
+            None => {
+                service
+                    .scrape(&body.url, &body.schema, &body.schema_name)
+                    .await
+            }
+        }
     } else {
-        let service =
-            ScrapeService::with_store(fetcher, cleaner, extractor, NullStore, model.to_string())
-                .with_provider(provider);
-        service
-            .scrape(&body.url, &body.schema, &body.schema_name)
-            .await
+        let service = builder.with_store(NullStore).build()?;
+        match &body.html {
+            Some(html) => {
+                service
+                    .scrape_html(html, &body.url, &body.schema, &body.schema_name)
+                    .await
+            }
+            None => {
+                service
+                    .scrape(&body.url, &body.schema, &body.schema_name)
+                    .await
+            }
+        }
     }
 }
 
@@ -234,6 +602,8 @@ async fn run_scrape<F: Fetcher>(
     request_body = CreateJobRequest,
     responses(
         (status = 202, description = "Job created", body = CreateJobResponse),
+        (status = 400, description = "Invalid schema or fetch options", body = crate::dto::ErrorResponse),
+        (status = 422, description = "Invalid JSON Schema (every violation listed)", body = crate::dto::ValidationErrorResponse),
         (status = 401, description = "Unauthorized"),
     ),
     security(("bearer" = [])),
@@ -245,6 +615,9 @@ pub async fn create_job(
 ) -> Result<impl IntoResponse, ApiError> {
     // Validate schema
     ares_core::validate_schema(&body.schema)?;
+    let fetch_options = FetchOptions::from(body.fetch);
+    validate_fetch_options(&state, &fetch_options)?;
+    validate_notify_url(body.notify_url.as_deref())?;
 
     let request = CreateScrapeJobRequest::new(
         body.url,
@@ -252,7 +625,10 @@ pub async fn create_job(
         body.schema,
         body.model,
         body.base_url,
-    );
+    )
+    .with_fetch_options(fetch_options)
+    .with_notify_url(body.notify_url)
+    .with_tags(body.tags.unwrap_or_default());
     let request = match body.max_retries {
         Some(max) => request.with_max_retries(max),
         None => request,
@@ -262,18 +638,112 @@ pub async fn create_job(
 
     let response = CreateJobResponse {
         job_id: job.id,
-        status: job.status.to_string(),
+        status: job.status.into(),
     };
 
     Ok((StatusCode::ACCEPTED, axum::Json(response)))
 }
 
+#[utoipa::path(
+    post,
+    path = "/v1/jobs/from-sitemap",
+    request_body = CreateJobsFromSitemapRequest,
+    responses(
+        (status = 202, description = "One job created per sitemap URL", body = CreateJobsFromSitemapResponse),
+        (status = 400, description = "Invalid schema, filters, or fetch options, or the sitemap could not be loaded", body = crate::dto::ErrorResponse),
+        (status = 422, description = "Invalid JSON Schema (every violation listed)", body = crate::dto::ValidationErrorResponse),
+        (status = 401, description = "Unauthorized"),
+    ),
+    security(("bearer" = [])),
+    tag = "jobs"
+)]
+pub async fn create_jobs_from_sitemap(
+    State(state): State<Arc<AppState>>,
+    axum::Json(body): axum::Json<CreateJobsFromSitemapRequest>,
+) -> Result<impl IntoResponse, ApiError> {
+    ares_core::validate_schema(&body.schema)?;
+    let fetch_options = FetchOptions::from(body.fetch);
+    validate_fetch_options(&state, &fetch_options)?;
+    validate_notify_url(body.notify_url.as_deref())?;
+    let max_urls = body.max_urls.unwrap_or(DEFAULT_MAX_SITEMAP_URLS);
+    if !(1..=DEFAULT_MAX_SITEMAP_URLS).contains(&max_urls) {
+        return Err(ares_core::AppError::InvalidInput(format!(
+            "max_urls must be between 1 and {DEFAULT_MAX_SITEMAP_URLS}"
+        ))
+        .into());
+    }
+
+    let loader = SitemapLoader::new()?
+        .with_filters(&body.include, &body.exclude)?
+        .with_max_urls(max_urls);
+    let mut entries = loader.load(&body.sitemap_url).await.map_err(|e| match e {
+        ares_core::AppError::HttpError(_)
+        | ares_core::AppError::NetworkError(_)
+        | ares_core::AppError::Timeout(_) => ares_core::AppError::InvalidInput(format!(
+            "Failed to load sitemap {}: {e}",
+            body.sitemap_url
+        )),
+        e => e,
+    })?;
+
+    let found = entries.len();
+    if body.skip_unmodified {
+        let urls: Vec<String> = entries.iter().map(|entry| entry.url.clone()).collect();
+        let latest = state
+            .db
+            .extraction_repo()
+            .latest_extracted_at(&urls, &body.schema_name)
+            .await?;
+        entries.retain(|entry| {
+            latest
+                .get(&entry.url)
+                .is_none_or(|&extracted_at| entry.modified_since(extracted_at))
+        });
+    }
+    let skipped = found - entries.len();
+
+    let tags = body.tags.unwrap_or_default();
+    let requests = entries
+        .into_iter()
+        .map(|entry| {
+            let request = CreateScrapeJobRequest::new(
+                entry.url,
+                body.schema_name.clone(),
+                body.schema.clone(),
+                body.model.clone(),
+                body.base_url.clone(),
+            )
+            .with_fetch_options(fetch_options.clone())
+            .with_notify_url(body.notify_url.clone())
+            .with_tags(tags.clone());
+            match body.max_retries {
+                Some(max) => request.with_max_retries(max),
+                None => request,
+            }
+        })
+        .collect();
+    let jobs = state.db.job_repo().create_jobs(requests).await?;
+    tracing::info!(
+        sitemap = %body.sitemap_url,
+        created = jobs.len(),
+        skipped,
+        "Jobs created from sitemap"
+    );
+
+    let response = CreateJobsFromSitemapResponse {
+        job_ids: jobs.into_iter().map(|job| job.id).collect(),
+        skipped,
+    };
+    Ok((StatusCode::ACCEPTED, axum::Json(response)))
+}
+
 #[utoipa::path(
     get,
     path = "/v1/jobs",
     params(ListJobsQuery),
     responses(
         (status = 200, description = "List of jobs", body = JobListResponse),
+        (status = 400, description = "Invalid query parameter, e.g. an unknown status", body = crate::dto::ErrorResponse),
         (status = 401, description = "Unauthorized"),
     ),
     security(("bearer" = [])),
@@ -281,24 +751,34 @@ pub async fn create_job(
 )]
 pub async fn list_jobs(
     State(state): State<Arc<AppState>>,
-    Query(query): Query<ListJobsQuery>,
+    query: Result<Query<ListJobsQuery>, QueryRejection>,
 ) -> Result<impl IntoResponse, ApiError> {
-    let status_filter = query
-        .status
-        .map(|s| {
-            s.parse()
-                .map_err(|e: String| ares_core::error::AppError::Generic(e))
-        })
-        .transpose()?;
+    let Query(query) =
+        query.map_err(|e| ares_core::error::AppError::InvalidInput(e.body_text()))?;
+    let status_filter = query.status.map(JobStatus::from);
 
     let limit = query.limit.unwrap_or(20).min(100);
     let offset = query.offset.unwrap_or(0);
     let jobs = state
         .db
         .job_repo()
-        .list_jobs(status_filter, limit, offset)
+        .list_jobs(
+            status_filter,
+            query.worker_id.as_deref(),
+            query.tag.as_deref(),
+            limit,
+            offset,
+        )
         .await?;
-    let total = state.db.job_repo().count_jobs(status_filter).await? as usize;
+    let total = state
+        .db
+        .job_repo()
+        .count_jobs(
+            status_filter,
+            query.worker_id.as_deref(),
+            query.tag.as_deref(),
+        )
+        .await? as usize;
 
     let response = JobListResponse {
         jobs: jobs.into_iter().map(JobResponse::from).collect(),
@@ -310,6 +790,49 @@ pub async fn list_jobs(
     Ok(axum::Json(response))
 }
 
+/// Number of pending jobs, as a cheap queue-depth probe for autoscalers.
+///
+/// Unauthenticated, like `/health`. Also updates the `ares_jobs_pending` gauge.
+#[utoipa::path(
+    get,
+    path = "/v1/jobs/pending/count",
+    responses(
+        (status = 200, description = "Pending job count", body = PendingCountResponse),
+    ),
+    tag = "jobs"
+)]
+pub async fn pending_job_count(
+    State(state): State<Arc<AppState>>,
+) -> Result<impl IntoResponse, ApiError> {
+    let count = state
+        .db
+        .job_repo()
+        .count_by_status(JobStatus::Pending)
+        .await?;
+    crate::metrics::record_job_count(JobStatus::Pending, count);
+
+    Ok(axum::Json(PendingCountResponse { count }))
+}
+
+/// Running jobs per worker ID, for spreading load across workers. Workers
+/// with nothing running are omitted.
+#[utoipa::path(
+    get,
+    path = "/v1/jobs/running/by-worker",
+    responses(
+        (status = 200, description = "Running job count per worker ID", body = std::collections::HashMap<String, i64>),
+        (status = 401, description = "Unauthorized"),
+    ),
+    security(("bearer" = [])),
+    tag = "jobs"
+)]
+pub async fn running_jobs_by_worker(
+    State(state): State<Arc<AppState>>,
+) -> Result<impl IntoResponse, ApiError> {
+    let counts = state.db.job_repo().count_running_by_worker().await?;
+    Ok(axum::Json(counts))
+}
+
 #[utoipa::path(
     get,
     path = "/v1/jobs/{id}",
@@ -318,6 +841,7 @@ pub async fn list_jobs(
     ),
     responses(
         (status = 200, description = "Job details", body = JobResponse),
+        (status = 304, description = "Not modified since the `If-None-Match` ETag"),
         (status = 404, description = "Not found", body = crate::dto::ErrorResponse),
         (status = 401, description = "Unauthorized"),
     ),
@@ -327,11 +851,23 @@ pub async fn list_jobs(
 pub async fn get_job(
     State(state): State<Arc<AppState>>,
     Path(id): Path<Uuid>,
+    headers: HeaderMap,
 ) -> Result<impl IntoResponse, ApiError> {
     let job = state.db.job_repo().get_job(id).await?;
 
     match job {
-        Some(job) => Ok(axum::Json(JobResponse::from(job)).into_response()),
+        Some(job) => {
+            let etag = weak_etag([
+                job.id.to_string(),
+                job.status.to_string(),
+                job.updated_at.to_rfc3339(),
+            ]);
+            Ok(conditional(
+                &headers,
+                etag,
+                axum::Json(JobResponse::from(job)),
+            ))
+        }
         None => {
             let body = crate::dto::ErrorResponse {
                 error: "not_found".to_string(),
@@ -359,6 +895,7 @@ pub async fn get_job(
 )]
 pub async fn cancel_job(
     State(state): State<Arc<AppState>>,
+    audit: Audit,
     Path(id): Path<Uuid>,
 ) -> Result<impl IntoResponse, ApiError> {
     // Check the job exists first
@@ -371,8 +908,15 @@ pub async fn cancel_job(
             };
             Ok((StatusCode::CONFLICT, axum::Json(body)).into_response())
         }
-        Some(_) => {
+        Some(job) => {
             state.db.job_repo().cancel_job(id).await?;
+            audit
+                .record(
+                    AuditAction::JobCancel,
+                    Some(id.to_string()),
+                    serde_json::json!({ "previous_status": job.status.to_string() }),
+                )
+                .await;
             Ok(StatusCode::NO_CONTENT.into_response())
         }
         None => {
@@ -385,6 +929,90 @@ pub async fn cancel_job(
     }
 }
 
+/// Upper bound on job IDs per bulk-cancel request.
+const MAX_BULK_CANCEL: usize = 1000;
+
+#[utoipa::path(
+    post,
+    path = "/v1/jobs/bulk-cancel",
+    request_body = BulkCancelRequest,
+    responses(
+        (status = 200, description = "Jobs cancelled", body = BulkCancelResponse),
+        (status = 400, description = "Bad request", body = crate::dto::ErrorResponse),
+        (status = 401, description = "Unauthorized"),
+    ),
+    security(("bearer" = [])),
+    tag = "jobs"
+)]
+pub async fn bulk_cancel_jobs(
+    State(state): State<Arc<AppState>>,
+    audit: Audit,
+    axum::Json(body): axum::Json<BulkCancelRequest>,
+) -> Result<impl IntoResponse, ApiError> {
+    if body.job_ids.len() > MAX_BULK_CANCEL {
+        return Err(ares_core::AppError::InvalidInput(format!(
+            "At most {MAX_BULK_CANCEL} jobs can be cancelled per request, got {}",
+            body.job_ids.len()
+        ))
+        .into());
+    }
+
+    let cancelled = state.db.job_repo().bulk_cancel(&body.job_ids).await?;
+    tracing::info!(
+        requested = body.job_ids.len(),
+        cancelled,
+        "Jobs bulk-cancelled"
+    );
+    audit
+        .record(
+            AuditAction::JobBulkCancel,
+            None,
+            serde_json::json!({ "requested": body.job_ids.len(), "cancelled": cancelled }),
+        )
+        .await;
+
+    Ok(axum::Json(BulkCancelResponse { cancelled }))
+}
+
+/// Delete the finished jobs with a tag, e.g. every job of one batch run.
+/// Pending and running jobs, and jobs whose crawl children are unfinished,
+/// are kept.
+#[utoipa::path(
+    delete,
+    path = "/v1/jobs",
+    params(DeleteJobsQuery),
+    responses(
+        (status = 200, description = "Jobs deleted", body = DeleteJobsResponse),
+        (status = 400, description = "Missing or empty `tag`", body = crate::dto::ErrorResponse),
+        (status = 401, description = "Unauthorized"),
+    ),
+    security(("bearer" = [])),
+    tag = "jobs"
+)]
+pub async fn delete_jobs(
+    State(state): State<Arc<AppState>>,
+    audit: Audit,
+    query: Result<Query<DeleteJobsQuery>, QueryRejection>,
+) -> Result<impl IntoResponse, ApiError> {
+    let Query(query) =
+        query.map_err(|e| ares_core::error::AppError::InvalidInput(e.body_text()))?;
+    if query.tag.is_empty() {
+        return Err(ares_core::AppError::InvalidInput("`tag` must not be empty".into()).into());
+    }
+
+    let deleted = state.db.job_repo().delete_jobs_by_tag(&query.tag).await?;
+    tracing::info!(tag = %query.tag, deleted, "Jobs deleted by tag");
+    audit
+        .record(
+            AuditAction::JobPrune,
+            None,
+            serde_json::json!({ "tag": query.tag, "deleted": deleted }),
+        )
+        .await;
+
+    Ok(axum::Json(DeleteJobsResponse { deleted }))
+}
+
 #[utoipa::path(
     post,
     path = "/v1/jobs/{id}/retry",
@@ -402,13 +1030,23 @@ pub async fn cancel_job(
 )]
 pub async fn retry_job(
     State(state): State<Arc<AppState>>,
+    audit: Audit,
     Path(id): Path<Uuid>,
 ) -> Result<impl IntoResponse, ApiError> {
     // Attempt the atomic retry first to avoid TOCTOU races.
     let retried = state.db.job_repo().retry_job(id).await?;
 
     match retried {
-        Some(job) => Ok(axum::Json(JobResponse::from(job)).into_response()),
+        Some(job) => {
+            audit
+                .record(
+                    AuditAction::JobRetry,
+                    Some(id.to_string()),
+                    serde_json::json!({ "retry_count": job.retry_count }),
+                )
+                .await;
+            Ok(axum::Json(JobResponse::from(job)).into_response())
+        }
         None => {
             // No row updated: either the job doesn't exist or isn't retryable.
             // Follow-up read to distinguish 404 vs 409.
@@ -433,12 +1071,107 @@ pub async fn retry_job(
     }
 }
 
+#[utoipa::path(
+    patch,
+    path = "/v1/jobs/{id}/schema",
+    params(
+        ("id" = Uuid, Path, description = "Job ID")
+    ),
+    request_body = UpdateJobSchemaRequest,
+    responses(
+        (status = 200, description = "Schema replaced", body = JobResponse),
+        (status = 404, description = "Not found", body = crate::dto::ErrorResponse),
+        (status = 409, description = "Job is no longer pending", body = crate::dto::ErrorResponse),
+        (status = 422, description = "Invalid JSON Schema (every violation listed)", body = crate::dto::ValidationErrorResponse),
+        (status = 401, description = "Unauthorized"),
+    ),
+    security(("bearer" = [])),
+    tag = "jobs"
+)]
+pub async fn update_job_schema(
+    State(state): State<Arc<AppState>>,
+    audit: Audit,
+    Path(id): Path<Uuid>,
+    axum::Json(body): axum::Json<UpdateJobSchemaRequest>,
+) -> Result<impl IntoResponse, ApiError> {
+    ares_core::validate_schema(&body.schema)?;
+
+    let repo = state.db.job_repo();
+    let summary = serde_json::json!({ "schema": schema_summary(&body.schema) });
+    // Update first to avoid racing a worker's claim; a conflict on a job
+    // that doesn't exist is a 404.
+    if let Err(e) = repo.update_job_schema(id, body.schema).await {
+        if matches!(e, ares_core::AppError::Conflict(_)) && repo.get_job(id).await?.is_none() {
+            let body = crate::dto::ErrorResponse {
+                error: "not_found".to_string(),
+                message: format!("Job not found: {id}"),
+            };
+            return Ok((StatusCode::NOT_FOUND, axum::Json(body)).into_response());
+        }
+        return Err(e.into());
+    }
+    tracing::info!(%id, "Job schema updated");
+    audit
+        .record(AuditAction::JobUpdateSchema, Some(id.to_string()), summary)
+        .await;
+
+    let job = repo.get_job(id).await?.ok_or_else(|| {
+        ares_core::AppError::Generic(format!("Job {id} was deleted during the update"))
+    })?;
+    Ok(axum::Json(JobResponse::from(job)).into_response())
+}
+
+/// Upper bound on events returned per job.
+const MAX_JOB_EVENTS: usize = 500;
+
+#[utoipa::path(
+    get,
+    path = "/v1/jobs/{id}/events/history",
+    params(
+        ("id" = Uuid, Path, description = "Job ID"),
+        JobEventsQuery
+    ),
+    responses(
+        (status = 200, description = "Recorded worker events for the job, oldest first", body = JobEventsResponse),
+        (status = 404, description = "Not found", body = crate::dto::ErrorResponse),
+        (status = 401, description = "Unauthorized"),
+    ),
+    security(("bearer" = [])),
+    tag = "jobs"
+)]
+pub async fn get_job_events(
+    State(state): State<Arc<AppState>>,
+    Path(id): Path<Uuid>,
+    Query(query): Query<JobEventsQuery>,
+) -> Result<impl IntoResponse, ApiError> {
+    if state.db.job_repo().get_job(id).await?.is_none() {
+        let body = crate::dto::ErrorResponse {
+            error: "not_found".to_string(),
+            message: format!("Job not found: {id}"),
+        };
+        return Ok((StatusCode::NOT_FOUND, axum::Json(body)).into_response());
+    }
+
+    let limit = query.limit.unwrap_or(50).min(MAX_JOB_EVENTS);
+    let events = state
+        .db
+        .worker_event_repo()
+        .list_for_job(id, limit)
+        .await?
+        .into_iter()
+        .map(WorkerEventResponse::from)
+        .collect();
+
+    Ok(axum::Json(JobEventsResponse { events }).into_response())
+}
+
 #[utoipa::path(
     get,
     path = "/v1/extractions",
     params(ExtractionHistoryQuery),
     responses(
         (status = 200, description = "Extraction history", body = ExtractionHistoryResponse),
+        (status = 304, description = "Not modified since the `If-None-Match` ETag"),
         (status = 401, description = "Unauthorized"),
     ),
     security(("bearer" = [])),
@@ -447,65 +1180,509 @@ pub async fn retry_job(
 pub async fn get_extractions(
     State(state): State<Arc<AppState>>,
     Query(query): Query<ExtractionHistoryQuery>,
+    headers: HeaderMap,
 ) -> Result<impl IntoResponse, ApiError> {
     let limit = query.limit.unwrap_or(10).min(100);
     let offset = query.offset.unwrap_or(0);
-    let extractions = state
+    let entries = state
         .db
         .extraction_repo()
-        .get_history(&query.url, &query.schema_name, limit, offset)
+        .get_history_entries(
+            &query.url,
+            &query.schema_name,
+            query.language.as_deref(),
+            limit,
+            offset,
+        )
         .await?;
     let total = state
         .db
         .extraction_repo()
-        .count_history(&query.url, &query.schema_name)
+        .count_history(&query.url, &query.schema_name, query.language.as_deref())
         .await? as usize;
 
+    // Each entry's ID, data hash, golden flag and scrub time (in page
+    // order), plus what it was compared with, identify the page. Scrubbing
+    // keeps the data hash, so its time has to be part of the tag.
+    let etag = weak_etag(
+        [
+            query.url.clone(),
+            query.schema_name.clone(),
+            query.language.clone().unwrap_or_default(),
+            format!("{limit}/{offset}/{total}"),
+        ]
+        .into_iter()
+        .chain(entries.iter().map(|e| {
+            format!(
+                "{}:{}:{}:{}:{}:{}",
+                e.extraction.id,
+                e.extraction.data_hash,
+                e.previous_extraction_id
+                    .map(|id| id.to_string())
+                    .unwrap_or_default(),
+                e.changed,
+                e.extraction.golden,
+                e.extraction
+                    .scrubbed_at
+                    .map(|at| at.to_rfc3339())
+                    .unwrap_or_default()
+            )
+        })),
+    );
+
     let response = ExtractionHistoryResponse {
-        extractions: extractions
-            .into_iter()
-            .map(ExtractionResponse::from)
-            .collect(),
+        extractions: entries.into_iter().map(ExtractionResponse::from).collect(),
         total,
         limit,
         offset,
     };
 
+    Ok(conditional(&headers, etag, axum::Json(response)))
+}
+
+#[utoipa::path(
+    get,
+    path = "/v1/extractions/latest",
+    params(LatestExtractionQuery),
+    responses(
+        (status = 200, description = "The most recent extraction of the URL with the schema", body = ExtractionResponse),
+        (status = 404, description = "The URL was never extracted with the schema", body = crate::dto::ErrorResponse),
+        (status = 401, description = "Unauthorized"),
+    ),
+    security(("bearer" = [])),
+    tag = "extractions"
+)]
+pub async fn get_latest_extraction(
+    State(state): State<Arc<AppState>>,
+    Query(query): Query<LatestExtractionQuery>,
+) -> Result<impl IntoResponse, ApiError> {
+    let latest = state
+        .db
+        .extraction_repo()
+        .get_latest(&query.url, &query.schema_name)
+        .await?;
+
+    match latest {
+        Some(extraction) => Ok(axum::Json(ExtractionResponse::from(extraction)).into_response()),
+        None => {
+            let body = crate::dto::ErrorResponse {
+                error: "not_found".to_string(),
+                message: format!(
+                    "No extractions found for url={} schema={}",
+                    query.url, query.schema_name
+                ),
+            };
+            Ok((StatusCode::NOT_FOUND, axum::Json(body)).into_response())
+        }
+    }
+}
+
+#[utoipa::path(
+    get,
+    path = "/v1/extractions/{id}/snapshot",
+    params(
+        ("id" = Uuid, Path, description = "Extraction ID"),
+        SnapshotQuery,
+    ),
+    responses(
+        (status = 200, description = "The archived HTML or Markdown the extraction was made from", content_type = "text/plain"),
+        (status = 404, description = "No such extraction, or no snapshot of that kind", body = crate::dto::ErrorResponse),
+        (status = 401, description = "Unauthorized"),
+    ),
+    security(("bearer" = [])),
+    tag = "extractions"
+)]
+pub async fn get_extraction_snapshot(
+    State(state): State<Arc<AppState>>,
+    Path(id): Path<Uuid>,
+    Query(query): Query<SnapshotQuery>,
+) -> Result<impl IntoResponse, ApiError> {
+    let extraction = state.db.extraction_repo().get(id).await?;
+    let key = extraction.as_ref().and_then(|e| e.snapshot_key(query.kind));
+    let content = match key {
+        Some(key) => state.snapshots.get(key).await?,
+        None => None,
+    };
+
+    match content {
+        Some(content) => {
+            Ok(([(header::CONTENT_TYPE, query.kind.content_type())], content).into_response())
+        }
+        None => {
+            let body = crate::dto::ErrorResponse {
+                error: "not_found".to_string(),
+                message: match extraction {
+                    Some(_) => format!("No {} snapshot for extraction {id}", query.kind),
+                    None => format!("Extraction not found: {id}"),
+                },
+            };
+            Ok((StatusCode::NOT_FOUND, axum::Json(body)).into_response())
+        }
+    }
+}
+
+#[utoipa::path(
+    get,
+    path = "/v1/extractions/{id}/raw-content",
+    params(("id" = Uuid, Path, description = "Extraction ID")),
+    responses(
+        (status = 200, description = "The cleaned Markdown the extraction was made from", content_type = "text/markdown"),
+        (status = 404, description = "No such extraction, or its content was not stored", body = crate::dto::ErrorResponse),
+        (status = 401, description = "Unauthorized"),
+    ),
+    security(("bearer" = [])),
+    tag = "extractions"
+)]
+pub async fn get_extraction_raw_content(
+    State(state): State<Arc<AppState>>,
+    Path(id): Path<Uuid>,
+) -> Result<impl IntoResponse, ApiError> {
+    let repo = state.db.extraction_repo();
+    if let Some(content) = repo.get_content(id).await? {
+        return Ok((
+            [(header::CONTENT_TYPE, SnapshotKind::Markdown.content_type())],
+            content,
+        )
+            .into_response());
+    }
+
+    let message = match repo.get(id).await? {
+        Some(_) => format!("No stored content for extraction {id} (see ARES_STORE_CONTENT)"),
+        None => format!("Extraction not found: {id}"),
+    };
+    let body = crate::dto::ErrorResponse {
+        error: "not_found".to_string(),
+        message,
+    };
+    Ok((StatusCode::NOT_FOUND, axum::Json(body)).into_response())
+}
+
+#[utoipa::path(
+    delete,
+    path = "/v1/extractions",
+    params(DeleteExtractionsQuery),
+    responses(
+        (status = 200, description = "Extractions deleted", body = DeleteExtractionsResponse),
+        (status = 400, description = "Not exactly one of `url`, `schema_name`, and `expired` given", body = crate::dto::ErrorResponse),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Missing admin scope", body = crate::dto::ErrorResponse),
+    ),
+    security(("bearer" = [])),
+    tag = "extractions"
+)]
+pub async fn delete_extractions(
+    State(state): State<Arc<AppState>>,
+    audit: Audit,
+    Query(query): Query<DeleteExtractionsQuery>,
+) -> Result<impl IntoResponse, ApiError> {
+    let repo = state.db.extraction_repo();
+    let (deleted, scrubbed, summary) = match (query.url, query.schema_name, query.expired) {
+        (Some(url), None, false) => {
+            let report = repo.delete_by_url(&url).await?;
+            delete_snapshots(&state, &report.released_snapshots).await?;
+            let deleted = report.deleted;
+            tracing::info!(%url, deleted, "Extractions deleted by URL");
+            (
+                deleted,
+                0,
+                serde_json::json!({ "url": url, "deleted": deleted }),
+            )
+        }
+        (None, Some(schema_name), false) => {
+            let report = repo.delete_by_schema(&schema_name).await?;
+            delete_snapshots(&state, &report.released_snapshots).await?;
+            let deleted = report.deleted;
+            tracing::info!(%schema_name, deleted, "Extractions deleted by schema");
+            (
+                deleted,
+                0,
+                serde_json::json!({ "schema_name": schema_name, "deleted": deleted }),
+            )
+        }
+        (None, None, true) => {
+            let report = repo.apply_retention(chrono::Utc::now()).await?;
+            delete_snapshots(&state, &report.released_snapshots).await?;
+            tracing::info!(
+                deleted = report.deleted,
+                scrubbed = report.scrubbed,
+                snapshots = report.released_snapshots.len(),
+                "Expired extractions pruned"
+            );
+            (
+                report.deleted,
+                report.scrubbed,
+                serde_json::json!({
+                    "expired": true,
+                    "deleted": report.deleted,
+                    "scrubbed": report.scrubbed,
+                }),
+            )
+        }
+        _ => {
+            return Err(ares_core::AppError::InvalidInput(
+                "Exactly one of `url`, `schema_name`, or `expired=true` is required".into(),
+            )
+            .into());
+        }
+    };
+    audit
+        .record(AuditAction::ExtractionPrune, None, summary)
+        .await;
+    Ok(axum::Json(DeleteExtractionsResponse { deleted, scrubbed }))
+}
+
+/// Delete snapshot objects released by deleted or expired extractions, plus
+/// any whose deletion failed before. The rows no longer point at them, so
+/// failures are recorded for the next cleanup to retry rather than failing
+/// the request.
+async fn delete_snapshots(state: &AppState, keys: &[String]) -> Result<(), ApiError> {
+    if !state.snapshots.is_enabled() {
+        return Ok(());
+    }
+    let repo = state.db.extraction_repo();
+    let mut keys = keys.to_vec();
+    keys.extend(repo.take_failed_snapshot_deletions().await?);
+    keys.sort();
+    keys.dedup();
+
+    let mut failed = Vec::new();
+    for key in keys {
+        if let Err(e) = state.snapshots.delete(&key).await {
+            tracing::warn!(%key, error = %e, "Snapshot not deleted; will retry");
+            failed.push(key);
+        }
+    }
+    repo.record_failed_snapshot_deletions(&failed).await?;
+    Ok(())
+}
+
+#[utoipa::path(
+    post,
+    path = "/v1/extractions/{id}/golden",
+    params(("id" = Uuid, Path, description = "Extraction ID")),
+    responses(
+        (status = 200, description = "Extraction marked golden", body = ExtractionResponse),
+        (status = 404, description = "Extraction not found", body = crate::dto::ErrorResponse),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Missing schemas:write scope", body = crate::dto::ErrorResponse),
+    ),
+    security(("bearer" = [])),
+    tag = "extractions"
+)]
+pub async fn mark_golden(
+    State(state): State<Arc<AppState>>,
+    audit: Audit,
+    Path(id): Path<Uuid>,
+) -> Result<impl IntoResponse, ApiError> {
+    set_golden(&state, audit, id, true).await
+}
+
+#[utoipa::path(
+    delete,
+    path = "/v1/extractions/{id}/golden",
+    params(("id" = Uuid, Path, description = "Extraction ID")),
+    responses(
+        (status = 200, description = "Golden mark cleared", body = ExtractionResponse),
+        (status = 404, description = "Extraction not found", body = crate::dto::ErrorResponse),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Missing schemas:write scope", body = crate::dto::ErrorResponse),
+    ),
+    security(("bearer" = [])),
+    tag = "extractions"
+)]
+pub async fn unmark_golden(
+    State(state): State<Arc<AppState>>,
+    audit: Audit,
+    Path(id): Path<Uuid>,
+) -> Result<impl IntoResponse, ApiError> {
+    set_golden(&state, audit, id, false).await
+}
+
+async fn set_golden(
+    state: &AppState,
+    audit: Audit,
+    id: Uuid,
+    golden: bool,
+) -> Result<axum::response::Response, ApiError> {
+    let Some(extraction) = state.db.extraction_repo().set_golden(id, golden).await? else {
+        let body = crate::dto::ErrorResponse {
+            error: "not_found".to_string(),
+            message: format!("Extraction not found: {id}"),
+        };
+        return Ok((StatusCode::NOT_FOUND, axum::Json(body)).into_response());
+    };
+
+    tracing::info!(%id, url = %extraction.url, golden, "Extraction golden mark set");
+    audit
+        .record(
+            AuditAction::ExtractionGolden,
+            Some(id.to_string()),
+            serde_json::json!({
+                "url": extraction.url,
+                "schema_name": extraction.schema_name,
+                "golden": golden,
+            }),
+        )
+        .await;
+    Ok(axum::Json(ExtractionResponse::from(extraction)).into_response())
+}
+
+#[utoipa::path(
+    post,
+    path = "/v1/verify",
+    request_body = VerifyRequest,
+    responses(
+        (status = 200, description = "Per-URL results; `passed` is false if any golden page changed or failed", body = VerifyResponse),
+        (status = 400, description = "Invalid provider, or the schema has more golden pages than ARES_VERIFY_MAX_GOLDENS", body = crate::dto::ErrorResponse),
+        (status = 404, description = "Schema not found", body = crate::dto::ErrorResponse),
+        (status = 401, description = "Unauthorized"),
+        (status = 429, description = "The daily LLM budget is used up", body = crate::dto::ErrorResponse),
+        (status = 503, description = "Upstream LLM unavailable, or its circuit breaker is open after repeated failures; retry later", body = crate::dto::ErrorResponse),
+    ),
+    security(("bearer" = [])),
+    tag = "extractions"
+)]
+pub async fn verify(
+    State(state): State<Arc<AppState>>,
+    axum::Json(body): axum::Json<VerifyRequest>,
+) -> Result<impl IntoResponse, ApiError> {
+    let resolved = match SchemaResolver::new(&state.schemas_dir).resolve(&body.schema) {
+        Ok(resolved) => resolved,
+        Err(_) => {
+            let body = crate::dto::ErrorResponse {
+                error: "not_found".to_string(),
+                message: format!("Schema not found: {}", body.schema),
+            };
+            return Ok((StatusCode::NOT_FOUND, axum::Json(body)).into_response());
+        }
+    };
+
+    let (extractor, model, base_url) = build_extractor(
+        state.llm_api_key.as_deref(),
+        body.provider,
+        body.model,
+        body.base_url,
+        None,
+    )?;
+    let provider = extractor.provider_name();
+    let extractor = CircuitBreakingExtractor::new(
+        BudgetedExtractor::new(extractor, state.budget.clone(), &model),
+        state.circuit_breakers.get_or_create(&base_url),
+    );
+    let service = ScrapeServiceBuilder::new(
+        create_reqwest_fetcher(&state)?,
+        HtmdCleaner::new(),
+        extractor,
+    )
+    .with_model(&model)
+    .with_provider(provider)
+    .with_store(NullStore)
+    .build()?;
+
+    let goldens = state
+        .db
+        .extraction_repo()
+        .list_golden(&resolved.name)
+        .await?;
+    if goldens.len() > state.max_verify_goldens {
+        return Err(ares_core::AppError::InvalidInput(format!(
+            "Schema {} has {} golden pages; one request verifies at most {} \
+             (ARES_VERIFY_MAX_GOLDENS). Run `ares verify` for larger sets",
+            resolved.name,
+            goldens.len(),
+            state.max_verify_goldens
+        ))
+        .into());
+    }
+    tracing::info!(schema = %resolved.name, goldens = goldens.len(), %model, "Verifying golden extractions");
+
+    let report = ares_core::verify_goldens(
+        &service,
+        &state.snapshots,
+        &goldens,
+        &resolved.schema,
+        &resolved.name,
+    )
+    .await;
+    Ok(axum::Json(VerifyResponse::from(report)).into_response())
+}
+
+// ---------------------------------------------------------------------------
+// Schemas
+// ---------------------------------------------------------------------------
+
+#[utoipa::path(
+    get,
+    path = "/v1/schemas",
+    responses(
+        (status = 200, description = "List of schemas", body = SchemaListResponse),
+        (status = 401, description = "Unauthorized"),
+    ),
+    security(("bearer" = [])),
+    tag = "schemas"
+)]
+pub async fn list_schemas(
+    State(state): State<Arc<AppState>>,
+) -> Result<impl IntoResponse, ApiError> {
+    let resolver = SchemaResolver::new(&state.schemas_dir);
+    let entries = resolver.list_schemas()?;
+
+    let response = SchemaListResponse {
+        schemas: entries.into_iter().map(SchemaEntryResponse::from).collect(),
+    };
+
     Ok(axum::Json(response))
 }
 
-// ---------------------------------------------------------------------------
-// Schemas
-// ---------------------------------------------------------------------------
-
 #[utoipa::path(
     get,
-    path = "/v1/schemas",
+    path = "/v1/stats",
+    responses(
+        (status = 200, description = "Schema and version counts", body = StatsResponse),
+        (status = 401, description = "Unauthorized"),
+    ),
+    security(("bearer" = [])),
+    tag = "system"
+)]
+pub async fn stats(State(state): State<Arc<AppState>>) -> Result<impl IntoResponse, ApiError> {
+    let resolver = SchemaResolver::new(&state.schemas_dir);
+    Ok(axum::Json(StatsResponse {
+        schemas: resolver.schema_count()?,
+        schema_versions: resolver.total_version_count()?,
+    }))
+}
+
+#[utoipa::path(
+    get,
+    path = "/v1/stats/domains",
+    params(DomainStatsQuery),
     responses(
-        (status = 200, description = "List of schemas", body = SchemaListResponse),
+        (status = 200, description = "Per-domain job outcomes, most failed jobs first", body = DomainStatsResponse),
+        (status = 400, description = "Invalid query parameter", body = crate::dto::ErrorResponse),
         (status = 401, description = "Unauthorized"),
     ),
     security(("bearer" = [])),
-    tag = "schemas"
+    tag = "system"
 )]
-pub async fn list_schemas(
+pub async fn domain_stats(
     State(state): State<Arc<AppState>>,
+    query: Result<Query<DomainStatsQuery>, QueryRejection>,
 ) -> Result<impl IntoResponse, ApiError> {
-    let resolver = SchemaResolver::new(&state.schemas_dir);
-    let entries = resolver.list_schemas()?;
+    let Query(query) =
+        query.map_err(|e| ares_core::error::AppError::InvalidInput(e.body_text()))?;
+    let since = query
+        .since
+        .unwrap_or_else(|| chrono::Utc::now() - chrono::Duration::hours(24));
 
-    let response = SchemaListResponse {
-        schemas: entries
+    let domains = state.db.stats_repo().domain_stats(since).await?;
+
+    Ok(axum::Json(DomainStatsResponse {
+        since,
+        domains: domains
             .into_iter()
-            .map(|e| SchemaEntryResponse {
-                name: e.name,
-                latest_version: e.latest_version,
-                versions: e.versions,
-            })
+            .map(DomainStatsEntryResponse::from)
             .collect(),
-    };
-
-    Ok(axum::Json(response))
+    }))
 }
 
 #[utoipa::path(
@@ -513,10 +1690,11 @@ pub async fn list_schemas(
     path = "/v1/schemas/{name}/{version}",
     params(
         ("name" = String, Path, description = "Schema name"),
-        ("version" = String, Path, description = "Schema version"),
+        ("version" = String, Path, description = "Schema version, or `latest`"),
     ),
     responses(
         (status = 200, description = "Schema details", body = SchemaDetailResponse),
+        (status = 304, description = "Not modified since the `If-None-Match` ETag"),
         (status = 404, description = "Not found", body = crate::dto::ErrorResponse),
         (status = 401, description = "Unauthorized"),
     ),
@@ -526,18 +1704,28 @@ pub async fn list_schemas(
 pub async fn get_schema(
     State(state): State<Arc<AppState>>,
     Path((name, version)): Path<(String, String)>,
+    headers: HeaderMap,
 ) -> Result<impl IntoResponse, ApiError> {
     let resolver = SchemaResolver::new(&state.schemas_dir);
     let schema_ref = format!("{name}@{version}");
 
     match resolver.resolve(&schema_ref) {
         Ok(resolved) => {
+            let etag = weak_etag([
+                schema_ref,
+                ares_core::compute_hash(resolved.schema.to_string()),
+            ]);
+            // `latest` is reported as the version it resolved to.
+            let version = resolved
+                .name
+                .rsplit_once('@')
+                .map_or(version, |(_, v)| v.to_string());
             let response = SchemaDetailResponse {
                 name,
                 version,
                 schema: resolved.schema,
             };
-            Ok(axum::Json(response).into_response())
+            Ok(conditional(&headers, etag, axum::Json(response)))
         }
         Err(_) => {
             let body = crate::dto::ErrorResponse {
@@ -556,6 +1744,7 @@ pub async fn get_schema(
     responses(
         (status = 201, description = "Schema created", body = CreateSchemaResponse),
         (status = 400, description = "Bad request", body = crate::dto::ErrorResponse),
+        (status = 422, description = "Invalid JSON Schema (every violation listed)", body = crate::dto::ValidationErrorResponse),
         (status = 401, description = "Unauthorized"),
     ),
     security(("bearer" = [])),
@@ -563,10 +1752,18 @@ pub async fn get_schema(
 )]
 pub async fn create_schema(
     State(state): State<Arc<AppState>>,
+    audit: Audit,
     axum::Json(body): axum::Json<CreateSchemaRequest>,
 ) -> Result<impl IntoResponse, ApiError> {
     let resolver = SchemaResolver::new(&state.schemas_dir);
     resolver.create_schema(&body.name, &body.version, &body.schema)?;
+    audit
+        .record(
+            AuditAction::SchemaCreate,
+            Some(format!("{}@{}", body.name, body.version)),
+            serde_json::json!({ "schema": schema_summary(&body.schema) }),
+        )
+        .await;
 
     let response = CreateSchemaResponse {
         name: body.name,
@@ -588,6 +1785,7 @@ pub async fn create_schema(
         (status = 200, description = "Schema updated", body = SchemaDetailResponse),
         (status = 404, description = "Not found", body = crate::dto::ErrorResponse),
         (status = 400, description = "Bad request", body = crate::dto::ErrorResponse),
+        (status = 422, description = "Invalid JSON Schema (every violation listed)", body = crate::dto::ValidationErrorResponse),
         (status = 401, description = "Unauthorized"),
     ),
     security(("bearer" = [])),
@@ -595,6 +1793,7 @@ pub async fn create_schema(
 )]
 pub async fn update_schema_version(
     State(state): State<Arc<AppState>>,
+    audit: Audit,
     Path((name, version)): Path<(String, String)>,
     axum::Json(body): axum::Json<UpdateSchemaRequest>,
 ) -> Result<impl IntoResponse, ApiError> {
@@ -602,6 +1801,13 @@ pub async fn update_schema_version(
 
     match resolver.update_schema(&name, &version, &body.schema) {
         Ok(()) => {
+            audit
+                .record(
+                    AuditAction::SchemaUpdate,
+                    Some(format!("{name}@{version}")),
+                    serde_json::json!({ "schema": schema_summary(&body.schema) }),
+                )
+                .await;
             let response = SchemaDetailResponse {
                 name,
                 version,
@@ -620,6 +1826,37 @@ pub async fn update_schema_version(
     }
 }
 
+#[utoipa::path(
+    post,
+    path = "/v1/schemas/{name}/{version}/latest",
+    params(
+        ("name" = String, Path, description = "Schema name"),
+        ("version" = String, Path, description = "Existing schema version to mark as latest"),
+    ),
+    responses(
+        (status = 204, description = "Latest version updated"),
+        (status = 404, description = "Not found", body = crate::dto::ErrorResponse),
+        (status = 401, description = "Unauthorized"),
+    ),
+    security(("bearer" = [])),
+    tag = "schemas"
+)]
+pub async fn set_latest_schema_version(
+    State(state): State<Arc<AppState>>,
+    audit: Audit,
+    Path((name, version)): Path<(String, String)>,
+) -> Result<impl IntoResponse, ApiError> {
+    SchemaResolver::new(&state.schemas_dir).set_latest(&name, &version)?;
+    audit
+        .record(
+            AuditAction::SchemaSetLatest,
+            Some(format!("{name}@{version}")),
+            serde_json::json!({}),
+        )
+        .await;
+    Ok(StatusCode::NO_CONTENT)
+}
+
 #[utoipa::path(
     delete,
     path = "/v1/schemas/{name}/{version}",
@@ -637,13 +1874,23 @@ pub async fn update_schema_version(
 )]
 pub async fn delete_schema_version(
     State(state): State<Arc<AppState>>,
+    audit: Audit,
     Path((name, version)): Path<(String, String)>,
 ) -> Result<impl IntoResponse, ApiError> {
     let resolver = SchemaResolver::new(&state.schemas_dir);
     let schema_ref = format!("{name}@{version}");
 
     match resolver.delete_schema(&name, &version) {
-        Ok(()) => Ok(StatusCode::NO_CONTENT.into_response()),
+        Ok(()) => {
+            audit
+                .record(
+                    AuditAction::SchemaDelete,
+                    Some(schema_ref),
+                    serde_json::json!({}),
+                )
+                .await;
+            Ok(StatusCode::NO_CONTENT.into_response())
+        }
         Err(ares_core::AppError::SchemaNotFound { .. }) => {
             let body = crate::dto::ErrorResponse {
                 error: "not_found".to_string(),
@@ -655,6 +1902,68 @@ pub async fn delete_schema_version(
     }
 }
 
+#[utoipa::path(
+    post,
+    path = "/v1/schemas/{name}/{version}/validate",
+    description = "Run the clean → extract → validate pipeline on sample HTML without \
+        fetching or persisting anything. Calls the configured LLM (requires `ARES_API_KEY` \
+        for cloud providers), so each request incurs a real, billable extraction.",
+    params(
+        ("name" = String, Path, description = "Schema name"),
+        ("version" = String, Path, description = "Schema version"),
+    ),
+    request_body = SchemaValidationTestRequest,
+    responses(
+        (status = 200, description = "Validation result", body = SchemaValidationTestResponse),
+        (status = 404, description = "Not found", body = crate::dto::ErrorResponse),
+        (status = 401, description = "Unauthorized"),
+        (status = 503, description = "Upstream LLM unavailable, or its circuit breaker is open after repeated failures; retry later", body = crate::dto::ErrorResponse),
+    ),
+    security(("bearer" = [])),
+    tag = "schemas"
+)]
+pub async fn validate_schema_sample(
+    State(state): State<Arc<AppState>>,
+    Path((name, version)): Path<(String, String)>,
+    axum::Json(body): axum::Json<SchemaValidationTestRequest>,
+) -> Result<impl IntoResponse, ApiError> {
+    let resolver = SchemaResolver::new(&state.schemas_dir);
+    let schema_ref = format!("{name}@{version}");
+
+    let schema = match resolver.resolve(&schema_ref) {
+        Ok(resolved) => resolved.schema,
+        Err(_) => {
+            let body = crate::dto::ErrorResponse {
+                error: "not_found".to_string(),
+                message: format!("Schema not found: {schema_ref}"),
+            };
+            return Ok((StatusCode::NOT_FOUND, axum::Json(body)).into_response());
+        }
+    };
+
+    let (extractor, model, base_url) =
+        build_extractor(state.llm_api_key.as_deref(), None, None, None, None)?;
+    let extractor = CircuitBreakingExtractor::new(
+        BudgetedExtractor::new(extractor, state.budget.clone(), model),
+        state.circuit_breakers.get_or_create(&base_url),
+    );
+
+    tracing::info!(schema = %schema_ref, url = %body.url, "Testing schema against sample HTML");
+
+    let markdown = HtmdCleaner::new().clean(&body.html)?;
+    let extracted = extractor.extract(&markdown, &schema).await?.value;
+    let errors = ares_core::extracted_output_errors(&schema, &extracted)?;
+
+    let response = SchemaValidationTestResponse {
+        markdown,
+        extracted_data: extracted,
+        valid: errors.is_empty(),
+        errors,
+    };
+
+    Ok(axum::Json(response).into_response())
+}
+
 // ---------------------------------------------------------------------------
 // Crawl
 // ---------------------------------------------------------------------------
@@ -665,6 +1974,7 @@ pub async fn delete_schema_version(
     request_body = CrawlRequest,
     responses(
         (status = 202, description = "Crawl started", body = CrawlResponse),
+        (status = 422, description = "Invalid JSON Schema (every violation listed)", body = crate::dto::ValidationErrorResponse),
         (status = 401, description = "Unauthorized"),
     ),
     security(("bearer" = [])),
@@ -799,41 +2109,433 @@ pub async fn get_crawl_results(
     Ok(axum::Json(response))
 }
 
+// ---------------------------------------------------------------------------
+// Admin: API keys
+// ---------------------------------------------------------------------------
+
+#[utoipa::path(
+    post,
+    path = "/v1/admin/api-keys",
+    request_body = CreateApiKeyRequest,
+    responses(
+        (status = 201, description = "API key created (key value shown only once)", body = CreateApiKeyResponse),
+        (status = 400, description = "Bad request", body = crate::dto::ErrorResponse),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Missing admin scope", body = crate::dto::ErrorResponse),
+    ),
+    security(("bearer" = [])),
+    tag = "admin"
+)]
+pub async fn create_api_key(
+    State(state): State<Arc<AppState>>,
+    audit: Audit,
+    axum::Json(body): axum::Json<CreateApiKeyRequest>,
+) -> Result<impl IntoResponse, ApiError> {
+    if body.name.trim().is_empty() {
+        return Err(
+            ares_core::AppError::InvalidInput("API key name must not be empty".into()).into(),
+        );
+    }
+    if body.scopes.is_empty() {
+        return Err(
+            ares_core::AppError::InvalidInput("At least one scope is required".into()).into(),
+        );
+    }
+    let scopes = body
+        .scopes
+        .iter()
+        .map(|s| s.parse::<ApiKeyScope>())
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(ares_core::AppError::InvalidInput)?;
+
+    let (key, value) = state
+        .db
+        .api_key_repo()
+        .create(body.name.trim(), &scopes)
+        .await?;
+
+    tracing::info!(key_id = %key.id, name = %key.name, "API key created");
+    // The key value itself is never recorded.
+    audit
+        .record(
+            AuditAction::ApiKeyCreate,
+            Some(key.id.to_string()),
+            serde_json::json!({ "name": key.name, "scopes": key.scopes }),
+        )
+        .await;
+
+    let response = CreateApiKeyResponse {
+        id: key.id,
+        name: key.name,
+        scopes: key.scopes.iter().map(|s| s.to_string()).collect(),
+        key: value,
+        created_at: key.created_at,
+    };
+
+    Ok((StatusCode::CREATED, axum::Json(response)))
+}
+
+#[utoipa::path(
+    get,
+    path = "/v1/admin/api-keys",
+    params(ListApiKeysQuery),
+    responses(
+        (status = 200, description = "List of API keys", body = ApiKeyListResponse),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Missing admin scope", body = crate::dto::ErrorResponse),
+    ),
+    security(("bearer" = [])),
+    tag = "admin"
+)]
+pub async fn list_api_keys(
+    State(state): State<Arc<AppState>>,
+    Query(query): Query<ListApiKeysQuery>,
+) -> Result<impl IntoResponse, ApiError> {
+    let keys = state
+        .db
+        .api_key_repo()
+        .list(query.include_revoked.unwrap_or(false))
+        .await?;
+
+    let response = ApiKeyListResponse {
+        api_keys: keys.into_iter().map(ApiKeyResponse::from).collect(),
+    };
+
+    Ok(axum::Json(response))
+}
+
+#[utoipa::path(
+    delete,
+    path = "/v1/admin/api-keys/{id}",
+    params(
+        ("id" = Uuid, Path, description = "API key ID")
+    ),
+    responses(
+        (status = 204, description = "API key revoked"),
+        (status = 404, description = "Not found", body = crate::dto::ErrorResponse),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Missing admin scope", body = crate::dto::ErrorResponse),
+    ),
+    security(("bearer" = [])),
+    tag = "admin"
+)]
+pub async fn revoke_api_key(
+    State(state): State<Arc<AppState>>,
+    audit: Audit,
+    Path(id): Path<Uuid>,
+) -> Result<impl IntoResponse, ApiError> {
+    if state.db.api_key_repo().revoke(id).await? {
+        tracing::info!(key_id = %id, "API key revoked");
+        audit
+            .record(
+                AuditAction::ApiKeyRevoke,
+                Some(id.to_string()),
+                serde_json::json!({}),
+            )
+            .await;
+        Ok(StatusCode::NO_CONTENT.into_response())
+    } else {
+        let body = crate::dto::ErrorResponse {
+            error: "not_found".to_string(),
+            message: format!("Active API key not found: {id}"),
+        };
+        Ok((StatusCode::NOT_FOUND, axum::Json(body)).into_response())
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Admin: circuit breakers
+// ---------------------------------------------------------------------------
+
+#[utoipa::path(
+    get,
+    path = "/v1/admin/circuit-breakers",
+    responses(
+        (status = 200, description = "Circuit breakers as last reported by workers", body = CircuitBreakerListResponse),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Missing admin scope", body = crate::dto::ErrorResponse),
+    ),
+    security(("bearer" = [])),
+    tag = "admin"
+)]
+pub async fn list_circuit_breakers(
+    State(state): State<Arc<AppState>>,
+) -> Result<impl IntoResponse, ApiError> {
+    let breakers = state.db.circuit_breaker_repo().list().await?;
+
+    let response = CircuitBreakerListResponse {
+        circuit_breakers: breakers
+            .into_iter()
+            .map(CircuitBreakerResponse::from)
+            .collect(),
+    };
+
+    Ok(axum::Json(response))
+}
+
+#[utoipa::path(
+    post,
+    path = "/v1/admin/circuit-breakers/{name}/reset",
+    params(
+        ("name" = String, Path, description = "Circuit breaker name")
+    ),
+    responses(
+        (status = 200, description = "Breaker closed; workers reset theirs on their next poll", body = CircuitBreakerResponse),
+        (status = 404, description = "Not found", body = crate::dto::ErrorResponse),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Missing admin scope", body = crate::dto::ErrorResponse),
+    ),
+    security(("bearer" = [])),
+    tag = "admin"
+)]
+pub async fn reset_circuit_breaker(
+    State(state): State<Arc<AppState>>,
+    audit: Audit,
+    Path(name): Path<String>,
+) -> Result<impl IntoResponse, ApiError> {
+    match state.db.circuit_breaker_repo().request_reset(&name).await? {
+        Some(record) => {
+            tracing::info!(circuit = %name, "Circuit breaker reset requested");
+            audit
+                .record(
+                    AuditAction::CircuitBreakerReset,
+                    Some(name.clone()),
+                    serde_json::json!({ "previous_state": record.stats.state.to_string() }),
+                )
+                .await;
+            Ok(axum::Json(CircuitBreakerResponse::from(record)).into_response())
+        }
+        None => {
+            let body = crate::dto::ErrorResponse {
+                error: "not_found".to_string(),
+                message: format!("Circuit breaker not found: {name}"),
+            };
+            Ok((StatusCode::NOT_FOUND, axum::Json(body)).into_response())
+        }
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Admin: audit log
+// ---------------------------------------------------------------------------
+
+#[utoipa::path(
+    get,
+    path = "/v1/admin/audit",
+    params(AuditLogQuery),
+    responses(
+        (status = 200, description = "Audit entries, newest first", body = AuditLogResponse),
+        (status = 400, description = "Invalid query parameter, e.g. an unknown action", body = crate::dto::ErrorResponse),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Missing admin scope", body = crate::dto::ErrorResponse),
+    ),
+    security(("bearer" = [])),
+    tag = "admin"
+)]
+pub async fn list_audit_log(
+    State(state): State<Arc<AppState>>,
+    query: Result<Query<AuditLogQuery>, QueryRejection>,
+) -> Result<impl IntoResponse, ApiError> {
+    let Query(query) =
+        query.map_err(|e| ares_core::error::AppError::InvalidInput(e.body_text()))?;
+    let action = query
+        .action
+        .as_deref()
+        .map(str::parse::<AuditAction>)
+        .transpose()
+        .map_err(ares_core::AppError::InvalidInput)?;
+
+    let limit = query.limit.unwrap_or(50).min(500);
+    let offset = query.offset.unwrap_or(0);
+    let repo = state.db.audit_repo();
+    let entries = repo.list(query.since, action, limit, offset).await?;
+    let total = repo.count(query.since, action).await? as usize;
+
+    let response = AuditLogResponse {
+        entries: entries.into_iter().map(AuditEntryResponse::from).collect(),
+        total,
+        limit,
+        offset,
+    };
+
+    Ok(axum::Json(response))
+}
+
+// ---------------------------------------------------------------------------
+// Admin: LLM usage
+// ---------------------------------------------------------------------------
+
+#[utoipa::path(
+    get,
+    path = "/v1/admin/usage",
+    responses(
+        (status = 200, description = "Today's LLM usage and the daily budget", body = UsageResponse),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Missing admin scope", body = crate::dto::ErrorResponse),
+    ),
+    security(("bearer" = [])),
+    tag = "admin"
+)]
+pub async fn get_usage(State(state): State<Arc<AppState>>) -> Result<impl IntoResponse, ApiError> {
+    let now = chrono::Utc::now();
+    let usage = state.db.usage_repo().get(now.date_naive()).await?;
+    Ok(axum::Json(UsageResponse::new(
+        usage,
+        state.budget.limits(),
+        next_reset(now),
+    )))
+}
+
 // ---------------------------------------------------------------------------
 // Health
 // ---------------------------------------------------------------------------
 
+/// Upper bound on each readiness dependency check.
+const HEALTH_PROBE_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(3);
+
+#[utoipa::path(
+    get,
+    path = "/livez",
+    responses(
+        (status = 200, description = "Process is running", body = LivenessResponse),
+    ),
+    tag = "system"
+)]
+pub async fn livez() -> impl IntoResponse {
+    axum::Json(LivenessResponse { status: "alive" })
+}
+
+#[utoipa::path(
+    get,
+    path = "/readyz",
+    responses(
+        (status = 200, description = "Ready (healthy or degraded)", body = HealthResponse),
+        (status = 503, description = "Not ready", body = HealthResponse),
+    ),
+    tag = "system"
+)]
+pub async fn readyz(State(state): State<Arc<AppState>>) -> impl IntoResponse {
+    readiness(&state).await
+}
+
 #[utoipa::path(
     get,
     path = "/health",
     responses(
-        (status = 200, description = "Service is healthy", body = HealthResponse),
+        (status = 200, description = "Service is healthy or degraded", body = HealthResponse),
         (status = 503, description = "Service is unhealthy", body = HealthResponse),
     ),
     tag = "system"
 )]
 pub async fn health(State(state): State<Arc<AppState>>) -> impl IntoResponse {
-    let db_status = match state.db.extraction_repo().health_check().await {
-        Ok(()) => "ok",
-        Err(_) => "error",
-    };
+    readiness(&state).await
+}
+
+/// Check every dependency concurrently. The database and migrations are
+/// critical (503 when failing); the schemas directory and the optional LLM
+/// probe only degrade the status.
+async fn readiness(state: &AppState) -> (StatusCode, axum::Json<HealthResponse>) {
+    let (database, migrations, schemas_dir, llm) = tokio::join!(
+        probe("database", async {
+            state
+                .db
+                .extraction_repo()
+                .health_check()
+                .await
+                .map_err(|e| e.to_string())
+        }),
+        probe("migrations", async {
+            match state.db.migrations_applied().await {
+                Ok(true) => Ok(()),
+                Ok(false) => Err("pending migrations".to_string()),
+                Err(e) => Err(e.to_string()),
+            }
+        }),
+        probe("schemas_dir", async {
+            tokio::fs::read_dir(&state.schemas_dir)
+                .await
+                .map(|_| ())
+                .map_err(|e| format!("{}: {e}", state.schemas_dir.display()))
+        }),
+        async {
+            if state.llm_probe {
+                Some(probe("llm", probe_llm(state.llm_api_key.as_deref())).await)
+            } else {
+                None
+            }
+        },
+    );
 
-    let status = if db_status == "ok" {
-        StatusCode::OK
+    let critical_ok = database.is_ok() && migrations.is_ok();
+    let (code, status) = if !critical_ok {
+        (StatusCode::SERVICE_UNAVAILABLE, "unhealthy")
+    } else if !schemas_dir.is_ok() || llm.as_ref().is_some_and(|l| !l.is_ok()) {
+        (StatusCode::OK, "degraded")
     } else {
-        StatusCode::SERVICE_UNAVAILABLE
+        (StatusCode::OK, "healthy")
     };
 
     let response = HealthResponse {
-        status: if db_status == "ok" {
-            "healthy"
-        } else {
-            "unhealthy"
-        },
-        database: db_status,
+        status,
+        database: if database.is_ok() { "ok" } else { "error" },
+        migrations_applied: migrations.is_ok(),
+        dependencies: [Some(database), Some(migrations), Some(schemas_dir), llm]
+            .into_iter()
+            .flatten()
+            .collect(),
     };
 
-    (status, axum::Json(response))
+    (code, axum::Json(response))
+}
+
+/// Run one dependency check, bounded by [`HEALTH_PROBE_TIMEOUT`].
+async fn probe(
+    name: &'static str,
+    check: impl std::future::Future<Output = Result<(), String>>,
+) -> DependencyHealth {
+    let start = std::time::Instant::now();
+    let result = tokio::time::timeout(HEALTH_PROBE_TIMEOUT, check)
+        .await
+        .unwrap_or_else(|_| {
+            Err(format!(
+                "timed out after {}s",
+                HEALTH_PROBE_TIMEOUT.as_secs()
+            ))
+        });
+
+    DependencyHealth {
+        name,
+        status: if result.is_ok() { "ok" } else { "error" },
+        latency_ms: start.elapsed().as_millis() as u64,
+        error: result.err(),
+    }
+}
+
+/// Check that the configured LLM endpoint answers. Any non-5xx response
+/// counts as reachable; native local inference has no endpoint to probe.
+async fn probe_llm(api_key: Option<&str>) -> Result<(), String> {
+    let provider_name = std::env::var("ARES_PROVIDER").unwrap_or_else(|_| "openai".to_string());
+    let provider = Provider::parse(&provider_name).map_err(|e| e.to_string())?;
+    if provider == Provider::Local {
+        return Ok(());
+    }
+    let base_url =
+        std::env::var("ARES_BASE_URL").unwrap_or_else(|_| provider.default_base_url().to_string());
+
+    let client = reqwest::Client::builder()
+        .timeout(HEALTH_PROBE_TIMEOUT)
+        .build()
+        .map_err(|e| e.to_string())?;
+    let mut request = client.get(format!("{}/models", base_url.trim_end_matches('/')));
+    if let Some(key) = api_key {
+        request = request.bearer_auth(key);
+    }
+    let response = request.send().await.map_err(|e| e.to_string())?;
+
+    if response.status().is_server_error() {
+        return Err(format!("{base_url} returned {}", response.status()));
+    }
+    Ok(())
 }
 
 #[cfg(test)]
@@ -848,4 +2550,19 @@ mod tests {
         ));
         assert!(upstream_api_key(Provider::OpenAi, None).is_err());
     }
+
+    #[tokio::test]
+    async fn probe_reports_errors_and_timeouts() {
+        let ok = probe("ok", async { Ok(()) }).await;
+        assert!(ok.is_ok());
+        assert_eq!(ok.status, "ok");
+
+        let failed = probe("failed", async { Err("boom".to_string()) }).await;
+        assert_eq!(failed.status, "error");
+        assert_eq!(failed.error.as_deref(), Some("boom"));
+
+        let hung = probe("hung", std::future::pending()).await;
+        assert_eq!(hung.status, "error");
+        assert!(hung.error.unwrap().contains("timed out"));
+    }
 }
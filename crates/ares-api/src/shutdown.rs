@@ -0,0 +1,264 @@
+//! Graceful shutdown with a drain phase.
+//!
+//! On a shutdown signal the server stops accepting connections, marks the
+//! [`Shutdown`] as draining so [`reject_while_draining`] answers new
+//! requests on kept-alive connections (including `/readyz`, which takes the
+//! instance out of load balancing) with 503, and gives in-flight requests (a
+//! scrape can take a while) up to the drain timeout to finish before
+//! exiting. Scrapes still running then are logged as abandoned.
+
+use std::collections::BTreeMap;
+use std::future::{Future, IntoFuture};
+use std::io;
+use std::net::SocketAddr;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::time::Duration;
+
+use axum::Router;
+use axum::extract::{Request, State};
+use axum::http::{HeaderValue, StatusCode, header};
+use axum::middleware::Next;
+use axum::response::{IntoResponse, Response};
+use tokio::net::TcpListener;
+use tokio::sync::oneshot;
+
+use crate::dto::ErrorResponse;
+
+/// Shutdown state shared by the server and its handlers: whether the server
+/// is draining, and the scrapes in flight. Clones share the same state.
+#[derive(Debug, Clone, Default)]
+pub struct Shutdown {
+    draining: Arc<AtomicBool>,
+    scrapes: InFlightScrapes,
+}
+
+impl Shutdown {
+    pub fn is_draining(&self) -> bool {
+        self.draining.load(Ordering::Relaxed)
+    }
+
+    fn start_draining(&self) {
+        self.draining.store(true, Ordering::Relaxed);
+    }
+
+    /// The scrapes in flight.
+    pub fn scrapes(&self) -> &InFlightScrapes {
+        &self.scrapes
+    }
+}
+
+/// URLs of the scrapes currently running.
+#[derive(Debug, Clone, Default)]
+pub struct InFlightScrapes {
+    next_id: Arc<AtomicU64>,
+    urls: Arc<Mutex<BTreeMap<u64, String>>>,
+}
+
+impl InFlightScrapes {
+    /// Record a scrape of `url` until the returned guard is dropped.
+    pub fn track(&self, url: &str) -> ScrapeGuard {
+        let id = self.next_id.fetch_add(1, Ordering::Relaxed);
+        self.lock().insert(id, url.to_string());
+        ScrapeGuard {
+            id,
+            scrapes: self.clone(),
+        }
+    }
+
+    /// URLs being scraped, oldest first.
+    pub fn urls(&self) -> Vec<String> {
+        self.lock().values().cloned().collect()
+    }
+
+    fn lock(&self) -> MutexGuard<'_, BTreeMap<u64, String>> {
+        self.urls
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+/// Marks a scrape as in flight while alive.
+#[derive(Debug)]
+pub struct ScrapeGuard {
+    id: u64,
+    scrapes: InFlightScrapes,
+}
+
+impl Drop for ScrapeGuard {
+    fn drop(&mut self) {
+        self.scrapes.lock().remove(&self.id);
+    }
+}
+
+/// Middleware answering 503 Service Unavailable, with `Connection: close`
+/// so the client reconnects elsewhere, once the server is draining.
+pub async fn reject_while_draining(
+    State(shutdown): State<Shutdown>,
+    request: Request,
+    next: Next,
+) -> Response {
+    if shutdown.is_draining() {
+        let body = ErrorResponse {
+            error: "shutting_down".to_string(),
+            message: "Server is shutting down; retry the request".to_string(),
+        };
+        let mut response = (StatusCode::SERVICE_UNAVAILABLE, axum::Json(body)).into_response();
+        response
+            .headers_mut()
+            .insert(header::CONNECTION, HeaderValue::from_static("close"));
+        return response;
+    }
+    next.run(request).await
+}
+
+/// Serve `app` until `signal` resolves, then drain: mark `shutdown` as
+/// draining, stop accepting connections, and wait up to `drain_timeout` for
+/// in-flight requests. Requests still running after that are dropped.
+pub async fn serve_with_drain(
+    listener: TcpListener,
+    app: Router,
+    shutdown: Shutdown,
+    drain_timeout: Duration,
+    signal: impl Future<Output = ()>,
+) -> io::Result<()> {
+    let (stop_tx, stop_rx) = oneshot::channel::<()>();
+    let server = axum::serve(
+        listener,
+        app.into_make_service_with_connect_info::<SocketAddr>(),
+    )
+    .with_graceful_shutdown(async {
+        stop_rx.await.ok();
+    })
+    .into_future();
+    tokio::pin!(server);
+
+    tokio::select! {
+        result = &mut server => return result,
+        () = signal => {}
+    }
+
+    shutdown.start_draining();
+    let _ = stop_tx.send(());
+    tracing::info!(
+        drain_timeout_secs = drain_timeout.as_secs(),
+        in_flight_scrapes = shutdown.scrapes().urls().len(),
+        "Draining in-flight requests"
+    );
+    match tokio::time::timeout(drain_timeout, server).await {
+        Ok(result) => result,
+        Err(_) => {
+            let abandoned = shutdown.scrapes().urls();
+            tracing::warn!(
+                abandoned_scrapes = abandoned.len(),
+                urls = ?abandoned,
+                "Drain timeout elapsed with requests still in flight; shutting down anyway"
+            );
+            Ok(())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use axum::body::Body;
+    use axum::routing::get;
+    use tower::ServiceExt;
+
+    use super::*;
+
+    /// `/slow` stands in for a scrape of `https://slow.example` that takes
+    /// `delay`.
+    fn app(shutdown: Shutdown, delay: Duration) -> Router {
+        let scrapes = shutdown.scrapes().clone();
+        Router::new()
+            .route(
+                "/slow",
+                get(move || async move {
+                    let _scrape = scrapes.track("https://slow.example");
+                    tokio::time::sleep(delay).await;
+                    "done"
+                }),
+            )
+            .layer(axum::middleware::from_fn_with_state(
+                shutdown,
+                reject_while_draining,
+            ))
+    }
+
+    #[tokio::test]
+    async fn draining_rejects_new_requests() {
+        let shutdown = Shutdown::default();
+        let router = app(shutdown.clone(), Duration::ZERO);
+        let request = || Request::get("/slow").body(Body::empty()).unwrap();
+
+        let response = router.clone().oneshot(request()).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+
+        shutdown.start_draining();
+        let response = router.oneshot(request()).await.unwrap();
+        assert_eq!(response.status(), StatusCode::SERVICE_UNAVAILABLE);
+        assert_eq!(response.headers()[header::CONNECTION], "close");
+    }
+
+    /// Start serving; the returned sender triggers shutdown.
+    async fn start(
+        delay: Duration,
+        drain_timeout: Duration,
+    ) -> (
+        String,
+        Shutdown,
+        oneshot::Sender<()>,
+        tokio::task::JoinHandle<io::Result<()>>,
+    ) {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}/slow", listener.local_addr().unwrap());
+        let shutdown = Shutdown::default();
+        let (signal_tx, signal_rx) = oneshot::channel::<()>();
+        let server = tokio::spawn(serve_with_drain(
+            listener,
+            app(shutdown.clone(), delay),
+            shutdown.clone(),
+            drain_timeout,
+            async {
+                signal_rx.await.ok();
+            },
+        ));
+        (url, shutdown, signal_tx, server)
+    }
+
+    #[tokio::test]
+    async fn in_flight_requests_finish_during_the_drain() {
+        let (url, shutdown, signal, server) =
+            start(Duration::from_millis(300), Duration::from_secs(5)).await;
+        let request = tokio::spawn(reqwest::get(url));
+        tokio::time::sleep(Duration::from_millis(100)).await;
+        assert_eq!(shutdown.scrapes().urls(), ["https://slow.example"]);
+        signal.send(()).unwrap();
+
+        let response = request.await.unwrap().unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(response.text().await.unwrap(), "done");
+        server.await.unwrap().unwrap();
+        assert!(shutdown.is_draining());
+        assert!(shutdown.scrapes().urls().is_empty());
+    }
+
+    #[tokio::test]
+    async fn drain_timeout_bounds_the_shutdown() {
+        let (url, shutdown, signal, server) =
+            start(Duration::from_secs(30), Duration::from_millis(200)).await;
+        let request = tokio::spawn(reqwest::get(url));
+        tokio::time::sleep(Duration::from_millis(100)).await;
+        signal.send(()).unwrap();
+
+        tokio::time::timeout(Duration::from_secs(5), server)
+            .await
+            .expect("server outlived its drain timeout")
+            .unwrap()
+            .unwrap();
+        // The scrape was abandoned, not finished.
+        assert_eq!(shutdown.scrapes().urls(), ["https://slow.example"]);
+        request.abort();
+    }
+}
@@ -1,7 +1,16 @@
 use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::Duration;
 
+use ares_client::{EventPublisher, Snapshots};
+use ares_core::budget::BudgetGuard;
+use ares_core::circuit_breaker::CircuitBreakerRegistry;
 use ares_core::proxy::{ProxyConfig, TlsBackend};
-use ares_db::Database;
+use ares_core::snapshot::SnapshotKind;
+use ares_db::{Database, UsageRepository};
+
+use crate::rate_limit::RateLimiter;
+use crate::shutdown::InFlightScrapes;
 
 /// Shared application state, available to all route handlers via `State<Arc<AppState>>`.
 pub struct AppState {
@@ -20,4 +29,44 @@ pub struct AppState {
     pub stealth: bool,
     /// TLS backend for fingerprint diversity (set via `ARES_TLS_BACKEND`).
     pub tls_backend: TlsBackend,
+    /// API key for the LLM provider (set via `ARES_API_KEY`).
+    pub llm_api_key: Option<String>,
+    /// Probe the LLM endpoint in readiness checks (set via `ARES_HEALTH_LLM_PROBE=true`).
+    pub llm_probe: bool,
+    /// Largest per-request fetch timeout (set via `ARES_MAX_FETCH_TIMEOUT_SECS`).
+    pub max_fetch_timeout_secs: u64,
+    /// Largest per-request LLM timeout (set via `ARES_MAX_LLM_TIMEOUT_SECS`).
+    pub max_llm_timeout_secs: u64,
+    /// Most golden pages one verify request re-extracts (set via `ARES_VERIFY_MAX_GOLDENS`).
+    pub max_verify_goldens: usize,
+    /// Where saved extractions are published (set via `ARES_NATS_URL`).
+    pub events: EventPublisher,
+    /// Where raw HTML and Markdown snapshots are archived (set via `ARES_SNAPSHOT_URL`).
+    pub snapshots: Snapshots,
+    /// Which snapshots scrapes archive (set via `ARES_SNAPSHOT_KINDS`).
+    pub snapshot_kinds: Vec<SnapshotKind>,
+    /// Keep the cleaned content of saved extractions (set via `ARES_STORE_CONTENT=true`).
+    pub store_content: bool,
+    /// Scrapes in progress, reported if a shutdown abandons them.
+    pub scrapes: InFlightScrapes,
+    /// Daily LLM budget shared with workers (set via `ARES_BUDGET_MAX_*`).
+    pub budget: BudgetGuard<UsageRepository>,
+    /// Cross-URL duplicate window (set via `ARES_DEDUP_WINDOW_HOURS`).
+    pub dedup_window: Option<Duration>,
+    /// Copy a duplicate's data instead of calling the LLM (set via `ARES_DEDUP_REUSE=true`).
+    pub dedup_reuse: bool,
+    /// One circuit breaker per LLM base URL, shared by every request that
+    /// calls an LLM.
+    pub circuit_breakers: CircuitBreakerRegistry,
+    /// Inbound request budgets (set via `ARES_RATE_LIMIT_*`).
+    pub rate_limiter: Arc<RateLimiter>,
+}
+
+impl AppState {
+    /// Use `registry` for the LLM circuit breakers, so an embedder can share
+    /// breakers with other services in the process or tune their config.
+    pub fn with_circuit_breaker_registry(mut self, registry: CircuitBreakerRegistry) -> Self {
+        self.circuit_breakers = registry;
+        self
+    }
 }
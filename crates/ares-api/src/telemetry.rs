@@ -0,0 +1,137 @@
+//! Tracing setup — log formatting, HTTP request spans, and (with the `otel`
+//! feature) span export to an OpenTelemetry collector over OTLP/gRPC.
+//!
+//! HTTP spans use OpenTelemetry semantic-convention field names
+//! (`http.method`, `http.route`, `http.status_code`) whether or not the
+//! exporter is compiled in, so log output and traces describe requests the
+//! same way. Each span also carries the request's `request_id`, taken from
+//! the `x-request-id` header (generated when the client sends none).
+
+use std::time::Duration;
+
+use axum::extract::MatchedPath;
+use axum::http::{Request, Response};
+use tracing::Span;
+use tracing_subscriber::layer::SubscriberExt;
+use tracing_subscriber::util::SubscriberInitExt;
+
+use ares_core::logging::LogConfig;
+
+/// Header carrying the request ID, set by the app's request-ID layer.
+pub const REQUEST_ID_HEADER: &str = "x-request-id";
+
+/// Install the global tracing subscriber, logging to stdout as configured by
+/// `ARES_LOG_FORMAT` and `ARES_LOG_LEVEL` (see [`ares_core::logging`]).
+///
+/// With the `otel` feature, spans are also exported to
+/// `OTEL_EXPORTER_OTLP_ENDPOINT` (default `http://localhost:4317`) under
+/// `OTEL_SERVICE_NAME` (default `ares-api`).
+pub fn init() -> anyhow::Result<()> {
+    let config = LogConfig::from_env()?;
+    let registry = tracing_subscriber::registry()
+        .with(config.filter()?)
+        .with(config.layer(std::io::stdout));
+
+    #[cfg(feature = "otel")]
+    registry.with(otel::layer()?).init();
+    #[cfg(not(feature = "otel"))]
+    registry.init();
+
+    Ok(())
+}
+
+/// The OTLP collector spans are exported to, or `None` without the `otel`
+/// feature.
+pub fn otel_endpoint() -> Option<String> {
+    cfg!(feature = "otel").then(|| {
+        std::env::var("OTEL_EXPORTER_OTLP_ENDPOINT")
+            .unwrap_or_else(|_| "http://localhost:4317".to_string())
+    })
+}
+
+/// Flush pending spans and shut down the exporter. No-op without `otel`.
+pub fn shutdown() {
+    #[cfg(feature = "otel")]
+    opentelemetry::global::shutdown_tracer_provider();
+}
+
+/// `make_span_with` callback for `tower_http::trace::TraceLayer`.
+///
+/// The route is the matched pattern (e.g. `/v1/jobs/{id}`), so spans from
+/// different IDs group together in the collector.
+pub fn make_span<B>(request: &Request<B>) -> Span {
+    let route = request
+        .extensions()
+        .get::<MatchedPath>()
+        .map(MatchedPath::as_str);
+    let name = match route {
+        Some(route) => format!("{} {route}", request.method()),
+        None => request.method().to_string(),
+    };
+    let request_id = request
+        .headers()
+        .get(REQUEST_ID_HEADER)
+        .and_then(|v| v.to_str().ok());
+
+    tracing::info_span!(
+        "request",
+        otel.name = %name,
+        otel.kind = "server",
+        http.method = %request.method(),
+        http.route = route,
+        http.target = %request.uri(),
+        request_id,
+        http.status_code = tracing::field::Empty,
+        otel.status_code = tracing::field::Empty,
+    )
+}
+
+/// `on_response` callback recording the status on the request span.
+pub fn on_response<B>(response: &Response<B>, latency: Duration, span: &Span) {
+    let status = response.status();
+    span.record("http.status_code", status.as_u16());
+    if status.is_server_error() {
+        span.record("otel.status_code", "ERROR");
+    }
+    tracing::debug!(
+        status = status.as_u16(),
+        latency_ms = latency.as_millis(),
+        "finished processing request"
+    );
+}
+
+#[cfg(feature = "otel")]
+mod otel {
+    use opentelemetry::KeyValue;
+    use opentelemetry::trace::TracerProvider as _;
+    use opentelemetry_otlp::WithExportConfig;
+    use opentelemetry_sdk::Resource;
+    use opentelemetry_sdk::runtime::Tokio;
+    use opentelemetry_sdk::trace::TracerProvider;
+    use tracing_subscriber::Layer;
+    use tracing_subscriber::registry::LookupSpan;
+
+    /// Build the OTLP exporter, register it as the global tracer provider,
+    /// and return a `tracing` layer feeding it.
+    pub fn layer<S>() -> anyhow::Result<impl Layer<S>>
+    where
+        S: tracing::Subscriber + for<'span> LookupSpan<'span>,
+    {
+        let endpoint = super::otel_endpoint().unwrap_or_default();
+        let service_name =
+            std::env::var("OTEL_SERVICE_NAME").unwrap_or_else(|_| "ares-api".to_string());
+
+        let exporter = opentelemetry_otlp::SpanExporter::builder()
+            .with_tonic()
+            .with_endpoint(endpoint)
+            .build()?;
+        let provider = TracerProvider::builder()
+            .with_batch_exporter(exporter, Tokio)
+            .with_resource(Resource::new([KeyValue::new("service.name", service_name)]))
+            .build();
+        let tracer = provider.tracer("ares-api");
+        opentelemetry::global::set_tracer_provider(provider);
+
+        Ok(tracing_opentelemetry::layer().with_tracer(tracer))
+    }
+}
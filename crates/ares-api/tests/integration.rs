@@ -1,4 +1,6 @@
 mod integration {
     pub mod api_tests;
     pub mod common;
+    pub mod metrics_tests;
+    pub mod rate_limit_tests;
 }
@@ -1,9 +1,19 @@
+use std::time::Duration;
+
 use axum::body::Body;
 use axum::http::{Request, StatusCode};
 use http_body_util::BodyExt;
 use tower::ServiceExt;
 
-use crate::integration::common::{TEST_API_KEY, setup_test_app, setup_test_app_no_auth};
+use ares_api::config::{CorsConfig, CorsOrigins};
+use ares_core::event_log::NewWorkerEvent;
+use ares_core::job::CreateScrapeJobRequest;
+use ares_core::job_queue::JobQueue;
+
+use crate::integration::common::{
+    TEST_API_KEY, TestApp, llm_base_url, setup_test_app, setup_test_app_no_auth,
+    setup_test_app_with, setup_test_app_with_wiremock, setup_test_app_with_wiremock_and,
+};
 
 #[tokio::test]
 async fn health_returns_200() {
@@ -21,6 +31,72 @@ async fn health_returns_200() {
     let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
     assert_eq!(json["status"], "healthy");
     assert_eq!(json["database"], "ok");
+    assert_eq!(json["migrations_applied"], true);
+
+    let names: Vec<&str> = json["dependencies"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|d| d["name"].as_str().unwrap())
+        .collect();
+    assert_eq!(names, ["database", "migrations", "schemas_dir"]);
+    assert!(
+        json["dependencies"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .all(|d| d["status"] == "ok" && d["error"].is_null())
+    );
+}
+
+#[tokio::test]
+async fn readyz_degraded_when_schemas_dir_missing() {
+    let app = setup_test_app().await;
+    std::fs::remove_dir_all(&app.schemas_dir).unwrap();
+
+    let response = app
+        .router
+        .oneshot(Request::get("/readyz").body(Body::empty()).unwrap())
+        .await
+        .unwrap();
+
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+    assert_eq!(json["status"], "degraded");
+    assert_eq!(json["dependencies"][2]["name"], "schemas_dir");
+    assert_eq!(json["dependencies"][2]["status"], "error");
+}
+
+#[tokio::test]
+async fn database_outage_is_unhealthy_but_live() {
+    let app = setup_test_app().await;
+    app.stop_database().await;
+
+    let response = app
+        .router
+        .clone()
+        .oneshot(Request::get("/readyz").body(Body::empty()).unwrap())
+        .await
+        .unwrap();
+
+    assert_eq!(response.status(), StatusCode::SERVICE_UNAVAILABLE);
+
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+    assert_eq!(json["status"], "unhealthy");
+    assert_eq!(json["database"], "error");
+    assert_eq!(json["migrations_applied"], false);
+    assert!(json["dependencies"][0]["error"].is_string());
+
+    let response = app
+        .router
+        .oneshot(Request::get("/livez").body(Body::empty()).unwrap())
+        .await
+        .unwrap();
+
+    assert_eq!(response.status(), StatusCode::OK);
 }
 
 #[tokio::test]
@@ -54,6 +130,37 @@ async fn wrong_api_key_returns_401() {
     assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
 }
 
+#[tokio::test]
+async fn api_key_header_and_query_parameter_authenticate() {
+    let app = setup_test_app().await;
+    let get = |request: Request<Body>| app.router.clone().oneshot(request);
+
+    let response = get(Request::get("/v1/jobs")
+        .header("x-api-key", TEST_API_KEY)
+        .body(Body::empty())
+        .unwrap())
+    .await
+    .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let response = get(
+        Request::get(format!("/v1/jobs?limit=5&api_key={TEST_API_KEY}"))
+            .body(Body::empty())
+            .unwrap(),
+    )
+    .await
+    .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let response = get(Request::get("/v1/jobs")
+        .header("x-api-key", "wrong-key")
+        .body(Body::empty())
+        .unwrap())
+    .await
+    .unwrap();
+    assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+}
+
 #[tokio::test]
 async fn no_admin_token_returns_403() {
     let app = setup_test_app_no_auth().await;
@@ -130,6 +237,220 @@ async fn create_and_get_job() {
     assert_eq!(json["url"], "https://example.com");
 }
 
+#[tokio::test]
+async fn pending_count_is_public_and_counts_pending_jobs() {
+    let app = setup_test_app().await;
+
+    let create_body = serde_json::json!({
+        "url": "https://example.com",
+        "schema_name": "test",
+        "schema": {"type": "object"},
+        "model": "gpt-4o-mini",
+        "base_url": "https://api.openai.com/v1"
+    });
+
+    for _ in 0..2 {
+        let response = app
+            .router
+            .clone()
+            .oneshot(
+                Request::post("/v1/jobs")
+                    .header("authorization", format!("Bearer {TEST_API_KEY}"))
+                    .header("content-type", "application/json")
+                    .body(Body::from(serde_json::to_vec(&create_body).unwrap()))
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::ACCEPTED);
+    }
+
+    // No Authorization header
+    let response = app
+        .router
+        .oneshot(
+            Request::get("/v1/jobs/pending/count")
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+    assert_eq!(json["count"], 2);
+}
+
+#[tokio::test]
+async fn list_jobs_filters_by_worker() {
+    let app = setup_test_app().await;
+    let repo = app.db.job_repo();
+    let request = CreateScrapeJobRequest::new(
+        "https://example.com",
+        "test",
+        serde_json::json!({"type": "object"}),
+        "gpt-4o-mini",
+        "https://api.openai.com/v1",
+    );
+    for _ in 0..3 {
+        repo.create_job(request.clone()).await.unwrap();
+    }
+    let claimed = repo.claim_job("worker-a").await.unwrap().unwrap();
+    repo.claim_job("worker-b").await.unwrap();
+
+    let response = app
+        .router
+        .oneshot(
+            Request::get("/v1/jobs?status=running&worker_id=worker-a")
+                .header("authorization", format!("Bearer {TEST_API_KEY}"))
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(response.status(), StatusCode::OK);
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+    assert_eq!(json["total"], 1);
+    assert_eq!(json["jobs"].as_array().unwrap().len(), 1);
+    assert_eq!(json["jobs"][0]["id"], claimed.id.to_string());
+}
+
+#[tokio::test]
+async fn jobs_are_listed_and_deleted_by_tag() {
+    let app = setup_test_app().await;
+
+    let mut job_ids = Vec::new();
+    for tags in [vec!["run-42", "nightly"], vec!["run-42"], vec![]] {
+        let create_body = serde_json::json!({
+            "url": "https://example.com",
+            "schema_name": "test",
+            "schema": {"type": "object"},
+            "model": "gpt-4o-mini",
+            "base_url": "https://api.openai.com/v1",
+            "tags": tags
+        });
+        let response = app
+            .router
+            .clone()
+            .oneshot(
+                Request::post("/v1/jobs")
+                    .header("authorization", format!("Bearer {TEST_API_KEY}"))
+                    .header("content-type", "application/json")
+                    .body(Body::from(serde_json::to_vec(&create_body).unwrap()))
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::ACCEPTED);
+        let body = response.into_body().collect().await.unwrap().to_bytes();
+        let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        job_ids.push(
+            json["job_id"]
+                .as_str()
+                .unwrap()
+                .parse::<uuid::Uuid>()
+                .unwrap(),
+        );
+    }
+
+    let response = app
+        .router
+        .clone()
+        .oneshot(
+            Request::get("/v1/jobs?tag=run-42")
+                .header("authorization", format!("Bearer {TEST_API_KEY}"))
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+    assert_eq!(json["total"], 2);
+    assert!(
+        json["jobs"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .all(|job| job["tags"].as_array().unwrap().contains(&"run-42".into()))
+    );
+
+    // The first job is finished; the second is still pending and kept.
+    app.db.job_repo().cancel_job(job_ids[0]).await.unwrap();
+    let response = app
+        .router
+        .clone()
+        .oneshot(
+            Request::delete("/v1/jobs?tag=run-42")
+                .header("authorization", format!("Bearer {TEST_API_KEY}"))
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+    assert_eq!(json["deleted"], 1);
+
+    let repo = app.db.job_repo();
+    assert!(repo.get_job(job_ids[0]).await.unwrap().is_none());
+    assert!(repo.get_job(job_ids[1]).await.unwrap().is_some());
+    assert!(repo.get_job(job_ids[2]).await.unwrap().is_some());
+
+    let response = app
+        .router
+        .oneshot(
+            Request::delete("/v1/jobs")
+                .header("authorization", format!("Bearer {TEST_API_KEY}"))
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+}
+
+#[tokio::test]
+async fn running_jobs_by_worker_counts_claimed_jobs() {
+    let app = setup_test_app().await;
+    let repo = app.db.job_repo();
+    let request = CreateScrapeJobRequest::new(
+        "https://example.com",
+        "test",
+        serde_json::json!({"type": "object"}),
+        "gpt-4o-mini",
+        "https://api.openai.com/v1",
+    );
+    for _ in 0..3 {
+        repo.create_job(request.clone()).await.unwrap();
+    }
+    repo.claim_job("worker-a").await.unwrap();
+    repo.claim_job("worker-a").await.unwrap();
+    repo.claim_job("worker-b").await.unwrap();
+
+    let response = app
+        .router
+        .oneshot(
+            Request::get("/v1/jobs/running/by-worker")
+                .header("authorization", format!("Bearer {TEST_API_KEY}"))
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(response.status(), StatusCode::OK);
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+    assert_eq!(json, serde_json::json!({"worker-a": 2, "worker-b": 1}));
+}
+
 // ---------------------------------------------------------------------------
 // Schema endpoints
 // ---------------------------------------------------------------------------
@@ -212,6 +533,127 @@ async fn create_and_list_schema() {
     assert_eq!(json["schemas"][0]["versions"], serde_json::json!(["1.0.0"]));
 }
 
+#[tokio::test]
+async fn stats_counts_schemas_and_versions() {
+    let app = setup_test_app().await;
+    let resolver = ares_core::SchemaResolver::new(&app.schemas_dir);
+    let schema = serde_json::json!({"type": "object"});
+    resolver.create_schema("blog", "1.0.0", &schema).unwrap();
+    resolver.create_schema("blog", "1.1.0", &schema).unwrap();
+    resolver.create_schema("product", "1.0.0", &schema).unwrap();
+
+    let response = app
+        .router
+        .oneshot(
+            Request::get("/v1/stats")
+                .header("authorization", format!("Bearer {TEST_API_KEY}"))
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(response.status(), StatusCode::OK);
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+    assert_eq!(
+        json,
+        serde_json::json!({"schemas": 2, "schema_versions": 3})
+    );
+}
+
+#[tokio::test]
+async fn domain_stats_reports_failures_per_domain() {
+    let app = setup_test_app().await;
+    let repo = app.db.job_repo();
+    let request = CreateScrapeJobRequest::new(
+        "https://example.com/page",
+        "test",
+        serde_json::json!({"type": "object"}),
+        "gpt-4o-mini",
+        "https://api.openai.com/v1",
+    );
+    repo.create_job(request).await.unwrap();
+    let claimed = repo.claim_job("worker-a").await.unwrap().unwrap();
+    repo.fail_job(claimed.id, "selector missing", None)
+        .await
+        .unwrap();
+
+    let get = |query: &'static str| {
+        app.router.clone().oneshot(
+            Request::get(format!("/v1/stats/domains{query}"))
+                .header("authorization", format!("Bearer {TEST_API_KEY}"))
+                .body(Body::empty())
+                .unwrap(),
+        )
+    };
+
+    let response = get("").await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+    let domains = json["domains"].as_array().unwrap();
+    assert_eq!(domains.len(), 1);
+    assert_eq!(domains[0]["domain"], "https://example.com:443");
+    assert_eq!(domains[0]["jobs_succeeded"], 0);
+    assert_eq!(domains[0]["jobs_failed"], 1);
+    assert_eq!(domains[0]["failure_rate"], 1.0);
+    assert!(domains[0]["last_success_at"].is_null());
+
+    let response = get("?since=2999-01-01T00:00:00Z").await.unwrap();
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+    assert_eq!(json["domains"], serde_json::json!([]));
+
+    let response = get("?since=yesterday").await.unwrap();
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+}
+
+#[tokio::test]
+async fn create_schema_lists_every_violation() {
+    let app = setup_test_app().await;
+    let create_body = serde_json::json!({
+        "name": "broken",
+        "version": "1.0.0",
+        "schema": {
+            "type": "object",
+            "properties": {"title": {"type": 42}},
+            "required": "title"
+        }
+    });
+
+    let response = app
+        .router
+        .clone()
+        .oneshot(
+            Request::post("/v1/schemas")
+                .header("authorization", format!("Bearer {TEST_API_KEY}"))
+                .header("content-type", "application/json")
+                .body(Body::from(serde_json::to_vec(&create_body).unwrap()))
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(response.status(), StatusCode::UNPROCESSABLE_ENTITY);
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+    assert_eq!(json["error"], "validation_error");
+    let messages: Vec<&str> = json["messages"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|m| m.as_str().unwrap())
+        .collect();
+    assert!(
+        messages
+            .iter()
+            .any(|m| m.contains("/properties/title/type"))
+    );
+    assert!(messages.iter().any(|m| m.contains("/required")));
+    assert!(!app.schemas_dir.join("broken/1.0.0.json").exists());
+}
+
 #[tokio::test]
 async fn get_schema_returns_content() {
     let app = setup_test_app().await;
@@ -458,11 +900,43 @@ async fn delete_schema_not_found_returns_404() {
     assert_eq!(json["error"], "not_found");
 }
 
+// ---------------------------------------------------------------------------
+// Validate schema against sample HTML
+// ---------------------------------------------------------------------------
+
 #[tokio::test]
-async fn delete_latest_version_updates_registry() {
+async fn validate_schema_sample_not_found_returns_404() {
     let app = setup_test_app().await;
 
-    let schema = serde_json::json!({"type": "object"});
+    let response = app
+        .router
+        .oneshot(
+            Request::post("/v1/schemas/ghost/9.9.9/validate")
+                .header("authorization", format!("Bearer {TEST_API_KEY}"))
+                .header("content-type", "application/json")
+                .body(Body::from(
+                    serde_json::json!({
+                        "html": "<html><body><h1>Hi</h1></body></html>",
+                        "url": "https://example.com"
+                    })
+                    .to_string(),
+                ))
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+    assert_eq!(json["error"], "not_found");
+}
+
+#[tokio::test]
+async fn delete_latest_version_updates_registry() {
+    let app = setup_test_app().await;
+
+    let schema = serde_json::json!({"type": "object"});
 
     // Create two versions
     for version in &["1.0.0", "2.0.0"] {
@@ -660,6 +1134,36 @@ async fn cancel_nonexistent_job() {
     assert_eq!(response.status(), StatusCode::NOT_FOUND);
 }
 
+#[tokio::test]
+async fn bulk_cancel_counts_only_cancelled_jobs() {
+    let app = setup_test_app().await;
+    let first = create_test_job(&app).await;
+    let second = create_test_job(&app).await;
+
+    let body = serde_json::json!({
+        "job_ids": [first, second, uuid::Uuid::new_v4()],
+    });
+    for expected in [2, 0] {
+        let response = app
+            .router
+            .clone()
+            .oneshot(
+                Request::post("/v1/jobs/bulk-cancel")
+                    .header("authorization", format!("Bearer {TEST_API_KEY}"))
+                    .header("content-type", "application/json")
+                    .body(Body::from(serde_json::to_vec(&body).unwrap()))
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::OK);
+        let body = response.into_body().collect().await.unwrap().to_bytes();
+        let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(json["cancelled"], expected);
+    }
+}
+
 // ---------------------------------------------------------------------------
 // List jobs endpoint
 // ---------------------------------------------------------------------------
@@ -713,6 +1217,59 @@ async fn list_jobs_with_status_filter() {
     assert_eq!(json["jobs"].as_array().unwrap().len(), 2);
 }
 
+#[tokio::test]
+async fn list_jobs_with_invalid_status_is_a_bad_request() {
+    let app = setup_test_app().await;
+
+    let response = app
+        .router
+        .oneshot(
+            Request::get("/v1/jobs?status=done")
+                .header("authorization", format!("Bearer {TEST_API_KEY}"))
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+    assert_eq!(json["error"], "invalid_input");
+    let message = json["message"].as_str().unwrap();
+    assert!(message.contains("'done'"), "{message}");
+    assert!(
+        message.contains("pending, running, completed, failed, cancelled"),
+        "{message}"
+    );
+}
+
+#[tokio::test]
+async fn openapi_describes_job_status_as_an_enum() {
+    let app = setup_test_app().await;
+
+    let response = app
+        .router
+        .oneshot(
+            Request::get("/api-docs/openapi.json")
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    let spec: serde_json::Value = serde_json::from_slice(&body).unwrap();
+
+    assert_eq!(
+        spec["components"]["schemas"]["JobStatusDto"]["enum"],
+        serde_json::json!(["pending", "running", "completed", "failed", "cancelled"])
+    );
+    assert_eq!(
+        spec["components"]["schemas"]["JobResponse"]["properties"]["status"]["$ref"],
+        "#/components/schemas/JobStatusDto"
+    );
+}
+
 // ---------------------------------------------------------------------------
 // Invalid request body
 // ---------------------------------------------------------------------------
@@ -738,200 +1295,1974 @@ async fn create_job_invalid_body() {
 }
 
 // ---------------------------------------------------------------------------
-// Extractions endpoint
+// Scrape endpoint
 // ---------------------------------------------------------------------------
 
+/// A `/v1/scrape` request with `fields` merged over a minimal valid body.
+fn scrape_request(fields: serde_json::Value) -> Request<Body> {
+    let mut body = serde_json::json!({
+        "url": "https://example.com/label",
+        "schema": {"type": "object", "properties": {"title": {"type": "string"}}},
+        "schema_name": "blog",
+        "save": false,
+    });
+    for (key, value) in fields.as_object().unwrap() {
+        body[key] = value.clone();
+    }
+    Request::post("/v1/scrape")
+        .header("authorization", format!("Bearer {TEST_API_KEY}"))
+        .header("content-type", "application/json")
+        .body(Body::from(body.to_string()))
+        .unwrap()
+}
+
+async fn error_message(response: axum::response::Response) -> String {
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+    json["message"].as_str().unwrap().to_string()
+}
+
 #[tokio::test]
-async fn get_extractions_empty() {
+async fn scrape_rejects_empty_html() {
     let app = setup_test_app().await;
 
     let response = app
         .router
-        .oneshot(
-            Request::get("/v1/extractions?url=https://example.com&schema_name=test")
-                .header("authorization", format!("Bearer {TEST_API_KEY}"))
-                .body(Body::empty())
-                .unwrap(),
-        )
+        .oneshot(scrape_request(serde_json::json!({"html": "  "})))
         .await
         .unwrap();
 
-    assert_eq!(response.status(), StatusCode::OK);
-
-    let body = response.into_body().collect().await.unwrap().to_bytes();
-    let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
-    assert_eq!(json["total"], 0);
-    assert_eq!(json["extractions"].as_array().unwrap().len(), 0);
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
 }
 
-// ---------------------------------------------------------------------------
-// Retry job endpoint
-// ---------------------------------------------------------------------------
+#[tokio::test]
+async fn scrape_rejects_html_over_body_limit() {
+    let app = setup_test_app().await;
+    let html = "x".repeat(ares_api::config::DEFAULT_SCRAPE_BODY_LIMIT + 1);
 
-/// Helper: create a job and return its ID.
-async fn create_test_job(app: &crate::integration::common::TestApp) -> String {
-    let create_body = serde_json::json!({
-        "url": "https://example.com",
-        "schema_name": "test",
-        "schema": {"type": "object"},
-        "model": "gpt-4o-mini",
-        "base_url": "https://api.openai.com/v1"
+    let response = app
+        .router
+        .oneshot(scrape_request(serde_json::json!({"html": html})))
+        .await
+        .unwrap();
+
+    assert_eq!(response.status(), StatusCode::PAYLOAD_TOO_LARGE);
+}
+
+#[tokio::test]
+async fn schema_uploads_have_their_own_body_limit() {
+    let app = setup_test_app_with(|config| config.with_schema_body_limit(1024)).await;
+    let body = serde_json::json!({
+        "name": "blog",
+        "version": "1.0.0",
+        "schema": {"type": "object", "description": "x".repeat(2048)}
     });
 
     let response = app
         .router
         .clone()
         .oneshot(
-            Request::post("/v1/jobs")
+            Request::post("/v1/schemas")
                 .header("authorization", format!("Bearer {TEST_API_KEY}"))
                 .header("content-type", "application/json")
-                .body(Body::from(serde_json::to_vec(&create_body).unwrap()))
+                .body(Body::from(serde_json::to_vec(&body).unwrap()))
                 .unwrap(),
         )
         .await
         .unwrap();
+    assert_eq!(response.status(), StatusCode::PAYLOAD_TOO_LARGE);
 
-    assert_eq!(response.status(), StatusCode::ACCEPTED);
-    let body = response.into_body().collect().await.unwrap().to_bytes();
-    let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
-    json["job_id"].as_str().unwrap().to_string()
+    // The scrape limit is unaffected: the body is read, then rejected for
+    // combining html with a fetch option.
+    let response = app
+        .router
+        .oneshot(scrape_request(
+            serde_json::json!({"html": "x".repeat(2048), "fetch_timeout_secs": 0}),
+        ))
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
 }
 
 #[tokio::test]
-async fn retry_cancelled_job() {
+async fn scrape_rejects_invalid_fetch_options() {
+    let app = setup_test_app().await;
+
+    for (fields, needle) in [
+        (
+            serde_json::json!({"html": "<p>hi</p>", "fetch_timeout_secs": 10}),
+            "html can't be combined",
+        ),
+        (
+            serde_json::json!({"fetch_timeout_secs": 100_000}),
+            "fetch_timeout_secs must be between 1 and",
+        ),
+        (
+            serde_json::json!({"llm_timeout_secs": 0}),
+            "llm_timeout_secs must be between 1 and",
+        ),
+        (
+            serde_json::json!({"wait_selector": "#app"}),
+            "wait_selector requires use_browser",
+        ),
+        (
+            serde_json::json!({"user_agent": "mobile"}),
+            "user_agent requires use_browser",
+        ),
+        (
+            serde_json::json!({"content_similarity_threshold": 0}),
+            "content_similarity_threshold must be between 1 and 64",
+        ),
+        (
+            serde_json::json!({"content_similarity_threshold": 8, "save": false}),
+            "content_similarity_threshold requires save",
+        ),
+        (
+            serde_json::json!({"content_selector": "[["}),
+            "Invalid CSS selector",
+        ),
+        (
+            serde_json::json!({"headers": {"bad header": "x"}}),
+            "invalid header name 'bad header'",
+        ),
+        (
+            serde_json::json!({"headers": {"X-Note": "line\nbreak"}}),
+            "invalid value for header 'X-Note'",
+        ),
+        (
+            serde_json::json!({"auth": {"type": "basic", "user": "a:b", "pass": "x"}}),
+            "auth user must not contain ':'",
+        ),
+        (
+            serde_json::json!({"auth": {"type": "header", "name": "bad header", "value": "x"}}),
+            "invalid auth header name 'bad header'",
+        ),
+    ] {
+        let response = app
+            .router
+            .clone()
+            .oneshot(scrape_request(fields.clone()))
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST, "{fields}");
+        let message = error_message(response).await;
+        assert!(message.contains(needle), "{fields}: {message}");
+    }
+}
+
+#[cfg(not(feature = "browser"))]
+#[tokio::test]
+async fn scrape_use_browser_requires_browser_feature() {
     let app = setup_test_app().await;
-    let job_id = create_test_job(&app).await;
 
-    // Cancel the job
     let response = app
         .router
-        .clone()
-        .oneshot(
-            Request::delete(format!("/v1/jobs/{job_id}"))
-                .header("authorization", format!("Bearer {TEST_API_KEY}"))
-                .body(Body::empty())
-                .unwrap(),
-        )
+        .oneshot(scrape_request(serde_json::json!({"use_browser": true})))
         .await
         .unwrap();
-    assert_eq!(response.status(), StatusCode::NO_CONTENT);
 
-    // Retry it
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    assert!(error_message(response).await.contains("`browser` feature"));
+}
+
+/// An inline-HTML scrape against the mock LLM, so nothing leaves the machine.
+fn mock_llm_scrape_request(server: &wiremock::MockServer) -> Request<Body> {
+    scrape_request(serde_json::json!({
+        "html": "<html><h1>Test</h1></html>",
+        "provider": "openai",
+        "model": "gpt-4o-mini",
+        "base_url": llm_base_url(server),
+    }))
+}
+
+/// Make the mock LLM answer every chat completion with `status`.
+async fn fail_llm_with(server: &wiremock::MockServer, status: u16) {
+    wiremock::Mock::given(wiremock::matchers::method("POST"))
+        .and(wiremock::matchers::path("/v1/chat/completions"))
+        .respond_with(wiremock::ResponseTemplate::new(status))
+        .with_priority(1)
+        .mount(server)
+        .await;
+}
+
+#[tokio::test]
+async fn scrape_extracts_with_mock_llm() {
+    let (app, server) = setup_test_app_with_wiremock().await;
+
     let response = app
         .router
-        .clone()
-        .oneshot(
-            Request::post(format!("/v1/jobs/{job_id}/retry"))
-                .header("authorization", format!("Bearer {TEST_API_KEY}"))
-                .body(Body::empty())
-                .unwrap(),
-        )
+        .oneshot(mock_llm_scrape_request(&server))
         .await
         .unwrap();
 
     assert_eq!(response.status(), StatusCode::OK);
     let body = response.into_body().collect().await.unwrap().to_bytes();
     let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
-    assert_eq!(json["id"], job_id);
-    assert_eq!(json["status"], "pending");
-    assert_eq!(json["retry_count"], 0);
-    assert!(json["error_message"].is_null());
+    assert_eq!(json["extracted_data"], serde_json::json!({"title": "Test"}));
+    assert!(json["extraction_id"].is_null());
+    assert_eq!(server.received_requests().await.unwrap().len(), 1);
 }
 
 #[tokio::test]
-async fn retry_pending_job_returns_409() {
-    let app = setup_test_app().await;
-    let job_id = create_test_job(&app).await;
+async fn scrape_llm_rate_limit_returns_429() {
+    let (app, server) = setup_test_app_with_wiremock().await;
+    fail_llm_with(&server, 429).await;
 
-    // Try to retry a pending job — should fail
     let response = app
         .router
-        .oneshot(
-            Request::post(format!("/v1/jobs/{job_id}/retry"))
-                .header("authorization", format!("Bearer {TEST_API_KEY}"))
-                .body(Body::empty())
-                .unwrap(),
-        )
+        .oneshot(mock_llm_scrape_request(&server))
         .await
         .unwrap();
 
-    assert_eq!(response.status(), StatusCode::CONFLICT);
+    assert_eq!(response.status(), StatusCode::TOO_MANY_REQUESTS);
+}
+
+#[tokio::test]
+async fn scrape_llm_server_error_returns_503() {
+    let (app, server) = setup_test_app_with_wiremock().await;
+    fail_llm_with(&server, 500).await;
+
+    let response = app
+        .router
+        .oneshot(mock_llm_scrape_request(&server))
+        .await
+        .unwrap();
+
+    assert_eq!(response.status(), StatusCode::SERVICE_UNAVAILABLE);
     let body = response.into_body().collect().await.unwrap().to_bytes();
     let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
-    assert_eq!(json["error"], "conflict");
+    assert_eq!(json["error"], "llm_unavailable");
 }
 
 #[tokio::test]
-async fn retry_nonexistent_job_returns_404() {
+async fn create_job_persists_fetch_options() {
     let app = setup_test_app().await;
+    let create_body = serde_json::json!({
+        "url": "https://example.com",
+        "schema_name": "test",
+        "schema": {"type": "object"},
+        "model": "gpt-4o-mini",
+        "base_url": "https://api.openai.com/v1",
+        "use_browser": true,
+        "wait_selector": "#app",
+        "content_selector": "main",
+        "headers": {"Accept-Language": "it-IT"},
+    });
 
-    let fake_id = uuid::Uuid::new_v4();
     let response = app
         .router
+        .clone()
         .oneshot(
-            Request::post(format!("/v1/jobs/{fake_id}/retry"))
+            Request::post("/v1/jobs")
                 .header("authorization", format!("Bearer {TEST_API_KEY}"))
-                .body(Body::empty())
+                .header("content-type", "application/json")
+                .body(Body::from(create_body.to_string()))
                 .unwrap(),
         )
         .await
         .unwrap();
+    assert_eq!(response.status(), StatusCode::ACCEPTED);
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+    let job_id = json["job_id"].as_str().unwrap();
 
-    assert_eq!(response.status(), StatusCode::NOT_FOUND);
-}
-
-// ---------------------------------------------------------------------------
-// Pagination
-// ---------------------------------------------------------------------------
-
-#[tokio::test]
-async fn list_jobs_with_pagination() {
-    let app = setup_test_app().await;
-
-    // Create 3 jobs
-    for _ in 0..3 {
-        create_test_job(&app).await;
-    }
-
-    // First page: limit=2, offset=0
     let response = app
         .router
-        .clone()
         .oneshot(
-            Request::get("/v1/jobs?limit=2&offset=0")
+            Request::get(format!("/v1/jobs/{job_id}"))
                 .header("authorization", format!("Bearer {TEST_API_KEY}"))
                 .body(Body::empty())
                 .unwrap(),
         )
         .await
         .unwrap();
-
-    assert_eq!(response.status(), StatusCode::OK);
     let body = response.into_body().collect().await.unwrap().to_bytes();
     let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
-    assert_eq!(json["total"], 3);
-    assert_eq!(json["limit"], 2);
-    assert_eq!(json["offset"], 0);
-    assert_eq!(json["jobs"].as_array().unwrap().len(), 2);
+    assert_eq!(json["fetch_options"]["use_browser"], true);
+    assert_eq!(json["fetch_options"]["wait_selector"], "#app");
+    assert_eq!(json["fetch_options"]["content_selector"], "main");
+    assert_eq!(json["fetch_options"]["headers"]["Accept-Language"], "it-IT");
+    assert!(json["fetch_options"]["fetch_timeout_secs"].is_null());
+}
+
+#[tokio::test]
+async fn job_responses_never_include_fetch_credentials() {
+    let app = setup_test_app().await;
+    let create_body = serde_json::json!({
+        "url": "https://example.com",
+        "schema_name": "test",
+        "schema": {"type": "object"},
+        "model": "gpt-4o-mini",
+        "base_url": "https://api.openai.com/v1",
+        "auth": {"type": "basic", "user": "ada", "pass": "hunter2-secret"},
+        "notify_url": "https://hooks.example.com/services/webhook-secret",
+    });
 
-    // Second page: limit=2, offset=2
     let response = app
         .router
         .clone()
         .oneshot(
-            Request::get("/v1/jobs?limit=2&offset=2")
+            Request::post("/v1/jobs")
                 .header("authorization", format!("Bearer {TEST_API_KEY}"))
-                .body(Body::empty())
+                .header("content-type", "application/json")
+                .body(Body::from(create_body.to_string()))
                 .unwrap(),
         )
         .await
         .unwrap();
-
-    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(response.status(), StatusCode::ACCEPTED);
     let body = response.into_body().collect().await.unwrap().to_bytes();
     let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
-    assert_eq!(json["total"], 3);
-    assert_eq!(json["limit"], 2);
-    assert_eq!(json["offset"], 2);
-    assert_eq!(json["jobs"].as_array().unwrap().len(), 1);
+    let job_id = json["job_id"].as_str().unwrap().to_string();
+
+    for uri in [format!("/v1/jobs/{job_id}"), "/v1/jobs".to_string()] {
+        let response = app
+            .router
+            .clone()
+            .oneshot(
+                Request::get(&uri)
+                    .header("authorization", format!("Bearer {TEST_API_KEY}"))
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        let body = response.into_body().collect().await.unwrap().to_bytes();
+        let text = String::from_utf8(body.to_vec()).unwrap();
+        assert!(!text.contains("hunter2-secret"), "{uri}: {text}");
+        assert!(!text.contains("\"pass\""), "{uri}: {text}");
+        assert!(!text.contains("webhook-secret"), "{uri}: {text}");
+    }
+
+    let response = app
+        .router
+        .oneshot(
+            Request::get(format!("/v1/jobs/{job_id}"))
+                .header("authorization", format!("Bearer {TEST_API_KEY}"))
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+    assert_eq!(json["fetch_options"]["auth_type"], "basic");
+}
+
+#[tokio::test]
+async fn create_jobs_from_sitemap_rejects_bad_requests() {
+    let app = setup_test_app().await;
+
+    for (fields, needle) in [
+        (
+            serde_json::json!({"include": ["("]}),
+            "Invalid sitemap filter '('",
+        ),
+        (
+            serde_json::json!({"max_urls": 0}),
+            "max_urls must be between 1 and",
+        ),
+        // The server never fetches sitemaps on private addresses.
+        (
+            serde_json::json!({"sitemap_url": "http://127.0.0.1:1/sitemap.xml"}),
+            "Failed to load sitemap",
+        ),
+    ] {
+        let mut body = serde_json::json!({
+            "sitemap_url": "https://example.com/sitemap.xml",
+            "schema_name": "test",
+            "schema": {"type": "object"},
+            "model": "gpt-4o-mini",
+            "base_url": "https://api.openai.com/v1",
+        });
+        for (key, value) in fields.as_object().unwrap() {
+            body[key] = value.clone();
+        }
+
+        let response = app
+            .router
+            .clone()
+            .oneshot(
+                Request::post("/v1/jobs/from-sitemap")
+                    .header("authorization", format!("Bearer {TEST_API_KEY}"))
+                    .header("content-type", "application/json")
+                    .body(Body::from(body.to_string()))
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST, "{fields}");
+        let message = error_message(response).await;
+        assert!(message.contains(needle), "{fields}: {message}");
+    }
+}
+
+#[tokio::test]
+async fn create_job_rejects_wait_selector_without_browser() {
+    let app = setup_test_app().await;
+    let create_body = serde_json::json!({
+        "url": "https://example.com",
+        "schema_name": "test",
+        "schema": {"type": "object"},
+        "model": "gpt-4o-mini",
+        "base_url": "https://api.openai.com/v1",
+        "wait_selector": "#app",
+    });
+
+    let response = app
+        .router
+        .oneshot(
+            Request::post("/v1/jobs")
+                .header("authorization", format!("Bearer {TEST_API_KEY}"))
+                .header("content-type", "application/json")
+                .body(Body::from(create_body.to_string()))
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+}
+
+// ---------------------------------------------------------------------------
+// Extractions endpoint
+// ---------------------------------------------------------------------------
+
+#[tokio::test]
+async fn get_extractions_empty() {
+    let app = setup_test_app().await;
+
+    let response = app
+        .router
+        .oneshot(
+            Request::get("/v1/extractions?url=https://example.com&schema_name=test")
+                .header("authorization", format!("Bearer {TEST_API_KEY}"))
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+    assert_eq!(json["total"], 0);
+    assert_eq!(json["extractions"].as_array().unwrap().len(), 0);
+}
+
+#[tokio::test]
+async fn get_latest_extraction_returns_the_newest_or_404() {
+    use ares_core::models::NewExtraction;
+
+    let app = setup_test_app().await;
+    let latest_request = |schema_name: &str| {
+        Request::get(format!(
+            "/v1/extractions/latest?url=https://example.com&schema_name={schema_name}"
+        ))
+        .header("authorization", format!("Bearer {TEST_API_KEY}"))
+        .body(Body::empty())
+        .unwrap()
+    };
+
+    let response = app
+        .router
+        .clone()
+        .oneshot(latest_request("test"))
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+    assert_eq!(json["error"], "not_found");
+
+    let repo = app.db.extraction_repo();
+    let first = NewExtraction {
+        url: "https://example.com".into(),
+        schema_name: "test".into(),
+        extracted_data: serde_json::json!({"title": "Old"}),
+        raw_content_hash: "chash".into(),
+        data_hash: "dhash-old".into(),
+        model: "model".into(),
+        ..Default::default()
+    };
+    repo.save(&first).await.unwrap();
+    tokio::time::sleep(Duration::from_millis(10)).await;
+    let newest = repo
+        .save(&NewExtraction {
+            extracted_data: serde_json::json!({"title": "New"}),
+            data_hash: "dhash-new".into(),
+            ..first
+        })
+        .await
+        .unwrap();
+
+    let response = app
+        .router
+        .clone()
+        .oneshot(latest_request("test"))
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+    assert_eq!(json["id"], newest.to_string());
+    assert_eq!(json["extracted_data"], serde_json::json!({"title": "New"}));
+
+    // Other schemas of the same URL have no extractions.
+    let response = app.router.oneshot(latest_request("other")).await.unwrap();
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+async fn get_extractions_includes_a_readable_age() {
+    use ares_core::models::NewExtraction;
+
+    let app = setup_test_app().await;
+    app.db
+        .extraction_repo()
+        .save(&NewExtraction {
+            url: "https://example.com".into(),
+            schema_name: "test".into(),
+            extracted_data: serde_json::json!({"title": "Hello"}),
+            raw_content_hash: "chash".into(),
+            data_hash: "dhash".into(),
+            model: "model".into(),
+            ..Default::default()
+        })
+        .await
+        .unwrap();
+
+    let response = app
+        .router
+        .oneshot(
+            Request::get("/v1/extractions?url=https://example.com&schema_name=test")
+                .header("authorization", format!("Bearer {TEST_API_KEY}"))
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+    let age = json["extractions"][0]["age_display"].as_str().unwrap();
+    assert!(age.ends_with("s ago"), "{age}");
+}
+
+#[tokio::test]
+async fn get_extractions_compares_the_last_row_with_the_one_past_the_page() {
+    use ares_core::models::NewExtraction;
+
+    let app = setup_test_app().await;
+    // Oldest first: the first two extractions hold the same data.
+    for data_hash in ["same", "same", "new"] {
+        app.db
+            .extraction_repo()
+            .save(&NewExtraction {
+                url: "https://example.com".into(),
+                schema_name: "test".into(),
+                extracted_data: serde_json::json!({"title": data_hash}),
+                raw_content_hash: "chash".into(),
+                data_hash: data_hash.into(),
+                model: "model".into(),
+                ..Default::default()
+            })
+            .await
+            .unwrap();
+        tokio::time::sleep(Duration::from_millis(10)).await;
+    }
+
+    let changed = |limit: usize| {
+        let router = app.router.clone();
+        async move {
+            let response = router
+                .oneshot(
+                    Request::get(format!(
+                        "/v1/extractions?url=https://example.com&schema_name=test&limit={limit}"
+                    ))
+                    .header("authorization", format!("Bearer {TEST_API_KEY}"))
+                    .body(Body::empty())
+                    .unwrap(),
+                )
+                .await
+                .unwrap();
+            let body = response.into_body().collect().await.unwrap().to_bytes();
+            let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+            json["extractions"]
+                .as_array()
+                .unwrap()
+                .iter()
+                .map(|e| e["changed"].as_bool().unwrap())
+                .collect::<Vec<_>>()
+        }
+    };
+
+    // The oldest visible row repeats the one just past the window.
+    assert_eq!(changed(2).await, [true, false]);
+    // The very first extraction counts as changed.
+    assert_eq!(changed(3).await, [true, false, true]);
+}
+
+#[tokio::test]
+async fn get_extractions_links_rows_to_their_previous_extraction() {
+    use ares_core::models::NewExtraction;
+
+    let app = setup_test_app().await;
+    // Oldest first.
+    let mut ids = Vec::new();
+    for data_hash in ["a", "b", "b", "a"] {
+        let id = app
+            .db
+            .extraction_repo()
+            .save(&NewExtraction {
+                url: "https://example.com".into(),
+                schema_name: "test".into(),
+                extracted_data: serde_json::json!({"title": data_hash}),
+                raw_content_hash: "chash".into(),
+                data_hash: data_hash.into(),
+                model: "model".into(),
+                ..Default::default()
+            })
+            .await
+            .unwrap();
+        ids.push(id.to_string());
+        tokio::time::sleep(Duration::from_millis(10)).await;
+    }
+
+    let response = app
+        .router
+        .oneshot(
+            Request::get(
+                "/v1/extractions?url=https://example.com&schema_name=test&limit=2&offset=2",
+            )
+            .header("authorization", format!("Bearer {TEST_API_KEY}"))
+            .body(Body::empty())
+            .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+    let rows = json["extractions"].as_array().unwrap();
+
+    assert_eq!(rows.len(), 2);
+    assert_eq!(rows[0]["id"], ids[1]);
+    assert_eq!(rows[0]["previous_extraction_id"], ids[0]);
+    assert_eq!(rows[0]["changed"], true);
+    // The first extraction has nothing to point back to.
+    assert_eq!(rows[1]["id"], ids[0]);
+    assert!(rows[1].get("previous_extraction_id").is_none());
+    assert_eq!(rows[1]["changed"], true);
+}
+
+#[tokio::test]
+async fn get_extraction_snapshot_returns_the_archived_content() {
+    use ares_client::FsSnapshotStore;
+    use ares_core::models::NewExtraction;
+    use ares_core::snapshot::SnapshotKind;
+    use ares_core::traits::SnapshotStore;
+
+    let snapshots = tempfile::tempdir().unwrap();
+    let store = FsSnapshotStore::new(snapshots.path());
+    let key = SnapshotKind::Html.key("ab12");
+    store.put(&key, b"<h1>Hello</h1>").await.unwrap();
+
+    let url = format!("file://{}", snapshots.path().display());
+    let app = setup_test_app_with(|config| config.with_snapshots(url, &SnapshotKind::ALL)).await;
+    let id = app
+        .db
+        .extraction_repo()
+        .save(&NewExtraction {
+            url: "https://example.com".into(),
+            schema_name: "test".into(),
+            extracted_data: serde_json::json!({"title": "Hello"}),
+            raw_content_hash: "chash".into(),
+            data_hash: "dhash".into(),
+            model: "model".into(),
+            html_snapshot_key: Some(key),
+            ..Default::default()
+        })
+        .await
+        .unwrap();
+
+    let get = |path: String| {
+        app.router.clone().oneshot(
+            Request::get(path)
+                .header("authorization", format!("Bearer {TEST_API_KEY}"))
+                .body(Body::empty())
+                .unwrap(),
+        )
+    };
+
+    let response = get(format!("/v1/extractions/{id}/snapshot?kind=html"))
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(
+        response.headers()["content-type"],
+        "text/html; charset=utf-8"
+    );
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    assert_eq!(&body[..], b"<h1>Hello</h1>");
+
+    let response = get(format!("/v1/extractions/{id}/snapshot?kind=markdown"))
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+
+    let missing = uuid::Uuid::new_v4();
+    let response = get(format!("/v1/extractions/{missing}/snapshot?kind=html"))
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+
+    let response = get(format!("/v1/extractions/{id}/snapshot?kind=pdf"))
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+}
+
+#[tokio::test]
+async fn get_extraction_raw_content_returns_stored_markdown() {
+    use ares_core::models::NewExtraction;
+
+    let app = setup_test_app().await;
+    let repo = app.db.extraction_repo();
+    let mut ids = Vec::new();
+    for url in ["https://example.com/a", "https://example.com/b"] {
+        let id = repo
+            .save(&NewExtraction {
+                url: url.into(),
+                schema_name: "test".into(),
+                extracted_data: serde_json::json!({"title": "Hello"}),
+                raw_content_hash: "chash".into(),
+                data_hash: "dhash".into(),
+                model: "model".into(),
+                ..Default::default()
+            })
+            .await
+            .unwrap();
+        ids.push(id);
+    }
+    repo.save_content(ids[0], "# Hello").await.unwrap();
+
+    let get = |id: uuid::Uuid| {
+        app.router.clone().oneshot(
+            Request::get(format!("/v1/extractions/{id}/raw-content"))
+                .header("authorization", format!("Bearer {TEST_API_KEY}"))
+                .body(Body::empty())
+                .unwrap(),
+        )
+    };
+
+    let response = get(ids[0]).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(
+        response.headers()["content-type"],
+        "text/markdown; charset=utf-8"
+    );
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    assert_eq!(&body[..], b"# Hello");
+
+    let response = get(ids[1]).await.unwrap();
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+    assert!(
+        json["message"]
+            .as_str()
+            .unwrap()
+            .contains("No stored content")
+    );
+
+    let response = get(uuid::Uuid::new_v4()).await.unwrap();
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+async fn delete_extractions_by_url_and_schema() {
+    use ares_core::models::NewExtraction;
+
+    let app = setup_test_app().await;
+    let repo = app.db.extraction_repo();
+    for (url, schema_name) in [
+        ("https://me.example", "blog"),
+        ("https://me.example", "profile"),
+        ("https://other.example", "legacy"),
+    ] {
+        repo.save(&NewExtraction {
+            url: url.into(),
+            schema_name: schema_name.into(),
+            extracted_data: serde_json::json!({"title": "Hello"}),
+            raw_content_hash: "chash".into(),
+            data_hash: "dhash".into(),
+            model: "model".into(),
+            ..Default::default()
+        })
+        .await
+        .unwrap();
+    }
+
+    let delete = |query: &str| {
+        Request::delete(format!("/v1/extractions{query}"))
+            .header("authorization", format!("Bearer {TEST_API_KEY}"))
+            .body(Body::empty())
+            .unwrap()
+    };
+
+    let response = app
+        .router
+        .clone()
+        .oneshot(delete("?url=https://me.example"))
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+    assert_eq!(json["deleted"], 2);
+
+    let response = app
+        .router
+        .clone()
+        .oneshot(delete("?schema_name=legacy"))
+        .await
+        .unwrap();
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+    assert_eq!(json["deleted"], 1);
+
+    // Nothing declares a retention policy, so nothing has expired.
+    let response = app
+        .router
+        .clone()
+        .oneshot(delete("?expired=true"))
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+    assert_eq!(
+        (json["deleted"].as_u64(), json["scrubbed"].as_u64()),
+        (Some(0), Some(0))
+    );
+
+    for query in [
+        "",
+        "?url=https://me.example&schema_name=blog",
+        "?url=https://me.example&expired=true",
+    ] {
+        let response = app.router.clone().oneshot(delete(query)).await.unwrap();
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST, "{query}");
+    }
+}
+
+#[tokio::test]
+async fn golden_extractions_are_marked_and_verified() {
+    use ares_client::FsSnapshotStore;
+    use ares_core::models::NewExtraction;
+    use ares_core::snapshot::SnapshotKind;
+    use ares_core::traits::SnapshotStore;
+
+    let snapshots = tempfile::tempdir().unwrap();
+    let store = FsSnapshotStore::new(snapshots.path());
+    let key = SnapshotKind::Html.key("ab12");
+    store.put(&key, b"<h1>Test</h1>").await.unwrap();
+
+    let url = format!("file://{}", snapshots.path().display());
+    let (app, server) =
+        setup_test_app_with_wiremock_and(|config| config.with_snapshots(url, &SnapshotKind::ALL))
+            .await;
+    let send = |request: Request<Body>| app.router.clone().oneshot(request);
+    let authed = |builder: axum::http::request::Builder| {
+        builder.header("authorization", format!("Bearer {TEST_API_KEY}"))
+    };
+
+    let create_schema = serde_json::json!({
+        "name": "product",
+        "version": "1.0.0",
+        "schema": {"type": "object", "properties": {"title": {"type": "string"}}}
+    });
+    let response = send(
+        authed(Request::post("/v1/schemas"))
+            .header("content-type", "application/json")
+            .body(Body::from(serde_json::to_vec(&create_schema).unwrap()))
+            .unwrap(),
+    )
+    .await
+    .unwrap();
+    assert_eq!(response.status(), StatusCode::CREATED);
+
+    // The LLM mock extracts {"title": "Test"}: the first page still matches,
+    // the second has drifted.
+    let repo = app.db.extraction_repo();
+    let mut ids = Vec::new();
+    for (page, title) in [("a", "Test"), ("b", "Old")] {
+        let id = repo
+            .save(&NewExtraction {
+                url: format!("https://example.com/{page}"),
+                schema_name: "product@1.0.0".into(),
+                extracted_data: serde_json::json!({"title": title}),
+                raw_content_hash: "chash".into(),
+                data_hash: "dhash".into(),
+                model: "model".into(),
+                html_snapshot_key: Some(key.clone()),
+                ..Default::default()
+            })
+            .await
+            .unwrap();
+        ids.push(id);
+    }
+
+    for id in &ids {
+        let response = send(
+            authed(Request::post(format!("/v1/extractions/{id}/golden")))
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        let body = response.into_body().collect().await.unwrap().to_bytes();
+        let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(json["golden"], true);
+    }
+    let missing = uuid::Uuid::new_v4();
+    let response = send(
+        authed(Request::post(format!("/v1/extractions/{missing}/golden")))
+            .body(Body::empty())
+            .unwrap(),
+    )
+    .await
+    .unwrap();
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+
+    let (_, audit) = get_audit_log(&app, "?action=extraction.golden").await;
+    assert_eq!(audit["entries"].as_array().unwrap().len(), 2);
+
+    let verify = |schema: &str| {
+        let body = serde_json::json!({
+            "schema": schema,
+            "provider": "openai",
+            "model": "gpt-4o-mini",
+            "base_url": llm_base_url(&server),
+        });
+        authed(Request::post("/v1/verify"))
+            .header("content-type", "application/json")
+            .body(Body::from(serde_json::to_vec(&body).unwrap()))
+            .unwrap()
+    };
+
+    let response = send(verify("product@1.0.0")).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+    assert_eq!(json["schema_name"], "product@1.0.0");
+    assert_eq!(json["passed"], false);
+    assert_eq!(json["total"], 2);
+    assert_eq!(json["failed"], 1);
+    let results = json["results"].as_array().unwrap();
+    assert_eq!(results[0]["passed"], true);
+    assert_eq!(results[0]["replayed"], true);
+    assert_eq!(results[1]["url"], "https://example.com/b");
+    assert_eq!(results[1]["changes"][0]["path"], "title");
+
+    // Unmarking the drifted page lets the schema pass.
+    let response = send(
+        authed(Request::delete(format!(
+            "/v1/extractions/{}/golden",
+            ids[1]
+        )))
+        .body(Body::empty())
+        .unwrap(),
+    )
+    .await
+    .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    let response = send(verify("product")).await.unwrap();
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+    assert_eq!(json["passed"], true);
+    assert_eq!(json["total"], 1);
+
+    let response = send(verify("missing@1.0.0")).await.unwrap();
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+async fn verify_rejects_more_goldens_than_the_cap() {
+    let (app, server) =
+        setup_test_app_with_wiremock_and(|config| config.with_max_verify_goldens(1)).await;
+    let send = |request: Request<Body>| app.router.clone().oneshot(request);
+    let authed = |builder: axum::http::request::Builder| {
+        builder.header("authorization", format!("Bearer {TEST_API_KEY}"))
+    };
+
+    let create_schema = serde_json::json!({
+        "name": "product",
+        "version": "1.0.0",
+        "schema": {"type": "object", "properties": {"title": {"type": "string"}}}
+    });
+    let response = send(
+        authed(Request::post("/v1/schemas"))
+            .header("content-type", "application/json")
+            .body(Body::from(serde_json::to_vec(&create_schema).unwrap()))
+            .unwrap(),
+    )
+    .await
+    .unwrap();
+    assert_eq!(response.status(), StatusCode::CREATED);
+
+    let repo = app.db.extraction_repo();
+    for page in ["a", "b"] {
+        let id = repo
+            .save(&NewExtraction {
+                url: format!("https://example.com/{page}"),
+                schema_name: "product@1.0.0".into(),
+                extracted_data: serde_json::json!({"title": "Test"}),
+                raw_content_hash: "chash".into(),
+                data_hash: "dhash".into(),
+                model: "model".into(),
+                ..Default::default()
+            })
+            .await
+            .unwrap();
+        repo.set_golden(id, true).await.unwrap();
+    }
+
+    let body = serde_json::json!({
+        "schema": "product@1.0.0",
+        "provider": "openai",
+        "model": "gpt-4o-mini",
+        "base_url": llm_base_url(&server),
+    });
+    let response = send(
+        authed(Request::post("/v1/verify"))
+            .header("content-type", "application/json")
+            .body(Body::from(serde_json::to_vec(&body).unwrap()))
+            .unwrap(),
+    )
+    .await
+    .unwrap();
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+    assert!(
+        json["message"]
+            .as_str()
+            .unwrap()
+            .contains("ARES_VERIFY_MAX_GOLDENS"),
+        "{json}"
+    );
+}
+
+// ---------------------------------------------------------------------------
+// Retry job endpoint
+// ---------------------------------------------------------------------------
+
+/// Helper: create a job and return its ID.
+async fn create_test_job(app: &crate::integration::common::TestApp) -> String {
+    let create_body = serde_json::json!({
+        "url": "https://example.com",
+        "schema_name": "test",
+        "schema": {"type": "object"},
+        "model": "gpt-4o-mini",
+        "base_url": "https://api.openai.com/v1"
+    });
+
+    let response = app
+        .router
+        .clone()
+        .oneshot(
+            Request::post("/v1/jobs")
+                .header("authorization", format!("Bearer {TEST_API_KEY}"))
+                .header("content-type", "application/json")
+                .body(Body::from(serde_json::to_vec(&create_body).unwrap()))
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(response.status(), StatusCode::ACCEPTED);
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+    json["job_id"].as_str().unwrap().to_string()
+}
+
+#[tokio::test]
+async fn retry_cancelled_job() {
+    let app = setup_test_app().await;
+    let job_id = create_test_job(&app).await;
+
+    // Cancel the job
+    let response = app
+        .router
+        .clone()
+        .oneshot(
+            Request::delete(format!("/v1/jobs/{job_id}"))
+                .header("authorization", format!("Bearer {TEST_API_KEY}"))
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::NO_CONTENT);
+
+    // Retry it
+    let response = app
+        .router
+        .clone()
+        .oneshot(
+            Request::post(format!("/v1/jobs/{job_id}/retry"))
+                .header("authorization", format!("Bearer {TEST_API_KEY}"))
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(response.status(), StatusCode::OK);
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+    assert_eq!(json["id"], job_id);
+    assert_eq!(json["status"], "pending");
+    assert_eq!(json["retry_count"], 0);
+    assert!(json["error_message"].is_null());
+}
+
+#[tokio::test]
+async fn retry_pending_job_returns_409() {
+    let app = setup_test_app().await;
+    let job_id = create_test_job(&app).await;
+
+    // Try to retry a pending job — should fail
+    let response = app
+        .router
+        .oneshot(
+            Request::post(format!("/v1/jobs/{job_id}/retry"))
+                .header("authorization", format!("Bearer {TEST_API_KEY}"))
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(response.status(), StatusCode::CONFLICT);
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+    assert_eq!(json["error"], "conflict");
+}
+
+#[tokio::test]
+async fn retry_nonexistent_job_returns_404() {
+    let app = setup_test_app().await;
+
+    let fake_id = uuid::Uuid::new_v4();
+    let response = app
+        .router
+        .oneshot(
+            Request::post(format!("/v1/jobs/{fake_id}/retry"))
+                .header("authorization", format!("Bearer {TEST_API_KEY}"))
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+async fn update_job_schema_patches_only_pending_jobs() {
+    let app = setup_test_app().await;
+    let job_id = create_test_job(&app).await;
+
+    let patch = |id: String, schema: serde_json::Value| {
+        Request::patch(format!("/v1/jobs/{id}/schema"))
+            .header("authorization", format!("Bearer {TEST_API_KEY}"))
+            .header("content-type", "application/json")
+            .body(Body::from(
+                serde_json::to_vec(&serde_json::json!({ "schema": schema })).unwrap(),
+            ))
+            .unwrap()
+    };
+    let fixed = serde_json::json!({
+        "type": "object",
+        "properties": {"title": {"type": "string"}, "author": {"type": "string"}}
+    });
+
+    let response = app
+        .router
+        .clone()
+        .oneshot(patch(job_id.clone(), fixed.clone()))
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+    assert_eq!(json["schema"], fixed);
+    let (_, audit) = get_audit_log(&app, "?action=job.update_schema").await;
+    assert_eq!(audit["entries"].as_array().unwrap().len(), 1);
+
+    let response = app
+        .router
+        .clone()
+        .oneshot(patch(
+            job_id.clone(),
+            serde_json::json!({"type": "not-a-type"}),
+        ))
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::UNPROCESSABLE_ENTITY);
+
+    let response = app
+        .router
+        .clone()
+        .oneshot(patch(uuid::Uuid::new_v4().to_string(), fixed.clone()))
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+
+    // Once a worker claims the job, its schema is fixed.
+    app.db.job_repo().claim_job("worker-1").await.unwrap();
+    let response = app
+        .router
+        .clone()
+        .oneshot(patch(job_id, fixed))
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::CONFLICT);
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+    assert_eq!(json["error"], "conflict");
+}
+
+#[tokio::test]
+async fn job_events_history_lists_recorded_events() {
+    let app = setup_test_app().await;
+    let job_id = create_test_job(&app).await;
+    let id: uuid::Uuid = job_id.parse().unwrap();
+
+    let event = |event_type: &str| NewWorkerEvent {
+        worker_id: "worker-1".to_string(),
+        job_id: Some(id),
+        event_type: event_type.to_string(),
+        detail: serde_json::json!({}),
+    };
+    app.db
+        .worker_event_repo()
+        .insert(&[
+            event("job_claimed"),
+            event("job_started"),
+            event("job_failed"),
+        ])
+        .await
+        .unwrap();
+
+    let response = app
+        .router
+        .clone()
+        .oneshot(
+            Request::get(format!("/v1/jobs/{job_id}/events/history?limit=2"))
+                .header("authorization", format!("Bearer {TEST_API_KEY}"))
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(response.status(), StatusCode::OK);
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+    let types: Vec<_> = json["events"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|e| e["event_type"].as_str().unwrap())
+        .collect();
+    assert_eq!(types, ["job_started", "job_failed"]);
+    assert_eq!(json["events"][0]["worker_id"], "worker-1");
+
+    let response = app
+        .router
+        .oneshot(
+            Request::get(format!("/v1/jobs/{}/events/history", uuid::Uuid::new_v4()))
+                .header("authorization", format!("Bearer {TEST_API_KEY}"))
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+}
+
+// ---------------------------------------------------------------------------
+// Pagination
+// ---------------------------------------------------------------------------
+
+#[tokio::test]
+async fn list_jobs_with_pagination() {
+    let app = setup_test_app().await;
+
+    // Create 3 jobs
+    for _ in 0..3 {
+        create_test_job(&app).await;
+    }
+
+    // First page: limit=2, offset=0
+    let response = app
+        .router
+        .clone()
+        .oneshot(
+            Request::get("/v1/jobs?limit=2&offset=0")
+                .header("authorization", format!("Bearer {TEST_API_KEY}"))
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(response.status(), StatusCode::OK);
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+    assert_eq!(json["total"], 3);
+    assert_eq!(json["limit"], 2);
+    assert_eq!(json["offset"], 0);
+    assert_eq!(json["jobs"].as_array().unwrap().len(), 2);
+
+    // Second page: limit=2, offset=2
+    let response = app
+        .router
+        .clone()
+        .oneshot(
+            Request::get("/v1/jobs?limit=2&offset=2")
+                .header("authorization", format!("Bearer {TEST_API_KEY}"))
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(response.status(), StatusCode::OK);
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+    assert_eq!(json["total"], 3);
+    assert_eq!(json["limit"], 2);
+    assert_eq!(json["offset"], 2);
+    assert_eq!(json["jobs"].as_array().unwrap().len(), 1);
+}
+
+// ---------------------------------------------------------------------------
+// Admin: circuit breakers
+// ---------------------------------------------------------------------------
+
+#[tokio::test]
+async fn reset_open_circuit_breaker() {
+    use ares_core::circuit_breaker::{CircuitBreaker, CircuitBreakerConfig};
+    use ares_core::job_queue::JobQueue;
+
+    let app = setup_test_app().await;
+
+    // A worker's breaker trips and publishes its state through the queue.
+    let config = CircuitBreakerConfig {
+        failure_threshold: 1,
+        ..Default::default()
+    };
+    let cb = CircuitBreaker::new("llm", config);
+    cb.record_failure(&ares_core::AppError::NetworkError("timeout".into()));
+    let queue = app.db.job_repo();
+    queue.save_circuit_breaker(&cb.stats()).await.unwrap();
+
+    let response = app
+        .router
+        .clone()
+        .oneshot(
+            Request::get("/v1/admin/circuit-breakers")
+                .header("authorization", format!("Bearer {TEST_API_KEY}"))
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(response.status(), StatusCode::OK);
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+    let breakers = json["circuit_breakers"].as_array().unwrap();
+    assert_eq!(breakers.len(), 1);
+    assert_eq!(breakers[0]["name"], "llm");
+    assert_eq!(breakers[0]["state"], "open");
+    assert_eq!(breakers[0]["failure_count"], 1);
+    assert!(breakers[0]["retry_after_secs"].is_u64());
+
+    let response = app
+        .router
+        .clone()
+        .oneshot(
+            Request::post("/v1/admin/circuit-breakers/llm/reset")
+                .header("authorization", format!("Bearer {TEST_API_KEY}"))
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(response.status(), StatusCode::OK);
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+    assert_eq!(json["state"], "closed");
+    assert_eq!(json["failure_count"], 0);
+    assert!(json["last_error"].is_null());
+
+    // Workers see the request on their next poll.
+    assert!(
+        queue
+            .circuit_breaker_reset_requested_at("llm")
+            .await
+            .unwrap()
+            .is_some()
+    );
+}
+
+#[tokio::test]
+async fn reset_unknown_circuit_breaker_returns_404() {
+    let app = setup_test_app().await;
+
+    let response = app
+        .router
+        .oneshot(
+            Request::post("/v1/admin/circuit-breakers/nope/reset")
+                .header("authorization", format!("Bearer {TEST_API_KEY}"))
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+}
+
+// ---------------------------------------------------------------------------
+// Admin: audit log
+// ---------------------------------------------------------------------------
+
+async fn get_audit_log(app: &TestApp, query: &str) -> (StatusCode, serde_json::Value) {
+    let response = app
+        .router
+        .clone()
+        .oneshot(
+            Request::get(format!("/v1/admin/audit{query}"))
+                .header("authorization", format!("Bearer {TEST_API_KEY}"))
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    let status = response.status();
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    (status, serde_json::from_slice(&body).unwrap())
+}
+
+#[tokio::test]
+async fn schema_changes_are_audited_with_redacted_summaries() {
+    use ares_core::api_key::ApiKeyScope;
+    use ares_core::audit::token_fingerprint;
+
+    let app = setup_test_app().await;
+    let (key, key_value) = app
+        .db
+        .api_key_repo()
+        .create("schema-admin", &[ApiKeyScope::SchemasWrite])
+        .await
+        .unwrap();
+
+    let create_body = serde_json::json!({
+        "name": "blog",
+        "version": "1.0.0",
+        "schema": {
+            "type": "object",
+            "properties": {
+                "author_email": {"type": "string", "examples": ["jane@example.com"]}
+            }
+        }
+    });
+    let response = app
+        .router
+        .clone()
+        .oneshot(
+            Request::post("/v1/schemas")
+                .header("authorization", format!("Bearer {TEST_API_KEY}"))
+                .header("content-type", "application/json")
+                .body(Body::from(serde_json::to_vec(&create_body).unwrap()))
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::CREATED);
+
+    let response = app
+        .router
+        .clone()
+        .oneshot(
+            Request::delete("/v1/schemas/blog/1.0.0")
+                .header("authorization", format!("Bearer {key_value}"))
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::NO_CONTENT);
+
+    let (status, json) = get_audit_log(&app, "").await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(json["total"], 2);
+    let entries = json["entries"].as_array().unwrap();
+
+    // Newest first; each names its actor.
+    assert_eq!(entries[0]["action"], "schema.delete");
+    assert_eq!(entries[0]["resource_id"], "blog@1.0.0");
+    assert_eq!(entries[0]["api_key_id"], key.id.to_string());
+    assert!(entries[0]["token_fingerprint"].is_null());
+
+    assert_eq!(entries[1]["action"], "schema.create");
+    assert_eq!(entries[1]["resource_type"], "schema");
+    assert_eq!(
+        entries[1]["token_fingerprint"],
+        token_fingerprint(TEST_API_KEY)
+    );
+    assert!(entries[1]["request_id"].is_string());
+    assert_eq!(
+        entries[1]["summary"]["schema"]["properties"],
+        serde_json::json!(["author_email"])
+    );
+    assert!(!json.to_string().contains("jane@example.com"));
+
+    let (_, json) = get_audit_log(&app, "?action=schema.create").await;
+    assert_eq!(json["total"], 1);
+    let (_, json) = get_audit_log(&app, "?since=2999-01-01T00:00:00Z").await;
+    assert_eq!(json["total"], 0);
+
+    let (status, json) = get_audit_log(&app, "?action=schema.read").await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert!(
+        json["message"]
+            .as_str()
+            .unwrap()
+            .contains("Unknown audit action")
+    );
+}
+
+// ---------------------------------------------------------------------------
+// Conditional requests and compression
+// ---------------------------------------------------------------------------
+
+async fn get_with_etag(
+    app: &crate::integration::common::TestApp,
+    uri: &str,
+    if_none_match: Option<&str>,
+) -> axum::response::Response {
+    let mut request = Request::get(uri).header("authorization", format!("Bearer {TEST_API_KEY}"));
+    if let Some(etag) = if_none_match {
+        request = request.header("if-none-match", etag);
+    }
+    app.router
+        .clone()
+        .oneshot(request.body(Body::empty()).unwrap())
+        .await
+        .unwrap()
+}
+
+fn etag_of(response: &axum::response::Response) -> String {
+    response.headers()["etag"].to_str().unwrap().to_string()
+}
+
+#[tokio::test]
+async fn get_job_honours_if_none_match() {
+    let app = setup_test_app().await;
+    let job_id = create_test_job(&app).await;
+    let uri = format!("/v1/jobs/{job_id}");
+
+    let response = get_with_etag(&app, &uri, None).await;
+    assert_eq!(response.status(), StatusCode::OK);
+    let etag = etag_of(&response);
+    assert!(etag.starts_with("W/\""));
+
+    let response = get_with_etag(&app, &uri, Some(&etag)).await;
+    assert_eq!(response.status(), StatusCode::NOT_MODIFIED);
+    assert_eq!(etag_of(&response), etag);
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    assert!(body.is_empty());
+
+    // Cancelling the job changes its representation.
+    let response = app
+        .router
+        .clone()
+        .oneshot(
+            Request::delete(&uri)
+                .header("authorization", format!("Bearer {TEST_API_KEY}"))
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::NO_CONTENT);
+
+    let response = get_with_etag(&app, &uri, Some(&etag)).await;
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_ne!(etag_of(&response), etag);
+}
+
+#[tokio::test]
+async fn get_schema_and_extractions_honour_if_none_match() {
+    let app = setup_test_app().await;
+    std::fs::create_dir_all(app.schemas_dir.join("blog")).unwrap();
+    std::fs::write(
+        app.schemas_dir.join("blog/1.0.0.json"),
+        r#"{"type": "object"}"#,
+    )
+    .unwrap();
+
+    for uri in [
+        "/v1/schemas/blog/1.0.0",
+        "/v1/extractions?url=https://example.com&schema_name=blog",
+    ] {
+        let response = get_with_etag(&app, uri, None).await;
+        assert_eq!(response.status(), StatusCode::OK, "{uri}");
+        let etag = etag_of(&response);
+
+        let response = get_with_etag(&app, uri, Some(&etag)).await;
+        assert_eq!(response.status(), StatusCode::NOT_MODIFIED, "{uri}");
+
+        let response = get_with_etag(&app, uri, Some("W/\"stale\"")).await;
+        assert_eq!(response.status(), StatusCode::OK, "{uri}");
+    }
+}
+
+#[tokio::test]
+async fn extraction_history_etag_tracks_golden_flag() {
+    use ares_core::models::NewExtraction;
+
+    let app = setup_test_app().await;
+    let id = app
+        .db
+        .extraction_repo()
+        .save(&NewExtraction {
+            url: "https://example.com".into(),
+            schema_name: "blog".into(),
+            extracted_data: serde_json::json!({"title": "Test"}),
+            raw_content_hash: "chash".into(),
+            data_hash: "dhash".into(),
+            model: "model".into(),
+            ..Default::default()
+        })
+        .await
+        .unwrap();
+    let uri = "/v1/extractions?url=https://example.com&schema_name=blog";
+
+    let response = get_with_etag(&app, uri, None).await;
+    let etag = etag_of(&response);
+
+    app.db
+        .extraction_repo()
+        .set_golden(id, true)
+        .await
+        .unwrap()
+        .unwrap();
+    let response = get_with_etag(&app, uri, Some(&etag)).await;
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_ne!(etag_of(&response), etag);
+}
+
+#[tokio::test]
+async fn extraction_history_etag_tracks_scrubbing() {
+    use ares_core::models::NewExtraction;
+    use ares_core::retention::RetentionPolicy;
+
+    let app = setup_test_app().await;
+    app.db
+        .extraction_repo()
+        .save(&NewExtraction {
+            url: "https://example.com".into(),
+            schema_name: "people".into(),
+            extracted_data: serde_json::json!({"email": "ada@example.com"}),
+            raw_content_hash: "chash".into(),
+            data_hash: "dhash".into(),
+            model: "model".into(),
+            retention: Some(RetentionPolicy {
+                days: 30,
+                scrub_fields: vec!["/email".into()],
+                scrub_on_save: false,
+            }),
+            ..Default::default()
+        })
+        .await
+        .unwrap();
+    let uri = "/v1/extractions?url=https://example.com&schema_name=people";
+
+    let response = get_with_etag(&app, uri, None).await;
+    let etag = etag_of(&response);
+
+    // Scrubbing rewrites the data but keeps its hash.
+    let now = chrono::Utc::now() + chrono::Duration::days(31);
+    let report = app.db.extraction_repo().apply_retention(now).await.unwrap();
+    assert_eq!(report.scrubbed, 1);
+    let response = get_with_etag(&app, uri, Some(&etag)).await;
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_ne!(etag_of(&response), etag);
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+    assert_eq!(
+        json["extractions"][0]["extracted_data"]["email"],
+        serde_json::Value::Null
+    );
+    assert!(json["extractions"][0]["scrubbed_at"].is_string());
+}
+
+#[tokio::test]
+async fn large_responses_are_compressed_when_accepted() {
+    let app = setup_test_app().await;
+
+    let response = app
+        .router
+        .clone()
+        .oneshot(
+            Request::get("/api-docs/openapi.json")
+                .header("accept-encoding", "gzip")
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(response.headers()["content-encoding"], "gzip");
+
+    let response = app
+        .router
+        .oneshot(
+            Request::get("/api-docs/openapi.json")
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert!(response.headers().get("content-encoding").is_none());
+}
+
+fn preflight(origin: &str, method: &str, headers: &str) -> Request<Body> {
+    Request::options("/v1/extractions")
+        .header("origin", origin)
+        .header("access-control-request-method", method)
+        .header("access-control-request-headers", headers)
+        .body(Body::empty())
+        .unwrap()
+}
+
+#[tokio::test]
+async fn cors_preflight_uses_the_default_methods_and_headers() {
+    let origins = CorsOrigins::parse("https://app.example").unwrap();
+    let app = setup_test_app_with(|config| config.with_cors(CorsConfig::new(origins))).await;
+
+    let response = app
+        .router
+        .oneshot(preflight(
+            "https://app.example",
+            "DELETE",
+            "authorization,content-type",
+        ))
+        .await
+        .unwrap();
+
+    assert_eq!(response.status(), StatusCode::OK);
+    let headers = response.headers();
+    assert_eq!(
+        headers["access-control-allow-origin"],
+        "https://app.example"
+    );
+    assert_eq!(
+        headers["access-control-allow-methods"],
+        "GET,POST,PUT,PATCH,DELETE"
+    );
+    assert_eq!(
+        headers["access-control-allow-headers"],
+        "authorization,content-type,if-none-match,x-api-key"
+    );
+    assert!(!headers.contains_key("access-control-allow-credentials"));
+    assert!(!headers.contains_key("access-control-max-age"));
+}
+
+#[tokio::test]
+async fn cors_wildcard_origin_allows_any_method_and_exposes_headers() {
+    let app =
+        setup_test_app_with(|config| config.with_cors(CorsConfig::new(CorsOrigins::Any))).await;
+
+    let response = app
+        .router
+        .clone()
+        .oneshot(preflight("https://app.example", "PATCH", "x-trace"))
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    let headers = response.headers();
+    assert_eq!(headers["access-control-allow-origin"], "*");
+    assert_eq!(headers["access-control-allow-methods"], "*");
+    assert_eq!(headers["access-control-allow-headers"], "*");
+
+    let response = app
+        .router
+        .oneshot(
+            Request::get("/health")
+                .header("origin", "https://app.example")
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(
+        response.headers()["access-control-expose-headers"],
+        "etag,retry-after,x-request-id"
+    );
+}
+
+#[tokio::test]
+async fn cors_preflight_reflects_configured_policy() {
+    let cors = CorsConfig::new(CorsOrigins::parse("https://app.example").unwrap())
+        .with_methods(CorsConfig::parse_methods("get,put").unwrap())
+        .with_headers(CorsConfig::parse_headers("authorization,x-trace").unwrap())
+        .with_credentials(true)
+        .with_max_age(Duration::from_secs(600));
+    let app = setup_test_app_with(|config| config.with_cors(cors)).await;
+
+    let response = app
+        .router
+        .clone()
+        .oneshot(preflight("https://app.example", "PUT", "x-trace"))
+        .await
+        .unwrap();
+
+    assert_eq!(response.status(), StatusCode::OK);
+    let headers = response.headers();
+    assert_eq!(headers["access-control-allow-methods"], "GET,PUT");
+    assert_eq!(
+        headers["access-control-allow-headers"],
+        "authorization,x-trace"
+    );
+    assert_eq!(headers["access-control-allow-credentials"], "true");
+    assert_eq!(headers["access-control-max-age"], "600");
+
+    let response = app
+        .router
+        .oneshot(preflight("https://evil.example", "PUT", "x-trace"))
+        .await
+        .unwrap();
+    assert!(
+        !response
+            .headers()
+            .contains_key("access-control-allow-origin")
+    );
+}
+
+// ---------------------------------------------------------------------------
+// Admin: LLM usage
+// ---------------------------------------------------------------------------
+
+#[tokio::test]
+async fn scrapes_stop_once_the_daily_budget_is_used_up() {
+    use ares_core::budget::BudgetLimits;
+
+    let (app, server) = setup_test_app_with_wiremock_and(|config| {
+        config.with_budget(BudgetLimits::default().with_max_calls(1))
+    })
+    .await;
+
+    let first = app
+        .router
+        .clone()
+        .oneshot(mock_llm_scrape_request(&server))
+        .await
+        .unwrap();
+    assert_eq!(first.status(), StatusCode::OK);
+
+    let second = app
+        .router
+        .clone()
+        .oneshot(mock_llm_scrape_request(&server))
+        .await
+        .unwrap();
+    assert_eq!(second.status(), StatusCode::TOO_MANY_REQUESTS);
+    assert!(second.headers().contains_key("retry-after"));
+    let body = second.into_body().collect().await.unwrap().to_bytes();
+    let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+    assert_eq!(json["error"], "budget_exceeded");
+    // The refused scrape never reached the LLM.
+    assert_eq!(server.received_requests().await.unwrap().len(), 1);
+
+    let response = app
+        .router
+        .clone()
+        .oneshot(
+            Request::get("/v1/admin/usage")
+                .header("authorization", format!("Bearer {TEST_API_KEY}"))
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    let usage: serde_json::Value = serde_json::from_slice(&body).unwrap();
+    assert_eq!(usage["llm_calls"], 1);
+    assert_eq!(usage["limits"]["max_calls"], 1);
+    assert!(usage["limits"]["max_tokens"].is_null());
+    assert_eq!(usage["exceeded"], "1 of 1 daily LLM calls used");
+}
+
+#[tokio::test]
+async fn scraping_a_mirror_reuses_the_original_extraction() {
+    let (app, server) = setup_test_app_with_wiremock_and(|config| {
+        config.with_dedup(Duration::from_secs(3600), true)
+    })
+    .await;
+
+    let mut scraped = Vec::new();
+    for url in [
+        "https://example.com/post",
+        "https://mirror.example.com/post?utm_source=feed",
+    ] {
+        let request = scrape_request(serde_json::json!({
+            "url": url,
+            "save": true,
+            "html": "<html><h1>Test</h1></html>",
+            "provider": "openai",
+            "model": "gpt-4o-mini",
+            "base_url": llm_base_url(&server),
+        }));
+        let response = app.router.clone().oneshot(request).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        let body = response.into_body().collect().await.unwrap().to_bytes();
+        scraped.push(serde_json::from_slice::<serde_json::Value>(&body).unwrap());
+    }
+
+    let (original, mirror) = (&scraped[0], &scraped[1]);
+    assert!(original["duplicate_of"].is_null());
+    assert_eq!(mirror["duplicate_of"], original["extraction_id"]);
+    assert_eq!(mirror["extracted_data"], original["extracted_data"]);
+    // The mirror was served from the original extraction, not the LLM.
+    assert_eq!(server.received_requests().await.unwrap().len(), 1);
+
+    let mirror_id: uuid::Uuid = serde_json::from_value(mirror["extraction_id"].clone()).unwrap();
+    let saved = app
+        .db
+        .extraction_repo()
+        .get(mirror_id)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(
+        saved.duplicate_of.map(|id| id.to_string()).as_deref(),
+        original["extraction_id"].as_str()
+    );
 }
@@ -1,5 +1,5 @@
 use std::path::PathBuf;
-use std::sync::Arc;
+use std::time::Duration;
 
 use axum::Router;
 use sqlx::PgPool;
@@ -8,76 +8,113 @@ use tempfile::TempDir;
 use testcontainers::core::{ContainerPort, WaitFor};
 use testcontainers::runners::AsyncRunner;
 use testcontainers::{ContainerAsync, GenericImage, ImageExt};
+use wiremock::matchers::{method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
 
-use ares_api::routes;
-use ares_api::state::AppState;
-use ares_db::Database;
+use ares_api::rate_limit::{RateLimitConfig, RateLimitTier};
+use ares_api::{ServerConfig, build_app};
+use ares_db::{Database, DatabaseConfig};
 
 pub const TEST_API_KEY: &str = "test-secret-key";
 
+/// Upstream LLM key the scrape routes authenticate with; the mock LLM server
+/// ignores it.
+const TEST_LLM_API_KEY: &str = "test-llm-key";
+
 /// Test app handle that keeps the temporary schemas directory alive.
 pub struct TestApp {
     pub router: Router,
+    pub db: Database,
     pub schemas_dir: PathBuf,
     _container: ContainerAsync<GenericImage>,
     _tmp_dir: TempDir,
 }
 
+impl TestApp {
+    /// Stop the PostgreSQL container, simulating a database outage.
+    pub async fn stop_database(&self) {
+        self._container
+            .stop()
+            .await
+            .expect("Failed to stop PostgreSQL container");
+    }
+}
+
 /// Spin up a PostgreSQL container and return the test app.
 pub async fn setup_test_app() -> TestApp {
-    let tmp_dir = TempDir::new().expect("Failed to create temp dir");
-    let schemas_dir = tmp_dir.path().join("schemas");
-    std::fs::create_dir_all(&schemas_dir).expect("Failed to create schemas dir");
+    setup(Some(TEST_API_KEY), |config| config).await
+}
 
-    let container = start_postgres().await;
-    let pool = connect_to_container(&container).await;
-    let db = Database::from_pool(pool);
-    db.migrate().await.expect("Failed to run migrations");
+/// Spin up a PostgreSQL container with no admin token configured (admin endpoints return 403).
+pub async fn setup_test_app_no_auth() -> TestApp {
+    setup(None, |config| config).await
+}
 
-    let state = Arc::new(AppState {
-        db,
-        admin_token: Some(TEST_API_KEY.to_string()),
-        schemas_dir: schemas_dir.clone(),
-        proxy_config: None,
-        random_ua: false,
-        browser: false,
-        stealth: false,
-        tls_backend: ares_core::proxy::TlsBackend::default(),
-    });
+/// Like [`setup_test_app`], with `configure` applied to the server config.
+pub async fn setup_test_app_with(configure: impl FnOnce(ServerConfig) -> ServerConfig) -> TestApp {
+    setup(Some(TEST_API_KEY), configure).await
+}
 
-    TestApp {
-        router: routes::router(state),
-        schemas_dir,
-        _container: container,
-        _tmp_dir: tmp_dir,
-    }
+/// Like [`setup_test_app`], plus a mock OpenAI-compatible server whose
+/// `POST /v1/chat/completions` extracts `{"title": "Test"}`. Point scrape
+/// requests at it with [`llm_base_url`]; mount a higher-priority mock to
+/// simulate upstream failures.
+pub async fn setup_test_app_with_wiremock() -> (TestApp, MockServer) {
+    setup_test_app_with_wiremock_and(|config| config).await
 }
 
-/// Spin up a PostgreSQL container with no admin token configured (admin endpoints return 403).
-pub async fn setup_test_app_no_auth() -> TestApp {
+/// Like [`setup_test_app_with_wiremock`], with `configure` applied to the
+/// server config.
+pub async fn setup_test_app_with_wiremock_and(
+    configure: impl FnOnce(ServerConfig) -> ServerConfig,
+) -> (TestApp, MockServer) {
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/v1/chat/completions"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "choices": [{"message": {"content": "{\"title\": \"Test\"}"}}]
+        })))
+        .mount(&server)
+        .await;
+
+    let app =
+        setup_test_app_with(|config| configure(config.with_llm_api_key(TEST_LLM_API_KEY))).await;
+    (app, server)
+}
+
+/// The OpenAI-style base URL served by a [`setup_test_app_with_wiremock`] server.
+pub fn llm_base_url(server: &MockServer) -> String {
+    format!("{}/v1", server.uri())
+}
+
+async fn setup(
+    admin_token: Option<&str>,
+    configure: impl FnOnce(ServerConfig) -> ServerConfig,
+) -> TestApp {
     let tmp_dir = TempDir::new().expect("Failed to create temp dir");
     let schemas_dir = tmp_dir.path().join("schemas");
     std::fs::create_dir_all(&schemas_dir).expect("Failed to create schemas dir");
 
     let container = start_postgres().await;
-    let pool = connect_to_container(&container).await;
-    let db = Database::from_pool(pool);
+    let url = container_url(&container).await;
+    let db = Database::from_pool(retry_connect(&url).await);
     db.migrate().await.expect("Failed to run migrations");
 
-    let state = Arc::new(AppState {
-        db,
-        admin_token: None,
-        schemas_dir,
-        proxy_config: None,
-        random_ua: false,
-        browser: false,
-        stealth: false,
-        tls_backend: ares_core::proxy::TlsBackend::default(),
-    });
+    // Tests issue many requests from one in-process "peer"; keep rate
+    // limiting out of the way.
+    let tier = RateLimitTier::new(10_000, Duration::from_secs(1));
+    let mut config = ServerConfig::new(DatabaseConfig::new(url))
+        .with_schemas_dir(&schemas_dir)
+        .with_rate_limit(RateLimitConfig::new(tier, tier));
+    if let Some(token) = admin_token {
+        config = config.with_admin_token(token);
+    }
+    let config = configure(config);
 
     TestApp {
-        router: routes::router(state),
-        schemas_dir: tmp_dir.path().join("schemas"),
+        router: build_app(config, db.clone(), Default::default()).expect("Failed to build app"),
+        db,
+        schemas_dir,
         _container: container,
         _tmp_dir: tmp_dir,
     }
@@ -96,15 +133,14 @@ async fn start_postgres() -> ContainerAsync<GenericImage> {
         .expect("Failed to start PostgreSQL container")
 }
 
-async fn connect_to_container(container: &ContainerAsync<GenericImage>) -> PgPool {
+async fn container_url(container: &ContainerAsync<GenericImage>) -> String {
     let host = container.get_host().await.expect("Failed to get host");
     let port = container
         .get_host_port_ipv4(5432)
         .await
         .expect("Failed to get port");
 
-    let url = format!("postgresql://postgres:postgres@{host}:{port}/ares_test");
-    retry_connect(&url).await
+    format!("postgresql://postgres:postgres@{host}:{port}/ares_test")
 }
 
 async fn retry_connect(url: &str) -> PgPool {
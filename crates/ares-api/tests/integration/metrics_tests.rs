@@ -0,0 +1,120 @@
+use axum::Router;
+use axum::body::Body;
+use axum::http::{Request, StatusCode, header};
+use axum::routing::get;
+use http_body_util::BodyExt;
+use tower::ServiceExt;
+
+use ares_api::metrics;
+
+/// Router with a tracked handler plus the metrics endpoint, backed by the
+/// process-wide recorder.
+fn metrics_router() -> Router {
+    let handle = metrics::install_recorder().unwrap();
+    Router::new()
+        .route("/ping/{id}", get(|| async { "pong" }))
+        .layer(axum::middleware::from_fn(metrics::track_http))
+        .merge(metrics::router(handle))
+}
+
+async fn get_body(app: &Router, uri: &str) -> (StatusCode, Option<String>, String) {
+    let response = app
+        .clone()
+        .oneshot(Request::get(uri).body(Body::empty()).unwrap())
+        .await
+        .unwrap();
+    let status = response.status();
+    let content_type = response
+        .headers()
+        .get(header::CONTENT_TYPE)
+        .map(|v| v.to_str().unwrap().to_string());
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    (
+        status,
+        content_type,
+        String::from_utf8(body.to_vec()).unwrap(),
+    )
+}
+
+/// Check one line of the Prometheus text format: a `# HELP`/`# TYPE`
+/// comment, or `name{labels} value` with a valid metric name and float value.
+fn assert_valid_line(line: &str) {
+    if let Some(comment) = line.strip_prefix("# ") {
+        let mut parts = comment.splitn(3, ' ');
+        let kind = parts.next().unwrap();
+        assert!(kind == "HELP" || kind == "TYPE", "bad comment: {line}");
+        assert!(
+            parts.next().is_some_and(|n| !n.is_empty()),
+            "bad comment: {line}"
+        );
+        if kind == "TYPE" {
+            let ty = parts.next().unwrap_or_default();
+            assert!(
+                ["counter", "gauge", "histogram", "summary", "untyped"].contains(&ty),
+                "bad type: {line}"
+            );
+        }
+        return;
+    }
+
+    let (series, value) = line.rsplit_once(' ').expect("sample has a value");
+    assert!(
+        value.parse::<f64>().is_ok() || ["+Inf", "-Inf", "NaN"].contains(&value),
+        "bad value: {line}"
+    );
+
+    let name = match series.split_once('{') {
+        Some((name, labels)) => {
+            assert!(labels.ends_with('}'), "unterminated labels: {line}");
+            name
+        }
+        None => series,
+    };
+    assert!(
+        !name.is_empty()
+            && !name.starts_with(|c: char| c.is_ascii_digit())
+            && name
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == ':'),
+        "bad metric name: {line}"
+    );
+}
+
+#[tokio::test]
+async fn metrics_endpoint_renders_exposition_format() {
+    let app = metrics_router();
+
+    let (status, _, _) = get_body(&app, "/ping/1").await;
+    assert_eq!(status, StatusCode::OK);
+    get_body(&app, "/ping/2").await;
+    metrics::record_job_count(ares_core::job::JobStatus::Pending, 3);
+
+    let (status, content_type, body) = get_body(&app, "/metrics").await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(content_type.as_deref(), Some(metrics::CONTENT_TYPE));
+
+    for line in body.lines().filter(|l| !l.is_empty()) {
+        assert_valid_line(line);
+    }
+
+    assert!(body.contains("# TYPE ares_http_requests_total counter"));
+    assert!(body.contains("# TYPE ares_http_request_duration_seconds histogram"));
+    // Both requests share the matched route pattern
+    assert!(
+        body.contains(
+            r#"ares_http_requests_total{method="GET",route="/ping/{id}",status="200"} 2"#
+        )
+    );
+    assert!(body.contains("ares_jobs_pending 3"));
+}
+
+#[tokio::test]
+async fn unmatched_paths_share_one_label() {
+    let app = metrics_router();
+
+    let (status, _, _) = get_body(&app, "/no/such/path").await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+
+    let (_, _, body) = get_body(&app, "/metrics").await;
+    assert!(!body.contains("/no/such/path"));
+}
@@ -0,0 +1,133 @@
+use std::time::Duration;
+
+use axum::Router;
+use axum::body::Body;
+use axum::http::{Request, StatusCode, header};
+use http_body_util::BodyExt;
+use tower::ServiceExt;
+
+use ares_api::rate_limit::{RateLimitConfig, RateLimitTier};
+use ares_core::api_key::ApiKeyScope;
+use ares_core::compute_hash;
+
+use crate::integration::common::{TEST_API_KEY, TestApp, setup_test_app_with};
+
+/// Test app with `config` as its rate limits. Requests are sent in-process,
+/// so they all appear to come from the same peer, as they would behind a NAT.
+async fn limited_app(config: RateLimitConfig) -> TestApp {
+    setup_test_app_with(|c| c.with_rate_limit(config)).await
+}
+
+fn tier(burst: u32) -> RateLimitTier {
+    RateLimitTier::new(burst, Duration::from_secs(60))
+}
+
+async fn ping(app: &Router, token: Option<&str>) -> axum::response::Response {
+    let mut request = Request::get("/health");
+    if let Some(token) = token {
+        request = request.header(header::AUTHORIZATION, format!("Bearer {token}"));
+    }
+    app.clone()
+        .oneshot(request.body(Body::empty()).unwrap())
+        .await
+        .unwrap()
+}
+
+#[tokio::test]
+async fn exceeded_limit_returns_429_with_retry_after() {
+    let app = limited_app(RateLimitConfig::new(tier(2), tier(2))).await;
+
+    for _ in 0..2 {
+        let status = ping(&app.router, Some(TEST_API_KEY)).await.status();
+        assert_eq!(status, StatusCode::OK);
+    }
+
+    let response = ping(&app.router, Some(TEST_API_KEY)).await;
+    assert_eq!(response.status(), StatusCode::TOO_MANY_REQUESTS);
+
+    let retry_after: u64 = response.headers()[header::RETRY_AFTER]
+        .to_str()
+        .unwrap()
+        .parse()
+        .unwrap();
+    assert!((1..=60).contains(&retry_after));
+
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+    assert_eq!(json["error"], "rate_limit_exceeded");
+    assert!(json["message"].as_str().unwrap().contains("Retry after"));
+}
+
+#[tokio::test]
+async fn api_keys_behind_same_ip_have_separate_budgets() {
+    let app = limited_app(RateLimitConfig::new(tier(1), tier(1))).await;
+    let (_, quiet) = app
+        .db
+        .api_key_repo()
+        .create("quiet", &[ApiKeyScope::Scrape])
+        .await
+        .unwrap();
+
+    assert_eq!(
+        ping(&app.router, Some(TEST_API_KEY)).await.status(),
+        StatusCode::OK
+    );
+    assert_eq!(
+        ping(&app.router, Some(TEST_API_KEY)).await.status(),
+        StatusCode::TOO_MANY_REQUESTS
+    );
+
+    // A different key from the same peer IP is unaffected.
+    assert_eq!(
+        ping(&app.router, Some(&quiet)).await.status(),
+        StatusCode::OK
+    );
+    // So are unauthenticated requests, which use the per-IP budget.
+    assert_eq!(ping(&app.router, None).await.status(), StatusCode::OK);
+}
+
+#[tokio::test]
+async fn unauthenticated_requests_are_limited_per_ip() {
+    let app = limited_app(RateLimitConfig::new(tier(5), tier(1))).await;
+
+    assert_eq!(ping(&app.router, None).await.status(), StatusCode::OK);
+    assert_eq!(
+        ping(&app.router, None).await.status(),
+        StatusCode::TOO_MANY_REQUESTS
+    );
+}
+
+#[tokio::test]
+async fn invalid_tokens_share_the_per_ip_budget() {
+    let app = limited_app(RateLimitConfig::new(tier(5), tier(1))).await;
+
+    assert_eq!(
+        ping(&app.router, Some("made-up-1")).await.status(),
+        StatusCode::OK
+    );
+    // A fresh random token doesn't buy a fresh budget.
+    assert_eq!(
+        ping(&app.router, Some("made-up-2")).await.status(),
+        StatusCode::TOO_MANY_REQUESTS
+    );
+    assert_eq!(
+        ping(&app.router, None).await.status(),
+        StatusCode::TOO_MANY_REQUESTS
+    );
+}
+
+#[tokio::test]
+async fn key_override_grants_larger_budget() {
+    let config = RateLimitConfig::new(tier(1), tier(1))
+        .with_key_override(compute_hash(TEST_API_KEY), tier(3));
+    let app = limited_app(config).await;
+
+    for _ in 0..3 {
+        let status = ping(&app.router, Some(TEST_API_KEY)).await.status();
+        assert_eq!(status, StatusCode::OK);
+    }
+    assert_eq!(
+        ping(&app.router, Some(TEST_API_KEY)).await.status(),
+        StatusCode::TOO_MANY_REQUESTS
+    );
+}
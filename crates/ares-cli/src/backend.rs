@@ -0,0 +1,283 @@
+//! Where the job, history, and schema commands read and write: the local
+//! database and schema directory, or an Ares server when `--remote` is set.
+//!
+//! Both sides return the same core types, so each command renders its
+//! output once regardless of mode.
+
+use std::path::Path;
+use std::time::Duration;
+
+use anyhow::Result;
+use uuid::Uuid;
+
+use ares_client::{AresApiClient, SitemapLoader, SitemapOptions};
+use ares_core::error::AppError;
+use ares_core::event_log::WorkerEventRecord;
+use ares_core::job::{CreateScrapeJobRequest, JobStatus, ScrapeJob};
+use ares_core::job_queue::JobQueue;
+use ares_core::models::{Extraction, HistoryEntry};
+use ares_core::{SchemaEntry, SchemaResolver};
+use ares_db::Database;
+
+use crate::config::CliConfig;
+
+/// Jobs and extraction history.
+pub enum Backend {
+    Local(Database),
+    Remote(AresApiClient),
+}
+
+impl Backend {
+    /// The server behind `remote`, or the local database (migrated) when it
+    /// is `None`.
+    pub async fn connect(remote: Option<AresApiClient>, config: &CliConfig) -> Result<Self> {
+        if let Some(api) = remote {
+            return Ok(Self::Remote(api));
+        }
+        let db = Database::connect(&config.database()?).await?;
+        db.migrate().await?;
+        Ok(Self::Local(db))
+    }
+
+    pub async fn create_job(&self, request: CreateScrapeJobRequest) -> Result<Uuid> {
+        Ok(match self {
+            Self::Local(db) => db.job_repo().create_job(request).await?.id,
+            Self::Remote(api) => api.create_job(&request).await?,
+        })
+    }
+
+    /// Queue one job per URL of the sitemap at `sitemap_url`, each like
+    /// `template` but for its URL. Returns the new job IDs and the number
+    /// of URLs skipped as unmodified.
+    pub async fn create_jobs_from_sitemap(
+        &self,
+        sitemap_url: &str,
+        options: &SitemapOptions,
+        template: CreateScrapeJobRequest,
+    ) -> Result<(Vec<Uuid>, usize)> {
+        let db = match self {
+            Self::Local(db) => db,
+            Self::Remote(api) => {
+                let response = api
+                    .create_jobs_from_sitemap(sitemap_url, options, &template)
+                    .await?;
+                return Ok((response.job_ids, response.skipped));
+            }
+        };
+
+        let loader = SitemapLoader::new()?
+            .allow_private_urls()
+            .with_options(options)?;
+        let mut entries = loader.load(sitemap_url).await?;
+        let found = entries.len();
+        if options.skip_unmodified {
+            let urls: Vec<String> = entries.iter().map(|entry| entry.url.clone()).collect();
+            let latest = db
+                .extraction_repo()
+                .latest_extracted_at(&urls, &template.schema_name)
+                .await?;
+            entries.retain(|entry| {
+                latest
+                    .get(&entry.url)
+                    .is_none_or(|&extracted_at| entry.modified_since(extracted_at))
+            });
+        }
+        let skipped = found - entries.len();
+
+        let requests = entries
+            .into_iter()
+            .map(|entry| CreateScrapeJobRequest {
+                url: entry.url,
+                ..template.clone()
+            })
+            .collect();
+        let jobs = db.job_repo().create_jobs(requests).await?;
+        Ok((jobs.into_iter().map(|job| job.id).collect(), skipped))
+    }
+
+    /// Jobs in `status` last updated more than `older_than` ago.
+    pub async fn jobs_for_cleanup(
+        &self,
+        older_than: Duration,
+        status: JobStatus,
+    ) -> Result<Vec<ScrapeJob>> {
+        match self {
+            Self::Local(db) => Ok(db
+                .job_repo()
+                .get_jobs_for_cleanup(older_than, status)
+                .await?),
+            Self::Remote(_) => Err(cleanup_unsupported()),
+        }
+    }
+
+    pub async fn delete_jobs(&self, ids: &[Uuid]) -> Result<u64> {
+        match self {
+            Self::Local(db) => Ok(db.job_repo().delete_jobs(ids).await?),
+            Self::Remote(_) => Err(cleanup_unsupported()),
+        }
+    }
+
+    pub async fn list_jobs(
+        &self,
+        status: Option<JobStatus>,
+        worker_id: Option<&str>,
+        tag: Option<&str>,
+        limit: usize,
+    ) -> Result<Vec<ScrapeJob>> {
+        Ok(match self {
+            Self::Local(db) => {
+                db.job_repo()
+                    .list_jobs(status, worker_id, tag, limit, 0)
+                    .await?
+            }
+            Self::Remote(api) => api.list_jobs(status, worker_id, tag, limit).await?,
+        })
+    }
+
+    pub async fn get_job(&self, id: Uuid) -> Result<Option<ScrapeJob>> {
+        Ok(match self {
+            Self::Local(db) => db.job_repo().get_job(id).await?,
+            Self::Remote(api) => api.get_job(id).await?,
+        })
+    }
+
+    pub async fn job_events(&self, id: Uuid, limit: usize) -> Result<Vec<WorkerEventRecord>> {
+        Ok(match self {
+            Self::Local(db) => db.worker_event_repo().list_for_job(id, limit).await?,
+            Self::Remote(api) => api.job_events(id, limit).await?,
+        })
+    }
+
+    pub async fn cancel_job(&self, id: Uuid) -> Result<()> {
+        match self {
+            Self::Local(db) => db.job_repo().cancel_job(id).await?,
+            Self::Remote(api) => api.cancel_job(id).await?,
+        }
+        Ok(())
+    }
+
+    /// The most recent extraction for `url` and `schema_name`, if any.
+    pub async fn latest(&self, url: &str, schema_name: &str) -> Result<Option<Extraction>> {
+        Ok(match self {
+            Self::Local(db) => db.extraction_repo().get_latest(url, schema_name).await?,
+            Self::Remote(api) => api.latest_extraction(url, schema_name).await?,
+        })
+    }
+
+    /// The latest `limit` extractions for `url` and `schema_name`, newest
+    /// first, each compared with the one before it, and the extraction just
+    /// older than those, if any.
+    pub async fn history(
+        &self,
+        url: &str,
+        schema_name: &str,
+        language: Option<&str>,
+        limit: usize,
+    ) -> Result<(Vec<HistoryEntry>, Option<Extraction>)> {
+        // One row more than asked for, to diff the oldest against. The
+        // server caps pages at 100, so remotely at `--limit 100` the oldest
+        // row has no predecessor to diff (its `changed` flag still holds).
+        let mut history = match self {
+            Self::Local(db) => {
+                db.extraction_repo()
+                    .get_history_entries(url, schema_name, language, limit + 1, 0)
+                    .await?
+            }
+            Self::Remote(api) => {
+                api.extraction_history(url, schema_name, language, limit + 1)
+                    .await?
+            }
+        };
+        let predecessor = if history.len() > limit {
+            history.pop().map(|entry| entry.extraction)
+        } else {
+            None
+        };
+        Ok((history, predecessor))
+    }
+}
+
+/// Versioned schemas: a local schema directory or the server's.
+pub enum SchemaStore {
+    Local(SchemaResolver),
+    Remote(AresApiClient),
+}
+
+impl SchemaStore {
+    pub async fn list(&self) -> Result<Vec<SchemaEntry>> {
+        Ok(match self {
+            Self::Local(resolver) => resolver.list_schemas()?,
+            Self::Remote(api) => api.list_schemas().await?,
+        })
+    }
+
+    /// Resolve `schema_ref` to its canonical `name@version` and JSON Schema.
+    ///
+    /// A path to an existing file is always read locally; with a remote
+    /// store, `name@version` and `name@latest` are fetched from the server.
+    pub async fn resolve(&self, schema_ref: &str) -> Result<(String, serde_json::Value)> {
+        Ok(match self {
+            Self::Remote(api) if !Path::new(schema_ref).exists() => {
+                let Some((name, version)) = schema_ref.split_once('@') else {
+                    anyhow::bail!("Schema not found: {schema_ref}");
+                };
+                let remote = api.get_schema(name, version).await?.ok_or_else(|| {
+                    AppError::SchemaNotFound {
+                        name: name.to_string(),
+                        version: version.to_string(),
+                    }
+                })?;
+                (format!("{}@{}", remote.name, remote.version), remote.schema)
+            }
+            Self::Local(resolver) => {
+                let resolved = resolver.resolve(schema_ref)?;
+                (resolved.name, resolved.schema)
+            }
+            Self::Remote(_) => {
+                let resolved = SchemaResolver::new("schemas").resolve(schema_ref)?;
+                (resolved.name, resolved.schema)
+            }
+        })
+    }
+
+    pub async fn exists(&self, name: &str, version: &str) -> Result<bool> {
+        Ok(match self {
+            Self::Local(resolver) => resolver.resolve(&format!("{name}@{version}")).is_ok(),
+            Self::Remote(api) => api.get_schema(name, version).await?.is_some(),
+        })
+    }
+
+    /// Add a schema version and return the schema's latest version afterwards.
+    pub async fn create(
+        &self,
+        name: &str,
+        version: &str,
+        schema: &serde_json::Value,
+    ) -> Result<String> {
+        match self {
+            Self::Local(resolver) => resolver.create_schema(name, version, schema)?,
+            Self::Remote(api) => api.create_schema(name, version, schema).await?,
+        }
+        let latest = self
+            .list()
+            .await?
+            .into_iter()
+            .find(|entry| entry.name == name)
+            .map(|entry| entry.latest_version)
+            .unwrap_or_default();
+        Ok(latest)
+    }
+
+    pub async fn set_latest(&self, name: &str, version: &str) -> Result<()> {
+        match self {
+            Self::Local(resolver) => resolver.set_latest(name, version)?,
+            Self::Remote(api) => api.set_latest_schema(name, version).await?,
+        }
+        Ok(())
+    }
+}
+
+/// Job cleanup deletes rows directly, which the REST API doesn't expose.
+fn cleanup_unsupported() -> anyhow::Error {
+    AppError::ConfigError("`ares job cleanup` does not support --remote".into()).into()
+}
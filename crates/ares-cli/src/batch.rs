@@ -0,0 +1,379 @@
+//! Progress and checkpointing for `ares scrape --urls-file`.
+//!
+//! With `--state-file`, the outcome of every URL is written to a JSON
+//! checkpoint as soon as it finishes, including the extracted data. A run
+//! that crashes or is interrupted can then be picked up with `--resume`:
+//! URLs completed earlier are not scraped again, but their data is still
+//! part of the output, so the resumed run's output covers the whole file.
+//! Failed URLs are retried.
+
+use std::collections::BTreeMap;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+use comfy_table::Table;
+use comfy_table::modifiers::UTF8_ROUND_CORNERS;
+use comfy_table::presets::UTF8_FULL_CONDENSED;
+use indicatif::{ProgressBar, ProgressStyle};
+use serde::{Deserialize, Serialize};
+use tempfile::NamedTempFile;
+
+use ares_core::error::AppError;
+
+/// Checkpoint format version, bumped on incompatible changes.
+const CHECKPOINT_VERSION: u32 = 1;
+
+const PROGRESS_TEMPLATE: &str = "{wide_bar} {pos}/{len} [{elapsed_precise}, ETA {eta}] {msg}";
+
+/// A successful extraction of one URL in a batch.
+#[derive(Debug, Clone)]
+pub struct Extracted {
+    pub data: serde_json::Value,
+    /// SHA-256 of `data`, as stored with the extraction.
+    pub data_hash: String,
+}
+
+/// How a URL ended in an earlier (or the current) run.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "status", rename_all = "lowercase")]
+pub enum UrlOutcome {
+    Completed {
+        data: serde_json::Value,
+        data_hash: String,
+    },
+    Failed {
+        error: String,
+    },
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct CheckpointFile {
+    version: u32,
+    urls: BTreeMap<String, UrlOutcome>,
+}
+
+/// The `--state-file` checkpoint, rewritten after every URL.
+#[derive(Debug)]
+pub struct Checkpoint {
+    path: PathBuf,
+    urls: BTreeMap<String, UrlOutcome>,
+}
+
+impl Checkpoint {
+    /// An empty checkpoint that will be written to `path`, replacing
+    /// whatever is there.
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self {
+            path: path.into(),
+            urls: BTreeMap::new(),
+        }
+    }
+
+    /// The checkpoint at `path`, or an empty one if the file doesn't exist.
+    pub fn load(path: impl Into<PathBuf>) -> Result<Self> {
+        let path = path.into();
+        let content = match std::fs::read_to_string(&path) {
+            Ok(content) => content,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Self::new(path)),
+            Err(e) => {
+                return Err(e).with_context(|| format!("Failed to read {}", path.display()));
+            }
+        };
+        let file: CheckpointFile = serde_json::from_str(&content)
+            .with_context(|| format!("Invalid state file {}", path.display()))?;
+        if file.version != CHECKPOINT_VERSION {
+            anyhow::bail!(
+                "State file {} has version {}, expected {CHECKPOINT_VERSION}",
+                path.display(),
+                file.version
+            );
+        }
+        Ok(Self {
+            path,
+            urls: file.urls,
+        })
+    }
+
+    /// The recorded outcome of `url`, if any.
+    pub fn outcome(&self, url: &str) -> Option<&UrlOutcome> {
+        self.urls.get(url)
+    }
+
+    /// The URLs of `urls` that still have to be scraped: everything not
+    /// completed in an earlier run, in input order.
+    pub fn remaining(&self, urls: &[String]) -> Vec<String> {
+        urls.iter()
+            .filter(|url| !matches!(self.outcome(url), Some(UrlOutcome::Completed { .. })))
+            .cloned()
+            .collect()
+    }
+
+    /// Record the outcome of `url` and write the checkpoint to disk.
+    pub fn record(&mut self, url: &str, outcome: UrlOutcome) -> Result<()> {
+        self.urls.insert(url.to_string(), outcome);
+        self.save()
+    }
+
+    /// Write through a temporary sibling so a crash mid-write never leaves
+    /// a truncated checkpoint behind.
+    fn save(&self) -> Result<()> {
+        let dir = match self.path.parent() {
+            Some(dir) if !dir.as_os_str().is_empty() => dir,
+            _ => Path::new("."),
+        };
+        let file = CheckpointFile {
+            version: CHECKPOINT_VERSION,
+            urls: self.urls.clone(),
+        };
+        let mut tmp = NamedTempFile::new_in(dir)
+            .with_context(|| format!("Failed to create state file in {}", dir.display()))?;
+        serde_json::to_writer_pretty(&mut tmp, &file)?;
+        tmp.flush()?;
+        tmp.persist(&self.path)
+            .with_context(|| format!("Failed to write {}", self.path.display()))?;
+        Ok(())
+    }
+}
+
+/// Bookkeeping for one batch run: the progress bar, the checkpoint, and the
+/// failures for the final summary.
+pub struct BatchRun {
+    progress: ProgressBar,
+    checkpoint: Option<Checkpoint>,
+    completed: usize,
+    failures: Vec<(String, String)>,
+}
+
+impl BatchRun {
+    /// Start a run over `urls` (including any already completed in
+    /// `checkpoint`). The progress bar draws on stderr, and only when it is
+    /// a terminal.
+    pub fn new(urls: &[String], checkpoint: Option<Checkpoint>) -> Self {
+        let progress = ProgressBar::new(urls.len() as u64);
+        if let Ok(style) = ProgressStyle::with_template(PROGRESS_TEMPLATE) {
+            progress.set_style(style);
+        }
+        let mut run = Self {
+            progress,
+            checkpoint,
+            completed: 0,
+            failures: Vec::new(),
+        };
+        let resumed = run.resumed(urls).len();
+        run.completed = resumed;
+        run.progress.inc(resumed as u64);
+        run.update_message();
+        run
+    }
+
+    /// URLs completed in an earlier run, with their data, in input order.
+    pub fn resumed(&self, urls: &[String]) -> Vec<(String, serde_json::Value)> {
+        let Some(checkpoint) = &self.checkpoint else {
+            return Vec::new();
+        };
+        urls.iter()
+            .filter_map(|url| match checkpoint.outcome(url) {
+                Some(UrlOutcome::Completed { data, .. }) => Some((url.clone(), data.clone())),
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// Record that `url` finished, advancing the progress bar and updating
+    /// the checkpoint.
+    pub fn record(&mut self, url: &str, result: &Result<Extracted, AppError>) -> Result<()> {
+        let outcome = match result {
+            Ok(extracted) => {
+                self.completed += 1;
+                UrlOutcome::Completed {
+                    data: extracted.data.clone(),
+                    data_hash: extracted.data_hash.clone(),
+                }
+            }
+            Err(e) => {
+                self.failures.push((url.to_string(), e.to_string()));
+                UrlOutcome::Failed {
+                    error: e.to_string(),
+                }
+            }
+        };
+        self.progress.inc(1);
+        self.update_message();
+        if let Some(checkpoint) = &mut self.checkpoint {
+            checkpoint.record(url, outcome)?;
+        }
+        Ok(())
+    }
+
+    /// Clear the progress bar and return the failed URLs with their errors.
+    pub fn finish(self) -> Vec<(String, String)> {
+        self.progress.finish_and_clear();
+        self.failures
+    }
+
+    fn update_message(&self) {
+        self.progress.set_message(format!(
+            "{} done, {} failed",
+            self.completed,
+            self.failures.len()
+        ));
+    }
+}
+
+/// URL/reason table of the failures of a batch run.
+pub fn format_failures(failures: &[(String, String)]) -> Table {
+    let mut table = Table::new();
+    table
+        .load_preset(UTF8_FULL_CONDENSED)
+        .apply_modifier(UTF8_ROUND_CORNERS)
+        .set_header(["URL", "ERROR"]);
+    for (url, error) in failures {
+        table.add_row([url, error]);
+    }
+    table
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn urls(urls: &[&str]) -> Vec<String> {
+        urls.iter().map(|url| url.to_string()).collect()
+    }
+
+    fn completed(title: &str) -> UrlOutcome {
+        UrlOutcome::Completed {
+            data: serde_json::json!({ "title": title }),
+            data_hash: format!("hash-{title}"),
+        }
+    }
+
+    #[test]
+    fn checkpoint_round_trips_through_the_file() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("run.json");
+
+        let mut checkpoint = Checkpoint::new(&path);
+        checkpoint
+            .record("https://a.example", completed("a"))
+            .unwrap();
+        checkpoint
+            .record(
+                "https://b.example",
+                UrlOutcome::Failed {
+                    error: "HTTP 500".into(),
+                },
+            )
+            .unwrap();
+
+        let loaded = Checkpoint::load(&path).unwrap();
+        assert_eq!(loaded.outcome("https://a.example"), Some(&completed("a")));
+        assert_eq!(
+            loaded.outcome("https://b.example"),
+            Some(&UrlOutcome::Failed {
+                error: "HTTP 500".into()
+            })
+        );
+        assert_eq!(loaded.outcome("https://c.example"), None);
+
+        let raw: serde_json::Value =
+            serde_json::from_str(&std::fs::read_to_string(&path).unwrap()).unwrap();
+        assert_eq!(raw["version"], CHECKPOINT_VERSION);
+        assert_eq!(raw["urls"]["https://a.example"]["status"], "completed");
+        assert_eq!(raw["urls"]["https://a.example"]["data_hash"], "hash-a");
+    }
+
+    #[test]
+    fn missing_checkpoint_loads_empty() {
+        let dir = TempDir::new().unwrap();
+        let checkpoint = Checkpoint::load(dir.path().join("run.json")).unwrap();
+        let all = urls(&["https://a.example"]);
+        assert_eq!(checkpoint.remaining(&all), all);
+    }
+
+    #[test]
+    fn invalid_checkpoints_are_rejected() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("run.json");
+
+        std::fs::write(&path, "not json").unwrap();
+        let err = Checkpoint::load(&path).unwrap_err();
+        assert!(err.to_string().starts_with("Invalid state file"), "{err}");
+
+        std::fs::write(&path, r#"{"version": 99, "urls": {}}"#).unwrap();
+        let err = Checkpoint::load(&path).unwrap_err();
+        assert!(err.to_string().contains("version 99"), "{err}");
+    }
+
+    #[test]
+    fn resume_skips_completed_urls_and_retries_failed_ones() {
+        let dir = TempDir::new().unwrap();
+        let mut checkpoint = Checkpoint::new(dir.path().join("run.json"));
+        checkpoint
+            .record("https://b.example", completed("b"))
+            .unwrap();
+        checkpoint
+            .record(
+                "https://c.example",
+                UrlOutcome::Failed {
+                    error: "timeout".into(),
+                },
+            )
+            .unwrap();
+
+        let all = urls(&[
+            "https://a.example",
+            "https://b.example",
+            "https://c.example",
+        ]);
+        assert_eq!(
+            checkpoint.remaining(&all),
+            urls(&["https://a.example", "https://c.example"])
+        );
+
+        let run = BatchRun::new(&all, Some(checkpoint));
+        assert_eq!(
+            run.resumed(&all),
+            vec![(
+                "https://b.example".to_string(),
+                serde_json::json!({ "title": "b" })
+            )]
+        );
+    }
+
+    #[test]
+    fn run_records_outcomes_and_collects_failures() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("run.json");
+        let all = urls(&["https://a.example", "https://b.example"]);
+        let mut run = BatchRun::new(&all, Some(Checkpoint::new(&path)));
+
+        run.record(
+            "https://a.example",
+            &Ok(Extracted {
+                data: serde_json::json!({ "title": "a" }),
+                data_hash: "hash-a".into(),
+            }),
+        )
+        .unwrap();
+        run.record(
+            "https://b.example",
+            &Err(AppError::HttpError("HTTP 404".into())),
+        )
+        .unwrap();
+
+        let failures = run.finish();
+        assert_eq!(failures.len(), 1);
+        assert_eq!(failures[0].0, "https://b.example");
+        assert!(failures[0].1.contains("HTTP 404"));
+
+        let loaded = Checkpoint::load(&path).unwrap();
+        assert_eq!(loaded.outcome("https://a.example"), Some(&completed("a")));
+        assert_eq!(loaded.remaining(&all), urls(&["https://b.example"]));
+
+        let table = format_failures(&failures).to_string();
+        assert!(table.contains("https://b.example"), "{table}");
+    }
+}
@@ -0,0 +1,169 @@
+//! `ares completions` and `ares manpages`, both generated from the `Cli`
+//! definition so they never drift from the real subcommands and flags.
+//!
+//! The default completion scripts are static: subcommands, flags, and fixed
+//! values such as job statuses. With `--dynamic`, bash and zsh get a short
+//! script that calls back into `ares` (with `COMPLETE` set) on every tab
+//! press instead, which also offers schema names from the local `schemas/`
+//! directory.
+
+use std::io::Write;
+use std::path::Path;
+
+use anyhow::{Context, Result};
+use clap::CommandFactory;
+use clap_complete::env::{Bash, Shells, Zsh};
+use clap_complete::{CompleteEnv, CompletionCandidate, Shell};
+
+use ares_core::schema::{SchemaEntry, SchemaResolver};
+
+use crate::Cli;
+
+/// Binary name the scripts register completions for.
+const BIN_NAME: &str = "ares";
+
+/// Environment variable that puts `ares` into completion mode.
+const COMPLETE_VAR: &str = "COMPLETE";
+
+/// Shells that support `--dynamic` completions.
+const DYNAMIC_SHELLS: Shells<'static> = Shells(&[&Bash, &Zsh]);
+
+/// Schema directory the runtime completers read, matching the default that
+/// `--schema` references resolve against.
+const LOCAL_SCHEMAS_DIR: &str = "schemas";
+
+/// Answers a completion request from a `--dynamic` script and exits; returns
+/// without doing anything on a normal run. Must run before anything is
+/// written to stdout.
+pub fn complete_from_env() {
+    CompleteEnv::with_factory(Cli::command)
+        .var(COMPLETE_VAR)
+        .shells(DYNAMIC_SHELLS)
+        .complete();
+}
+
+/// Writes the completion script for `shell` to `out`.
+pub fn write_completions(shell: Shell, dynamic: bool, out: &mut dyn Write) -> Result<()> {
+    if !dynamic {
+        clap_complete::generate(shell, &mut Cli::command(), BIN_NAME, out);
+        return Ok(());
+    }
+
+    let Some(completer) = DYNAMIC_SHELLS.completer(&shell.to_string()) else {
+        let supported: Vec<_> = DYNAMIC_SHELLS.names().collect();
+        anyhow::bail!(
+            "--dynamic completions are only available for {}",
+            supported.join(" and ")
+        );
+    };
+    completer.write_registration(COMPLETE_VAR, BIN_NAME, BIN_NAME, BIN_NAME, out)?;
+    Ok(())
+}
+
+/// Writes a man page for `ares` and one per subcommand into `out_dir`,
+/// creating it if needed.
+pub fn write_manpages(out_dir: &Path) -> Result<()> {
+    std::fs::create_dir_all(out_dir)
+        .with_context(|| format!("Failed to create {}", out_dir.display()))?;
+    clap_mangen::generate_to(Cli::command(), out_dir)
+        .with_context(|| format!("Failed to write man pages to {}", out_dir.display()))
+}
+
+/// `--schema` candidates: `name@latest` and `name@version` for every schema
+/// in the local schema directory.
+pub fn schema_ref_candidates() -> Vec<CompletionCandidate> {
+    let mut candidates = Vec::new();
+    for entry in local_schemas() {
+        candidates.push(
+            CompletionCandidate::new(format!("{}@latest", entry.name)).help(Some(
+                format!("{}@{}", entry.name, entry.latest_version).into(),
+            )),
+        );
+        candidates.extend(
+            entry
+                .versions
+                .iter()
+                .map(|version| CompletionCandidate::new(format!("{}@{version}", entry.name))),
+        );
+    }
+    candidates
+}
+
+/// Schema name candidates from the local schema directory.
+pub fn schema_name_candidates() -> Vec<CompletionCandidate> {
+    local_schemas()
+        .into_iter()
+        .map(|entry| CompletionCandidate::new(entry.name))
+        .collect()
+}
+
+/// Schemas in the local schema directory; none if it is missing or broken,
+/// since a completer has nowhere to report errors.
+fn local_schemas() -> Vec<SchemaEntry> {
+    SchemaResolver::new(LOCAL_SCHEMAS_DIR)
+        .list_schemas()
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use clap::ValueEnum;
+
+    #[test]
+    fn completions_cover_every_shell_and_key_subcommands() {
+        for shell in Shell::value_variants() {
+            let mut out: Vec<u8> = Vec::new();
+            write_completions(*shell, false, &mut out).unwrap();
+            let script = String::from_utf8(out).unwrap();
+            assert!(!script.is_empty(), "{shell}: empty script");
+            for subcommand in ["scrape", "job", "schema", "worker", "crawl"] {
+                assert!(script.contains(subcommand), "{shell}: no `{subcommand}`");
+            }
+        }
+    }
+
+    #[test]
+    fn static_completions_offer_job_statuses() {
+        let mut out: Vec<u8> = Vec::new();
+        write_completions(Shell::Fish, false, &mut out).unwrap();
+        let script = String::from_utf8(out).unwrap();
+        for status in ["pending", "running", "completed", "failed", "cancelled"] {
+            assert!(script.contains(status), "no `{status}`");
+        }
+    }
+
+    #[test]
+    fn dynamic_completions_call_back_into_ares() {
+        for shell in [Shell::Bash, Shell::Zsh] {
+            let mut out: Vec<u8> = Vec::new();
+            write_completions(shell, true, &mut out).unwrap();
+            let script = String::from_utf8(out).unwrap();
+            assert!(script.contains(COMPLETE_VAR), "{shell}: {script}");
+            assert!(script.contains(BIN_NAME), "{shell}: {script}");
+        }
+
+        let err = write_completions(Shell::Fish, true, &mut Vec::<u8>::new()).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "--dynamic completions are only available for bash and zsh"
+        );
+    }
+
+    #[test]
+    fn manpages_are_written_per_subcommand() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let out_dir = tmp.path().join("man");
+        write_manpages(&out_dir).unwrap();
+
+        for page in [
+            "ares.1",
+            "ares-scrape.1",
+            "ares-job-list.1",
+            "ares-completions.1",
+        ] {
+            let content = std::fs::read_to_string(out_dir.join(page)).unwrap();
+            assert!(!content.is_empty(), "{page} is empty");
+        }
+    }
+}
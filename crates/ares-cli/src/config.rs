@@ -0,0 +1,579 @@
+//! `ares.toml` — per-project defaults for the CLI.
+//!
+//! The file is read from the current directory, or else from
+//! `~/.config/ares/config.toml`. It only fills in what the command line and
+//! environment leave unset, so a setting comes from the first of: flag,
+//! environment variable, config file, built-in default.
+//!
+//! ```toml
+//! [llm]
+//! model = "gpt-4o-mini"
+//! timeout_secs = 180
+//!
+//! [fetch]
+//! throttle_ms = 1000
+//!
+//! [scrape]
+//! schema = "blog@latest"
+//! format = "table"
+//! ```
+
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+
+use ares_core::error::AppError;
+use ares_core::scrape::DEFAULT_BATCH_CONCURRENCY;
+use ares_db::DatabaseConfig;
+
+use crate::output::OutputFormat;
+use crate::{Commands, CrawlCommands, JobCommands};
+
+/// Config file name looked up in the current directory.
+pub const FILE_NAME: &str = "ares.toml";
+
+/// LLM provider when neither `--provider`, `ARES_PROVIDER`, nor the file
+/// set one.
+pub const DEFAULT_PROVIDER: &str = "openai";
+
+/// Worker poll interval when neither `--poll-interval` nor the file set one.
+pub const DEFAULT_POLL_INTERVAL_SECS: u64 = 5;
+
+/// Shown in place of secrets by `ares config show`.
+const REDACTED: &str = "[REDACTED]";
+
+/// The contents of a config file. Every key is optional.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct CliConfig {
+    pub llm: LlmConfig,
+    pub fetch: FetchConfig,
+    pub database: DatabaseSection,
+    pub scrape: ScrapeDefaults,
+    pub worker: WorkerDefaults,
+    /// The file the values were read from, if any.
+    #[serde(skip)]
+    pub source: Option<PathBuf>,
+}
+
+/// `[llm]` — model and prompt settings for `scrape`, `worker`,
+/// `job create`, and `crawl start`.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct LlmConfig {
+    pub provider: Option<String>,
+    pub model: Option<String>,
+    pub base_url: Option<String>,
+    pub api_key: Option<String>,
+    pub timeout_secs: Option<u64>,
+    pub system_prompt: Option<String>,
+}
+
+/// `[fetch]` — how `scrape` and `worker` download pages.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct FetchConfig {
+    pub timeout_secs: Option<u64>,
+    pub user_agent: Option<String>,
+    pub throttle_ms: Option<u64>,
+}
+
+/// `[database]` — used when `DATABASE_URL` and friends are unset.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct DatabaseSection {
+    pub url: Option<String>,
+    pub max_connections: Option<u32>,
+}
+
+/// `[scrape]` — defaults for `ares scrape`.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ScrapeDefaults {
+    pub schema: Option<String>,
+    pub format: Option<OutputFormat>,
+    pub concurrency: Option<usize>,
+}
+
+/// `[worker]` — defaults for `ares worker`.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct WorkerDefaults {
+    pub poll_interval_secs: Option<u64>,
+    pub schemas_dir: Option<String>,
+}
+
+impl CliConfig {
+    /// Read the first config file found in [`search_paths`], or an empty
+    /// config when there is none.
+    pub fn load() -> Result<Self> {
+        Self::load_from(&search_paths())
+    }
+
+    /// Read the first of `paths` that exists, or an empty config when none
+    /// do.
+    pub fn load_from(paths: &[PathBuf]) -> Result<Self> {
+        match paths.iter().find(|path| path.is_file()) {
+            Some(path) => Self::from_file(path),
+            None => Ok(Self::default()),
+        }
+    }
+
+    /// Parse the config file at `path`.
+    pub fn from_file(path: &Path) -> Result<Self> {
+        let text = std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read config file {}", path.display()))?;
+        let config: Self = toml::from_str(&text)
+            .with_context(|| format!("Invalid config file {}", path.display()))?;
+        Ok(Self {
+            source: Some(path.to_path_buf()),
+            ..config
+        })
+    }
+
+    /// Fill the settings `command` left unset (no flag, no environment
+    /// variable) from this file.
+    ///
+    /// With `remote`, settings that only apply to a local scrape (system
+    /// prompt, throttle, User-Agent) are left alone, since `--remote`
+    /// rejects them.
+    pub fn apply(&self, command: &mut Commands, remote: bool) {
+        match command {
+            Commands::Scrape {
+                schema,
+                model,
+                provider,
+                base_url,
+                api_key,
+                concurrency,
+                streaming,
+                fetch_timeout,
+                llm_timeout,
+                system_prompt,
+                throttle,
+                user_agent,
+                format,
+                ..
+            } => {
+                fill(schema, &self.scrape.schema);
+                fill(model, &self.llm.model);
+                fill(provider, &self.llm.provider);
+                fill(base_url, &self.llm.base_url);
+                fill(api_key, &self.llm.api_key);
+                fill(fetch_timeout, &self.fetch.timeout_secs);
+                fill(llm_timeout, &self.llm.timeout_secs);
+                fill(concurrency, &self.scrape.concurrency);
+                // --streaming always writes JSON lines.
+                if !*streaming {
+                    fill(format, &self.scrape.format);
+                }
+                if !remote {
+                    fill(system_prompt, &self.llm.system_prompt);
+                    fill(throttle, &self.fetch.throttle_ms);
+                    fill(user_agent, &self.fetch.user_agent);
+                }
+            }
+            Commands::Verify {
+                model,
+                provider,
+                base_url,
+                api_key,
+                system_prompt,
+                fetch_timeout,
+                llm_timeout,
+                ..
+            } => {
+                fill(model, &self.llm.model);
+                fill(provider, &self.llm.provider);
+                fill(base_url, &self.llm.base_url);
+                fill(api_key, &self.llm.api_key);
+                fill(system_prompt, &self.llm.system_prompt);
+                fill(fetch_timeout, &self.fetch.timeout_secs);
+                fill(llm_timeout, &self.llm.timeout_secs);
+            }
+            Commands::Worker {
+                provider,
+                api_key,
+                poll_interval,
+                fetch_timeout,
+                llm_timeout,
+                system_prompt,
+                throttle,
+                user_agent,
+                schemas_dir,
+                ..
+            } => {
+                fill(provider, &self.llm.provider);
+                fill(api_key, &self.llm.api_key);
+                fill(poll_interval, &self.worker.poll_interval_secs);
+                fill(fetch_timeout, &self.fetch.timeout_secs);
+                fill(llm_timeout, &self.llm.timeout_secs);
+                fill(system_prompt, &self.llm.system_prompt);
+                fill(throttle, &self.fetch.throttle_ms);
+                fill(user_agent, &self.fetch.user_agent);
+                fill(schemas_dir, &self.worker.schemas_dir);
+            }
+            Commands::Job {
+                action:
+                    JobCommands::Create {
+                        model, base_url, ..
+                    },
+            }
+            | Commands::Crawl {
+                action:
+                    CrawlCommands::Start {
+                        model, base_url, ..
+                    },
+            } => {
+                fill(model, &self.llm.model);
+                fill(base_url, &self.llm.base_url);
+            }
+            _ => {}
+        }
+    }
+
+    /// Database settings from the `DATABASE_*` environment variables,
+    /// falling back to `[database]`.
+    pub fn database(&self) -> Result<DatabaseConfig, AppError> {
+        self.database_from(|key| std::env::var(key).ok())
+    }
+
+    /// Like [`CliConfig::database`], reading variables through `get`.
+    pub fn database_from(
+        &self,
+        get: impl Fn(&str) -> Option<String>,
+    ) -> Result<DatabaseConfig, AppError> {
+        DatabaseConfig::from_lookup(|key| {
+            get(key).or_else(|| match key {
+                "DATABASE_URL" => self.database.url.clone(),
+                "DATABASE_MAX_CONNECTIONS" => self.database.max_connections.map(|n| n.to_string()),
+                _ => None,
+            })
+        })
+    }
+
+    /// The settings in effect before any flags: environment variables read
+    /// through `get` over the file, over the built-in defaults.
+    pub fn effective(&self, get: impl Fn(&str) -> Option<String>) -> Self {
+        let mut config = self.clone();
+        let llm = &mut config.llm;
+        for (value, key) in [
+            (&mut llm.provider, "ARES_PROVIDER"),
+            (&mut llm.model, "ARES_MODEL"),
+            (&mut llm.base_url, "ARES_BASE_URL"),
+            (&mut llm.api_key, "ARES_API_KEY"),
+            (&mut config.database.url, "DATABASE_URL"),
+        ] {
+            if let Some(env) = get(key) {
+                *value = Some(env);
+            }
+        }
+        if let Some(max) = get("DATABASE_MAX_CONNECTIONS").and_then(|raw| raw.parse().ok()) {
+            config.database.max_connections = Some(max);
+        }
+
+        fill(
+            &mut config.llm.provider,
+            &Some(DEFAULT_PROVIDER.to_string()),
+        );
+        fill(
+            &mut config.database.max_connections,
+            &Some(DatabaseConfig::new("").max_connections),
+        );
+        fill(
+            &mut config.scrape.concurrency,
+            &Some(DEFAULT_BATCH_CONCURRENCY),
+        );
+        fill(
+            &mut config.worker.poll_interval_secs,
+            &Some(DEFAULT_POLL_INTERVAL_SECS),
+        );
+        config
+    }
+
+    /// This config with the API key and database password replaced by
+    /// `[REDACTED]`.
+    pub fn masked(mut self) -> Self {
+        if self.llm.api_key.is_some() {
+            self.llm.api_key = Some(REDACTED.to_string());
+        }
+        if let Some(url) = &mut self.database.url {
+            *url = DatabaseConfig::new(url.as_str()).redacted_url();
+        }
+        self
+    }
+
+    /// Write this config as TOML, preceded by a comment naming its file.
+    pub fn write_toml(&self, out: &mut impl Write) -> Result<()> {
+        match &self.source {
+            Some(path) => writeln!(out, "# Config file: {}", path.display())?,
+            None => {
+                let paths: Vec<_> = search_paths()
+                    .iter()
+                    .map(|p| p.display().to_string())
+                    .collect();
+                writeln!(out, "# No config file found ({})", paths.join(", "))?;
+            }
+        }
+        write!(out, "{}", toml::to_string(self)?)?;
+        Ok(())
+    }
+}
+
+/// Where config files are looked for, in order: `./ares.toml`, then
+/// `~/.config/ares/config.toml`.
+pub fn search_paths() -> Vec<PathBuf> {
+    let mut paths = vec![PathBuf::from(FILE_NAME)];
+    if let Some(home) = std::env::var_os("HOME") {
+        paths.push(PathBuf::from(home).join(".config/ares/config.toml"));
+    }
+    paths
+}
+
+/// Set `value` to `fallback` if it is unset.
+fn fill<T: Clone>(value: &mut Option<T>, fallback: &Option<T>) {
+    if value.is_none() {
+        value.clone_from(fallback);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use clap::Parser;
+
+    use super::*;
+    use crate::Cli;
+
+    fn config(toml: &str) -> CliConfig {
+        toml::from_str(toml).unwrap()
+    }
+
+    fn parse(args: &[&str]) -> Commands {
+        Cli::try_parse_from(args).unwrap().command
+    }
+
+    fn vars(vars: &[(&str, &str)]) -> impl Fn(&str) -> Option<String> {
+        let vars: HashMap<String, String> = vars
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect();
+        move |key| vars.get(key).cloned()
+    }
+
+    const FILE: &str = r#"
+        [llm]
+        provider = "anthropic"
+        model = "file-model"
+        timeout_secs = 180
+        system_prompt = "Be terse."
+
+        [fetch]
+        throttle_ms = 500
+        user_agent = "file-agent"
+
+        [scrape]
+        schema = "blog@latest"
+        format = "table"
+        concurrency = 2
+
+        [worker]
+        poll_interval_secs = 30
+    "#;
+
+    #[test]
+    fn flags_take_precedence_over_the_file() {
+        let mut command = parse(&[
+            "ares",
+            "scrape",
+            "-u",
+            "https://example.com",
+            "-s",
+            "news@1.0.0",
+            "-m",
+            "flag-model",
+            "--throttle",
+            "0",
+            "--format",
+            "json",
+        ]);
+        config(FILE).apply(&mut command, false);
+
+        let Commands::Scrape {
+            schema,
+            model,
+            throttle,
+            format,
+            ..
+        } = command
+        else {
+            unreachable!()
+        };
+        assert_eq!(schema.as_deref(), Some("news@1.0.0"));
+        assert_eq!(model.as_deref(), Some("flag-model"));
+        assert_eq!(throttle, Some(0));
+        assert_eq!(format, Some(OutputFormat::Json));
+    }
+
+    #[test]
+    fn file_fills_what_flags_leave_unset() {
+        let mut command = parse(&["ares", "scrape", "--urls-file", "urls.txt"]);
+        config(FILE).apply(&mut command, false);
+
+        let Commands::Scrape {
+            schema,
+            model,
+            provider,
+            concurrency,
+            llm_timeout,
+            fetch_timeout,
+            system_prompt,
+            user_agent,
+            format,
+            ..
+        } = command
+        else {
+            unreachable!()
+        };
+        assert_eq!(schema.as_deref(), Some("blog@latest"));
+        assert_eq!(model.as_deref(), Some("file-model"));
+        assert_eq!(provider.as_deref(), Some("anthropic"));
+        assert_eq!(concurrency, Some(2));
+        assert_eq!(llm_timeout, Some(180));
+        assert_eq!(fetch_timeout, None);
+        assert_eq!(system_prompt.as_deref(), Some("Be terse."));
+        assert_eq!(user_agent.as_deref(), Some("file-agent"));
+        assert_eq!(format, Some(OutputFormat::Table));
+
+        let mut command = parse(&["ares", "worker", "--poll-interval", "1"]);
+        config(FILE).apply(&mut command, false);
+        let Commands::Worker {
+            poll_interval,
+            throttle,
+            ..
+        } = command
+        else {
+            unreachable!()
+        };
+        assert_eq!(poll_interval, Some(1));
+        assert_eq!(throttle, Some(500));
+    }
+
+    #[test]
+    fn defaults_apply_without_a_file() {
+        let mut command = parse(&["ares", "worker"]);
+        CliConfig::default().apply(&mut command, false);
+        let Commands::Worker {
+            provider,
+            poll_interval,
+            ..
+        } = command
+        else {
+            unreachable!()
+        };
+        assert_eq!(provider, None);
+        assert_eq!(poll_interval, None);
+
+        let effective = CliConfig::default().effective(vars(&[]));
+        assert_eq!(effective.llm.provider.as_deref(), Some(DEFAULT_PROVIDER));
+        assert_eq!(
+            effective.worker.poll_interval_secs,
+            Some(DEFAULT_POLL_INTERVAL_SECS)
+        );
+    }
+
+    #[test]
+    fn remote_mode_skips_local_only_settings() {
+        let mut command = parse(&["ares", "scrape", "-u", "https://example.com"]);
+        config(FILE).apply(&mut command, true);
+        let Commands::Scrape {
+            model,
+            system_prompt,
+            throttle,
+            user_agent,
+            ..
+        } = command
+        else {
+            unreachable!()
+        };
+        assert_eq!(model.as_deref(), Some("file-model"));
+        assert_eq!((system_prompt, throttle, user_agent), (None, None, None));
+    }
+
+    #[test]
+    fn environment_takes_precedence_over_the_file() {
+        let file = config(
+            r#"
+            llm.model = "file-model"
+            database.url = "postgres://file/ares"
+            database.max_connections = 9
+            "#,
+        );
+        let env = vars(&[
+            ("ARES_MODEL", "env-model"),
+            ("DATABASE_URL", "postgres://env/ares"),
+        ]);
+
+        let effective = file.effective(&env);
+        assert_eq!(effective.llm.model.as_deref(), Some("env-model"));
+        assert_eq!(
+            effective.database.url.as_deref(),
+            Some("postgres://env/ares")
+        );
+
+        let db = file.database_from(&env).unwrap();
+        assert_eq!(db.url, "postgres://env/ares");
+        assert_eq!(db.max_connections, 9);
+        let db = file.database_from(vars(&[])).unwrap();
+        assert_eq!(db.url, "postgres://file/ares");
+    }
+
+    #[test]
+    fn show_masks_secrets() {
+        let file = config(
+            r#"
+            llm.api_key = "sk-secret-key"
+            database.url = "postgres://ares:hunter22@db/ares"
+            "#,
+        );
+        let mut out = Vec::new();
+        file.effective(vars(&[]))
+            .masked()
+            .write_toml(&mut out)
+            .unwrap();
+        let out = String::from_utf8(out).unwrap();
+
+        assert!(!out.contains("sk-secret-key"), "{out}");
+        assert!(!out.contains("hunter22"), "{out}");
+        assert!(out.contains(r#"api_key = "[REDACTED]""#), "{out}");
+        assert!(
+            out.contains(r#"url = "postgres://ares:[REDACTED]@db/ares""#),
+            "{out}"
+        );
+        assert!(out.contains(r#"provider = "openai""#), "{out}");
+    }
+
+    #[test]
+    fn first_existing_file_wins_and_errors_name_it() {
+        let tmp = tempfile::tempdir().unwrap();
+        let project = tmp.path().join(FILE_NAME);
+        let user = tmp.path().join("config.toml");
+        std::fs::write(&user, "llm.model = \"user-model\"").unwrap();
+
+        let paths = [project.clone(), user.clone()];
+        let loaded = CliConfig::load_from(&paths).unwrap();
+        assert_eq!(loaded.llm.model.as_deref(), Some("user-model"));
+        assert_eq!(loaded.source.as_deref(), Some(user.as_path()));
+
+        std::fs::write(&project, "[llm]\nmodle = \"typo\"").unwrap();
+        let err = CliConfig::load_from(&paths).unwrap_err();
+        assert!(format!("{err:#}").contains("ares.toml"), "{err:#}");
+        assert!(format!("{err:#}").contains("modle"), "{err:#}");
+
+        let none = CliConfig::load_from(&[tmp.path().join("missing.toml")]).unwrap();
+        assert_eq!(none, CliConfig::default());
+    }
+}
@@ -0,0 +1,205 @@
+//! Process exit codes per error class, so scripts wrapping `ares` can tell a
+//! dead page from a bad API key from a rate limit.
+//!
+//! The code comes from the first [`AppError`] (or circuit breaker error, or
+//! [`VerifyFailed`]) in the error's chain; anything else exits 1. Clap exits 2
+//! on its own for usage errors, which matches [`USAGE`].
+
+use std::process::ExitCode;
+
+use ares_core::circuit_breaker::CircuitBreakerError;
+use ares_core::error::AppError;
+
+/// Unclassified error.
+pub const FAILURE: u8 = 1;
+/// Invalid arguments or configuration (missing API key, unknown provider, ...).
+pub const USAGE: u8 = 2;
+/// The page could not be fetched: HTTP error status, network failure, timeout,
+/// or an anti-bot block.
+pub const FETCH: u8 = 3;
+/// The LLM API rejected the request or local inference failed.
+pub const LLM: u8 = 4;
+/// Schema not found or invalid, or the extraction did not match it.
+pub const SCHEMA: u8 = 5;
+/// Database connection or query failed.
+pub const DATABASE: u8 = 6;
+/// Rate limited (by the LLM API or the Ares server), daily budget exceeded, or
+/// circuit breaker open.
+pub const RATE_LIMITED: u8 = 7;
+/// `ares verify` found a golden page whose re-extraction differs from its
+/// golden data, or couldn't be re-extracted.
+pub const VERIFY_FAILED: u8 = 8;
+
+/// The error `ares verify` ends with once its report is printed, if any
+/// golden page failed.
+#[derive(Debug)]
+pub struct VerifyFailed {
+    pub failed: usize,
+    pub total: usize,
+}
+
+impl std::fmt::Display for VerifyFailed {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} of {} golden pages failed verification",
+            self.failed, self.total
+        )
+    }
+}
+
+impl std::error::Error for VerifyFailed {}
+
+/// The exit code table, shown at the end of `ares --help`.
+pub const HELP: &str = "\
+Exit codes:
+  0  Success
+  1  Unclassified error
+  2  Usage or configuration error
+  3  Fetch error (HTTP error status, network failure, timeout, anti-bot block)
+  4  LLM error
+  5  Schema or validation error
+  6  Database error
+  7  Rate limited, daily budget exceeded, or circuit breaker open
+  8  Verification against golden extractions failed (`ares verify`)";
+
+/// The exit code for a failed run.
+pub fn code(err: &anyhow::Error) -> ExitCode {
+    ExitCode::from(classify(err))
+}
+
+fn classify(err: &anyhow::Error) -> u8 {
+    err.chain()
+        .find_map(|cause| {
+            if let Some(e) = cause.downcast_ref::<AppError>() {
+                Some(app_error_code(e))
+            } else if cause.is::<VerifyFailed>() {
+                Some(VERIFY_FAILED)
+            } else if let Some(e) = cause.downcast_ref::<CircuitBreakerError>() {
+                match e {
+                    CircuitBreakerError::Open { .. } => Some(RATE_LIMITED),
+                    CircuitBreakerError::Inner(e) => Some(app_error_code(e)),
+                }
+            } else {
+                None
+            }
+        })
+        .unwrap_or(FAILURE)
+}
+
+fn app_error_code(err: &AppError) -> u8 {
+    match err {
+        AppError::ConfigError(_) | AppError::InvalidInput(_) => USAGE,
+        AppError::HttpError(_)
+        | AppError::NetworkError(_)
+        | AppError::Timeout(_)
+        | AppError::CleanerError(_)
+        | AppError::Blocked { .. } => FETCH,
+        AppError::RateLimitExceeded
+        | AppError::BudgetExceeded { .. }
+        | AppError::LlmError {
+            status_code: 429, ..
+        } => RATE_LIMITED,
+        // Unparseable LLM output is the model's fault, not the schema's.
+        AppError::LlmError { .. }
+        | AppError::LocalInferenceError { .. }
+        | AppError::SchemaValidationError(_) => LLM,
+        AppError::ExtractionValidationError(_)
+        | AppError::SchemaError(_)
+        | AppError::ValidationError(_)
+        | AppError::SchemaNotFound { .. } => SCHEMA,
+        AppError::DatabaseError(_) => DATABASE,
+        AppError::Conflict(_) | AppError::SerializationError(_) | AppError::Generic(_) => FAILURE,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use anyhow::Context;
+
+    use super::*;
+
+    fn classify_app(err: AppError) -> u8 {
+        classify(&err.into())
+    }
+
+    #[test]
+    fn app_errors_map_to_their_class() {
+        assert_eq!(classify_app(AppError::ConfigError("no key".into())), USAGE);
+        assert_eq!(classify_app(AppError::HttpError("HTTP 404".into())), FETCH);
+        assert_eq!(classify_app(AppError::Timeout(30)), FETCH);
+        assert_eq!(
+            classify_app(AppError::Blocked {
+                url: "https://example.com".into(),
+                reason: "HTTP 403 from Cloudflare".into(),
+                hint: "retry with --browser".into(),
+            }),
+            FETCH
+        );
+        let llm = |status_code| AppError::LlmError {
+            message: "nope".into(),
+            status_code,
+            retryable: false,
+        };
+        assert_eq!(classify_app(llm(401)), LLM);
+        assert_eq!(classify_app(llm(429)), RATE_LIMITED);
+        assert_eq!(classify_app(AppError::RateLimitExceeded), RATE_LIMITED);
+        assert_eq!(
+            classify_app(AppError::BudgetExceeded {
+                reason: "100 of 100 daily LLM calls used".into(),
+                resets_at: chrono::Utc::now(),
+            }),
+            RATE_LIMITED
+        );
+        assert_eq!(
+            classify_app(AppError::SchemaNotFound {
+                name: "blog".into(),
+                version: "9.0.0".into(),
+            }),
+            SCHEMA
+        );
+        assert_eq!(
+            classify_app(AppError::ExtractionValidationError("title".into())),
+            SCHEMA
+        );
+        assert_eq!(
+            classify_app(AppError::DatabaseError("down".into())),
+            DATABASE
+        );
+        assert_eq!(classify_app(AppError::Conflict("claimed".into())), FAILURE);
+        assert_eq!(classify_app(AppError::Generic("?".into())), FAILURE);
+    }
+
+    #[test]
+    fn classifies_through_context_and_circuit_breaker_errors() {
+        let err = Err::<(), _>(AppError::DatabaseError("down".into()))
+            .context("Failed to list jobs")
+            .unwrap_err();
+        assert_eq!(classify(&err), DATABASE);
+
+        let open = CircuitBreakerError::Open {
+            name: "llm".into(),
+            retry_after: Duration::from_secs(30),
+        };
+        assert_eq!(classify(&open.into()), RATE_LIMITED);
+        let inner = CircuitBreakerError::Inner(AppError::HttpError("HTTP 500".into()));
+        assert_eq!(classify(&inner.into()), FETCH);
+    }
+
+    #[test]
+    fn failed_verification_has_its_own_code() {
+        let err = anyhow::Error::from(VerifyFailed {
+            failed: 1,
+            total: 3,
+        });
+        assert_eq!(err.to_string(), "1 of 3 golden pages failed verification");
+        assert_eq!(classify(&err), VERIFY_FAILED);
+    }
+
+    #[test]
+    fn other_errors_exit_one() {
+        assert_eq!(classify(&anyhow::anyhow!("something else")), FAILURE);
+    }
+}
@@ -0,0 +1,368 @@
+//! Rich terminal rendering for job listings, job details, and extraction
+//! diffs.
+//!
+//! Tables adapt to the terminal width (long URLs wrap instead of being
+//! truncated) and color job statuses. Colors are only emitted when stdout is
+//! a terminal, and can be turned off entirely with `--no-color` / `NO_COLOR`.
+
+use comfy_table::modifiers::UTF8_ROUND_CORNERS;
+use comfy_table::presets::UTF8_FULL_CONDENSED;
+use comfy_table::{Cell, Color, ColumnConstraint, ContentArrangement, Table};
+
+use ares_core::FetchOptions;
+use ares_core::diff::JsonChange;
+use ares_core::event_log::WorkerEventRecord;
+use ares_core::job::{JobStatus, ScrapeJob};
+
+const TIMESTAMP_FORMAT: &str = "%Y-%m-%d %H:%M:%S UTC";
+
+const ANSI_GREEN: &str = "\x1b[32m";
+const ANSI_RED: &str = "\x1b[31m";
+const ANSI_YELLOW: &str = "\x1b[33m";
+const ANSI_RESET: &str = "\x1b[0m";
+
+/// One row per job: ID, status, name, URL, schema, model, and creation time.
+pub fn format_jobs_table(jobs: &[ScrapeJob], color: bool) -> Table {
+    let mut table = new_table();
+    table.set_header(["ID", "STATUS", "NAME", "URL", "SCHEMA", "MODEL", "CREATED"]);
+
+    for job in jobs {
+        table.add_row(vec![
+            Cell::new(job.id),
+            status_cell(job.status, color),
+            Cell::new(job.display_name()),
+            Cell::new(&job.url),
+            Cell::new(&job.schema_name),
+            Cell::new(&job.model),
+            Cell::new(job.created_at.format("%Y-%m-%d %H:%M")),
+        ]);
+    }
+
+    // IDs and timestamps stay on one line; the URL column absorbs the squeeze.
+    for index in [0, 1, 6] {
+        if let Some(column) = table.column_mut(index) {
+            column.set_constraint(ColumnConstraint::ContentWidth);
+        }
+    }
+    table
+}
+
+/// Field/value table with every populated field of one job.
+pub fn format_job_detail(job: &ScrapeJob, color: bool) -> Table {
+    let mut table = new_table();
+    table.set_header(["FIELD", "VALUE"]);
+
+    table.add_row(vec![Cell::new("ID"), Cell::new(job.id)]);
+    table.add_row(vec![Cell::new("Status"), status_cell(job.status, color)]);
+
+    let mut rows: Vec<(&str, String)> = vec![
+        ("URL", job.url.clone()),
+        ("Schema", job.schema_name.clone()),
+        ("Model", job.model.clone()),
+        ("Base URL", job.base_url.clone()),
+        (
+            "Created",
+            job.created_at.format(TIMESTAMP_FORMAT).to_string(),
+        ),
+        (
+            "Updated",
+            job.updated_at.format(TIMESTAMP_FORMAT).to_string(),
+        ),
+    ];
+    if let Some(started) = job.started_at {
+        rows.push(("Started", started.format(TIMESTAMP_FORMAT).to_string()));
+    }
+    if let Some(completed) = job.completed_at {
+        rows.push(("Completed", completed.format(TIMESTAMP_FORMAT).to_string()));
+    }
+    rows.push((
+        "Retries",
+        format!("{}/{}", job.retry_count, job.max_retries),
+    ));
+    if let Some(next) = job.next_retry_at {
+        rows.push(("Next retry", next.format(TIMESTAMP_FORMAT).to_string()));
+    }
+    if let Some(worker) = &job.worker_id {
+        rows.push(("Worker", worker.clone()));
+    }
+    if let Some(extraction_id) = job.extraction_id {
+        rows.push(("Extraction", extraction_id.to_string()));
+    }
+    if !job.tags.is_empty() {
+        rows.push(("Tags", job.tags.join(", ")));
+    }
+    if !job.fetch_options.is_default() {
+        rows.push(("Fetch", describe_fetch_options(&job.fetch_options)));
+    }
+    if let Some(session) = job.crawl_session_id {
+        rows.push(("Crawl session", session.to_string()));
+        rows.push(("Depth", format!("{}/{}", job.depth, job.max_depth)));
+    }
+
+    for (field, value) in rows {
+        table.add_row(vec![Cell::new(field), Cell::new(value)]);
+    }
+
+    if let Some(err) = &job.error_message {
+        let cell = Cell::new(err);
+        let cell = if color { cell.fg(Color::Red) } else { cell };
+        table.add_row(vec![Cell::new("Error"), cell]);
+    }
+    table
+}
+
+/// One row per recorded worker event: time, worker, event, and details.
+/// Failures are red and completions green.
+pub fn format_job_events(events: &[WorkerEventRecord], color: bool) -> Table {
+    let mut table = new_table();
+    table.set_header(["TIME", "WORKER", "EVENT", "DETAIL"]);
+
+    for event in events {
+        let kind = Cell::new(&event.event_type);
+        let kind = match event.event_type.as_str() {
+            "job_failed" if color => kind.fg(Color::Red),
+            "job_completed" if color => kind.fg(Color::Green),
+            _ => kind,
+        };
+        table.add_row(vec![
+            Cell::new(event.created_at.format(TIMESTAMP_FORMAT)),
+            Cell::new(&event.worker_id),
+            kind,
+            Cell::new(describe_event_detail(&event.detail)),
+        ]);
+    }
+
+    for index in [0, 2] {
+        if let Some(column) = table.column_mut(index) {
+            column.set_constraint(ColumnConstraint::ContentWidth);
+        }
+    }
+    table
+}
+
+/// `key: value` pairs of an event's detail object, skipping nulls.
+fn describe_event_detail(detail: &serde_json::Value) -> String {
+    let Some(fields) = detail.as_object() else {
+        return detail.to_string();
+    };
+    fields
+        .iter()
+        .filter(|(_, value)| !value.is_null())
+        .map(|(key, value)| match value {
+            serde_json::Value::String(s) => format!("{key}: {s}"),
+            other => format!("{key}: {other}"),
+        })
+        .collect::<Vec<_>>()
+        .join("; ")
+}
+
+/// One line per change: `+` added (green), `-` removed (red), `~` changed
+/// (yellow). The caller decides whether stdout supports color.
+pub fn format_json_diff(changes: &[JsonChange], color: bool) -> String {
+    if changes.is_empty() {
+        return "  (no field-level changes)\n".to_string();
+    }
+
+    let mut out = String::new();
+    for change in changes {
+        let (ansi, line) = match change {
+            JsonChange::Added { path, value } => (ANSI_GREEN, format!("+ {path}: {value}")),
+            JsonChange::Removed { path, value } => (ANSI_RED, format!("- {path}: {value}")),
+            JsonChange::Changed { path, old, new } => {
+                (ANSI_YELLOW, format!("~ {path}: {old} → {new}"))
+            }
+        };
+        if color {
+            out.push_str(&format!("  {ansi}{line}{ANSI_RESET}\n"));
+        } else {
+            out.push_str(&format!("  {line}\n"));
+        }
+    }
+    out
+}
+
+/// Comma-separated summary of the options a job was created with.
+fn describe_fetch_options(options: &FetchOptions) -> String {
+    let mut parts = Vec::new();
+    if options.use_browser {
+        parts.push("browser".to_string());
+    }
+    if let Some(secs) = options.fetch_timeout_secs {
+        parts.push(format!("fetch timeout {secs}s"));
+    }
+    if let Some(secs) = options.llm_timeout_secs {
+        parts.push(format!("LLM timeout {secs}s"));
+    }
+    if let Some(selector) = &options.content_selector {
+        parts.push(format!("content {selector}"));
+    }
+    if let Some(selector) = &options.wait_selector {
+        parts.push(format!("wait for {selector}"));
+    }
+    if !options.headers.is_empty() {
+        let names: Vec<&str> = options.headers.keys().map(String::as_str).collect();
+        parts.push(format!("headers {}", names.join(", ")));
+    }
+    if let Some(auth) = &options.auth {
+        parts.push(format!("{} auth", auth.kind()));
+    }
+    parts.join("; ")
+}
+
+fn new_table() -> Table {
+    let mut table = Table::new();
+    table
+        .load_preset(UTF8_FULL_CONDENSED)
+        .apply_modifier(UTF8_ROUND_CORNERS)
+        .set_content_arrangement(ContentArrangement::Dynamic);
+    table
+}
+
+fn status_cell(status: JobStatus, color: bool) -> Cell {
+    let cell = Cell::new(status);
+    if !color {
+        return cell;
+    }
+    match status {
+        JobStatus::Completed => cell.fg(Color::Green),
+        JobStatus::Failed => cell.fg(Color::Red),
+        JobStatus::Running => cell.fg(Color::Yellow),
+        JobStatus::Pending | JobStatus::Cancelled => cell,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::Utc;
+    use uuid::Uuid;
+
+    use super::*;
+
+    fn job(url: &str, status: JobStatus) -> ScrapeJob {
+        ScrapeJob {
+            id: Uuid::nil(),
+            url: url.to_string(),
+            schema_name: "blog".to_string(),
+            schema: serde_json::json!({}),
+            model: "gpt-4o-mini".to_string(),
+            base_url: "https://api.openai.com/v1".to_string(),
+            status,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+            started_at: None,
+            completed_at: None,
+            retry_count: 1,
+            max_retries: 3,
+            next_retry_at: None,
+            error_message: None,
+            extraction_id: None,
+            worker_id: None,
+            crawl_session_id: None,
+            parent_job_id: None,
+            depth: 0,
+            max_depth: 0,
+            max_pages: 0,
+            allowed_domains: vec![],
+            fetch_options: ares_core::FetchOptions::default(),
+            notify_url: None,
+            tags: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn long_urls_wrap_within_the_width() {
+        let url = format!("https://example.com/{}", "segment/".repeat(20));
+        let mut table = format_jobs_table(&[job(&url, JobStatus::Pending)], false);
+        table.set_width(100);
+
+        let out = table.to_string();
+        assert!(out.lines().all(|l| l.chars().count() <= 100));
+        // Wrapped, not truncated: every character of the URL survives.
+        let cells: String = out
+            .lines()
+            .skip(3)
+            .filter_map(|l| l.split('┆').nth(3))
+            .map(str::trim)
+            .collect();
+        assert!(cells.contains(&url));
+    }
+
+    #[test]
+    fn no_color_emits_no_escape_codes() {
+        let mut table = format_jobs_table(&[job("https://a.example", JobStatus::Failed)], false);
+        table.enforce_styling();
+        let out = table.to_string();
+        assert!(!out.contains('\u{1b}'));
+        assert!(out.contains("blog@a.example"));
+
+        let mut table = format_jobs_table(&[job("https://a.example", JobStatus::Failed)], true);
+        table.enforce_styling();
+        assert!(table.to_string().contains('\u{1b}'));
+    }
+
+    #[test]
+    fn json_diff_marks_each_kind_of_change() {
+        let changes = ares_core::diff_json(
+            &serde_json::json!({"price": 10, "draft": true}),
+            &serde_json::json!({"price": 12, "tags": ["new"]}),
+        );
+
+        let out = format_json_diff(&changes, false);
+        assert!(out.contains("~ price: 10 → 12\n"));
+        assert!(out.contains("- draft: true\n"));
+        assert!(out.contains("+ tags: [\"new\"]\n"));
+        assert!(!out.contains('\u{1b}'));
+
+        assert!(format_json_diff(&changes, true).contains("\x1b[33m~ price"));
+    }
+
+    #[test]
+    fn events_table_summarizes_details() {
+        let event = |event_type: &str, detail: serde_json::Value| WorkerEventRecord {
+            id: 1,
+            worker_id: "worker-1".to_string(),
+            job_id: Some(Uuid::nil()),
+            event_type: event_type.to_string(),
+            detail,
+            created_at: Utc::now(),
+        };
+        let events = [
+            event("job_completed", serde_json::json!({"extraction_id": null})),
+            event(
+                "job_failed",
+                serde_json::json!({"error": "HTTP 500", "will_retry": true}),
+            ),
+        ];
+
+        let out = format_job_events(&events, false).to_string();
+        assert!(out.contains("worker-1"));
+        assert!(out.contains("error: HTTP 500; will_retry: true"));
+        assert!(!out.contains("extraction_id"));
+        assert!(!out.contains('\u{1b}'));
+    }
+
+    #[test]
+    fn detail_lists_optional_fields_only_when_set() {
+        let mut failed = job("https://a.example", JobStatus::Failed);
+        failed.error_message = Some("boom".to_string());
+        failed.worker_id = Some("worker-1".to_string());
+
+        let out = format_job_detail(&failed, false).to_string();
+        assert!(out.contains("boom"));
+        assert!(out.contains("worker-1"));
+        assert!(out.contains("1/3"));
+        assert!(!out.contains("Started"));
+        assert!(!out.contains("Crawl session"));
+        assert!(!out.contains("Fetch"));
+
+        let mut rendered = job("https://a.example", JobStatus::Pending);
+        rendered.fetch_options.use_browser = true;
+        rendered.fetch_options.wait_selector = Some("#app".to_string());
+        rendered
+            .fetch_options
+            .headers
+            .insert("Accept-Language".to_string(), "it".to_string());
+        let out = format_job_detail(&rendered, false).to_string();
+        assert!(out.contains("browser; wait for #app; headers Accept-Language"));
+    }
+}
@@ -0,0 +1,231 @@
+//! Liveness and readiness endpoints for `ares worker --health-addr`.
+//!
+//! `/livez` answers as long as the process is up. `/readyz` checks the
+//! database and that the run loop is still polling (or working on a job that
+//! hasn't outlived its timeouts), and reports the circuit breaker: an open
+//! breaker degrades the worker but keeps it ready, since it keeps claiming
+//! jobs and scheduling retries.
+
+use std::net::SocketAddr;
+use std::time::Duration;
+
+use axum::extract::State;
+use axum::http::StatusCode;
+use axum::response::IntoResponse;
+use axum::routing::get;
+use axum::{Json, Router};
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use tokio::net::TcpListener;
+
+use ares_core::circuit_breaker::CircuitState;
+use ares_core::error::AppError;
+use ares_core::worker::{WorkerStatus, WorkerStatusSnapshot};
+use ares_db::Database;
+
+/// The worker is not ready once it has gone this many poll intervals
+/// without polling the queue.
+const STALE_POLL_INTERVALS: u32 = 3;
+
+/// Fetch and LLM timeouts assumed when the worker doesn't set its own; the
+/// defaults of the HTTP fetcher and the LLM clients.
+const DEFAULT_FETCH_TIMEOUT: Duration = Duration::from_secs(30);
+const DEFAULT_LLM_TIMEOUT: Duration = Duration::from_secs(120);
+
+/// Headroom on top of a job's fetch and LLM timeouts for everything else it
+/// does (robots.txt, cleaning, saving) before the worker counts as stuck.
+const JOB_SLACK: Duration = Duration::from_secs(60);
+
+/// Upper bound on the readiness database check.
+const DB_PROBE_TIMEOUT: Duration = Duration::from_secs(3);
+
+#[derive(Clone)]
+struct HealthState {
+    status: WorkerStatus,
+    db: Database,
+    poll_interval: Duration,
+    max_job_age: Duration,
+}
+
+#[derive(Debug, Serialize)]
+struct WorkerHealth {
+    /// `healthy`, `degraded` (circuit breaker not closed), or `unhealthy`
+    /// (database unreachable or the run loop stopped polling).
+    status: &'static str,
+    /// Database connectivity: `ok` or `error`.
+    database: &'static str,
+    database_error: Option<String>,
+    /// Whether the run loop polled, or claimed its current job, within the
+    /// allowed window.
+    polling: bool,
+    #[serde(flatten)]
+    worker: WorkerStatusSnapshot,
+}
+
+/// Bind `addr` and serve the health endpoints in the background.
+///
+/// `fetch_timeout` and `llm_timeout` are the worker's own (`None` for the
+/// client defaults); a job running longer than both plus some slack makes
+/// the worker not ready.
+pub async fn serve(
+    addr: SocketAddr,
+    status: WorkerStatus,
+    db: Database,
+    poll_interval: Duration,
+    fetch_timeout: Option<Duration>,
+    llm_timeout: Option<Duration>,
+) -> anyhow::Result<()> {
+    let listener = TcpListener::bind(addr).await?;
+    tracing::info!("Serving worker health checks on {addr}");
+    let app = router(HealthState {
+        status,
+        db,
+        poll_interval,
+        max_job_age: fetch_timeout.unwrap_or(DEFAULT_FETCH_TIMEOUT)
+            + llm_timeout.unwrap_or(DEFAULT_LLM_TIMEOUT)
+            + JOB_SLACK,
+    });
+    tokio::spawn(async move {
+        if let Err(e) = axum::serve(listener, app).await {
+            tracing::error!("Health server failed: {e}");
+        }
+    });
+    Ok(())
+}
+
+fn router(state: HealthState) -> Router {
+    Router::new()
+        .route("/livez", get(livez))
+        .route("/readyz", get(readyz))
+        .with_state(state)
+}
+
+async fn livez() -> impl IntoResponse {
+    Json(serde_json::json!({ "status": "alive" }))
+}
+
+async fn readyz(State(state): State<HealthState>) -> impl IntoResponse {
+    let database =
+        tokio::time::timeout(DB_PROBE_TIMEOUT, state.db.extraction_repo().health_check())
+            .await
+            .unwrap_or_else(|_| {
+                Err(AppError::DatabaseError(format!(
+                    "timed out after {}s",
+                    DB_PROBE_TIMEOUT.as_secs()
+                )))
+            })
+            .map_err(|e| e.to_string());
+
+    let (code, health) = readiness(
+        state.status.snapshot(),
+        database,
+        state.poll_interval * STALE_POLL_INTERVALS,
+        state.max_job_age,
+        Utc::now(),
+    );
+    (code, Json(health))
+}
+
+/// Combine the database check and the worker's status into a response.
+fn readiness(
+    worker: WorkerStatusSnapshot,
+    database: Result<(), String>,
+    max_poll_age: Duration,
+    max_job_age: Duration,
+    now: DateTime<Utc>,
+) -> (StatusCode, WorkerHealth) {
+    let polling = worker.is_polling(max_poll_age, max_job_age, now);
+    let (code, status) = if database.is_err() || !polling {
+        (StatusCode::SERVICE_UNAVAILABLE, "unhealthy")
+    } else if worker.circuit_breaker != CircuitState::Closed {
+        (StatusCode::OK, "degraded")
+    } else {
+        (StatusCode::OK, "healthy")
+    };
+
+    let health = WorkerHealth {
+        status,
+        database: if database.is_ok() { "ok" } else { "error" },
+        database_error: database.err(),
+        polling,
+        worker,
+    };
+    (code, health)
+}
+
+#[cfg(test)]
+mod tests {
+    use ares_core::WorkerRunSummary;
+
+    use super::*;
+
+    const JOB_AGE: Duration = Duration::from_secs(300);
+
+    fn snapshot(last_poll_secs_ago: i64) -> (WorkerStatusSnapshot, DateTime<Utc>) {
+        let now = Utc::now();
+        let snapshot = WorkerStatusSnapshot {
+            worker_id: "worker-1".to_string(),
+            running: true,
+            started_at: Some(now - chrono::Duration::minutes(5)),
+            last_poll_at: Some(now - chrono::Duration::seconds(last_poll_secs_ago)),
+            current_job: None,
+            current_job_started_at: None,
+            circuit_breaker: CircuitState::Closed,
+            jobs: WorkerRunSummary::default(),
+        };
+        (snapshot, now)
+    }
+
+    #[test]
+    fn readiness_needs_database_and_recent_poll() {
+        let window = Duration::from_secs(15);
+
+        let (worker, now) = snapshot(2);
+        let (code, health) = readiness(worker, Ok(()), window, JOB_AGE, now);
+        assert_eq!(code, StatusCode::OK);
+        let json = serde_json::to_value(&health).unwrap();
+        assert_eq!(json["status"], "healthy");
+        assert_eq!(json["database"], "ok");
+        assert_eq!(json["worker_id"], "worker-1");
+        assert_eq!(json["circuit_breaker"], "closed");
+
+        let (worker, now) = snapshot(2);
+        let (code, health) = readiness(worker, Err("refused".into()), window, JOB_AGE, now);
+        assert_eq!(code, StatusCode::SERVICE_UNAVAILABLE);
+        assert_eq!(health.database_error.as_deref(), Some("refused"));
+
+        let (worker, now) = snapshot(60);
+        let (code, health) = readiness(worker, Ok(()), window, JOB_AGE, now);
+        assert_eq!(code, StatusCode::SERVICE_UNAVAILABLE);
+        assert!(!health.polling);
+    }
+
+    #[test]
+    fn stuck_job_makes_worker_unready() {
+        let (mut worker, now) = snapshot(60);
+        worker.current_job = Some(uuid::Uuid::new_v4());
+        worker.current_job_started_at = worker.last_poll_at;
+        let (code, _) = readiness(
+            worker.clone(),
+            Ok(()),
+            Duration::from_secs(15),
+            JOB_AGE,
+            now,
+        );
+        assert_eq!(code, StatusCode::OK);
+
+        worker.current_job_started_at = Some(now - chrono::Duration::minutes(10));
+        let (code, health) = readiness(worker, Ok(()), Duration::from_secs(15), JOB_AGE, now);
+        assert_eq!(code, StatusCode::SERVICE_UNAVAILABLE);
+        assert!(!health.polling);
+    }
+
+    #[test]
+    fn open_breaker_degrades_but_stays_ready() {
+        let (mut worker, now) = snapshot(2);
+        worker.circuit_breaker = CircuitState::Open;
+        let (code, health) = readiness(worker, Ok(()), Duration::from_secs(15), JOB_AGE, now);
+        assert_eq!(code, StatusCode::OK);
+        assert_eq!(health.status, "degraded");
+    }
+}
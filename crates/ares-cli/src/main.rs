@@ -1,14 +1,24 @@
+use std::io::{IsTerminal, Write};
+use std::net::SocketAddr;
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
 use std::time::Duration;
 
 use anyhow::{Context, Result};
-use clap::{Parser, Subcommand};
+use chrono::{DateTime, Utc};
+use clap::builder::{PossibleValuesParser, TypedValueParser};
+use clap::{Args, Parser, Subcommand};
+use clap_complete::{ArgValueCandidates, Shell};
+use futures::StreamExt;
 use tokio_util::sync::CancellationToken;
-use tracing_subscriber::EnvFilter;
+use tracing::level_filters::LevelFilter;
 use uuid::Uuid;
 
+use ares_client::api::ScrapeRequest;
 use ares_client::{
-    CachedRobotsChecker, HtmdCleaner, HtmlLinkDiscoverer, Provider, ProviderExtractor,
-    ProviderExtractorFactory, ReqwestFetcher,
+    AresApiClient, CachedRobotsChecker, EventPublisher, HtmdCleaner, HtmlLinkDiscoverer, Notifiers,
+    Provider, ProviderExtractor, ProviderExtractorFactory, ReqwestFetcher, SitemapOptions,
+    SlackNotifier, Snapshots, WebhookNotifier, model_pricing,
 };
 
 #[cfg(feature = "local-llm")]
@@ -16,37 +26,67 @@ use ares_client::LocalModelStore;
 
 #[cfg(not(feature = "local-llm"))]
 use ares_client::LOCAL_LLM_FEATURE_MSG;
+use ares_core::api_key::ApiKeyScope;
+use ares_core::audit::AuditAction;
+use ares_core::budget::{BudgetGuard, BudgetLimits, BudgetedExtractorFactory, DailyUsage};
 use ares_core::circuit_breaker::{CircuitBreaker, CircuitBreakerConfig};
-use ares_core::job::{CreateScrapeJobRequest, JobStatus, WorkerConfig};
+use ares_core::error::AppError;
+use ares_core::event_log::{DEFAULT_EVENT_BUFFER, PersistingWorkerReporter};
+use ares_core::fetch::{FetchAuth, FetchOptions, FetcherSelector, LazyFetcher};
+use ares_core::job::{CreateScrapeJobRequest, JobStatus, ScrapeJob, WorkerConfig};
 use ares_core::job_queue::JobQueue;
+use ares_core::logging::{LogConfig, init_logging};
 use ares_core::proxy::{ProxyConfig, ProxyEntry, RotationStrategy, TlsBackend};
-use ares_core::traits::Fetcher;
+use ares_core::snapshot::{SnapshotKind, parse_snapshot_kinds};
+use ares_core::traits::{Fetcher, SnapshotStore};
 use ares_core::worker::{TracingWorkerReporter, WorkerService};
 use ares_core::{
-    CacheConfig, ContentCache, ExtractionCache, NullStore, SchemaResolver, ScrapeService,
-    ThrottleConfig, ThrottledFetcher, validate_schema,
+    CacheConfig, ContentCache, Extraction, ExtractionCache, HistoryEntry, NullStore,
+    SchemaResolver, ScrapeService, ScrapeServiceBuilder, ThrottleConfig, ThrottledFetcher,
+    VerifyReport, diff_json, format_age, validate_schema, verify_goldens,
+};
+use ares_db::{
+    ApiKeyRepository, AuditRepository, CircuitBreakerRepository, Database, DatabaseConfig,
+    ExtractionRepository, StatsRepository, UsageRepository,
 };
-use ares_db::{Database, DatabaseConfig, ExtractionRepository};
 
+mod backend;
+mod batch;
+mod completions;
+mod config;
+mod exit;
+mod format;
+mod health;
 mod output;
-use output::{OutputFormat, OutputFormatter};
+use backend::{Backend, SchemaStore};
+use batch::{BatchRun, Checkpoint, Extracted};
+use config::CliConfig;
+use output::{HistoryFormat, OutputFormat, OutputFormatter, OutputWriter};
 
 // ---------------------------------------------------------------------------
-// Fetcher creation — shared by Scrape and Worker commands.
+// Fetcher creation — shared by the Scrape and Worker commands.
 // ---------------------------------------------------------------------------
 
 /// Creates a fetcher (browser or reqwest, with optional throttle wrapping,
-/// proxy rotation, User-Agent rotation, and browser stealth) and passes it
-/// to a generic async body. Uses a macro because `Fetcher` is not
+/// proxy rotation, a fixed or rotating User-Agent, browser stealth, a
+/// browser pre-capture script and User-Agent, and a response size cap) and
+/// passes it to a generic async body. Uses a macro because `Fetcher` is not
 /// object-safe (returns `impl Future`).
 macro_rules! with_fetcher {
-    ($browser:expr, $timeout:expr, $throttle:expr, $proxy:expr, $random_ua:expr, $stealth:expr, $tls:expr, |$f:ident| $body:expr) => {{
+    ($browser:expr, $timeout:expr, $throttle:expr, $proxy:expr, $random_ua:expr, $user_agent:expr, $stealth:expr, $script:expr, $browser_ua:expr, $tls:expr, $max_response_size:expr, |$f:ident| $body:expr) => {{
         async {
             if $browser {
                 let proxy_url: Option<String> = $proxy
                     .as_ref()
                     .map(|pc: &ProxyConfig| pc.next().authenticated_url());
-                let base = create_browser_fetcher($timeout, proxy_url.as_deref(), $stealth).await?;
+                let base = create_browser_fetcher(
+                    $timeout,
+                    proxy_url.as_deref(),
+                    $stealth,
+                    $script,
+                    $browser_ua,
+                )
+                .await?;
                 match $throttle.filter(|&ms| ms > 0) {
                     Some(ms) => {
                         let $f = ThrottledFetcher::new(
@@ -61,24 +101,14 @@ macro_rules! with_fetcher {
                     }
                 }
             } else {
-                let mut base = match $timeout {
-                    Some(t) => ReqwestFetcher::with_timeout(t),
-                    None => ReqwestFetcher::new(),
-                }
-                .context("Failed to create HTTP client")?
-                .with_tls_backend($tls)
-                .context("Failed to set TLS backend")?
-                .allow_private_urls();
-
-                if let Some(proxy_config) = $proxy {
-                    base = base
-                        .with_proxies(proxy_config)
-                        .context("Failed to configure proxies")?;
-                }
-                if $random_ua {
-                    base = base.with_random_ua();
-                }
-
+                let base = create_http_fetcher(
+                    $timeout,
+                    $proxy,
+                    $random_ua,
+                    $user_agent,
+                    $tls,
+                    $max_response_size,
+                )?;
                 match $throttle.filter(|&ms| ms > 0) {
                     Some(ms) => {
                         let $f = ThrottledFetcher::new(
@@ -98,38 +128,93 @@ macro_rules! with_fetcher {
 }
 
 #[derive(Parser)]
-#[command(name = "ares", version, about = "Industrial Grade AI Scraper")]
+#[command(
+    name = "ares",
+    version,
+    about = "Industrial Grade AI Scraper",
+    after_help = exit::HELP
+)]
 struct Cli {
+    /// Disable colored output (also honoured via the NO_COLOR env var)
+    #[arg(long, global = true, default_value_t = false)]
+    no_color: bool,
+
+    /// Only log warnings and errors to stderr
+    #[arg(short, long, global = true, default_value_t = false)]
+    quiet: bool,
+
+    /// Run scrape, job, history, and schema commands against this Ares
+    /// server instead of the local database, schema directory, and LLM
+    #[arg(long, global = true, env = "ARES_REMOTE_URL", value_name = "URL")]
+    remote: Option<String>,
+
+    /// Bearer token for --remote (an API key or the server's admin token)
+    #[arg(long, global = true, env = "ARES_REMOTE_TOKEN", hide_env_values = true)]
+    token: Option<String>,
+
     #[command(subcommand)]
     command: Commands,
 }
 
 #[derive(Subcommand)]
 enum Commands {
-    /// Extract structured data from a web page
+    /// Extract structured data from a web page, or from many with --urls-file
+    #[command(alias = "batch-scrape")]
     Scrape {
-        /// Target URL to scrape
-        #[arg(short, long)]
-        url: String,
+        /// Target URL to scrape (with --html-file, only the storage key)
+        #[arg(short, long, required_unless_present = "urls_file")]
+        url: Option<String>,
+
+        /// Scrape every URL in this file (one per line; blank lines and
+        /// `#` comments are skipped)
+        #[arg(long, value_name = "FILE", conflicts_with_all = ["url", "html_file"])]
+        urls_file: Option<String>,
+
+        /// Pages scraped at once with --urls-file (default: 8)
+        #[arg(long, conflicts_with = "url")]
+        concurrency: Option<usize>,
+
+        /// With --urls-file, print each result as a JSON line as soon as it
+        /// finishes instead of all at the end in input order
+        #[arg(long, default_value_t = false, conflicts_with = "url")]
+        streaming: bool,
+
+        /// With --urls-file, record each URL's outcome and data in this JSON
+        /// file as it finishes, so an interrupted run can be resumed
+        #[arg(long, value_name = "FILE", requires = "urls_file")]
+        state_file: Option<PathBuf>,
+
+        /// Skip the URLs --state-file records as completed; their data is
+        /// still included in the output
+        #[arg(long, default_value_t = false, requires = "state_file")]
+        resume: bool,
 
         /// JSON Schema path or name@version (e.g., schemas/blog/1.0.0.json or blog@1.0.0)
-        #[arg(short, long)]
-        schema: String,
+        #[arg(short, long, add = ArgValueCandidates::new(completions::schema_ref_candidates))]
+        schema: Option<String>,
+
+        /// Read HTML from this file instead of fetching the URL
+        #[arg(
+            long,
+            value_name = "FILE",
+            conflicts_with_all = ["browser", "fetch_timeout", "fetch_basic_auth", "fetch_bearer", "throttle", "user_agent", "random_ua", "stealth", "browser_script", "browser_user_agent", "max_response_size"]
+        )]
+        html_file: Option<String>,
 
         /// LLM model (e.g., "gpt-4o-mini", "gemini-2.5-flash", "claude-haiku-4-5")
         #[arg(short, long, env = "ARES_MODEL")]
-        model: String,
+        model: Option<String>,
 
         /// LLM provider: "openai" (OpenAI-compatible, default), "anthropic", or "local"
-        #[arg(long, env = "ARES_PROVIDER", default_value = "openai")]
-        provider: String,
+        #[arg(long, env = "ARES_PROVIDER")]
+        provider: Option<String>,
 
         /// API base URL (defaults to the selected provider's endpoint)
         #[arg(short, long, env = "ARES_BASE_URL")]
         base_url: Option<String>,
 
         /// API key (required for cloud providers; reads from ARES_API_KEY)
-        #[arg(short, long, env = "ARES_API_KEY")]
+        #[arg(short, long, env = "ARES_API_KEY", hide_env_values = true)]
         api_key: Option<String>,
 
         /// Save extraction to database (requires DATABASE_URL)
@@ -152,6 +237,18 @@ enum Commands {
         #[arg(long)]
         llm_timeout: Option<u64>,
 
+        #[command(flatten)]
+        fetch_auth: FetchAuthArgs,
+
+        #[command(flatten)]
+        notify: NotifyArgs,
+
+        #[command(flatten)]
+        events: EventArgs,
+
+        #[command(flatten)]
+        snapshots: SnapshotArgs,
+
         /// Custom system prompt for LLM extraction
         #[arg(long)]
         system_prompt: Option<String>,
@@ -160,10 +257,35 @@ enum Commands {
         #[arg(long, default_value_t = false)]
         skip_unchanged: bool,
 
+        /// Update the existing record when extracted data is unchanged instead
+        /// of saving a new snapshot (requires --save)
+        #[arg(long, default_value_t = false)]
+        upsert: bool,
+
+        /// Mark pages whose content another URL had within this many hours
+        /// as duplicates of that extraction (requires --save)
+        #[arg(long, value_name = "HOURS", value_parser = clap::value_parser!(u64).range(1..))]
+        dedup_window_hours: Option<u64>,
+
+        /// Skip the LLM call for duplicate pages and copy the original
+        /// extraction's data
+        #[arg(long, default_value_t = false, requires = "dedup_window_hours")]
+        reuse_duplicates: bool,
+
+        /// Treat the page as unchanged, skipping the LLM, when its content's
+        /// simhash differs from the last extraction's in fewer than BITS of
+        /// 64 bits (requires --save)
+        #[arg(long, value_name = "BITS", value_parser = clap::value_parser!(u32).range(1..=64))]
+        content_similarity_threshold: Option<u32>,
+
         /// Per-domain throttle delay in milliseconds (e.g., 1000 for 1s between requests)
         #[arg(long)]
         throttle: Option<u64>,
 
+        /// User-Agent header for HTTP fetches (ignored with --random-ua)
+        #[arg(long)]
+        user_agent: Option<String>,
+
         /// Proxy URL (http, https, or socks5)
         #[arg(long, env = "ARES_PROXY")]
         proxy: Option<String>,
@@ -185,10 +307,24 @@ enum Commands {
         #[arg(long, default_value_t = false)]
         stealth: bool,
 
+        /// JavaScript file evaluated on each page before capture (requires
+        /// --browser); its result is appended to the HTML as injected data
+        #[arg(long, value_name = "FILE")]
+        browser_script: Option<String>,
+
+        /// User-Agent of the browser: "desktop", "mobile", "googlebot", or
+        /// any string (requires --browser)
+        #[arg(long, value_name = "UA", requires = "browser")]
+        browser_user_agent: Option<String>,
+
         /// TLS backend for fingerprint diversity (rustls, native, random)
         #[arg(long, env = "ARES_TLS_BACKEND", default_value = "rustls")]
         tls_backend: String,
 
+        /// Reject HTTP responses larger than this many bytes
+        #[arg(long, env = "ARES_MAX_RESPONSE_BYTES", value_name = "BYTES")]
+        max_response_size: Option<usize>,
+
         /// Disable in-memory caching
         #[arg(long, default_value_t = false)]
         no_cache: bool,
@@ -201,9 +337,15 @@ enum Commands {
         #[arg(long)]
         max_content: Option<usize>,
 
-        /// Output format (json, jsonl, csv, table, jq)
-        #[arg(long, default_value = "json")]
-        format: OutputFormat,
+        /// Output format (json, jsonl, yaml, csv, table, jq); defaults to
+        /// json, or jsonl with --urls-file
+        #[arg(long)]
+        format: Option<OutputFormat>,
+
+        /// Write the output to this file instead of stdout; it is replaced
+        /// only once everything has been written
+        #[arg(short, long, value_name = "PATH")]
+        output: Option<PathBuf>,
     },
 
     /// Show extraction history for a URL
@@ -213,17 +355,80 @@ enum Commands {
         url: String,
 
         /// Schema name to filter by
-        #[arg(short, long)]
+        #[arg(short, long, add = ArgValueCandidates::new(completions::schema_name_candidates))]
         schema_name: String,
 
         /// Number of results to show
         #[arg(short, long, default_value_t = 10)]
         limit: usize,
 
-        /// Output format
+        /// Only extractions whose content was detected in this language (ISO
+        /// 639-3, e.g. "eng"); "diff" then compares those with each other
+        #[arg(long)]
+        language: Option<String>,
+
+        /// Output format: "summary" (one row per extraction), "diff" (what
+        /// changed between consecutive extractions), or "json"
         #[arg(long, default_value = "json")]
+        format: HistoryFormat,
+    },
+
+    /// Print the data of the most recent extraction for a URL, as JSON
+    Latest {
+        /// Target URL
+        #[arg(short, long)]
+        url: String,
+
+        /// Schema name the URL was extracted with
+        #[arg(short, long, add = ArgValueCandidates::new(completions::schema_name_candidates))]
+        schema_name: String,
+    },
+
+    /// Re-extract every golden page of a schema and fail if any result
+    /// differs from its golden data
+    Verify {
+        /// Schema path or name@version whose golden extractions are checked
+        #[arg(short, long, add = ArgValueCandidates::new(completions::schema_ref_candidates))]
+        schema: String,
+
+        /// LLM model (e.g., "gpt-4o-mini", "gemini-2.5-flash", "claude-haiku-4-5")
+        #[arg(short, long, env = "ARES_MODEL")]
+        model: Option<String>,
+
+        /// LLM provider: "openai" (OpenAI-compatible, default), "anthropic", or "local"
+        #[arg(long, env = "ARES_PROVIDER")]
+        provider: Option<String>,
+
+        /// API base URL (defaults to the selected provider's endpoint)
+        #[arg(short, long, env = "ARES_BASE_URL")]
+        base_url: Option<String>,
+
+        /// API key (required for cloud providers; reads from ARES_API_KEY)
+        #[arg(short, long, env = "ARES_API_KEY", hide_env_values = true)]
+        api_key: Option<String>,
+
+        /// Custom system prompt for LLM extraction
+        #[arg(long)]
+        system_prompt: Option<String>,
+
+        /// HTTP fetch timeout in seconds (default: 30)
+        #[arg(long)]
+        fetch_timeout: Option<u64>,
+
+        /// LLM API timeout in seconds (default: 120)
+        #[arg(long)]
+        llm_timeout: Option<u64>,
+
+        /// Replay golden pages from the HTML archived here instead of
+        /// fetching them: file:///path, or s3://bucket/prefix
+        #[arg(long, value_name = "URL", env = "ARES_SNAPSHOT_URL")]
+        snapshot_url: Option<String>,
+
+        /// Output format
+        #[arg(long, default_value = "table")]
         format: OutputFormat,
     },
+
     /// Manage crawl sessions
     Crawl {
         #[command(subcommand)]
@@ -236,38 +441,125 @@ enum Commands {
         action: JobCommands,
     },
 
-    /// Manage schemas
+    /// Manage the local schema directory
     Schema {
+        /// Schema directory holding `{name}/{version}.json` and `registry.json`
+        #[arg(long, global = true, value_name = "DIR", default_value = "schemas")]
+        schemas_dir: PathBuf,
+
         #[command(subcommand)]
         action: SchemaCommands,
     },
 
+    /// Manage API keys for the HTTP server
+    #[command(name = "apikey")]
+    ApiKey {
+        #[command(subcommand)]
+        action: ApiKeyCommands,
+    },
+
+    /// Inspect and reset the LLM circuit breakers reported by workers
+    Breaker {
+        #[command(subcommand)]
+        action: BreakerCommands,
+    },
+
+    /// Review the audit log of administrative actions taken through the HTTP server
+    Audit {
+        #[command(subcommand)]
+        action: AuditCommands,
+    },
+
+    /// Show LLM usage per day (UTC) against the daily budget
+    Usage {
+        /// Days to show, counting today
+        #[arg(long, default_value_t = 1, value_parser = clap::value_parser!(u64).range(1..))]
+        days: u64,
+
+        #[command(flatten)]
+        budget: BudgetArgs,
+
+        /// Output format
+        #[arg(long, default_value = "table")]
+        format: OutputFormat,
+    },
+
+    /// Show job and extraction statistics from the database
+    Stats {
+        #[command(subcommand)]
+        action: StatsCommands,
+    },
+
+    /// Run, inspect, and clean up after database migrations
+    Db {
+        #[command(subcommand)]
+        action: DbCommands,
+    },
+
     /// Download, inspect, and remove native local models
     Model {
         #[command(subcommand)]
         action: ModelCommands,
     },
 
+    /// Inspect the CLI config file (`ares.toml`)
+    Config {
+        #[command(subcommand)]
+        action: ConfigCommands,
+    },
+
+    /// Print a shell completion script to stdout
+    Completions {
+        /// Shell to complete for
+        #[arg(value_enum)]
+        shell: Shell,
+
+        /// Complete by calling back into `ares` on every tab press (bash and
+        /// zsh only), which also suggests schemas from `schemas/`
+        #[arg(long, default_value_t = false)]
+        dynamic: bool,
+    },
+
+    /// Write man pages for `ares` and each of its subcommands
+    Manpages {
+        /// Directory to write the pages to (created if missing)
+        #[arg(long, value_name = "DIR", default_value = "target/man")]
+        out_dir: PathBuf,
+    },
+
     /// Start a worker to process scrape jobs
     Worker {
         /// Worker ID (auto-generated if not provided)
         #[arg(long)]
         worker_id: Option<String>,
 
-        /// Poll interval in seconds
-        #[arg(long, default_value_t = 5)]
-        poll_interval: u64,
+        /// Poll interval in seconds (default: 5)
+        #[arg(long)]
+        poll_interval: Option<u64>,
+
+        /// Exit after processing this many jobs
+        #[arg(long, value_parser = clap::value_parser!(u64).range(1..))]
+        max_jobs: Option<u64>,
+
+        /// Exit once the queue has no job ready to claim, instead of polling
+        #[arg(long, default_value_t = false)]
+        once: bool,
+
+        /// Serve /livez and /readyz on this address (e.g. 0.0.0.0:8081)
+        #[arg(long, env = "ARES_WORKER_HEALTH_ADDR")]
+        health_addr: Option<SocketAddr>,
 
         /// API key for cloud LLM calls (not needed with --provider local)
-        #[arg(short, long, env = "ARES_API_KEY")]
+        #[arg(short, long, env = "ARES_API_KEY", hide_env_values = true)]
         api_key: Option<String>,
 
         /// LLM provider: "openai" (default), "anthropic", or "local". Per-job base URLs
         /// should target the selected provider's API.
-        #[arg(long, env = "ARES_PROVIDER", default_value = "openai")]
-        provider: String,
+        #[arg(long, env = "ARES_PROVIDER")]
+        provider: Option<String>,
 
-        /// Use headless browser for JS-rendered pages (requires `browser` feature)
+        /// Render every job in the headless browser, not only jobs that ask for
+        /// it (requires `browser` feature)
         #[arg(long, default_value_t = false)]
         browser: bool,
 
@@ -287,10 +579,46 @@ enum Commands {
         #[arg(long, default_value_t = false)]
         skip_unchanged: bool,
 
+        /// Update the existing record when extracted data is unchanged instead
+        /// of saving a new snapshot
+        #[arg(long, default_value_t = false)]
+        upsert: bool,
+
+        /// Keep the cleaned Markdown sent to the LLM with each saved
+        /// extraction, served at /v1/extractions/{id}/raw-content
+        #[arg(long, env = "ARES_STORE_CONTENT", default_value_t = false)]
+        store_content: bool,
+
+        /// Also validate extracted data against the job's schema file in this
+        /// directory; jobs whose output does not match fail without retry
+        #[arg(long, value_name = "DIR")]
+        schemas_dir: Option<String>,
+
+        /// Only claim jobs whose schema name matches this pattern, where `*`
+        /// matches anything (e.g. blog*)
+        #[arg(long, value_name = "PATTERN", env = "ARES_WORKER_SCHEMA_FILTER")]
+        schema_filter: Option<String>,
+
+        #[command(flatten)]
+        notify: NotifyArgs,
+
+        #[command(flatten)]
+        events: EventArgs,
+
+        #[command(flatten)]
+        snapshots: SnapshotArgs,
+
+        #[command(flatten)]
+        budget: BudgetArgs,
+
         /// Per-domain throttle delay in milliseconds (e.g., 1000 for 1s between requests)
         #[arg(long)]
         throttle: Option<u64>,
 
+        /// User-Agent header for HTTP fetches (ignored with --random-ua)
+        #[arg(long)]
+        user_agent: Option<String>,
+
         /// Proxy URL (http, https, or socks5)
         #[arg(long, env = "ARES_PROXY")]
         proxy: Option<String>,
@@ -312,10 +640,24 @@ enum Commands {
         #[arg(long, default_value_t = false)]
         stealth: bool,
 
+        /// JavaScript file evaluated on each page before capture (requires
+        /// --browser); its result is appended to the HTML as injected data
+        #[arg(long, value_name = "FILE")]
+        browser_script: Option<String>,
+
+        /// User-Agent of the browser for jobs rendered in it: "desktop",
+        /// "mobile", "googlebot", or any string
+        #[arg(long, value_name = "UA")]
+        browser_user_agent: Option<String>,
+
         /// TLS backend for fingerprint diversity (rustls, native, random)
         #[arg(long, env = "ARES_TLS_BACKEND", default_value = "rustls")]
         tls_backend: String,
 
+        /// Reject HTTP responses larger than this many bytes
+        #[arg(long, env = "ARES_MAX_RESPONSE_BYTES", value_name = "BYTES")]
+        max_response_size: Option<usize>,
+
         /// Disable in-memory caching
         #[arg(long, default_value_t = false)]
         no_cache: bool,
@@ -323,6 +665,17 @@ enum Commands {
         /// Cache TTL in seconds (default: 3600)
         #[arg(long, env = "ARES_CACHE_TTL", default_value_t = 3600)]
         cache_ttl: u64,
+
+        /// Apply pending migrations at startup; with `false` the worker
+        /// refuses to start until `ares db migrate` has run
+        #[arg(
+            long,
+            env = "ARES_AUTO_MIGRATE",
+            value_name = "BOOL",
+            default_value_t = true,
+            action = clap::ArgAction::Set
+        )]
+        auto_migrate: bool,
     },
 }
 
@@ -331,36 +684,61 @@ enum JobCommands {
     /// Create a new scrape job
     Create {
         /// Target URL to scrape
-        #[arg(short, long)]
-        url: String,
+        #[arg(short, long, required_unless_present = "sitemap")]
+        url: Option<String>,
 
         /// JSON Schema path or name@version (e.g., schemas/blog/1.0.0.json or blog@1.0.0)
-        #[arg(short, long)]
+        #[arg(short, long, add = ArgValueCandidates::new(completions::schema_ref_candidates))]
         schema: String,
 
         /// LLM model to use
         #[arg(short, long, env = "ARES_MODEL")]
-        model: String,
+        model: Option<String>,
 
-        /// OpenAI-compatible API base URL
-        #[arg(
-            short,
-            long,
-            env = "ARES_BASE_URL",
-            default_value = "https://api.openai.com/v1"
-        )]
-        base_url: String,
+        /// OpenAI-compatible API base URL (default: https://api.openai.com/v1)
+        #[arg(short, long, env = "ARES_BASE_URL")]
+        base_url: Option<String>,
 
         /// Schema name (defaults to filename without extension)
         #[arg(long)]
         schema_name: Option<String>,
+
+        /// Webhook told when the job's data changes, instead of the worker's
+        /// --notify-webhook-url
+        #[arg(long, value_name = "URL")]
+        notify_url: Option<String>,
+
+        /// Label the job, e.g. with a run ID, to list or delete it with
+        /// others later (repeatable)
+        #[arg(long = "tag", value_name = "TAG")]
+        tags: Vec<String>,
+
+        #[command(flatten)]
+        fetch: JobFetchArgs,
+
+        #[command(flatten)]
+        sitemap: SitemapArgs,
     },
 
     /// List scrape jobs
     List {
-        /// Filter by status (pending, running, completed, failed, cancelled)
-        #[arg(short, long)]
-        status: Option<String>,
+        /// Filter by status
+        #[arg(
+            short,
+            long,
+            value_parser = job_status_parser(&JobStatus::ALL),
+            ignore_case = true
+        )]
+        status: Option<JobStatus>,
+
+        /// Only jobs claimed by this worker; with `--status running`, the
+        /// jobs it currently holds
+        #[arg(short, long, value_name = "WORKER_ID")]
+        worker: Option<String>,
+
+        /// Only jobs with this tag
+        #[arg(long, value_name = "TAG")]
+        tag: Option<String>,
 
         /// Number of results
         #[arg(short, long, default_value_t = 20)]
@@ -376,6 +754,10 @@ enum JobCommands {
         /// Job ID
         #[arg(value_name = "JOB_ID")]
         id: Uuid,
+
+        /// Also list the worker events recorded for the job
+        #[arg(long, default_value_t = false)]
+        events: bool,
     },
 
     /// Cancel a pending or running job
@@ -384,6 +766,296 @@ enum JobCommands {
         #[arg(value_name = "JOB_ID")]
         id: Uuid,
     },
+
+    /// Delete finished jobs last updated before a cutoff
+    Cleanup {
+        /// Minimum age, as a number with an s, m, h, d, or w suffix (e.g. 30d)
+        #[arg(long, value_name = "AGE", value_parser = parse_age)]
+        older_than: Duration,
+
+        /// Status of the jobs to delete
+        #[arg(
+            long,
+            default_value = "completed",
+            value_parser = job_status_parser(&[
+                JobStatus::Completed,
+                JobStatus::Failed,
+                JobStatus::Cancelled,
+            ]),
+            ignore_case = true
+        )]
+        status: JobStatus,
+
+        /// List the jobs that would be deleted without deleting them
+        #[arg(long, default_value_t = false)]
+        dry_run: bool,
+    },
+}
+
+/// Per-job fetch options, stored with the job and applied by the worker.
+#[derive(Args)]
+struct JobFetchArgs {
+    /// Render the page in a headless browser (the worker must be built with
+    /// the `browser` feature)
+    #[arg(long, default_value_t = false)]
+    browser: bool,
+
+    /// Fetch timeout in seconds for this job
+    #[arg(long)]
+    fetch_timeout: Option<u64>,
+
+    /// LLM timeout in seconds for this job
+    #[arg(long)]
+    llm_timeout: Option<u64>,
+
+    /// CSS selector; only matching elements are sent to the LLM
+    #[arg(long)]
+    content_selector: Option<String>,
+
+    /// CSS selector the browser waits for before capture
+    #[arg(long, requires = "browser")]
+    wait_selector: Option<String>,
+
+    /// Extra request header, as `NAME: VALUE` (repeatable)
+    #[arg(long = "header", value_name = "NAME: VALUE")]
+    headers: Vec<String>,
+
+    #[command(flatten)]
+    auth: FetchAuthArgs,
+}
+
+/// Seeding jobs from a sitemap instead of a single `--url`.
+#[derive(Args)]
+struct SitemapArgs {
+    /// Create one job per URL of this sitemap.xml or sitemap index
+    #[arg(long, value_name = "URL", conflicts_with = "url")]
+    sitemap: Option<String>,
+
+    /// Only queue sitemap URLs matching this regex (repeatable)
+    #[arg(long, value_name = "REGEX", requires = "sitemap")]
+    filter: Vec<String>,
+
+    /// Skip sitemap URLs matching this regex (repeatable)
+    #[arg(long, value_name = "REGEX", requires = "sitemap")]
+    exclude: Vec<String>,
+
+    /// Queue at most this many sitemap URLs (default: 10000)
+    #[arg(long, requires = "sitemap")]
+    max_urls: Option<usize>,
+
+    /// Skip URLs whose sitemap lastmod is not newer than their latest
+    /// extraction with this schema
+    #[arg(long, default_value_t = false, requires = "sitemap")]
+    skip_unmodified: bool,
+}
+
+impl SitemapArgs {
+    fn into_options(self) -> SitemapOptions {
+        SitemapOptions {
+            include: self.filter,
+            exclude: self.exclude,
+            max_urls: self.max_urls,
+            skip_unmodified: self.skip_unmodified,
+        }
+    }
+}
+
+/// Credentials for fetching protected pages.
+#[derive(Args)]
+struct FetchAuthArgs {
+    /// HTTP Basic credentials for the page fetch, as `USER:PASS`
+    #[arg(long, value_name = "USER:PASS")]
+    fetch_basic_auth: Option<String>,
+
+    /// Bearer token sent with the page fetch
+    #[arg(long, value_name = "TOKEN", conflicts_with = "fetch_basic_auth")]
+    fetch_bearer: Option<String>,
+}
+
+impl FetchAuthArgs {
+    fn into_auth(self) -> Result<Option<FetchAuth>> {
+        if let Some(credentials) = self.fetch_basic_auth {
+            let (user, pass) = credentials
+                .split_once(':')
+                .context("Invalid --fetch-basic-auth: expected USER:PASS")?;
+            return Ok(Some(FetchAuth::Basic {
+                user: user.to_string(),
+                pass: pass.to_string(),
+            }));
+        }
+        Ok(self.fetch_bearer.map(|token| FetchAuth::Bearer { token }))
+    }
+}
+
+/// Where change notifications are sent when extracted data changes.
+#[derive(Args)]
+struct NotifyArgs {
+    /// Slack incoming webhook URL told about data changes
+    #[arg(
+        long,
+        value_name = "URL",
+        env = "ARES_NOTIFY_SLACK_URL",
+        hide_env_values = true
+    )]
+    notify_slack_url: Option<String>,
+
+    /// Slack message template; may use {url}, {schema_name}, {count},
+    /// {changes}, and {extraction_id}
+    #[arg(
+        long,
+        value_name = "TEMPLATE",
+        env = "ARES_NOTIFY_SLACK_TEMPLATE",
+        requires = "notify_slack_url"
+    )]
+    notify_slack_template: Option<String>,
+
+    /// URL the change event is POSTed to as JSON
+    #[arg(long, value_name = "URL", env = "ARES_NOTIFY_WEBHOOK_URL")]
+    notify_webhook_url: Option<String>,
+
+    /// Secret for the webhook's X-Ares-Signature HMAC-SHA256 header
+    #[arg(
+        long,
+        value_name = "SECRET",
+        env = "ARES_NOTIFY_WEBHOOK_SECRET",
+        hide_env_values = true
+    )]
+    notify_webhook_secret: Option<String>,
+}
+
+impl NotifyArgs {
+    /// The configured notifiers. The webhook may be on a private network,
+    /// since the user controls the machine; per-job overrides may not.
+    fn into_notifiers(self) -> Result<Notifiers> {
+        let slack = self
+            .notify_slack_url
+            .map(|url| {
+                let slack = SlackNotifier::new(url)?;
+                Ok::<_, AppError>(match self.notify_slack_template {
+                    Some(template) => slack.with_template(template),
+                    None => slack,
+                })
+            })
+            .transpose()?;
+        let webhook = self
+            .notify_webhook_url
+            .map(|url| {
+                let webhook = WebhookNotifier::new(url)?.allow_private_urls();
+                Ok::<_, AppError>(match self.notify_webhook_secret {
+                    Some(secret) => webhook.with_secret(secret),
+                    None => webhook,
+                })
+            })
+            .transpose()?;
+        Ok(Notifiers { slack, webhook })
+    }
+}
+
+/// Daily caps on LLM calls, shared by every worker and server using the
+/// same database.
+#[derive(Args)]
+struct BudgetArgs {
+    /// Most LLM calls per day (UTC); jobs over the budget are retried after
+    /// midnight UTC
+    #[arg(long, value_name = "N", env = "ARES_BUDGET_MAX_CALLS")]
+    budget_max_calls: Option<u64>,
+
+    /// Most prompt plus completion tokens per day (UTC)
+    #[arg(long, value_name = "N", env = "ARES_BUDGET_MAX_TOKENS")]
+    budget_max_tokens: Option<u64>,
+
+    /// Most estimated spend in USD per day (UTC); models without a known
+    /// price count as free
+    #[arg(long, value_name = "USD", env = "ARES_BUDGET_MAX_COST_USD")]
+    budget_max_cost_usd: Option<f64>,
+}
+
+impl BudgetArgs {
+    fn limits(&self) -> BudgetLimits {
+        BudgetLimits {
+            max_calls: self.budget_max_calls,
+            max_tokens: self.budget_max_tokens,
+            max_cost_usd: self.budget_max_cost_usd,
+        }
+    }
+}
+
+/// Where saved extractions are published as events.
+#[derive(Args)]
+struct EventArgs {
+    /// NATS server saved extractions are published to, on
+    /// ares.extractions.<schema> (requires the events-nats feature)
+    #[arg(
+        long,
+        value_name = "URL",
+        env = "ARES_NATS_URL",
+        hide_env_values = true
+    )]
+    nats_url: Option<String>,
+}
+
+impl EventArgs {
+    fn into_publisher(self) -> Result<EventPublisher> {
+        Ok(EventPublisher::new(self.nats_url.as_deref())?)
+    }
+}
+
+/// Where the source content behind saved extractions is archived.
+#[derive(Args)]
+struct SnapshotArgs {
+    /// Archive raw HTML and Markdown snapshots of saved extractions here:
+    /// file:///path, or s3://bucket/prefix (requires the s3 feature)
+    #[arg(long, value_name = "URL", env = "ARES_SNAPSHOT_URL")]
+    snapshot_url: Option<String>,
+
+    /// Which snapshots to archive: html, markdown, or both
+    #[arg(
+        long,
+        value_name = "KINDS",
+        env = "ARES_SNAPSHOT_KINDS",
+        default_value = "html,markdown"
+    )]
+    snapshot_kinds: String,
+}
+
+impl SnapshotArgs {
+    fn into_store(self) -> Result<(Snapshots, Vec<SnapshotKind>)> {
+        let store = Snapshots::new(self.snapshot_url.as_deref())?;
+        let kinds = parse_snapshot_kinds(&self.snapshot_kinds).map_err(|e| anyhow::anyhow!(e))?;
+        Ok((store, kinds))
+    }
+}
+
+impl JobFetchArgs {
+    fn into_options(self) -> Result<FetchOptions> {
+        for selector in [&self.content_selector, &self.wait_selector]
+            .into_iter()
+            .flatten()
+        {
+            ares_client::cleaner::parse_selector(selector).map_err(|e| anyhow::anyhow!("{e}"))?;
+        }
+        let headers = self
+            .headers
+            .iter()
+            .map(|header| {
+                let (name, value) = header
+                    .split_once(':')
+                    .filter(|(name, _)| !name.trim().is_empty())
+                    .with_context(|| format!("Invalid header '{header}': expected NAME: VALUE"))?;
+                Ok((name.trim().to_string(), value.trim().to_string()))
+            })
+            .collect::<Result<_>>()?;
+
+        Ok(FetchOptions {
+            use_browser: self.browser,
+            fetch_timeout_secs: self.fetch_timeout,
+            llm_timeout_secs: self.llm_timeout,
+            content_selector: self.content_selector,
+            wait_selector: self.wait_selector,
+            headers,
+            auth: self.auth.into_auth()?,
+        })
+    }
 }
 
 #[derive(Subcommand)]
@@ -395,7 +1067,7 @@ enum CrawlCommands {
         url: String,
 
         /// JSON Schema path or name@version (e.g., blog@1.0.0)
-        #[arg(short, long)]
+        #[arg(short, long, add = ArgValueCandidates::new(completions::schema_ref_candidates))]
         schema: String,
 
         /// Maximum depth for recursion
@@ -404,16 +1076,11 @@ enum CrawlCommands {
 
         /// LLM model to use
         #[arg(short, long, env = "ARES_MODEL")]
-        model: String,
+        model: Option<String>,
 
-        /// OpenAI-compatible API base URL
-        #[arg(
-            short,
-            long,
-            env = "ARES_BASE_URL",
-            default_value = "https://api.openai.com/v1"
-        )]
-        base_url: String,
+        /// OpenAI-compatible API base URL (default: https://api.openai.com/v1)
+        #[arg(short, long, env = "ARES_BASE_URL")]
+        base_url: Option<String>,
 
         /// Maximum number of pages to crawl
         #[arg(long, default_value_t = 100)]
@@ -426,6 +1093,9 @@ enum CrawlCommands {
         /// Schema name (defaults to filename without extension)
         #[arg(long)]
         schema_name: Option<String>,
+
+        #[command(flatten)]
+        fetch: Box<JobFetchArgs>,
     },
 
     /// Show status of a crawl session
@@ -435,56 +1105,300 @@ enum CrawlCommands {
         id: Uuid,
     },
 
-    /// Show results of a crawl session
+    /// Show results of a crawl session, one `{url, data}` row per page
     Results {
         /// Crawl session ID
         #[arg(value_name = "SESSION_ID")]
         id: Uuid,
+
+        /// Output format (json, jsonl, yaml, csv, table, jq)
+        #[arg(long, default_value = "jsonl")]
+        format: OutputFormat,
+
+        /// Write the output to this file instead of stdout; it is replaced
+        /// only once everything has been written
+        #[arg(short, long, value_name = "PATH")]
+        output: Option<PathBuf>,
     },
 }
 
 #[derive(Subcommand)]
 enum SchemaCommands {
-    /// Validate a JSON Schema file
-    Validate {
-        /// Path to the JSON Schema file
-        #[arg(value_name = "PATH")]
-        path: String,
+    /// List schemas with their versions and latest version
+    List {
+        /// Output format
+        #[arg(long, default_value = "table")]
+        format: OutputFormat,
     },
-}
 
-#[derive(Subcommand)]
-enum ModelCommands {
-    /// Download a supported native model into Ares' local cache
-    Pull {
-        #[arg(value_name = "MODEL")]
-        model: String,
-    },
-    /// Show native models currently cached by Ares
-    List,
-    /// Remove a native model from Ares' local cache
-    Remove {
-        #[arg(value_name = "MODEL")]
-        model: String,
+    /// Print a schema's JSON
+    Show {
+        /// Schema reference (e.g., blog@1.0.0 or blog@latest)
+        #[arg(
+            value_name = "NAME@VERSION",
+            add = ArgValueCandidates::new(completions::schema_ref_candidates)
+        )]
+        schema: String,
     },
-}
-
-#[tokio::main]
-async fn main() -> Result<()> {
-    let _ = dotenvy::dotenv();
 
-    tracing_subscriber::fmt()
-        .with_env_filter(EnvFilter::from_default_env().add_directive("ares=info".parse()?))
-        .with_target(false)
-        .with_writer(std::io::stderr)
-        .init();
+    /// Add a schema version from a file (becomes latest if it is the highest version)
+    Create {
+        /// Schema name
+        #[arg(short, long)]
+        name: String,
 
-    let cli = Cli::parse();
+        /// Schema version (e.g., 1.1.0)
+        #[arg(short, long)]
+        version: String,
+
+        /// Path to the JSON Schema file
+        #[arg(short, long, value_name = "PATH")]
+        file: PathBuf,
+    },
+
+    /// Validate a JSON Schema file without adding it
+    Validate {
+        /// Path to the JSON Schema file
+        #[arg(short, long, value_name = "PATH")]
+        file: PathBuf,
+    },
+
+    /// Point a schema's latest version at an existing version
+    SetLatest {
+        /// Schema name
+        #[arg(
+            value_name = "NAME",
+            add = ArgValueCandidates::new(completions::schema_name_candidates)
+        )]
+        name: String,
+
+        /// Version to mark as latest
+        #[arg(value_name = "VERSION")]
+        version: String,
+    },
+}
+
+#[derive(Subcommand)]
+enum ApiKeyCommands {
+    /// Create a new API key (the key value is printed only once)
+    Create {
+        /// Name of the consumer (e.g., "ingest-service")
+        #[arg(short, long)]
+        name: String,
+
+        /// Comma-separated scopes: scrape, jobs:write, schemas:write, admin
+        #[arg(short, long, value_delimiter = ',', required = true)]
+        scopes: Vec<String>,
+    },
+
+    /// List API keys
+    List {
+        /// Include revoked keys
+        #[arg(long, default_value_t = false)]
+        all: bool,
+
+        /// Output format
+        #[arg(long, default_value = "table")]
+        format: OutputFormat,
+    },
+
+    /// Revoke an API key
+    Revoke {
+        /// API key ID
+        #[arg(value_name = "KEY_ID")]
+        id: Uuid,
+    },
+}
+
+#[derive(Subcommand)]
+enum BreakerCommands {
+    /// List circuit breakers and their last reported state
+    List {
+        /// Output format
+        #[arg(long, default_value = "table")]
+        format: OutputFormat,
+    },
+
+    /// Close a circuit breaker; workers reset theirs on their next poll
+    Reset {
+        /// Circuit breaker name (e.g., "llm")
+        #[arg(value_name = "NAME")]
+        name: String,
+    },
+}
+
+#[derive(Subcommand)]
+enum AuditCommands {
+    /// List audit entries, newest first
+    List {
+        /// Only entries recorded within this age, as a number with an s, m, h, d, or w suffix (e.g. 7d)
+        #[arg(long, value_name = "AGE", value_parser = parse_age)]
+        since: Option<Duration>,
+
+        /// Only entries for this action (e.g. schema.create, job.cancel, api_key.revoke)
+        #[arg(long)]
+        action: Option<AuditAction>,
+
+        /// Maximum entries to show
+        #[arg(long, default_value_t = 50)]
+        limit: usize,
+
+        /// Entries to skip, for paging
+        #[arg(long, default_value_t = 0)]
+        offset: usize,
+
+        /// Output format
+        #[arg(long, default_value = "table")]
+        format: OutputFormat,
+    },
+}
+
+#[derive(Subcommand)]
+enum StatsCommands {
+    /// Per-domain job successes and failures, most failures first
+    Domains {
+        /// Window to count over, as a number with an s, m, h, d, or w suffix (e.g. 7d)
+        #[arg(long, value_name = "AGE", default_value = "24h", value_parser = parse_age)]
+        since: Duration,
+
+        /// Output format
+        #[arg(long, default_value = "table")]
+        format: OutputFormat,
+    },
+}
+
+#[derive(Subcommand)]
+enum DbCommands {
+    /// Apply pending migrations and print the versions applied
+    Migrate,
+
+    /// List applied and pending migrations, with row counts
+    Status {
+        /// Output format
+        #[arg(long, default_value = "table")]
+        format: OutputFormat,
+    },
+
+    /// Delete finished jobs, worker events, and crawl bookkeeping older than
+    /// the retention period, and expire extractions whose schema declares
+    /// `x-ares-retention`; other extractions are kept
+    Prune {
+        /// Retention period in days
+        #[arg(long, value_name = "DAYS", value_parser = clap::value_parser!(u32).range(1..))]
+        older_than_days: u32,
+
+        /// Also delete the archived snapshots of extractions older than the
+        /// retention period, unless a newer extraction shares them
+        #[arg(long, default_value_t = false)]
+        snapshots: bool,
+
+        /// Snapshot store to prune (file:///path or s3://bucket/prefix)
+        #[arg(long, value_name = "URL", env = "ARES_SNAPSHOT_URL")]
+        snapshot_url: Option<String>,
+    },
+}
+
+#[derive(Subcommand)]
+enum ModelCommands {
+    /// Download a supported native model into Ares' local cache
+    Pull {
+        #[arg(value_name = "MODEL")]
+        model: String,
+    },
+    /// Show native models currently cached by Ares
+    List,
+    /// Remove a native model from Ares' local cache
+    Remove {
+        #[arg(value_name = "MODEL")]
+        model: String,
+    },
+}
+
+#[derive(Subcommand)]
+enum ConfigCommands {
+    /// Print the settings in effect before flags (environment, then config
+    /// file, then built-in defaults), with secrets masked
+    Show,
+}
+
+/// Secrets passed on the command line (or filled in from the config file):
+/// the LLM `--api-key` and the `--remote` `--token`.
+fn cli_secrets(cli: &Cli) -> Vec<String> {
+    let api_key = match &cli.command {
+        Commands::Scrape { api_key, .. }
+        | Commands::Verify { api_key, .. }
+        | Commands::Worker { api_key, .. } => api_key.clone(),
+        _ => None,
+    };
+    api_key.into_iter().chain(cli.token.clone()).collect()
+}
+
+/// Worker events listed by `job show --events`.
+const JOB_SHOW_EVENTS: usize = 50;
+
+#[tokio::main]
+async fn main() -> ExitCode {
+    match run().await {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(err) => {
+            eprintln!("Error: {err:?}");
+            exit::code(&err)
+        }
+    }
+}
+
+async fn run() -> Result<()> {
+    let _ = dotenvy::dotenv();
+    completions::complete_from_env();
+
+    let mut cli = Cli::parse();
+
+    let config = CliConfig::load()?;
+    config.apply(&mut cli.command, cli.remote.is_some());
+
+    let mut log_config = LogConfig::from_env()?;
+    if cli.quiet {
+        log_config.level = log_config.level.min(LevelFilter::WARN);
+    }
+    // Keys given as flags or in the config file are as secret as the
+    // environment's.
+    log_config.secrets.extend(cli_secrets(&cli));
+    init_logging(&log_config, std::io::stderr)?;
+    let color = !cli.no_color && std::env::var_os("NO_COLOR").is_none();
+    let remote = cli
+        .remote
+        .as_deref()
+        .map(|url| AresApiClient::new(url, cli.token.as_deref()))
+        .transpose()?;
+    if remote.is_some()
+        && let Some(command) = match cli.command {
+            Commands::Crawl { .. } => Some("crawl"),
+            Commands::ApiKey { .. } => Some("apikey"),
+            Commands::Breaker { .. } => Some("breaker"),
+            Commands::Audit { .. } => Some("audit"),
+            Commands::Usage { .. } => Some("usage"),
+            Commands::Stats { .. } => Some("stats"),
+            Commands::Verify { .. } => Some("verify"),
+            Commands::Db { .. } => Some("db"),
+            Commands::Worker { .. } => Some("worker"),
+            _ => None,
+        }
+    {
+        return Err(
+            AppError::ConfigError(format!("`ares {command}` does not support --remote")).into(),
+        );
+    }
 
     match cli.command {
         Commands::Scrape {
             url,
+            urls_file,
+            concurrency,
+            streaming,
+            state_file,
+            resume,
             schema,
+            html_file,
             model,
             provider,
             base_url,
@@ -494,50 +1408,149 @@ async fn main() -> Result<()> {
             browser,
             fetch_timeout,
             llm_timeout,
+            fetch_auth,
+            notify,
+            events,
+            snapshots,
             system_prompt,
             skip_unchanged,
+            upsert,
+            dedup_window_hours,
+            reuse_duplicates,
+            content_similarity_threshold,
             throttle,
+            user_agent,
             proxy,
             proxy_file,
             proxy_rotation,
             random_ua,
             stealth,
+            browser_script,
+            browser_user_agent,
             tls_backend,
+            max_response_size,
             no_cache,
             cache_ttl,
             max_content,
             format,
+            output,
         } => {
-            let resolved = SchemaResolver::new("schemas").resolve(&schema)?;
-            validate_schema(&resolved.schema).map_err(|e| anyhow::anyhow!("{e}"))?;
-            let schema_name = schema_name.unwrap_or(resolved.name);
-            let schema_value = resolved.schema;
+            let schema = schema.ok_or_else(|| {
+                AppError::ConfigError(
+                    "--schema is required (or set `schema` under [scrape] in ares.toml)".into(),
+                )
+            })?;
+            let model = require_model(model)?;
+            let (resolved_name, schema_value) =
+                schema_store(remote.as_ref()).resolve(&schema).await?;
+            validate_schema(&schema_value)?;
+            let schema_name = schema_name.unwrap_or(resolved_name);
+            let fetch_auth = fetch_auth.into_auth()?;
+
+            let provider = provider.as_deref().unwrap_or(config::DEFAULT_PROVIDER);
+            let provider = Provider::parse(provider)?;
+            let html = html_file
+                .map(|path| {
+                    std::fs::read_to_string(&path)
+                        .with_context(|| format!("Failed to read HTML file: {path}"))
+                })
+                .transpose()?;
+            if streaming && format.is_some_and(|f| f != OutputFormat::Jsonl) {
+                anyhow::bail!("--streaming always writes JSON lines; drop --format");
+            }
+            let format = format.unwrap_or(if urls_file.is_some() {
+                OutputFormat::Jsonl
+            } else {
+                OutputFormat::Json
+            });
+            let target = match (urls_file, url, html) {
+                (Some(path), _, _) => ScrapeTarget::Batch {
+                    urls: read_urls_file(&path)?,
+                    concurrency: concurrency
+                        .unwrap_or(ares_core::scrape::DEFAULT_BATCH_CONCURRENCY),
+                    streaming,
+                    state_file,
+                    resume,
+                },
+                (None, Some(url), Some(html)) => ScrapeTarget::Html { url, html },
+                (None, Some(url), None) => ScrapeTarget::Url(url),
+                (None, None, _) => unreachable!("clap requires --url or --urls-file"),
+            };
+
+            if let Some(api) = &remote {
+                reject_local_only(&[
+                    ("--system-prompt", system_prompt.is_some()),
+                    ("--skip-unchanged", skip_unchanged),
+                    ("--upsert", upsert),
+                    ("--dedup-window-hours", dedup_window_hours.is_some()),
+                    ("--reuse-duplicates", reuse_duplicates),
+                    ("--throttle", throttle.is_some()),
+                    ("--user-agent", user_agent.is_some()),
+                    ("--proxy", proxy.is_some()),
+                    ("--proxy-file", proxy_file.is_some()),
+                    ("--random-ua", random_ua),
+                    ("--stealth", stealth),
+                    ("--browser-script", browser_script.is_some()),
+                    ("--max-response-size", max_response_size.is_some()),
+                    ("--no-cache", no_cache),
+                    ("--max-content", max_content.is_some()),
+                ])?;
+                let request = ScrapeRequest {
+                    schema: schema_value,
+                    schema_name,
+                    model: Some(model),
+                    provider: Some(provider.name().to_string()),
+                    base_url,
+                    save,
+                    user_agent: browser_user_agent,
+                    content_similarity_threshold,
+                    fetch: FetchOptions {
+                        use_browser: browser,
+                        fetch_timeout_secs: fetch_timeout,
+                        llm_timeout_secs: llm_timeout,
+                        auth: fetch_auth,
+                        ..FetchOptions::default()
+                    },
+                    ..ScrapeRequest::default()
+                };
+                scrape_remote(api, request, target, format, output.as_deref()).await?;
+                return Ok(());
+            }
 
-            let provider = Provider::parse(&provider).map_err(|e| anyhow::anyhow!("{e}"))?;
             let base_url = base_url.unwrap_or_else(|| provider.default_base_url().to_string());
             let api_key = api_key_for(provider, api_key.as_deref())?;
-
             let fetch_timeout = fetch_timeout.map(Duration::from_secs);
             let proxy_config = build_proxy_config(proxy, proxy_file, &proxy_rotation)?;
+            let browser_script = read_browser_script(browser_script.as_deref())?;
             let tls: TlsBackend = tls_backend
                 .parse()
                 .map_err(|e: String| anyhow::anyhow!("{e}"))?;
             let opts = ScrapeOpts {
-                url: &url,
+                target,
                 schema_value,
                 schema_name: &schema_name,
                 model: &model,
                 provider,
                 base_url: &base_url,
                 api_key: &api_key,
-                save,
+                database: save.then(|| config.database()).transpose()?,
                 llm_timeout: llm_timeout.map(Duration::from_secs),
+                fetch_auth,
+                notifiers: notify.into_notifiers()?,
+                events: events.into_publisher()?,
+                snapshots: snapshots.into_store()?,
                 system_prompt: system_prompt.as_deref(),
                 skip_unchanged,
+                upsert,
+                dedup_window: dedup_window_hours
+                    .map(|hours| Duration::from_secs(hours.saturating_mul(3600))),
+                reuse_duplicates,
+                content_similarity_threshold,
                 no_cache,
                 cache_ttl,
                 max_content,
                 format,
+                output,
             };
 
             with_fetcher!(
@@ -546,8 +1559,12 @@ async fn main() -> Result<()> {
                 throttle,
                 proxy_config,
                 random_ua,
+                user_agent.as_deref(),
                 stealth,
+                browser_script.as_deref(),
+                browser_user_agent.as_deref(),
                 tls,
+                max_response_size,
                 |f| cmd_scrape(f, opts).await
             )
             .await?;
@@ -557,18 +1574,92 @@ async fn main() -> Result<()> {
             url,
             schema_name,
             limit,
+            language,
+            format,
+        } => {
+            let backend = Backend::connect(remote, &config).await?;
+            cmd_history(
+                &url,
+                &schema_name,
+                language.as_deref(),
+                limit,
+                &backend,
+                format,
+                color,
+            )
+            .await?;
+        }
+
+        Commands::Latest { url, schema_name } => {
+            let backend = Backend::connect(remote, &config).await?;
+            cmd_latest(&url, &schema_name, &backend, &mut std::io::stdout()).await?;
+        }
+
+        Commands::Verify {
+            schema,
+            model,
+            provider,
+            base_url,
+            api_key,
+            system_prompt,
+            fetch_timeout,
+            llm_timeout,
+            snapshot_url,
             format,
         } => {
-            let db = Database::connect(&DatabaseConfig::from_env()?).await?;
+            let model = require_model(model)?;
+            let (schema_name, schema_value) = schema_store(None).resolve(&schema).await?;
+            validate_schema(&schema_value)?;
+            let provider =
+                Provider::parse(provider.as_deref().unwrap_or(config::DEFAULT_PROVIDER))?;
+            let base_url = base_url.unwrap_or_else(|| provider.default_base_url().to_string());
+            let api_key = api_key_for(provider, api_key.as_deref())?;
+            let extractor = ProviderExtractor::build(
+                provider,
+                &api_key,
+                &model,
+                &base_url,
+                llm_timeout.map(Duration::from_secs),
+                system_prompt.as_deref(),
+            )?;
+            let fetcher = create_http_fetcher(
+                fetch_timeout.map(Duration::from_secs),
+                None,
+                false,
+                None,
+                TlsBackend::default(),
+                None,
+            )?;
+            let service = ScrapeServiceBuilder::new(fetcher, HtmdCleaner::new(), extractor)
+                .with_model(&model)
+                .with_provider(provider.name())
+                .with_store(NullStore)
+                .build()?;
+            let snapshots = Snapshots::new(snapshot_url.as_deref())?;
+
+            let db = Database::connect(&config.database()?).await?;
             db.migrate().await?;
-            let repo = db.extraction_repo();
-            cmd_history(&url, &schema_name, limit, &repo, format).await?;
+            let goldens = db.extraction_repo().list_golden(&schema_name).await?;
+            if goldens.is_empty() {
+                println!("No golden extractions for {schema_name}.");
+                return Ok(());
+            }
+
+            let report =
+                verify_goldens(&service, &snapshots, &goldens, &schema_value, &schema_name).await;
+            print_verify_report(&report, format, color)?;
+            if !report.passed() {
+                return Err(exit::VerifyFailed {
+                    failed: report.failed(),
+                    total: report.checks.len(),
+                }
+                .into());
+            }
         }
 
         Commands::Job { action } => {
-            let db = Database::connect(&DatabaseConfig::from_env()?).await?;
-            db.migrate().await?;
-            let job_repo = db.job_repo();
+            let schemas = schema_store(remote.as_ref());
+            let backend = Backend::connect(remote, &config).await?;
 
             match action {
                 JobCommands::Create {
@@ -577,130 +1668,194 @@ async fn main() -> Result<()> {
                     model,
                     base_url,
                     schema_name,
+                    notify_url,
+                    tags,
+                    fetch,
+                    sitemap,
                 } => {
-                    let resolved = SchemaResolver::new("schemas").resolve(&schema)?;
-                    validate_schema(&resolved.schema).map_err(|e| anyhow::anyhow!("{e}"))?;
-                    let schema_name = schema_name.unwrap_or(resolved.name);
-                    let schema_value = resolved.schema;
+                    let fetch_options = fetch.into_options()?;
+                    let (resolved_name, schema_value) = schemas.resolve(&schema).await?;
+                    validate_schema(&schema_value)?;
+                    let schema_name = schema_name.unwrap_or(resolved_name);
 
                     let request = CreateScrapeJobRequest::new(
-                        url,
+                        url.unwrap_or_default(),
                         schema_name,
                         schema_value,
-                        model,
-                        base_url,
-                    );
-                    let job = job_repo.create_job(request).await?;
-                    println!("Created job: {}", job.id);
+                        require_model(model)?,
+                        base_url.unwrap_or_else(|| Provider::OpenAi.default_base_url().into()),
+                    )
+                    .with_fetch_options(fetch_options)
+                    .with_notify_url(notify_url)
+                    .with_tags(tags);
+
+                    if let Some(sitemap_url) = sitemap.sitemap.clone() {
+                        let options = sitemap.into_options();
+                        let (ids, skipped) = backend
+                            .create_jobs_from_sitemap(&sitemap_url, &options, request)
+                            .await?;
+                        println!("Created {} jobs from {sitemap_url}", ids.len());
+                        if skipped > 0 {
+                            println!("Skipped {skipped} unmodified URLs");
+                        }
+                    } else {
+                        let id = backend.create_job(request).await?;
+                        println!("Created job: {id}");
+                    }
                 }
 
                 JobCommands::List {
                     status,
+                    worker,
+                    tag,
                     limit,
                     format,
                 } => {
-                    let status_filter = status
-                        .map(|s| {
-                            s.parse::<JobStatus>()
-                                .map_err(|e| anyhow::anyhow!("Invalid status: {e}"))
-                        })
-                        .transpose()?;
-
-                    let jobs = job_repo.list_jobs(status_filter, limit, 0).await?;
+                    let jobs = backend
+                        .list_jobs(status, worker.as_deref(), tag.as_deref(), limit)
+                        .await?;
 
                     if jobs.is_empty() {
                         println!("No jobs found.");
                         return Ok(());
                     }
 
-                    let val = match format {
-                        OutputFormat::Table => {
-                            let mut rows = vec![];
-                            for job in &jobs {
-                                let url_display = if job.url.chars().count() > 38 {
-                                    let truncated: String = job.url.chars().take(35).collect();
-                                    format!("{truncated}...")
-                                } else {
-                                    job.url.clone()
-                                };
-                                rows.push(serde_json::json!({
-                                    "ID": job.id.to_string(),
-                                    "STATUS": job.status.to_string(),
-                                    "URL": url_display,
-                                    "MODEL": job.model.clone(),
-                                    "CREATED": job.created_at.format("%Y-%m-%d %H:%M").to_string()
-                                }));
-                            }
-                            serde_json::to_value(rows)?
-                        }
-                        _ => serde_json::to_value(&jobs)?,
-                    };
-
-                    OutputFormatter::format(format, &val)?;
-
                     if format == OutputFormat::Table {
+                        println!("{}", format::format_jobs_table(&jobs, color));
                         println!("\nTotal: {} jobs", jobs.len());
+                    } else {
+                        // Jobs carry their fetch credentials; never print them.
+                        let jobs: Vec<ScrapeJob> = jobs
+                            .into_iter()
+                            .map(|job| ScrapeJob {
+                                fetch_options: job.fetch_options.redacted(),
+                                ..job
+                            })
+                            .collect();
+                        OutputFormatter::format(format, &serde_json::to_value(&jobs)?)?;
                     }
                 }
 
-                JobCommands::Show { id } => {
-                    let job = job_repo
+                JobCommands::Show { id, events } => {
+                    let job = backend
                         .get_job(id)
                         .await?
                         .ok_or_else(|| anyhow::anyhow!("Job not found: {id}"))?;
 
-                    println!("Job: {}", job.id);
-                    println!("  Status:      {}", job.status);
-                    println!("  URL:         {}", job.url);
-                    println!("  Schema:      {}", job.schema_name);
-                    println!("  Model:       {}", job.model);
-                    println!("  Base URL:    {}", job.base_url);
-                    println!("  Created:     {}", job.created_at);
-                    println!("  Updated:     {}", job.updated_at);
-                    if let Some(started) = job.started_at {
-                        println!("  Started:     {started}");
-                    }
-                    if let Some(completed) = job.completed_at {
-                        println!("  Completed:   {completed}");
-                    }
-                    println!("  Retries:     {}/{}", job.retry_count, job.max_retries);
-                    if let Some(next) = job.next_retry_at {
-                        println!("  Next retry:  {next}");
-                    }
-                    if let Some(err) = &job.error_message {
-                        println!("  Error:       {err}");
-                    }
-                    if let Some(eid) = job.extraction_id {
-                        println!("  Extraction:  {eid}");
-                    }
-                    if let Some(wid) = &job.worker_id {
-                        println!("  Worker:      {wid}");
+                    println!("{}", format::format_job_detail(&job, color));
+
+                    if events {
+                        let events = backend.job_events(id, JOB_SHOW_EVENTS).await?;
+                        if events.is_empty() {
+                            println!("No worker events recorded for this job.");
+                        } else {
+                            println!("{}", format::format_job_events(&events, color));
+                        }
                     }
                 }
 
                 JobCommands::Cancel { id } => {
-                    job_repo.cancel_job(id).await?;
+                    backend.cancel_job(id).await?;
                     println!("Cancelled job: {id}");
                 }
+
+                JobCommands::Cleanup {
+                    older_than,
+                    status,
+                    dry_run,
+                } => {
+                    let jobs = backend.jobs_for_cleanup(older_than, status).await?;
+                    if jobs.is_empty() {
+                        println!("No {status} jobs to clean up.");
+                        return Ok(());
+                    }
+
+                    if dry_run {
+                        println!("{}", format::format_jobs_table(&jobs, color));
+                        println!("\nWould delete {} {status} jobs", jobs.len());
+                    } else {
+                        let ids: Vec<Uuid> = jobs.iter().map(|job| job.id).collect();
+                        let deleted = backend.delete_jobs(&ids).await?;
+                        println!("Deleted {deleted} {status} jobs");
+                    }
+                }
             }
         }
 
-        Commands::Schema { action } => match action {
-            SchemaCommands::Validate { path } => {
-                let content = std::fs::read_to_string(&path)
-                    .with_context(|| format!("Failed to read file: {path}"))?;
-                let value: serde_json::Value = serde_json::from_str(&content)
-                    .with_context(|| format!("Invalid JSON in file: {path}"))?;
-                validate_schema(&value).map_err(|e| anyhow::anyhow!("{e}"))?;
-                println!("Valid JSON Schema: {path}");
-            }
-        },
+        Commands::Schema {
+            schemas_dir,
+            action,
+        } => {
+            let store = match remote {
+                Some(api) => SchemaStore::Remote(api),
+                None => SchemaStore::Local(SchemaResolver::new(schemas_dir)),
+            };
+            cmd_schema(action, &store, &mut std::io::stdout().lock()).await?;
+        }
+
+        Commands::ApiKey { action } => {
+            let db = Database::connect(&config.database()?).await?;
+            db.migrate().await?;
+            cmd_apikey(action, &db.api_key_repo()).await?;
+        }
+
+        Commands::Breaker { action } => {
+            let db = Database::connect(&config.database()?).await?;
+            db.migrate().await?;
+            cmd_breaker(action, &db.circuit_breaker_repo()).await?;
+        }
+
+        Commands::Audit { action } => {
+            let db = Database::connect(&config.database()?).await?;
+            db.migrate().await?;
+            cmd_audit(action, &db.audit_repo()).await?;
+        }
+
+        Commands::Usage {
+            days,
+            budget,
+            format,
+        } => {
+            let db = Database::connect(&config.database()?).await?;
+            db.migrate().await?;
+            cmd_usage(days, &budget.limits(), format, &db.usage_repo()).await?;
+        }
+
+        Commands::Stats { action } => {
+            let db = Database::connect(&config.database()?).await?;
+            db.migrate().await?;
+            cmd_stats(action, &db.stats_repo()).await?;
+        }
+
+        Commands::Db { action } => {
+            let db = Database::connect(&config.database()?).await?;
+            cmd_db(action, &db).await?;
+        }
 
         Commands::Model { action } => cmd_model(action)?,
 
+        Commands::Config { action } => match action {
+            ConfigCommands::Show => config
+                .effective(|key| std::env::var(key).ok())
+                .masked()
+                .write_toml(&mut std::io::stdout().lock())?,
+        },
+
+        Commands::Completions { shell, dynamic } => {
+            completions::write_completions(shell, dynamic, &mut std::io::stdout().lock())?
+        }
+
+        Commands::Manpages { out_dir } => {
+            completions::write_manpages(&out_dir)?;
+            println!("Wrote man pages to {}", out_dir.display());
+        }
+
         Commands::Worker {
             worker_id,
             poll_interval,
+            max_jobs,
+            once,
+            health_addr,
             api_key,
             provider,
             browser,
@@ -708,50 +1863,119 @@ async fn main() -> Result<()> {
             llm_timeout,
             system_prompt,
             skip_unchanged,
+            upsert,
+            store_content,
+            schemas_dir,
+            schema_filter,
+            notify,
+            events,
+            snapshots,
+            budget,
             throttle,
+            user_agent,
             proxy,
             proxy_file,
             proxy_rotation,
             random_ua,
             stealth,
+            browser_script,
+            browser_user_agent,
             tls_backend,
+            max_response_size,
             no_cache,
             cache_ttl,
+            auto_migrate,
         } => {
-            let provider = Provider::parse(&provider).map_err(|e| anyhow::anyhow!("{e}"))?;
+            let provider = provider.as_deref().unwrap_or(config::DEFAULT_PROVIDER);
+            let provider = Provider::parse(provider)?;
             let api_key = api_key_for(provider, api_key.as_deref())?;
             let proxy_config = build_proxy_config(proxy, proxy_file, &proxy_rotation)?;
+            let browser_script = read_browser_script(browser_script.as_deref())?;
             let tls: TlsBackend = tls_backend
                 .parse()
                 .map_err(|e: String| anyhow::anyhow!("{e}"))?;
             let worker_opts = WorkerOpts {
+                database: config.database()?,
                 api_key: &api_key,
                 provider,
                 worker_id,
-                poll_interval,
+                poll_interval: poll_interval.unwrap_or(config::DEFAULT_POLL_INTERVAL_SECS),
+                max_jobs,
+                once,
+                health_addr,
                 fetch_timeout: fetch_timeout.map(Duration::from_secs),
                 llm_timeout: llm_timeout.map(Duration::from_secs),
                 system_prompt: system_prompt.as_deref(),
                 skip_unchanged,
+                upsert,
+                store_content,
+                schemas_dir,
+                schema_filter,
+                notifiers: notify.into_notifiers()?,
+                events: events.into_publisher()?,
+                snapshots: snapshots.into_store()?,
+                budget: budget.limits(),
                 no_cache,
                 cache_ttl,
+                auto_migrate,
             };
 
-            with_fetcher!(
-                browser,
+            // The browser is launched on the first job that needs it, or at
+            // startup with --browser so a missing Chromium fails fast.
+            let browser_proxy = proxy_config
+                .as_ref()
+                .map(|pc: &ProxyConfig| pc.next().authenticated_url());
+            let browser_timeout = worker_opts.fetch_timeout;
+            let browser_fetcher = LazyFetcher::new(move || {
+                let proxy_url = browser_proxy.clone();
+                let script = browser_script.clone();
+                let user_agent = browser_user_agent.clone();
+                async move {
+                    create_browser_fetcher(
+                        browser_timeout,
+                        proxy_url.as_deref(),
+                        stealth,
+                        script.as_deref(),
+                        user_agent.as_deref(),
+                    )
+                    .await
+                    .map_err(|e| AppError::Generic(format!("{e:#}")))
+                }
+            });
+            if browser {
+                browser_fetcher
+                    .get()
+                    .await
+                    .map_err(|e| anyhow::anyhow!("{e}"))?;
+            }
+
+            let http = create_http_fetcher(
                 worker_opts.fetch_timeout,
-                throttle,
                 proxy_config,
                 random_ua,
-                stealth,
+                user_agent.as_deref(),
                 tls,
-                |f| cmd_worker(f, worker_opts).await
-            )
-            .await?;
+                max_response_size,
+            )?;
+            let mut fetcher = FetcherSelector::new(http).with_browser_by_default(browser);
+            if cfg!(feature = "browser") {
+                fetcher = fetcher.with_browser(browser_fetcher);
+            }
+
+            match throttle.filter(|&ms| ms > 0) {
+                Some(ms) => {
+                    let fetcher = ThrottledFetcher::new(
+                        fetcher,
+                        ThrottleConfig::new(Duration::from_millis(ms)),
+                    );
+                    cmd_worker(fetcher, worker_opts).await?;
+                }
+                None => cmd_worker(fetcher, worker_opts).await?,
+            }
         }
 
         Commands::Crawl { action } => {
-            let db = Database::connect(&DatabaseConfig::from_env()?).await?;
+            let db = Database::connect(&config.database()?).await?;
             db.migrate().await?;
 
             match action {
@@ -764,9 +1988,11 @@ async fn main() -> Result<()> {
                     max_pages,
                     allowed_domains,
                     schema_name,
+                    fetch,
                 } => {
+                    let fetch_options = fetch.into_options()?;
                     let resolved = SchemaResolver::new("schemas").resolve(&schema)?;
-                    validate_schema(&resolved.schema).map_err(|e| anyhow::anyhow!("{e}"))?;
+                    validate_schema(&resolved.schema)?;
                     let schema_name = schema_name.unwrap_or(resolved.name);
                     let schema_value = resolved.schema;
 
@@ -786,11 +2012,12 @@ async fn main() -> Result<()> {
                         url,
                         schema_name,
                         schema_value,
-                        model,
-                        base_url,
+                        require_model(model)?,
+                        base_url.unwrap_or_else(|| Provider::OpenAi.default_base_url().into()),
                     )
                     .with_crawl_context(session_id, None, 0, max_depth)
-                    .with_crawl_config(max_pages, allowed_domains);
+                    .with_crawl_config(max_pages, allowed_domains)
+                    .with_fetch_options(fetch_options);
 
                     let job = db.job_repo().create_job(request).await?;
                     println!("Crawl started!");
@@ -805,68 +2032,634 @@ async fn main() -> Result<()> {
                         return Ok(());
                     }
 
-                    let mut pending: i64 = 0;
-                    let mut running: i64 = 0;
-                    let mut completed: i64 = 0;
-                    let mut failed: i64 = 0;
-                    let mut cancelled: i64 = 0;
+                    let mut pending: i64 = 0;
+                    let mut running: i64 = 0;
+                    let mut completed: i64 = 0;
+                    let mut failed: i64 = 0;
+                    let mut cancelled: i64 = 0;
+
+                    for (status, count) in &counts {
+                        match status.as_str() {
+                            "pending" => pending = *count,
+                            "running" => running = *count,
+                            "completed" => completed = *count,
+                            "failed" => failed = *count,
+                            "cancelled" => cancelled = *count,
+                            _ => {}
+                        }
+                    }
+
+                    let total = pending + running + completed + failed + cancelled;
+
+                    println!("Crawl Session: {id}");
+                    println!("  Total Jobs:     {total}");
+                    println!("  Pending:        {pending}");
+                    println!("  Running:        {running}");
+                    println!("  Completed:      {completed}");
+                    println!("  Failed:         {failed}");
+                    println!("  Cancelled:      {cancelled}");
+
+                    if total > 0 {
+                        let progress = (completed as f64 / total as f64) * 100.0;
+                        println!("  Progress:       {progress:.1}%");
+                    }
+                }
+
+                CrawlCommands::Results { id, format, output } => {
+                    let extractions = db.extraction_repo().get_by_crawl_session(id).await?;
+                    if extractions.is_empty() {
+                        eprintln!("No results found for this crawl session.");
+                        return Ok(());
+                    }
+
+                    let rows: Vec<_> = extractions
+                        .into_iter()
+                        .map(|e| serde_json::json!({"url": e.url, "data": e.extracted_data}))
+                        .collect();
+                    let mut out = OutputWriter::new(format, output.as_deref())?;
+                    out.write(&serde_json::Value::Array(rows))?;
+                    out.finish()?;
+                }
+            }
+        }
+    }
+
+    Ok(())
+}
+
+fn api_key_for(provider: Provider, api_key: Option<&str>) -> Result<String> {
+    match (provider, api_key.filter(|key| !key.trim().is_empty())) {
+        (Provider::Local, key) => Ok(key.unwrap_or_default().to_string()),
+        (_, Some(key)) => Ok(key.to_string()),
+        (_, None) => Err(AppError::ConfigError(format!(
+            "--api-key (or ARES_API_KEY) is required for {provider:?} provider"
+        ))
+        .into()),
+    }
+}
+
+/// The schemas that `--schema` references resolve against: the server's with
+/// `--remote`, otherwise the local `schemas/` directory.
+/// The model from `--model`, `ARES_MODEL`, or the config file.
+fn require_model(model: Option<String>) -> Result<String> {
+    model.ok_or_else(|| {
+        AppError::ConfigError(
+            "--model is required (or set ARES_MODEL, or `model` under [llm] in ares.toml)".into(),
+        )
+        .into()
+    })
+}
+
+fn schema_store(remote: Option<&AresApiClient>) -> SchemaStore {
+    match remote {
+        Some(api) => SchemaStore::Remote(api.clone()),
+        None => SchemaStore::Local(SchemaResolver::new("schemas")),
+    }
+}
+
+/// Parse an age like `90s`, `15m`, `12h`, `30d`, or `2w`.
+fn parse_age(s: &str) -> Result<Duration, String> {
+    let split = s.find(|c: char| !c.is_ascii_digit()).unwrap_or(s.len());
+    let (amount, unit) = s.split_at(split);
+    let amount: u64 = amount
+        .parse()
+        .map_err(|_| format!("invalid age '{s}', expected e.g. 30d"))?;
+    let unit_secs = match unit {
+        "s" => 1,
+        "m" => 60,
+        "h" => 60 * 60,
+        "d" => 24 * 60 * 60,
+        "w" => 7 * 24 * 60 * 60,
+        _ => {
+            return Err(format!(
+                "invalid age '{s}', expected a s, m, h, d, or w suffix"
+            ));
+        }
+    };
+    match amount.checked_mul(unit_secs) {
+        Some(0) => Err("age must be greater than zero".into()),
+        Some(secs) => Ok(Duration::from_secs(secs)),
+        None => Err(format!("age '{s}' is too large")),
+    }
+}
+
+/// One of `statuses`, listed in `--help` and parsed by [`JobStatus`]'s
+/// `FromStr` like the server's `status` query parameter.
+fn job_status_parser(statuses: &[JobStatus]) -> impl TypedValueParser<Value = JobStatus> {
+    PossibleValuesParser::new(statuses.iter().map(|status| status.as_str()))
+        .try_map(|s| s.parse::<JobStatus>())
+}
+
+/// Fail if any of `flags` (name, whether it was given) is set, since the
+/// server does not accept them.
+fn reject_local_only(flags: &[(&str, bool)]) -> Result<()> {
+    if let Some((flag, _)) = flags.iter().find(|(_, set)| *set) {
+        return Err(AppError::ConfigError(format!("{flag} is not supported with --remote")).into());
+    }
+    Ok(())
+}
+
+async fn cmd_apikey(action: ApiKeyCommands, repo: &ApiKeyRepository) -> Result<()> {
+    let scope_list = |scopes: &[ApiKeyScope]| {
+        scopes
+            .iter()
+            .map(|s| s.as_str())
+            .collect::<Vec<_>>()
+            .join(",")
+    };
+
+    match action {
+        ApiKeyCommands::Create { name, scopes } => {
+            let scopes = scopes
+                .iter()
+                .map(|s| s.parse::<ApiKeyScope>())
+                .collect::<Result<Vec<_>, _>>()
+                .map_err(|e| anyhow::anyhow!("{e}"))?;
+            let (key, value) = repo.create(&name, &scopes).await?;
+            println!("Created API key: {}", key.id);
+            println!("  Name:    {}", key.name);
+            println!("  Scopes:  {}", scope_list(&key.scopes));
+            println!("  Key:     {value}");
+            println!("\nStore this key now — it cannot be shown again.");
+        }
+
+        ApiKeyCommands::List { all, format } => {
+            let keys = repo.list(all).await?;
+            if keys.is_empty() {
+                println!("No API keys found.");
+                return Ok(());
+            }
+
+            let val = match format {
+                OutputFormat::Table => {
+                    let rows: Vec<_> = keys
+                        .iter()
+                        .map(|k| {
+                            serde_json::json!({
+                                "ID": k.id.to_string(),
+                                "NAME": k.name.clone(),
+                                "SCOPES": scope_list(&k.scopes),
+                                "CREATED": k.created_at.format("%Y-%m-%d %H:%M").to_string(),
+                                "LAST_USED": k.last_used_at
+                                    .map(|t| t.format("%Y-%m-%d %H:%M").to_string())
+                                    .unwrap_or_default(),
+                                "REVOKED": k.revoked_at
+                                    .map(|t| t.format("%Y-%m-%d %H:%M").to_string())
+                                    .unwrap_or_default(),
+                            })
+                        })
+                        .collect();
+                    serde_json::to_value(rows)?
+                }
+                _ => serde_json::to_value(&keys)?,
+            };
+
+            OutputFormatter::format(format, &val)?;
+        }
+
+        ApiKeyCommands::Revoke { id } => {
+            if !repo.revoke(id).await? {
+                anyhow::bail!("Active API key not found: {id}");
+            }
+            println!("Revoked API key: {id}");
+        }
+    }
+
+    Ok(())
+}
+
+async fn cmd_breaker(action: BreakerCommands, repo: &CircuitBreakerRepository) -> Result<()> {
+    match action {
+        BreakerCommands::List { format } => {
+            let breakers = repo.list().await?;
+            if breakers.is_empty() {
+                println!("No circuit breakers reported yet.");
+                return Ok(());
+            }
+
+            let rows: Vec<_> = breakers
+                .iter()
+                .map(|b| {
+                    let retry_after = b.stats.time_until_half_open.map(|d| d.as_secs());
+                    match format {
+                        OutputFormat::Table => serde_json::json!({
+                            "NAME": b.stats.name.clone(),
+                            "STATE": b.stats.state.to_string(),
+                            "FAILURES": b.stats.failure_count,
+                            "RETRY_IN": retry_after.map(|s| format!("{s}s")).unwrap_or_default(),
+                            "LAST_ERROR": b.stats.last_error.clone().unwrap_or_default(),
+                            "UPDATED": b.updated_at.format("%Y-%m-%d %H:%M:%S").to_string(),
+                        }),
+                        _ => serde_json::json!({
+                            "name": b.stats.name,
+                            "state": b.stats.state.to_string(),
+                            "failure_count": b.stats.failure_count,
+                            "success_count": b.stats.success_count,
+                            "last_error": b.stats.last_error,
+                            "retry_after_secs": retry_after,
+                            "updated_at": b.updated_at,
+                            "reset_requested_at": b.reset_requested_at,
+                        }),
+                    }
+                })
+                .collect();
+
+            OutputFormatter::format(format, &serde_json::to_value(rows)?)?;
+        }
+
+        BreakerCommands::Reset { name } => {
+            if repo.request_reset(&name).await?.is_none() {
+                anyhow::bail!("Circuit breaker not found: {name}");
+            }
+            println!("Reset circuit breaker: {name}");
+            println!("Workers will close their breaker on their next poll.");
+        }
+    }
+
+    Ok(())
+}
+
+async fn cmd_audit(action: AuditCommands, repo: &AuditRepository) -> Result<()> {
+    match action {
+        AuditCommands::List {
+            since,
+            action,
+            limit,
+            offset,
+            format,
+        } => {
+            let since = since
+                .map(|age| {
+                    chrono::Duration::from_std(age)
+                        .ok()
+                        .and_then(|age| Utc::now().checked_sub_signed(age))
+                        .ok_or_else(|| anyhow::anyhow!("--since is too far in the past"))
+                })
+                .transpose()?;
+            let entries = repo.list(since, action, limit, offset).await?;
+            if entries.is_empty() {
+                println!("No audit entries found.");
+                return Ok(());
+            }
+
+            let val = match format {
+                OutputFormat::Table => {
+                    let rows: Vec<_> = entries
+                        .iter()
+                        .map(|e| {
+                            let actor = match (&e.api_key_id, &e.token_fingerprint) {
+                                (Some(id), _) => id.to_string(),
+                                (None, Some(fingerprint)) => format!("admin token {fingerprint}"),
+                                (None, None) => String::new(),
+                            };
+                            serde_json::json!({
+                                "TIME": e.created_at.format("%Y-%m-%d %H:%M:%S").to_string(),
+                                "ACTION": e.action.clone(),
+                                "RESOURCE": e.resource_id.clone().unwrap_or_default(),
+                                "ACTOR": actor,
+                                "REQUEST_ID": e.request_id.clone().unwrap_or_default(),
+                            })
+                        })
+                        .collect();
+                    serde_json::to_value(rows)?
+                }
+                _ => serde_json::to_value(&entries)?,
+            };
+
+            OutputFormatter::format(format, &val)?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Print LLM usage for the last `days` days (UTC), newest first, against
+/// `limits`.
+async fn cmd_usage(
+    days: u64,
+    limits: &BudgetLimits,
+    format: OutputFormat,
+    repo: &UsageRepository,
+) -> Result<()> {
+    let today = Utc::now().date_naive();
+    let since = today
+        .checked_sub_days(chrono::Days::new(days - 1))
+        .ok_or_else(|| anyhow::anyhow!("--days is too far in the past"))?;
+    let mut usage = repo.list_since(since).await?;
+    if usage.first().is_none_or(|u| u.day != today) {
+        usage.insert(0, DailyUsage::empty(today));
+    }
+
+    let cap = |max: Option<String>| max.unwrap_or_else(|| "-".to_string());
+    let val = match format {
+        OutputFormat::Table => {
+            let rows: Vec<_> = usage
+                .iter()
+                .map(|u| {
+                    serde_json::json!({
+                        "DAY": u.day.to_string(),
+                        "LLM_CALLS": u.llm_calls,
+                        "TOKENS": u.tokens,
+                        "COST_USD": format!("{:.2}", u.cost_usd),
+                        "EXCEEDED": limits.exceeded_by(u).unwrap_or_default(),
+                    })
+                })
+                .collect();
+            serde_json::to_value(rows)?
+        }
+        _ => serde_json::json!({ "limits": limits, "usage": usage }),
+    };
+    OutputFormatter::format(format, &val)?;
+
+    if matches!(format, OutputFormat::Table) {
+        println!(
+            "Daily caps: {} calls, {} tokens, ${} (resets at midnight UTC)",
+            cap(limits.max_calls.map(|n| n.to_string())),
+            cap(limits.max_tokens.map(|n| n.to_string())),
+            cap(limits.max_cost_usd.map(|usd| format!("{usd:.2}"))),
+        );
+    }
+    Ok(())
+}
+
+async fn cmd_stats(action: StatsCommands, repo: &StatsRepository) -> Result<()> {
+    match action {
+        StatsCommands::Domains { since, format } => {
+            let since = chrono::Duration::from_std(since)
+                .ok()
+                .and_then(|age| Utc::now().checked_sub_signed(age))
+                .ok_or_else(|| anyhow::anyhow!("--since is too far in the past"))?;
+            let domains = repo.domain_stats(since).await?;
+            if domains.is_empty() {
+                println!(
+                    "No jobs finished since {}.",
+                    since.format("%Y-%m-%d %H:%M:%S")
+                );
+                return Ok(());
+            }
+
+            let val = match format {
+                OutputFormat::Table => {
+                    let rows: Vec<_> = domains
+                        .iter()
+                        .map(|d| {
+                            serde_json::json!({
+                                "DOMAIN": d.domain.clone(),
+                                "SUCCEEDED": d.jobs_succeeded,
+                                "FAILED": d.jobs_failed,
+                                "FAILURE_RATE": d
+                                    .failure_rate()
+                                    .map(|rate| format!("{:.0}%", rate * 100.0))
+                                    .unwrap_or_default(),
+                                "AVG_DURATION": d
+                                    .avg_duration_secs
+                                    .map(|secs| format!("{secs:.1}s"))
+                                    .unwrap_or_default(),
+                                "LAST_SUCCESS": d
+                                    .last_success_at
+                                    .map(|t| t.format("%Y-%m-%d %H:%M:%S").to_string())
+                                    .unwrap_or_else(|| "never".to_string()),
+                                "EXTRACTIONS": d.extractions,
+                            })
+                        })
+                        .collect();
+                    serde_json::to_value(rows)?
+                }
+                _ => serde_json::to_value(&domains)?,
+            };
+
+            OutputFormatter::format(format, &val)?;
+        }
+    }
+
+    Ok(())
+}
+
+async fn cmd_db(action: DbCommands, db: &Database) -> Result<()> {
+    match action {
+        DbCommands::Migrate => {
+            let pending: Vec<_> = db
+                .migration_status()
+                .await?
+                .into_iter()
+                .filter(|m| !m.is_applied())
+                .collect();
+            db.migrate().await?;
+            if pending.is_empty() {
+                println!("Database is up to date.");
+            }
+            for m in pending {
+                println!("Applied migration {:03} {}", m.version, m.description);
+            }
+        }
+
+        DbCommands::Status { format } => {
+            let migrations = db.migration_status().await?;
+            let counts = db.table_counts().await?;
+            match format {
+                OutputFormat::Table => {
+                    let rows: Vec<_> = migrations
+                        .iter()
+                        .map(|m| {
+                            let status = if m.is_applied() { "applied" } else { "pending" };
+                            serde_json::json!({
+                                "VERSION": format!("{:03}", m.version),
+                                "DESCRIPTION": m.description,
+                                "STATUS": status,
+                                "APPLIED": m
+                                    .installed_on
+                                    .map(|t| t.format("%Y-%m-%d %H:%M:%S").to_string())
+                                    .unwrap_or_default(),
+                            })
+                        })
+                        .collect();
+                    OutputFormatter::format(format, &serde_json::to_value(rows)?)?;
+                    println!();
+                    println!("extractions: {}", counts.extractions);
+                    println!("scrape_jobs: {}", counts.scrape_jobs);
+                }
+                _ => OutputFormatter::format(
+                    format,
+                    &serde_json::json!({
+                        "migrations": migrations,
+                        "row_counts": counts,
+                    }),
+                )?,
+            }
+        }
+
+        DbCommands::Prune {
+            older_than_days,
+            snapshots,
+            snapshot_url,
+        } => {
+            // Check the store before touching the database.
+            let store = Snapshots::new(snapshot_url.as_deref())?;
+            if snapshots && !store.is_enabled() {
+                return Err(AppError::ConfigError(
+                    "--snapshots needs --snapshot-url or ARES_SNAPSHOT_URL".into(),
+                )
+                .into());
+            }
+
+            let cutoff = Utc::now() - chrono::Duration::days(older_than_days.into());
+            let report = db.prune(cutoff).await?;
+            println!(
+                "Pruned rows older than {} ({older_than_days} days):",
+                cutoff.format("%Y-%m-%d %H:%M:%S")
+            );
+            println!("  scrape_jobs:        {}", report.scrape_jobs);
+            println!("  worker_events:      {}", report.worker_events);
+            println!("  crawl_visited_urls: {}", report.crawl_visited_urls);
+
+            // Expired snapshots hold the data retention removed, so they are
+            // deleted whenever a store is configured, with or without
+            // --snapshots.
+            let repo = db.extraction_repo();
+            let mut failed = 0;
+            if store.is_enabled() {
+                let retried = repo.take_failed_snapshot_deletions().await?;
+                if !retried.is_empty() {
+                    failed = delete_snapshots(&store, &repo, &retried).await?;
+                    println!("Snapshots whose deletion failed before:");
+                    println!("  deleted:            {}", retried.len() - failed);
+                }
+            }
+
+            let retention = repo.apply_retention(Utc::now()).await?;
+            println!("Expired extractions (x-ares-retention):");
+            println!("  deleted:            {}", retention.deleted);
+            println!("  scrubbed:           {}", retention.scrubbed);
+            if store.is_enabled() {
+                let failed_expired =
+                    delete_snapshots(&store, &repo, &retention.released_snapshots).await?;
+                println!(
+                    "  snapshots:          {}",
+                    retention.released_snapshots.len() - failed_expired
+                );
+                failed += failed_expired;
+            } else if !retention.released_snapshots.is_empty() {
+                tracing::warn!(
+                    count = retention.released_snapshots.len(),
+                    "Expired snapshots left in place: no snapshot store configured"
+                );
+            }
+
+            if snapshots {
+                let keys = repo.release_snapshots(cutoff).await?;
+                let failed_old = delete_snapshots(&store, &repo, &keys).await?;
+                println!("Snapshots older than the retention period:");
+                println!("  deleted:            {}", keys.len() - failed_old);
+                failed += failed_old;
+            }
+            if failed > 0 {
+                anyhow::bail!(
+                    "{failed} snapshots could not be deleted; see the log. \
+                     The next prune retries them"
+                );
+            }
+        }
+    }
 
-                    for (status, count) in &counts {
-                        match status.as_str() {
-                            "pending" => pending = *count,
-                            "running" => running = *count,
-                            "completed" => completed = *count,
-                            "failed" => failed = *count,
-                            "cancelled" => cancelled = *count,
-                            _ => {}
-                        }
-                    }
+    Ok(())
+}
 
-                    let total = pending + running + completed + failed + cancelled;
+/// Delete `keys` from `store`, logging each failure and recording it so the
+/// next prune retries it. Returns how many failed.
+async fn delete_snapshots(
+    store: &Snapshots,
+    repo: &ExtractionRepository,
+    keys: &[String],
+) -> Result<usize> {
+    let mut failed = Vec::new();
+    for key in keys {
+        if let Err(e) = store.delete(key).await {
+            tracing::warn!(%key, error = %e, "Snapshot not deleted");
+            failed.push(key.clone());
+        }
+    }
+    repo.record_failed_snapshot_deletions(&failed).await?;
+    Ok(failed.len())
+}
 
-                    println!("Crawl Session: {id}");
-                    println!("  Total Jobs:     {total}");
-                    println!("  Pending:        {pending}");
-                    println!("  Running:        {running}");
-                    println!("  Completed:      {completed}");
-                    println!("  Failed:         {failed}");
-                    println!("  Cancelled:      {cancelled}");
+async fn cmd_schema(
+    action: SchemaCommands,
+    store: &SchemaStore,
+    out: &mut impl Write,
+) -> Result<()> {
+    match action {
+        SchemaCommands::List { format } => {
+            let schemas = store.list().await?;
+            if schemas.is_empty() {
+                writeln!(out, "No schemas registered.")?;
+                return Ok(());
+            }
 
-                    if total > 0 {
-                        let progress = (completed as f64 / total as f64) * 100.0;
-                        println!("  Progress:       {progress:.1}%");
+            let rows: Vec<_> = schemas
+                .iter()
+                .map(|s| {
+                    let created_at = s.created_at.map(DateTime::<Utc>::from);
+                    let updated_at = s.updated_at.map(DateTime::<Utc>::from);
+                    match format {
+                        OutputFormat::Table => serde_json::json!({
+                            "NAME": s.name,
+                            "LATEST": s.latest_version,
+                            "VERSIONS": format!("{} ({})", s.version_count(), s.versions.join(", ")),
+                            "UPDATED": updated_at
+                                .map(|t| t.format("%Y-%m-%d %H:%M:%S").to_string())
+                                .unwrap_or_default(),
+                        }),
+                        _ => serde_json::json!({
+                            "name": s.name,
+                            "latest_version": s.latest_version,
+                            "versions": s.versions,
+                            "version_count": s.version_count(),
+                            "created_at": created_at,
+                            "updated_at": updated_at,
+                        }),
                     }
-                }
+                })
+                .collect();
+            OutputFormatter::write(format, &serde_json::Value::Array(rows), out)?;
+        }
 
-                CrawlCommands::Results { id } => {
-                    let extractions = db.extraction_repo().get_by_crawl_session(id).await?;
-                    if extractions.is_empty() {
-                        println!("No results found for this crawl session.");
-                        return Ok(());
-                    }
+        SchemaCommands::Show { schema } => {
+            let (_, schema) = store.resolve(&schema).await?;
+            writeln!(out, "{}", serde_json::to_string_pretty(&schema)?)?;
+        }
 
-                    println!("Results for Crawl Session: {id}\n");
-                    for e in extractions {
-                        println!("--- {} ---", e.url);
-                        println!("{}", serde_json::to_string_pretty(&e.extracted_data)?);
-                        println!();
-                    }
-                }
+        SchemaCommands::Create {
+            name,
+            version,
+            file,
+        } => {
+            let schema = read_schema_file(&file)?;
+            if store.exists(&name, &version).await? {
+                anyhow::bail!("Schema {name}@{version} already exists");
             }
+            let latest = store.create(&name, &version, &schema).await?;
+            writeln!(out, "Created schema {name}@{version} (latest: {latest})")?;
+        }
+
+        SchemaCommands::Validate { file } => {
+            validate_schema(&read_schema_file(&file)?)?;
+            writeln!(out, "Valid JSON Schema: {}", file.display())?;
+        }
+
+        SchemaCommands::SetLatest { name, version } => {
+            store.set_latest(&name, &version).await?;
+            writeln!(out, "Latest version of {name} is now {version}")?;
         }
     }
 
     Ok(())
 }
 
-fn api_key_for(provider: Provider, api_key: Option<&str>) -> Result<String> {
-    match (provider, api_key.filter(|key| !key.trim().is_empty())) {
-        (Provider::Local, key) => Ok(key.unwrap_or_default().to_string()),
-        (_, Some(key)) => Ok(key.to_string()),
-        (_, None) => {
-            anyhow::bail!("--api-key (or ARES_API_KEY) is required for {provider:?} provider")
-        }
-    }
+fn read_schema_file(path: &Path) -> Result<serde_json::Value> {
+    let content = std::fs::read_to_string(path)
+        .with_context(|| format!("Failed to read file: {}", path.display()))?;
+    serde_json::from_str(&content)
+        .with_context(|| format!("Invalid JSON in file: {}", path.display()))
 }
 
 fn cmd_model(action: ModelCommands) -> Result<()> {
@@ -961,24 +2754,57 @@ fn build_proxy_config(
 // Generic command handlers — pure injection, no business logic.
 // ---------------------------------------------------------------------------
 
+/// What `ares scrape` extracts from.
+enum ScrapeTarget {
+    /// Fetch one URL.
+    Url(String),
+    /// Pre-fetched HTML; `url` is only the storage key.
+    Html { url: String, html: String },
+    /// Fetch many URLs concurrently (`--urls-file`).
+    Batch {
+        urls: Vec<String>,
+        concurrency: usize,
+        streaming: bool,
+        /// Checkpoint file (`--state-file`), resumed with `--resume`.
+        state_file: Option<PathBuf>,
+        resume: bool,
+    },
+}
+
 /// Options for a one-shot scrape — passed as a single struct to keep the
 /// generic `cmd_scrape` below the clippy argument-count threshold.
 struct ScrapeOpts<'a> {
-    url: &'a str,
+    target: ScrapeTarget,
     schema_value: serde_json::Value,
     schema_name: &'a str,
     model: &'a str,
     provider: Provider,
     base_url: &'a str,
     api_key: &'a str,
-    save: bool,
+    /// Where extractions are saved (`--save`), if anywhere.
+    database: Option<DatabaseConfig>,
     llm_timeout: Option<Duration>,
+    /// Credentials sent with every page fetch.
+    fetch_auth: Option<FetchAuth>,
+    /// Told about changed data when saving.
+    notifiers: Notifiers,
+    /// Publishes saved extractions.
+    events: EventPublisher,
+    /// Archives the source content of saved extractions, and which kinds.
+    snapshots: (Snapshots, Vec<SnapshotKind>),
     system_prompt: Option<&'a str>,
     skip_unchanged: bool,
+    upsert: bool,
+    /// Cross-URL duplicate window when saving, if any.
+    dedup_window: Option<Duration>,
+    reuse_duplicates: bool,
+    /// Simhash distance below which content counts as unchanged, if any.
+    content_similarity_threshold: Option<u32>,
     no_cache: bool,
     cache_ttl: u64,
     max_content: Option<usize>,
     format: OutputFormat,
+    output: Option<PathBuf>,
 }
 
 fn build_caches(no_cache: bool, ttl_secs: u64) -> (Option<ContentCache>, Option<ExtractionCache>) {
@@ -1008,71 +2834,342 @@ async fn cmd_scrape<F: Fetcher>(fetcher: F, opts: ScrapeOpts<'_>) -> Result<()>
     )?;
 
     let (content_cache, extraction_cache) = build_caches(opts.no_cache, opts.cache_ttl);
+    let fetch_options = FetchOptions {
+        auth: opts.fetch_auth.clone(),
+        ..FetchOptions::default()
+    };
 
-    let result = if opts.save {
-        let db = Database::connect(&DatabaseConfig::from_env()?).await?;
-        db.migrate().await?;
-        let repo = db.extraction_repo();
-        let service =
-            ScrapeService::with_store(fetcher, cleaner, extractor, repo, opts.model.to_string())
-                .with_skip_unchanged(opts.skip_unchanged)
-                .with_provider(opts.provider.name())
-                .with_max_content_chars(opts.max_content)
-                .with_caches(content_cache, extraction_cache);
-        service
-            .scrape(opts.url, &opts.schema_value, opts.schema_name)
-            .await?
-    } else {
-        let service = ScrapeService::with_store(
-            fetcher,
-            cleaner,
-            extractor,
-            NullStore,
-            opts.model.to_string(),
-        )
+    let builder = ScrapeServiceBuilder::new(fetcher, cleaner, extractor)
+        .with_model(opts.model)
         .with_provider(opts.provider.name())
         .with_max_content_chars(opts.max_content)
-        .with_caches(content_cache, extraction_cache);
-        service
-            .scrape(opts.url, &opts.schema_value, opts.schema_name)
-            .await?
+        .with_caches(content_cache, extraction_cache)
+        .with_fetch_options(fetch_options);
+    if let Some(database) = &opts.database {
+        let db = Database::connect(database).await?;
+        db.migrate().await?;
+        let service = builder
+            .with_store(db.extraction_repo())
+            .with_skip_unchanged(opts.skip_unchanged)
+            .with_upsert(opts.upsert)
+            .with_dedup_window(opts.dedup_window)
+            .with_reuse_duplicates(opts.reuse_duplicates)
+            .with_content_similarity_threshold(opts.content_similarity_threshold)
+            .with_notifier(opts.notifiers.clone())
+            .with_publisher(opts.events.clone())
+            .with_snapshots(opts.snapshots.0.clone(), &opts.snapshots.1)
+            .build()?;
+        run_scrape_target(service, &opts).await
+    } else {
+        let service = builder.with_store(NullStore).build()?;
+        run_scrape_target(service, &opts).await
+    }
+}
+
+/// Scrape `opts.target` with `service` and write the extracted data to
+/// stdout or `--output`.
+async fn run_scrape_target<F, C, E, S, N, P, A>(
+    service: ScrapeService<F, C, E, S, N, P, A>,
+    opts: &ScrapeOpts<'_>,
+) -> Result<()>
+where
+    F: Fetcher,
+    C: ares_core::Cleaner,
+    E: ares_core::Extractor,
+    S: ares_core::ExtractionStore,
+    N: ares_core::ChangeNotifier,
+    P: ares_core::ExtractionEventPublisher,
+    A: ares_core::SnapshotStore,
+{
+    let result = match &opts.target {
+        ScrapeTarget::Url(url) => {
+            service
+                .scrape(url, &opts.schema_value, opts.schema_name)
+                .await?
+        }
+        ScrapeTarget::Html { url, html } => {
+            service
+                .scrape_html(html, url, &opts.schema_value, opts.schema_name)
+                .await?
+        }
+        ScrapeTarget::Batch {
+            urls,
+            concurrency,
+            streaming,
+            state_file,
+            resume,
+        } => {
+            let service = service.with_batch_concurrency(*concurrency);
+            let checkpoint = batch_checkpoint(state_file.as_deref(), *resume)?;
+            return scrape_batch(&service, urls, checkpoint, *streaming, opts).await;
+        }
+    };
+
+    if let Some(original) = result.duplicate_of {
+        eprintln!("Duplicate of extraction {original}");
+    }
+    if let Some(language) = &result.detected_language {
+        eprintln!("Detected language: {language}");
+    }
+    let mut out = OutputWriter::new(opts.format, opts.output.as_deref())?;
+    out.write(&result.extracted_data)?;
+    out.finish()
+}
+
+/// Scrape every URL not already completed in `checkpoint` with `service`;
+/// see [`write_batch`].
+async fn scrape_batch<F, C, E, S, N, P, A>(
+    service: &ScrapeService<F, C, E, S, N, P, A>,
+    urls: &[String],
+    checkpoint: Option<Checkpoint>,
+    streaming: bool,
+    opts: &ScrapeOpts<'_>,
+) -> Result<()>
+where
+    F: Fetcher,
+    C: ares_core::Cleaner,
+    E: ares_core::Extractor,
+    S: ares_core::ExtractionStore,
+    N: ares_core::ChangeNotifier,
+    P: ares_core::ExtractionEventPublisher,
+    A: ares_core::SnapshotStore,
+{
+    let remaining = match &checkpoint {
+        Some(checkpoint) => checkpoint.remaining(urls),
+        None => urls.to_vec(),
+    };
+    let results = service
+        .scrape_batch_parallel(remaining, &opts.schema_value, opts.schema_name)
+        .map(|(url, result)| {
+            let result = result.map(|r| Extracted {
+                data: r.extracted_data,
+                data_hash: r.data_hash,
+            });
+            (url, result)
+        });
+    write_batch(
+        results,
+        urls,
+        BatchRun::new(urls, checkpoint),
+        streaming,
+        opts.format,
+        opts.output.as_deref(),
+    )
+    .await
+}
+
+/// The `--state-file` checkpoint: the existing one with `--resume`,
+/// otherwise a fresh one replacing it.
+fn batch_checkpoint(state_file: Option<&Path>, resume: bool) -> Result<Option<Checkpoint>> {
+    state_file
+        .map(|path| {
+            if resume {
+                Checkpoint::load(path)
+            } else {
+                Ok(Checkpoint::new(path))
+            }
+        })
+        .transpose()
+}
+
+/// Scrape `target` on the server behind `--remote`, writing the same output
+/// a local scrape would.
+async fn scrape_remote(
+    api: &AresApiClient,
+    request: ScrapeRequest,
+    target: ScrapeTarget,
+    format: OutputFormat,
+    output: Option<&Path>,
+) -> Result<()> {
+    let request = match target {
+        ScrapeTarget::Url(url) => ScrapeRequest { url, ..request },
+        ScrapeTarget::Html { url, html } => ScrapeRequest {
+            url,
+            html: Some(html),
+            ..request
+        },
+        ScrapeTarget::Batch {
+            urls,
+            concurrency,
+            streaming,
+            state_file,
+            resume,
+        } => {
+            let checkpoint = batch_checkpoint(state_file.as_deref(), resume)?;
+            let remaining = match &checkpoint {
+                Some(checkpoint) => checkpoint.remaining(&urls),
+                None => urls.clone(),
+            };
+            let results = futures::stream::iter(remaining)
+                .map(|url| {
+                    let request = ScrapeRequest {
+                        url: url.clone(),
+                        ..request.clone()
+                    };
+                    async move {
+                        let result = api.scrape(&request).await.map(|r| Extracted {
+                            data: r.extracted_data,
+                            data_hash: r.data_hash,
+                        });
+                        (url, result)
+                    }
+                })
+                .buffer_unordered(concurrency.max(1));
+            let run = BatchRun::new(&urls, checkpoint);
+            return write_batch(results, &urls, run, streaming, format, output).await;
+        }
     };
 
-    let val = serde_json::to_value(&result.extracted_data)?;
-    OutputFormatter::format(opts.format, &val)?;
+    let result = api.scrape(&request).await?;
+    if let Some(original) = result.duplicate_of {
+        eprintln!("Duplicate of extraction {original}");
+    }
+    if let Some(language) = &result.detected_language {
+        eprintln!("Detected language: {language}");
+    }
+    let mut out = OutputWriter::new(format, output)?;
+    out.write(&result.extracted_data)?;
+    out.finish()
+}
+
+/// Write one `{url, data}` or `{url, error}` row per batch result, plus one
+/// per URL `run` resumed: as JSON lines while they finish with `streaming`,
+/// otherwise all at once in input order using `format`. Fails if any URL
+/// failed, after the output has been written and the failures listed on
+/// stderr.
+async fn write_batch(
+    results: impl futures::Stream<Item = (String, Result<Extracted, AppError>)>,
+    urls: &[String],
+    mut run: BatchRun,
+    streaming: bool,
+    format: OutputFormat,
+    output: Option<&Path>,
+) -> Result<()> {
+    let mut results = std::pin::pin!(results);
+    let mut out = OutputWriter::new(format, output)?;
+    let mut rows = Vec::with_capacity(urls.len());
+    let resumed = run
+        .resumed(urls)
+        .into_iter()
+        .map(|(url, data)| serde_json::json!({"url": url, "data": data}));
+    for row in resumed {
+        if streaming {
+            out.write_line(&row)?;
+        } else {
+            rows.push(row);
+        }
+    }
+    while let Some((url, result)) = results.next().await {
+        run.record(&url, &result)?;
+        let row = match result {
+            Ok(extracted) => serde_json::json!({"url": url, "data": extracted.data}),
+            Err(e) => serde_json::json!({"url": url, "error": e.to_string()}),
+        };
+        if streaming {
+            out.write_line(&row)?;
+        } else {
+            rows.push(row);
+        }
+    }
+
+    if !streaming {
+        let position = |row: &serde_json::Value| {
+            urls.iter()
+                .position(|url| row["url"] == url.as_str())
+                .unwrap_or(usize::MAX)
+        };
+        rows.sort_by_key(position);
+        out.write(&serde_json::Value::Array(rows))?;
+    }
+    out.finish()?;
+
+    let failures = run.finish();
+    if !failures.is_empty() {
+        eprintln!("{}", batch::format_failures(&failures));
+        anyhow::bail!("{} of {} URL(s) failed", failures.len(), urls.len());
+    }
     Ok(())
 }
 
+/// Read `--urls-file`: one URL per line, skipping blank lines and `#`
+/// comments.
+fn read_urls_file(path: &str) -> Result<Vec<String>> {
+    let content = std::fs::read_to_string(path)
+        .with_context(|| format!("Failed to read URLs file: {path}"))?;
+    let urls: Vec<String> = content
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .map(String::from)
+        .collect();
+    if urls.is_empty() {
+        anyhow::bail!("No URLs found in {path}");
+    }
+    Ok(urls)
+}
+
 /// Options for the worker command.
 struct WorkerOpts<'a> {
+    database: DatabaseConfig,
     api_key: &'a str,
     provider: Provider,
     worker_id: Option<String>,
     poll_interval: u64,
+    max_jobs: Option<u64>,
+    once: bool,
+    health_addr: Option<SocketAddr>,
     fetch_timeout: Option<Duration>,
     llm_timeout: Option<Duration>,
     system_prompt: Option<&'a str>,
     skip_unchanged: bool,
+    upsert: bool,
+    store_content: bool,
+    schemas_dir: Option<String>,
+    schema_filter: Option<String>,
+    notifiers: Notifiers,
+    events: EventPublisher,
+    snapshots: (Snapshots, Vec<SnapshotKind>),
+    budget: BudgetLimits,
     no_cache: bool,
     cache_ttl: u64,
+    auto_migrate: bool,
 }
 
 /// Long-running worker: poll job queue → circuit breaker → scrape → persist.
+///
+/// With `--once` or `--max-jobs` the worker exits on its own, and fails if
+/// any job it processed failed permanently.
 async fn cmd_worker<F: Fetcher>(fetcher: F, opts: WorkerOpts<'_>) -> Result<()> {
-    let db = Database::connect(&DatabaseConfig::from_env()?).await?;
-    db.migrate().await?;
-    let job_repo = db.job_repo();
+    let db = Database::connect(&opts.database).await?;
+    db.ensure_migrated(opts.auto_migrate).await?;
+    let job_repo = match &opts.schema_filter {
+        Some(filter) => db.job_repo().with_schema_filter(filter),
+        None => db.job_repo(),
+    };
     let extraction_repo = db.extraction_repo();
 
+    let poll_interval = Duration::from_secs(opts.poll_interval);
     let config = WorkerConfig::default()
-        .with_poll_interval(Duration::from_secs(opts.poll_interval))
+        .with_poll_interval(poll_interval)
         .with_skip_unchanged(opts.skip_unchanged)
-        .with_provider(opts.provider.name());
+        .with_upsert(opts.upsert)
+        .with_store_content(opts.store_content)
+        .with_provider(opts.provider.name())
+        .with_run_once(opts.once);
+    let config = match opts.max_jobs {
+        Some(max_jobs) => config.with_max_jobs(max_jobs),
+        None => config,
+    };
     let config = if let Some(id) = opts.worker_id {
         config.with_worker_id(id)
     } else {
         config
     };
+    let config = match opts.schemas_dir {
+        Some(dir) => config.with_schemas_dir(dir),
+        None => config,
+    };
+    let worker_id = config.worker_id.clone();
 
     let cleaner = HtmdCleaner::new();
     let extractor_factory = ProviderExtractorFactory::build(
@@ -1081,6 +3178,8 @@ async fn cmd_worker<F: Fetcher>(fetcher: F, opts: WorkerOpts<'_>) -> Result<()>
         opts.llm_timeout,
         opts.system_prompt,
     )?;
+    let budget = BudgetGuard::new(db.usage_repo(), opts.budget).with_pricing(model_pricing::cost);
+    let extractor_factory = BudgetedExtractorFactory::new(extractor_factory, budget);
     let discoverer = HtmlLinkDiscoverer::new();
     let robots_checker = CachedRobotsChecker::with_user_agent("Ares/0.2");
     let cb = CircuitBreaker::new("llm", CircuitBreakerConfig::default());
@@ -1098,7 +3197,22 @@ async fn cmd_worker<F: Fetcher>(fetcher: F, opts: WorkerOpts<'_>) -> Result<()>
         cb,
         config,
     )
-    .with_caches(content_cache, extraction_cache);
+    .with_caches(content_cache, extraction_cache)
+    .with_notifier(opts.notifiers)
+    .with_publisher(opts.events)
+    .with_snapshots(opts.snapshots.0, &opts.snapshots.1);
+
+    if let Some(addr) = opts.health_addr {
+        health::serve(
+            addr,
+            worker.status(),
+            db.clone(),
+            poll_interval,
+            opts.fetch_timeout,
+            opts.llm_timeout,
+        )
+        .await?;
+    }
 
     let cancel = CancellationToken::new();
     let token = cancel.clone();
@@ -1109,11 +3223,68 @@ async fn cmd_worker<F: Fetcher>(fetcher: F, opts: WorkerOpts<'_>) -> Result<()>
         token.cancel();
     });
 
-    worker.run(cancel, &TracingWorkerReporter).await?;
+    let reporter = (
+        TracingWorkerReporter,
+        PersistingWorkerReporter::new(db.worker_event_repo(), worker_id, DEFAULT_EVENT_BUFFER),
+    );
+    let result = worker.run(cancel, &reporter).await;
+    let (_, event_log) = reporter;
+    event_log.shutdown().await;
+    let summary = result?;
+    tracing::info!(
+        processed = summary.processed,
+        completed = summary.completed,
+        retrying = summary.retrying,
+        failed = summary.failed,
+        "Worker finished"
+    );
 
+    if (opts.once || opts.max_jobs.is_some()) && summary.failed > 0 {
+        anyhow::bail!(
+            "{} of {} job(s) failed permanently",
+            summary.failed,
+            summary.processed
+        );
+    }
     Ok(())
 }
 
+/// Build a `ReqwestFetcher` from the CLI's fetch flags. Private URLs are
+/// allowed, since the user controls the machine.
+fn create_http_fetcher(
+    timeout: Option<Duration>,
+    proxy: Option<ProxyConfig>,
+    random_ua: bool,
+    user_agent: Option<&str>,
+    tls: TlsBackend,
+    max_response_size: Option<usize>,
+) -> Result<ReqwestFetcher> {
+    let mut fetcher = match timeout {
+        Some(t) => ReqwestFetcher::with_timeout(t),
+        None => ReqwestFetcher::new(),
+    }
+    .context("Failed to create HTTP client")?
+    .with_tls_backend(tls)
+    .context("Failed to set TLS backend")?
+    .allow_private_urls();
+
+    if let Some(proxy_config) = proxy {
+        fetcher = fetcher
+            .with_proxies(proxy_config)
+            .context("Failed to configure proxies")?;
+    }
+    if random_ua {
+        fetcher = fetcher.with_random_ua();
+    }
+    if let Some(user_agent) = user_agent {
+        fetcher = fetcher.with_user_agent(user_agent);
+    }
+    if let Some(bytes) = max_response_size {
+        fetcher = fetcher.with_max_response_size(bytes);
+    }
+    Ok(fetcher)
+}
+
 // ---------------------------------------------------------------------------
 // Browser fetcher factory — feature-gated.
 // ---------------------------------------------------------------------------
@@ -1123,15 +3294,23 @@ async fn create_browser_fetcher(
     timeout: Option<Duration>,
     proxy_url: Option<&str>,
     stealth: bool,
+    script: Option<&str>,
+    user_agent: Option<&str>,
 ) -> Result<ares_client::BrowserFetcher> {
     use ares_core::stealth::StealthConfig;
     let timeout = timeout.unwrap_or(Duration::from_secs(30));
-    let fetcher = ares_client::BrowserFetcher::with_timeout_and_proxy(timeout, proxy_url).await?;
-    Ok(if stealth {
-        fetcher.with_stealth(StealthConfig::full())
-    } else {
-        fetcher
-    })
+    let mut fetcher =
+        ares_client::BrowserFetcher::with_timeout_and_proxy(timeout, proxy_url).await?;
+    if stealth {
+        fetcher = fetcher.with_stealth(StealthConfig::full());
+    }
+    if let Some(script) = script {
+        fetcher = fetcher.with_pre_capture_script(script);
+    }
+    if let Some(user_agent) = user_agent {
+        fetcher = fetcher.with_user_agent(ares_client::UserAgent::from(user_agent).as_str());
+    }
+    Ok(fetcher)
 }
 
 #[cfg(not(feature = "browser"))]
@@ -1139,6 +3318,8 @@ async fn create_browser_fetcher(
     _timeout: Option<Duration>,
     _proxy_url: Option<&str>,
     _stealth: bool,
+    _script: Option<&str>,
+    _user_agent: Option<&str>,
 ) -> Result<ReqwestFetcher> {
     anyhow::bail!(
         "--browser requires the `browser` feature.\n\
@@ -1146,56 +3327,195 @@ async fn create_browser_fetcher(
     );
 }
 
+/// Read the `--browser-script` file, if one was given.
+fn read_browser_script(path: Option<&str>) -> Result<Option<String>> {
+    path.map(|path| {
+        std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read browser script: {path}"))
+    })
+    .transpose()
+}
+
+/// Print the `extracted_data` of the latest extraction, so it can be piped
+/// into `jq`.
+async fn cmd_latest(
+    url: &str,
+    schema_name: &str,
+    backend: &Backend,
+    out: &mut impl Write,
+) -> Result<()> {
+    let Some(extraction) = backend.latest(url, schema_name).await? else {
+        anyhow::bail!("No extractions found for url={url} schema={schema_name}");
+    };
+    writeln!(
+        out,
+        "{}",
+        serde_json::to_string_pretty(&extraction.extracted_data)?
+    )?;
+    Ok(())
+}
+
+/// Print each golden page's outcome, then the diffs of the ones that
+/// changed.
+fn print_verify_report(report: &VerifyReport, format: OutputFormat, color: bool) -> Result<()> {
+    if format != OutputFormat::Table {
+        let mut val = serde_json::to_value(report)?;
+        val["passed"] = report.passed().into();
+        return OutputFormatter::format(format, &val);
+    }
+
+    let rows: Vec<_> = report
+        .checks
+        .iter()
+        .map(|check| {
+            let detail = match &check.error {
+                Some(error) => error.clone(),
+                None if check.changes.is_empty() => String::new(),
+                None => format!("{} changed fields", check.changes.len()),
+            };
+            serde_json::json!({
+                "URL": check.url.clone(),
+                "RESULT": if check.passed() { "pass" } else { "fail" },
+                "REPLAYED": if check.replayed { "yes" } else { "no" },
+                "DETAIL": detail,
+            })
+        })
+        .collect();
+    OutputFormatter::format(format, &serde_json::to_value(rows)?)?;
+
+    for check in report.checks.iter().filter(|c| !c.changes.is_empty()) {
+        print!(
+            "\n{}\n{}",
+            check.url,
+            format::format_json_diff(&check.changes, color)
+        );
+    }
+    println!(
+        "\n{} of {} golden pages passed for {}",
+        report.checks.len() - report.failed(),
+        report.checks.len(),
+        report.schema_name
+    );
+    Ok(())
+}
+
 async fn cmd_history(
     url: &str,
     schema_name: &str,
+    language: Option<&str>,
     limit: usize,
-    repo: &ExtractionRepository,
-    format: OutputFormat,
+    backend: &Backend,
+    format: HistoryFormat,
+    color: bool,
 ) -> Result<()> {
-    let history = repo.get_history(url, schema_name, limit, 0).await?;
+    let (history, predecessor) = backend.history(url, schema_name, language, limit).await?;
 
     if history.is_empty() {
         println!("No extractions found for url={url} schema={schema_name}");
         return Ok(());
     }
 
-    let val = match format {
-        OutputFormat::Table => {
+    match format {
+        HistoryFormat::Json => {
+            OutputFormatter::format(OutputFormat::Json, &serde_json::to_value(&history)?)?;
+        }
+
+        HistoryFormat::Summary => {
             let mut rows = vec![];
-            for (i, extraction) in history.iter().enumerate() {
-                let changed = if i + 1 < history.len() {
-                    extraction.data_hash != history[i + 1].data_hash
-                } else {
-                    true
-                };
-                let status = if changed { "CHANGED" } else { "unchanged" };
+            for HistoryEntry {
+                extraction,
+                changed,
+                ..
+            } in &history
+            {
+                let status = if *changed { "CHANGED" } else { "unchanged" };
                 rows.push(serde_json::json!({
                     "STATUS": status,
                     "CREATED_AT": extraction.created_at.format("%Y-%m-%d %H:%M:%S UTC").to_string(),
+                    "AGE": format_age(extraction.age()),
                     "ID": extraction.id.to_string(),
                     "MODEL": extraction.model.clone(),
-                    "HASH": format!("{}...", &extraction.data_hash[..8])
+                    "PROMPT": extraction
+                        .prompt_hash
+                        .as_deref()
+                        .map_or_else(|| "-".to_string(), |hash| format!("{}...", &hash[..8.min(hash.len())])),
+                    "HASH": format!("{}...", &extraction.data_hash[..8]),
+                    "LANGUAGE": extraction.detected_language.as_deref().unwrap_or("-"),
+                    "DUPLICATE_OF": extraction
+                        .duplicate_of
+                        .map_or_else(|| "-".to_string(), |id| id.to_string())
                 }));
             }
-            serde_json::to_value(rows)?
-        }
-        _ => serde_json::to_value(&history)?,
-    };
 
-    if format == OutputFormat::Table {
-        println!("Extraction history for {url} (schema: {schema_name}):\n");
-    }
+            println!("Extraction history for {url} (schema: {schema_name}):\n");
+            OutputFormatter::format(OutputFormat::Table, &serde_json::to_value(rows)?)?;
+            println!("\nTotal: {} extractions", history.len());
+        }
 
-    OutputFormatter::format(format, &val)?;
+        HistoryFormat::Diff => {
+            let color = color && std::io::stdout().is_terminal();
+            println!("Extraction history for {url} (schema: {schema_name}):\n");
+
+            // History is newest first; diff each extraction against the one
+            // before it, including the oldest shown against its predecessor.
+            let chain: Vec<&Extraction> = history
+                .iter()
+                .map(|entry| &entry.extraction)
+                .chain(&predecessor)
+                .collect();
+            let mut changes = 0;
+            for pair in chain.windows(2) {
+                let (newer, older) = (pair[0], pair[1]);
+                if newer.data_hash == older.data_hash {
+                    continue;
+                }
+                changes += 1;
+                println!(
+                    "{} → {} ({})",
+                    older.created_at.format("%Y-%m-%d %H:%M:%S UTC"),
+                    newer.created_at.format("%Y-%m-%d %H:%M:%S UTC"),
+                    newer.id
+                );
+                if let Some(change) = config_change(older, newer) {
+                    println!("  Different configuration: {change}");
+                }
+                print!(
+                    "{}",
+                    format::format_json_diff(
+                        &diff_json(&older.extracted_data, &newer.extracted_data),
+                        color
+                    )
+                );
+                println!();
+            }
 
-    if format == OutputFormat::Table {
-        println!("\nTotal: {} extractions", history.len());
+            if changes == 0 {
+                println!("No changes across {} extractions.", history.len());
+            } else {
+                println!("{changes} change(s) across {} extractions", history.len());
+            }
+        }
     }
 
     Ok(())
 }
 
+/// How the extraction configuration differs between two runs, if it does,
+/// so a diff isn't mistaken for a change in the page. Rows without a
+/// recorded prompt hash aren't compared on it.
+fn config_change(older: &Extraction, newer: &Extraction) -> Option<String> {
+    let mut changes = Vec::new();
+    if older.model != newer.model {
+        changes.push(format!("model {} → {}", older.model, newer.model));
+    }
+    if let (Some(old_hash), Some(new_hash)) = (&older.prompt_hash, &newer.prompt_hash)
+        && old_hash != new_hash
+    {
+        changes.push("prompt changed".to_string());
+    }
+    (!changes.is_empty()).then(|| changes.join(", "))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1208,9 +3528,534 @@ mod tests {
         Cli::command().debug_assert();
     }
 
+    fn run_with(model: &str, prompt_hash: Option<&str>) -> Extraction {
+        serde_json::from_value(serde_json::json!({
+            "id": "00000000-0000-0000-0000-000000000000",
+            "url": "https://example.com",
+            "schema_name": "blog",
+            "extracted_data": {},
+            "content_hash": "c",
+            "data_hash": "d",
+            "model": model,
+            "provider": "openai",
+            "prompt_hash": prompt_hash,
+            "created_at": "2026-01-01T00:00:00Z",
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn config_change_reports_model_and_prompt_differences() {
+        let base = run_with("gpt-4o-mini", Some("aaa"));
+        assert_eq!(
+            config_change(&base, &run_with("gpt-4o-mini", Some("aaa"))),
+            None
+        );
+        assert_eq!(config_change(&base, &run_with("gpt-4o-mini", None)), None);
+        assert_eq!(
+            config_change(&base, &run_with("gpt-4o", Some("bbb"))).as_deref(),
+            Some("model gpt-4o-mini → gpt-4o, prompt changed")
+        );
+    }
+
     #[test]
     fn local_provider_does_not_require_an_upstream_api_key() {
         assert_eq!(api_key_for(Provider::Local, None).unwrap(), "");
         assert!(api_key_for(Provider::OpenAi, None).is_err());
     }
+
+    #[test]
+    fn batch_scrape_alias_takes_a_urls_file() {
+        let cli = Cli::try_parse_from([
+            "ares",
+            "batch-scrape",
+            "--urls-file",
+            "urls.txt",
+            "-s",
+            "blog",
+            "-m",
+            "gpt-4o-mini",
+            "--streaming",
+        ])
+        .unwrap();
+        assert!(matches!(
+            cli.command,
+            Commands::Scrape {
+                url: None,
+                urls_file: Some(_),
+                streaming: true,
+                ..
+            }
+        ));
+
+        let single_with_streaming = Cli::try_parse_from([
+            "ares",
+            "scrape",
+            "-u",
+            "https://example.com",
+            "-s",
+            "blog",
+            "-m",
+            "gpt-4o-mini",
+            "--streaming",
+        ]);
+        assert!(single_with_streaming.is_err());
+    }
+
+    #[test]
+    fn parse_age_accepts_unit_suffixes() {
+        assert_eq!(parse_age("90s"), Ok(Duration::from_secs(90)));
+        assert_eq!(parse_age("15m"), Ok(Duration::from_secs(15 * 60)));
+        assert_eq!(parse_age("30d"), Ok(Duration::from_secs(30 * 24 * 60 * 60)));
+        assert_eq!(parse_age("2w"), Ok(Duration::from_secs(14 * 24 * 60 * 60)));
+        for invalid in ["", "30", "d", "30y", "-1d", "0d", "1.5h"] {
+            assert!(parse_age(invalid).is_err(), "{invalid}");
+        }
+    }
+
+    #[test]
+    fn job_status_flags_parse_into_statuses() {
+        let list_status =
+            |status: &str| match Cli::try_parse_from(["ares", "job", "list", "--status", status])?
+                .command
+            {
+                Commands::Job {
+                    action: JobCommands::List { status, .. },
+                } => Ok::<_, clap::Error>(status),
+                _ => unreachable!("parsed a job list command"),
+            };
+        assert_eq!(list_status("Running").unwrap(), Some(JobStatus::Running));
+        assert!(list_status("done").is_err());
+
+        let cleanup_status = |flags: &[&str]| {
+            let mut argv = vec!["ares", "job", "cleanup", "--older-than", "30d"];
+            argv.extend_from_slice(flags);
+            match Cli::try_parse_from(argv)?.command {
+                Commands::Job {
+                    action: JobCommands::Cleanup { status, .. },
+                } => Ok::<_, clap::Error>(status),
+                _ => unreachable!("parsed a job cleanup command"),
+            }
+        };
+        assert_eq!(cleanup_status(&[]).unwrap(), JobStatus::Completed);
+        assert_eq!(
+            cleanup_status(&["--status", "failed"]).unwrap(),
+            JobStatus::Failed
+        );
+        assert!(cleanup_status(&["--status", "pending"]).is_err());
+    }
+
+    #[test]
+    fn job_create_takes_a_url_or_a_sitemap() {
+        let parse = |flags: &[&str]| {
+            let mut argv = vec!["ares", "job", "create", "-s", "blog"];
+            argv.extend_from_slice(flags);
+            Cli::try_parse_from(argv).map(|cli| cli.command)
+        };
+
+        let Ok(Commands::Job {
+            action: JobCommands::Create { url, sitemap, .. },
+        }) = parse(&[
+            "--sitemap",
+            "https://a.example/sitemap.xml",
+            "--filter",
+            "products/",
+            "--filter",
+            "offers/",
+            "--max-urls",
+            "50",
+            "--skip-unmodified",
+        ])
+        else {
+            panic!("expected a job create command");
+        };
+        assert_eq!(url, None);
+        assert_eq!(
+            sitemap.sitemap.as_deref(),
+            Some("https://a.example/sitemap.xml")
+        );
+        assert_eq!(
+            sitemap.into_options(),
+            SitemapOptions {
+                include: vec!["products/".into(), "offers/".into()],
+                exclude: Vec::new(),
+                max_urls: Some(50),
+                skip_unmodified: true,
+            }
+        );
+
+        let Ok(Commands::Job {
+            action: JobCommands::Create { tags, .. },
+        }) = parse(&[
+            "-u",
+            "https://a.example",
+            "--tag",
+            "run-42",
+            "--tag",
+            "nightly",
+        ])
+        else {
+            panic!("expected a job create command");
+        };
+        assert_eq!(tags, ["run-42", "nightly"]);
+
+        assert!(parse(&[]).is_err());
+        assert!(parse(&["-u", "https://a.example", "--filter", "x"]).is_err());
+        assert!(
+            parse(&[
+                "-u",
+                "https://a.example",
+                "--sitemap",
+                "https://a.example/sitemap.xml"
+            ])
+            .is_err()
+        );
+    }
+
+    #[test]
+    fn fetch_auth_flags_parse_into_credentials() {
+        let parse = |flags: &[&str]| -> Result<FetchOptions> {
+            let mut argv = vec![
+                "ares",
+                "job",
+                "create",
+                "-u",
+                "https://a.example",
+                "-s",
+                "blog",
+            ];
+            argv.extend_from_slice(flags);
+            let Commands::Job {
+                action: JobCommands::Create { fetch, .. },
+            } = Cli::try_parse_from(argv)?.command
+            else {
+                unreachable!("parsed a job create command");
+            };
+            fetch.into_options()
+        };
+
+        let options = parse(&["--fetch-basic-auth", "ada:pa:ss"]).unwrap();
+        assert_eq!(
+            options.auth,
+            Some(FetchAuth::Basic {
+                user: "ada".into(),
+                pass: "pa:ss".into()
+            })
+        );
+        let options = parse(&["--fetch-bearer", "t0ken"]).unwrap();
+        assert_eq!(
+            options.auth,
+            Some(FetchAuth::Bearer {
+                token: "t0ken".into()
+            })
+        );
+        assert_eq!(parse(&[]).unwrap().auth, None);
+        assert!(parse(&["--fetch-basic-auth", "ada"]).is_err());
+        assert!(parse(&["--fetch-basic-auth", "a:b", "--fetch-bearer", "t"]).is_err());
+    }
+
+    /// Run `ares schema --schemas-dir <dir> <args>` and capture its output.
+    async fn run_schema(dir: &Path, args: &[&str]) -> Result<String> {
+        let mut argv = vec!["ares", "schema", "--schemas-dir", dir.to_str().unwrap()];
+        argv.extend_from_slice(args);
+        let Commands::Schema {
+            schemas_dir,
+            action,
+        } = Cli::try_parse_from(argv)?.command
+        else {
+            unreachable!("parsed a schema command");
+        };
+        let mut out = Vec::new();
+        let store = SchemaStore::Local(SchemaResolver::new(schemas_dir));
+        cmd_schema(action, &store, &mut out).await?;
+        Ok(String::from_utf8(out)?)
+    }
+
+    /// A temporary schema directory and a valid schema file outside it.
+    fn schema_fixture() -> (tempfile::TempDir, PathBuf, String) {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let dir = tmp.path().join("schemas");
+        let file = tmp.path().join("blog.json");
+        std::fs::write(
+            &file,
+            r#"{"type": "object", "properties": {"title": {"type": "string"}}}"#,
+        )
+        .unwrap();
+        let file = file.to_str().unwrap().to_string();
+        (tmp, dir, file)
+    }
+
+    #[tokio::test]
+    async fn schema_list_renders_registered_schemas() {
+        let (_tmp, dir, file) = schema_fixture();
+        assert_eq!(
+            run_schema(&dir, &["list"]).await.unwrap(),
+            "No schemas registered.\n"
+        );
+
+        for version in ["1.0.0", "1.1.0"] {
+            run_schema(&dir, &["create", "-n", "blog", "-v", version, "-f", &file])
+                .await
+                .unwrap();
+        }
+        let out = run_schema(&dir, &["list", "--format", "json"])
+            .await
+            .unwrap();
+        let rows: serde_json::Value = serde_json::from_str(&out).unwrap();
+        assert_eq!(rows.as_array().unwrap().len(), 1);
+        assert_eq!(rows[0]["name"], "blog");
+        assert_eq!(rows[0]["latest_version"], "1.1.0");
+        assert_eq!(rows[0]["versions"], serde_json::json!(["1.0.0", "1.1.0"]));
+        assert_eq!(rows[0]["version_count"], 2);
+        assert!(rows[0]["updated_at"].is_string());
+
+        let table = run_schema(&dir, &["list"]).await.unwrap();
+        assert!(table.contains("2 (1.0.0, 1.1.0)"), "{table}");
+
+        std::fs::write(dir.join("registry.json"), "not json").unwrap();
+        assert!(run_schema(&dir, &["list"]).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn schema_show_prints_the_resolved_schema() {
+        let (_tmp, dir, file) = schema_fixture();
+        run_schema(&dir, &["create", "-n", "blog", "-v", "1.0.0", "-f", &file])
+            .await
+            .unwrap();
+
+        let out = run_schema(&dir, &["show", "blog@latest"]).await.unwrap();
+        let schema: serde_json::Value = serde_json::from_str(&out).unwrap();
+        assert_eq!(schema["properties"]["title"]["type"], "string");
+
+        let err = run_schema(&dir, &["show", "blog@9.9.9"]).await.unwrap_err();
+        assert!(err.to_string().contains("not found"), "{err}");
+    }
+
+    #[tokio::test]
+    async fn schema_create_writes_the_version_once() {
+        let (tmp, dir, file) = schema_fixture();
+        let out = run_schema(
+            &dir,
+            &[
+                "create",
+                "--name",
+                "blog",
+                "--version",
+                "1.1.0",
+                "--file",
+                &file,
+            ],
+        )
+        .await
+        .unwrap();
+        assert_eq!(out, "Created schema blog@1.1.0 (latest: 1.1.0)\n");
+        assert!(dir.join("blog/1.1.0.json").exists());
+
+        let err = run_schema(&dir, &["create", "-n", "blog", "-v", "1.1.0", "-f", &file])
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("already exists"), "{err}");
+
+        let invalid = tmp.path().join("invalid.json");
+        std::fs::write(&invalid, r#"{"type": "not-a-type"}"#).unwrap();
+        let invalid = invalid.to_str().unwrap();
+        let err = run_schema(
+            &dir,
+            &["create", "-n", "blog", "-v", "2.0.0", "-f", invalid],
+        )
+        .await
+        .unwrap_err();
+        assert!(err.to_string().starts_with("Validation failed"), "{err}");
+        assert!(!dir.join("blog/2.0.0.json").exists());
+    }
+
+    #[tokio::test]
+    async fn schema_validate_does_not_write() {
+        let (tmp, dir, file) = schema_fixture();
+        let out = run_schema(&dir, &["validate", "--file", &file])
+            .await
+            .unwrap();
+        assert!(out.starts_with("Valid JSON Schema"), "{out}");
+        assert!(!dir.exists());
+
+        let broken = tmp.path().join("broken.json");
+        std::fs::write(&broken, "{").unwrap();
+        let err = run_schema(&dir, &["validate", "-f", broken.to_str().unwrap()])
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("Invalid JSON"), "{err}");
+    }
+
+    #[tokio::test]
+    async fn schema_set_latest_moves_the_registry_pointer() {
+        let (_tmp, dir, file) = schema_fixture();
+        for version in ["1.0.0", "2.0.0"] {
+            run_schema(&dir, &["create", "-n", "blog", "-v", version, "-f", &file])
+                .await
+                .unwrap();
+        }
+
+        run_schema(&dir, &["set-latest", "blog", "1.0.0"])
+            .await
+            .unwrap();
+        let resolved = SchemaResolver::new(&dir).resolve("blog@latest").unwrap();
+        assert_eq!(resolved.name, "blog@1.0.0");
+
+        let err = run_schema(&dir, &["set-latest", "blog", "3.0.0"])
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("not found"), "{err}");
+    }
+
+    /// Serve the schema endpoints of the REST API from `dir`, the way
+    /// `ares-api` does, and return a client for them.
+    async fn schema_server(dir: PathBuf) -> AresApiClient {
+        use axum::extract::{Path, State};
+        use axum::http::StatusCode;
+        use axum::response::{IntoResponse, Response};
+        use axum::routing::{get, post};
+
+        fn reply(result: Result<serde_json::Value, AppError>, ok: StatusCode) -> Response {
+            match result {
+                Ok(body) => (ok, axum::Json(body)).into_response(),
+                Err(e @ AppError::ValidationError(_)) => {
+                    let AppError::ValidationError(messages) = &e else {
+                        unreachable!()
+                    };
+                    let body = serde_json::json!({
+                        "error": "validation_error", "message": e.to_string(), "messages": messages
+                    });
+                    (StatusCode::UNPROCESSABLE_ENTITY, axum::Json(body)).into_response()
+                }
+                Err(e) => {
+                    let body = serde_json::json!({"error": "not_found", "message": e.to_string()});
+                    (StatusCode::NOT_FOUND, axum::Json(body)).into_response()
+                }
+            }
+        }
+
+        let router = axum::Router::new()
+            .route(
+                "/v1/schemas",
+                get(|State(dir): State<PathBuf>| async move {
+                    let schemas = SchemaResolver::new(dir).list_schemas().map(|entries| {
+                        let entries: Vec<_> = entries
+                            .into_iter()
+                            .map(|e| {
+                                serde_json::json!({
+                                    "name": e.name,
+                                    "latest_version": e.latest_version,
+                                    "versions": e.versions,
+                                    "created_at": e.created_at.map(DateTime::<Utc>::from),
+                                    "updated_at": e.updated_at.map(DateTime::<Utc>::from),
+                                })
+                            })
+                            .collect();
+                        serde_json::json!({ "schemas": entries })
+                    });
+                    reply(schemas, StatusCode::OK)
+                })
+                .post(
+                    |State(dir): State<PathBuf>, axum::Json(body): axum::Json<serde_json::Value>| async move {
+                        let name = body["name"].as_str().unwrap();
+                        let version = body["version"].as_str().unwrap();
+                        let created = SchemaResolver::new(dir)
+                            .create_schema(name, version, &body["schema"])
+                            .map(|()| serde_json::json!({"name": name, "version": version}));
+                        reply(created, StatusCode::CREATED)
+                    },
+                ),
+            )
+            .route(
+                "/v1/schemas/{name}/{version}",
+                get(
+                    |State(dir): State<PathBuf>, Path((name, version)): Path<(String, String)>| async move {
+                        let schema = SchemaResolver::new(dir)
+                            .resolve(&format!("{name}@{version}"))
+                            .map(|r| {
+                                let version = r.name.rsplit_once('@').unwrap().1;
+                                serde_json::json!({"name": name, "version": version, "schema": r.schema})
+                            });
+                        reply(schema, StatusCode::OK)
+                    },
+                ),
+            )
+            .route(
+                "/v1/schemas/{name}/{version}/latest",
+                post(
+                    |State(dir): State<PathBuf>, Path((name, version)): Path<(String, String)>| async move {
+                        let set = SchemaResolver::new(dir)
+                            .set_latest(&name, &version)
+                            .map(|()| serde_json::Value::Null);
+                        reply(set, StatusCode::NO_CONTENT)
+                    },
+                ),
+            )
+            .with_state(dir);
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move { axum::serve(listener, router).await.unwrap() });
+        AresApiClient::new(&format!("http://{addr}"), Some("token")).unwrap()
+    }
+
+    /// Run `ares schema <args>` against `store` and capture its output.
+    async fn run_schema_with(store: &SchemaStore, args: &[&str]) -> Result<String> {
+        let argv = ["ares", "schema"].iter().chain(args);
+        let Commands::Schema { action, .. } = Cli::try_parse_from(argv)?.command else {
+            unreachable!("parsed a schema command");
+        };
+        let mut out = Vec::new();
+        cmd_schema(action, store, &mut out).await?;
+        Ok(String::from_utf8(out)?)
+    }
+
+    #[tokio::test]
+    async fn remote_schema_commands_match_local_output() {
+        let (_tmp, dir, file) = schema_fixture();
+        let remote = SchemaStore::Remote(schema_server(dir.clone()).await);
+        let local = SchemaStore::Local(SchemaResolver::new(&dir));
+
+        let create = ["create", "-n", "blog", "-v", "1.0.0", "-f", file.as_str()];
+        assert_eq!(
+            run_schema_with(&remote, &create).await.unwrap(),
+            "Created schema blog@1.0.0 (latest: 1.0.0)\n"
+        );
+        assert!(dir.join("blog/1.0.0.json").exists());
+        let create = ["create", "-n", "blog", "-v", "2.0.0", "-f", file.as_str()];
+        run_schema_with(&remote, &create).await.unwrap();
+
+        for args in [
+            &["list"][..],
+            &["list", "--format", "json"],
+            &["show", "blog@latest"],
+            &["show", "blog@1.0.0"],
+        ] {
+            assert_eq!(
+                run_schema_with(&remote, args).await.unwrap(),
+                run_schema_with(&local, args).await.unwrap(),
+                "ares schema {args:?}"
+            );
+        }
+
+        run_schema_with(&remote, &["set-latest", "blog", "1.0.0"])
+            .await
+            .unwrap();
+        assert_eq!(
+            SchemaResolver::new(&dir).load_registry().unwrap()["blog"],
+            "1.0.0"
+        );
+
+        // Server errors surface with the server's message.
+        let err = run_schema_with(&remote, &create).await.unwrap_err();
+        assert_eq!(err.to_string(), "Schema blog@2.0.0 already exists");
+        let err = run_schema_with(&remote, &["set-latest", "blog", "9.0.0"])
+            .await
+            .unwrap_err();
+        assert_eq!(err.to_string(), "Schema not found: blog@9.0.0");
+        let err = run_schema_with(&remote, &["show", "blog@9.0.0"])
+            .await
+            .unwrap_err();
+        assert_eq!(err.to_string(), "Schema not found: blog@9.0.0");
+    }
 }
@@ -1,38 +1,82 @@
-use anyhow::Result;
+//! Rendering command results: [`OutputFormatter`] turns a JSON value into one
+//! of the [`OutputFormat`]s, and [`OutputWriter`] sends it to stdout or to a
+//! file that only appears once everything has been written.
+
+use std::io::{self, BufWriter, Write};
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
 use clap::ValueEnum;
+use serde::{Deserialize, Serialize};
 use serde_json::Value;
+use tempfile::NamedTempFile;
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+/// Table cells longer than this many characters are truncated.
+const MAX_CELL_CHARS: usize = 60;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
 pub enum OutputFormat {
     Json,
     Jq,
     Jsonl,
+    Yaml,
     Csv,
     Table,
 }
 
+/// Output formats for `ares history`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum HistoryFormat {
+    /// One row per extraction, flagging whether its data changed
+    #[value(alias = "table")]
+    Summary,
+    /// Field-level changes between consecutive extractions
+    Diff,
+    /// All extractions as a JSON array
+    Json,
+}
+
 pub struct OutputFormatter;
 
 impl OutputFormatter {
+    /// Print `data` to stdout.
     pub fn format(format: OutputFormat, data: &Value) -> Result<()> {
+        let mut out = io::stdout().lock();
+        Self::write(format, data, &mut out)?;
+        out.flush()?;
+        Ok(())
+    }
+
+    /// Write `data` to `out`.
+    pub fn write(
+        format: OutputFormat,
+        data: &Value,
+        out: &mut (impl Write + ?Sized),
+    ) -> Result<()> {
         match format {
             OutputFormat::Json => {
-                println!("{}", serde_json::to_string_pretty(data)?);
+                writeln!(out, "{}", serde_json::to_string_pretty(data)?)?;
             }
             OutputFormat::Jq => {
-                println!("{}", serde_json::to_string(data)?);
+                writeln!(out, "{}", serde_json::to_string(data)?)?;
             }
             OutputFormat::Jsonl => {
                 if let Some(arr) = data.as_array() {
                     for item in arr {
-                        println!("{}", serde_json::to_string(item)?);
+                        writeln!(out, "{}", serde_json::to_string(item)?)?;
                     }
                 } else {
-                    println!("{}", serde_json::to_string(data)?);
+                    writeln!(out, "{}", serde_json::to_string(data)?)?;
                 }
             }
+            OutputFormat::Yaml => {
+                let mut yaml = String::new();
+                write_yaml(&mut yaml, data, 0);
+                out.write_all(yaml.as_bytes())?;
+            }
             OutputFormat::Csv => {
-                let mut wtr = csv::Writer::from_writer(std::io::stdout());
+                let mut wtr = csv::Writer::from_writer(&mut *out);
 
                 if let Some(arr) = data.as_array() {
                     let header_index =
@@ -84,78 +128,399 @@ impl OutputFormatter {
                 }
                 wtr.flush()?;
             }
-            OutputFormat::Table => {
-                let items = match data {
-                    Value::Array(arr) => arr.clone(),
-                    Value::Object(_) => vec![data.clone()],
-                    _ => anyhow::bail!("Cannot format scalar value as Table"),
-                };
+            OutputFormat::Table => write_table(data, out)?,
+        }
+        Ok(())
+    }
+}
 
-                if items.is_empty() {
-                    return Ok(());
-                }
+/// Render objects as aligned columns, one row per object, keyed by the first
+/// object's fields. Nested arrays and objects are summarized, control
+/// characters escaped, and long cells truncated.
+fn write_table(data: &Value, out: &mut (impl Write + ?Sized)) -> Result<()> {
+    let items = match data {
+        Value::Array(arr) => arr.as_slice(),
+        Value::Object(_) => std::slice::from_ref(data),
+        _ => anyhow::bail!("Cannot format scalar value as Table"),
+    };
 
-                if let Some(first_obj) = items[0].as_object() {
-                    let keys: Vec<&str> = first_obj.keys().map(|s| s.as_str()).collect();
-                    let mut widths = vec![0; keys.len()];
+    let Some(first) = items.first() else {
+        return Ok(());
+    };
+    let Some(first_obj) = first.as_object() else {
+        anyhow::bail!("Array elements must be objects to render as a table");
+    };
 
-                    for (i, key) in keys.iter().enumerate() {
-                        widths[i] = key.len();
-                    }
+    let keys: Vec<&str> = first_obj.keys().map(|s| s.as_str()).collect();
+    let headers: Vec<String> = keys
+        .iter()
+        .map(|k| escape_cell(&k.to_uppercase()))
+        .collect();
+    let mut widths: Vec<usize> = headers.iter().map(|h| h.chars().count()).collect();
 
-                    let mut rows = vec![];
-                    for item in &items {
-                        if let Some(obj) = item.as_object() {
-                            let mut row = vec![];
-                            for (i, key) in keys.iter().enumerate() {
-                                let val = obj
-                                    .get(*key)
-                                    .map(|v| match v {
-                                        Value::String(s) => s.clone(),
-                                        Value::Null => String::new(),
-                                        _ => v.to_string(),
-                                    })
-                                    .unwrap_or_default();
-
-                                let val_display = if val.chars().count() > 60 {
-                                    let truncated: String = val.chars().take(57).collect();
-                                    format!("{truncated}...")
-                                } else {
-                                    val
-                                };
-
-                                widths[i] = widths[i].max(val_display.len());
-                                row.push(val_display);
-                            }
-                            rows.push(row);
-                        }
-                    }
+    let mut rows = vec![];
+    for item in items {
+        let Some(obj) = item.as_object() else {
+            continue;
+        };
+        let row: Vec<String> = keys
+            .iter()
+            .map(|key| table_cell(obj.get(*key).unwrap_or(&Value::Null)))
+            .collect();
+        for (width, cell) in widths.iter_mut().zip(&row) {
+            *width = (*width).max(cell.chars().count());
+        }
+        rows.push(row);
+    }
 
-                    // Print Headers
-                    let header_row = keys
-                        .iter()
-                        .enumerate()
-                        .map(|(i, k)| format!("{k:<w$}", k = k.to_uppercase(), w = widths[i]))
-                        .collect::<Vec<_>>()
-                        .join("  ");
-                    println!("{header_row}");
-                    println!("{}", "-".repeat(header_row.len()));
-
-                    // Print Rows
-                    for row in rows {
-                        let formatted_row = row
-                            .iter()
-                            .enumerate()
-                            .map(|(i, col)| format!("{col:<w$}", col = col, w = widths[i]))
-                            .collect::<Vec<_>>()
-                            .join("  ");
-                        println!("{formatted_row}");
-                    }
+    let header_row = pad_row(&headers, &widths);
+    writeln!(out, "{header_row}")?;
+    writeln!(out, "{}", "-".repeat(header_row.chars().count()))?;
+    for row in rows {
+        writeln!(out, "{}", pad_row(&row, &widths))?;
+    }
+    Ok(())
+}
+
+/// Join `cells`, each left-aligned to its column width in characters.
+fn pad_row(cells: &[String], widths: &[usize]) -> String {
+    cells
+        .iter()
+        .zip(widths)
+        .map(|(cell, &w)| format!("{cell:<w$}"))
+        .collect::<Vec<_>>()
+        .join("  ")
+        .trim_end()
+        .to_string()
+}
+
+/// A table cell for `value`: scalars as text, arrays and objects as a count.
+fn table_cell(value: &Value) -> String {
+    let text = match value {
+        Value::Null => String::new(),
+        Value::String(s) => escape_cell(s),
+        Value::Array(items) => match items.len() {
+            1 => "[1 item]".to_string(),
+            n => format!("[{n} items]"),
+        },
+        Value::Object(fields) => match fields.len() {
+            1 => "{1 field}".to_string(),
+            n => format!("{{{n} fields}}"),
+        },
+        _ => value.to_string(),
+    };
+
+    if text.chars().count() > MAX_CELL_CHARS {
+        let truncated: String = text.chars().take(MAX_CELL_CHARS - 3).collect();
+        format!("{truncated}...")
+    } else {
+        text
+    }
+}
+
+/// Escape control characters (newlines, tabs, terminal escapes) so scraped
+/// text cannot break the table layout or drive the terminal.
+fn escape_cell(s: &str) -> String {
+    let mut escaped = String::with_capacity(s.len());
+    for c in s.chars() {
+        if c.is_control() {
+            escaped.extend(c.escape_default());
+        } else {
+            escaped.push(c);
+        }
+    }
+    escaped
+}
+
+/// Append `value` to `out` as block-style YAML indented by `indent` spaces.
+fn write_yaml(out: &mut String, value: &Value, indent: usize) {
+    let pad = " ".repeat(indent);
+    match value {
+        Value::Object(fields) if !fields.is_empty() => {
+            for (key, value) in fields {
+                out.push_str(&pad);
+                out.push_str(&yaml_string(key));
+                out.push(':');
+                if is_yaml_block(value) {
+                    out.push('\n');
+                    write_yaml(out, value, indent + 2);
                 } else {
-                    anyhow::bail!("Array elements must be objects to render as a table");
+                    out.push(' ');
+                    out.push_str(&yaml_scalar(value));
+                    out.push('\n');
                 }
             }
         }
+        Value::Array(items) if !items.is_empty() => {
+            for item in items {
+                out.push_str(&pad);
+                out.push_str("- ");
+                if is_yaml_block(item) {
+                    // Render one level deeper, then start its first line
+                    // after the dash: `- key: value`.
+                    let mut block = String::new();
+                    write_yaml(&mut block, item, indent + 2);
+                    out.push_str(&block[indent + 2..]);
+                } else {
+                    out.push_str(&yaml_scalar(item));
+                    out.push('\n');
+                }
+            }
+        }
+        _ => {
+            out.push_str(&pad);
+            out.push_str(&yaml_scalar(value));
+            out.push('\n');
+        }
+    }
+}
+
+/// Non-empty collections are written as blocks; everything else inline.
+fn is_yaml_block(value: &Value) -> bool {
+    match value {
+        Value::Object(fields) => !fields.is_empty(),
+        Value::Array(items) => !items.is_empty(),
+        _ => false,
+    }
+}
+
+fn yaml_scalar(value: &Value) -> String {
+    match value {
+        Value::Null => "null".to_string(),
+        Value::String(s) => yaml_string(s),
+        Value::Array(_) => "[]".to_string(),
+        Value::Object(_) => "{}".to_string(),
+        _ => value.to_string(),
+    }
+}
+
+/// `s` as a plain scalar when it cannot be mistaken for anything else,
+/// otherwise double-quoted. JSON string escapes are valid YAML, apart from
+/// the Unicode line breaks JSON leaves unescaped.
+fn yaml_string(s: &str) -> String {
+    const RESERVED: &[&str] = &[
+        "null", "~", "true", "false", "yes", "no", "on", "off", "y", "n",
+    ];
+    let plain = s.starts_with(|c: char| c.is_ascii_alphabetic())
+        && !s.ends_with(' ')
+        && s.chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, ' ' | '_' | '-' | '.' | '/'))
+        && !RESERVED.contains(&s.to_ascii_lowercase().as_str());
+    if plain {
+        return s.to_string();
+    }
+
+    serde_json::to_string(s)
+        .expect("strings always serialize")
+        .replace('\u{85}', "\\N")
+        .replace('\u{2028}', "\\L")
+        .replace('\u{2029}', "\\P")
+}
+
+/// Destination for a command's output: stdout, or a file written through a
+/// temporary sibling and renamed into place by [`OutputWriter::finish`], so
+/// readers never see a partial file and a failed run leaves any existing
+/// file untouched.
+pub struct OutputWriter {
+    format: OutputFormat,
+    dest: Destination,
+}
+
+enum Destination {
+    Stdout,
+    File {
+        tmp: BufWriter<NamedTempFile>,
+        path: PathBuf,
+    },
+}
+
+impl OutputWriter {
+    /// Write in `format` to `path`, or to stdout when `None`.
+    pub fn new(format: OutputFormat, path: Option<&Path>) -> Result<Self> {
+        let dest = match path {
+            None => Destination::Stdout,
+            Some(path) => {
+                let dir = match path.parent() {
+                    Some(dir) if !dir.as_os_str().is_empty() => dir,
+                    _ => Path::new("."),
+                };
+                let tmp = NamedTempFile::new_in(dir).with_context(|| {
+                    format!("Failed to create output file in {}", dir.display())
+                })?;
+                Destination::File {
+                    tmp: BufWriter::new(tmp),
+                    path: path.to_path_buf(),
+                }
+            }
+        };
+        Ok(Self { format, dest })
+    }
+
+    /// Write `data` as a whole document in this writer's format.
+    pub fn write(&mut self, data: &Value) -> Result<()> {
+        let format = self.format;
+        self.with_out(|out| OutputFormatter::write(format, data, out))
+    }
+
+    /// Write `row` as one JSON line, flushed so it shows up immediately when
+    /// streaming to stdout.
+    pub fn write_line(&mut self, row: &Value) -> Result<()> {
+        self.with_out(|out| {
+            writeln!(out, "{}", serde_json::to_string(row)?)?;
+            out.flush()?;
+            Ok(())
+        })
+    }
+
+    /// Flush everything written and move the file into place.
+    pub fn finish(self) -> Result<()> {
+        match self.dest {
+            Destination::Stdout => io::stdout().flush()?,
+            Destination::File { tmp, path } => {
+                let tmp = tmp.into_inner().map_err(|e| e.into_error())?;
+                tmp.as_file().sync_all()?;
+                tmp.persist(&path)
+                    .with_context(|| format!("Failed to write {}", path.display()))?;
+            }
+        }
         Ok(())
     }
+
+    fn with_out(&mut self, write: impl FnOnce(&mut dyn Write) -> Result<()>) -> Result<()> {
+        let result = match &mut self.dest {
+            Destination::Stdout => write(&mut io::stdout().lock()),
+            Destination::File { tmp, .. } => write(tmp),
+        };
+        result.context("Failed to write output")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    fn render(format: OutputFormat, data: &Value) -> String {
+        let mut out = Vec::new();
+        OutputFormatter::write(format, data, &mut out).unwrap();
+        String::from_utf8(out).unwrap()
+    }
+
+    #[test]
+    fn json_formats_pretty_compact_and_lines() {
+        let data = json!([{"a": 1}, {"a": "x\ny"}]);
+        assert_eq!(
+            render(OutputFormat::Json, &json!({"a": 1})),
+            "{\n  \"a\": 1\n}\n"
+        );
+        assert_eq!(
+            render(OutputFormat::Jq, &data),
+            "[{\"a\":1},{\"a\":\"x\\ny\"}]\n"
+        );
+        assert_eq!(
+            render(OutputFormat::Jsonl, &data),
+            "{\"a\":1}\n{\"a\":\"x\\ny\"}\n"
+        );
+    }
+
+    #[test]
+    fn yaml_nests_blocks_and_quotes_ambiguous_strings() {
+        let data = json!({
+            "title": "Hello world",
+            "tags": ["rust", {"name": "yes", "count": 2}, [1, 2]],
+            "published": "2024-01-01",
+            "note": "line\nbreak: \"quoted\"\u{2028}",
+            "empty": [],
+            "author": null,
+            "nested": {"ok": true}
+        });
+        assert_eq!(
+            render(OutputFormat::Yaml, &data),
+            "author: null\n\
+             empty: []\n\
+             nested:\n  ok: true\n\
+             note: \"line\\nbreak: \\\"quoted\\\"\\L\"\n\
+             published: \"2024-01-01\"\n\
+             tags:\n  - rust\n  - count: 2\n    name: \"yes\"\n  - - 1\n    - 2\n\
+             title: Hello world\n"
+        );
+        assert_eq!(render(OutputFormat::Yaml, &json!("true")), "\"true\"\n");
+    }
+
+    #[test]
+    fn csv_quotes_fields_with_separators() {
+        let data = json!([{"name": "a, b", "n": 1}, {"name": "say \"hi\"", "n": null}]);
+        assert_eq!(
+            render(OutputFormat::Csv, &data),
+            "n,name\n1,\"a, b\"\n,\"say \"\"hi\"\"\"\n"
+        );
+    }
+
+    #[test]
+    fn table_summarizes_collections_and_escapes_control_characters() {
+        let data = json!({
+            "title": "Café\t\u{1b}[31mred",
+            "tags": ["a", "b", "c"],
+            "meta": {"k": 1},
+            "price": 9.5
+        });
+        let out = render(OutputFormat::Table, &data);
+        let lines: Vec<&str> = out.lines().collect();
+        assert_eq!(lines[0], "META       PRICE  TAGS       TITLE");
+        assert_eq!(
+            lines[2],
+            "{1 field}  9.5    [3 items]  Café\\t\\u{1b}[31mred"
+        );
+        assert!(!out.contains('\u{1b}'));
+    }
+
+    #[test]
+    fn table_truncates_on_character_boundaries() {
+        let long = "日本語".repeat(30);
+        let out = render(OutputFormat::Table, &json!([{"text": long}]));
+        let cell = out.lines().nth(2).unwrap();
+        assert_eq!(cell.chars().count(), MAX_CELL_CHARS);
+        assert!(cell.ends_with("..."));
+    }
+
+    #[test]
+    fn output_file_appears_only_after_finish() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("out.jsonl");
+        std::fs::write(&path, "old\n").unwrap();
+
+        let mut writer = OutputWriter::new(OutputFormat::Jsonl, Some(&path)).unwrap();
+        writer.write_line(&json!({"url": "a"})).unwrap();
+        writer.write(&json!([{"url": "b"}])).unwrap();
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), "old\n");
+
+        writer.finish().unwrap();
+        assert_eq!(
+            std::fs::read_to_string(&path).unwrap(),
+            "{\"url\":\"a\"}\n{\"url\":\"b\"}\n"
+        );
+        assert_eq!(std::fs::read_dir(dir.path()).unwrap().count(), 1);
+    }
+
+    #[test]
+    fn dropped_writer_leaves_existing_file_untouched() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("out.json");
+        std::fs::write(&path, "old\n").unwrap();
+
+        let mut writer = OutputWriter::new(OutputFormat::Json, Some(&path)).unwrap();
+        writer.write(&json!({"a": 1})).unwrap();
+        drop(writer);
+
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), "old\n");
+        assert_eq!(std::fs::read_dir(dir.path()).unwrap().count(), 1);
+    }
+
+    #[test]
+    fn missing_output_directory_is_an_error() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("missing/out.json");
+        assert!(OutputWriter::new(OutputFormat::Json, Some(&path)).is_err());
+    }
 }
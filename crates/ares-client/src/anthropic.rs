@@ -15,6 +15,7 @@
 use std::time::Duration;
 
 use ares_core::error::AppError;
+use ares_core::language::output_language_instruction;
 use ares_core::models::{ExtractionOutcome, Usage};
 use ares_core::traits::{Extractor, ExtractorFactory};
 use reqwest::Client;
@@ -123,7 +124,8 @@ impl AnthropicExtractor {
             messages: vec![Message {
                 role: "user".to_string(),
                 content: format!(
-                    "Extract data matching the `extract` tool's schema from the following web content:\n\n{content}"
+                    "Extract data matching the `extract` tool's schema from the following web content:\n\n{content}{}",
+                    output_language_instruction(schema).unwrap_or_default()
                 ),
             }],
         }
@@ -168,6 +170,8 @@ struct MessagesResponse {
     content: Vec<ContentBlock>,
     #[serde(default)]
     usage: Option<ApiUsage>,
+    #[serde(default)]
+    model: Option<String>,
 }
 
 /// Anthropic Messages API usage block (`input_tokens` / `output_tokens`).
@@ -197,8 +201,8 @@ struct ApiErrorDetail {
     message: String,
 }
 
-/// Extract the forced-tool result (and token usage) from a Messages API
-/// response body.
+/// Extract the forced-tool result (and token usage and answering model) from
+/// a Messages API response body.
 ///
 /// Pure function (no HTTP) so it can be unit-tested against recorded responses.
 fn parse_extraction(body: &str) -> Result<ExtractionOutcome, AppError> {
@@ -231,7 +235,11 @@ fn parse_extraction(body: &str) -> Result<ExtractionOutcome, AppError> {
             retryable: false,
         })?;
 
-    Ok(ExtractionOutcome { value, usage })
+    Ok(ExtractionOutcome {
+        value,
+        usage,
+        model: response.model.filter(|m| !m.is_empty()),
+    })
 }
 
 impl Extractor for AnthropicExtractor {
@@ -291,6 +299,10 @@ impl Extractor for AnthropicExtractor {
 
         parse_extraction(&body)
     }
+
+    fn fingerprint(&self) -> Option<String> {
+        Some(self.system_prompt.clone())
+    }
 }
 
 /// Factory that creates `AnthropicExtractor` instances with a shared API key.
@@ -378,6 +390,21 @@ mod tests {
         let json = serde_json::to_value(&req).unwrap();
         assert_eq!(json["tool_choice"]["type"], "tool");
         assert_eq!(json["system"], DEFAULT_SYSTEM_PROMPT);
+        assert!(!req.messages[0].content.contains("Write every extracted"));
+    }
+
+    #[test]
+    fn build_request_appends_declared_output_language() {
+        let extractor = AnthropicExtractor::new("key", "claude-haiku-4-5").unwrap();
+        let mut schema = schema();
+        schema["x-ares-output-language"] = "en".into();
+        let req = extractor.build_request("Der Stadtrat hat getagt.", &schema);
+
+        let content = &req.messages[0].content;
+        assert!(content.contains("Der Stadtrat hat getagt."));
+        assert!(content.ends_with(
+            "Write every extracted text value in the language `en`, translating from the source content where it is written in another language. Enum values must match the schema exactly."
+        ));
     }
 
     #[test]
@@ -386,6 +413,7 @@ mod tests {
             "id": "msg_1",
             "type": "message",
             "role": "assistant",
+            "model": "claude-haiku-4-5-20251001",
             "content": [
                 { "type": "tool_use", "id": "toolu_1", "name": "extract", "input": { "title": "Hello" } }
             ],
@@ -396,6 +424,7 @@ mod tests {
 
         let outcome = parse_extraction(&body).unwrap();
         assert_eq!(outcome.value, serde_json::json!({ "title": "Hello" }));
+        assert_eq!(outcome.model.as_deref(), Some("claude-haiku-4-5-20251001"));
         let usage = outcome.usage.expect("usage present");
         assert_eq!(usage.prompt_tokens, 120);
         assert_eq!(usage.completion_tokens, 8);
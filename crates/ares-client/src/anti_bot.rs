@@ -0,0 +1,166 @@
+//! Recognizing anti-bot blocks and challenge pages, so a fetch fails with
+//! [`AppError::Blocked`] instead of retrying pointlessly or handing the
+//! challenge HTML to the extractor.
+//!
+//! The checks are deliberately narrow: a page is only called blocked by its
+//! `<title>` or by markers that appear on challenge pages alone. Scripts
+//! Cloudflare injects into ordinary pages (`/cdn-cgi/challenge-platform/...`)
+//! don't count.
+
+use ares_core::error::AppError;
+use reqwest::StatusCode;
+use reqwest::header::HeaderMap;
+
+/// Hint for blocks hit by a plain HTTP fetch.
+pub(crate) const PLAIN_FETCH_HINT: &str = "retry with --browser, or configure a proxy";
+
+/// Hint for blocks hit by a browser fetch, which already ran the page's
+/// JavaScript.
+#[cfg(feature = "browser")]
+pub(crate) const BROWSER_FETCH_HINT: &str =
+    "configure a proxy, or lower the request rate for this site";
+
+/// `<title>`s of challenge and block pages, lowercased.
+const BLOCK_TITLES: &[(&str, &str)] = &[
+    ("just a moment...", "Cloudflare JS challenge"),
+    ("just a moment\u{2026}", "Cloudflare JS challenge"),
+    ("attention required! | cloudflare", "Cloudflare block page"),
+];
+
+/// Markers found only on challenge pages.
+const BLOCK_MARKERS: &[(&str, &str)] = &[
+    ("window._cf_chl_opt", "Cloudflare JS challenge"),
+    ("captcha-delivery.com", "DataDome captcha"),
+    ("id=\"px-captcha\"", "PerimeterX captcha"),
+];
+
+/// Why an HTTP response looks like an anti-bot block, if it does.
+pub(crate) fn blocked_response(
+    status: StatusCode,
+    headers: &HeaderMap,
+    body: &str,
+) -> Option<String> {
+    if let Some(reason) = challenge_page(body) {
+        return Some(if status.is_success() {
+            reason.to_string()
+        } else {
+            format!("{reason} (HTTP {})", status.as_u16())
+        });
+    }
+    if status == StatusCode::FORBIDDEN && headers.contains_key("cf-ray") {
+        return Some("HTTP 403 from Cloudflare".to_string());
+    }
+    None
+}
+
+/// What kind of challenge or block page `html` is, if it is one.
+pub(crate) fn challenge_page(html: &str) -> Option<&'static str> {
+    if let Some(title) = title(html) {
+        let title = title.trim().to_lowercase();
+        if let Some((_, reason)) = BLOCK_TITLES.iter().find(|(t, _)| *t == title) {
+            return Some(*reason);
+        }
+    }
+    BLOCK_MARKERS
+        .iter()
+        .find(|(marker, _)| html.contains(marker))
+        .map(|(_, reason)| *reason)
+}
+
+/// The [`AppError::Blocked`] for a fetch of `url`.
+pub(crate) fn blocked(url: &str, reason: impl Into<String>, hint: &str) -> AppError {
+    AppError::Blocked {
+        url: url.to_string(),
+        reason: reason.into(),
+        hint: hint.to_string(),
+    }
+}
+
+/// The text of the first `<title>` element.
+fn title(html: &str) -> Option<&str> {
+    let lower = html.to_ascii_lowercase();
+    let open = lower.find("<title")?;
+    let start = open + lower[open..].find('>')? + 1;
+    let end = start + lower[start..].find("</title")?;
+    html.get(start..end)
+}
+
+#[cfg(test)]
+mod tests {
+    use reqwest::header::HeaderValue;
+
+    use super::*;
+
+    const CLOUDFLARE_CHALLENGE: &str =
+        include_str!("../tests/fixtures/blocked/cloudflare-challenge.html");
+    const CLOUDFLARE_BLOCK: &str = include_str!("../tests/fixtures/blocked/cloudflare-block.html");
+    const DATADOME: &str = include_str!("../tests/fixtures/blocked/datadome.html");
+    const ARTICLE: &str = include_str!("../tests/fixtures/blocked/article.html");
+
+    fn cloudflare_headers() -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        headers.insert("cf-ray", HeaderValue::from_static("8c1f0a2b3d4e5f60-AMS"));
+        headers.insert("server", HeaderValue::from_static("cloudflare"));
+        headers
+    }
+
+    #[test]
+    fn challenge_pages_are_recognized() {
+        assert_eq!(
+            challenge_page(CLOUDFLARE_CHALLENGE),
+            Some("Cloudflare JS challenge")
+        );
+        assert_eq!(
+            challenge_page(CLOUDFLARE_BLOCK),
+            Some("Cloudflare block page")
+        );
+        assert_eq!(challenge_page(DATADOME), Some("DataDome captcha"));
+        assert_eq!(
+            challenge_page("<html><head><TITLE> Just a moment\u{2026} </TITLE></head></html>"),
+            Some("Cloudflare JS challenge")
+        );
+    }
+
+    #[test]
+    fn ordinary_pages_are_not_blocks() {
+        // Mentions the challenge title in its text and loads Cloudflare's
+        // injected bot-management script.
+        assert_eq!(challenge_page(ARTICLE), None);
+        assert_eq!(
+            blocked_response(StatusCode::OK, &cloudflare_headers(), ARTICLE),
+            None
+        );
+    }
+
+    #[test]
+    fn responses_are_judged_by_status_headers_and_body() {
+        assert_eq!(
+            blocked_response(
+                StatusCode::SERVICE_UNAVAILABLE,
+                &cloudflare_headers(),
+                CLOUDFLARE_CHALLENGE
+            )
+            .as_deref(),
+            Some("Cloudflare JS challenge (HTTP 503)")
+        );
+        assert_eq!(
+            blocked_response(StatusCode::TOO_MANY_REQUESTS, &HeaderMap::new(), DATADOME).as_deref(),
+            Some("DataDome captcha (HTTP 429)")
+        );
+        assert_eq!(
+            blocked_response(StatusCode::FORBIDDEN, &cloudflare_headers(), "").as_deref(),
+            Some("HTTP 403 from Cloudflare")
+        );
+
+        // A 403 or 429 without Cloudflare or challenge markers is an ordinary
+        // HTTP error.
+        assert_eq!(
+            blocked_response(StatusCode::FORBIDDEN, &HeaderMap::new(), "Forbidden"),
+            None
+        );
+        assert_eq!(
+            blocked_response(StatusCode::TOO_MANY_REQUESTS, &cloudflare_headers(), ""),
+            None
+        );
+    }
+}
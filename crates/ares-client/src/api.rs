@@ -0,0 +1,737 @@
+//! Client for the Ares REST API.
+//!
+//! [`AresApiClient`] lets the CLI run against a central `ares-api` server
+//! instead of a local database and LLM key. Responses are decoded into the
+//! same core types the server builds its DTOs from ([`ScrapeJob`],
+//! [`HistoryEntry`], [`SchemaEntry`], ...), so callers can render them exactly
+//! as they render local results. Error bodies are turned back into
+//! [`AppError`]s carrying the server's message.
+
+use std::time::{Duration, SystemTime};
+
+use ares_core::error::AppError;
+use ares_core::event_log::WorkerEventRecord;
+use ares_core::fetch::FetchOptions;
+use ares_core::job::{CreateScrapeJobRequest, JobStatus, ScrapeJob};
+use ares_core::models::{Extraction, HistoryEntry};
+use ares_core::schema::SchemaEntry;
+use chrono::{DateTime, Utc};
+use reqwest::{Client, Method, RequestBuilder, Response, StatusCode};
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use url::Url;
+use uuid::Uuid;
+
+use crate::sitemap::SitemapOptions;
+
+/// Upper bound on a single API call; scrapes wait for the server's LLM call.
+const DEFAULT_TIMEOUT: Duration = Duration::from_secs(300);
+
+/// Body of `POST /v1/scrape`.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct ScrapeRequest {
+    pub url: String,
+    /// Pre-fetched HTML to extract from instead of fetching `url`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub html: Option<String>,
+    pub schema: serde_json::Value,
+    pub schema_name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub model: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub provider: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub base_url: Option<String>,
+    /// Persist the extraction on the server (the server defaults to `true`).
+    pub save: bool,
+    /// User-Agent of the server's browser: a [`UserAgent`](crate::UserAgent)
+    /// preset name or any string. Needs the page rendered in the browser.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub user_agent: Option<String>,
+    /// Skip the LLM when the content's simhash is within this many bits of
+    /// the previous extraction's. Needs `save`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub content_similarity_threshold: Option<u32>,
+    #[serde(flatten)]
+    pub fetch: FetchOptions,
+}
+
+/// Response of `POST /v1/scrape`.
+#[derive(Debug, Clone, Deserialize)]
+pub struct ScrapeResponse {
+    pub extracted_data: serde_json::Value,
+    pub content_hash: String,
+    pub data_hash: String,
+    pub changed: bool,
+    pub extraction_id: Option<Uuid>,
+    /// The extraction of another URL with the same content, when the server
+    /// dedups across URLs.
+    pub duplicate_of: Option<Uuid>,
+    /// ISO 639-3 code of the cleaned content's language, when detected.
+    pub detected_language: Option<String>,
+}
+
+/// A schema version as served by `GET /v1/schemas/{name}/{version}`.
+#[derive(Debug, Clone, Deserialize)]
+pub struct RemoteSchema {
+    pub name: String,
+    /// The concrete version, also when `latest` was requested.
+    pub version: String,
+    pub schema: serde_json::Value,
+}
+
+/// Error body shared by every endpoint; `messages` is only set on 422s.
+#[derive(Deserialize)]
+struct ErrorBody {
+    message: String,
+    #[serde(default)]
+    messages: Vec<String>,
+}
+
+#[derive(Deserialize)]
+struct CreateJobResponse {
+    job_id: Uuid,
+}
+
+/// Response of `POST /v1/jobs/from-sitemap`.
+#[derive(Debug, Clone, Deserialize)]
+pub struct SitemapJobsResponse {
+    /// The created jobs, in sitemap order.
+    pub job_ids: Vec<Uuid>,
+    /// URLs skipped as unmodified since their last extraction.
+    pub skipped: usize,
+}
+
+#[derive(Deserialize)]
+struct JobList {
+    jobs: Vec<ScrapeJob>,
+}
+
+#[derive(Deserialize)]
+struct JobEvents {
+    events: Vec<WorkerEventRecord>,
+}
+
+#[derive(Deserialize)]
+struct ExtractionHistory {
+    extractions: Vec<HistoryEntry>,
+}
+
+#[derive(Deserialize)]
+struct SchemaList {
+    schemas: Vec<RemoteSchemaEntry>,
+}
+
+#[derive(Deserialize)]
+struct RemoteSchemaEntry {
+    name: String,
+    latest_version: String,
+    versions: Vec<String>,
+    created_at: Option<DateTime<Utc>>,
+    updated_at: Option<DateTime<Utc>>,
+}
+
+impl From<RemoteSchemaEntry> for SchemaEntry {
+    fn from(e: RemoteSchemaEntry) -> Self {
+        Self {
+            name: e.name,
+            latest_version: e.latest_version,
+            versions: e.versions,
+            created_at: e.created_at.map(SystemTime::from),
+            updated_at: e.updated_at.map(SystemTime::from),
+        }
+    }
+}
+
+/// Typed client for an Ares server's `/v1` API.
+#[derive(Clone)]
+pub struct AresApiClient {
+    client: Client,
+    base_url: Url,
+    token: Option<String>,
+}
+
+impl AresApiClient {
+    /// A client for the server at `base_url` (e.g. `https://ares.internal`),
+    /// authenticating with `token` (an API key or the admin token) when set.
+    pub fn new(base_url: &str, token: Option<&str>) -> Result<Self, AppError> {
+        let base_url = Url::parse(base_url)
+            .ok()
+            .filter(|url| matches!(url.scheme(), "http" | "https"))
+            .ok_or_else(|| AppError::ConfigError(format!("Invalid Ares server URL: {base_url}")))?;
+        let client = Client::builder()
+            .timeout(DEFAULT_TIMEOUT)
+            .build()
+            .map_err(|e| AppError::HttpError(e.to_string()))?;
+
+        Ok(Self {
+            client,
+            base_url,
+            token: token.filter(|t| !t.is_empty()).map(String::from),
+        })
+    }
+
+    /// Scrape one page on the server.
+    pub async fn scrape(&self, request: &ScrapeRequest) -> Result<ScrapeResponse, AppError> {
+        let response = self
+            .send(self.request(Method::POST, &["scrape"]).json(request))
+            .await?;
+        decode(response).await
+    }
+
+    /// Queue a scrape job and return its ID.
+    pub async fn create_job(&self, request: &CreateScrapeJobRequest) -> Result<Uuid, AppError> {
+        let body = job_body(request)?;
+        let response = self
+            .send(self.request(Method::POST, &["jobs"]).json(&body))
+            .await?;
+        Ok(decode::<CreateJobResponse>(response).await?.job_id)
+    }
+
+    /// Have the server queue one job per URL of the sitemap at
+    /// `sitemap_url`, each like `template` but for its URL.
+    pub async fn create_jobs_from_sitemap(
+        &self,
+        sitemap_url: &str,
+        options: &SitemapOptions,
+        template: &CreateScrapeJobRequest,
+    ) -> Result<SitemapJobsResponse, AppError> {
+        let mut body = job_body(template)?;
+        if let (Some(body), serde_json::Value::Object(options)) =
+            (body.as_object_mut(), serde_json::to_value(options)?)
+        {
+            body.remove("url");
+            body.insert("sitemap_url".into(), sitemap_url.into());
+            body.extend(options);
+        }
+
+        let response = self
+            .send(
+                self.request(Method::POST, &["jobs", "from-sitemap"])
+                    .json(&body),
+            )
+            .await?;
+        decode(response).await
+    }
+
+    /// The most recent jobs, optionally filtered by status, by the worker
+    /// that claimed them and by tag.
+    pub async fn list_jobs(
+        &self,
+        status: Option<JobStatus>,
+        worker_id: Option<&str>,
+        tag: Option<&str>,
+        limit: usize,
+    ) -> Result<Vec<ScrapeJob>, AppError> {
+        let mut query = vec![("limit", limit.to_string())];
+        if let Some(status) = status {
+            query.push(("status", status.to_string()));
+        }
+        if let Some(worker_id) = worker_id {
+            query.push(("worker_id", worker_id.to_string()));
+        }
+        if let Some(tag) = tag {
+            query.push(("tag", tag.to_string()));
+        }
+        let response = self
+            .send(self.request_with_query(Method::GET, &["jobs"], &query))
+            .await?;
+        Ok(decode::<JobList>(response).await?.jobs)
+    }
+
+    /// A job by ID, or `None` if the server has no such job.
+    pub async fn get_job(&self, id: Uuid) -> Result<Option<ScrapeJob>, AppError> {
+        let response = self
+            .send_allowing_not_found(self.request(Method::GET, &["jobs", &id.to_string()]))
+            .await?;
+        match response {
+            Some(response) => decode(response).await.map(Some),
+            None => Ok(None),
+        }
+    }
+
+    /// Worker events recorded for a job, oldest first.
+    pub async fn job_events(
+        &self,
+        id: Uuid,
+        limit: usize,
+    ) -> Result<Vec<WorkerEventRecord>, AppError> {
+        let response = self
+            .send(self.request_with_query(
+                Method::GET,
+                &["jobs", &id.to_string(), "events", "history"],
+                &[("limit", limit.to_string())],
+            ))
+            .await?;
+        Ok(decode::<JobEvents>(response).await?.events)
+    }
+
+    /// Cancel a pending or running job.
+    pub async fn cancel_job(&self, id: Uuid) -> Result<(), AppError> {
+        self.send(self.request(Method::DELETE, &["jobs", &id.to_string()]))
+            .await?;
+        Ok(())
+    }
+
+    /// Extractions for a URL and schema, newest first, each with the
+    /// server's comparison against the one before it. `language` keeps only
+    /// those whose content was detected in it.
+    pub async fn extraction_history(
+        &self,
+        url: &str,
+        schema_name: &str,
+        language: Option<&str>,
+        limit: usize,
+    ) -> Result<Vec<HistoryEntry>, AppError> {
+        let mut query = vec![
+            ("url", url.to_string()),
+            ("schema_name", schema_name.to_string()),
+            ("limit", limit.to_string()),
+        ];
+        if let Some(language) = language {
+            query.push(("language", language.to_string()));
+        }
+        let response = self
+            .send(self.request_with_query(Method::GET, &["extractions"], &query))
+            .await?;
+        Ok(decode::<ExtractionHistory>(response).await?.extractions)
+    }
+
+    /// The most recent extraction for a URL and schema, or `None` if the
+    /// URL was never extracted with it.
+    pub async fn latest_extraction(
+        &self,
+        url: &str,
+        schema_name: &str,
+    ) -> Result<Option<Extraction>, AppError> {
+        let response = self
+            .send_allowing_not_found(self.request_with_query(
+                Method::GET,
+                &["extractions", "latest"],
+                &[
+                    ("url", url.to_string()),
+                    ("schema_name", schema_name.to_string()),
+                ],
+            ))
+            .await?;
+        match response {
+            Some(response) => decode(response).await.map(Some),
+            None => Ok(None),
+        }
+    }
+
+    /// Every schema in the server's schema directory.
+    pub async fn list_schemas(&self) -> Result<Vec<SchemaEntry>, AppError> {
+        let response = self.send(self.request(Method::GET, &["schemas"])).await?;
+        let list: SchemaList = decode(response).await?;
+        Ok(list.schemas.into_iter().map(SchemaEntry::from).collect())
+    }
+
+    /// A schema version (or `latest`), or `None` if the server has no such
+    /// version.
+    pub async fn get_schema(
+        &self,
+        name: &str,
+        version: &str,
+    ) -> Result<Option<RemoteSchema>, AppError> {
+        let response = self
+            .send_allowing_not_found(self.request(Method::GET, &["schemas", name, version]))
+            .await?;
+        match response {
+            Some(response) => decode(response).await.map(Some),
+            None => Ok(None),
+        }
+    }
+
+    /// Upload a new schema version.
+    pub async fn create_schema(
+        &self,
+        name: &str,
+        version: &str,
+        schema: &serde_json::Value,
+    ) -> Result<(), AppError> {
+        let body = serde_json::json!({ "name": name, "version": version, "schema": schema });
+        self.send(self.request(Method::POST, &["schemas"]).json(&body))
+            .await?;
+        Ok(())
+    }
+
+    /// Point a schema's latest version at an existing version.
+    pub async fn set_latest_schema(&self, name: &str, version: &str) -> Result<(), AppError> {
+        self.send(self.request(Method::POST, &["schemas", name, version, "latest"]))
+            .await?;
+        Ok(())
+    }
+
+    /// A request to `/v1/{segments...}`, each segment percent-encoded.
+    fn request(&self, method: Method, segments: &[&str]) -> RequestBuilder {
+        self.request_with_query(method, segments, &[])
+    }
+
+    fn request_with_query(
+        &self,
+        method: Method,
+        segments: &[&str],
+        query: &[(&str, String)],
+    ) -> RequestBuilder {
+        let mut url = self.base_url.clone();
+        if let Ok(mut path) = url.path_segments_mut() {
+            path.pop_if_empty().push("v1").extend(segments);
+        }
+        if !query.is_empty() {
+            url.query_pairs_mut().extend_pairs(query);
+        }
+
+        let request = self.client.request(method, url);
+        match &self.token {
+            Some(token) => request.bearer_auth(token),
+            None => request,
+        }
+    }
+
+    /// Send `request`, turning non-2xx responses into errors.
+    async fn send(&self, request: RequestBuilder) -> Result<Response, AppError> {
+        let response = request.send().await.map_err(request_error)?;
+        if response.status().is_success() {
+            return Ok(response);
+        }
+        Err(response_error(response).await)
+    }
+
+    /// Like [`send`](Self::send), but a 404 is `Ok(None)`.
+    async fn send_allowing_not_found(
+        &self,
+        request: RequestBuilder,
+    ) -> Result<Option<Response>, AppError> {
+        let response = request.send().await.map_err(request_error)?;
+        if response.status() == StatusCode::NOT_FOUND {
+            return Ok(None);
+        }
+        if response.status().is_success() {
+            return Ok(Some(response));
+        }
+        Err(response_error(response).await)
+    }
+}
+
+/// Body of `POST /v1/jobs`: the job fields plus its flattened fetch options.
+fn job_body(request: &CreateScrapeJobRequest) -> Result<serde_json::Value, AppError> {
+    let mut body = serde_json::json!({
+        "url": request.url,
+        "schema_name": request.schema_name,
+        "schema": request.schema,
+        "model": request.model,
+        "base_url": request.base_url,
+        "max_retries": request.max_retries,
+        "notify_url": request.notify_url,
+        "tags": request.tags,
+    });
+    if let (Some(body), serde_json::Value::Object(fetch)) = (
+        body.as_object_mut(),
+        serde_json::to_value(&request.fetch_options)?,
+    ) {
+        body.extend(fetch);
+    }
+    Ok(body)
+}
+
+async fn decode<T: DeserializeOwned>(response: Response) -> Result<T, AppError> {
+    let url = response.url().path().to_string();
+    response
+        .json()
+        .await
+        .map_err(|e| AppError::HttpError(format!("Unexpected response from Ares API {url}: {e}")))
+}
+
+fn request_error(e: reqwest::Error) -> AppError {
+    if e.is_timeout() {
+        AppError::Timeout(DEFAULT_TIMEOUT.as_secs())
+    } else if e.is_connect() {
+        AppError::NetworkError(format!("Could not reach the Ares server: {e}"))
+    } else {
+        AppError::HttpError(e.to_string())
+    }
+}
+
+/// The error a failed response stands for, keeping the server's message.
+async fn response_error(response: Response) -> AppError {
+    let status = response.status();
+    let path = response.url().path().to_string();
+    let body = response.text().await.unwrap_or_default();
+    let Ok(error) = serde_json::from_str::<ErrorBody>(&body) else {
+        return AppError::HttpError(format!("Ares API returned {status} for {path}"));
+    };
+
+    match status {
+        StatusCode::UNPROCESSABLE_ENTITY if !error.messages.is_empty() => {
+            AppError::ValidationError(error.messages)
+        }
+        StatusCode::UNAUTHORIZED => AppError::ConfigError(format!(
+            "{} (set --token or ARES_REMOTE_TOKEN)",
+            error.message
+        )),
+        StatusCode::TOO_MANY_REQUESTS => AppError::RateLimitExceeded,
+        _ => AppError::Generic(error.message),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::{Arc, Mutex};
+
+    use axum::extract::{Path, Query};
+    use axum::http::{HeaderMap, StatusCode};
+    use axum::response::IntoResponse;
+    use axum::routing::{get, post};
+    use axum::{Json, Router};
+
+    use super::*;
+
+    /// Serve `router` on a random local port and return a client for it.
+    async fn serve(router: Router) -> AresApiClient {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move { axum::serve(listener, router).await.unwrap() });
+        AresApiClient::new(&format!("http://{addr}"), Some("secret")).unwrap()
+    }
+
+    fn job_json(id: Uuid) -> serde_json::Value {
+        serde_json::json!({
+            "id": id,
+            "url": "https://example.com",
+            "schema_name": "blog",
+            "schema": {"type": "object"},
+            "model": "gpt-4o-mini",
+            "base_url": "https://api.openai.com/v1",
+            "status": "pending",
+            "created_at": "2026-01-01T00:00:00Z",
+            "updated_at": "2026-01-01T00:00:00Z",
+            "started_at": null,
+            "completed_at": null,
+            "retry_count": 0,
+            "max_retries": 3,
+            "next_retry_at": null,
+            "error_message": null,
+            "extraction_id": null,
+            "worker_id": null,
+            "crawl_session_id": null,
+            "parent_job_id": null,
+            "depth": 0,
+            "max_depth": 0,
+            "max_pages": 100,
+            "allowed_domains": [],
+            "fetch_options": {
+                "use_browser": false,
+                "fetch_timeout_secs": null,
+                "llm_timeout_secs": null,
+                "content_selector": null,
+                "wait_selector": null,
+                "headers": {}
+            }
+        })
+    }
+
+    #[tokio::test]
+    async fn scrape_sends_the_request_with_the_token() {
+        let seen = Arc::new(Mutex::new(None));
+        let captured = seen.clone();
+        let client = serve(Router::new().route(
+            "/v1/scrape",
+            post(
+                move |headers: HeaderMap, Json(body): Json<serde_json::Value>| {
+                    let auth = headers["authorization"].to_str().unwrap().to_string();
+                    *captured.lock().unwrap() = Some((auth, body));
+                    async {
+                        Json(serde_json::json!({
+                            "extracted_data": {"title": "Hello"},
+                            "content_hash": "c",
+                            "data_hash": "d",
+                            "changed": true,
+                            "extraction_id": null
+                        }))
+                    }
+                },
+            ),
+        ))
+        .await;
+
+        let response = client
+            .scrape(&ScrapeRequest {
+                url: "https://example.com".into(),
+                schema: serde_json::json!({"type": "object"}),
+                schema_name: "blog".into(),
+                fetch: FetchOptions {
+                    fetch_timeout_secs: Some(5),
+                    ..FetchOptions::default()
+                },
+                ..ScrapeRequest::default()
+            })
+            .await
+            .unwrap();
+
+        assert_eq!(response.extracted_data["title"], "Hello");
+        let (auth, body) = seen.lock().unwrap().take().unwrap();
+        assert_eq!(auth, "Bearer secret");
+        assert_eq!(body["schema_name"], "blog");
+        assert_eq!(body["save"], false);
+        assert_eq!(body["fetch_timeout_secs"], 5);
+        assert!(body.get("html").is_none());
+    }
+
+    #[tokio::test]
+    async fn jobs_decode_into_scrape_jobs() {
+        let id = Uuid::new_v4();
+        let client = serve(
+            Router::new()
+                .route(
+                    "/v1/jobs",
+                    get(move |Query(q): Query<Vec<(String, String)>>| async move {
+                        assert!(q.contains(&("status".into(), "pending".into())));
+                        Json(serde_json::json!({
+                            "jobs": [job_json(id)], "total": 1, "limit": 5, "offset": 0
+                        }))
+                    })
+                    .post(move || async move {
+                        (
+                            StatusCode::CREATED,
+                            Json(serde_json::json!({"job_id": id, "status": "pending"})),
+                        )
+                    }),
+                )
+                .route(
+                    "/v1/jobs/{id}",
+                    get(move |Path(requested): Path<Uuid>| async move {
+                        if requested == id {
+                            Json(job_json(id)).into_response()
+                        } else {
+                            (
+                                StatusCode::NOT_FOUND,
+                                Json(serde_json::json!({
+                                    "error": "not_found",
+                                    "message": format!("Job not found: {requested}")
+                                })),
+                            )
+                                .into_response()
+                        }
+                    }),
+                ),
+        )
+        .await;
+
+        let request = CreateScrapeJobRequest::new(
+            "https://example.com",
+            "blog",
+            serde_json::json!({"type": "object"}),
+            "gpt-4o-mini",
+            "https://api.openai.com/v1",
+        );
+        assert_eq!(client.create_job(&request).await.unwrap(), id);
+
+        let jobs = client
+            .list_jobs(Some(JobStatus::Pending), None, None, 5)
+            .await
+            .unwrap();
+        assert_eq!(jobs.len(), 1);
+        assert_eq!(jobs[0].status, JobStatus::Pending);
+        assert_eq!(jobs[0].max_pages, 100);
+
+        assert_eq!(client.get_job(id).await.unwrap().unwrap().id, id);
+        assert!(client.get_job(Uuid::new_v4()).await.unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn error_bodies_become_readable_errors() {
+        let client = serve(
+            Router::new()
+                .route(
+                    "/v1/jobs/{id}",
+                    axum::routing::delete(|| async {
+                        (
+                            StatusCode::CONFLICT,
+                            Json(serde_json::json!({
+                                "error": "conflict",
+                                "message": "Job is already in terminal state: completed"
+                            })),
+                        )
+                    }),
+                )
+                .route(
+                    "/v1/schemas",
+                    post(|| async {
+                        (
+                            StatusCode::UNPROCESSABLE_ENTITY,
+                            Json(serde_json::json!({
+                                "error": "validation_error",
+                                "message": "a; b",
+                                "messages": ["a", "b"]
+                            })),
+                        )
+                    })
+                    .get(|| async {
+                        (
+                            StatusCode::UNAUTHORIZED,
+                            Json(serde_json::json!({
+                                "error": "unauthorized",
+                                "message": "Invalid API key"
+                            })),
+                        )
+                    }),
+                ),
+        )
+        .await;
+
+        let err = client.cancel_job(Uuid::new_v4()).await.unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Job is already in terminal state: completed"
+        );
+
+        let err = client
+            .create_schema("blog", "1.0.0", &serde_json::json!({}))
+            .await
+            .unwrap_err();
+        assert!(matches!(err, AppError::ValidationError(ref m) if m == &["a", "b"]));
+
+        let err = client.list_schemas().await.unwrap_err();
+        assert!(err.to_string().contains("ARES_REMOTE_TOKEN"), "{err}");
+
+        // Routes the server doesn't have answer without an error body.
+        let err = client
+            .extraction_history("u", "s", None, 1)
+            .await
+            .unwrap_err();
+        assert!(
+            err.to_string()
+                .contains("404 Not Found for /v1/extractions"),
+            "{err}"
+        );
+    }
+
+    #[tokio::test]
+    async fn schema_segments_are_encoded() {
+        let client = serve(Router::new().route(
+            "/v1/schemas/{name}/{version}",
+            get(|Path((name, version)): Path<(String, String)>| async move {
+                Json(serde_json::json!({
+                    "name": name, "version": version, "schema": {"type": "object"}
+                }))
+            }),
+        ))
+        .await;
+
+        let schema = client
+            .get_schema("my blog", "1.0.0")
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(schema.name, "my blog");
+        assert_eq!(schema.version, "1.0.0");
+    }
+
+    #[test]
+    fn rejects_non_http_server_urls() {
+        assert!(AresApiClient::new("ares.internal", None).is_err());
+        assert!(AresApiClient::new("ftp://ares.internal", None).is_err());
+        assert!(AresApiClient::new("https://ares.internal/base/", None).is_ok());
+    }
+}
@@ -1,17 +1,25 @@
+use std::collections::BTreeMap;
 use std::path::PathBuf;
 use std::sync::Arc;
 use std::time::Duration;
 
 use ares_core::error::AppError;
+use ares_core::fetch::FetchOptions;
 use ares_core::stealth::{self, StealthConfig};
 use ares_core::traits::Fetcher;
 use chromiumoxide::cdp::browser_protocol::emulation::SetDeviceMetricsOverrideParams;
+use chromiumoxide::cdp::browser_protocol::network::{Headers, SetExtraHttpHeadersParams};
 use chromiumoxide::cdp::browser_protocol::page::AddScriptToEvaluateOnNewDocumentParams;
 use chromiumoxide::{Browser, BrowserConfig, Page};
 use futures::StreamExt;
 
+use crate::anti_bot;
+use crate::cleaner::INJECTED_DATA_ID;
 use crate::user_agent::UserAgentPool;
 
+/// How often a `wait_selector` is re-checked while the page renders.
+const WAIT_SELECTOR_POLL_INTERVAL: Duration = Duration::from_millis(100);
+
 /// Headless-browser fetcher using Chromium via the Chrome DevTools Protocol.
 ///
 /// Unlike [`super::ReqwestFetcher`], this renders JavaScript before returning
@@ -35,6 +43,19 @@ use crate::user_agent::UserAgentPool;
 /// - Viewport dimensions randomised
 /// - `navigator.platform` and `navigator.languages` overridden
 ///
+/// # User-Agent
+///
+/// [`BrowserFetcher::with_user_agent`] fixes the User-Agent of every page,
+/// for sites that serve different content to known scrapers. It takes
+/// precedence over stealth rotation.
+///
+/// # Pre-capture script
+///
+/// [`BrowserFetcher::with_pre_capture_script`] runs a JavaScript expression on
+/// the rendered page just before the HTML is captured. Its JSON result is
+/// appended to the HTML as `<script id="ares-injected-data">`, which
+/// [`super::HtmdCleaner`] surfaces to the extractor.
+///
 /// # Example
 ///
 /// ```rust,no_run
@@ -54,6 +75,8 @@ pub struct BrowserFetcher {
     timeout: Duration,
     stealth: StealthConfig,
     ua_pool: Option<UserAgentPool>,
+    user_agent: Option<Arc<str>>,
+    pre_capture_script: Option<Arc<str>>,
 }
 
 impl BrowserFetcher {
@@ -97,6 +120,28 @@ impl BrowserFetcher {
         self
     }
 
+    /// Send `ua` as the User-Agent of every page, e.g. a
+    /// [`UserAgent`](crate::UserAgent) preset.
+    ///
+    /// Chromium is already running once a fetcher exists, so rather than its
+    /// `--user-agent` flag, the override is set on each tab before it
+    /// navigates, which covers every clone sharing the browser.
+    pub fn with_user_agent(mut self, ua: impl Into<String>) -> Self {
+        self.user_agent = Some(Arc::from(ua.into()));
+        self
+    }
+
+    /// Evaluate `script` on every page after it renders, before the HTML is
+    /// captured.
+    ///
+    /// The script may be an expression or a function; a returned promise is
+    /// awaited. The JSON-serialised result is appended to the captured HTML
+    /// as `<script id="ares-injected-data">…</script>`.
+    pub fn with_pre_capture_script(mut self, script: impl Into<String>) -> Self {
+        self.pre_capture_script = Some(Arc::from(script.into()));
+        self
+    }
+
     /// Internal launcher shared by all constructors.
     async fn launch(timeout: Duration, proxy_url: Option<&str>) -> Result<Self, AppError> {
         let mut builder = BrowserConfig::builder();
@@ -149,6 +194,8 @@ impl BrowserFetcher {
             timeout,
             stealth: StealthConfig::default(),
             ua_pool: None,
+            user_agent: None,
+            pre_capture_script: None,
         })
     }
 
@@ -166,8 +213,11 @@ impl BrowserFetcher {
                 .map_err(map_err)?;
         }
 
-        // 2. User-Agent override (rotated per page)
-        let ua = if self.stealth.rotate_user_agent {
+        // 2. User-Agent override (fixed, or rotated per page)
+        let ua = if let Some(ua) = self.user_agent.as_deref() {
+            page.set_user_agent(ua).await.map_err(map_err)?;
+            Some(ua)
+        } else if self.stealth.rotate_user_agent {
             let ua = self.ua_pool.as_ref().map(|p| p.next()).unwrap_or(
                 "Mozilla/5.0 (Windows NT 10.0; Win64; x64) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/131.0.0.0 Safari/537.36",
             );
@@ -231,6 +281,53 @@ impl BrowserFetcher {
         Ok(())
     }
 
+    /// Wait for the page body (and `wait_selector`, if any), run the
+    /// pre-capture script (if any), and return the rendered HTML. Closes the
+    /// page either way.
+    async fn capture(&self, page: Page, wait_selector: Option<&str>) -> Result<String, AppError> {
+        let result = self.capture_inner(&page, wait_selector).await;
+        let _ = page.close().await;
+        result
+    }
+
+    async fn capture_inner(
+        &self,
+        page: &Page,
+        wait_selector: Option<&str>,
+    ) -> Result<String, AppError> {
+        page.find_element("body")
+            .await
+            .map_err(|e| AppError::HttpError(format!("Page did not render body: {e}")))?;
+
+        // Poll until the selector appears; the caller's timeout bounds the wait.
+        if let Some(selector) = wait_selector {
+            while page.find_element(selector).await.is_err() {
+                tokio::time::sleep(WAIT_SELECTOR_POLL_INTERVAL).await;
+            }
+        }
+
+        let injected = match &self.pre_capture_script {
+            Some(script) => {
+                let result = page
+                    .evaluate(script.as_ref())
+                    .await
+                    .map_err(|e| AppError::HttpError(format!("Pre-capture script failed: {e}")))?;
+                Some(result.value().cloned().unwrap_or(serde_json::Value::Null))
+            }
+            None => None,
+        };
+
+        let html = page
+            .content()
+            .await
+            .map_err(|e| AppError::HttpError(format!("Failed to read page content: {e}")))?;
+
+        Ok(match injected {
+            Some(value) => append_injected_data(html, &value),
+            None => html,
+        })
+    }
+
     /// Tries to locate the real Chrome/Chromium binary.
     ///
     /// On systems where Chromium is installed via **snap**, the wrapper at
@@ -263,9 +360,16 @@ impl BrowserFetcher {
     }
 }
 
-impl Fetcher for BrowserFetcher {
-    async fn fetch(&self, url: &str) -> Result<String, AppError> {
-        let timeout = self.timeout;
+impl BrowserFetcher {
+    /// Render `url` within `timeout`, optionally waiting for `wait_selector`
+    /// and sending extra `headers` with every request the page makes.
+    async fn render(
+        &self,
+        url: &str,
+        timeout: Duration,
+        wait_selector: Option<&str>,
+        headers: &BTreeMap<String, String>,
+    ) -> Result<String, AppError> {
         let has_stealth = self.stealth.hide_webdriver
             || self.stealth.rotate_user_agent
             || self.stealth.randomize_viewport
@@ -273,50 +377,97 @@ impl Fetcher for BrowserFetcher {
             || self.stealth.spoof_platform;
 
         let result = tokio::time::timeout(timeout, async {
-            if has_stealth {
-                // Open a blank tab, apply stealth injections, then navigate.
-                // This ensures AddScriptToEvaluateOnNewDocument hooks fire
-                // before any site JavaScript on the target page.
+            if has_stealth || self.user_agent.is_some() || !headers.is_empty() {
+                // Open a blank tab, apply stealth injections, the User-Agent
+                // and headers, then navigate. This ensures
+                // AddScriptToEvaluateOnNewDocument hooks fire before any site
+                // JavaScript on the target page.
                 let page =
                     self.browser.new_page("about:blank").await.map_err(|e| {
                         AppError::HttpError(format!("Failed to open blank page: {e}"))
                     })?;
-                self.apply_stealth(&page).await?;
+                if has_stealth {
+                    self.apply_stealth(&page).await?;
+                } else if let Some(ua) = self.user_agent.as_deref() {
+                    page.set_user_agent(ua).await.map_err(|e| {
+                        AppError::HttpError(format!("Failed to set User-Agent: {e}"))
+                    })?;
+                }
+                if !headers.is_empty() {
+                    let headers = serde_json::to_value(headers)?;
+                    page.execute(SetExtraHttpHeadersParams::new(Headers::new(headers)))
+                        .await
+                        .map_err(|e| {
+                            AppError::HttpError(format!("Failed to set request headers: {e}"))
+                        })?;
+                }
                 page.goto(url).await.map_err(|e| {
                     AppError::HttpError(format!("Failed to navigate to {url}: {e}"))
                 })?;
-
-                page.find_element("body")
-                    .await
-                    .map_err(|e| AppError::HttpError(format!("Page did not render body: {e}")))?;
-
-                let html = page.content().await.map_err(|e| {
-                    AppError::HttpError(format!("Failed to read page content: {e}"))
-                })?;
-                let _ = page.close().await;
-                Ok::<String, AppError>(html)
+                self.capture(page, wait_selector).await
             } else {
                 // No stealth — navigate directly.
                 let page = self.browser.new_page(url).await.map_err(|e| {
                     AppError::HttpError(format!("Failed to navigate to {url}: {e}"))
                 })?;
-
-                page.find_element("body")
-                    .await
-                    .map_err(|e| AppError::HttpError(format!("Page did not render body: {e}")))?;
-
-                let html = page.content().await.map_err(|e| {
-                    AppError::HttpError(format!("Failed to read page content: {e}"))
-                })?;
-                let _ = page.close().await;
-                Ok::<String, AppError>(html)
+                self.capture(page, wait_selector).await
             }
         })
         .await;
 
-        match result {
-            Ok(inner) => inner,
-            Err(_) => Err(AppError::Timeout(timeout.as_secs())),
+        let html = match result {
+            Ok(inner) => inner?,
+            Err(_) => return Err(AppError::Timeout(timeout.as_secs())),
+        };
+        // The page's JavaScript ran, yet it's still the challenge.
+        if let Some(reason) = anti_bot::challenge_page(&html) {
+            return Err(anti_bot::blocked(url, reason, anti_bot::BROWSER_FETCH_HINT));
         }
+        Ok(html)
+    }
+}
+
+impl Fetcher for BrowserFetcher {
+    async fn fetch(&self, url: &str) -> Result<String, AppError> {
+        self.render(url, self.timeout, None, &BTreeMap::new()).await
+    }
+
+    async fn fetch_with(&self, url: &str, options: &FetchOptions) -> Result<String, AppError> {
+        let timeout = options.fetch_timeout().unwrap_or(self.timeout);
+        self.render(
+            url,
+            timeout,
+            options.wait_selector.as_deref(),
+            &crate::fetcher::request_headers(options),
+        )
+        .await
+    }
+}
+
+/// Append `value` to `html` as a `<script id="ares-injected-data">` element.
+///
+/// `</` is escaped as `<\/` (equivalent in JSON) so string values cannot
+/// close the element early.
+fn append_injected_data(mut html: String, value: &serde_json::Value) -> String {
+    let json = value.to_string().replace("</", "<\\/");
+    html.push_str(&format!(
+        "<script id=\"{INJECTED_DATA_ID}\">{json}</script>"
+    ));
+    html
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_injected_data_is_appended_and_escaped() {
+        let value = serde_json::json!({"price": 42, "note": "</script><b>"});
+        let html = append_injected_data("<html><body>Hi</body></html>".to_string(), &value);
+
+        assert!(html.starts_with("<html><body>Hi</body></html><script id=\"ares-injected-data\""));
+        assert!(html.ends_with("</script>"));
+        assert_eq!(html.matches("</script>").count(), 1);
+        assert!(html.contains(r#""note":"<\/script><b>""#));
     }
 }
@@ -19,6 +19,7 @@ use serde::{Deserialize, Serialize};
 use tokenizers::Tokenizer;
 
 use ares_core::error::AppError;
+use ares_core::language::output_language_instruction;
 use ares_core::models::ExtractionOutcome;
 use ares_core::schema::validate_extracted_output;
 use ares_core::traits::{Extractor, ExtractorFactory};
@@ -343,8 +344,9 @@ impl CandleExtractor {
     ) -> Result<serde_json::Value, AppError> {
         let schema_text = serde_json::to_string_pretty(schema)?;
         let correction = correction.map(|message| format!("\n\nYour previous output was rejected: {message}. Return a corrected JSON object only.")).unwrap_or_default();
+        let language = output_language_instruction(schema).unwrap_or_default();
         let prompt = format!(
-            "<|im_start|>system\n{}<|im_end|>\n<|im_start|>user\nExtract data according to this JSON schema:\n```json\n{schema_text}\n```\n\nFrom the following web content:\n\n{content}{correction}<|im_end|>\n<|im_start|>assistant\n",
+            "<|im_start|>system\n{}<|im_end|>\n<|im_start|>user\nExtract data according to this JSON schema:\n```json\n{schema_text}\n```\n\nFrom the following web content:\n\n{content}{language}{correction}<|im_end|>\n<|im_start|>assistant\n",
             self.system_prompt,
         );
         let raw = self
@@ -379,6 +381,10 @@ impl Extractor for CandleExtractor {
         };
         Ok(ExtractionOutcome::new(value))
     }
+
+    fn fingerprint(&self) -> Option<String> {
+        Some(self.system_prompt.clone())
+    }
 }
 
 #[derive(Clone)]
@@ -5,6 +5,9 @@ use ares_core::traits::Cleaner;
 use htmd::HtmlToMarkdown;
 use scraper::{Html, Selector};
 
+/// Element ID of the `<script>` holding a browser pre-capture script's result.
+pub const INJECTED_DATA_ID: &str = "ares-injected-data";
+
 /// HTML-to-Markdown cleaner using htmd.
 ///
 /// Converts raw HTML into clean Markdown text, stripping non-content
@@ -16,6 +19,9 @@ use scraper::{Html, Selector};
 /// *hallucinate* plausible values rather than omit them. To prevent that, a
 /// small "Page metadata" block harvested from `<head>` is prepended to the
 /// Markdown so those fields are grounded in real input.
+///
+/// Data injected by a browser pre-capture script (a `<script>` element with
+/// ID [`INJECTED_DATA_ID`]) is likewise kept, as an "Injected data" block.
 pub struct HtmdCleaner {
     converter: Arc<HtmlToMarkdown>,
 }
@@ -48,21 +54,33 @@ impl Default for HtmdCleaner {
     }
 }
 
-impl Cleaner for HtmdCleaner {
-    fn clean(&self, html: &str) -> Result<String, AppError> {
+impl HtmdCleaner {
+    /// Convert `body_html` to Markdown and prepend the metadata and injected
+    /// data found in the full document.
+    fn render(&self, doc: &Html, body_html: &str) -> Result<String, AppError> {
         let body = self
             .converter
-            .convert(html)
+            .convert(body_html)
             .map_err(|e| AppError::CleanerError(e.to_string()))?;
 
-        let metadata = extract_metadata(html);
-        if metadata.is_empty() {
+        let metadata = extract_metadata(doc);
+        let injected = extract_injected_data(doc);
+        if metadata.is_empty() && injected.is_none() {
             return Ok(body);
         }
 
-        let mut out = String::from("## Page metadata\n");
-        for (label, value) in metadata {
-            out.push_str(&format!("- {label}: {value}\n"));
+        let mut out = String::new();
+        if !metadata.is_empty() {
+            out.push_str("## Page metadata\n");
+            for (label, value) in metadata {
+                out.push_str(&format!("- {label}: {value}\n"));
+            }
+        }
+        if let Some(data) = injected {
+            if !out.is_empty() {
+                out.push('\n');
+            }
+            out.push_str(&format!("## Injected data\n{data}\n"));
         }
         out.push_str("\n---\n\n");
         out.push_str(&body);
@@ -70,10 +88,35 @@ impl Cleaner for HtmdCleaner {
     }
 }
 
+impl Cleaner for HtmdCleaner {
+    fn clean(&self, html: &str) -> Result<String, AppError> {
+        self.render(&Html::parse_document(html), html)
+    }
+
+    /// Only the elements matching `selector` are converted; page metadata and
+    /// injected data still come from the whole document.
+    fn clean_within(&self, html: &str, selector: &str) -> Result<String, AppError> {
+        let parsed = parse_selector(selector)?;
+        let doc = Html::parse_document(html);
+        let fragments: Vec<String> = doc.select(&parsed).map(|el| el.html()).collect();
+        if fragments.is_empty() {
+            return Err(AppError::CleanerError(format!(
+                "content selector '{selector}' matched no elements"
+            )));
+        }
+        self.render(&doc, &fragments.join("\n"))
+    }
+}
+
+/// Parse a CSS selector, reporting an invalid one as [`AppError::InvalidInput`].
+pub fn parse_selector(selector: &str) -> Result<Selector, AppError> {
+    Selector::parse(selector)
+        .map_err(|e| AppError::InvalidInput(format!("Invalid CSS selector '{selector}': {e}")))
+}
+
 /// Harvest grounded metadata from `<head>` (and `<title>`) as `(label, value)`
 /// pairs. Only fields actually present are returned, first match wins.
-fn extract_metadata(html: &str) -> Vec<(&'static str, String)> {
-    let doc = Html::parse_document(html);
+fn extract_metadata(doc: &Html) -> Vec<(&'static str, String)> {
     let mut out = Vec::new();
 
     // Each entry: label → ordered list of (css selector, attribute) sources.
@@ -125,13 +168,22 @@ fn extract_metadata(html: &str) -> Vec<(&'static str, String)> {
     ];
 
     for (label, sources) in fields {
-        if let Some(value) = first_value(&doc, sources) {
+        if let Some(value) = first_value(doc, sources) {
             out.push((*label, value));
         }
     }
     out
 }
 
+/// Text of the injected-data `<script>` appended by a browser pre-capture
+/// script, if present and non-empty.
+fn extract_injected_data(doc: &Html) -> Option<String> {
+    let sel = Selector::parse(&format!("script#{INJECTED_DATA_ID}")).ok()?;
+    let text = doc.select(&sel).next()?.text().collect::<String>();
+    let trimmed = text.trim();
+    (!trimmed.is_empty()).then(|| trimmed.to_string())
+}
+
 /// Return the first non-empty value across the given `(selector, attr)` sources.
 fn first_value(doc: &Html, sources: &[(&str, &str)]) -> Option<String> {
     for (selector, attr) in sources {
@@ -262,6 +314,21 @@ mod tests {
         assert!(meta_idx < body_idx);
     }
 
+    #[test]
+    fn test_keeps_injected_data() {
+        let cleaner = HtmdCleaner::new();
+        let html = concat!(
+            "<html><body><p>Body text</p>",
+            "<script>var tracking = 1;</script>",
+            "<script id=\"ares-injected-data\">{\"price\":42}</script>",
+            "</body></html>",
+        );
+        let md = cleaner.clean(html).unwrap();
+        assert!(md.starts_with("## Injected data\n{\"price\":42}\n"));
+        assert!(md.contains("Body text"));
+        assert!(!md.contains("tracking"));
+    }
+
     #[test]
     fn test_og_fallbacks_when_no_canonical() {
         let cleaner = HtmdCleaner::new();
@@ -275,4 +342,26 @@ mod tests {
         assert!(md.contains("URL: https://ex.com/p"));
         assert!(md.contains("Title: OG Title"));
     }
+
+    #[test]
+    fn test_clean_within_keeps_only_selected_content() {
+        let cleaner = HtmdCleaner::new();
+        let html = concat!(
+            "<html><head><title>Page</title></head><body>",
+            "<div class=\"promo\">Buy now</div>",
+            "<article><p>First</p></article><article><p>Second</p></article>",
+            "</body></html>",
+        );
+        let md = cleaner.clean_within(html, "article").unwrap();
+        assert!(md.contains("Title: Page"));
+        assert!(md.contains("First") && md.contains("Second"));
+        assert!(!md.contains("Buy now"));
+
+        let err = cleaner.clean_within(html, "main").unwrap_err();
+        assert!(err.to_string().contains("matched no elements"));
+        assert!(matches!(
+            cleaner.clean_within(html, "[[").unwrap_err(),
+            AppError::InvalidInput(_)
+        ));
+    }
 }
@@ -0,0 +1,238 @@
+//! Routing of fetched responses by content type.
+//!
+//! Fetchers hand the cleaner a string, so non-HTML documents are converted
+//! here, at fetch time: PDFs become HTML through [`PdfCleaner`] (with the
+//! `pdf` feature), text passes through unchanged, and media types that
+//! can't hold extractable text are rejected instead of being fed to the
+//! LLM as garbage.
+//!
+//! [`PdfCleaner`]: crate::pdf::PdfCleaner
+
+use ares_core::error::AppError;
+use encoding_rs::{Encoding, UTF_8};
+use reqwest::Response;
+use reqwest::header::CONTENT_TYPE;
+
+/// Leading bytes of every PDF file.
+const PDF_MAGIC: &[u8] = b"%PDF-";
+
+/// Cap on binary (PDF or untyped) downloads.
+const MAX_DOCUMENT_BYTES: usize = 20 * 1024 * 1024;
+
+/// How a response body is turned into cleaner input.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum BodyKind {
+    /// HTML or other text, decoded with the declared charset.
+    Text,
+    /// Declared as a PDF.
+    Pdf,
+    /// Untyped binary or a `.pdf` URL: a PDF if the bytes say so, text
+    /// otherwise.
+    Sniff,
+    /// Images, audio, video, and fonts.
+    Unsupported(String),
+}
+
+fn body_kind(url: &str, content_type: Option<&str>) -> BodyKind {
+    let mime = content_type
+        .and_then(|value| value.split(';').next())
+        .map(|mime| mime.trim().to_ascii_lowercase())
+        .unwrap_or_default();
+    let pdf_url =
+        url::Url::parse(url).is_ok_and(|url| url.path().to_ascii_lowercase().ends_with(".pdf"));
+
+    match mime.as_str() {
+        "application/pdf" | "application/x-pdf" => BodyKind::Pdf,
+        "application/octet-stream" | "binary/octet-stream" => BodyKind::Sniff,
+        _ if ["image/", "audio/", "video/", "font/"]
+            .iter()
+            .any(|prefix| mime.starts_with(prefix)) =>
+        {
+            BodyKind::Unsupported(mime)
+        }
+        _ if pdf_url => BodyKind::Sniff,
+        _ => BodyKind::Text,
+    }
+}
+
+/// The body of a successful `response` for `url`, as input for the cleaner.
+///
+/// With `max_bytes`, a body over that size fails with an
+/// [`AppError::HttpError`] instead of being read into memory.
+pub(crate) async fn read_body(
+    url: &str,
+    response: Response,
+    max_bytes: Option<usize>,
+) -> Result<String, AppError> {
+    let content_type = response
+        .headers()
+        .get(CONTENT_TYPE)
+        .and_then(|value| value.to_str().ok())
+        .map(str::to_string);
+
+    match body_kind(url, content_type.as_deref()) {
+        BodyKind::Text => match max_bytes {
+            Some(max_bytes) => {
+                let bytes = read_limited(response, max_bytes, response_too_large).await?;
+                Ok(decode_text(content_type.as_deref(), &bytes))
+            }
+            None => response.text().await.map_err(read_error),
+        },
+        BodyKind::Unsupported(mime) => Err(AppError::CleanerError(format!(
+            "Unsupported content type {mime} for {url}"
+        ))),
+        kind => {
+            let bytes = read_bytes(url, response, max_bytes).await?;
+            if kind == BodyKind::Pdf || bytes.starts_with(PDF_MAGIC) {
+                pdf_to_html(url, bytes).await
+            } else {
+                Ok(String::from_utf8_lossy(&bytes).into_owned())
+            }
+        }
+    }
+}
+
+/// Read a binary body, refusing anything over [`MAX_DOCUMENT_BYTES`] or a
+/// smaller `max_bytes`.
+async fn read_bytes(
+    url: &str,
+    response: Response,
+    max_bytes: Option<usize>,
+) -> Result<Vec<u8>, AppError> {
+    match max_bytes {
+        Some(max_bytes) if max_bytes < MAX_DOCUMENT_BYTES => {
+            read_limited(response, max_bytes, response_too_large).await
+        }
+        _ => {
+            read_limited(response, MAX_DOCUMENT_BYTES, |_| {
+                AppError::CleanerError(format!(
+                    "Document at {url} is over the {MAX_DOCUMENT_BYTES} byte limit"
+                ))
+            })
+            .await
+        }
+    }
+}
+
+/// Read a body of at most `limit` bytes. A larger `Content-Length` fails
+/// before anything is read; an undeclared length fails as soon as the
+/// streamed body passes the limit. `too_large` gets the size seen.
+async fn read_limited(
+    mut response: Response,
+    limit: usize,
+    too_large: impl Fn(u64) -> AppError,
+) -> Result<Vec<u8>, AppError> {
+    if let Some(length) = response
+        .content_length()
+        .filter(|&length| length > limit as u64)
+    {
+        return Err(too_large(length));
+    }
+    let mut body = Vec::new();
+    while let Some(chunk) = response.chunk().await.map_err(read_error)? {
+        if body.len() + chunk.len() > limit {
+            return Err(too_large((body.len() + chunk.len()) as u64));
+        }
+        body.extend_from_slice(&chunk);
+    }
+    Ok(body)
+}
+
+fn response_too_large(bytes: u64) -> AppError {
+    AppError::HttpError(format!("Response too large: {bytes} bytes"))
+}
+
+/// Decode text with the `charset` of `content_type`, as
+/// [`Response::text`] does, defaulting to UTF-8.
+fn decode_text(content_type: Option<&str>, bytes: &[u8]) -> String {
+    let encoding = content_type
+        .and_then(|value| {
+            value.split(';').skip(1).find_map(|param| {
+                let (name, label) = param.split_once('=')?;
+                name.trim()
+                    .eq_ignore_ascii_case("charset")
+                    .then(|| label.trim().trim_matches('"'))
+            })
+        })
+        .and_then(|label| Encoding::for_label(label.as_bytes()))
+        .unwrap_or(UTF_8);
+    encoding.decode(bytes).0.into_owned()
+}
+
+#[cfg(feature = "pdf")]
+async fn pdf_to_html(url: &str, bytes: Vec<u8>) -> Result<String, AppError> {
+    let text = tokio::task::spawn_blocking(move || crate::pdf::PdfCleaner::new().extract(&bytes))
+        .await
+        .map_err(|e| AppError::CleanerError(format!("PDF extraction failed: {e}")))??;
+    for warning in &text.warnings {
+        tracing::warn!(url, "PDF extraction: {warning}");
+    }
+    Ok(text.to_html())
+}
+
+#[cfg(not(feature = "pdf"))]
+async fn pdf_to_html(url: &str, _bytes: Vec<u8>) -> Result<String, AppError> {
+    Err(AppError::CleanerError(format!(
+        "{url} is a PDF. {}",
+        crate::PDF_FEATURE_MSG
+    )))
+}
+
+fn read_error(e: reqwest::Error) -> AppError {
+    AppError::HttpError(format!("Failed to read response body: {e}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn routes_by_content_type_and_extension() {
+        let kind = |url: &str, content_type: Option<&str>| body_kind(url, content_type);
+
+        assert_eq!(
+            kind("https://a.example/", Some("text/html")),
+            BodyKind::Text
+        );
+        assert_eq!(kind("https://a.example/", None), BodyKind::Text);
+        assert_eq!(
+            kind(
+                "https://a.example/notes.txt",
+                Some("text/plain; charset=utf-8")
+            ),
+            BodyKind::Text
+        );
+        assert_eq!(
+            kind("https://a.example/doc", Some("Application/PDF")),
+            BodyKind::Pdf
+        );
+        assert_eq!(
+            kind("https://a.example/prices.PDF?v=2", Some("text/html")),
+            BodyKind::Sniff
+        );
+        assert_eq!(
+            kind(
+                "https://a.example/download",
+                Some("application/octet-stream")
+            ),
+            BodyKind::Sniff
+        );
+        assert_eq!(
+            kind("https://a.example/logo.png", Some("image/png")),
+            BodyKind::Unsupported("image/png".into())
+        );
+    }
+
+    #[test]
+    fn decodes_text_with_the_declared_charset() {
+        assert_eq!(decode_text(None, "café".as_bytes()), "café");
+        assert_eq!(
+            decode_text(Some("text/html; Charset=\"ISO-8859-1\""), b"caf\xe9"),
+            "café"
+        );
+        assert_eq!(
+            decode_text(Some("text/html; charset=bogus"), "café".as_bytes()),
+            "café"
+        );
+    }
+}
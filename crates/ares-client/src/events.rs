@@ -0,0 +1,149 @@
+//! Extraction events for a message bus.
+//!
+//! [`EventPublisher`] is the [`ExtractionEventPublisher`] the CLI and API
+//! hand to the scrape service. Given a NATS URL (`ARES_NATS_URL`, with the
+//! `events-nats` feature) it publishes each saved extraction to JetStream
+//! through [`NatsPublisher`]; otherwise it does nothing. Events that still
+//! fail after the publisher's short retry are counted and dropped.
+//!
+//! [`NatsPublisher`]: crate::nats::NatsPublisher
+
+use std::sync::Arc;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+use ares_core::error::AppError;
+use ares_core::models::ExtractionEvent;
+use ares_core::traits::ExtractionEventPublisher;
+
+/// Subjects are `<prefix>.<schema_name>`.
+pub const DEFAULT_SUBJECT_PREFIX: &str = "ares.extractions";
+
+/// The subject for events of `schema_name`. Characters NATS reserves for
+/// token separators and wildcards (and anything else outside
+/// `[A-Za-z0-9_-]`) become `_`, so a schema is always a single token.
+pub fn subject(prefix: &str, schema_name: &str) -> String {
+    let token: String = schema_name
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || c == '_' || c == '-' {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect();
+    if token.is_empty() {
+        format!("{prefix}._")
+    } else {
+        format!("{prefix}.{token}")
+    }
+}
+
+/// The configured event bus, if any, plus a count of dropped events.
+#[derive(Clone, Default)]
+pub struct EventPublisher {
+    #[cfg(feature = "events-nats")]
+    nats: Option<crate::nats::NatsPublisher>,
+    dropped: Arc<AtomicU64>,
+}
+
+impl EventPublisher {
+    /// A publisher for `nats_url`, or a no-op one when it is `None`.
+    ///
+    /// Fails when a URL is given but the `events-nats` feature is off.
+    pub fn new(nats_url: Option<&str>) -> Result<Self, AppError> {
+        match nats_url {
+            None => Ok(Self::default()),
+            #[cfg(feature = "events-nats")]
+            Some(url) => Ok(Self {
+                nats: Some(crate::nats::NatsPublisher::new(url)),
+                dropped: Arc::default(),
+            }),
+            #[cfg(not(feature = "events-nats"))]
+            Some(_) => Err(AppError::ConfigError(
+                crate::EVENTS_NATS_FEATURE_MSG.to_string(),
+            )),
+        }
+    }
+
+    /// Whether events go anywhere.
+    #[cfg(feature = "events-nats")]
+    pub fn is_enabled(&self) -> bool {
+        self.nats.is_some()
+    }
+
+    /// Whether events go anywhere.
+    #[cfg(not(feature = "events-nats"))]
+    pub fn is_enabled(&self) -> bool {
+        false
+    }
+
+    /// Events that could not be published since this publisher (or any
+    /// clone of it) was created.
+    pub fn dropped_events(&self) -> u64 {
+        self.dropped.load(Ordering::Relaxed)
+    }
+}
+
+impl ExtractionEventPublisher for EventPublisher {
+    async fn publish(&self, event: &ExtractionEvent) -> Result<(), AppError> {
+        #[cfg(feature = "events-nats")]
+        if let Some(nats) = &self.nats {
+            let result = nats.publish(event).await;
+            if result.is_err() {
+                self.dropped.fetch_add(1, Ordering::Relaxed);
+            }
+            return result;
+        }
+        #[cfg(not(feature = "events-nats"))]
+        let _ = event;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn schema_names_become_a_single_subject_token() {
+        assert_eq!(
+            subject(DEFAULT_SUBJECT_PREFIX, "pricing"),
+            "ares.extractions.pricing"
+        );
+        assert_eq!(subject("bus", "blog.posts v2/*>"), "bus.blog_posts_v2___");
+        assert_eq!(subject("bus", "job-board_1"), "bus.job-board_1");
+        assert_eq!(subject("bus", ""), "bus._");
+    }
+
+    #[test]
+    fn no_url_means_no_op() {
+        let publisher = EventPublisher::new(None).unwrap();
+        assert!(!publisher.is_enabled());
+        assert_eq!(publisher.dropped_events(), 0);
+    }
+
+    #[cfg(not(feature = "events-nats"))]
+    #[test]
+    fn url_without_the_feature_is_a_config_error() {
+        let err = EventPublisher::new(Some("nats://localhost:4222")).unwrap_err();
+        assert!(matches!(err, AppError::ConfigError(_)), "{err}");
+    }
+
+    #[cfg(feature = "events-nats")]
+    #[tokio::test]
+    async fn failed_publishes_are_counted() {
+        let publisher = EventPublisher::new(Some("nats://127.0.0.1:1")).unwrap();
+        assert!(publisher.is_enabled());
+        let event = ExtractionEvent {
+            extraction_id: uuid::Uuid::new_v4(),
+            previous_extraction_id: None,
+            extraction: Default::default(),
+            changed: true,
+            changes: Vec::new(),
+            saved_at: chrono::Utc::now(),
+        };
+        assert!(publisher.publish(&event).await.is_err());
+        assert_eq!(publisher.clone().dropped_events(), 1);
+    }
+}
@@ -1,15 +1,24 @@
+use std::collections::BTreeMap;
 use std::net::IpAddr;
 use std::sync::Arc;
 use std::time::Duration;
 
 use ares_core::error::AppError;
+use ares_core::fetch::{FetchAuth, FetchOptions};
 use ares_core::proxy::{ProxyConfig, TlsBackend};
 use ares_core::traits::Fetcher;
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD as BASE64;
 use reqwest::Client;
 use url::Url;
 
+use crate::anti_bot;
 use crate::user_agent::UserAgentPool;
 
+/// Content types accepted by a filter meant to admit only web pages; see
+/// [`ReqwestFetcher::with_content_type_filter`].
+pub const HTML_CONTENT_TYPES: [&str; 2] = ["text/html", "application/xhtml+xml"];
+
 /// HTTP fetcher using reqwest.
 ///
 /// Downloads raw HTML from URLs with configurable User-Agent and timeout.
@@ -30,9 +39,15 @@ pub struct ReqwestFetcher {
     proxy_clients: Option<Arc<ProxyClients>>,
     /// If set, override the default User-Agent with a random one per request.
     ua_pool: Option<UserAgentPool>,
+    /// Fixed User-Agent sent instead of the default, unless rotation is on.
+    user_agent: Option<String>,
     /// TLS backend — stored so `with_proxies` can build per-proxy clients
     /// with the same backend.
     tls_backend: TlsBackend,
+    /// Lowercase content-type prefixes a response must match, if filtered.
+    allowed_content_types: Option<Arc<[String]>>,
+    /// Largest response body read, in bytes.
+    max_response_size: Option<usize>,
 }
 
 /// Holds the proxy pool and pre-built reqwest clients for each proxy.
@@ -56,7 +71,10 @@ impl ReqwestFetcher {
             ssrf_protection: true,
             proxy_clients: None,
             ua_pool: None,
+            user_agent: None,
             tls_backend,
+            allowed_content_types: None,
+            max_response_size: None,
         })
     }
 
@@ -92,6 +110,40 @@ impl ReqwestFetcher {
         self
     }
 
+    /// Send `user_agent` instead of the default User-Agent. Ignored when
+    /// [`with_random_ua`](Self::with_random_ua) is enabled.
+    pub fn with_user_agent(mut self, user_agent: impl Into<String>) -> Self {
+        self.user_agent = Some(user_agent.into());
+        self
+    }
+
+    /// Reject responses whose `Content-Type` doesn't start with one of
+    /// `allowed` (e.g. [`HTML_CONTENT_TYPES`]), compared case-insensitively
+    /// and ignoring parameters such as `charset`. Responses without the
+    /// header still go through content sniffing.
+    ///
+    /// Without a filter, PDFs and text are accepted and converted as usual.
+    pub fn with_content_type_filter(
+        mut self,
+        allowed: impl IntoIterator<Item = impl Into<String>>,
+    ) -> Self {
+        self.allowed_content_types = Some(
+            allowed
+                .into_iter()
+                .map(|prefix| prefix.into().trim().to_ascii_lowercase())
+                .collect(),
+        );
+        self
+    }
+
+    /// Fail with "Response too large" instead of reading a body over
+    /// `bytes`: up front when `Content-Length` says so, or as soon as a
+    /// streamed body passes the limit.
+    pub fn with_max_response_size(mut self, bytes: usize) -> Self {
+        self.max_response_size = Some(bytes);
+        self
+    }
+
     /// Disable SSRF protection, allowing requests to private/reserved IPs.
     ///
     /// Only use this for CLI usage where the user controls the machine.
@@ -146,8 +198,15 @@ fn build_client(
         .map_err(|e| AppError::HttpError(e.to_string()))
 }
 
-impl Fetcher for ReqwestFetcher {
-    async fn fetch(&self, url: &str) -> Result<String, AppError> {
+impl ReqwestFetcher {
+    /// Fetch `url`, overriding the client timeout when `timeout` is set and
+    /// sending `headers` on top of the client's own.
+    async fn get(
+        &self,
+        url: &str,
+        timeout: Option<Duration>,
+        headers: &BTreeMap<String, String>,
+    ) -> Result<String, AppError> {
         if self.ssrf_protection {
             validate_url(url).await?;
         }
@@ -155,13 +214,19 @@ impl Fetcher for ReqwestFetcher {
         let (client, ua_override) = self.next_client();
 
         let mut request = client.get(url);
-        if let Some(ua) = ua_override {
+        if let Some(timeout) = timeout {
+            request = request.timeout(timeout);
+        }
+        if let Some(ua) = ua_override.or(self.user_agent.as_deref()) {
             request = request.header(reqwest::header::USER_AGENT, ua);
         }
+        for (name, value) in headers {
+            request = request.header(name.as_str(), value.as_str());
+        }
 
         let response = request.send().await.map_err(|e| {
             if e.is_timeout() {
-                AppError::Timeout(self.timeout.as_secs())
+                AppError::Timeout(timeout.unwrap_or(self.timeout).as_secs())
             } else if e.is_connect() {
                 AppError::NetworkError(format!("Connection failed: {e}"))
             } else {
@@ -171,20 +236,89 @@ impl Fetcher for ReqwestFetcher {
 
         let status = response.status();
         if !status.is_success() {
+            let headers = response.headers().clone();
+            let body = response.text().await.unwrap_or_default();
+            if let Some(reason) = anti_bot::blocked_response(status, &headers, &body) {
+                return Err(anti_bot::blocked(url, reason, anti_bot::PLAIN_FETCH_HINT));
+            }
             return Err(AppError::HttpError(format!(
                 "HTTP {} for {}",
                 status.as_u16(),
                 url
             )));
         }
+        self.check_content_type(&response)?;
 
-        response
-            .text()
+        let html = crate::document::read_body(url, response, self.max_response_size).await?;
+        // Some challenges are served with 200 OK.
+        if let Some(reason) = anti_bot::challenge_page(&html) {
+            return Err(anti_bot::blocked(url, reason, anti_bot::PLAIN_FETCH_HINT));
+        }
+        Ok(html)
+    }
+
+    fn check_content_type(&self, response: &reqwest::Response) -> Result<(), AppError> {
+        let Some(allowed) = &self.allowed_content_types else {
+            return Ok(());
+        };
+        let Some(content_type) = response
+            .headers()
+            .get(reqwest::header::CONTENT_TYPE)
+            .and_then(|value| value.to_str().ok())
+        else {
+            return Ok(());
+        };
+        let mime = content_type
+            .split(';')
+            .next()
+            .unwrap_or_default()
+            .trim()
+            .to_ascii_lowercase();
+        if allowed
+            .iter()
+            .any(|prefix| mime.starts_with(prefix.as_str()))
+        {
+            Ok(())
+        } else {
+            Err(AppError::HttpError(format!(
+                "Unexpected content type: {mime}"
+            )))
+        }
+    }
+}
+
+/// A plain HTTP fetch can't render or wait for selectors; only the timeout
+/// override applies.
+impl Fetcher for ReqwestFetcher {
+    async fn fetch(&self, url: &str) -> Result<String, AppError> {
+        self.get(url, None, &BTreeMap::new()).await
+    }
+
+    async fn fetch_with(&self, url: &str, options: &FetchOptions) -> Result<String, AppError> {
+        self.get(url, options.fetch_timeout(), &request_headers(options))
             .await
-            .map_err(|e| AppError::HttpError(format!("Failed to read response body: {e}")))
     }
 }
 
+/// The extra headers for a page fetch: `options.headers` plus the
+/// credentials in `options.auth`, which replace a same-named header.
+pub(crate) fn request_headers(options: &FetchOptions) -> BTreeMap<String, String> {
+    let mut headers = options.headers.clone();
+    if let Some(auth) = &options.auth {
+        let (name, value) = match auth {
+            FetchAuth::Basic { user, pass } => (
+                "Authorization".to_string(),
+                format!("Basic {}", BASE64.encode(format!("{user}:{pass}"))),
+            ),
+            FetchAuth::Bearer { token } => ("Authorization".to_string(), format!("Bearer {token}")),
+            FetchAuth::Header { name, value } => (name.clone(), value.clone()),
+        };
+        headers.retain(|existing, _| !existing.eq_ignore_ascii_case(&name));
+        headers.insert(name, value);
+    }
+    headers
+}
+
 // ---------------------------------------------------------------------------
 // SSRF protection
 // ---------------------------------------------------------------------------
@@ -194,7 +328,7 @@ impl Fetcher for ReqwestFetcher {
 /// 1. Only allow `http` and `https` schemes.
 /// 2. Resolve the hostname via DNS.
 /// 3. Reject if any resolved IP is private/reserved.
-async fn validate_url(url: &str) -> Result<(), AppError> {
+pub(crate) async fn validate_url(url: &str) -> Result<(), AppError> {
     let parsed = Url::parse(url).map_err(|e| AppError::HttpError(format!("Invalid URL: {e}")))?;
 
     // 1. Scheme check
@@ -377,4 +511,258 @@ mod tests {
         let result = validate_url("https://example.com").await;
         assert!(result.is_ok());
     }
+
+    #[tokio::test]
+    async fn fetcher_sends_configured_user_agent() {
+        use axum::http::HeaderMap;
+        use axum::http::header::USER_AGENT;
+
+        let router = axum::Router::new().route(
+            "/",
+            axum::routing::get(|headers: HeaderMap| async move {
+                headers[USER_AGENT].to_str().unwrap().to_string()
+            }),
+        );
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}/", listener.local_addr().unwrap());
+        tokio::spawn(async move { axum::serve(listener, router).await.unwrap() });
+
+        let fetcher = ReqwestFetcher::new().unwrap().allow_private_urls();
+        assert_eq!(fetcher.fetch(&url).await.unwrap(), "Ares/0.2 (AI Scraper)");
+        let fetcher = fetcher.with_user_agent("my-crawler/1.0");
+        assert_eq!(fetcher.fetch(&url).await.unwrap(), "my-crawler/1.0");
+    }
+
+    #[tokio::test]
+    async fn fetcher_sends_auth_credentials() {
+        use axum::http::HeaderMap;
+
+        let router = axum::Router::new().route(
+            "/",
+            axum::routing::get(|headers: HeaderMap| async move {
+                let value = |name: &str| {
+                    headers
+                        .get_all(name)
+                        .iter()
+                        .map(|v| v.to_str().unwrap().to_string())
+                        .collect::<Vec<_>>()
+                        .join(",")
+                };
+                format!("{}|{}", value("authorization"), value("x-api-key"))
+            }),
+        );
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}/", listener.local_addr().unwrap());
+        tokio::spawn(async move { axum::serve(listener, router).await.unwrap() });
+
+        let fetcher = ReqwestFetcher::new().unwrap().allow_private_urls();
+        let fetch = |auth: FetchAuth| {
+            let options = FetchOptions {
+                headers: [("authorization".to_string(), "Bearer stale".to_string())].into(),
+                auth: Some(auth),
+                ..FetchOptions::default()
+            };
+            let (fetcher, url) = (fetcher.clone(), url.clone());
+            async move { fetcher.fetch_with(&url, &options).await.unwrap() }
+        };
+
+        let basic = fetch(FetchAuth::Basic {
+            user: "Aladdin".into(),
+            pass: "open sesame".into(),
+        })
+        .await;
+        assert_eq!(basic, "Basic QWxhZGRpbjpvcGVuIHNlc2FtZQ==|");
+        let bearer = fetch(FetchAuth::Bearer {
+            token: "t0ken".into(),
+        })
+        .await;
+        assert_eq!(bearer, "Bearer t0ken|");
+        let header = fetch(FetchAuth::Header {
+            name: "X-Api-Key".into(),
+            value: "k3y".into(),
+        })
+        .await;
+        assert_eq!(header, "Bearer stale|k3y");
+    }
+
+    #[tokio::test]
+    async fn fetcher_routes_documents_by_content_type() {
+        use axum::http::header::CONTENT_TYPE;
+
+        const PRICE_LIST: &[u8] = include_bytes!("../tests/fixtures/documents/price-list.pdf");
+        let router = axum::Router::new()
+            .route(
+                "/prices",
+                axum::routing::get(|| async { ([(CONTENT_TYPE, "application/pdf")], PRICE_LIST) }),
+            )
+            .route(
+                "/download.pdf",
+                axum::routing::get(|| async {
+                    ([(CONTENT_TYPE, "application/octet-stream")], PRICE_LIST)
+                }),
+            )
+            .route(
+                "/logo.png",
+                axum::routing::get(|| async { ([(CONTENT_TYPE, "image/png")], &b"\x89PNG"[..]) }),
+            );
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let base = format!("http://{}", listener.local_addr().unwrap());
+        tokio::spawn(async move { axum::serve(listener, router).await.unwrap() });
+
+        let fetcher = ReqwestFetcher::new().unwrap().allow_private_urls();
+        for path in ["/prices", "/download.pdf"] {
+            let result = fetcher.fetch(&format!("{base}{path}")).await;
+            #[cfg(feature = "pdf")]
+            {
+                let html = result.unwrap();
+                assert!(html.contains("<h2>Page 1</h2>"), "{html}");
+                assert!(html.contains("Acme price list 2026"), "{html}");
+            }
+            #[cfg(not(feature = "pdf"))]
+            {
+                let err = result.unwrap_err();
+                assert!(err.to_string().contains("`pdf` feature"), "{err}");
+            }
+        }
+
+        let err = fetcher
+            .fetch(&format!("{base}/logo.png"))
+            .await
+            .unwrap_err();
+        assert!(matches!(err, AppError::CleanerError(_)), "{err}");
+        assert!(
+            err.to_string()
+                .contains("Unsupported content type image/png"),
+            "{err}"
+        );
+    }
+
+    #[tokio::test]
+    async fn content_type_filter_rejects_other_documents() {
+        use axum::http::header::CONTENT_TYPE;
+
+        let router = axum::Router::new()
+            .route(
+                "/page",
+                axum::routing::get(|| async {
+                    (
+                        [(CONTENT_TYPE, "Text/HTML; charset=ISO-8859-1")],
+                        "<p>hi</p>",
+                    )
+                }),
+            )
+            .route(
+                "/archive.zip",
+                axum::routing::get(|| async { ([(CONTENT_TYPE, "application/zip")], "PK") }),
+            );
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let base = format!("http://{}", listener.local_addr().unwrap());
+        tokio::spawn(async move { axum::serve(listener, router).await.unwrap() });
+
+        let fetcher = ReqwestFetcher::new()
+            .unwrap()
+            .allow_private_urls()
+            .with_content_type_filter(HTML_CONTENT_TYPES);
+        assert_eq!(
+            fetcher.fetch(&format!("{base}/page")).await.unwrap(),
+            "<p>hi</p>"
+        );
+
+        let err = fetcher
+            .fetch(&format!("{base}/archive.zip"))
+            .await
+            .unwrap_err();
+        assert!(matches!(err, AppError::HttpError(_)), "{err}");
+        assert!(
+            err.to_string()
+                .contains("Unexpected content type: application/zip"),
+            "{err}"
+        );
+        assert!(!err.is_retryable());
+    }
+
+    #[tokio::test]
+    async fn anti_bot_challenges_fail_as_blocked() {
+        use axum::http::StatusCode;
+
+        const CHALLENGE: &str = include_str!("../tests/fixtures/blocked/cloudflare-challenge.html");
+        let router = axum::Router::new()
+            .route(
+                "/challenge",
+                axum::routing::get(|| async {
+                    (
+                        StatusCode::SERVICE_UNAVAILABLE,
+                        [("cf-ray", "8c1f0a2b3d4e5f60-AMS")],
+                        CHALLENGE,
+                    )
+                }),
+            )
+            .route("/served-ok", axum::routing::get(|| async { CHALLENGE }))
+            .route(
+                "/forbidden",
+                axum::routing::get(|| async { (StatusCode::FORBIDDEN, "Forbidden") }),
+            );
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let base = format!("http://{}", listener.local_addr().unwrap());
+        tokio::spawn(async move { axum::serve(listener, router).await.unwrap() });
+
+        let fetcher = ReqwestFetcher::new().unwrap().allow_private_urls();
+        for path in ["challenge", "served-ok"] {
+            let err = fetcher.fetch(&format!("{base}/{path}")).await.unwrap_err();
+            let AppError::Blocked { reason, hint, .. } = &err else {
+                panic!("{path}: expected Blocked, got {err}");
+            };
+            assert!(
+                reason.starts_with("Cloudflare JS challenge"),
+                "{path}: {reason}"
+            );
+            assert_eq!(hint, anti_bot::PLAIN_FETCH_HINT);
+            assert!(!err.is_retryable());
+        }
+
+        let err = fetcher
+            .fetch(&format!("{base}/forbidden"))
+            .await
+            .unwrap_err();
+        assert!(matches!(err, AppError::HttpError(_)), "{err}");
+    }
+
+    #[tokio::test]
+    async fn max_response_size_rejects_large_bodies() {
+        let router = axum::Router::new()
+            .route("/small", axum::routing::get(|| async { "x".repeat(100) }))
+            .route(
+                "/declared",
+                axum::routing::get(|| async { "x".repeat(1_000) }),
+            )
+            .route(
+                "/streamed",
+                axum::routing::get(|| async {
+                    let chunks = (0..4).map(|_| Ok::<_, std::io::Error>("x".repeat(300)));
+                    axum::body::Body::from_stream(futures::stream::iter(chunks))
+                }),
+            );
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let base = format!("http://{}", listener.local_addr().unwrap());
+        tokio::spawn(async move { axum::serve(listener, router).await.unwrap() });
+
+        let fetcher = ReqwestFetcher::new()
+            .unwrap()
+            .allow_private_urls()
+            .with_max_response_size(500);
+        assert_eq!(
+            fetcher.fetch(&format!("{base}/small")).await.unwrap().len(),
+            100
+        );
+
+        for path in ["declared", "streamed"] {
+            let err = fetcher.fetch(&format!("{base}/{path}")).await.unwrap_err();
+            assert!(matches!(err, AppError::HttpError(_)), "{path}: {err}");
+            assert!(
+                err.to_string().contains("Response too large"),
+                "{path}: {err}"
+            );
+            assert!(!err.is_retryable());
+        }
+    }
 }
@@ -1,11 +1,19 @@
 //! HTTP clients and adapters — fetchers, HTML cleaner, and LLM extractor.
 
+mod anti_bot;
+pub mod api;
 pub mod cleaner;
+mod document;
+pub mod events;
 pub mod fetcher;
 pub mod link_discovery;
 pub mod llm;
+pub mod model_pricing;
+pub mod notify;
 pub mod provider;
 pub mod robots;
+pub mod sitemap;
+pub mod snapshot;
 pub mod user_agent;
 pub(crate) mod util;
 
@@ -18,13 +26,27 @@ pub mod anthropic;
 #[cfg(feature = "browser")]
 pub mod browser_fetcher;
 
+#[cfg(feature = "pdf")]
+pub mod pdf;
+
+#[cfg(feature = "events-nats")]
+pub mod nats;
+
+#[cfg(feature = "s3")]
+pub mod s3;
+
+pub use api::AresApiClient;
 pub use cleaner::HtmdCleaner;
+pub use events::EventPublisher;
 pub use fetcher::ReqwestFetcher;
 pub use link_discovery::HtmlLinkDiscoverer;
 pub use llm::{OpenAiExtractor, OpenAiExtractorFactory};
+pub use notify::{Notifiers, SlackNotifier, WebhookNotifier};
 pub use provider::{Provider, ProviderExtractor, ProviderExtractorFactory};
 pub use robots::CachedRobotsChecker;
-pub use user_agent::UserAgentPool;
+pub use sitemap::{SitemapEntry, SitemapLoader, SitemapOptions};
+pub use snapshot::{FsSnapshotStore, Snapshots};
+pub use user_agent::{UserAgent, UserAgentPool};
 
 /// The only native model alias supported by the first local-inference release.
 pub const LOCAL_MODEL_ALIAS: &str = "qwen2.5-3b-instruct-q4";
@@ -32,6 +54,17 @@ pub const LOCAL_MODEL_ALIAS: &str = "qwen2.5-3b-instruct-q4";
 /// Explains how to enable the optional native inference backend.
 pub const LOCAL_LLM_FEATURE_MSG: &str = "Local provider requires the `local-llm` feature. Rebuild with: cargo build --features local-llm";
 
+/// Explains how to enable PDF text extraction.
+pub const PDF_FEATURE_MSG: &str =
+    "PDF extraction requires the `pdf` feature. Rebuild with: cargo build --features pdf";
+
+/// Explains how to enable publishing extraction events to NATS.
+pub const EVENTS_NATS_FEATURE_MSG: &str = "ARES_NATS_URL requires the `events-nats` feature. Rebuild with: cargo build --features events-nats";
+
+/// Explains how to enable archiving snapshots to S3.
+pub const S3_FEATURE_MSG: &str =
+    "s3:// snapshot URLs require the `s3` feature. Rebuild with: cargo build --features s3";
+
 #[cfg(feature = "anthropic")]
 pub use anthropic::{AnthropicExtractor, AnthropicExtractorFactory};
 
@@ -40,3 +73,12 @@ pub use candle::{CandleExtractor, CandleExtractorFactory, LocalModelStatus, Loca
 
 #[cfg(feature = "browser")]
 pub use browser_fetcher::BrowserFetcher;
+
+#[cfg(feature = "pdf")]
+pub use pdf::{PdfCleaner, PdfText};
+
+#[cfg(feature = "events-nats")]
+pub use nats::NatsPublisher;
+
+#[cfg(feature = "s3")]
+pub use s3::S3SnapshotStore;
@@ -1,14 +1,20 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicU64, Ordering};
 use std::time::Duration;
 
 use ares_core::error::AppError;
+use ares_core::language::output_language_instruction;
 use ares_core::models::{ExtractionOutcome, Usage};
 use ares_core::traits::{Extractor, ExtractorFactory};
 use reqwest::Client;
 use serde::{Deserialize, Serialize};
 
-use crate::util::truncate_for_error;
+use crate::model_pricing;
+use crate::util::{repair_json, truncate_for_error};
 
 const DEFAULT_BASE_URL: &str = "https://api.openai.com/v1";
+const GEMINI_BASE_URL: &str = "https://generativelanguage.googleapis.com/v1beta/openai";
 const DEFAULT_LLM_TIMEOUT: Duration = Duration::from_secs(120);
 const DEFAULT_SYSTEM_PROMPT: &str = "You are a data extraction assistant. Extract the requested fields from the provided web content. Respond ONLY with valid JSON matching the requested schema. Do not include explanations.";
 
@@ -17,6 +23,10 @@ const DEFAULT_SYSTEM_PROMPT: &str = "You are a data extraction assistant. Extrac
 /// Works with any OpenAI-compatible API, including:
 /// - OpenAI directly (`https://api.openai.com/v1`)
 /// - Gemini via compatibility layer (`https://generativelanguage.googleapis.com/v1beta/openai`)
+///
+/// Clones share a running total of the cost of their calls; see
+/// [`total_cost_usd`](Self::total_cost_usd). They also share the count of
+/// responses [JSON repair](Self::with_json_repair) fixed.
 #[derive(Clone)]
 pub struct OpenAiExtractor {
     client: Client,
@@ -25,6 +35,10 @@ pub struct OpenAiExtractor {
     model: String,
     timeout_secs: u64,
     system_prompt: String,
+    /// Accumulated cost in USD, stored as `f64` bits.
+    total_cost: Arc<AtomicU64>,
+    json_repair: bool,
+    repairs_applied: Arc<AtomicU64>,
 }
 
 impl OpenAiExtractor {
@@ -37,7 +51,12 @@ impl OpenAiExtractor {
     }
 
     pub fn with_timeout(self, timeout: Duration) -> Result<Self, AppError> {
-        Self::build(&self.api_key, &self.model, &self.base_url, timeout)
+        Ok(Self {
+            total_cost: self.total_cost.clone(),
+            json_repair: self.json_repair,
+            repairs_applied: self.repairs_applied.clone(),
+            ..Self::build(&self.api_key, &self.model, &self.base_url, timeout)?
+        })
     }
 
     pub fn with_system_prompt(mut self, prompt: impl Into<String>) -> Self {
@@ -45,6 +64,14 @@ impl OpenAiExtractor {
         self
     }
 
+    /// Try to repair a response that isn't valid JSON (trailing commas,
+    /// unquoted keys, Python-style `True`/`False`/`None`) before failing
+    /// with [`AppError::SchemaValidationError`]. Off by default.
+    pub fn with_json_repair(mut self, enabled: bool) -> Self {
+        self.json_repair = enabled;
+        self
+    }
+
     fn build(
         api_key: &str,
         model: &str,
@@ -63,8 +90,73 @@ impl OpenAiExtractor {
             model: model.to_string(),
             timeout_secs: timeout.as_secs(),
             system_prompt: DEFAULT_SYSTEM_PROMPT.to_string(),
+            total_cost: Arc::new(AtomicU64::new(0f64.to_bits())),
+            json_repair: false,
+            repairs_applied: Arc::new(AtomicU64::new(0)),
         })
     }
+
+    /// Estimated USD cost of a call to `model`, from
+    /// [`model_pricing`]. Unknown models cost `0.0`.
+    pub fn estimate_cost(input_tokens: u64, output_tokens: u64, model: &str) -> f64 {
+        model_pricing::cost(model, input_tokens, output_tokens).unwrap_or(0.0)
+    }
+
+    /// Total estimated cost of every call this extractor (or a clone) made
+    /// that reported token usage.
+    pub fn total_cost_usd(&self) -> f64 {
+        f64::from_bits(self.total_cost.load(Ordering::Relaxed))
+    }
+
+    /// How many responses this extractor (or a clone) only parsed after
+    /// repairing them.
+    pub fn repairs_applied(&self) -> u64 {
+        self.repairs_applied.load(Ordering::Relaxed)
+    }
+
+    /// Parse the LLM's reply, repairing it first if that's enabled and it
+    /// doesn't parse as is.
+    fn parse_response(&self, content: &str) -> Result<serde_json::Value, AppError> {
+        let err = match serde_json::from_str(content) {
+            Ok(value) => return Ok(value),
+            Err(e) => e,
+        };
+        if self.json_repair
+            && let Some(repaired) = repair_json(content)
+            && let Ok(value) = serde_json::from_str(&repaired)
+        {
+            self.repairs_applied.fetch_add(1, Ordering::Relaxed);
+            tracing::warn!(model = %self.model, error = %err, "Repaired invalid JSON from LLM");
+            return Ok(value);
+        }
+        Err(AppError::SchemaValidationError(format!(
+            "LLM returned invalid JSON: {err}. Raw: {}",
+            truncate_for_error(content)
+        )))
+    }
+
+    /// Add the cost of one call to the running total and log it.
+    fn record_usage(&self, usage: &Usage) {
+        let cost = model_pricing::cost(
+            &self.model,
+            u64::from(usage.prompt_tokens),
+            u64::from(usage.completion_tokens),
+        );
+        if let Some(cost) = cost {
+            let _ = self
+                .total_cost
+                .fetch_update(Ordering::Relaxed, Ordering::Relaxed, |bits| {
+                    Some((f64::from_bits(bits) + cost).to_bits())
+                });
+        }
+        tracing::info!(
+            model = %self.model,
+            prompt_tokens = usage.prompt_tokens,
+            completion_tokens = usage.completion_tokens,
+            cost_usd = ?cost,
+            "LLM usage"
+        );
+    }
 }
 
 // ---- OpenAI API types ----
@@ -103,6 +195,9 @@ struct ChatResponse {
     choices: Vec<Choice>,
     #[serde(default)]
     usage: Option<ApiUsage>,
+    /// The model that answered (e.g. a dated snapshot of the requested alias).
+    #[serde(default)]
+    model: Option<String>,
 }
 
 /// OpenAI-style usage block. Optional because some compatible servers omit it.
@@ -152,9 +247,10 @@ impl Extractor for OpenAiExtractor {
                 Message {
                     role: "user".to_string(),
                     content: format!(
-                        "Extract data according to this JSON schema:\n```json\n{}\n```\n\nFrom the following web content:\n\n{}",
+                        "Extract data according to this JSON schema:\n```json\n{}\n```\n\nFrom the following web content:\n\n{}{}",
                         serde_json::to_string_pretty(schema)?,
-                        content
+                        content,
+                        output_language_instruction(schema).unwrap_or_default()
                     ),
                 },
             ],
@@ -220,6 +316,9 @@ impl Extractor for OpenAiExtractor {
             .as_ref()
             .map(|u| Usage::new(u.prompt_tokens, u.completion_tokens))
             .filter(|u| u.total_tokens() > 0);
+        if let Some(usage) = &usage {
+            self.record_usage(usage);
+        }
 
         let content_str = chat_response
             .choices
@@ -231,26 +330,34 @@ impl Extractor for OpenAiExtractor {
                 retryable: false,
             })?;
 
-        let value: serde_json::Value = serde_json::from_str(content_str).map_err(|e| {
-            AppError::SchemaValidationError(format!(
-                "LLM returned invalid JSON: {e}. Raw: {}",
-                truncate_for_error(content_str)
-            ))
-        })?;
+        let value = self.parse_response(content_str)?;
 
-        Ok(ExtractionOutcome { value, usage })
+        Ok(ExtractionOutcome {
+            value,
+            usage,
+            model: chat_response.model.filter(|m| !m.is_empty()),
+        })
+    }
+
+    fn fingerprint(&self) -> Option<String> {
+        Some(self.system_prompt.clone())
     }
 }
 
 /// Factory that creates `OpenAiExtractor` instances with a shared API key.
 ///
 /// Used by the worker to construct per-job extractors, since each job may
-/// specify a different model or base URL.
+/// specify a different model or base URL. Jobs with no base URL, or with the
+/// default OpenAI one, are sent to the known provider whose model name prefix
+/// matches (e.g. `gemini-` models go to Gemini's OpenAI-compatible endpoint);
+/// see [`with_known_provider`](Self::with_known_provider).
 #[derive(Clone)]
 pub struct OpenAiExtractorFactory {
     api_key: String,
     llm_timeout: Option<Duration>,
     system_prompt: Option<String>,
+    /// Base URL by model name prefix.
+    known_providers: HashMap<String, String>,
 }
 
 impl OpenAiExtractorFactory {
@@ -259,6 +366,43 @@ impl OpenAiExtractorFactory {
             api_key: api_key.into(),
             llm_timeout: None,
             system_prompt: None,
+            known_providers: HashMap::from([("gemini-".to_string(), GEMINI_BASE_URL.to_string())]),
+        }
+    }
+
+    /// Send models whose name starts with `model_prefix` to `base_url` when
+    /// the job names no other endpoint. Replaces any URL already registered
+    /// for the prefix.
+    pub fn with_known_provider(
+        mut self,
+        model_prefix: impl Into<String>,
+        base_url: impl Into<String>,
+    ) -> Self {
+        self.known_providers
+            .insert(model_prefix.into(), base_url.into());
+        self
+    }
+
+    /// The base URL to call `model` at: `base_url`, unless it is empty or the
+    /// default OpenAI URL and a known provider's prefix matches the model.
+    /// The longest matching prefix wins.
+    fn resolve_base_url<'a>(&'a self, model: &str, base_url: &'a str) -> &'a str {
+        let base_url = base_url.trim();
+        if !base_url.is_empty() && base_url.trim_end_matches('/') != DEFAULT_BASE_URL {
+            return base_url;
+        }
+        let known = self
+            .known_providers
+            .iter()
+            .filter(|(prefix, _)| model.starts_with(prefix.as_str()))
+            .max_by_key(|(prefix, _)| prefix.len());
+        match known {
+            Some((prefix, url)) => {
+                tracing::debug!(model, %prefix, base_url = %url, "Using known provider base URL");
+                url.as_str()
+            }
+            None if base_url.is_empty() => DEFAULT_BASE_URL,
+            None => base_url,
         }
     }
 
@@ -277,6 +421,7 @@ impl ExtractorFactory for OpenAiExtractorFactory {
     type Extractor = OpenAiExtractor;
 
     fn create(&self, model: &str, base_url: &str) -> Result<OpenAiExtractor, AppError> {
+        let base_url = self.resolve_base_url(model, base_url);
         let extractor = OpenAiExtractor::with_base_url(&self.api_key, model, base_url)?;
         let extractor = match self.llm_timeout {
             Some(t) => extractor.with_timeout(t)?,
@@ -289,3 +434,116 @@ impl ExtractorFactory for OpenAiExtractorFactory {
         Ok(extractor)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn estimate_cost_uses_model_pricing() {
+        let usd = OpenAiExtractor::estimate_cost(2_000, 1_000, "gpt-4o");
+        assert!((usd - 0.015).abs() < 1e-12);
+        assert_eq!(
+            OpenAiExtractor::estimate_cost(2_000, 1_000, "my-local-model"),
+            0.0
+        );
+    }
+
+    #[test]
+    fn usage_accumulates_across_clones() {
+        let extractor = OpenAiExtractor::new("key", "gpt-4o-mini")
+            .unwrap()
+            .with_timeout(Duration::from_secs(5))
+            .unwrap();
+        let clone = extractor.clone();
+
+        extractor.record_usage(&Usage::new(1_000_000, 0));
+        clone.record_usage(&Usage::new(0, 1_000_000));
+        assert!((extractor.total_cost_usd() - 0.75).abs() < 1e-9);
+        assert_eq!(clone.total_cost_usd(), extractor.total_cost_usd());
+
+        let unpriced = OpenAiExtractor::new("key", "llama3").unwrap();
+        unpriced.record_usage(&Usage::new(500, 500));
+        assert_eq!(unpriced.total_cost_usd(), 0.0);
+    }
+
+    #[test]
+    fn fingerprint_follows_the_system_prompt() {
+        let default = OpenAiExtractor::new("key", "gpt-4o-mini").unwrap();
+        let custom = default.clone().with_system_prompt("Only extract titles.");
+
+        assert_eq!(
+            default.fingerprint().as_deref(),
+            Some(DEFAULT_SYSTEM_PROMPT)
+        );
+        assert_eq!(
+            custom.fingerprint().as_deref(),
+            Some("Only extract titles.")
+        );
+    }
+
+    #[test]
+    fn json_repair_is_opt_in_and_counted_across_clones() {
+        let raw = r#"{title: "Hello", "draft": False,}"#;
+
+        let strict = OpenAiExtractor::new("key", "gpt-4o-mini").unwrap();
+        assert!(matches!(
+            strict.parse_response(raw),
+            Err(AppError::SchemaValidationError(_))
+        ));
+        assert_eq!(strict.repairs_applied(), 0);
+
+        let lenient = strict
+            .with_json_repair(true)
+            .with_timeout(Duration::from_secs(5))
+            .unwrap();
+        let clone = lenient.clone();
+        assert_eq!(
+            lenient.parse_response(raw).unwrap(),
+            serde_json::json!({"title": "Hello", "draft": false})
+        );
+        assert_eq!(
+            clone.parse_response(r#"{"ok": true}"#).unwrap(),
+            serde_json::json!({"ok": true})
+        );
+        assert_eq!(clone.repairs_applied(), 1);
+        assert!(matches!(
+            lenient.parse_response("not json at all"),
+            Err(AppError::SchemaValidationError(_))
+        ));
+    }
+
+    #[test]
+    fn factory_falls_back_to_known_providers() {
+        let factory = OpenAiExtractorFactory::new("key")
+            .with_known_provider("gemini-2.5-pro", "https://pro.example.com/v1");
+
+        // No base URL, or the OpenAI default: the model prefix decides.
+        assert_eq!(
+            factory.resolve_base_url("gemini-2.5-flash", ""),
+            GEMINI_BASE_URL
+        );
+        assert_eq!(
+            factory.resolve_base_url("gemini-2.5-flash", "https://api.openai.com/v1/"),
+            GEMINI_BASE_URL
+        );
+        assert_eq!(
+            factory.resolve_base_url("gemini-2.5-pro-preview", ""),
+            "https://pro.example.com/v1"
+        );
+        assert_eq!(
+            factory.resolve_base_url("gpt-4o-mini", ""),
+            DEFAULT_BASE_URL
+        );
+        assert_eq!(
+            factory.resolve_base_url("gpt-4o-mini", DEFAULT_BASE_URL),
+            DEFAULT_BASE_URL
+        );
+
+        // An explicit endpoint always wins.
+        assert_eq!(
+            factory.resolve_base_url("gemini-2.5-flash", "http://localhost:11434/v1"),
+            "http://localhost:11434/v1"
+        );
+    }
+}
@@ -0,0 +1,77 @@
+//! Per-token prices for known OpenAI models, for cost estimates.
+//!
+//! Prices are USD per million tokens, `(input, output)`, from OpenAI's public
+//! price list. Dated snapshots (`gpt-4o-2024-08-06`) are priced as their base
+//! model. Models not listed here (Gemini, self-hosted endpoints) have no
+//! price and are reported without a cost.
+
+/// `(model, input USD per 1M tokens, output USD per 1M tokens)`.
+const PRICES: &[(&str, f64, f64)] = &[
+    ("gpt-4.1", 2.00, 8.00),
+    ("gpt-4.1-mini", 0.40, 1.60),
+    ("gpt-4.1-nano", 0.10, 0.40),
+    ("gpt-4o", 2.50, 10.00),
+    ("gpt-4o-mini", 0.15, 0.60),
+    ("gpt-4-turbo", 10.00, 30.00),
+    ("gpt-4", 30.00, 60.00),
+    ("gpt-3.5-turbo", 0.50, 1.50),
+    ("o1", 15.00, 60.00),
+    ("o1-mini", 1.10, 4.40),
+    ("o1-pro", 150.00, 600.00),
+    ("o3", 2.00, 8.00),
+    ("o3-mini", 1.10, 4.40),
+    ("o4-mini", 1.10, 4.40),
+];
+
+/// `(input_cost_per_million, output_cost_per_million)` for `model`, or `None`
+/// when the model is unknown.
+///
+/// Matches the exact name or the longest listed name followed by a `-`
+/// suffix, so `gpt-4o-mini-2024-07-18` is priced as `gpt-4o-mini`, not
+/// `gpt-4o`.
+pub fn lookup(model: &str) -> Option<(f64, f64)> {
+    PRICES
+        .iter()
+        .filter(|(name, _, _)| {
+            model == *name
+                || model
+                    .strip_prefix(name)
+                    .is_some_and(|rest| rest.starts_with('-'))
+        })
+        .max_by_key(|(name, _, _)| name.len())
+        .map(|&(_, input, output)| (input, output))
+}
+
+/// USD cost of a call to `model`, or `None` when the model is unknown.
+pub fn cost(model: &str, input_tokens: u64, output_tokens: u64) -> Option<f64> {
+    lookup(model).map(|(input, output)| {
+        (input_tokens as f64 * input + output_tokens as f64 * output) / 1_000_000.0
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn dated_snapshots_use_the_most_specific_price() {
+        assert_eq!(lookup("gpt-4o"), Some((2.50, 10.00)));
+        assert_eq!(lookup("gpt-4o-2024-08-06"), Some((2.50, 10.00)));
+        assert_eq!(lookup("gpt-4o-mini-2024-07-18"), Some((0.15, 0.60)));
+        assert_eq!(lookup("o1-pro"), Some((150.00, 600.00)));
+    }
+
+    #[test]
+    fn unknown_models_have_no_price() {
+        assert_eq!(lookup("gemini-2.5-flash"), None);
+        assert_eq!(lookup("gpt-4oo"), None);
+        assert_eq!(cost("llama3", 1000, 1000), None);
+    }
+
+    #[test]
+    fn cost_scales_per_million_tokens() {
+        let usd = cost("gpt-4o-mini", 1_000_000, 500_000).unwrap();
+        assert!((usd - 0.45).abs() < 1e-9);
+        assert_eq!(cost("gpt-4o", 0, 0), Some(0.0));
+    }
+}
@@ -0,0 +1,187 @@
+//! NATS JetStream publishing of extraction events.
+//!
+//! [`NatsPublisher`] connects on first use, so building one never blocks
+//! startup on the bus, and makes sure a stream captures
+//! `ares.extractions.>` before the first publish.
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use ares_core::error::AppError;
+use ares_core::models::ExtractionEvent;
+use ares_core::traits::ExtractionEventPublisher;
+use async_nats::jetstream::{self, Context};
+use tokio::sync::OnceCell;
+
+use crate::events::{DEFAULT_SUBJECT_PREFIX, subject};
+
+/// Stream created for the extraction subjects when none exists.
+pub const DEFAULT_STREAM_NAME: &str = "ARES_EXTRACTIONS";
+
+/// Publish attempts per event, including the first.
+const MAX_ATTEMPTS: u32 = 3;
+
+/// Wait before the first retry; doubled for each later one.
+const RETRY_BACKOFF: Duration = Duration::from_millis(100);
+
+/// Publishes [`ExtractionEvent`]s as JSON to JetStream, on
+/// `<prefix>.<schema_name>`, retrying briefly on failure.
+#[derive(Clone)]
+pub struct NatsPublisher {
+    url: String,
+    prefix: String,
+    stream: String,
+    context: Arc<OnceCell<Context>>,
+}
+
+impl NatsPublisher {
+    pub fn new(url: impl Into<String>) -> Self {
+        Self {
+            url: url.into(),
+            prefix: DEFAULT_SUBJECT_PREFIX.to_string(),
+            stream: DEFAULT_STREAM_NAME.to_string(),
+            context: Arc::new(OnceCell::new()),
+        }
+    }
+
+    /// Publish on `<prefix>.<schema_name>` instead of the default subjects.
+    pub fn with_subject_prefix(mut self, prefix: impl Into<String>) -> Self {
+        self.prefix = prefix.into();
+        self
+    }
+
+    /// Name of the stream created for the subjects when none exists.
+    pub fn with_stream(mut self, stream: impl Into<String>) -> Self {
+        self.stream = stream.into();
+        self
+    }
+
+    /// The JetStream context, connecting on first use. A failed connection
+    /// is retried by the next call.
+    async fn context(&self) -> Result<&Context, AppError> {
+        self.context
+            .get_or_try_init(|| async {
+                let client = async_nats::connect(self.url.as_str()).await.map_err(|e| {
+                    AppError::NetworkError(format!("Failed to connect to NATS: {e}"))
+                })?;
+                let context = jetstream::new(client);
+                self.ensure_stream(&context).await;
+                Ok(context)
+            })
+            .await
+    }
+
+    /// Create the stream for the subjects unless one exists. Failure is only
+    /// logged: an operator-managed stream may already cover the subjects.
+    async fn ensure_stream(&self, context: &Context) {
+        let config = jetstream::stream::Config {
+            name: self.stream.clone(),
+            subjects: vec![format!("{}.>", self.prefix)],
+            ..Default::default()
+        };
+        if let Err(e) = context.get_or_create_stream(config).await {
+            tracing::warn!(stream = %self.stream, "Could not create NATS stream: {e}");
+        }
+    }
+
+    async fn try_publish(&self, subject: &str, payload: &[u8]) -> Result<(), AppError> {
+        let context = self.context().await?;
+        let publish_error = |e: &dyn std::fmt::Display| {
+            AppError::NetworkError(format!("Failed to publish to {subject}: {e}"))
+        };
+        let ack = context
+            .publish(subject.to_string(), payload.to_vec().into())
+            .await
+            .map_err(|e| publish_error(&e))?;
+        ack.await.map_err(|e| publish_error(&e))?;
+        Ok(())
+    }
+}
+
+impl ExtractionEventPublisher for NatsPublisher {
+    async fn publish(&self, event: &ExtractionEvent) -> Result<(), AppError> {
+        let subject = subject(&self.prefix, &event.extraction.schema_name);
+        let payload = serde_json::to_vec(event)?;
+
+        let mut backoff = RETRY_BACKOFF;
+        let mut attempt = 1;
+        loop {
+            match self.try_publish(&subject, &payload).await {
+                Ok(()) => return Ok(()),
+                Err(e) if attempt < MAX_ATTEMPTS => {
+                    tracing::debug!(attempt, "Retrying extraction event: {e}");
+                    tokio::time::sleep(backoff).await;
+                    backoff *= 2;
+                    attempt += 1;
+                }
+                Err(e) => return Err(e),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::Utc;
+    use futures::StreamExt;
+    use testcontainers::core::{ContainerPort, WaitFor};
+    use testcontainers::runners::AsyncRunner;
+    use testcontainers::{GenericImage, ImageExt};
+    use uuid::Uuid;
+
+    use ares_core::models::NewExtraction;
+
+    use super::*;
+
+    fn event() -> ExtractionEvent {
+        ExtractionEvent {
+            extraction_id: Uuid::new_v4(),
+            previous_extraction_id: None,
+            extraction: NewExtraction {
+                url: "https://example.com/pricing".into(),
+                schema_name: "pricing".into(),
+                extracted_data: serde_json::json!({ "plan": "pro", "price": 42 }),
+                ..Default::default()
+            },
+            changed: true,
+            changes: Vec::new(),
+            saved_at: Utc::now(),
+        }
+    }
+
+    #[tokio::test]
+    async fn publishes_events_to_jetstream() {
+        let container = GenericImage::new("nats", "2.10")
+            .with_exposed_port(ContainerPort::Tcp(4222))
+            .with_wait_for(WaitFor::message_on_stderr("Server is ready"))
+            .with_cmd(["-js"])
+            .start()
+            .await
+            .expect("Failed to start NATS container");
+        let port = container.get_host_port_ipv4(4222).await.unwrap();
+        let url = format!("nats://127.0.0.1:{port}");
+
+        let client = async_nats::connect(url.as_str()).await.unwrap();
+        let mut subscriber = client.subscribe("ares.extractions.pricing").await.unwrap();
+
+        let publisher = NatsPublisher::new(&url);
+        let event = event();
+        publisher.publish(&event).await.unwrap();
+
+        let message = tokio::time::timeout(Duration::from_secs(5), subscriber.next())
+            .await
+            .expect("no event received")
+            .unwrap();
+        let payload: serde_json::Value = serde_json::from_slice(&message.payload).unwrap();
+        assert_eq!(payload["extraction_id"], event.extraction_id.to_string());
+        assert_eq!(payload["extraction"]["extracted_data"]["price"], 42);
+        assert_eq!(payload["changed"], true);
+    }
+
+    #[tokio::test]
+    async fn unreachable_server_fails_after_retries() {
+        let publisher = NatsPublisher::new("nats://127.0.0.1:1");
+        let err = publisher.publish(&event()).await.unwrap_err();
+        assert!(matches!(err, AppError::NetworkError(_)), "{err}");
+    }
+}
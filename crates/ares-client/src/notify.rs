@@ -0,0 +1,412 @@
+//! Change notifications: Slack incoming webhooks and signed JSON webhooks.
+//!
+//! [`Notifiers`] is the [`ChangeNotifier`] the CLI hands to the worker and
+//! scrape service. It sends each [`ChangeEvent`] to whichever of
+//! [`SlackNotifier`] and [`WebhookNotifier`] are configured; a job's
+//! `notify_url` replaces the webhook, never the Slack channel.
+
+use std::time::Duration;
+
+use ares_core::diff::JsonChange;
+use ares_core::error::AppError;
+use ares_core::models::ChangeEvent;
+use ares_core::traits::ChangeNotifier;
+use hmac::{Hmac, Mac};
+use reqwest::Client;
+use sha2::Sha256;
+
+use crate::fetcher::validate_url;
+
+/// Header carrying `sha256=<hex HMAC-SHA256 of the body>` when the webhook
+/// has a secret.
+pub const SIGNATURE_HEADER: &str = "X-Ares-Signature";
+
+/// Slack message used when no template is configured.
+pub const DEFAULT_SLACK_TEMPLATE: &str =
+    "Data changed for *{schema_name}* at {url} ({count} changes):\n{changes}";
+
+/// Changes listed in a Slack message before the rest are summarized.
+const MAX_SLACK_CHANGES: usize = 20;
+
+const NOTIFY_TIMEOUT: Duration = Duration::from_secs(10);
+
+fn client() -> Result<Client, AppError> {
+    Client::builder()
+        .user_agent("Ares/0.2 (AI Scraper)")
+        .timeout(NOTIFY_TIMEOUT)
+        .build()
+        .map_err(|e| AppError::HttpError(e.to_string()))
+}
+
+/// POST `body` to `url`, mapping transport errors and non-2xx statuses.
+async fn post(
+    client: &Client,
+    target: &str,
+    url: &str,
+    body: Vec<u8>,
+    signature: Option<String>,
+) -> Result<(), AppError> {
+    let mut request = client
+        .post(url)
+        .header(reqwest::header::CONTENT_TYPE, "application/json")
+        .body(body);
+    if let Some(signature) = signature {
+        request = request.header(SIGNATURE_HEADER, signature);
+    }
+    // Webhook URLs carry tokens; keep them out of errors and logs.
+    let response = request.send().await.map_err(|e| {
+        let e = e.without_url();
+        if e.is_timeout() {
+            AppError::Timeout(NOTIFY_TIMEOUT.as_secs())
+        } else {
+            AppError::NetworkError(format!("{target} notification failed: {e}"))
+        }
+    })?;
+    let status = response.status();
+    if !status.is_success() {
+        return Err(AppError::HttpError(format!(
+            "{target} notification failed: HTTP {}",
+            status.as_u16()
+        )));
+    }
+    Ok(())
+}
+
+/// Posts a templated message to a Slack incoming webhook.
+///
+/// The template may use `{url}`, `{schema_name}`, `{count}` (number of
+/// changes), `{changes}` (one line per change), and `{extraction_id}`.
+#[derive(Clone)]
+pub struct SlackNotifier {
+    client: Client,
+    webhook_url: String,
+    template: String,
+}
+
+impl SlackNotifier {
+    pub fn new(webhook_url: impl Into<String>) -> Result<Self, AppError> {
+        Ok(Self {
+            client: client()?,
+            webhook_url: webhook_url.into(),
+            template: DEFAULT_SLACK_TEMPLATE.to_string(),
+        })
+    }
+
+    /// Use `template` instead of [`DEFAULT_SLACK_TEMPLATE`].
+    pub fn with_template(mut self, template: impl Into<String>) -> Self {
+        self.template = template.into();
+        self
+    }
+
+    /// The message text for `change`.
+    pub fn render(&self, change: &ChangeEvent) -> String {
+        self.template
+            .replace("{url}", &change.url)
+            .replace("{schema_name}", &change.schema_name)
+            .replace("{count}", &change.changes.len().to_string())
+            .replace("{extraction_id}", &change.extraction_id.to_string())
+            .replace("{changes}", &slack_change_lines(&change.changes))
+    }
+}
+
+fn slack_change_lines(changes: &[JsonChange]) -> String {
+    let mut lines: Vec<String> = changes
+        .iter()
+        .take(MAX_SLACK_CHANGES)
+        .map(|change| match change {
+            JsonChange::Added { path, value } => format!("• added `{path}`: {value}"),
+            JsonChange::Removed { path, value } => format!("• removed `{path}`: {value}"),
+            JsonChange::Changed { path, old, new } => format!("• `{path}`: {old} → {new}"),
+        })
+        .collect();
+    if changes.len() > MAX_SLACK_CHANGES {
+        lines.push(format!("…and {} more", changes.len() - MAX_SLACK_CHANGES));
+    }
+    lines.join("\n")
+}
+
+impl ChangeNotifier for SlackNotifier {
+    async fn notify(&self, change: &ChangeEvent) -> Result<(), AppError> {
+        let body = serde_json::to_vec(&serde_json::json!({ "text": self.render(change) }))?;
+        post(&self.client, "Slack", &self.webhook_url, body, None).await
+    }
+}
+
+/// POSTs the [`ChangeEvent`] as JSON, signed with HMAC-SHA256 in
+/// [`SIGNATURE_HEADER`] when a secret is set.
+///
+/// Like [`ReqwestFetcher`](crate::ReqwestFetcher), SSRF protection is
+/// enabled by default; use [`allow_private_urls`](Self::allow_private_urls)
+/// for receivers on the local network.
+#[derive(Clone)]
+pub struct WebhookNotifier {
+    client: Client,
+    url: String,
+    secret: Option<String>,
+    ssrf_protection: bool,
+}
+
+impl WebhookNotifier {
+    pub fn new(url: impl Into<String>) -> Result<Self, AppError> {
+        Ok(Self {
+            client: client()?,
+            url: url.into(),
+            secret: None,
+            ssrf_protection: true,
+        })
+    }
+
+    /// Sign request bodies with `secret`.
+    pub fn with_secret(mut self, secret: impl Into<String>) -> Self {
+        self.secret = Some(secret.into());
+        self
+    }
+
+    /// Disable SSRF protection, allowing webhooks on private/reserved IPs.
+    pub fn allow_private_urls(mut self) -> Self {
+        self.ssrf_protection = false;
+        self
+    }
+
+    /// The same webhook, posting to `url`, with SSRF protection back on:
+    /// per-job URLs come from API callers, not the operator.
+    fn with_url(&self, url: &str) -> Self {
+        Self {
+            url: url.to_string(),
+            ssrf_protection: true,
+            ..self.clone()
+        }
+    }
+}
+
+/// `sha256=<hex>` HMAC-SHA256 signature of `body` under `secret`.
+pub fn sign(secret: &str, body: &[u8]) -> String {
+    let mut mac =
+        Hmac::<Sha256>::new_from_slice(secret.as_bytes()).expect("HMAC accepts keys of any size");
+    mac.update(body);
+    format!("sha256={:x}", mac.finalize().into_bytes())
+}
+
+impl ChangeNotifier for WebhookNotifier {
+    async fn notify(&self, change: &ChangeEvent) -> Result<(), AppError> {
+        if self.ssrf_protection {
+            validate_url(&self.url).await?;
+        }
+        let body = serde_json::to_vec(change)?;
+        let signature = self.secret.as_deref().map(|secret| sign(secret, &body));
+        post(&self.client, "Webhook", &self.url, body, signature).await
+    }
+
+    fn with_webhook_override(&self, url: &str) -> Self {
+        self.with_url(url)
+    }
+}
+
+/// The configured notifiers, any of which may be absent.
+#[derive(Clone, Default)]
+pub struct Notifiers {
+    pub slack: Option<SlackNotifier>,
+    pub webhook: Option<WebhookNotifier>,
+}
+
+impl Notifiers {
+    /// Whether no notifier is configured.
+    pub fn is_empty(&self) -> bool {
+        self.slack.is_none() && self.webhook.is_none()
+    }
+}
+
+impl ChangeNotifier for Notifiers {
+    /// Send to every configured notifier, even when one fails; the errors
+    /// are joined into one.
+    async fn notify(&self, change: &ChangeEvent) -> Result<(), AppError> {
+        let mut errors = Vec::new();
+        if let Some(slack) = &self.slack
+            && let Err(e) = slack.notify(change).await
+        {
+            errors.push(e.to_string());
+        }
+        if let Some(webhook) = &self.webhook
+            && let Err(e) = webhook.notify(change).await
+        {
+            errors.push(e.to_string());
+        }
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(AppError::HttpError(errors.join("; ")))
+        }
+    }
+
+    /// Post to `url` in place of the configured webhook, keeping its secret.
+    /// Slack is unaffected.
+    fn with_webhook_override(&self, url: &str) -> Self {
+        let webhook = match &self.webhook {
+            Some(webhook) => webhook.with_url(url),
+            None => match WebhookNotifier::new(url) {
+                Ok(webhook) => webhook,
+                Err(e) => {
+                    tracing::warn!(error = %e, "Ignoring job notify_url");
+                    return self.clone();
+                }
+            },
+        };
+        Self {
+            slack: self.slack.clone(),
+            webhook: Some(webhook),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::{Arc, Mutex};
+
+    use axum::Router;
+    use axum::extract::State;
+    use axum::http::{HeaderMap, StatusCode};
+    use axum::routing::post;
+    use chrono::Utc;
+    use serde_json::json;
+    use uuid::Uuid;
+
+    use super::*;
+
+    type Received = Arc<Mutex<Vec<(String, HeaderMap, String)>>>;
+
+    /// A sink recording every POST; `/fail` answers 500.
+    async fn sink() -> (String, Received) {
+        let received = Received::default();
+        async fn record(
+            State(received): State<Received>,
+            uri: axum::http::Uri,
+            headers: HeaderMap,
+            body: String,
+        ) -> StatusCode {
+            let path = uri.path().to_string();
+            let status = match path.as_str() {
+                "/fail" => StatusCode::INTERNAL_SERVER_ERROR,
+                _ => StatusCode::OK,
+            };
+            received.lock().unwrap().push((path, headers, body));
+            status
+        }
+        let router = Router::new()
+            .route("/{*path}", post(record))
+            .with_state(received.clone());
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let base = format!("http://{}", listener.local_addr().unwrap());
+        tokio::spawn(async move { axum::serve(listener, router).await.unwrap() });
+        (base, received)
+    }
+
+    fn change() -> ChangeEvent {
+        ChangeEvent {
+            url: "https://shop.example/p/1".into(),
+            schema_name: "product".into(),
+            previous_extraction_id: Uuid::new_v4(),
+            extraction_id: Uuid::new_v4(),
+            changes: vec![JsonChange::Changed {
+                path: "price".into(),
+                old: json!(10),
+                new: json!(12),
+            }],
+            detected_at: Utc::now(),
+        }
+    }
+
+    #[tokio::test]
+    async fn slack_posts_the_rendered_template() {
+        let (base, received) = sink().await;
+        let slack = SlackNotifier::new(format!("{base}/slack")).unwrap();
+        slack.notify(&change()).await.unwrap();
+
+        let received = received.lock().unwrap();
+        let body: serde_json::Value = serde_json::from_str(&received[0].2).unwrap();
+        assert_eq!(
+            body["text"],
+            "Data changed for *product* at https://shop.example/p/1 (1 changes):\n\
+             • `price`: 10 → 12"
+        );
+
+        let custom = slack.with_template("{schema_name}: {count}");
+        assert_eq!(custom.render(&change()), "product: 1");
+    }
+
+    #[tokio::test]
+    async fn webhook_posts_the_signed_event() {
+        let (base, received) = sink().await;
+        let webhook = WebhookNotifier::new(format!("{base}/hook"))
+            .unwrap()
+            .allow_private_urls()
+            .with_secret("s3cret");
+        let change = change();
+        webhook.notify(&change).await.unwrap();
+
+        let received = received.lock().unwrap();
+        let (path, headers, body) = &received[0];
+        assert_eq!(path, "/hook");
+        assert_eq!(headers[SIGNATURE_HEADER], sign("s3cret", body.as_bytes()));
+        let body: serde_json::Value = serde_json::from_str(body).unwrap();
+        assert_eq!(body["extraction_id"], change.extraction_id.to_string());
+        assert_eq!(
+            body["changes"],
+            json!([{"kind": "changed", "path": "price", "old": 10, "new": 12}])
+        );
+    }
+
+    #[test]
+    fn signature_is_hex_hmac_sha256() {
+        // RFC 4231 test case 2.
+        assert_eq!(
+            sign("Jefe", b"what do ya want for nothing?"),
+            "sha256=5bdcc146bf60754e6a042426089575c75a003f089d2739839dec58b964ec3843"
+        );
+    }
+
+    #[tokio::test]
+    async fn override_replaces_the_webhook_and_blocks_private_urls() {
+        let (base, received) = sink().await;
+        let notifiers = Notifiers {
+            slack: Some(SlackNotifier::new(format!("{base}/slack")).unwrap()),
+            webhook: Some(
+                WebhookNotifier::new(format!("{base}/hook"))
+                    .unwrap()
+                    .allow_private_urls(),
+            ),
+        };
+        notifiers.notify(&change()).await.unwrap();
+
+        let err = notifiers
+            .with_webhook_override(&format!("{base}/job"))
+            .notify(&change())
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("private"), "{err}");
+
+        let paths: Vec<String> = received
+            .lock()
+            .unwrap()
+            .iter()
+            .map(|(path, _, _)| path.clone())
+            .collect();
+        // The override still notifies Slack; only the job URL was refused.
+        assert_eq!(paths, ["/slack", "/hook", "/slack"]);
+    }
+
+    #[tokio::test]
+    async fn failures_are_reported_after_notifying_the_rest() {
+        let (base, received) = sink().await;
+        let notifiers = Notifiers {
+            slack: Some(SlackNotifier::new(format!("{base}/fail")).unwrap()),
+            webhook: Some(
+                WebhookNotifier::new(format!("{base}/hook"))
+                    .unwrap()
+                    .allow_private_urls(),
+            ),
+        };
+        let err = notifiers.notify(&change()).await.unwrap_err();
+        assert!(err.to_string().contains("HTTP 500"), "{err}");
+        assert_eq!(received.lock().unwrap().len(), 2);
+    }
+}
@@ -0,0 +1,228 @@
+//! PDF text extraction for PDF targets (price lists, reports).
+//!
+//! [`PdfCleaner`] turns PDF bytes into a small HTML document, one `<pre>`
+//! block per page, so PDFs go through the same [`HtmdCleaner`] and extractor
+//! as web pages. `<pre>` keeps the column layout pdf-extract reconstructs,
+//! which matters for tables.
+//!
+//! [`HtmdCleaner`]: crate::HtmdCleaner
+
+use std::panic::{self, AssertUnwindSafe};
+
+use ares_core::error::AppError;
+use pdf_extract::{Document, PlainTextOutput};
+
+/// Default cap on the size of a PDF download.
+pub const DEFAULT_MAX_PDF_BYTES: usize = 20 * 1024 * 1024;
+
+/// Default cap on the number of pages extracted; later pages are dropped.
+pub const DEFAULT_MAX_PDF_PAGES: usize = 50;
+
+/// Below this many characters per page on average, a PDF is probably
+/// scanned images without a text layer.
+const MIN_CHARS_PER_PAGE: usize = 20;
+
+/// The text of a PDF, page by page.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PdfText {
+    /// Text of the extracted pages, in order.
+    pub pages: Vec<String>,
+    /// Pages in the document, including any beyond the page limit.
+    pub total_pages: usize,
+    /// Reasons the text may be incomplete or unusable.
+    pub warnings: Vec<String>,
+}
+
+impl PdfText {
+    /// An HTML document for [`HtmdCleaner`](crate::HtmdCleaner): the
+    /// warnings as notes, then each page under a "Page N" heading.
+    pub fn to_html(&self) -> String {
+        let mut html = String::from("<html><body>\n");
+        for warning in &self.warnings {
+            html.push_str(&format!("<p>Note: {}</p>\n", escape(warning)));
+        }
+        for (i, page) in self.pages.iter().enumerate() {
+            html.push_str(&format!(
+                "<h2>Page {}</h2>\n<pre>{}</pre>\n",
+                i + 1,
+                escape(page.trim())
+            ));
+        }
+        html.push_str("</body></html>\n");
+        html
+    }
+}
+
+/// Extracts the text layer of PDFs, within a size and page limit.
+#[derive(Debug, Clone)]
+pub struct PdfCleaner {
+    max_bytes: usize,
+    max_pages: usize,
+}
+
+impl Default for PdfCleaner {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl PdfCleaner {
+    pub fn new() -> Self {
+        Self {
+            max_bytes: DEFAULT_MAX_PDF_BYTES,
+            max_pages: DEFAULT_MAX_PDF_PAGES,
+        }
+    }
+
+    /// Reject PDFs larger than `max_bytes`.
+    pub fn with_max_bytes(mut self, max_bytes: usize) -> Self {
+        self.max_bytes = max_bytes;
+        self
+    }
+
+    /// Extract at most `max_pages` pages.
+    pub fn with_max_pages(mut self, max_pages: usize) -> Self {
+        self.max_pages = max_pages;
+        self
+    }
+
+    /// Extract the text of `bytes`.
+    ///
+    /// pdf-extract panics on some malformed documents; that is reported as
+    /// an error like any other unreadable PDF.
+    pub fn extract(&self, bytes: &[u8]) -> Result<PdfText, AppError> {
+        if bytes.len() > self.max_bytes {
+            return Err(AppError::CleanerError(format!(
+                "PDF is {} bytes, over the {} byte limit",
+                bytes.len(),
+                self.max_bytes
+            )));
+        }
+
+        panic::catch_unwind(AssertUnwindSafe(|| self.extract_pages(bytes))).unwrap_or_else(|_| {
+            Err(AppError::CleanerError(
+                "Failed to read PDF: malformed document".to_string(),
+            ))
+        })
+    }
+
+    /// [`extract`](Self::extract), rendered with [`PdfText::to_html`].
+    pub fn to_html(&self, bytes: &[u8]) -> Result<String, AppError> {
+        Ok(self.extract(bytes)?.to_html())
+    }
+
+    fn extract_pages(&self, bytes: &[u8]) -> Result<PdfText, AppError> {
+        let mut doc = Document::load_mem(bytes)
+            .map_err(|e| AppError::CleanerError(format!("Failed to read PDF: {e}")))?;
+        // Owner-password-only PDFs open with an empty user password.
+        if doc.is_encrypted() && doc.decrypt("").is_err() {
+            return Err(AppError::CleanerError(
+                "PDF is password-protected".to_string(),
+            ));
+        }
+
+        let page_numbers: Vec<u32> = doc.get_pages().into_keys().collect();
+        let mut pages = Vec::with_capacity(page_numbers.len().min(self.max_pages));
+        for &number in page_numbers.iter().take(self.max_pages) {
+            let mut text = String::new();
+            {
+                let mut output = PlainTextOutput::new(&mut text);
+                pdf_extract::output_doc_page(&doc, &mut output, number).map_err(|e| {
+                    AppError::CleanerError(format!("Failed to read PDF page {number}: {e}"))
+                })?;
+            }
+            pages.push(text);
+        }
+
+        let total_pages = page_numbers.len();
+        let mut warnings = Vec::new();
+        if total_pages > pages.len() {
+            warnings.push(format!(
+                "only the first {} of {total_pages} pages were extracted",
+                pages.len()
+            ));
+        }
+        let chars: usize = pages.iter().map(|page| page.trim().chars().count()).sum();
+        if chars < MIN_CHARS_PER_PAGE * pages.len().max(1) {
+            warnings.push(format!(
+                "the PDF has little extractable text ({chars} characters over {} pages); \
+                 it may be scanned images",
+                pages.len()
+            ));
+        }
+
+        Ok(PdfText {
+            pages,
+            total_pages,
+            warnings,
+        })
+    }
+}
+
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PRICE_LIST: &[u8] = include_bytes!("../tests/fixtures/documents/price-list.pdf");
+
+    #[test]
+    fn extracts_text_page_by_page() {
+        let text = PdfCleaner::new().extract(PRICE_LIST).unwrap();
+        assert_eq!(text.total_pages, 2);
+        assert_eq!(text.pages.len(), 2);
+        assert!(text.pages[0].contains("Acme price list 2026"), "{text:?}");
+        assert!(text.pages[0].contains("19.50 EUR"), "{text:?}");
+        assert!(text.pages[1].contains("Prices include VAT."), "{text:?}");
+        assert!(text.warnings.is_empty(), "{text:?}");
+    }
+
+    #[test]
+    fn page_limit_truncates_with_a_warning() {
+        let text = PdfCleaner::new()
+            .with_max_pages(1)
+            .extract(PRICE_LIST)
+            .unwrap();
+        assert_eq!(text.total_pages, 2);
+        assert_eq!(text.pages.len(), 1);
+        assert_eq!(
+            text.warnings,
+            ["only the first 1 of 2 pages were extracted"]
+        );
+
+        let html = text.to_html();
+        assert!(
+            html.contains("<p>Note: only the first 1 of 2 pages"),
+            "{html}"
+        );
+        assert!(html.contains("<h2>Page 1</h2>"), "{html}");
+        assert!(!html.contains("Page 2"), "{html}");
+    }
+
+    #[test]
+    fn oversized_and_invalid_pdfs_are_rejected() {
+        let err = PdfCleaner::new()
+            .with_max_bytes(100)
+            .extract(PRICE_LIST)
+            .unwrap_err();
+        assert!(err.to_string().contains("byte limit"), "{err}");
+
+        let err = PdfCleaner::new().extract(b"%PDF-1.4 garbage").unwrap_err();
+        assert!(matches!(err, AppError::CleanerError(_)), "{err}");
+    }
+
+    #[test]
+    fn html_escapes_page_text() {
+        let text = PdfText {
+            pages: vec!["a < b & c".into()],
+            total_pages: 1,
+            warnings: Vec::new(),
+        };
+        assert!(text.to_html().contains("<pre>a &lt; b &amp; c</pre>"));
+    }
+}
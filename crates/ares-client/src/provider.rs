@@ -168,6 +168,16 @@ impl Extractor for ProviderExtractor {
             ProviderExtractor::Local(e) => e.extract(content, schema).await,
         }
     }
+
+    fn fingerprint(&self) -> Option<String> {
+        match self {
+            ProviderExtractor::OpenAi(e) => e.fingerprint(),
+            #[cfg(feature = "anthropic")]
+            ProviderExtractor::Anthropic(e) => e.fingerprint(),
+            #[cfg(feature = "local-llm")]
+            ProviderExtractor::Local(e) => e.fingerprint(),
+        }
+    }
 }
 
 /// An [`ExtractorFactory`] backed by whichever provider was selected. Used by
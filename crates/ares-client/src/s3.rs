@@ -0,0 +1,192 @@
+//! S3-compatible snapshot storage.
+//!
+//! [`S3SnapshotStore`] loads its configuration on first use through the AWS
+//! default chain: credentials from `AWS_ACCESS_KEY_ID`/`AWS_SECRET_ACCESS_KEY`,
+//! profiles, or instance roles, and the region from `AWS_REGION`. Setting
+//! `AWS_ENDPOINT_URL` (or `AWS_ENDPOINT_URL_S3`) points it at MinIO, R2 or
+//! another S3-compatible service, addressed path-style.
+
+use std::sync::Arc;
+
+use ares_core::error::AppError;
+use ares_core::snapshot::{SnapshotKind, is_valid_key};
+use ares_core::traits::SnapshotStore;
+use aws_config::BehaviorVersion;
+use aws_sdk_s3::Client;
+use aws_sdk_s3::primitives::ByteStream;
+use tokio::sync::OnceCell;
+
+/// Stores snapshots as objects under `<prefix>/<key>` in one bucket.
+#[derive(Clone)]
+pub struct S3SnapshotStore {
+    bucket: String,
+    prefix: String,
+    client: Arc<OnceCell<Client>>,
+}
+
+impl S3SnapshotStore {
+    pub fn new(bucket: impl Into<String>, prefix: impl Into<String>) -> Self {
+        Self {
+            bucket: bucket.into(),
+            prefix: prefix.into().trim_matches('/').to_string(),
+            client: Arc::new(OnceCell::new()),
+        }
+    }
+
+    /// A store for `bucket/prefix`, the part of an `s3://` URL after the
+    /// scheme. The prefix is optional.
+    pub fn from_location(location: &str) -> Result<Self, AppError> {
+        let (bucket, prefix) = location.split_once('/').unwrap_or((location, ""));
+        if bucket.is_empty() {
+            return Err(AppError::ConfigError(
+                "Snapshot URL s3:// needs a bucket, e.g. s3://my-bucket/ares".into(),
+            ));
+        }
+        Ok(Self::new(bucket, prefix))
+    }
+
+    pub fn bucket(&self) -> &str {
+        &self.bucket
+    }
+
+    pub fn prefix(&self) -> &str {
+        &self.prefix
+    }
+
+    /// The object key for snapshot `key`.
+    fn object_key(&self, key: &str) -> Result<String, AppError> {
+        if !is_valid_key(key) {
+            return Err(AppError::InvalidInput(format!(
+                "Invalid snapshot key '{key}'"
+            )));
+        }
+        Ok(if self.prefix.is_empty() {
+            key.to_string()
+        } else {
+            format!("{}/{key}", self.prefix)
+        })
+    }
+
+    async fn client(&self) -> &Client {
+        self.client
+            .get_or_init(|| async {
+                let sdk_config = aws_config::load_defaults(BehaviorVersion::latest()).await;
+                let config = aws_sdk_s3::config::Builder::from(&sdk_config)
+                    .force_path_style(sdk_config.endpoint_url().is_some())
+                    .build();
+                Client::from_conf(config)
+            })
+            .await
+    }
+
+    fn error(&self, action: &str, key: &str, e: impl std::fmt::Display) -> AppError {
+        AppError::NetworkError(format!(
+            "Failed to {action} s3://{}/{key}: {e}",
+            self.bucket
+        ))
+    }
+}
+
+/// The content type of `key`, from the kind in its first segment.
+fn content_type(key: &str) -> &'static str {
+    key.split('/')
+        .next()
+        .and_then(|kind| kind.parse::<SnapshotKind>().ok())
+        .map_or("application/octet-stream", SnapshotKind::content_type)
+}
+
+impl SnapshotStore for S3SnapshotStore {
+    async fn put(&self, key: &str, content: &[u8]) -> Result<(), AppError> {
+        let object_key = self.object_key(key)?;
+        self.client()
+            .await
+            .put_object()
+            .bucket(&self.bucket)
+            .key(&object_key)
+            .content_type(content_type(key))
+            .body(ByteStream::from(content.to_vec()))
+            .send()
+            .await
+            .map_err(|e| self.error("write", &object_key, e.into_service_error()))?;
+        Ok(())
+    }
+
+    async fn get(&self, key: &str) -> Result<Option<Vec<u8>>, AppError> {
+        let object_key = self.object_key(key)?;
+        let output = match self
+            .client()
+            .await
+            .get_object()
+            .bucket(&self.bucket)
+            .key(&object_key)
+            .send()
+            .await
+        {
+            Ok(output) => output,
+            Err(e) => {
+                let e = e.into_service_error();
+                if e.is_no_such_key() {
+                    return Ok(None);
+                }
+                return Err(self.error("read", &object_key, e));
+            }
+        };
+        let body = output
+            .body
+            .collect()
+            .await
+            .map_err(|e| self.error("read", &object_key, e))?;
+        Ok(Some(body.into_bytes().to_vec()))
+    }
+
+    async fn delete(&self, key: &str) -> Result<(), AppError> {
+        let object_key = self.object_key(key)?;
+        self.client()
+            .await
+            .delete_object()
+            .bucket(&self.bucket)
+            .key(&object_key)
+            .send()
+            .await
+            .map_err(|e| self.error("delete", &object_key, e.into_service_error()))?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_bucket_and_prefix() {
+        let store = S3SnapshotStore::from_location("archive/ares/prod/").unwrap();
+        assert_eq!(store.bucket(), "archive");
+        assert_eq!(store.prefix(), "ares/prod");
+        assert_eq!(
+            store.object_key("html/3f/3f9a.html").unwrap(),
+            "ares/prod/html/3f/3f9a.html"
+        );
+
+        let store = S3SnapshotStore::from_location("archive").unwrap();
+        assert_eq!(
+            store.object_key("markdown/3f/3f9a.md").unwrap(),
+            "markdown/3f/3f9a.md"
+        );
+
+        assert!(S3SnapshotStore::from_location("/prefix").is_err());
+        assert!(store.object_key("../x").is_err());
+    }
+
+    #[test]
+    fn objects_carry_the_kind_content_type() {
+        assert_eq!(
+            content_type("html/3f/3f9a.html"),
+            "text/html; charset=utf-8"
+        );
+        assert_eq!(
+            content_type("markdown/3f/3f9a.md"),
+            "text/markdown; charset=utf-8"
+        );
+        assert_eq!(content_type("other/x"), "application/octet-stream");
+    }
+}
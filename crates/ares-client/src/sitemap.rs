@@ -0,0 +1,487 @@
+//! sitemap.xml loading, for seeding jobs from a site's own URL list.
+//!
+//! [`SitemapLoader`] fetches a sitemap, follows sitemap indexes into their
+//! child sitemaps, and decompresses gzipped ones (detected by their magic
+//! bytes, so both `sitemap.xml.gz` and a gzip body without the extension
+//! work). Page URLs are filtered with include/exclude regexes, deduplicated,
+//! and capped, and come back with their `<lastmod>` when the sitemap has one.
+
+use std::collections::{HashSet, VecDeque};
+use std::io::Read;
+use std::time::Duration;
+
+use chrono::{DateTime, NaiveDate, Utc};
+use flate2::read::GzDecoder;
+use quick_xml::Reader;
+use quick_xml::events::Event;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use url::Url;
+
+use ares_core::error::AppError;
+
+use crate::fetcher::validate_url;
+
+/// Default cap on the URLs one sitemap load returns.
+pub const DEFAULT_MAX_SITEMAP_URLS: usize = 10_000;
+
+/// How many levels of sitemap indexes are followed below the root.
+const MAX_INDEX_DEPTH: usize = 3;
+
+/// The sitemap protocol caps a sitemap at 50 MiB uncompressed.
+const MAX_SITEMAP_BYTES: u64 = 50 * 1024 * 1024;
+
+const GZIP_MAGIC: [u8; 2] = [0x1f, 0x8b];
+
+/// A page URL from a sitemap.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SitemapEntry {
+    pub url: String,
+    /// When the page last changed, if the sitemap says.
+    pub lastmod: Option<DateTime<Utc>>,
+}
+
+impl SitemapEntry {
+    /// Whether the page changed after `extracted_at`. Pages without a
+    /// `lastmod` always count as changed.
+    pub fn modified_since(&self, extracted_at: DateTime<Utc>) -> bool {
+        self.lastmod.is_none_or(|lastmod| lastmod > extracted_at)
+    }
+}
+
+/// Which sitemap URLs become jobs, as sent to `POST /v1/jobs/from-sitemap`.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SitemapOptions {
+    /// Keep only URLs matching at least one of these regexes.
+    pub include: Vec<String>,
+    /// Drop URLs matching any of these regexes.
+    pub exclude: Vec<String>,
+    /// Stop after this many URLs.
+    pub max_urls: Option<usize>,
+    /// Skip URLs whose `lastmod` is not newer than their latest extraction.
+    pub skip_unmodified: bool,
+}
+
+/// One parsed sitemap document.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Sitemap {
+    /// A `<urlset>` of page URLs.
+    Urls(Vec<SitemapEntry>),
+    /// A `<sitemapindex>` of child sitemap URLs.
+    Index(Vec<SitemapEntry>),
+}
+
+#[derive(Clone, Copy)]
+enum Field {
+    Loc,
+    Lastmod,
+}
+
+/// Parse a `<urlset>` or `<sitemapindex>` document. `<loc>` values are
+/// returned as written; entries without one are dropped, and unparseable
+/// `<lastmod>` values are ignored.
+pub fn parse_sitemap(xml: &str) -> Result<Sitemap, AppError> {
+    let invalid = |e: &dyn std::fmt::Display| AppError::HttpError(format!("Invalid sitemap: {e}"));
+
+    let mut reader = Reader::from_str(xml);
+    reader.config_mut().trim_text(true);
+
+    // Only `<loc>` and `<lastmod>` directly under `<url>`/`<sitemap>` count,
+    // not e.g. the `<image:loc>` of an image extension.
+    let mut depth = 0;
+    let mut index = None;
+    let mut entries = Vec::new();
+    let mut entry: Option<(String, String)> = None;
+    let mut field = None;
+    loop {
+        match reader.read_event().map_err(|e| invalid(&e))? {
+            Event::Start(start) => {
+                depth += 1;
+                field = None;
+                match (depth, start.local_name().as_ref()) {
+                    (1, b"urlset") => index = Some(false),
+                    (1, b"sitemapindex") => index = Some(true),
+                    (2, b"url" | b"sitemap") => entry = Some(Default::default()),
+                    (3, b"loc") => field = Some(Field::Loc),
+                    (3, b"lastmod") => field = Some(Field::Lastmod),
+                    _ => {}
+                }
+            }
+            Event::Text(text) => {
+                if let (Some(field), Some((loc, lastmod))) = (field, &mut entry) {
+                    let text = text.unescape().map_err(|e| invalid(&e))?;
+                    match field {
+                        Field::Loc => loc.push_str(&text),
+                        Field::Lastmod => lastmod.push_str(&text),
+                    }
+                }
+            }
+            Event::CData(data) => {
+                if let (Some(Field::Loc), Some((loc, _))) = (field, &mut entry) {
+                    loc.push_str(&String::from_utf8_lossy(&data));
+                }
+            }
+            Event::End(_) => {
+                if depth == 2
+                    && let Some((loc, lastmod)) = entry.take()
+                    && !loc.trim().is_empty()
+                {
+                    entries.push(SitemapEntry {
+                        url: loc.trim().to_string(),
+                        lastmod: parse_lastmod(lastmod.trim()),
+                    });
+                }
+                field = None;
+                depth -= 1;
+            }
+            Event::Eof => break,
+            _ => {}
+        }
+    }
+
+    match index {
+        Some(false) => Ok(Sitemap::Urls(entries)),
+        Some(true) => Ok(Sitemap::Index(entries)),
+        None => Err(invalid(&"expected <urlset> or <sitemapindex>")),
+    }
+}
+
+/// A W3C datetime: a full RFC 3339 timestamp or a bare date (taken as
+/// midnight UTC).
+fn parse_lastmod(value: &str) -> Option<DateTime<Utc>> {
+    if let Ok(datetime) = DateTime::parse_from_rfc3339(value) {
+        return Some(datetime.with_timezone(&Utc));
+    }
+    NaiveDate::parse_from_str(value, "%Y-%m-%d")
+        .ok()
+        .and_then(|date| date.and_hms_opt(0, 0, 0))
+        .map(|datetime| datetime.and_utc())
+}
+
+/// Fetches sitemaps and collects their page URLs.
+///
+/// Like [`ReqwestFetcher`](crate::ReqwestFetcher), SSRF protection is
+/// enabled by default; use [`allow_private_urls`](Self::allow_private_urls)
+/// where the user controls the machine.
+#[derive(Clone)]
+pub struct SitemapLoader {
+    client: reqwest::Client,
+    timeout: Duration,
+    ssrf_protection: bool,
+    include: Vec<Regex>,
+    exclude: Vec<Regex>,
+    max_urls: usize,
+}
+
+impl SitemapLoader {
+    pub fn new() -> Result<Self, AppError> {
+        let timeout = Duration::from_secs(30);
+        let client = reqwest::Client::builder()
+            .user_agent("Ares/0.2 (AI Scraper)")
+            .timeout(timeout)
+            .build()
+            .map_err(|e| AppError::HttpError(e.to_string()))?;
+        Ok(Self {
+            client,
+            timeout,
+            ssrf_protection: true,
+            include: Vec::new(),
+            exclude: Vec::new(),
+            max_urls: DEFAULT_MAX_SITEMAP_URLS,
+        })
+    }
+
+    /// Keep only URLs matching at least one `include` regex (all URLs when
+    /// there are none) and no `exclude` regex.
+    pub fn with_filters(
+        mut self,
+        include: &[String],
+        exclude: &[String],
+    ) -> Result<Self, AppError> {
+        let compile = |pattern: &String| {
+            Regex::new(pattern).map_err(|e| {
+                AppError::InvalidInput(format!("Invalid sitemap filter '{pattern}': {e}"))
+            })
+        };
+        self.include = include.iter().map(compile).collect::<Result<_, _>>()?;
+        self.exclude = exclude.iter().map(compile).collect::<Result<_, _>>()?;
+        Ok(self)
+    }
+
+    /// Apply the filters and cap of `options`.
+    pub fn with_options(self, options: &SitemapOptions) -> Result<Self, AppError> {
+        let loader = self.with_filters(&options.include, &options.exclude)?;
+        Ok(match options.max_urls {
+            Some(max_urls) => loader.with_max_urls(max_urls),
+            None => loader,
+        })
+    }
+
+    /// Stop after `max_urls` URLs.
+    pub fn with_max_urls(mut self, max_urls: usize) -> Self {
+        self.max_urls = max_urls;
+        self
+    }
+
+    /// Disable SSRF protection, allowing sitemaps on private/reserved IPs.
+    pub fn allow_private_urls(mut self) -> Self {
+        self.ssrf_protection = false;
+        self
+    }
+
+    /// Whether `url` passes the include/exclude filters.
+    pub fn matches(&self, url: &str) -> bool {
+        (self.include.is_empty() || self.include.iter().any(|re| re.is_match(url)))
+            && !self.exclude.iter().any(|re| re.is_match(url))
+    }
+
+    /// The page URLs of the sitemap at `url`, in document order, following
+    /// sitemap indexes. The root sitemap must load; a child sitemap that
+    /// fails is skipped with a warning.
+    pub async fn load(&self, url: &str) -> Result<Vec<SitemapEntry>, AppError> {
+        let mut entries = Vec::new();
+        let mut seen = HashSet::new();
+        let mut visited = HashSet::new();
+        let mut queue = VecDeque::from([(url.to_string(), 0)]);
+
+        while let Some((sitemap_url, depth)) = queue.pop_front() {
+            if !visited.insert(sitemap_url.clone()) {
+                continue;
+            }
+            let sitemap = match self.fetch(&sitemap_url).await {
+                Ok(sitemap) => sitemap,
+                Err(e) if depth > 0 => {
+                    tracing::warn!(sitemap = %sitemap_url, error = %e, "Skipping child sitemap");
+                    continue;
+                }
+                Err(e) => return Err(e),
+            };
+            let base = Url::parse(&sitemap_url)
+                .map_err(|e| AppError::InvalidInput(format!("Invalid sitemap URL: {e}")))?;
+
+            match sitemap {
+                Sitemap::Index(children) => {
+                    if depth >= MAX_INDEX_DEPTH {
+                        tracing::warn!(
+                            sitemap = %sitemap_url,
+                            "Sitemap indexes nested deeper than {MAX_INDEX_DEPTH} levels; ignoring"
+                        );
+                        continue;
+                    }
+                    queue.extend(
+                        children
+                            .into_iter()
+                            .filter_map(|child| base.join(&child.url).ok())
+                            .map(|child| (child.to_string(), depth + 1)),
+                    );
+                }
+                Sitemap::Urls(pages) => {
+                    for page in pages {
+                        if entries.len() >= self.max_urls {
+                            return Ok(entries);
+                        }
+                        let Ok(page_url) = base.join(&page.url) else {
+                            continue;
+                        };
+                        let page_url = page_url.to_string();
+                        if self.matches(&page_url) && seen.insert(page_url.clone()) {
+                            entries.push(SitemapEntry {
+                                url: page_url,
+                                lastmod: page.lastmod,
+                            });
+                        }
+                    }
+                }
+            }
+        }
+        Ok(entries)
+    }
+
+    async fn fetch(&self, url: &str) -> Result<Sitemap, AppError> {
+        if self.ssrf_protection {
+            validate_url(url).await?;
+        }
+        let response = self.client.get(url).send().await.map_err(|e| {
+            if e.is_timeout() {
+                AppError::Timeout(self.timeout.as_secs())
+            } else if e.is_connect() {
+                AppError::NetworkError(format!("Connection failed: {e}"))
+            } else {
+                AppError::HttpError(e.to_string())
+            }
+        })?;
+        let status = response.status();
+        if !status.is_success() {
+            return Err(AppError::HttpError(format!(
+                "HTTP {} for {url}",
+                status.as_u16()
+            )));
+        }
+        let body = response
+            .bytes()
+            .await
+            .map_err(|e| AppError::HttpError(format!("Failed to read sitemap body: {e}")))?;
+        parse_sitemap(&decode_body(&body)?)
+    }
+}
+
+/// The sitemap text in `body`, gunzipped if it is gzip data.
+fn decode_body(body: &[u8]) -> Result<String, AppError> {
+    let too_large = || AppError::HttpError(format!("Sitemap exceeds {MAX_SITEMAP_BYTES} bytes"));
+    let bytes = if body.starts_with(&GZIP_MAGIC) {
+        let mut bytes = Vec::new();
+        GzDecoder::new(body)
+            .take(MAX_SITEMAP_BYTES + 1)
+            .read_to_end(&mut bytes)
+            .map_err(|e| AppError::HttpError(format!("Invalid gzipped sitemap: {e}")))?;
+        bytes
+    } else {
+        body.to_vec()
+    };
+    if bytes.len() as u64 > MAX_SITEMAP_BYTES {
+        return Err(too_large());
+    }
+    String::from_utf8(bytes)
+        .map_err(|e| AppError::HttpError(format!("Sitemap is not valid UTF-8: {e}")))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Write;
+
+    use axum::Router;
+    use axum::routing::get;
+    use flate2::Compression;
+    use flate2::write::GzEncoder;
+
+    use super::*;
+
+    const URLSET: &str = include_str!("../tests/fixtures/sitemaps/urlset.xml");
+    const INDEX: &str = include_str!("../tests/fixtures/sitemaps/index.xml");
+
+    fn gzip(text: &str) -> Vec<u8> {
+        let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+        encoder.write_all(text.as_bytes()).unwrap();
+        encoder.finish().unwrap()
+    }
+
+    fn urls(entries: &[SitemapEntry]) -> Vec<&str> {
+        entries.iter().map(|entry| entry.url.as_str()).collect()
+    }
+
+    #[test]
+    fn parses_urlsets_with_lastmod() {
+        let Sitemap::Urls(entries) = parse_sitemap(URLSET).unwrap() else {
+            panic!("expected a urlset");
+        };
+        assert_eq!(
+            urls(&entries),
+            [
+                "https://shop.example/products/1",
+                "https://shop.example/products/2?color=red&size=m",
+                "https://shop.example/about",
+                "https://shop.example/products/3",
+            ]
+        );
+        assert_eq!(
+            entries[0].lastmod,
+            Some("2026-03-01T10:00:00Z".parse().unwrap())
+        );
+        assert_eq!(
+            entries[1].lastmod,
+            Some("2026-02-01T00:00:00Z".parse().unwrap())
+        );
+        // Unparseable and missing lastmods.
+        assert_eq!(entries[2].lastmod, None);
+        assert_eq!(entries[3].lastmod, None);
+    }
+
+    #[test]
+    fn parses_sitemap_indexes() {
+        let Sitemap::Index(children) = parse_sitemap(INDEX).unwrap() else {
+            panic!("expected a sitemap index");
+        };
+        assert_eq!(urls(&children), ["/products.xml.gz", "/pages.xml"]);
+    }
+
+    #[test]
+    fn rejects_documents_that_are_not_sitemaps() {
+        for xml in ["<html><body>nope</body></html>", "not xml"] {
+            assert!(parse_sitemap(xml).is_err(), "{xml}");
+        }
+    }
+
+    #[test]
+    fn decodes_gzipped_bodies() {
+        assert_eq!(decode_body(&gzip(URLSET)).unwrap(), URLSET);
+        assert_eq!(decode_body(URLSET.as_bytes()).unwrap(), URLSET);
+    }
+
+    #[test]
+    fn modified_since_treats_missing_lastmod_as_changed() {
+        let extracted_at = "2026-02-15T00:00:00Z".parse().unwrap();
+        let entry = |lastmod: Option<&str>| SitemapEntry {
+            url: "https://a.example".into(),
+            lastmod: lastmod.map(|value| value.parse().unwrap()),
+        };
+        assert!(entry(Some("2026-03-01T00:00:00Z")).modified_since(extracted_at));
+        assert!(!entry(Some("2026-02-01T00:00:00Z")).modified_since(extracted_at));
+        assert!(entry(None).modified_since(extracted_at));
+    }
+
+    #[test]
+    fn invalid_filters_are_rejected() {
+        let err = SitemapLoader::new()
+            .unwrap()
+            .with_filters(&["(".into()], &[])
+            .err()
+            .unwrap();
+        assert!(matches!(err, AppError::InvalidInput(_)), "{err}");
+    }
+
+    #[tokio::test]
+    async fn loader_follows_indexes_filters_and_caps() {
+        let pages = r#"<?xml version="1.0" encoding="UTF-8"?>
+<urlset xmlns="http://www.sitemaps.org/schemas/sitemap/0.9">
+  <url><loc>https://shop.example/about</loc></url>
+  <url><loc>https://shop.example/products/1</loc></url>
+</urlset>"#;
+        let router = Router::new()
+            .route("/sitemap.xml", get(|| async { INDEX }))
+            .route("/products.xml.gz", get(|| async { gzip(URLSET) }))
+            .route("/pages.xml", get(move || async move { pages }));
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let root = format!("http://{}/sitemap.xml", listener.local_addr().unwrap());
+        tokio::spawn(async move { axum::serve(listener, router).await.unwrap() });
+
+        let loader = SitemapLoader::new().unwrap().allow_private_urls();
+        let entries = loader.load(&root).await.unwrap();
+        // products/1 appears in both child sitemaps but is returned once.
+        assert_eq!(entries.len(), 4);
+
+        let loader = loader
+            .with_filters(&["/products/".into()], &["color=".into()])
+            .unwrap();
+        let entries = loader.load(&root).await.unwrap();
+        assert_eq!(
+            urls(&entries),
+            [
+                "https://shop.example/products/1",
+                "https://shop.example/products/3"
+            ]
+        );
+
+        let entries = loader.with_max_urls(1).load(&root).await.unwrap();
+        assert_eq!(urls(&entries), ["https://shop.example/products/1"]);
+    }
+
+    #[tokio::test]
+    async fn loader_blocks_private_sitemaps_by_default() {
+        let err = SitemapLoader::new()
+            .unwrap()
+            .load("http://127.0.0.1:1/sitemap.xml")
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("SSRF"), "{err}");
+    }
+}
@@ -0,0 +1,221 @@
+//! Snapshot stores: where raw HTML and cleaned Markdown are archived.
+//!
+//! [`Snapshots`] is the [`SnapshotStore`] the CLI and API hand to the scrape
+//! service. It is built from a URL (`ARES_SNAPSHOT_URL`):
+//!
+//! - `file:///var/lib/ares/snapshots` — [`FsSnapshotStore`], for development
+//! - `s3://bucket/prefix` — [`S3SnapshotStore`], with the `s3` feature
+//!
+//! Without a URL it stores nothing.
+//!
+//! [`S3SnapshotStore`]: crate::s3::S3SnapshotStore
+
+use std::io::ErrorKind;
+use std::path::{Path, PathBuf};
+
+use ares_core::error::AppError;
+use ares_core::snapshot::is_valid_key;
+use ares_core::traits::SnapshotStore;
+
+fn check_key(key: &str) -> Result<(), AppError> {
+    if is_valid_key(key) {
+        Ok(())
+    } else {
+        Err(AppError::InvalidInput(format!(
+            "Invalid snapshot key '{key}'"
+        )))
+    }
+}
+
+/// Stores snapshots as files under a root directory, one file per key.
+#[derive(Debug, Clone)]
+pub struct FsSnapshotStore {
+    root: PathBuf,
+}
+
+impl FsSnapshotStore {
+    pub fn new(root: impl Into<PathBuf>) -> Self {
+        Self { root: root.into() }
+    }
+
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+
+    fn path(&self, key: &str) -> Result<PathBuf, AppError> {
+        check_key(key)?;
+        Ok(self.root.join(key))
+    }
+}
+
+fn io_error(action: &str, path: &Path, e: std::io::Error) -> AppError {
+    AppError::Generic(format!(
+        "Failed to {action} snapshot {}: {e}",
+        path.display()
+    ))
+}
+
+impl SnapshotStore for FsSnapshotStore {
+    /// Write to a temporary file and rename it into place, so a reader never
+    /// sees a partial snapshot.
+    async fn put(&self, key: &str, content: &[u8]) -> Result<(), AppError> {
+        let path = self.path(key)?;
+        if let Some(parent) = path.parent() {
+            tokio::fs::create_dir_all(parent)
+                .await
+                .map_err(|e| io_error("create directory for", &path, e))?;
+        }
+        let tmp = path.with_extension(format!("tmp-{}", uuid::Uuid::new_v4()));
+        tokio::fs::write(&tmp, content)
+            .await
+            .map_err(|e| io_error("write", &path, e))?;
+        if let Err(e) = tokio::fs::rename(&tmp, &path).await {
+            let _ = tokio::fs::remove_file(&tmp).await;
+            return Err(io_error("write", &path, e));
+        }
+        Ok(())
+    }
+
+    async fn get(&self, key: &str) -> Result<Option<Vec<u8>>, AppError> {
+        let path = self.path(key)?;
+        match tokio::fs::read(&path).await {
+            Ok(content) => Ok(Some(content)),
+            Err(e) if e.kind() == ErrorKind::NotFound => Ok(None),
+            Err(e) => Err(io_error("read", &path, e)),
+        }
+    }
+
+    async fn delete(&self, key: &str) -> Result<(), AppError> {
+        let path = self.path(key)?;
+        match tokio::fs::remove_file(&path).await {
+            Err(e) if e.kind() != ErrorKind::NotFound => Err(io_error("delete", &path, e)),
+            _ => Ok(()),
+        }
+    }
+}
+
+/// The configured snapshot store, if any.
+#[derive(Clone, Default)]
+pub enum Snapshots {
+    /// Snapshots are off.
+    #[default]
+    Off,
+    Fs(FsSnapshotStore),
+    #[cfg(feature = "s3")]
+    S3(crate::s3::S3SnapshotStore),
+}
+
+impl Snapshots {
+    /// The store for `url`, or [`Snapshots::Off`] when it is `None`.
+    ///
+    /// Fails on an unsupported scheme, or an `s3://` URL when the `s3`
+    /// feature is off.
+    pub fn new(url: Option<&str>) -> Result<Self, AppError> {
+        let Some(url) = url else {
+            return Ok(Self::Off);
+        };
+        if let Some(path) = url.strip_prefix("file://") {
+            if path.is_empty() {
+                return Err(AppError::ConfigError(
+                    "Snapshot URL file:// needs a directory, e.g. file:///var/lib/ares".into(),
+                ));
+            }
+            return Ok(Self::Fs(FsSnapshotStore::new(path)));
+        }
+        if let Some(location) = url.strip_prefix("s3://") {
+            #[cfg(feature = "s3")]
+            return crate::s3::S3SnapshotStore::from_location(location).map(Self::S3);
+            #[cfg(not(feature = "s3"))]
+            {
+                let _ = location;
+                return Err(AppError::ConfigError(crate::S3_FEATURE_MSG.to_string()));
+            }
+        }
+        Err(AppError::ConfigError(format!(
+            "Unsupported snapshot URL '{url}' (expected file:///path or s3://bucket/prefix)"
+        )))
+    }
+
+    /// Whether snapshots are stored anywhere.
+    pub fn is_enabled(&self) -> bool {
+        !matches!(self, Self::Off)
+    }
+}
+
+impl SnapshotStore for Snapshots {
+    async fn put(&self, key: &str, content: &[u8]) -> Result<(), AppError> {
+        match self {
+            Self::Off => Ok(()),
+            Self::Fs(store) => store.put(key, content).await,
+            #[cfg(feature = "s3")]
+            Self::S3(store) => store.put(key, content).await,
+        }
+    }
+
+    async fn get(&self, key: &str) -> Result<Option<Vec<u8>>, AppError> {
+        match self {
+            Self::Off => Ok(None),
+            Self::Fs(store) => store.get(key).await,
+            #[cfg(feature = "s3")]
+            Self::S3(store) => store.get(key).await,
+        }
+    }
+
+    async fn delete(&self, key: &str) -> Result<(), AppError> {
+        match self {
+            Self::Off => Ok(()),
+            Self::Fs(store) => store.delete(key).await,
+            #[cfg(feature = "s3")]
+            Self::S3(store) => store.delete(key).await,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn fs_store_round_trips_objects() {
+        let dir = tempfile::tempdir().unwrap();
+        let store = FsSnapshotStore::new(dir.path());
+        let key = "html/3f/3f9a.html";
+
+        assert_eq!(store.get(key).await.unwrap(), None);
+        store.put(key, b"<p>one</p>").await.unwrap();
+        store.put(key, b"<p>two</p>").await.unwrap();
+        assert_eq!(store.get(key).await.unwrap().unwrap(), b"<p>two</p>");
+        assert!(dir.path().join(key).is_file());
+
+        store.delete(key).await.unwrap();
+        store.delete(key).await.unwrap();
+        assert_eq!(store.get(key).await.unwrap(), None);
+    }
+
+    #[tokio::test]
+    async fn fs_store_rejects_keys_outside_the_root() {
+        let dir = tempfile::tempdir().unwrap();
+        let store = FsSnapshotStore::new(dir.path().join("store"));
+        let err = store.put("../escape.html", b"x").await.unwrap_err();
+        assert!(matches!(err, AppError::InvalidInput(_)), "{err}");
+        assert!(!dir.path().join("escape.html").exists());
+    }
+
+    #[test]
+    fn parses_snapshot_urls() {
+        assert!(!Snapshots::new(None).unwrap().is_enabled());
+        match Snapshots::new(Some("file:///tmp/ares")).unwrap() {
+            Snapshots::Fs(store) => assert_eq!(store.root(), Path::new("/tmp/ares")),
+            _ => panic!("expected a filesystem store"),
+        }
+        assert!(Snapshots::new(Some("file://")).is_err());
+        assert!(Snapshots::new(Some("ftp://host/dir")).is_err());
+    }
+
+    #[cfg(not(feature = "s3"))]
+    #[test]
+    fn s3_url_without_the_feature_is_a_config_error() {
+        let err = Snapshots::new(Some("s3://bucket/prefix")).err().unwrap();
+        assert!(matches!(err, AppError::ConfigError(_)), "{err}");
+    }
+}
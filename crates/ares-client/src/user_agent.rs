@@ -57,10 +57,75 @@ impl UserAgentPool {
     }
 }
 
+/// A fixed User-Agent for `BrowserFetcher::with_user_agent`: a preset, or
+/// any string.
+///
+/// Parses from `desktop`, `mobile` or `googlebot` (any case); anything else
+/// is taken as a custom User-Agent string.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum UserAgent {
+    /// Chrome on Windows.
+    Desktop,
+    /// Chrome on Android.
+    Mobile,
+    /// Google's crawler, for sites that serve it pre-rendered content.
+    Googlebot,
+    Custom(String),
+}
+
+impl UserAgent {
+    /// The User-Agent header value.
+    pub fn as_str(&self) -> &str {
+        match self {
+            Self::Desktop => {
+                "Mozilla/5.0 (Windows NT 10.0; Win64; x64) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/131.0.0.0 Safari/537.36"
+            }
+            Self::Mobile => {
+                "Mozilla/5.0 (Linux; Android 14; Pixel 8) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/131.0.0.0 Mobile Safari/537.36"
+            }
+            Self::Googlebot => {
+                "Mozilla/5.0 (compatible; Googlebot/2.1; +http://www.google.com/bot.html)"
+            }
+            Self::Custom(ua) => ua,
+        }
+    }
+}
+
+impl From<&str> for UserAgent {
+    fn from(value: &str) -> Self {
+        match value.trim().to_ascii_lowercase().as_str() {
+            "desktop" => Self::Desktop,
+            "mobile" => Self::Mobile,
+            "googlebot" => Self::Googlebot,
+            _ => Self::Custom(value.to_string()),
+        }
+    }
+}
+
+impl std::str::FromStr for UserAgent {
+    type Err = std::convert::Infallible;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(Self::from(s))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn user_agent_presets_parse_by_name() {
+        assert_eq!(UserAgent::from("Mobile"), UserAgent::Mobile);
+        assert_eq!(UserAgent::from(" googlebot "), UserAgent::Googlebot);
+        assert!(UserAgent::Desktop.as_str().contains("Windows NT"));
+        assert!(UserAgent::Mobile.as_str().contains("Mobile Safari"));
+        assert!(UserAgent::Googlebot.as_str().contains("Googlebot/2.1"));
+
+        let custom = UserAgent::from("MyBot/1.0 (+https://example.com)");
+        assert_eq!(custom.as_str(), "MyBot/1.0 (+https://example.com)");
+    }
+
     #[test]
     fn pool_returns_valid_ua() {
         let pool = UserAgentPool;
@@ -19,6 +19,80 @@ pub(crate) fn truncate_for_error(body: &str) -> String {
     format!("{prefix}… (truncated)")
 }
 
+/// Fix the near-JSON mistakes LLMs commonly make: trailing commas, unquoted
+/// object keys, and Python's `True`/`False`/`None`. String contents are left
+/// alone. Returns `None` if there was nothing to fix.
+///
+/// This is a small subset of a real repairer; anything else (truncated
+/// output, single quotes, comments) is left for the parser to reject.
+pub(crate) fn repair_json(raw: &str) -> Option<String> {
+    let chars: Vec<char> = raw.chars().collect();
+    let mut out = String::with_capacity(raw.len());
+    let mut changed = false;
+    let mut i = 0;
+
+    let next_significant = |from: usize| chars[from..].iter().find(|c| !c.is_whitespace());
+
+    while i < chars.len() {
+        let c = chars[i];
+        match c {
+            '"' => {
+                // Copy the string through, honoring escapes.
+                out.push(c);
+                i += 1;
+                while i < chars.len() {
+                    out.push(chars[i]);
+                    match chars[i] {
+                        '\\' if i + 1 < chars.len() => {
+                            out.push(chars[i + 1]);
+                            i += 2;
+                        }
+                        '"' => {
+                            i += 1;
+                            break;
+                        }
+                        _ => i += 1,
+                    }
+                }
+            }
+            ',' if matches!(next_significant(i + 1), Some('}' | ']')) => {
+                changed = true;
+                i += 1;
+            }
+            c if c.is_ascii_alphabetic() || c == '_' || c == '$' => {
+                let start = i;
+                while i < chars.len()
+                    && (chars[i].is_ascii_alphanumeric() || chars[i] == '_' || chars[i] == '$')
+                {
+                    i += 1;
+                }
+                let word: String = chars[start..i].iter().collect();
+                if next_significant(i) == Some(&':') {
+                    out.push('"');
+                    out.push_str(&word);
+                    out.push('"');
+                    changed = true;
+                } else {
+                    let fixed = match word.as_str() {
+                        "True" => "true",
+                        "False" => "false",
+                        "None" => "null",
+                        other => other,
+                    };
+                    changed |= fixed != word;
+                    out.push_str(fixed);
+                }
+            }
+            _ => {
+                out.push(c);
+                i += 1;
+            }
+        }
+    }
+
+    changed.then_some(out)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -39,4 +113,29 @@ mod tests {
             MAX_ERROR_BODY_CHARS + "… (truncated)".chars().count()
         );
     }
+
+    #[test]
+    fn repair_fixes_common_llm_mistakes() {
+        let raw = r#"{title: "Hello", "tags": ["a", "b",], "draft": False, "author": None,}"#;
+        let repaired = repair_json(raw).unwrap();
+        assert_eq!(
+            serde_json::from_str::<serde_json::Value>(&repaired).unwrap(),
+            serde_json::json!({"title": "Hello", "tags": ["a", "b"], "draft": false, "author": null})
+        );
+    }
+
+    #[test]
+    fn repair_leaves_strings_alone() {
+        let raw = r#"{"text": "True, None, {a: 1,}", "quote": "say \"hi,\"",}"#;
+        let repaired = repair_json(raw).unwrap();
+        assert_eq!(
+            serde_json::from_str::<serde_json::Value>(&repaired).unwrap(),
+            serde_json::json!({"text": "True, None, {a: 1,}", "quote": "say \"hi,\""})
+        );
+    }
+
+    #[test]
+    fn repair_returns_none_for_valid_json() {
+        assert_eq!(repair_json(r#"{"ok": true, "n": null}"#), None);
+    }
 }
@@ -0,0 +1,155 @@
+use std::fmt;
+use std::str::FromStr;
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::models::compute_hash;
+
+/// Prefix on generated API keys, to make them recognisable in configs and logs.
+pub const API_KEY_PREFIX: &str = "ares_";
+
+/// Permission granted to an API key.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum ApiKeyScope {
+    /// Run one-shot scrapes (`POST /v1/scrape`, schema sample validation).
+    #[serde(rename = "scrape")]
+    Scrape,
+    /// Create, cancel, and retry jobs and crawls.
+    #[serde(rename = "jobs:write")]
+    JobsWrite,
+    /// Create, update, and delete schemas.
+    #[serde(rename = "schemas:write")]
+    SchemasWrite,
+    /// Full access, including API key management. Implies every other scope.
+    #[serde(rename = "admin")]
+    Admin,
+}
+
+impl ApiKeyScope {
+    pub const ALL: [ApiKeyScope; 4] = [
+        ApiKeyScope::Scrape,
+        ApiKeyScope::JobsWrite,
+        ApiKeyScope::SchemasWrite,
+        ApiKeyScope::Admin,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ApiKeyScope::Scrape => "scrape",
+            ApiKeyScope::JobsWrite => "jobs:write",
+            ApiKeyScope::SchemasWrite => "schemas:write",
+            ApiKeyScope::Admin => "admin",
+        }
+    }
+}
+
+impl fmt::Display for ApiKeyScope {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+impl FromStr for ApiKeyScope {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().as_str() {
+            "scrape" => Ok(ApiKeyScope::Scrape),
+            "jobs:write" => Ok(ApiKeyScope::JobsWrite),
+            "schemas:write" => Ok(ApiKeyScope::SchemasWrite),
+            "admin" => Ok(ApiKeyScope::Admin),
+            _ => Err(format!(
+                "Unknown API key scope: {s} (expected scrape, jobs:write, schemas:write, or admin)"
+            )),
+        }
+    }
+}
+
+/// A stored API key. Only the SHA-256 hash of the key value is kept.
+#[derive(Debug, Clone, Serialize)]
+pub struct ApiKey {
+    pub id: Uuid,
+    pub name: String,
+    #[serde(skip)]
+    pub key_hash: String,
+    pub scopes: Vec<ApiKeyScope>,
+    pub created_at: DateTime<Utc>,
+    pub revoked_at: Option<DateTime<Utc>>,
+    pub last_used_at: Option<DateTime<Utc>>,
+}
+
+impl ApiKey {
+    pub fn is_revoked(&self) -> bool {
+        self.revoked_at.is_some()
+    }
+
+    /// Whether this key grants `scope` (`admin` grants everything).
+    pub fn has_scope(&self, scope: ApiKeyScope) -> bool {
+        self.scopes
+            .iter()
+            .any(|s| *s == scope || *s == ApiKeyScope::Admin)
+    }
+}
+
+/// Generate a new random API key value (`ares_` + 64 hex chars).
+///
+/// Randomness comes from two v4 UUIDs, which are drawn from the OS CSPRNG.
+pub fn generate_api_key() -> String {
+    format!(
+        "{API_KEY_PREFIX}{}{}",
+        Uuid::new_v4().simple(),
+        Uuid::new_v4().simple()
+    )
+}
+
+/// Hash an API key value for storage and lookup.
+pub fn hash_api_key(key: &str) -> String {
+    compute_hash(key)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn key_with(scopes: Vec<ApiKeyScope>) -> ApiKey {
+        ApiKey {
+            id: Uuid::new_v4(),
+            name: "test".into(),
+            key_hash: hash_api_key("k"),
+            scopes,
+            created_at: Utc::now(),
+            revoked_at: None,
+            last_used_at: None,
+        }
+    }
+
+    #[test]
+    fn scope_roundtrips_through_str() {
+        for scope in ApiKeyScope::ALL {
+            assert_eq!(scope.as_str().parse::<ApiKeyScope>().unwrap(), scope);
+        }
+        assert!("jobs:read".parse::<ApiKeyScope>().is_err());
+    }
+
+    #[test]
+    fn admin_scope_implies_all() {
+        let key = key_with(vec![ApiKeyScope::Admin]);
+        assert!(ApiKeyScope::ALL.iter().all(|s| key.has_scope(*s)));
+
+        let key = key_with(vec![ApiKeyScope::Scrape]);
+        assert!(key.has_scope(ApiKeyScope::Scrape));
+        assert!(!key.has_scope(ApiKeyScope::JobsWrite));
+    }
+
+    #[test]
+    fn generated_keys_are_unique_and_prefixed() {
+        let a = generate_api_key();
+        let b = generate_api_key();
+        assert_ne!(a, b);
+        assert!(a.starts_with(API_KEY_PREFIX));
+        assert_eq!(a.len(), API_KEY_PREFIX.len() + 64);
+        assert_ne!(hash_api_key(&a), hash_api_key(&b));
+    }
+}
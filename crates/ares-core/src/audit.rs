@@ -0,0 +1,280 @@
+//! Audit trail of administrative actions taken through the HTTP API.
+//!
+//! Each entry names who acted (an API key ID, or a fingerprint of the
+//! bootstrap `ARES_ADMIN_TOKEN`), what they did, and to which resource. The
+//! payload is kept as a short summary, never the request body: schemas are
+//! reduced to their shape by [`schema_summary`], and [`redact_summary`]
+//! masks credential-like fields and example values in whatever remains.
+
+use std::fmt;
+use std::str::FromStr;
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::api_key::hash_api_key;
+use crate::logging::REDACTED;
+
+/// Hex characters of the token hash kept as its fingerprint.
+const FINGERPRINT_LEN: usize = 12;
+
+/// Field names whose values are credentials, matched case-insensitively
+/// as substrings.
+const CREDENTIAL_FIELDS: &[&str] = &[
+    "password",
+    "secret",
+    "token",
+    "api_key",
+    "api-key",
+    "apikey",
+    "authorization",
+    "cookie",
+    "credential",
+];
+
+/// JSON Schema keywords that can carry sample data.
+const EXAMPLE_FIELDS: &[&str] = &["example", "examples", "default", "const", "enum"];
+
+/// An administrative action recorded in the audit log.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum AuditAction {
+    #[serde(rename = "schema.create")]
+    SchemaCreate,
+    #[serde(rename = "schema.update")]
+    SchemaUpdate,
+    #[serde(rename = "schema.delete")]
+    SchemaDelete,
+    #[serde(rename = "schema.set_latest")]
+    SchemaSetLatest,
+    #[serde(rename = "job.cancel")]
+    JobCancel,
+    #[serde(rename = "job.bulk_cancel")]
+    JobBulkCancel,
+    #[serde(rename = "job.retry")]
+    JobRetry,
+    /// A pending job's schema replaced.
+    #[serde(rename = "job.update_schema")]
+    JobUpdateSchema,
+    /// Finished jobs deleted by tag.
+    #[serde(rename = "job.prune")]
+    JobPrune,
+    /// Extractions deleted by URL or schema.
+    #[serde(rename = "extraction.prune")]
+    ExtractionPrune,
+    /// An extraction marked or unmarked as golden.
+    #[serde(rename = "extraction.golden")]
+    ExtractionGolden,
+    #[serde(rename = "api_key.create")]
+    ApiKeyCreate,
+    #[serde(rename = "api_key.revoke")]
+    ApiKeyRevoke,
+    #[serde(rename = "circuit_breaker.reset")]
+    CircuitBreakerReset,
+}
+
+impl AuditAction {
+    pub const ALL: [AuditAction; 14] = [
+        AuditAction::SchemaCreate,
+        AuditAction::SchemaUpdate,
+        AuditAction::SchemaDelete,
+        AuditAction::SchemaSetLatest,
+        AuditAction::JobCancel,
+        AuditAction::JobBulkCancel,
+        AuditAction::JobRetry,
+        AuditAction::JobUpdateSchema,
+        AuditAction::JobPrune,
+        AuditAction::ExtractionPrune,
+        AuditAction::ExtractionGolden,
+        AuditAction::ApiKeyCreate,
+        AuditAction::ApiKeyRevoke,
+        AuditAction::CircuitBreakerReset,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            AuditAction::SchemaCreate => "schema.create",
+            AuditAction::SchemaUpdate => "schema.update",
+            AuditAction::SchemaDelete => "schema.delete",
+            AuditAction::SchemaSetLatest => "schema.set_latest",
+            AuditAction::JobCancel => "job.cancel",
+            AuditAction::JobBulkCancel => "job.bulk_cancel",
+            AuditAction::JobRetry => "job.retry",
+            AuditAction::JobUpdateSchema => "job.update_schema",
+            AuditAction::JobPrune => "job.prune",
+            AuditAction::ExtractionPrune => "extraction.prune",
+            AuditAction::ExtractionGolden => "extraction.golden",
+            AuditAction::ApiKeyCreate => "api_key.create",
+            AuditAction::ApiKeyRevoke => "api_key.revoke",
+            AuditAction::CircuitBreakerReset => "circuit_breaker.reset",
+        }
+    }
+
+    /// The kind of resource acted on: the part of the action name before
+    /// the dot.
+    pub fn resource_type(&self) -> &'static str {
+        let action = self.as_str();
+        action
+            .split_once('.')
+            .map_or(action, |(resource, _)| resource)
+    }
+}
+
+impl fmt::Display for AuditAction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+impl FromStr for AuditAction {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim().to_lowercase();
+        Self::ALL
+            .into_iter()
+            .find(|action| action.as_str() == s)
+            .ok_or_else(|| {
+                let known: Vec<_> = Self::ALL.iter().map(AuditAction::as_str).collect();
+                format!(
+                    "Unknown audit action: {s} (expected one of {})",
+                    known.join(", ")
+                )
+            })
+    }
+}
+
+/// An audit entry ready to be stored.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct NewAuditEntry {
+    /// The acting API key, or `None` for the bootstrap admin token.
+    pub api_key_id: Option<Uuid>,
+    /// [`token_fingerprint`] of the bootstrap admin token, when it was used.
+    pub token_fingerprint: Option<String>,
+    pub action: AuditAction,
+    /// ID or name of the resource acted on; `None` for bulk actions.
+    pub resource_id: Option<String>,
+    /// The `x-request-id` of the request that took the action.
+    pub request_id: Option<String>,
+    /// What was done, already passed through [`redact_summary`].
+    pub summary: serde_json::Value,
+}
+
+/// A stored audit entry.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AuditEntry {
+    pub id: i64,
+    pub api_key_id: Option<Uuid>,
+    pub token_fingerprint: Option<String>,
+    /// An [`AuditAction`] name, kept as text so entries written by newer
+    /// servers still load.
+    pub action: String,
+    pub resource_type: String,
+    pub resource_id: Option<String>,
+    pub request_id: Option<String>,
+    pub summary: serde_json::Value,
+    pub created_at: DateTime<Utc>,
+}
+
+/// Short, stable identifier for a bearer token that doesn't reveal it: the
+/// first 12 hex characters of its SHA-256 hash.
+pub fn token_fingerprint(token: &str) -> String {
+    let mut hash = hash_api_key(token);
+    hash.truncate(FINGERPRINT_LEN);
+    hash
+}
+
+/// The shape of a JSON Schema without any of its content: its top-level
+/// type, property names, and required fields.
+pub fn schema_summary(schema: &serde_json::Value) -> serde_json::Value {
+    let properties: Vec<&str> = schema
+        .get("properties")
+        .and_then(|p| p.as_object())
+        .map(|p| p.keys().map(String::as_str).collect())
+        .unwrap_or_default();
+    serde_json::json!({
+        "type": schema.get("type"),
+        "properties": properties,
+        "required": schema.get("required").and_then(|r| r.as_array()).map_or(0, Vec::len),
+    })
+}
+
+/// `summary` with the values of credential-like fields and JSON Schema
+/// example keywords replaced by `[REDACTED]`, at any depth.
+pub fn redact_summary(summary: serde_json::Value) -> serde_json::Value {
+    match summary {
+        serde_json::Value::Object(fields) => fields
+            .into_iter()
+            .map(|(key, value)| {
+                let lower = key.to_lowercase();
+                let sensitive = CREDENTIAL_FIELDS.iter().any(|f| lower.contains(f))
+                    || EXAMPLE_FIELDS.contains(&lower.as_str());
+                let value = if sensitive {
+                    serde_json::Value::String(REDACTED.to_string())
+                } else {
+                    redact_summary(value)
+                };
+                (key, value)
+            })
+            .collect(),
+        serde_json::Value::Array(items) => items.into_iter().map(redact_summary).collect(),
+        other => other,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn action_roundtrips_through_str() {
+        for action in AuditAction::ALL {
+            assert_eq!(action.as_str().parse::<AuditAction>().unwrap(), action);
+        }
+        assert_eq!(AuditAction::ApiKeyRevoke.resource_type(), "api_key");
+        assert_eq!(AuditAction::JobBulkCancel.resource_type(), "job");
+        assert!("schema.read".parse::<AuditAction>().is_err());
+    }
+
+    #[test]
+    fn schema_summary_keeps_only_the_shape() {
+        let schema = serde_json::json!({
+            "type": "object",
+            "properties": {
+                "email": {"type": "string", "examples": ["jane@example.com"]},
+                "name": {"type": "string", "default": "Jane Doe"}
+            },
+            "required": ["email"]
+        });
+        let summary = schema_summary(&schema);
+        assert_eq!(
+            summary,
+            serde_json::json!({"type": "object", "properties": ["email", "name"], "required": 1})
+        );
+        assert!(!summary.to_string().contains("jane"));
+    }
+
+    #[test]
+    fn credentials_and_examples_are_redacted() {
+        let summary = redact_summary(serde_json::json!({
+            "name": "ingest",
+            "headers": {"Authorization": "Bearer abc", "X-Api-Key": "k"},
+            "fetch": [{"password": "hunter2", "url": "https://example.com"}],
+            "schema": {"examples": [{"ssn": "123"}]},
+        }));
+        assert_eq!(summary["name"], "ingest");
+        assert_eq!(summary["headers"]["Authorization"], REDACTED);
+        assert_eq!(summary["headers"]["X-Api-Key"], REDACTED);
+        assert_eq!(summary["fetch"][0]["password"], REDACTED);
+        assert_eq!(summary["fetch"][0]["url"], "https://example.com");
+        assert_eq!(summary["schema"]["examples"], REDACTED);
+    }
+
+    #[test]
+    fn fingerprints_are_short_and_stable() {
+        let fingerprint = token_fingerprint("admin-token-value");
+        assert_eq!(fingerprint.len(), 12);
+        assert_eq!(fingerprint, token_fingerprint("admin-token-value"));
+        assert_ne!(fingerprint, token_fingerprint("other-token-value"));
+    }
+}
@@ -0,0 +1,404 @@
+//! Daily spending caps on LLM calls.
+//!
+//! A [`BudgetGuard`] checks today's usage (UTC) against [`BudgetLimits`]
+//! before each LLM call and adds the call's tokens and estimated cost
+//! afterwards. Counters live in a [`UsageStore`], keyed by day, so every
+//! worker and API server sharing the store shares the budget. Once a cap is
+//! reached, calls fail with [`AppError::BudgetExceeded`] until midnight UTC.
+//!
+//! Wrap an extractor in a [`BudgetedExtractor`] (or a factory in a
+//! [`BudgetedExtractorFactory`]) to apply the guard. The check and the
+//! increment are separate steps, so concurrent callers can each overshoot a
+//! cap by the one call they had in flight.
+
+use std::future::Future;
+
+use chrono::{DateTime, NaiveDate, NaiveTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::error::AppError;
+use crate::models::{ExtractionOutcome, Usage};
+use crate::traits::{Extractor, ExtractorFactory};
+
+/// Estimated USD cost of a call to a model, from its input and output token
+/// counts, or `None` when the model has no known price.
+pub type PriceFn = fn(&str, u64, u64) -> Option<f64>;
+
+/// Daily caps. `None` leaves that dimension uncapped.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub struct BudgetLimits {
+    /// LLM calls per day, successful or not.
+    pub max_calls: Option<u64>,
+    /// Prompt plus completion tokens per day.
+    pub max_tokens: Option<u64>,
+    /// Estimated USD spend per day. Calls to models without a known price
+    /// count as free.
+    pub max_cost_usd: Option<f64>,
+}
+
+impl BudgetLimits {
+    pub fn with_max_calls(mut self, max_calls: u64) -> Self {
+        self.max_calls = Some(max_calls);
+        self
+    }
+
+    pub fn with_max_tokens(mut self, max_tokens: u64) -> Self {
+        self.max_tokens = Some(max_tokens);
+        self
+    }
+
+    pub fn with_max_cost_usd(mut self, max_cost_usd: f64) -> Self {
+        self.max_cost_usd = Some(max_cost_usd);
+        self
+    }
+
+    /// True when no cap is set.
+    pub fn is_unlimited(&self) -> bool {
+        self.max_calls.is_none() && self.max_tokens.is_none() && self.max_cost_usd.is_none()
+    }
+
+    /// Which cap `usage` has reached, if any.
+    pub fn exceeded_by(&self, usage: &DailyUsage) -> Option<String> {
+        if let Some(max) = self.max_calls
+            && usage.llm_calls >= max
+        {
+            return Some(format!("{} of {max} daily LLM calls used", usage.llm_calls));
+        }
+        if let Some(max) = self.max_tokens
+            && usage.tokens >= max
+        {
+            return Some(format!("{} of {max} daily tokens used", usage.tokens));
+        }
+        if let Some(max) = self.max_cost_usd
+            && usage.cost_usd >= max
+        {
+            return Some(format!(
+                "${:.2} of ${max:.2} daily estimated spend used",
+                usage.cost_usd
+            ));
+        }
+        None
+    }
+}
+
+/// LLM usage counted for one UTC day. Also used as the increment passed to
+/// [`UsageStore::add_usage`].
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct DailyUsage {
+    pub day: NaiveDate,
+    pub llm_calls: u64,
+    pub tokens: u64,
+    pub cost_usd: f64,
+}
+
+impl DailyUsage {
+    /// No usage on `day`.
+    pub fn empty(day: NaiveDate) -> Self {
+        Self {
+            day,
+            llm_calls: 0,
+            tokens: 0,
+            cost_usd: 0.0,
+        }
+    }
+}
+
+/// Persistent per-day usage counters.
+pub trait UsageStore: Send + Sync + Clone + 'static {
+    /// Usage counted on `day`; empty when nothing was recorded.
+    fn usage_on(&self, day: NaiveDate)
+    -> impl Future<Output = Result<DailyUsage, AppError>> + Send;
+
+    /// Atomically add `increment` to the counters of `increment.day` and
+    /// return the new totals.
+    fn add_usage(
+        &self,
+        increment: &DailyUsage,
+    ) -> impl Future<Output = Result<DailyUsage, AppError>> + Send;
+}
+
+/// The next midnight UTC after `now`, when a day's budget resets.
+pub fn next_reset(now: DateTime<Utc>) -> DateTime<Utc> {
+    now.date_naive()
+        .succ_opt()
+        .unwrap_or(NaiveDate::MAX)
+        .and_time(NaiveTime::MIN)
+        .and_utc()
+}
+
+/// Checks and records LLM usage against daily [`BudgetLimits`].
+#[derive(Clone)]
+pub struct BudgetGuard<U: UsageStore> {
+    store: U,
+    limits: BudgetLimits,
+    pricing: Option<PriceFn>,
+}
+
+impl<U: UsageStore> BudgetGuard<U> {
+    pub fn new(store: U, limits: BudgetLimits) -> Self {
+        Self {
+            store,
+            limits,
+            pricing: None,
+        }
+    }
+
+    /// Estimate each call's cost with `pricing`. Without it, every call is
+    /// free and `max_cost_usd` is never reached.
+    pub fn with_pricing(mut self, pricing: PriceFn) -> Self {
+        self.pricing = Some(pricing);
+        self
+    }
+
+    pub fn limits(&self) -> &BudgetLimits {
+        &self.limits
+    }
+
+    /// Usage counted so far today.
+    pub async fn usage(&self) -> Result<DailyUsage, AppError> {
+        self.store.usage_on(Utc::now().date_naive()).await
+    }
+
+    /// Fail with [`AppError::BudgetExceeded`] if today's budget is used up.
+    pub async fn check(&self) -> Result<(), AppError> {
+        self.check_at(Utc::now()).await
+    }
+
+    /// [`check`](Self::check) as of `now`.
+    pub async fn check_at(&self, now: DateTime<Utc>) -> Result<(), AppError> {
+        if self.limits.is_unlimited() {
+            return Ok(());
+        }
+        let usage = self.store.usage_on(now.date_naive()).await?;
+        match self.limits.exceeded_by(&usage) {
+            Some(reason) => Err(AppError::BudgetExceeded {
+                reason,
+                resets_at: next_reset(now),
+            }),
+            None => Ok(()),
+        }
+    }
+
+    /// Count one call to `model` that used `usage` (`None` when the call
+    /// failed or reported no tokens). Returns today's new totals.
+    pub async fn record(&self, model: &str, usage: Option<&Usage>) -> Result<DailyUsage, AppError> {
+        self.record_at(Utc::now(), model, usage).await
+    }
+
+    /// [`record`](Self::record) as of `now`.
+    pub async fn record_at(
+        &self,
+        now: DateTime<Utc>,
+        model: &str,
+        usage: Option<&Usage>,
+    ) -> Result<DailyUsage, AppError> {
+        let (prompt, completion) = usage.map_or((0, 0), |u| {
+            (u64::from(u.prompt_tokens), u64::from(u.completion_tokens))
+        });
+        let cost_usd = self
+            .pricing
+            .and_then(|price| price(model, prompt, completion))
+            .unwrap_or(0.0);
+        self.store
+            .add_usage(&DailyUsage {
+                day: now.date_naive(),
+                llm_calls: 1,
+                tokens: prompt + completion,
+                cost_usd,
+            })
+            .await
+    }
+}
+
+/// Extractor that checks a [`BudgetGuard`] before every call and records the
+/// call's usage after it.
+///
+/// Recording is best-effort: a failed write is logged and the extraction
+/// still succeeds.
+#[derive(Clone)]
+pub struct BudgetedExtractor<E: Extractor, U: UsageStore> {
+    inner: E,
+    guard: BudgetGuard<U>,
+    model: String,
+}
+
+impl<E: Extractor, U: UsageStore> BudgetedExtractor<E, U> {
+    /// Wrap `inner`, which calls `model`.
+    pub fn new(inner: E, guard: BudgetGuard<U>, model: impl Into<String>) -> Self {
+        Self {
+            inner,
+            guard,
+            model: model.into(),
+        }
+    }
+}
+
+impl<E: Extractor, U: UsageStore> Extractor for BudgetedExtractor<E, U> {
+    async fn extract(
+        &self,
+        content: &str,
+        schema: &serde_json::Value,
+    ) -> Result<ExtractionOutcome, AppError> {
+        self.guard.check().await?;
+        let result = self.inner.extract(content, schema).await;
+
+        let (model, usage) = match &result {
+            Ok(outcome) => (
+                outcome.model.as_deref().unwrap_or(&self.model),
+                outcome.usage.as_ref(),
+            ),
+            Err(_) => (self.model.as_str(), None),
+        };
+        if let Err(e) = self.guard.record(model, usage).await {
+            tracing::warn!(model, "Failed to record LLM usage: {e}");
+        }
+        result
+    }
+
+    fn fingerprint(&self) -> Option<String> {
+        self.inner.fingerprint()
+    }
+}
+
+/// Factory whose extractors are all [`BudgetedExtractor`]s sharing one guard.
+#[derive(Clone)]
+pub struct BudgetedExtractorFactory<EF: ExtractorFactory, U: UsageStore> {
+    inner: EF,
+    guard: BudgetGuard<U>,
+}
+
+impl<EF: ExtractorFactory, U: UsageStore> BudgetedExtractorFactory<EF, U> {
+    pub fn new(inner: EF, guard: BudgetGuard<U>) -> Self {
+        Self { inner, guard }
+    }
+}
+
+impl<EF: ExtractorFactory, U: UsageStore> ExtractorFactory for BudgetedExtractorFactory<EF, U> {
+    type Extractor = BudgetedExtractor<EF::Extractor, U>;
+
+    fn create(&self, model: &str, base_url: &str) -> Result<Self::Extractor, AppError> {
+        let inner = self.inner.create(model, base_url)?;
+        Ok(BudgetedExtractor::new(inner, self.guard.clone(), model))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::TimeZone;
+
+    use super::*;
+    use crate::testutil::{MockExtractor, MockUsageStore};
+
+    fn at(day: u32, hour: u32) -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2026, 3, day, hour, 30, 0).unwrap()
+    }
+
+    fn flat_price(_model: &str, input: u64, output: u64) -> Option<f64> {
+        Some((input + output) as f64 / 1_000.0)
+    }
+
+    #[tokio::test]
+    async fn caps_reset_at_midnight_utc() {
+        let guard = BudgetGuard::new(
+            MockUsageStore::new(),
+            BudgetLimits::default().with_max_calls(2),
+        );
+        let usage = Usage::new(100, 20);
+
+        guard.check_at(at(9, 22)).await.unwrap();
+        guard.record_at(at(9, 22), "m", Some(&usage)).await.unwrap();
+        guard.record_at(at(9, 23), "m", None).await.unwrap();
+
+        match guard.check_at(at(9, 23)).await {
+            Err(AppError::BudgetExceeded { reason, resets_at }) => {
+                assert_eq!(reason, "2 of 2 daily LLM calls used");
+                assert_eq!(
+                    resets_at,
+                    Utc.with_ymd_and_hms(2026, 3, 10, 0, 0, 0).unwrap()
+                );
+            }
+            other => panic!("expected BudgetExceeded, got {other:?}"),
+        }
+
+        // A new day starts with fresh counters.
+        guard.check_at(at(10, 0)).await.unwrap();
+        let today = guard.record_at(at(10, 0), "m", Some(&usage)).await.unwrap();
+        assert_eq!(today.llm_calls, 1);
+        assert_eq!(today.tokens, 120);
+    }
+
+    #[tokio::test]
+    async fn token_and_cost_caps_use_recorded_usage() {
+        let store = MockUsageStore::new();
+        let tokens = BudgetGuard::new(
+            store.clone(),
+            BudgetLimits::default().with_max_tokens(1_000),
+        );
+        let dollars = BudgetGuard::new(store, BudgetLimits::default().with_max_cost_usd(1.5))
+            .with_pricing(flat_price);
+
+        dollars
+            .record_at(at(9, 12), "m", Some(&Usage::new(800, 100)))
+            .await
+            .unwrap();
+        tokens.check_at(at(9, 12)).await.unwrap();
+        dollars.check_at(at(9, 12)).await.unwrap();
+
+        let total = dollars
+            .record_at(at(9, 13), "m", Some(&Usage::new(500, 100)))
+            .await
+            .unwrap();
+        assert_eq!(total.tokens, 1_500);
+        assert!((total.cost_usd - 1.5).abs() < 1e-9);
+        assert!(matches!(
+            tokens.check_at(at(9, 13)).await,
+            Err(AppError::BudgetExceeded { .. })
+        ));
+        assert!(matches!(
+            dollars.check_at(at(9, 13)).await,
+            Err(AppError::BudgetExceeded { .. })
+        ));
+    }
+
+    #[tokio::test]
+    async fn concurrent_calls_are_all_counted() {
+        let store = MockUsageStore::new();
+        let guard = BudgetGuard::new(store.clone(), BudgetLimits::default());
+        let usage = Usage::new(10, 5);
+
+        let tasks: Vec<_> = (0..50)
+            .map(|_| {
+                let guard = guard.clone();
+                tokio::spawn(async move { guard.record("m", Some(&usage)).await.unwrap() })
+            })
+            .collect();
+        for task in tasks {
+            task.await.unwrap();
+        }
+
+        let today = guard.usage().await.unwrap();
+        assert_eq!(today.llm_calls, 50);
+        assert_eq!(today.tokens, 750);
+    }
+
+    #[tokio::test]
+    async fn budgeted_extractor_stops_calling_once_exceeded() {
+        let inner = MockExtractor::with_responses(vec![
+            Ok(serde_json::json!({"a": 1})),
+            Ok(serde_json::json!({"a": 2})),
+        ]);
+        let guard = BudgetGuard::new(
+            MockUsageStore::new(),
+            BudgetLimits::default().with_max_calls(1),
+        );
+        let extractor = BudgetedExtractor::new(inner.clone(), guard.clone(), "gpt-4o-mini");
+        let schema = serde_json::json!({"type": "object"});
+
+        extractor.extract("content", &schema).await.unwrap();
+        let err = extractor.extract("content", &schema).await.unwrap_err();
+
+        assert!(matches!(err, AppError::BudgetExceeded { .. }));
+        assert_eq!(inner.call_count(), 1);
+        assert_eq!(guard.usage().await.unwrap().llm_calls, 1);
+    }
+}
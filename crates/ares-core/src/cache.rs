@@ -73,7 +73,7 @@ impl ExtractionCache {
     }
 
     fn cache_key(content_hash: &str, schema_name: &str, schema_hash: &str, model: &str) -> String {
-        compute_hash(&format!(
+        compute_hash(format!(
             "{content_hash}:{schema_name}:{schema_hash}:{model}"
         ))
     }
@@ -13,14 +13,22 @@
 //! CLOSED <---------------------------[success]----------------------------+
 //! ```
 
+use std::collections::HashMap;
 use std::future::Future;
+use std::str::FromStr;
 use std::sync::{Arc, Mutex};
 use std::time::{Duration, Instant};
 
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+
 use crate::error::AppError;
+use crate::models::ExtractionOutcome;
+use crate::traits::Extractor;
 
 /// Current state of the circuit breaker.
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "kebab-case")]
 pub enum CircuitState {
     /// Circuit is closed - requests flow normally.
     Closed,
@@ -30,12 +38,31 @@ pub enum CircuitState {
     HalfOpen,
 }
 
+impl CircuitState {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            CircuitState::Closed => "closed",
+            CircuitState::Open => "open",
+            CircuitState::HalfOpen => "half-open",
+        }
+    }
+}
+
 impl std::fmt::Display for CircuitState {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        match self {
-            CircuitState::Closed => write!(f, "closed"),
-            CircuitState::Open => write!(f, "open"),
-            CircuitState::HalfOpen => write!(f, "half-open"),
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for CircuitState {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "closed" => Ok(CircuitState::Closed),
+            "open" => Ok(CircuitState::Open),
+            "half-open" => Ok(CircuitState::HalfOpen),
+            other => Err(format!("Unknown circuit state: '{other}'")),
         }
     }
 }
@@ -106,6 +133,17 @@ pub struct CircuitBreakerStats {
     pub time_until_half_open: Option<Duration>,
 }
 
+/// A breaker's state as last reported by a worker through the job queue
+/// backend, so the server can show and reset breakers it does not own.
+#[derive(Debug, Clone)]
+pub struct CircuitBreakerRecord {
+    pub stats: CircuitBreakerStats,
+    /// When a worker last reported this breaker.
+    pub updated_at: DateTime<Utc>,
+    /// When an operator last asked for the breaker to be reset.
+    pub reset_requested_at: Option<DateTime<Utc>>,
+}
+
 /// Error type for circuit breaker operations.
 #[derive(Debug)]
 pub enum CircuitBreakerError {
@@ -371,6 +409,81 @@ impl CircuitBreaker {
     }
 }
 
+/// Circuit breakers by name, created on first use with a shared config.
+///
+/// Lets a server that talks to several LLM endpoints keep one breaker per
+/// endpoint, so an outage of one doesn't reject calls to the others.
+#[derive(Clone)]
+pub struct CircuitBreakerRegistry {
+    config: CircuitBreakerConfig,
+    breakers: Arc<Mutex<HashMap<String, CircuitBreaker>>>,
+}
+
+impl CircuitBreakerRegistry {
+    pub fn new(config: CircuitBreakerConfig) -> Self {
+        Self {
+            config,
+            breakers: Arc::default(),
+        }
+    }
+
+    /// The breaker named `name`, created closed if there is none yet.
+    /// Clones share state, so every caller sees the same failures.
+    pub fn get_or_create(&self, name: &str) -> CircuitBreaker {
+        self.breakers
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .entry(name.to_string())
+            .or_insert_with(|| CircuitBreaker::new(name, self.config.clone()))
+            .clone()
+    }
+}
+
+impl Default for CircuitBreakerRegistry {
+    fn default() -> Self {
+        Self::new(CircuitBreakerConfig::default())
+    }
+}
+
+/// Extractor whose calls go through a [`CircuitBreaker`]. While the circuit
+/// is open, calls fail at once with a retryable [`AppError::LlmError`] (503)
+/// instead of waiting for the LLM to time out.
+#[derive(Clone)]
+pub struct CircuitBreakingExtractor<E: Extractor> {
+    inner: E,
+    breaker: CircuitBreaker,
+}
+
+impl<E: Extractor> CircuitBreakingExtractor<E> {
+    pub fn new(inner: E, breaker: CircuitBreaker) -> Self {
+        Self { inner, breaker }
+    }
+}
+
+impl<E: Extractor> Extractor for CircuitBreakingExtractor<E> {
+    async fn extract(
+        &self,
+        content: &str,
+        schema: &serde_json::Value,
+    ) -> Result<ExtractionOutcome, AppError> {
+        self.breaker
+            .call(|| self.inner.extract(content, schema))
+            .await
+            .map_err(|e| match e {
+                CircuitBreakerError::Inner(e) => e,
+                open @ CircuitBreakerError::Open { .. } => AppError::LlmError {
+                    message: open.to_string(),
+                    status_code: 503,
+                    retryable: true,
+                },
+            })
+    }
+
+    fn fingerprint(&self) -> Option<String> {
+        self.inner.fingerprint()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -583,4 +696,62 @@ mod tests {
         let stats = cb.stats();
         assert_eq!(stats.failure_count, 1);
     }
+
+    #[test]
+    fn test_state_round_trips_through_str() {
+        for state in [
+            CircuitState::Closed,
+            CircuitState::Open,
+            CircuitState::HalfOpen,
+        ] {
+            assert_eq!(state.to_string().parse::<CircuitState>(), Ok(state));
+        }
+        assert!("ajar".parse::<CircuitState>().is_err());
+    }
+
+    #[test]
+    fn registry_shares_one_breaker_per_name() {
+        let registry = CircuitBreakerRegistry::default();
+        registry
+            .get_or_create("https://api.openai.com/v1")
+            .record_failure(&AppError::Timeout(30));
+
+        let again = registry.get_or_create("https://api.openai.com/v1");
+        assert_eq!(again.stats().failure_count, 1);
+        assert_eq!(again.name(), "https://api.openai.com/v1");
+        let other = registry.get_or_create("http://localhost:11434/v1");
+        assert_eq!(other.stats().failure_count, 0);
+    }
+
+    #[tokio::test]
+    async fn open_breaker_rejects_extraction_without_calling_the_llm() {
+        use crate::testutil::MockExtractor;
+
+        let config = CircuitBreakerConfig {
+            failure_threshold: 1,
+            ..CircuitBreakerConfig::default()
+        };
+        let breaker = CircuitBreaker::new("llm", config);
+        let inner = MockExtractor::with_error(AppError::Timeout(30));
+        let extractor = CircuitBreakingExtractor::new(inner.clone(), breaker.clone());
+        let schema = serde_json::json!({"type": "object"});
+
+        let err = extractor.extract("content", &schema).await.unwrap_err();
+        assert!(matches!(err, AppError::Timeout(30)), "{err}");
+        assert_eq!(breaker.state(), CircuitState::Open);
+
+        let err = extractor.extract("content", &schema).await.unwrap_err();
+        assert!(
+            matches!(
+                err,
+                AppError::LlmError {
+                    status_code: 503,
+                    retryable: true,
+                    ..
+                }
+            ),
+            "{err}"
+        );
+        assert_eq!(inner.call_count(), 1);
+    }
 }
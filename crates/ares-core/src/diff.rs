@@ -0,0 +1,178 @@
+//! Structural diff between two extracted JSON values, used to show what
+//! changed between consecutive extractions of the same page.
+//!
+//! Paths use the same notation as [`crate::groundedness`]: `author.name`,
+//! `tags[2]`, and `<root>` for a top-level scalar. Arrays are compared
+//! position by position, so an insertion near the front shows up as a run of
+//! changes followed by an addition.
+
+use serde::Serialize;
+use serde_json::Value;
+
+/// One difference between an old and a new JSON value.
+///
+/// Serializes as `{"kind": "added" | "removed" | "changed", "path": ..., ...}`.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(tag = "kind", rename_all = "lowercase")]
+pub enum JsonChange {
+    /// Present only in the new value.
+    Added { path: String, value: Value },
+    /// Present only in the old value.
+    Removed { path: String, value: Value },
+    /// Present in both, with different values.
+    Changed {
+        path: String,
+        old: Value,
+        new: Value,
+    },
+}
+
+impl JsonChange {
+    pub fn path(&self) -> &str {
+        match self {
+            JsonChange::Added { path, .. }
+            | JsonChange::Removed { path, .. }
+            | JsonChange::Changed { path, .. } => path,
+        }
+    }
+}
+
+/// List the leaf-level differences going from `old` to `new`. An empty
+/// result means the values are equal.
+pub fn diff_json(old: &Value, new: &Value) -> Vec<JsonChange> {
+    let mut out = Vec::new();
+    walk(old, new, &mut String::new(), &mut out);
+    out
+}
+
+fn walk(old: &Value, new: &Value, path: &mut String, out: &mut Vec<JsonChange>) {
+    match (old, new) {
+        (Value::Object(old_map), Value::Object(new_map)) => {
+            for (key, old_value) in old_map {
+                let len = push_key(path, key);
+                match new_map.get(key) {
+                    Some(new_value) => walk(old_value, new_value, path, out),
+                    None => out.push(JsonChange::Removed {
+                        path: display(path),
+                        value: old_value.clone(),
+                    }),
+                }
+                path.truncate(len);
+            }
+            for (key, new_value) in new_map {
+                if !old_map.contains_key(key) {
+                    let len = push_key(path, key);
+                    out.push(JsonChange::Added {
+                        path: display(path),
+                        value: new_value.clone(),
+                    });
+                    path.truncate(len);
+                }
+            }
+        }
+        (Value::Array(old_items), Value::Array(new_items)) => {
+            for i in 0..old_items.len().max(new_items.len()) {
+                let len = path.len();
+                path.push_str(&format!("[{i}]"));
+                match (old_items.get(i), new_items.get(i)) {
+                    (Some(o), Some(n)) => walk(o, n, path, out),
+                    (Some(o), None) => out.push(JsonChange::Removed {
+                        path: display(path),
+                        value: o.clone(),
+                    }),
+                    (None, Some(n)) => out.push(JsonChange::Added {
+                        path: display(path),
+                        value: n.clone(),
+                    }),
+                    (None, None) => unreachable!("index is below the longer length"),
+                }
+                path.truncate(len);
+            }
+        }
+        _ if old != new => out.push(JsonChange::Changed {
+            path: display(path),
+            old: old.clone(),
+            new: new.clone(),
+        }),
+        _ => {}
+    }
+}
+
+/// Append `key` to `path`, returning the length to truncate back to.
+fn push_key(path: &mut String, key: &str) -> usize {
+    let len = path.len();
+    if !path.is_empty() {
+        path.push('.');
+    }
+    path.push_str(key);
+    len
+}
+
+fn display(path: &str) -> String {
+    if path.is_empty() {
+        "<root>".to_string()
+    } else {
+        path.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn equal_values_have_no_changes() {
+        let value = json!({"title": "A", "tags": ["x", "y"]});
+        assert!(diff_json(&value, &value).is_empty());
+    }
+
+    #[test]
+    fn reports_nested_changes_additions_and_removals() {
+        let old = json!({"price": 10, "author": {"name": "Ann"}, "draft": true});
+        let new = json!({"price": 12, "author": {"name": "Ann", "email": "a@x.io"}});
+
+        let mut changes = diff_json(&old, &new);
+        changes.sort_by(|a, b| a.path().cmp(b.path()));
+        assert_eq!(
+            changes,
+            vec![
+                JsonChange::Added {
+                    path: "author.email".into(),
+                    value: json!("a@x.io"),
+                },
+                JsonChange::Removed {
+                    path: "draft".into(),
+                    value: json!(true),
+                },
+                JsonChange::Changed {
+                    path: "price".into(),
+                    old: json!(10),
+                    new: json!(12),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn compares_arrays_by_position() {
+        let old = json!({"tags": ["a", "b"]});
+        let new = json!({"tags": ["a", "c", "d"]});
+
+        let paths: Vec<_> = diff_json(&old, &new)
+            .iter()
+            .map(|c| c.path().to_string())
+            .collect();
+        assert_eq!(paths, ["tags[1]", "tags[2]"]);
+    }
+
+    #[test]
+    fn type_change_is_a_single_change() {
+        let changes = diff_json(&json!({"a": [1]}), &json!({"a": "1"}));
+        assert_eq!(changes.len(), 1);
+        assert_eq!(changes[0].path(), "a");
+
+        assert_eq!(diff_json(&json!(1), &json!(2))[0].path(), "<root>");
+    }
+}
@@ -1,3 +1,4 @@
+use chrono::{DateTime, Utc};
 use thiserror::Error;
 
 /// Application-wide error types for Ares.
@@ -35,10 +36,20 @@ pub enum AppError {
     #[error("Schema error: {0}")]
     SchemaError(String),
 
+    /// A document failed validation in several independent ways, one message
+    /// per failure (e.g. every meta-schema violation in a JSON Schema).
+    #[error("Validation failed: {}", .0.join("; "))]
+    ValidationError(Vec<String>),
+
     /// A client-supplied input was invalid (bad parameter or unsupported option).
     #[error("Invalid input: {0}")]
     InvalidInput(String),
 
+    /// The resource is not in a state that allows the operation, e.g. a
+    /// job that was claimed before its schema could be changed.
+    #[error("Conflict: {0}")]
+    Conflict(String),
+
     /// A specific schema version was not found.
     #[error("Schema not found: {name}@{version}")]
     SchemaNotFound { name: String, version: String },
@@ -55,6 +66,23 @@ pub enum AppError {
     #[error("Rate limit exceeded")]
     RateLimitExceeded,
 
+    /// The site served an anti-bot block or challenge page instead of the
+    /// page. Retrying the same fetch won't help; `hint` says what might.
+    #[error("Blocked fetching {url}: {reason} (hint: {hint})")]
+    Blocked {
+        url: String,
+        reason: String,
+        hint: String,
+    },
+
+    /// The daily LLM budget is used up. Retrying after `resets_at`
+    /// (midnight UTC) will succeed.
+    #[error("Daily budget exceeded: {reason} (resets at {resets_at})")]
+    BudgetExceeded {
+        reason: String,
+        resets_at: DateTime<Utc>,
+    },
+
     /// Network/connection error.
     #[error("Network error: {0}")]
     NetworkError(String),
@@ -76,7 +104,10 @@ impl AppError {
     /// Returns true if this error is transient and worth retrying.
     pub fn is_retryable(&self) -> bool {
         match self {
-            AppError::NetworkError(_) | AppError::Timeout(_) | AppError::RateLimitExceeded => true,
+            AppError::NetworkError(_)
+            | AppError::Timeout(_)
+            | AppError::RateLimitExceeded
+            | AppError::BudgetExceeded { .. } => true,
             AppError::LocalInferenceError { retryable, .. } => *retryable,
             AppError::LlmError { retryable, .. } => *retryable,
             AppError::HttpError(msg) => {
@@ -107,6 +138,15 @@ impl AppError {
     }
 }
 
+/// Lets database code use `?` on sqlx results. Enabled by `ares-db`
+/// through the `sqlx` feature.
+#[cfg(feature = "sqlx")]
+impl From<sqlx::Error> for AppError {
+    fn from(e: sqlx::Error) -> Self {
+        AppError::DatabaseError(e.to_string())
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -134,6 +174,36 @@ mod tests {
         assert!(!AppError::SchemaValidationError("bad".into()).should_trip_circuit());
     }
 
+    #[test]
+    fn test_error_conversions() {
+        fn assert_std_error<E: std::error::Error + Send + Sync + 'static>() {}
+        assert_std_error::<AppError>();
+
+        let parse = serde_json::from_str::<serde_json::Value>("{").unwrap_err();
+        let err: AppError = parse.into();
+        assert!(matches!(err, AppError::SerializationError(_)));
+        assert!(std::error::Error::source(&err).is_some());
+    }
+
+    #[cfg(feature = "sqlx")]
+    #[test]
+    fn test_sqlx_errors_become_database_errors() {
+        let err: AppError = sqlx::Error::RowNotFound.into();
+        assert!(matches!(err, AppError::DatabaseError(_)));
+        assert!(err.to_string().starts_with("Database error: "), "{err}");
+    }
+
+    #[test]
+    fn test_validation_error_lists_every_message() {
+        let err =
+            AppError::ValidationError(vec!["a is required".into(), "b must be a string".into()]);
+        assert_eq!(
+            err.to_string(),
+            "Validation failed: a is required; b must be a string"
+        );
+        assert!(!err.is_retryable());
+    }
+
     #[test]
     fn test_http_error_retryable_on_timeout() {
         assert!(AppError::HttpError("connection timeout".into()).is_retryable());
@@ -216,6 +286,22 @@ mod tests {
         assert!(!AppError::HttpError("HTTP 404 Not Found".into()).should_trip_circuit());
     }
 
+    #[test]
+    fn blocked_fetches_are_permanent_and_carry_the_hint() {
+        let err = AppError::Blocked {
+            url: "https://example.com".into(),
+            reason: "Cloudflare JS challenge".into(),
+            hint: "retry with --browser".into(),
+        };
+        assert!(!err.is_retryable());
+        assert!(!err.should_trip_circuit());
+        assert_eq!(
+            err.to_string(),
+            "Blocked fetching https://example.com: Cloudflare JS challenge \
+             (hint: retry with --browser)"
+        );
+    }
+
     #[test]
     fn local_inference_errors_follow_their_retryability() {
         let transient = AppError::LocalInferenceError {
@@ -232,4 +318,19 @@ mod tests {
         assert!(!config.is_retryable());
         assert!(!config.should_trip_circuit());
     }
+
+    #[test]
+    fn budget_exceeded_is_retryable_without_tripping_the_circuit() {
+        let err = AppError::BudgetExceeded {
+            reason: "100 of 100 daily LLM calls used".into(),
+            resets_at: "2026-03-10T00:00:00Z".parse().unwrap(),
+        };
+        assert!(err.is_retryable());
+        assert!(!err.should_trip_circuit());
+        assert_eq!(
+            err.to_string(),
+            "Daily budget exceeded: 100 of 100 daily LLM calls used \
+             (resets at 2026-03-10 00:00:00 UTC)"
+        );
+    }
 }
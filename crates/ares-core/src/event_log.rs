@@ -0,0 +1,332 @@
+//! Durable record of job attempts.
+//!
+//! [`PersistingWorkerReporter`] turns the job-level [`WorkerEvent`]s a worker
+//! emits (claimed, started, completed, failed) into [`NewWorkerEvent`] rows
+//! and hands them to a [`WorkerEventStore`] from a background task. Reporting
+//! only queues the event on a bounded channel, so a slow or failing store
+//! never delays or fails a job; when the buffer is full, events are dropped
+//! and counted.
+
+use std::future::Future;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use tokio::sync::mpsc;
+use tokio::task::JoinHandle;
+use uuid::Uuid;
+
+use crate::circuit_breaker::CircuitState;
+use crate::error::AppError;
+use crate::worker::{WorkerEvent, WorkerReporter};
+
+/// Events buffered between the worker and the store by default.
+pub const DEFAULT_EVENT_BUFFER: usize = 1024;
+
+/// Events written to the store in one call, at most.
+const MAX_BATCH: usize = 100;
+
+/// A worker event ready to be stored.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct NewWorkerEvent {
+    pub worker_id: String,
+    pub job_id: Option<Uuid>,
+    /// `job_claimed`, `job_started`, `job_completed`, or `job_failed`.
+    pub event_type: String,
+    pub detail: serde_json::Value,
+}
+
+/// A stored worker event.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct WorkerEventRecord {
+    pub id: i64,
+    pub worker_id: String,
+    pub job_id: Option<Uuid>,
+    pub event_type: String,
+    pub detail: serde_json::Value,
+    pub created_at: DateTime<Utc>,
+}
+
+impl NewWorkerEvent {
+    /// The row recorded for `event`, or `None` for events that are not about
+    /// a job (polling, startup, shutdown).
+    pub fn from_event(worker_id: &str, event: &WorkerEvent<'_>) -> Option<Self> {
+        let (job_id, event_type, detail) = match event {
+            WorkerEvent::JobClaimed { job } => (
+                job.id,
+                "job_claimed",
+                serde_json::json!({
+                    "url": job.url,
+                    "schema_name": job.schema_name,
+                    "retry_count": job.retry_count,
+                }),
+            ),
+            WorkerEvent::JobStarted {
+                job_id,
+                job_name,
+                url,
+            } => (
+                *job_id,
+                "job_started",
+                serde_json::json!({ "job_name": job_name, "url": url }),
+            ),
+            WorkerEvent::JobCompleted {
+                job_id,
+                extraction_id,
+                duration,
+                changed,
+                content_bytes,
+                model,
+                detected_language,
+            } => (
+                *job_id,
+                "job_completed",
+                serde_json::json!({
+                    "extraction_id": extraction_id,
+                    "duration_ms": duration.as_millis() as u64,
+                    "changed": changed,
+                    "content_bytes": content_bytes,
+                    "model": model,
+                    "detected_language": detected_language,
+                }),
+            ),
+            WorkerEvent::JobFailed {
+                job,
+                error,
+                will_retry,
+            } => (
+                job.id,
+                "job_failed",
+                serde_json::json!({ "error": error, "will_retry": will_retry }),
+            ),
+            WorkerEvent::Started { .. }
+            | WorkerEvent::Polling
+            | WorkerEvent::ShuttingDown { .. }
+            | WorkerEvent::Stopped { .. } => return None,
+        };
+        Some(Self {
+            worker_id: worker_id.to_string(),
+            job_id: Some(job_id),
+            event_type: event_type.to_string(),
+            detail,
+        })
+    }
+}
+
+/// Persistent storage for worker events.
+pub trait WorkerEventStore: Send + Sync + Clone + 'static {
+    /// Store `events` in order.
+    fn save_events(
+        &self,
+        events: &[NewWorkerEvent],
+    ) -> impl Future<Output = Result<(), AppError>> + Send;
+}
+
+/// Worker reporter that stores job events through a [`WorkerEventStore`].
+///
+/// Must be created inside a Tokio runtime. Call [`shutdown`](Self::shutdown)
+/// after the worker stops to flush buffered events.
+pub struct PersistingWorkerReporter {
+    worker_id: String,
+    tx: mpsc::Sender<NewWorkerEvent>,
+    writer: JoinHandle<()>,
+    dropped: Arc<AtomicU64>,
+}
+
+impl PersistingWorkerReporter {
+    /// A reporter for `worker_id` buffering up to `capacity` events.
+    pub fn new<S: WorkerEventStore>(
+        store: S,
+        worker_id: impl Into<String>,
+        capacity: usize,
+    ) -> Self {
+        let (tx, rx) = mpsc::channel(capacity.max(1));
+        Self {
+            worker_id: worker_id.into(),
+            tx,
+            writer: tokio::spawn(write_events(store, rx)),
+            dropped: Arc::new(AtomicU64::new(0)),
+        }
+    }
+
+    /// Events dropped because the buffer was full.
+    pub fn dropped(&self) -> u64 {
+        self.dropped.load(Ordering::Relaxed)
+    }
+
+    /// Stop accepting events and wait until the buffered ones are written.
+    pub async fn shutdown(self) {
+        drop(self.tx);
+        if let Err(e) = self.writer.await {
+            tracing::warn!(error = %e, "Worker event writer stopped unexpectedly");
+        }
+    }
+}
+
+impl WorkerReporter for PersistingWorkerReporter {
+    fn report(&self, event: WorkerEvent<'_>) {
+        let Some(event) = NewWorkerEvent::from_event(&self.worker_id, &event) else {
+            return;
+        };
+        if let Err(e) = self.tx.try_send(event) {
+            let dropped = self.dropped.fetch_add(1, Ordering::Relaxed) + 1;
+            match e {
+                mpsc::error::TrySendError::Full(event) => tracing::warn!(
+                    event_type = %event.event_type,
+                    job_id = ?event.job_id,
+                    dropped,
+                    "Worker event buffer full; dropping event"
+                ),
+                mpsc::error::TrySendError::Closed(_) => {}
+            }
+        }
+    }
+}
+
+/// Drain `rx` into `store` in batches until every sender is gone. A failed
+/// write is logged and its batch discarded.
+async fn write_events<S: WorkerEventStore>(store: S, mut rx: mpsc::Receiver<NewWorkerEvent>) {
+    let mut batch = Vec::with_capacity(MAX_BATCH);
+    while rx.recv_many(&mut batch, MAX_BATCH).await > 0 {
+        if let Err(e) = store.save_events(&batch).await {
+            tracing::warn!(error = %e, events = batch.len(), "Failed to store worker events");
+        }
+        batch.clear();
+    }
+}
+
+/// Forwards every event to both reporters, in order.
+impl<A: WorkerReporter, B: WorkerReporter> WorkerReporter for (A, B) {
+    fn report(&self, event: WorkerEvent<'_>) {
+        self.0.report(event.clone());
+        self.1.report(event);
+    }
+
+    fn on_circuit_breaker_change(&self, name: &str, state: CircuitState) {
+        self.0.on_circuit_breaker_change(name, state);
+        self.1.on_circuit_breaker_change(name, state);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use tokio_util::sync::CancellationToken;
+
+    use super::*;
+    use crate::circuit_breaker::{CircuitBreaker, CircuitBreakerConfig};
+    use crate::job::WorkerConfig;
+    use crate::testutil::*;
+    use crate::worker::WorkerService;
+
+    fn completed(job_id: Uuid) -> WorkerEvent<'static> {
+        WorkerEvent::JobCompleted {
+            job_id,
+            extraction_id: None,
+            duration: Duration::from_millis(1_250),
+            changed: Some(false),
+            content_bytes: 2_048,
+            model: "gpt-4o-mini",
+            detected_language: Some("eng"),
+        }
+    }
+
+    #[tokio::test]
+    async fn job_events_are_stored_in_order() {
+        let store = MockEventStore::new();
+        let reporter = PersistingWorkerReporter::new(store.clone(), "worker-1", 16);
+        let job = make_test_job();
+
+        reporter.report(WorkerEvent::Polling);
+        reporter.report(WorkerEvent::JobClaimed { job: &job });
+        reporter.report(WorkerEvent::JobFailed {
+            job: &job,
+            error: "HTTP 500",
+            will_retry: true,
+        });
+        reporter.report(WorkerEvent::Stopped {
+            worker_id: "worker-1",
+        });
+        reporter.shutdown().await;
+
+        let events = store.events.lock().unwrap();
+        let types: Vec<_> = events.iter().map(|e| e.event_type.as_str()).collect();
+        assert_eq!(types, ["job_claimed", "job_failed"]);
+        assert!(events.iter().all(|e| e.worker_id == "worker-1"));
+        assert!(events.iter().all(|e| e.job_id == Some(job.id)));
+        assert_eq!(events[0].detail["url"], "https://example.com");
+        assert_eq!(events[1].detail["will_retry"], true);
+    }
+
+    #[test]
+    fn completed_events_record_timing_and_change() {
+        let job_id = Uuid::new_v4();
+        let row = NewWorkerEvent::from_event("worker-1", &completed(job_id)).unwrap();
+        assert_eq!(row.event_type, "job_completed");
+        assert_eq!(row.detail["duration_ms"], 1_250);
+        assert_eq!(row.detail["changed"], false);
+        assert_eq!(row.detail["content_bytes"], 2_048);
+        assert_eq!(row.detail["model"], "gpt-4o-mini");
+        assert_eq!(row.detail["detected_language"], "eng");
+    }
+
+    #[tokio::test]
+    async fn full_buffer_drops_events_without_blocking() {
+        let store = MockEventStore::paused();
+        let reporter = PersistingWorkerReporter::new(store.clone(), "worker-1", 2);
+        let job_id = Uuid::new_v4();
+
+        // The writer holds at most one batch while the store is paused, so
+        // most of these must be dropped rather than waited on.
+        let reported = tokio::time::timeout(Duration::from_secs(1), async {
+            for _ in 0..10 {
+                reporter.report(completed(job_id));
+                tokio::task::yield_now().await;
+            }
+        })
+        .await;
+        assert!(reported.is_ok(), "report must not wait for the store");
+        let dropped = reporter.dropped();
+        assert!(dropped >= 7, "dropped {dropped} of 10");
+
+        store.resume();
+        reporter.shutdown().await;
+        assert_eq!(store.events.lock().unwrap().len() as u64, 10 - dropped);
+    }
+
+    #[tokio::test]
+    async fn failing_store_does_not_fail_jobs() {
+        let store = MockEventStore::failing();
+        let reporter = PersistingWorkerReporter::new(store.clone(), "test-worker", 16);
+        let queue = MockJobQueue::with_jobs(vec![make_test_job()]);
+
+        let worker = WorkerService::new(
+            queue.clone(),
+            MockFetcher::new("<html>ok</html>"),
+            MockCleaner::passthrough(),
+            MockExtractorFactory::new(serde_json::json!({"title": "Test"})),
+            MockStore::empty(),
+            MockLinkDiscoverer::new(),
+            MockRobotsChecker::new(),
+            CircuitBreaker::new("test", CircuitBreakerConfig::default()),
+            WorkerConfig::default()
+                .with_worker_id("test-worker")
+                .with_poll_interval(Duration::from_millis(10))
+                .with_run_once(true),
+        );
+
+        let reporters = (MockReporter::new(), reporter);
+        let summary = worker
+            .run(CancellationToken::new(), &reporters)
+            .await
+            .unwrap();
+        let (mock, reporter) = reporters;
+        reporter.shutdown().await;
+
+        assert_eq!(summary.completed, 1);
+        assert!(store.save_calls() > 0);
+        assert!(mock.events.lock().unwrap().contains(&"JobCompleted".into()));
+    }
+}
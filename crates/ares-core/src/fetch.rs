@@ -0,0 +1,469 @@
+//! Per-request fetch options.
+//!
+//! [`FetchOptions`] travels with a one-shot scrape or a queued job and tells
+//! the pipeline how to fetch and clean one page: which fetcher to use, how
+//! long to wait, and which part of the page to keep. Everything is optional;
+//! the default leaves the fetcher's and extractor's own configuration alone.
+//!
+//! [`FetcherSelector`] lets one worker serve both plain and browser-rendered
+//! jobs by picking a fetcher per page from those options, and [`LazyFetcher`]
+//! defers building an expensive fetcher (a headless browser) until a page
+//! actually needs it.
+
+use std::collections::BTreeMap;
+use std::fmt;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+use tokio::sync::OnceCell;
+
+use crate::error::AppError;
+use crate::logging::REDACTED;
+use crate::traits::Fetcher;
+
+/// How to fetch and clean a single page.
+///
+/// Stored as JSON on scrape jobs, so every field defaults when absent.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct FetchOptions {
+    /// Render the page in a headless browser instead of a plain HTTP fetch.
+    pub use_browser: bool,
+    /// Overrides the fetcher's timeout for this page.
+    pub fetch_timeout_secs: Option<u64>,
+    /// Upper bound on the extraction call for this page.
+    pub llm_timeout_secs: Option<u64>,
+    /// CSS selector; only matching elements are cleaned and sent to the LLM.
+    pub content_selector: Option<String>,
+    /// CSS selector the browser waits for before capturing the page.
+    pub wait_selector: Option<String>,
+    /// Extra request headers sent with the page fetch.
+    pub headers: BTreeMap<String, String>,
+    /// Credentials sent with the page fetch.
+    pub auth: Option<FetchAuth>,
+}
+
+impl FetchOptions {
+    /// `true` when no option is set.
+    pub fn is_default(&self) -> bool {
+        *self == Self::default()
+    }
+
+    pub fn fetch_timeout(&self) -> Option<Duration> {
+        self.fetch_timeout_secs.map(Duration::from_secs)
+    }
+
+    pub fn llm_timeout(&self) -> Option<Duration> {
+        self.llm_timeout_secs.map(Duration::from_secs)
+    }
+
+    /// `true` when any option that only affects fetching is set, as opposed
+    /// to cleaning or extraction.
+    pub fn affects_fetch(&self) -> bool {
+        self.use_browser
+            || self.fetch_timeout_secs.is_some()
+            || self.wait_selector.is_some()
+            || !self.headers.is_empty()
+            || self.auth.is_some()
+    }
+
+    /// `true` when the fetch sends credentials or custom headers, so the page
+    /// it returns may be specific to them and must not be shared by URL.
+    pub fn personalizes_fetch(&self) -> bool {
+        !self.headers.is_empty() || self.auth.is_some()
+    }
+
+    /// These options with the auth secrets replaced by `[REDACTED]`, for
+    /// output that must not reveal them, like `ares job list --format json`.
+    pub fn redacted(&self) -> Self {
+        Self {
+            auth: self.auth.as_ref().map(FetchAuth::redacted),
+            ..self.clone()
+        }
+    }
+}
+
+/// Credentials for fetching a protected page.
+///
+/// Stored with the job like the other options, but never shown: `Debug`
+/// redacts the secrets, and API responses only report [`kind`](Self::kind).
+#[derive(Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "lowercase")]
+pub enum FetchAuth {
+    /// HTTP Basic authentication.
+    Basic { user: String, pass: String },
+    /// `Authorization: Bearer <token>`.
+    Bearer { token: String },
+    /// A custom header, e.g. `X-Api-Key`.
+    Header { name: String, value: String },
+}
+
+impl FetchAuth {
+    /// `"basic"`, `"bearer"`, or `"header"`.
+    pub fn kind(&self) -> &'static str {
+        match self {
+            Self::Basic { .. } => "basic",
+            Self::Bearer { .. } => "bearer",
+            Self::Header { .. } => "header",
+        }
+    }
+
+    /// The same credentials with the secret part replaced by `[REDACTED]`.
+    pub fn redacted(&self) -> Self {
+        match self {
+            Self::Basic { user, .. } => Self::Basic {
+                user: user.clone(),
+                pass: REDACTED.to_string(),
+            },
+            Self::Bearer { .. } => Self::Bearer {
+                token: REDACTED.to_string(),
+            },
+            Self::Header { name, .. } => Self::Header {
+                name: name.clone(),
+                value: REDACTED.to_string(),
+            },
+        }
+    }
+}
+
+impl fmt::Debug for FetchAuth {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Basic { user, .. } => f
+                .debug_struct("Basic")
+                .field("user", user)
+                .field("pass", &REDACTED)
+                .finish(),
+            Self::Bearer { .. } => f.debug_struct("Bearer").field("token", &REDACTED).finish(),
+            Self::Header { name, .. } => f
+                .debug_struct("Header")
+                .field("name", name)
+                .field("value", &REDACTED)
+                .finish(),
+        }
+    }
+}
+
+/// Routes each fetch to an HTTP or a browser fetcher.
+///
+/// Pages whose [`FetchOptions::use_browser`] is set go to the browser; the
+/// rest go over plain HTTP, unless the selector is built to render every page
+/// with [`with_browser_by_default`](Self::with_browser_by_default). A page
+/// that needs the browser when none was configured fails with a
+/// non-retryable [`AppError::ConfigError`], since retrying on the same worker
+/// cannot succeed.
+#[derive(Debug, Clone)]
+pub struct FetcherSelector<H, B> {
+    http: H,
+    browser: Option<B>,
+    browser_by_default: bool,
+}
+
+impl<H: Fetcher, B: Fetcher> FetcherSelector<H, B> {
+    /// A selector with only the HTTP fetcher.
+    pub fn new(http: H) -> Self {
+        Self {
+            http,
+            browser: None,
+            browser_by_default: false,
+        }
+    }
+
+    /// Serve pages that ask for a browser with `browser`.
+    pub fn with_browser(mut self, browser: B) -> Self {
+        self.browser = Some(browser);
+        self
+    }
+
+    /// Render every page in the browser, not only those that ask for it.
+    pub fn with_browser_by_default(mut self, enabled: bool) -> Self {
+        self.browser_by_default = enabled;
+        self
+    }
+
+    /// Whether a browser fetcher is configured.
+    pub fn has_browser(&self) -> bool {
+        self.browser.is_some()
+    }
+
+    fn browser(&self) -> Result<&B, AppError> {
+        self.browser.as_ref().ok_or_else(|| {
+            AppError::ConfigError(
+                "page requires a headless browser, but this worker has no browser fetcher \
+                 (it must be built with the `browser` feature)"
+                    .to_string(),
+            )
+        })
+    }
+}
+
+impl<H: Fetcher, B: Fetcher> Fetcher for FetcherSelector<H, B> {
+    async fn fetch(&self, url: &str) -> Result<String, AppError> {
+        if self.browser_by_default {
+            self.browser()?.fetch(url).await
+        } else {
+            self.http.fetch(url).await
+        }
+    }
+
+    async fn fetch_with(&self, url: &str, options: &FetchOptions) -> Result<String, AppError> {
+        if options.use_browser || self.browser_by_default {
+            self.browser()?.fetch_with(url, options).await
+        } else {
+            self.http.fetch_with(url, options).await
+        }
+    }
+}
+
+type FetcherInit<F> =
+    Arc<dyn Fn() -> Pin<Box<dyn Future<Output = Result<F, AppError>> + Send>> + Send + Sync>;
+
+/// A fetcher built on first use and shared by every clone afterwards.
+///
+/// If building fails, the error is returned for that fetch and the next
+/// fetch tries again.
+pub struct LazyFetcher<F> {
+    cell: Arc<OnceCell<F>>,
+    init: FetcherInit<F>,
+}
+
+impl<F> Clone for LazyFetcher<F> {
+    fn clone(&self) -> Self {
+        Self {
+            cell: self.cell.clone(),
+            init: self.init.clone(),
+        }
+    }
+}
+
+impl<F: Fetcher + 'static> LazyFetcher<F> {
+    /// A fetcher that runs `init` the first time it is used.
+    pub fn new<Fut>(init: impl Fn() -> Fut + Send + Sync + 'static) -> Self
+    where
+        Fut: Future<Output = Result<F, AppError>> + Send + 'static,
+    {
+        Self {
+            cell: Arc::new(OnceCell::new()),
+            init: Arc::new(move || Box::pin(init())),
+        }
+    }
+
+    /// The inner fetcher, building it if this is the first use.
+    pub async fn get(&self) -> Result<&F, AppError> {
+        self.cell.get_or_try_init(|| (self.init)()).await
+    }
+}
+
+impl<F: Fetcher + 'static> Fetcher for LazyFetcher<F> {
+    async fn fetch(&self, url: &str) -> Result<String, AppError> {
+        self.get().await?.fetch(url).await
+    }
+
+    async fn fetch_with(&self, url: &str, options: &FetchOptions) -> Result<String, AppError> {
+        self.get().await?.fetch_with(url, options).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    use super::*;
+    use crate::testutil::MockFetcher;
+
+    fn browser_options() -> FetchOptions {
+        FetchOptions {
+            use_browser: true,
+            ..FetchOptions::default()
+        }
+    }
+
+    #[test]
+    fn missing_fields_default() {
+        let options: FetchOptions = serde_json::from_str("{}").unwrap();
+        assert!(options.is_default());
+        assert!(!options.affects_fetch());
+
+        let options: FetchOptions =
+            serde_json::from_str(r#"{"use_browser": true, "fetch_timeout_secs": 5}"#).unwrap();
+        assert!(options.use_browser);
+        assert_eq!(options.fetch_timeout(), Some(Duration::from_secs(5)));
+        assert!(options.affects_fetch());
+    }
+
+    #[test]
+    fn content_selector_alone_does_not_affect_fetch() {
+        let options = FetchOptions {
+            content_selector: Some("main".into()),
+            llm_timeout_secs: Some(30),
+            ..FetchOptions::default()
+        };
+        assert!(!options.affects_fetch());
+    }
+
+    #[test]
+    fn headers_affect_fetch() {
+        let options: FetchOptions =
+            serde_json::from_str(r#"{"headers": {"Accept-Language": "it"}}"#).unwrap();
+        assert_eq!(options.headers["Accept-Language"], "it");
+        assert!(options.affects_fetch());
+    }
+
+    #[test]
+    fn auth_round_trips_and_debug_redacts_secrets() {
+        let options: FetchOptions = serde_json::from_str(
+            r#"{"auth": {"type": "basic", "user": "ada", "pass": "hunter2"}}"#,
+        )
+        .unwrap();
+        let auth = options.auth.clone().unwrap();
+        assert_eq!(
+            auth,
+            FetchAuth::Basic {
+                user: "ada".into(),
+                pass: "hunter2".into()
+            }
+        );
+        assert_eq!(auth.kind(), "basic");
+        assert!(options.affects_fetch());
+
+        let debug = format!("{options:?}");
+        assert!(debug.contains("ada"), "{debug}");
+        assert!(!debug.contains("hunter2"), "{debug}");
+
+        for auth in [
+            FetchAuth::Bearer {
+                token: "s3cret".into(),
+            },
+            FetchAuth::Header {
+                name: "X-Api-Key".into(),
+                value: "s3cret".into(),
+            },
+        ] {
+            let debug = format!("{auth:?}");
+            assert!(!debug.contains("s3cret"), "{debug}");
+            let json = serde_json::to_value(&auth).unwrap();
+            assert_eq!(json["type"], auth.kind());
+            assert_eq!(serde_json::from_value::<FetchAuth>(json).unwrap(), auth);
+        }
+    }
+
+    #[test]
+    fn redacted_options_keep_everything_but_the_secrets() {
+        let options = FetchOptions {
+            use_browser: true,
+            auth: Some(FetchAuth::Basic {
+                user: "ada".into(),
+                pass: "hunter2".into(),
+            }),
+            ..FetchOptions::default()
+        };
+        let json = serde_json::to_string(&options.redacted()).unwrap();
+        assert!(!json.contains("hunter2"), "{json}");
+        assert!(json.contains(r#""user":"ada""#), "{json}");
+        assert!(json.contains(r#""use_browser":true"#), "{json}");
+
+        for auth in [
+            FetchAuth::Bearer {
+                token: "s3cret".into(),
+            },
+            FetchAuth::Header {
+                name: "X-Api-Key".into(),
+                value: "s3cret".into(),
+            },
+        ] {
+            let redacted = auth.redacted();
+            assert_eq!(redacted.kind(), auth.kind());
+            let json = serde_json::to_string(&redacted).unwrap();
+            assert!(!json.contains("s3cret"), "{json}");
+            assert!(json.contains(REDACTED), "{json}");
+        }
+    }
+
+    #[tokio::test]
+    async fn selector_routes_browser_pages_to_the_browser() {
+        let selector = FetcherSelector::new(MockFetcher::new("http"))
+            .with_browser(MockFetcher::new("browser"));
+
+        let html = selector
+            .fetch_with("https://a.example", &browser_options())
+            .await
+            .unwrap();
+        assert_eq!(html, "browser");
+        let html = selector
+            .fetch_with("https://a.example", &FetchOptions::default())
+            .await
+            .unwrap();
+        assert_eq!(html, "http");
+    }
+
+    #[tokio::test]
+    async fn selector_renders_everything_when_browser_is_the_default() {
+        let selector = FetcherSelector::new(MockFetcher::new("http"))
+            .with_browser(MockFetcher::with_responses(vec![
+                Ok("first".into()),
+                Ok("second".into()),
+            ]))
+            .with_browser_by_default(true);
+
+        assert_eq!(selector.fetch("https://a.example").await.unwrap(), "first");
+        let html = selector
+            .fetch_with("https://a.example", &FetchOptions::default())
+            .await
+            .unwrap();
+        assert_eq!(html, "second");
+    }
+
+    #[tokio::test]
+    async fn selector_without_browser_fails_browser_pages_permanently() {
+        let selector: FetcherSelector<_, MockFetcher> =
+            FetcherSelector::new(MockFetcher::new("http"));
+        assert!(!selector.has_browser());
+
+        let err = selector
+            .fetch_with("https://a.example", &browser_options())
+            .await
+            .unwrap_err();
+        assert!(matches!(err, AppError::ConfigError(_)));
+        assert!(!err.is_retryable());
+        assert!(err.to_string().contains("`browser` feature"));
+    }
+
+    #[tokio::test]
+    async fn lazy_fetcher_builds_once_on_first_use() {
+        let builds = Arc::new(AtomicUsize::new(0));
+        let counter = builds.clone();
+        let lazy = LazyFetcher::new(move || {
+            counter.fetch_add(1, Ordering::SeqCst);
+            async { Ok(MockFetcher::new("lazy")) }
+        });
+        assert_eq!(builds.load(Ordering::SeqCst), 0);
+
+        let clone = lazy.clone();
+        assert_eq!(lazy.fetch("https://a.example").await.unwrap(), "lazy");
+        clone.fetch("https://a.example").await.unwrap();
+        assert_eq!(builds.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn lazy_fetcher_retries_a_failed_build() {
+        let builds = Arc::new(AtomicUsize::new(0));
+        let counter = builds.clone();
+        let lazy = LazyFetcher::new(move || {
+            let attempt = counter.fetch_add(1, Ordering::SeqCst);
+            async move {
+                if attempt == 0 {
+                    Err(AppError::Generic("no chromium".into()))
+                } else {
+                    Ok(MockFetcher::new("lazy"))
+                }
+            }
+        });
+
+        assert!(lazy.fetch("https://a.example").await.is_err());
+        assert_eq!(lazy.fetch("https://a.example").await.unwrap(), "lazy");
+        assert_eq!(builds.load(Ordering::SeqCst), 2);
+    }
+}
@@ -36,14 +36,12 @@ pub fn ungrounded_fields(source: &str, value: &Value) -> Vec<String> {
 
 fn walk(value: &Value, path: &mut String, source: &str, out: &mut Vec<String>) {
     match value {
-        Value::String(s) => {
-            if is_checkable(s) && !is_grounded(s, source) {
-                out.push(if path.is_empty() {
-                    "<root>".to_string()
-                } else {
-                    path.clone()
-                });
-            }
+        Value::String(s) if is_checkable(s) && !is_grounded(s, source) => {
+            out.push(if path.is_empty() {
+                "<root>".to_string()
+            } else {
+                path.clone()
+            });
         }
         Value::Array(items) => {
             for (i, item) in items.iter().enumerate() {
@@ -64,7 +62,7 @@ fn walk(value: &Value, path: &mut String, source: &str, out: &mut Vec<String>) {
                 path.truncate(len);
             }
         }
-        _ => {} // numbers / bools / null are not groundedness-checked
+        _ => {} // grounded strings; numbers / bools / null are not checked
     }
 }
 
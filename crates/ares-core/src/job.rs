@@ -1,4 +1,5 @@
 use std::fmt;
+use std::path::PathBuf;
 use std::str::FromStr;
 use std::time::Duration;
 
@@ -6,6 +7,9 @@ use chrono::{DateTime, TimeDelta, Utc};
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
+use crate::error::AppError;
+use crate::fetch::FetchOptions;
+
 /// Status of a scrape job in the queue.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "lowercase")]
@@ -18,6 +22,14 @@ pub enum JobStatus {
 }
 
 impl JobStatus {
+    pub const ALL: [JobStatus; 5] = [
+        JobStatus::Pending,
+        JobStatus::Running,
+        JobStatus::Completed,
+        JobStatus::Failed,
+        JobStatus::Cancelled,
+    ];
+
     pub fn as_str(&self) -> &'static str {
         match self {
             JobStatus::Pending => "pending",
@@ -28,6 +40,20 @@ impl JobStatus {
         }
     }
 
+    /// Parse a status as stored in `scrape_jobs.status`.
+    ///
+    /// Unlike [`FromStr`], which accepts any case from user input, only the
+    /// exact stored names are valid; anything else means a corrupt row and
+    /// is reported rather than mapped to some default status.
+    pub fn from_db_str(s: &str) -> Result<Self, AppError> {
+        JobStatus::ALL
+            .into_iter()
+            .find(|status| status.as_str() == s)
+            .ok_or_else(|| {
+                AppError::DatabaseError(format!("Corrupt job status in database: '{s}'"))
+            })
+    }
+
     pub fn is_terminal(&self) -> bool {
         matches!(
             self,
@@ -45,15 +71,19 @@ impl fmt::Display for JobStatus {
 impl FromStr for JobStatus {
     type Err = String;
 
+    /// Case-insensitive; the error lists the valid statuses.
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        match s.to_lowercase().as_str() {
-            "pending" => Ok(JobStatus::Pending),
-            "running" => Ok(JobStatus::Running),
-            "completed" => Ok(JobStatus::Completed),
-            "failed" => Ok(JobStatus::Failed),
-            "cancelled" => Ok(JobStatus::Cancelled),
-            _ => Err(format!("Unknown job status: {s}")),
-        }
+        let lower = s.to_lowercase();
+        JobStatus::ALL
+            .into_iter()
+            .find(|status| status.as_str() == lower)
+            .ok_or_else(|| {
+                let valid: Vec<_> = JobStatus::ALL.iter().map(JobStatus::as_str).collect();
+                format!(
+                    "unknown job status '{s}' (expected one of: {})",
+                    valid.join(", ")
+                )
+            })
     }
 }
 
@@ -106,6 +136,7 @@ pub struct ScrapeJob {
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
     pub started_at: Option<DateTime<Utc>>,
+    /// When the job completed or permanently failed.
     pub completed_at: Option<DateTime<Utc>>,
     pub retry_count: u32,
     pub max_retries: u32,
@@ -119,9 +150,45 @@ pub struct ScrapeJob {
     pub max_depth: u32,
     pub max_pages: u32,
     pub allowed_domains: Vec<String>,
+    /// How this job's page is fetched and cleaned.
+    pub fetch_options: FetchOptions,
+    /// Webhook told when this job's extracted data changes, in place of the
+    /// worker's configured one. May embed a secret token, so it is never
+    /// serialized.
+    #[serde(default, skip_serializing)]
+    pub notify_url: Option<String>,
+    /// Free-form labels for grouping jobs, e.g. by the pipeline run that
+    /// created them.
+    #[serde(default)]
+    pub tags: Vec<String>,
 }
 
 impl ScrapeJob {
+    /// Short human-readable label, `"{schema_name}@{host}"` (e.g.
+    /// `"blog@example.com"`), for log lines and tables. Falls back to the
+    /// full URL when it has no host.
+    pub fn display_name(&self) -> String {
+        match url::Url::parse(&self.url)
+            .ok()
+            .and_then(|u| u.host_str().map(String::from))
+        {
+            Some(host) => format!("{}@{host}", self.schema_name),
+            None => format!("{}@{}", self.schema_name, self.url),
+        }
+    }
+
+    /// Time since the job was created; zero if `created_at` is in the future.
+    pub fn age(&self) -> Duration {
+        (Utc::now() - self.created_at).to_std().unwrap_or_default()
+    }
+
+    /// Time until the scheduled retry, or `None` if none is scheduled; zero
+    /// once the retry is due.
+    pub fn time_to_retry(&self) -> Option<Duration> {
+        self.next_retry_at
+            .map(|at| (at - Utc::now()).to_std().unwrap_or_default())
+    }
+
     pub fn can_retry(&self) -> bool {
         self.retry_count < self.max_retries
     }
@@ -147,6 +214,9 @@ pub struct CreateScrapeJobRequest {
     pub max_depth: u32,
     pub max_pages: u32,
     pub allowed_domains: Vec<String>,
+    pub fetch_options: FetchOptions,
+    pub notify_url: Option<String>,
+    pub tags: Vec<String>,
 }
 
 impl CreateScrapeJobRequest {
@@ -170,6 +240,9 @@ impl CreateScrapeJobRequest {
             max_depth: 0,
             max_pages: 100,
             allowed_domains: Vec::new(),
+            fetch_options: FetchOptions::default(),
+            notify_url: None,
+            tags: Vec::new(),
         }
     }
 
@@ -197,6 +270,24 @@ impl CreateScrapeJobRequest {
         self.allowed_domains = allowed_domains;
         self
     }
+
+    pub fn with_fetch_options(mut self, options: FetchOptions) -> Self {
+        self.fetch_options = options;
+        self
+    }
+
+    /// Send this job's change notifications to `url` instead of the
+    /// worker's webhook.
+    pub fn with_notify_url(mut self, url: Option<String>) -> Self {
+        self.notify_url = url;
+        self
+    }
+
+    /// Label the job with `tags`, to list or delete it with others later.
+    pub fn with_tags(mut self, tags: Vec<String>) -> Self {
+        self.tags = tags;
+        self
+    }
 }
 
 /// Configuration for a worker process.
@@ -206,8 +297,21 @@ pub struct WorkerConfig {
     pub poll_interval: Duration,
     pub retry_config: RetryConfig,
     pub skip_unchanged: bool,
+    /// Persist via upsert, updating the existing record for unchanged data.
+    pub upsert: bool,
+    /// Keep the cleaned content sent to the extractor with each saved
+    /// extraction.
+    pub store_content: bool,
     /// LLM provider name recorded in extraction run metadata (e.g. `openai`).
     pub provider: String,
+    /// Stop after processing this many jobs.
+    pub max_jobs: Option<u64>,
+    /// Stop as soon as the queue has no claimable job instead of polling.
+    pub run_once: bool,
+    /// Schemas directory; when set, extracted data is also validated against
+    /// the job's schema file here, which may be newer than the copy stored
+    /// with the job.
+    pub schemas_dir: Option<PathBuf>,
 }
 
 impl Default for WorkerConfig {
@@ -217,7 +321,12 @@ impl Default for WorkerConfig {
             poll_interval: Duration::from_secs(5),
             retry_config: RetryConfig::default(),
             skip_unchanged: false,
+            upsert: false,
+            store_content: false,
             provider: "openai".to_string(),
+            max_jobs: None,
+            run_once: false,
+            schemas_dir: None,
         }
     }
 }
@@ -238,10 +347,37 @@ impl WorkerConfig {
         self
     }
 
+    pub fn with_upsert(mut self, upsert: bool) -> Self {
+        self.upsert = upsert;
+        self
+    }
+
+    pub fn with_store_content(mut self, store: bool) -> Self {
+        self.store_content = store;
+        self
+    }
+
     pub fn with_provider(mut self, provider: impl Into<String>) -> Self {
         self.provider = provider.into();
         self
     }
+
+    pub fn with_max_jobs(mut self, max_jobs: u64) -> Self {
+        self.max_jobs = Some(max_jobs);
+        self
+    }
+
+    pub fn with_run_once(mut self, run_once: bool) -> Self {
+        self.run_once = run_once;
+        self
+    }
+
+    /// Also validate extracted data against the job's schema file in `dir`;
+    /// see [`WorkerConfig::schemas_dir`].
+    pub fn with_schemas_dir(mut self, dir: impl Into<PathBuf>) -> Self {
+        self.schemas_dir = Some(dir.into());
+        self
+    }
 }
 
 #[cfg(test)]
@@ -250,17 +386,33 @@ mod tests {
 
     #[test]
     fn test_job_status_roundtrip() {
-        for status in [
-            JobStatus::Pending,
-            JobStatus::Running,
-            JobStatus::Completed,
-            JobStatus::Failed,
-            JobStatus::Cancelled,
-        ] {
+        for status in JobStatus::ALL {
             let s = status.as_str();
             let parsed: JobStatus = s.parse().unwrap();
             assert_eq!(parsed, status);
         }
+        assert_eq!("Failed".parse::<JobStatus>(), Ok(JobStatus::Failed));
+
+        let err = "done".parse::<JobStatus>().unwrap_err();
+        assert!(
+            err.contains("expected one of: pending, running, completed, failed, cancelled"),
+            "{err}"
+        );
+    }
+
+    #[test]
+    fn from_db_str_rejects_corrupt_statuses() {
+        assert_eq!(
+            JobStatus::from_db_str("completed").unwrap(),
+            JobStatus::Completed
+        );
+        for corrupt in ["", "COMPLETED", "done", " pending"] {
+            let err = JobStatus::from_db_str(corrupt).unwrap_err();
+            assert!(
+                matches!(err, AppError::DatabaseError(_)),
+                "{corrupt}: {err}"
+            );
+        }
     }
 
     #[test]
@@ -272,6 +424,34 @@ mod tests {
         assert!(JobStatus::Cancelled.is_terminal());
     }
 
+    #[test]
+    fn test_display_name_uses_url_host() {
+        let mut job = crate::testutil::make_test_job();
+        job.schema_name = "blog".into();
+        job.url = "https://www.example.com:8443/posts/1?page=2".into();
+        assert_eq!(job.display_name(), "blog@www.example.com");
+
+        job.url = "not a url".into();
+        assert_eq!(job.display_name(), "blog@not a url");
+    }
+
+    #[test]
+    fn test_age_and_time_to_retry() {
+        let mut job = crate::testutil::make_test_job();
+        job.created_at = Utc::now() - TimeDelta::minutes(5);
+        assert!(job.age() >= Duration::from_secs(300));
+        job.created_at = Utc::now() + TimeDelta::minutes(5);
+        assert_eq!(job.age(), Duration::ZERO);
+
+        job.next_retry_at = None;
+        assert_eq!(job.time_to_retry(), None);
+        job.next_retry_at = Some(Utc::now() + TimeDelta::minutes(5));
+        let retry_in = job.time_to_retry().unwrap();
+        assert!(retry_in > Duration::from_secs(290) && retry_in <= Duration::from_secs(300));
+        job.next_retry_at = Some(Utc::now() - TimeDelta::minutes(5));
+        assert_eq!(job.time_to_retry(), Some(Duration::ZERO));
+    }
+
     #[test]
     fn test_retry_delay_schedule() {
         let config = RetryConfig::default();
@@ -322,6 +502,9 @@ mod tests {
             max_depth: 0,
             max_pages: 100,
             allowed_domains: Vec::new(),
+            fetch_options: FetchOptions::default(),
+            notify_url: None,
+            tags: Vec::new(),
         };
         assert!(!job.can_retry());
 
@@ -357,6 +540,9 @@ mod tests {
             max_depth: 0,
             max_pages: 100,
             allowed_domains: Vec::new(),
+            fetch_options: FetchOptions::default(),
+            notify_url: None,
+            tags: Vec::new(),
         };
         assert!(!job.can_retry());
     }
@@ -3,8 +3,11 @@ use std::future::Future;
 use chrono::{DateTime, Utc};
 use uuid::Uuid;
 
+use crate::circuit_breaker::CircuitBreakerStats;
 use crate::error::AppError;
 use crate::job::{CreateScrapeJobRequest, JobStatus, ScrapeJob};
+use crate::models::NewExtraction;
+use crate::traits::ExtractionStore;
 
 /// Persistent job queue for scrape jobs.
 ///
@@ -16,6 +19,23 @@ pub trait JobQueue: Send + Sync + Clone {
         request: CreateScrapeJobRequest,
     ) -> impl Future<Output = Result<ScrapeJob, AppError>> + Send;
 
+    /// Create every job in `requests`, in order, all or none.
+    ///
+    /// The default implementation creates one job at a time (and so isn't
+    /// atomic); backends should override it with a batch insert.
+    fn create_jobs(
+        &self,
+        requests: Vec<CreateScrapeJobRequest>,
+    ) -> impl Future<Output = Result<Vec<ScrapeJob>, AppError>> + Send {
+        async move {
+            let mut jobs = Vec::with_capacity(requests.len());
+            for request in requests {
+                jobs.push(self.create_job(request).await?);
+            }
+            Ok(jobs)
+        }
+    }
+
     /// Atomically claim the next pending job for processing.
     ///
     /// Returns `None` if no jobs are available.
@@ -30,8 +50,34 @@ pub trait JobQueue: Send + Sync + Clone {
         extraction_id: Option<Uuid>,
     ) -> impl Future<Output = Result<(), AppError>> + Send;
 
+    /// Save `extraction` (upserting it when `upsert` is set) and mark the
+    /// job completed with it. Returns the extraction's ID.
+    ///
+    /// The default saves through `store`, then completes the job, so a failure
+    /// in between leaves the extraction saved and the job running. Queues
+    /// that share a database with the extraction store override this to do
+    /// both in one transaction.
+    fn complete_job_with_extraction<S: ExtractionStore>(
+        &self,
+        job_id: Uuid,
+        store: &S,
+        extraction: &NewExtraction,
+        upsert: bool,
+    ) -> impl Future<Output = Result<Uuid, AppError>> + Send {
+        async move {
+            let id = if upsert {
+                store.upsert(extraction).await?
+            } else {
+                store.save(extraction).await?
+            };
+            self.complete_job(job_id, Some(id)).await?;
+            Ok(id)
+        }
+    }
+
     /// Mark a job as failed. If `next_retry_at` is provided, the job is
     /// reset to `pending` for retry; otherwise it is marked as permanently `failed`.
+    /// Completed jobs are left as they are.
     fn fail_job(
         &self,
         job_id: Uuid,
@@ -39,16 +85,51 @@ pub trait JobQueue: Send + Sync + Clone {
         next_retry_at: Option<DateTime<Utc>>,
     ) -> impl Future<Output = Result<(), AppError>> + Send;
 
+    /// Put a job back to `pending` until `until` without counting a retry,
+    /// recording `reason` as its error. For jobs that didn't fail but can't
+    /// run yet, such as when the daily LLM budget is spent. Completed jobs
+    /// are left as they are.
+    fn defer_job(
+        &self,
+        job_id: Uuid,
+        reason: &str,
+        until: DateTime<Utc>,
+    ) -> impl Future<Output = Result<(), AppError>> + Send;
+
     fn cancel_job(&self, job_id: Uuid) -> impl Future<Output = Result<(), AppError>> + Send;
 
+    /// Cancel every listed job that is not already completed or cancelled.
+    /// Unknown IDs are ignored. Returns the number of jobs cancelled.
+    ///
+    /// The default implementation cancels one job at a time; backends should
+    /// override it with a single statement.
+    fn bulk_cancel(&self, job_ids: &[Uuid]) -> impl Future<Output = Result<u64, AppError>> + Send {
+        async move {
+            let mut cancelled = 0;
+            for &job_id in job_ids {
+                if let Some(job) = self.get_job(job_id).await?
+                    && !matches!(job.status, JobStatus::Completed | JobStatus::Cancelled)
+                {
+                    self.cancel_job(job_id).await?;
+                    cancelled += 1;
+                }
+            }
+            Ok(cancelled)
+        }
+    }
+
     fn get_job(
         &self,
         job_id: Uuid,
     ) -> impl Future<Output = Result<Option<ScrapeJob>, AppError>> + Send;
 
+    /// Jobs newest first, optionally filtered by status, by the worker that
+    /// claimed them, and by a tag they carry.
     fn list_jobs(
         &self,
         status: Option<JobStatus>,
+        worker_id: Option<&str>,
+        tag: Option<&str>,
         limit: usize,
         offset: usize,
     ) -> impl Future<Output = Result<Vec<ScrapeJob>, AppError>> + Send;
@@ -60,6 +141,17 @@ pub trait JobQueue: Send + Sync + Clone {
         job_id: Uuid,
     ) -> impl Future<Output = Result<Option<ScrapeJob>, AppError>> + Send;
 
+    /// Replace the schema of a pending job, so a schema bug can be fixed
+    /// before a worker claims the job.
+    ///
+    /// Fails with [`AppError::Conflict`] if the job isn't pending, including
+    /// when it doesn't exist.
+    fn update_job_schema(
+        &self,
+        job_id: Uuid,
+        new_schema: serde_json::Value,
+    ) -> impl Future<Output = Result<(), AppError>> + Send;
+
     // TODO(#3): Currently unused — will be needed for per-job release during crawl cancellation
     fn release_job(&self, job_id: Uuid) -> impl Future<Output = Result<(), AppError>> + Send;
 
@@ -74,6 +166,12 @@ pub trait JobQueue: Send + Sync + Clone {
         status: JobStatus,
     ) -> impl Future<Output = Result<i64, AppError>> + Send;
 
+    /// Count the jobs a worker is currently running.
+    fn count_by_worker(
+        &self,
+        worker_id: &str,
+    ) -> impl Future<Output = Result<i64, AppError>> + Send;
+
     /// Atomically mark a URL as visited in a crawl session.
     ///
     /// Returns `true` if the URL was newly inserted, `false` if it was already visited.
@@ -88,4 +186,26 @@ pub trait JobQueue: Send + Sync + Clone {
         &self,
         session_id: Uuid,
     ) -> impl Future<Output = Result<i64, AppError>> + Send;
+
+    /// Publish a worker's circuit-breaker state so it can be inspected from
+    /// outside the worker process. The default implementation discards it.
+    fn save_circuit_breaker(
+        &self,
+        stats: &CircuitBreakerStats,
+    ) -> impl Future<Output = Result<(), AppError>> + Send {
+        let _ = stats;
+        async { Ok(()) }
+    }
+
+    /// When an operator last requested a reset of the named circuit breaker.
+    ///
+    /// Workers reset their local breaker whenever this moves forward. The
+    /// default implementation never requests a reset.
+    fn circuit_breaker_reset_requested_at(
+        &self,
+        name: &str,
+    ) -> impl Future<Output = Result<Option<DateTime<Utc>>, AppError>> + Send {
+        let _ = name;
+        async { Ok(None) }
+    }
 }
@@ -0,0 +1,97 @@
+//! Source-language detection and the schema's output-language hint.
+//!
+//! Sites in several languages often share one schema, and a model reading a
+//! German page tends to answer in German even when the schema's enums are
+//! English. Two small pieces address that:
+//! - [`detect_language`] tags each scrape with the language of its cleaned
+//!   content, so results can be filtered and audited by it.
+//! - A schema may declare `"x-ares-output-language": "en"`; extractors append
+//!   [`output_language_instruction`] to their prompt so the model translates
+//!   rather than copies.
+
+use serde_json::Value;
+
+/// Schema keyword naming the language extracted values should be written in.
+pub const OUTPUT_LANGUAGE_KEY: &str = "x-ares-output-language";
+
+/// Detect the language of `text`, as an ISO 639-3 code (`eng`, `deu`, ...).
+///
+/// Returns `None` when the detector isn't confident — typically for short or
+/// mixed-language content — rather than guessing.
+pub fn detect_language(text: &str) -> Option<String> {
+    whatlang::detect(text)
+        .filter(|info| info.is_reliable())
+        .map(|info| info.lang().code().to_string())
+}
+
+/// The output language a schema declares under [`OUTPUT_LANGUAGE_KEY`], if any.
+pub fn output_language(schema: &Value) -> Option<&str> {
+    schema
+        .get(OUTPUT_LANGUAGE_KEY)
+        .and_then(Value::as_str)
+        .map(str::trim)
+        .filter(|lang| !lang.is_empty())
+}
+
+/// The prompt instruction for a schema's declared output language, ready to be
+/// appended to an extractor's user message. `None` when the schema declares none.
+pub fn output_language_instruction(schema: &Value) -> Option<String> {
+    output_language(schema).map(|lang| {
+        format!(
+            "\n\nWrite every extracted text value in the language `{lang}`, translating from the source content where it is written in another language. Enum values must match the schema exactly."
+        )
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    const ENGLISH: &str = "The city council approved the new budget on Tuesday evening after a long debate about public transport, school funding and the repair of several old bridges across the river. Residents who attended the meeting said they were pleased with the outcome.";
+    const GERMAN: &str = "Der Stadtrat hat am Dienstagabend nach einer langen Debatte über den öffentlichen Nahverkehr, die Finanzierung der Schulen und die Reparatur mehrerer alter Brücken über den Fluss den neuen Haushalt beschlossen. Die Bürger waren mit dem Ergebnis zufrieden.";
+    const FRENCH: &str = "Le conseil municipal a approuvé le nouveau budget mardi soir après un long débat sur les transports publics, le financement des écoles et la réparation de plusieurs vieux ponts sur la rivière. Les habitants présents se sont dits satisfaits du résultat.";
+    const SPANISH: &str = "El ayuntamiento aprobó el nuevo presupuesto el martes por la noche después de un largo debate sobre el transporte público, la financiación de las escuelas y la reparación de varios puentes antiguos sobre el río. Los vecinos se mostraron satisfechos con el resultado.";
+    const ITALIAN: &str = "Il consiglio comunale ha approvato il nuovo bilancio martedì sera dopo un lungo dibattito sui trasporti pubblici, sul finanziamento delle scuole e sulla riparazione di diversi vecchi ponti sul fiume. I cittadini presenti si sono detti soddisfatti del risultato.";
+
+    #[test]
+    fn detects_each_fixture_language() {
+        assert_eq!(detect_language(ENGLISH).as_deref(), Some("eng"));
+        assert_eq!(detect_language(GERMAN).as_deref(), Some("deu"));
+        assert_eq!(detect_language(FRENCH).as_deref(), Some("fra"));
+        assert_eq!(detect_language(SPANISH).as_deref(), Some("spa"));
+        assert_eq!(detect_language(ITALIAN).as_deref(), Some("ita"));
+    }
+
+    #[test]
+    fn gives_up_on_empty_or_ambiguous_text() {
+        assert_eq!(detect_language(""), None);
+        assert_eq!(detect_language("42 — 17"), None);
+    }
+
+    #[test]
+    fn reads_the_declared_output_language() {
+        let schema = json!({"type": "object", "x-ares-output-language": " en "});
+        assert_eq!(output_language(&schema), Some("en"));
+
+        let instruction = output_language_instruction(&schema).unwrap();
+        assert!(instruction.contains("language `en`"));
+        assert!(instruction.starts_with("\n\n"));
+    }
+
+    #[test]
+    fn no_instruction_without_a_usable_declaration() {
+        assert_eq!(
+            output_language_instruction(&json!({"type": "object"})),
+            None
+        );
+        assert_eq!(
+            output_language_instruction(&json!({"x-ares-output-language": ""})),
+            None
+        );
+        assert_eq!(
+            output_language_instruction(&json!({"x-ares-output-language": 1})),
+            None
+        );
+    }
+}
@@ -1,46 +1,80 @@
 //! Core library for Ares — traits, pipeline logic, job scheduling, and error types.
 
+pub mod api_key;
+pub mod audit;
+pub mod budget;
 pub mod cache;
 pub mod circuit_breaker;
 pub mod crawl;
+pub mod diff;
 pub mod error;
+pub mod event_log;
+pub mod fetch;
 pub mod groundedness;
 pub mod job;
 pub mod job_queue;
+pub mod language;
+pub mod logging;
 pub mod models;
 pub mod proxy;
 pub mod rand;
+pub mod retention;
 pub mod schema;
 pub mod scrape;
+pub mod snapshot;
 pub mod stealth;
 pub mod throttle;
 pub mod traits;
+pub mod verify;
 pub mod worker;
 
-#[cfg(test)]
+#[cfg(feature = "test-util")]
 pub mod testutil;
 
+pub use api_key::{ApiKey, ApiKeyScope, generate_api_key, hash_api_key};
+pub use audit::{AuditAction, AuditEntry, NewAuditEntry};
+pub use budget::{
+    BudgetGuard, BudgetLimits, BudgetedExtractor, BudgetedExtractorFactory, DailyUsage, UsageStore,
+};
 pub use cache::{CacheConfig, ContentCache, ExtractionCache};
-pub use circuit_breaker::{CircuitBreaker, CircuitBreakerConfig};
+pub use circuit_breaker::{
+    CircuitBreaker, CircuitBreakerConfig, CircuitBreakerRegistry, CircuitBreakingExtractor,
+};
 pub use crawl::CrawlConfig;
+pub use diff::{JsonChange, diff_json};
 pub use error::AppError;
+pub use event_log::{
+    NewWorkerEvent, PersistingWorkerReporter, WorkerEventRecord, WorkerEventStore,
+};
+pub use fetch::{FetchAuth, FetchOptions, FetcherSelector, LazyFetcher};
 pub use groundedness::ungrounded_fields;
 pub use job::{CreateScrapeJobRequest, JobStatus, RetryConfig, ScrapeJob, WorkerConfig};
 pub use job_queue::JobQueue;
+pub use language::{
+    OUTPUT_LANGUAGE_KEY, detect_language, output_language, output_language_instruction,
+};
+pub use logging::{LogConfig, LogFormat, Redactor, init_logging};
 pub use models::{
-    Extraction, ExtractionOutcome, ExtractionSchema, NewExtraction, ScrapeResult, Usage,
-    compute_hash,
+    ChangeEvent, Extraction, ExtractionEvent, ExtractionOutcome, ExtractionSchema, HistoryEntry,
+    NewExtraction, SaveOutcome, ScrapeResult, Usage, compute_hash, compute_json_hash,
+    compute_simhash, format_age, simhash_distance,
 };
 pub use proxy::{ProxyConfig, ProxyEntry, RotationStrategy, TlsBackend};
+pub use retention::{RETENTION_KEY, RetentionPolicy, ScrubObserver, scrub};
 pub use schema::{
-    ResolvedSchema, SchemaEntry, SchemaResolver, derive_schema_name, validate_extracted_output,
-    validate_schema,
+    ResolvedSchema, SchemaEntry, SchemaResolver, derive_schema_name, extracted_output_errors,
+    validate_extracted_output, validate_schema,
 };
-pub use scrape::ScrapeService;
+pub use scrape::{ScrapeService, ScrapeServiceBuilder};
+pub use snapshot::{SnapshotKind, parse_snapshot_kinds};
 pub use stealth::StealthConfig;
-pub use throttle::{ThrottleConfig, ThrottledFetcher};
+pub use throttle::{ThrottleConfig, ThrottledFetcher, domain_key};
 pub use traits::{
-    Cleaner, ExtractionStore, Extractor, ExtractorFactory, Fetcher, LinkDiscoverer,
-    NoRobotsChecker, NullStore, RobotsChecker,
+    ChangeNotifier, Cleaner, ExtractionEventPublisher, ExtractionStore, Extractor,
+    ExtractorFactory, Fetcher, LinkDiscoverer, NoRobotsChecker, NullNotifier, NullPublisher,
+    NullSnapshotStore, NullStore, RobotsChecker, SnapshotStore,
+};
+pub use verify::{GoldenCheck, VerifyReport, verify_goldens};
+pub use worker::{
+    JobOutcome, WorkerEvent, WorkerRunSummary, WorkerService, WorkerStatus, WorkerStatusSnapshot,
 };
-pub use worker::{WorkerEvent, WorkerService};
@@ -0,0 +1,415 @@
+//! Log output shared by the `ares` CLI and the API server.
+//!
+//! `ARES_LOG_FORMAT` selects the format: `full` (the default human format),
+//! `pretty`, `compact`, or `json` (one object per line with timestamp,
+//! level, target, event fields, and the fields of enclosing spans such as
+//! `job_id`, `worker_id`, and `request_id`). `ARES_LOG_LEVEL` sets the level
+//! for Ares' own crates (default `info`); `RUST_LOG` still controls the rest.
+//!
+//! Every formatted line passes through a [`Redactor`] before it is written,
+//! so bearer tokens and the configured API keys never reach the log, even
+//! when they end up inside an error message.
+
+use std::borrow::Cow;
+use std::fmt;
+use std::io;
+use std::str::FromStr;
+use std::sync::{Arc, LazyLock};
+
+use regex::Regex;
+use tracing::Subscriber;
+use tracing_subscriber::filter::LevelFilter;
+use tracing_subscriber::fmt::MakeWriter;
+use tracing_subscriber::layer::SubscriberExt;
+use tracing_subscriber::registry::LookupSpan;
+use tracing_subscriber::util::SubscriberInitExt;
+use tracing_subscriber::{EnvFilter, Layer};
+
+use crate::error::AppError;
+
+/// Environment variables holding secrets, whose values are masked in log
+/// output. Add new secret variables here rather than masking them ad hoc.
+pub const SECRET_VARS: &[&str] = &[
+    "ARES_API_KEY",
+    "ARES_ADMIN_TOKEN",
+    "ARES_REMOTE_TOKEN",
+    "ARES_NOTIFY_WEBHOOK_SECRET",
+    // Slack and generic webhook URLs embed their credentials in the path.
+    "ARES_NOTIFY_SLACK_URL",
+    "ARES_NOTIFY_WEBHOOK_URL",
+];
+
+/// Configured secrets shorter than this are not masked; replacing every
+/// occurrence of a few characters would garble the log without hiding much.
+const MIN_SECRET_LEN: usize = 8;
+
+/// Replaces redacted values in log output.
+pub(crate) const REDACTED: &str = "[REDACTED]";
+
+static BEARER_TOKEN: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"(?i)\b(bearer)\s+[A-Za-z0-9\-._~+/]+=*").expect("valid bearer token pattern")
+});
+
+/// An API key passed as a URL query parameter, e.g. in a request target.
+static API_KEY_PARAM: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"(?i)([?&]api_key=)[^&#\s\x22]+").expect("valid api_key parameter pattern")
+});
+
+/// How log lines are formatted.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum LogFormat {
+    /// Human-readable single-line output.
+    #[default]
+    Full,
+    /// Multi-line human-readable output, for local debugging.
+    Pretty,
+    /// Abbreviated single-line output.
+    Compact,
+    /// Newline-delimited JSON objects, for log pipelines.
+    Json,
+}
+
+impl LogFormat {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            LogFormat::Full => "full",
+            LogFormat::Pretty => "pretty",
+            LogFormat::Compact => "compact",
+            LogFormat::Json => "json",
+        }
+    }
+}
+
+impl fmt::Display for LogFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+impl FromStr for LogFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().as_str() {
+            "full" => Ok(LogFormat::Full),
+            "pretty" => Ok(LogFormat::Pretty),
+            "compact" => Ok(LogFormat::Compact),
+            "json" => Ok(LogFormat::Json),
+            _ => Err(format!(
+                "Unknown log format: {s} (expected full, pretty, compact, or json)"
+            )),
+        }
+    }
+}
+
+/// Logging configuration.
+#[derive(Debug, Clone)]
+pub struct LogConfig {
+    pub format: LogFormat,
+    /// Level for Ares' own crates.
+    pub level: LevelFilter,
+    /// Values masked wherever they appear in log output.
+    pub secrets: Vec<String>,
+}
+
+impl Default for LogConfig {
+    fn default() -> Self {
+        Self {
+            format: LogFormat::default(),
+            level: LevelFilter::INFO,
+            secrets: Vec::new(),
+        }
+    }
+}
+
+impl LogConfig {
+    /// Read configuration from environment variables.
+    ///
+    /// - `ARES_LOG_FORMAT` (optional, `full`, `pretty`, `compact`, or `json`;
+    ///   defaults to `full`)
+    /// - `ARES_LOG_LEVEL` (optional, `trace` through `error`, or `off`;
+    ///   defaults to `info`)
+    /// - the [`SECRET_VARS`] are masked when set
+    pub fn from_env() -> Result<Self, AppError> {
+        Self::from_lookup(|key| std::env::var(key).ok())
+    }
+
+    /// Like [`LogConfig::from_env`], reading variables through `get`.
+    pub fn from_lookup(get: impl Fn(&str) -> Option<String>) -> Result<Self, AppError> {
+        let format = match get("ARES_LOG_FORMAT") {
+            None => LogFormat::default(),
+            Some(raw) => raw
+                .parse()
+                .map_err(|e| AppError::ConfigError(format!("Invalid ARES_LOG_FORMAT: {e}")))?,
+        };
+
+        let level = match get("ARES_LOG_LEVEL") {
+            None => LevelFilter::INFO,
+            Some(raw) => raw.trim().parse().map_err(|_| {
+                AppError::ConfigError(format!(
+                    "Invalid ARES_LOG_LEVEL '{raw}': expected trace, debug, info, warn, error, or off"
+                ))
+            })?,
+        };
+
+        let secrets = SECRET_VARS.iter().filter_map(|var| get(var)).collect();
+
+        Ok(Self {
+            format,
+            level,
+            secrets,
+        })
+    }
+
+    /// `RUST_LOG` directives, with Ares' own crates at [`LogConfig::level`].
+    pub fn filter(&self) -> Result<EnvFilter, AppError> {
+        let directive = format!("ares={}", self.level)
+            .parse()
+            .map_err(|e| AppError::ConfigError(format!("Invalid log level: {e}")))?;
+        Ok(EnvFilter::from_default_env().add_directive(directive))
+    }
+
+    /// A formatting layer writing redacted lines to `writer`.
+    pub fn layer<S, W>(&self, writer: W) -> Box<dyn Layer<S> + Send + Sync>
+    where
+        S: Subscriber + for<'span> LookupSpan<'span>,
+        W: for<'w> MakeWriter<'w> + Send + Sync + 'static,
+    {
+        let writer = RedactingWriter {
+            inner: writer,
+            redactor: Arc::new(Redactor::new(self.secrets.iter().cloned())),
+        };
+        let layer = tracing_subscriber::fmt::layer().with_writer(writer);
+        match self.format {
+            LogFormat::Full => layer.with_target(false).boxed(),
+            LogFormat::Pretty => layer.pretty().with_target(false).boxed(),
+            LogFormat::Compact => layer.compact().with_target(false).boxed(),
+            LogFormat::Json => layer
+                .json()
+                .with_current_span(true)
+                .with_span_list(true)
+                .boxed(),
+        }
+    }
+}
+
+/// Install the global subscriber: [`LogConfig::filter`] plus
+/// [`LogConfig::layer`] writing to `writer`.
+pub fn init_logging<W>(config: &LogConfig, writer: W) -> Result<(), AppError>
+where
+    W: for<'w> MakeWriter<'w> + Send + Sync + 'static,
+{
+    tracing_subscriber::registry()
+        .with(config.filter()?)
+        .with(config.layer(writer))
+        .try_init()
+        .map_err(|e| AppError::ConfigError(format!("Failed to initialize logging: {e}")))
+}
+
+/// Masks secrets in log output: bearer tokens, and any configured value.
+#[derive(Debug, Clone, Default)]
+pub struct Redactor {
+    /// Longest first, so a secret containing another is masked whole.
+    secrets: Vec<String>,
+}
+
+impl Redactor {
+    /// A redactor masking `secrets`. Blank values and values shorter than
+    /// 8 characters are ignored.
+    pub fn new(secrets: impl IntoIterator<Item = impl Into<String>>) -> Self {
+        let mut secrets: Vec<String> = secrets
+            .into_iter()
+            .map(Into::into)
+            .map(|s| s.trim().to_string())
+            .filter(|s| s.len() >= MIN_SECRET_LEN)
+            .collect();
+        secrets.sort_by_key(|s| std::cmp::Reverse(s.len()));
+        secrets.dedup();
+        Self { secrets }
+    }
+
+    /// `text` with every secret replaced by `[REDACTED]`.
+    pub fn redact<'a>(&self, text: &'a str) -> Cow<'a, str> {
+        let mut text = BEARER_TOKEN.replace_all(text, format!("$1 {REDACTED}"));
+        if API_KEY_PARAM.is_match(&text) {
+            text = Cow::Owned(
+                API_KEY_PARAM
+                    .replace_all(&text, format!("${{1}}{REDACTED}"))
+                    .into_owned(),
+            );
+        }
+        for secret in &self.secrets {
+            if text.contains(secret.as_str()) {
+                text = Cow::Owned(text.replace(secret.as_str(), REDACTED));
+            }
+        }
+        text
+    }
+}
+
+/// [`MakeWriter`] passing each formatted event through a [`Redactor`].
+struct RedactingWriter<W> {
+    inner: W,
+    redactor: Arc<Redactor>,
+}
+
+impl<'a, W: MakeWriter<'a>> MakeWriter<'a> for RedactingWriter<W> {
+    type Writer = RedactedWrite<'a, W::Writer>;
+
+    fn make_writer(&'a self) -> Self::Writer {
+        RedactedWrite {
+            inner: self.inner.make_writer(),
+            redactor: &self.redactor,
+        }
+    }
+}
+
+/// The formatting layer writes each event with a single `write_all`, so
+/// every call sees a whole line and a secret is never split across calls.
+struct RedactedWrite<'a, W> {
+    inner: W,
+    redactor: &'a Redactor,
+}
+
+impl<W: io::Write> io::Write for RedactedWrite<'_, W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        match std::str::from_utf8(buf) {
+            Ok(text) => self
+                .inner
+                .write_all(self.redactor.redact(text).as_bytes())?,
+            Err(_) => self.inner.write_all(buf)?,
+        }
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.inner.flush()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use super::*;
+    use crate::testutil::CapturedLogs;
+
+    fn capture(config: &LogConfig, log: impl FnOnce()) -> Vec<String> {
+        let captured = CapturedLogs::default();
+        let subscriber = tracing_subscriber::registry().with(config.layer(captured.clone()));
+        tracing::subscriber::with_default(subscriber, log);
+        captured.lines()
+    }
+
+    fn lookup(vars: &[(&str, &str)]) -> Result<LogConfig, AppError> {
+        let vars: HashMap<String, String> = vars
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect();
+        LogConfig::from_lookup(|key| vars.get(key).cloned())
+    }
+
+    #[test]
+    fn json_lines_carry_level_target_and_span_fields() {
+        let config = LogConfig {
+            format: LogFormat::Json,
+            ..LogConfig::default()
+        };
+        let lines = capture(&config, || {
+            let span = tracing::info_span!("job", job_id = "job-1", worker_id = "worker-1");
+            let _guard = span.enter();
+            tracing::warn!(attempt = 2, "Fetch failed");
+        });
+
+        assert_eq!(lines.len(), 1);
+        let line: serde_json::Value = serde_json::from_str(&lines[0]).unwrap();
+        assert!(line["timestamp"].is_string());
+        assert_eq!(line["level"], "WARN");
+        assert_eq!(line["target"], "ares_core::logging::tests");
+        assert_eq!(line["fields"]["message"], "Fetch failed");
+        assert_eq!(line["fields"]["attempt"], 2);
+        assert_eq!(line["span"]["job_id"], "job-1");
+        assert_eq!(line["spans"][0]["worker_id"], "worker-1");
+    }
+
+    #[test]
+    fn configured_keys_and_bearer_tokens_are_masked() {
+        let config = lookup(&[
+            ("ARES_LOG_FORMAT", "json"),
+            ("ARES_API_KEY", "sk-test-0123456789abcdef"),
+        ])
+        .unwrap();
+        let lines = capture(&config, || {
+            let err = AppError::LlmError {
+                message: "invalid key sk-test-0123456789abcdef".into(),
+                status_code: 401,
+                retryable: false,
+            };
+            tracing::error!(error = %err, "Extraction failed");
+            tracing::info!(header = "Bearer abc.def-ghi", "Calling upstream");
+        });
+
+        let output = lines.join("\n");
+        assert!(!output.contains("sk-test-0123456789abcdef"), "{output}");
+        assert!(!output.contains("abc.def-ghi"), "{output}");
+        for line in &lines {
+            serde_json::from_str::<serde_json::Value>(line).unwrap();
+        }
+        assert!(lines[0].contains("invalid key [REDACTED]"));
+        assert!(lines[1].contains("Bearer [REDACTED]"));
+    }
+
+    #[test]
+    fn every_secret_var_is_masked() {
+        for var in SECRET_VARS {
+            let config = lookup(&[(*var, "s3cret-0123456789abcdef")]).unwrap();
+            let lines = capture(&config, || {
+                tracing::warn!("Request failed with s3cret-0123456789abcdef");
+            });
+            assert!(
+                lines[0].contains("failed with [REDACTED]"),
+                "{var}: {lines:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn redactor_ignores_short_secrets() {
+        let redactor = Redactor::new(["tok", "", "supersecret-token"]);
+        assert_eq!(
+            redactor.redact("token tok supersecret-token"),
+            "token tok [REDACTED]"
+        );
+        assert_eq!(
+            redactor.redact("authorization: bearer x"),
+            "authorization: bearer [REDACTED]"
+        );
+        assert_eq!(
+            redactor.redact("GET /v1/jobs?limit=5&api_key=ares_abc123&tag=x"),
+            "GET /v1/jobs?limit=5&api_key=[REDACTED]&tag=x"
+        );
+        assert!(matches!(redactor.redact("nothing here"), Cow::Borrowed(_)));
+    }
+
+    #[test]
+    fn config_parses_format_and_level() {
+        let config = lookup(&[]).unwrap();
+        assert_eq!(config.format, LogFormat::Full);
+        assert_eq!(config.level, LevelFilter::INFO);
+        assert!(config.secrets.is_empty());
+
+        let config =
+            lookup(&[("ARES_LOG_FORMAT", "Compact"), ("ARES_LOG_LEVEL", "debug")]).unwrap();
+        assert_eq!(config.format, LogFormat::Compact);
+        assert_eq!(config.level, LevelFilter::DEBUG);
+
+        assert!(matches!(
+            lookup(&[("ARES_LOG_FORMAT", "xml")]),
+            Err(AppError::ConfigError(_))
+        ));
+        assert!(matches!(
+            lookup(&[("ARES_LOG_LEVEL", "loud")]),
+            Err(AppError::ConfigError(_))
+        ));
+    }
+}
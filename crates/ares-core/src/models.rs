@@ -1,9 +1,14 @@
 use std::sync::Arc;
+use std::time::Duration;
 
 use chrono::{DateTime, Utc};
 use sha2::{Digest, Sha256};
 use uuid::Uuid;
 
+use crate::diff::JsonChange;
+use crate::retention::RetentionPolicy;
+use crate::snapshot::SnapshotKind;
+
 /// User-defined extraction schema (JSON Schema subset).
 ///
 /// The `schema` field is the raw JSON Schema passed to the LLM
@@ -50,21 +55,34 @@ impl Usage {
 pub struct ExtractionOutcome {
     pub value: serde_json::Value,
     pub usage: Option<Usage>,
+    /// The model the provider reports having answered with (e.g. a dated
+    /// snapshot of an alias, or a fallback). `None` means the requested one.
+    pub model: Option<String>,
 }
 
 impl ExtractionOutcome {
     /// An outcome with no usage information (local backends, mocks).
     pub fn new(value: serde_json::Value) -> Self {
-        Self { value, usage: None }
+        Self {
+            value,
+            usage: None,
+            model: None,
+        }
     }
 
     /// An outcome carrying reported token usage.
     pub fn with_usage(value: serde_json::Value, usage: Usage) -> Self {
         Self {
-            value,
             usage: Some(usage),
+            ..Self::new(value)
         }
     }
+
+    /// Record the model that actually answered.
+    pub fn with_model(mut self, model: impl Into<String>) -> Self {
+        self.model = Some(model.into());
+        self
+    }
 }
 
 impl From<serde_json::Value> for ExtractionOutcome {
@@ -74,7 +92,7 @@ impl From<serde_json::Value> for ExtractionOutcome {
 }
 
 /// A completed extraction result.
-#[derive(Debug, Clone, serde::Serialize)]
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
 pub struct Extraction {
     pub id: Uuid,
     pub url: String,
@@ -82,12 +100,26 @@ pub struct Extraction {
     pub extracted_data: serde_json::Value,
     /// SHA-256 of the cleaned markdown content
     pub content_hash: String,
+    /// Simhash of the cleaned markdown content, for telling near-identical
+    /// content apart from changed content (see [`compute_simhash`]). `None`
+    /// for extractions saved before it was recorded.
+    #[serde(default)]
+    pub content_simhash: Option<String>,
     /// SHA-256 of the extracted JSON data (for change detection)
     pub data_hash: String,
+    /// The model that actually answered, which may differ from the one
+    /// requested.
     pub model: String,
     // -- Run metadata --
     /// LLM provider used (e.g. `openai`, `anthropic`, `local`).
     pub provider: String,
+    /// SHA-256 of the extractor's effective prompt, so runs under different
+    /// prompts can be told apart. `None` when the extractor doesn't report one.
+    pub prompt_hash: Option<String>,
+    /// SHA-256 of the JSON schema the data was extracted with. `None` for
+    /// extractions saved before it was recorded.
+    #[serde(default)]
+    pub schema_hash: Option<String>,
     /// Schema version, when known (parsed from a `name@version` reference).
     pub schema_version: Option<String>,
     /// Extractor-call latency in ms. `None` for cache-served results.
@@ -96,9 +128,56 @@ pub struct Extraction {
     /// backends or cache hits.
     pub prompt_tokens: Option<i32>,
     pub completion_tokens: Option<i32>,
+    /// Key of the archived source HTML in the snapshot store, if archived.
+    pub html_snapshot_key: Option<String>,
+    /// Key of the archived cleaned Markdown, if archived.
+    pub markdown_snapshot_key: Option<String>,
+    /// The extraction of another URL this page's content duplicates (same
+    /// schema and content hash), when cross-URL dedup is enabled.
+    pub duplicate_of: Option<Uuid>,
+    /// ISO 639-3 code of the cleaned content's language, when it could be
+    /// detected reliably (see [`crate::language`]).
+    #[serde(default)]
+    pub detected_language: Option<String>,
+    /// Marked as a known-good result that re-extractions of the page are
+    /// verified against (see [`crate::verify`]).
+    #[serde(default)]
+    pub golden: bool,
+    /// When a retention policy scrubbed fields out of the data (see
+    /// [`crate::retention`]). `data_hash` still describes the original data.
+    #[serde(default)]
+    pub scrubbed_at: Option<DateTime<Utc>>,
     pub created_at: DateTime<Utc>,
 }
 
+impl Extraction {
+    /// The snapshot key of `kind`, if that content was archived.
+    pub fn snapshot_key(&self, kind: SnapshotKind) -> Option<&str> {
+        match kind {
+            SnapshotKind::Html => self.html_snapshot_key.as_deref(),
+            SnapshotKind::Markdown => self.markdown_snapshot_key.as_deref(),
+        }
+    }
+
+    /// Time since the extraction was saved; zero if `created_at` is in the
+    /// future (clock skew).
+    pub fn age(&self) -> Duration {
+        (Utc::now() - self.created_at).to_std().unwrap_or_default()
+    }
+}
+
+/// Extractions are equal when they hold the same data for the same URL and
+/// schema, whatever their IDs, timestamps, or run metadata.
+impl PartialEq for Extraction {
+    fn eq(&self, other: &Self) -> bool {
+        self.url == other.url
+            && self.schema_name == other.schema_name
+            && self.data_hash == other.data_hash
+    }
+}
+
+impl Eq for Extraction {}
+
 /// DTO for inserting a new extraction into the database.
 ///
 /// `Default` is provided so tests can set only the fields they care about
@@ -109,14 +188,28 @@ pub struct NewExtraction {
     pub schema_name: String,
     pub extracted_data: serde_json::Value,
     pub raw_content_hash: String,
+    /// See `Extraction::content_simhash`.
+    pub content_simhash: Option<String>,
     pub data_hash: String,
     pub model: String,
     // -- Run metadata (see `Extraction`) --
     pub provider: String,
+    pub prompt_hash: Option<String>,
+    pub schema_hash: Option<String>,
     pub schema_version: Option<String>,
     pub latency_ms: Option<i64>,
     pub prompt_tokens: Option<i32>,
     pub completion_tokens: Option<i32>,
+    // -- Snapshot keys (see `Extraction`) --
+    pub html_snapshot_key: Option<String>,
+    pub markdown_snapshot_key: Option<String>,
+    /// See `Extraction::duplicate_of`.
+    pub duplicate_of: Option<Uuid>,
+    /// See `Extraction::detected_language`.
+    pub detected_language: Option<String>,
+    /// The schema's retention policy, stored with the row so pruning can
+    /// expire or scrub it.
+    pub retention: Option<RetentionPolicy>,
 }
 
 impl Default for NewExtraction {
@@ -126,15 +219,95 @@ impl Default for NewExtraction {
             schema_name: String::new(),
             extracted_data: serde_json::Value::Null,
             raw_content_hash: String::new(),
+            content_simhash: None,
             data_hash: String::new(),
             model: String::new(),
             // Canonical default provider (matches the DB column default and
             // `ScrapeService`), so `..Default::default()` never persists "".
             provider: "openai".to_string(),
+            prompt_hash: None,
+            schema_hash: None,
             schema_version: None,
             latency_ms: None,
             prompt_tokens: None,
             completion_tokens: None,
+            html_snapshot_key: None,
+            markdown_snapshot_key: None,
+            duplicate_of: None,
+            detected_language: None,
+            retention: None,
+        }
+    }
+}
+
+/// An extraction in the history of a URL and schema, with how it relates
+/// to the extraction before it.
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct HistoryEntry {
+    #[serde(flatten)]
+    pub extraction: Extraction,
+    /// The extraction of the same URL and schema just before this one, if
+    /// any.
+    pub previous_extraction_id: Option<Uuid>,
+    /// Whether the data differs from the previous extraction (always true
+    /// for the first one).
+    pub changed: bool,
+}
+
+/// A page whose extracted data changed since its previous extraction, as
+/// sent to a [`ChangeNotifier`](crate::traits::ChangeNotifier).
+#[derive(Debug, Clone, PartialEq, serde::Serialize)]
+pub struct ChangeEvent {
+    pub url: String,
+    pub schema_name: String,
+    /// The extraction the data changed from.
+    pub previous_extraction_id: Uuid,
+    /// The extraction holding the new data.
+    pub extraction_id: Uuid,
+    /// Field-level differences from the previous data to the new.
+    pub changes: Vec<JsonChange>,
+    pub detected_at: DateTime<Utc>,
+}
+
+/// A saved extraction, as sent to an
+/// [`ExtractionEventPublisher`](crate::traits::ExtractionEventPublisher).
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct ExtractionEvent {
+    pub extraction_id: Uuid,
+    /// The latest extraction of the same page before this one, if any.
+    pub previous_extraction_id: Option<Uuid>,
+    pub extraction: NewExtraction,
+    /// Whether the data differs from the previous extraction (always true
+    /// for the first one).
+    pub changed: bool,
+    /// Field-level differences from the previous data; empty when unchanged
+    /// or for the first extraction.
+    pub changes: Vec<JsonChange>,
+    pub saved_at: DateTime<Utc>,
+}
+
+/// How a scrape persisted its extraction: saved as a new row, or kept the
+/// latest one via
+/// [`ExtractionStore::keep_if_unchanged`](crate::traits::ExtractionStore::keep_if_unchanged).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SaveOutcome {
+    /// The data differed from the latest extraction, so a new row was saved.
+    Inserted(Uuid),
+    /// The data matched the latest extraction, which was kept instead.
+    /// `last_seen_bumped` is whether its last-seen time was updated.
+    Unchanged {
+        existing_id: Uuid,
+        last_seen_bumped: bool,
+    },
+}
+
+impl SaveOutcome {
+    /// The ID of the inserted or kept extraction.
+    pub fn extraction_id(&self) -> Uuid {
+        match *self {
+            SaveOutcome::Inserted(id) => id,
+            SaveOutcome::Unchanged { existing_id, .. } => existing_id,
         }
     }
 }
@@ -146,30 +319,191 @@ pub struct ScrapeResult {
     pub extracted_data: serde_json::Value,
     /// SHA-256 of the cleaned markdown content.
     pub content_hash: String,
+    /// Simhash of the cleaned markdown content (see [`compute_simhash`]).
+    pub content_simhash: String,
     /// SHA-256 of the extracted JSON data.
     pub data_hash: String,
     /// Whether data changed compared to the previous extraction.
     pub changed: bool,
     /// The persisted extraction ID (if saved to DB).
     pub extraction_id: Option<Uuid>,
+    /// Whether the extraction was saved or an unchanged one kept; `None`
+    /// without a store or when the extraction was upserted.
+    pub saved: Option<SaveOutcome>,
     /// Wall-clock time spent in the extractor call (LLM round-trip), in ms.
     /// `None` when the result was served from the extraction cache.
     pub latency_ms: Option<u128>,
     /// Token usage reported by the extractor. `None` for local backends or
     /// cache hits.
     pub usage: Option<Usage>,
+    /// The extraction of another URL with the same content, when cross-URL
+    /// dedup found one (see [`ScrapeService::with_dedup_window`]).
+    ///
+    /// [`ScrapeService::with_dedup_window`]: crate::scrape::ScrapeService::with_dedup_window
+    pub duplicate_of: Option<Uuid>,
+    /// ISO 639-3 code of the cleaned content's language, when detected
+    /// reliably.
+    pub detected_language: Option<String>,
     /// The raw HTML content (used for link discovery in crawling).
     #[serde(skip)]
     pub raw_html: Option<Arc<str>>,
 }
 
-/// Compute a SHA-256 hash of a string, returned as 64-char hex.
-pub fn compute_hash(content: &str) -> String {
+/// Scrape results are equal when they extracted the same data.
+impl PartialEq for ScrapeResult {
+    fn eq(&self, other: &Self) -> bool {
+        self.data_hash == other.data_hash
+    }
+}
+
+impl ScrapeResult {
+    /// Build the [`NewExtraction`] that persists this result for `url` under
+    /// `schema_name`, extracted by `model`.
+    ///
+    /// The schema version is the part after `@` in a `name@version` reference
+    /// (`None` for bare names or a trailing `@`). Latency and token counts that
+    /// overflow the column types are dropped rather than wrapped. `provider`
+    /// keeps the [`NewExtraction`] default; callers set it when known.
+    pub fn to_extraction(&self, url: &str, schema_name: &str, model: &str) -> NewExtraction {
+        let schema_version = schema_name
+            .rsplit_once('@')
+            .map(|(_, v)| v.to_string())
+            .filter(|v| !v.is_empty());
+
+        NewExtraction {
+            url: url.to_string(),
+            schema_name: schema_name.to_string(),
+            extracted_data: self.extracted_data.clone(),
+            raw_content_hash: self.content_hash.clone(),
+            content_simhash: Some(self.content_simhash.clone()),
+            data_hash: self.data_hash.clone(),
+            model: model.to_string(),
+            schema_version,
+            latency_ms: self.latency_ms.and_then(|l| i64::try_from(l).ok()),
+            prompt_tokens: self.usage.and_then(|u| i32::try_from(u.prompt_tokens).ok()),
+            completion_tokens: self
+                .usage
+                .and_then(|u| i32::try_from(u.completion_tokens).ok()),
+            duplicate_of: self.duplicate_of,
+            detected_language: self.detected_language.clone(),
+            ..NewExtraction::default()
+        }
+    }
+}
+
+/// Compute a SHA-256 hash of some bytes (or a string), returned as 64-char hex.
+pub fn compute_hash(content: impl AsRef<[u8]>) -> String {
     let mut hasher = Sha256::new();
-    hasher.update(content.as_bytes());
+    hasher.update(content.as_ref());
     format!("{:x}", hasher.finalize())
 }
 
+/// Compute a SHA-256 hash of a JSON value in canonical form: compact, with
+/// object keys sorted. Equal data hashes the same whatever its key order.
+pub fn compute_json_hash(value: &serde_json::Value) -> String {
+    let mut canonical = String::new();
+    write_canonical_json(value, &mut canonical);
+    compute_hash(canonical)
+}
+
+fn write_canonical_json(value: &serde_json::Value, out: &mut String) {
+    match value {
+        serde_json::Value::Object(map) => {
+            let mut entries: Vec<_> = map.iter().collect();
+            entries.sort_unstable_by(|a, b| a.0.cmp(b.0));
+            out.push('{');
+            for (i, (key, value)) in entries.into_iter().enumerate() {
+                if i > 0 {
+                    out.push(',');
+                }
+                out.push_str(&serde_json::Value::String(key.clone()).to_string());
+                out.push(':');
+                write_canonical_json(value, out);
+            }
+            out.push('}');
+        }
+        serde_json::Value::Array(items) => {
+            out.push('[');
+            for (i, item) in items.iter().enumerate() {
+                if i > 0 {
+                    out.push(',');
+                }
+                write_canonical_json(item, out);
+            }
+            out.push(']');
+        }
+        scalar => out.push_str(&scalar.to_string()),
+    }
+}
+
+/// Words per shingle (overlapping word sequence) hashed into a simhash.
+const SIMHASH_SHINGLE_WORDS: usize = 3;
+
+/// Compute a 64-bit simhash of text, returned as 16-char hex.
+///
+/// Unlike [`compute_hash`], near-identical texts get near-identical
+/// fingerprints: a changed timestamp or a rotated "related articles" block
+/// flips a few of the 64 bits, so [`simhash_distance`] can tell a page that
+/// barely changed from a rewritten one. Words are lowercased and split on
+/// anything that isn't alphanumeric, so whitespace and punctuation don't
+/// count; each overlapping three-word shingle votes on every bit.
+pub fn compute_simhash(content: &str) -> String {
+    let words: Vec<String> = content
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .map(str::to_lowercase)
+        .collect();
+
+    let mut votes = [0i64; 64];
+    let mut vote = |feature: &str| {
+        let digest = Sha256::digest(feature.as_bytes());
+        let hash = u64::from_be_bytes(<[u8; 8]>::try_from(&digest[..8]).expect("8 bytes"));
+        for (bit, count) in votes.iter_mut().enumerate() {
+            if (hash >> bit) & 1 == 1 {
+                *count += 1;
+            } else {
+                *count -= 1;
+            }
+        }
+    };
+    if words.len() < SIMHASH_SHINGLE_WORDS {
+        for word in &words {
+            vote(word);
+        }
+    } else {
+        for shingle in words.windows(SIMHASH_SHINGLE_WORDS) {
+            vote(&shingle.join(" "));
+        }
+    }
+
+    let fingerprint = votes
+        .iter()
+        .enumerate()
+        .filter(|(_, count)| **count > 0)
+        .fold(0u64, |fingerprint, (bit, _)| fingerprint | (1 << bit));
+    format!("{fingerprint:016x}")
+}
+
+/// The number of bits (0–64) that differ between two [`compute_simhash`]
+/// fingerprints, or `None` if either isn't one.
+pub fn simhash_distance(a: &str, b: &str) -> Option<u32> {
+    let a = u64::from_str_radix(a, 16).ok()?;
+    let b = u64::from_str_radix(b, 16).ok()?;
+    Some((a ^ b).count_ones())
+}
+
+/// A short human-readable age in the largest whole unit: "45s ago",
+/// "5m ago", "3h ago", "2d ago".
+pub fn format_age(age: Duration) -> String {
+    let secs = age.as_secs();
+    match secs {
+        0..60 => format!("{secs}s ago"),
+        60..3_600 => format!("{}m ago", secs / 60),
+        3_600..86_400 => format!("{}h ago", secs / 3_600),
+        _ => format!("{}d ago", secs / 86_400),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -182,10 +516,204 @@ mod tests {
         assert_eq!(h1.len(), 64);
     }
 
+    /// `words` distinct words, `{prefix}0 {prefix}1 ...`.
+    fn words(prefix: &str, words: usize) -> String {
+        (0..words)
+            .map(|i| format!("{prefix}{i}"))
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+
+    #[test]
+    fn test_simhash_is_stable_hex() {
+        let text = words("w", 200);
+        let simhash = compute_simhash(&text);
+        assert_eq!(simhash, compute_simhash(&text));
+        assert_eq!(simhash.len(), 16);
+        assert!(simhash.chars().all(|c| c.is_ascii_hexdigit()));
+        assert_eq!(simhash_distance(&simhash, &simhash), Some(0));
+    }
+
+    #[test]
+    fn test_simhash_ignores_case_whitespace_and_punctuation() {
+        assert_eq!(
+            compute_simhash("Hello, World!  The *quick* brown fox."),
+            compute_simhash("hello world\nthe quick BROWN fox")
+        );
+        // Texts shorter than a shingle still fingerprint their words.
+        assert_ne!(compute_simhash("hello"), compute_simhash("goodbye"));
+        assert_eq!(compute_simhash(""), "0000000000000000");
+        assert_eq!(compute_simhash(" -- "), "0000000000000000");
+    }
+
+    #[test]
+    fn test_simhash_near_duplicates_are_close_and_rewrites_far() {
+        let body = words("w", 3_000);
+        let before = compute_simhash(&format!("{body} Updated 2026-10-16 12:00"));
+        let after = compute_simhash(&format!("{body} Updated 2026-10-17 09:30"));
+        let near = simhash_distance(&before, &after).unwrap();
+        assert!(near <= 6, "near-duplicate distance {near}");
+
+        let rewritten = compute_simhash(&words("x", 3_000));
+        let far = simhash_distance(&before, &rewritten).unwrap();
+        assert!(far >= 16, "unrelated distance {far}");
+        assert!(near < far);
+    }
+
+    #[test]
+    fn test_simhash_distance_counts_differing_bits() {
+        assert_eq!(
+            simhash_distance("ffffffffffffffff", "0000000000000000"),
+            Some(64)
+        );
+        assert_eq!(
+            simhash_distance("0000000000000003", "0000000000000001"),
+            Some(1)
+        );
+        assert_eq!(simhash_distance("not hex", "0000000000000000"), None);
+        assert_eq!(simhash_distance("0000000000000000", ""), None);
+    }
+
+    #[test]
+    fn test_format_age_uses_the_largest_whole_unit() {
+        let age = |secs| format_age(Duration::from_secs(secs));
+        assert_eq!(age(0), "0s ago");
+        assert_eq!(age(59), "59s ago");
+        assert_eq!(age(5 * 60 + 30), "5m ago");
+        assert_eq!(age(3 * 3_600 + 59 * 60), "3h ago");
+        assert_eq!(age(2 * 86_400 + 3_600), "2d ago");
+    }
+
+    #[test]
+    fn test_compute_hash_accepts_bytes() {
+        assert_eq!(compute_hash(b"hello"), compute_hash("hello"));
+        assert_eq!(compute_hash(String::from("hello")), compute_hash("hello"));
+    }
+
+    #[test]
+    fn test_compute_json_hash_sorts_keys() {
+        let mut reversed = serde_json::Map::new();
+        reversed.insert("title".into(), serde_json::json!("Caf\u{e9} \"menu\""));
+        reversed.insert(
+            "items".into(),
+            serde_json::json!([{ "price": 4.5, "name": "tea" }, null]),
+        );
+        let value = serde_json::Value::Object(reversed);
+
+        assert_eq!(
+            compute_json_hash(&value),
+            compute_hash(r#"{"items":[{"name":"tea","price":4.5},null],"title":"Café \"menu\""}"#)
+        );
+    }
+
     #[test]
     fn test_compute_hash_different_inputs() {
         let h1 = compute_hash("hello");
         let h2 = compute_hash("world");
         assert_ne!(h1, h2);
     }
+
+    #[test]
+    fn test_to_extraction_copies_result_and_context() {
+        let result = ScrapeResult {
+            extracted_data: serde_json::json!({"title": "Hi"}),
+            content_hash: "c".repeat(64),
+            content_simhash: "00ff00ff00ff00ff".into(),
+            data_hash: "d".repeat(64),
+            changed: true,
+            extraction_id: None,
+            saved: None,
+            latency_ms: Some(1200),
+            usage: Some(Usage {
+                prompt_tokens: 100,
+                completion_tokens: 20,
+            }),
+            duplicate_of: None,
+            detected_language: Some("deu".into()),
+            raw_html: None,
+        };
+
+        let new = result.to_extraction("https://example.com", "blog@1.2.0", "gpt-4o-mini");
+        assert_eq!(new.url, "https://example.com");
+        assert_eq!(new.schema_name, "blog@1.2.0");
+        assert_eq!(new.schema_version.as_deref(), Some("1.2.0"));
+        assert_eq!(new.model, "gpt-4o-mini");
+        assert_eq!(new.extracted_data, result.extracted_data);
+        assert_eq!(new.raw_content_hash, result.content_hash);
+        assert_eq!(new.content_simhash.as_deref(), Some("00ff00ff00ff00ff"));
+        assert_eq!(new.data_hash, result.data_hash);
+        assert_eq!(new.latency_ms, Some(1200));
+        assert_eq!(new.prompt_tokens, Some(100));
+        assert_eq!(new.completion_tokens, Some(20));
+        assert_eq!(new.provider, "openai");
+        assert_eq!(new.detected_language.as_deref(), Some("deu"));
+    }
+
+    #[test]
+    fn test_to_extraction_without_version_or_metadata() {
+        let result = ScrapeResult {
+            extracted_data: serde_json::Value::Null,
+            content_hash: String::new(),
+            content_simhash: String::new(),
+            data_hash: String::new(),
+            changed: false,
+            extraction_id: None,
+            saved: None,
+            latency_ms: Some(u128::MAX),
+            usage: None,
+            duplicate_of: None,
+            detected_language: None,
+            raw_html: None,
+        };
+
+        for schema_name in ["blog", "blog@"] {
+            let new = result.to_extraction("https://example.com", schema_name, "m");
+            assert_eq!(new.schema_version, None);
+            assert_eq!(new.latency_ms, None);
+            assert_eq!(new.prompt_tokens, None);
+        }
+
+        // Results compare by extracted data only.
+        let cached = ScrapeResult {
+            latency_ms: None,
+            changed: true,
+            ..result.clone()
+        };
+        assert_eq!(result, cached);
+        assert_ne!(
+            result,
+            ScrapeResult {
+                data_hash: "other".to_string(),
+                ..cached
+            }
+        );
+    }
+
+    #[test]
+    fn test_extraction_eq_ignores_ids_and_run_metadata() {
+        let a = crate::testutil::make_test_extraction("d1");
+        let rerun = Extraction {
+            id: Uuid::new_v4(),
+            model: "other-model".to_string(),
+            created_at: a.created_at + chrono::Duration::hours(1),
+            ..a.clone()
+        };
+        assert_eq!(a, rerun);
+
+        assert_ne!(a, crate::testutil::make_test_extraction("d2"));
+        assert_ne!(
+            a,
+            Extraction {
+                url: "https://other.example".to_string(),
+                ..a.clone()
+            }
+        );
+        assert_ne!(
+            a,
+            Extraction {
+                schema_name: "other".to_string(),
+                ..a.clone()
+            }
+        );
+    }
 }
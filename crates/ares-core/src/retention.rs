@@ -0,0 +1,198 @@
+//! Per-schema data retention and scrubbing of personal data.
+//!
+//! Extractions are history and are normally kept forever. A schema that
+//! extracts personal data can declare how long that data may be kept:
+//!
+//! ```json
+//! "x-ares-retention": {"days": 30, "scrub_fields": ["/author/email"]}
+//! ```
+//!
+//! The policy is stored with every extraction made with the schema. Once a
+//! row is older than `days`, pruning nulls out the `scrub_fields` (JSON
+//! Pointers into the extracted data) and keeps the rest, or deletes the row
+//! when no fields are listed. With `"scrub_on_save": true` the fields are
+//! also scrubbed before the extraction is saved, by [`ScrubObserver`].
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::error::AppError;
+
+/// Schema keyword declaring a [`RetentionPolicy`].
+pub const RETENTION_KEY: &str = "x-ares-retention";
+
+/// How long an extraction's data may be kept, and which of it.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct RetentionPolicy {
+    /// Days after which the extraction expires.
+    pub days: u32,
+    /// JSON Pointers (RFC 6901) to the values nulled out on expiry. When
+    /// empty, the whole extraction is deleted instead.
+    #[serde(default)]
+    pub scrub_fields: Vec<String>,
+    /// Also null out `scrub_fields` before the extraction is saved, so the
+    /// values are never stored.
+    #[serde(default)]
+    pub scrub_on_save: bool,
+}
+
+impl RetentionPolicy {
+    /// The policy `schema` declares under [`RETENTION_KEY`], if any.
+    ///
+    /// Fails with a [`AppError::SchemaError`] when the declaration is
+    /// malformed, so a schema can't silently keep data it meant to expire.
+    pub fn from_schema(schema: &Value) -> Result<Option<Self>, AppError> {
+        let Some(declared) = schema.get(RETENTION_KEY) else {
+            return Ok(None);
+        };
+        let policy: Self = serde_json::from_value(declared.clone())
+            .map_err(|e| AppError::SchemaError(format!("Invalid {RETENTION_KEY}: {e}")))?;
+        if policy.days == 0 {
+            return Err(AppError::SchemaError(format!(
+                "{RETENTION_KEY}: days must be at least 1"
+            )));
+        }
+        if let Some(pointer) = policy.scrub_fields.iter().find(|p| !p.starts_with('/')) {
+            return Err(AppError::SchemaError(format!(
+                "{RETENTION_KEY}: scrub_fields entry '{pointer}' is not a JSON Pointer (e.g. /author/email)"
+            )));
+        }
+        if policy.scrub_on_save && policy.scrub_fields.is_empty() {
+            return Err(AppError::SchemaError(format!(
+                "{RETENTION_KEY}: scrub_on_save needs scrub_fields"
+            )));
+        }
+        Ok(Some(policy))
+    }
+}
+
+/// Null out the values `pointers` address in `data`. Pointers to values that
+/// don't exist are skipped. Returns how many values were nulled.
+pub fn scrub(data: &mut Value, pointers: &[String]) -> usize {
+    let mut scrubbed = 0;
+    for pointer in pointers {
+        if let Some(value) = data.pointer_mut(pointer).filter(|value| !value.is_null()) {
+            *value = Value::Null;
+            scrubbed += 1;
+        }
+    }
+    scrubbed
+}
+
+/// Scrubs extracted data before it is hashed and saved, for schemas whose
+/// retention policy sets `scrub_on_save`. The scrubbed values then never
+/// reach the store, change notifications, or published events.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ScrubObserver;
+
+impl ScrubObserver {
+    /// Null out the scrub fields of `retention` in `data` if the policy asks
+    /// for it. Returns how many values were nulled.
+    pub fn before_save(&self, retention: Option<&RetentionPolicy>, data: &mut Value) -> usize {
+        match retention {
+            Some(policy) if policy.scrub_on_save => scrub(data, &policy.scrub_fields),
+            _ => 0,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn policy(schema: Value) -> Result<Option<RetentionPolicy>, AppError> {
+        RetentionPolicy::from_schema(&schema)
+    }
+
+    #[test]
+    fn reads_the_declared_policy() {
+        let parsed = policy(json!({
+            "type": "object",
+            "x-ares-retention": {"days": 30, "scrub_fields": ["/author/email"]}
+        }))
+        .unwrap()
+        .unwrap();
+        assert_eq!(parsed.days, 30);
+        assert_eq!(parsed.scrub_fields, vec!["/author/email"]);
+        assert!(!parsed.scrub_on_save);
+
+        assert_eq!(policy(json!({"type": "object"})).unwrap(), None);
+    }
+
+    #[test]
+    fn rejects_malformed_policies() {
+        for (declared, needle) in [
+            (json!({"days": 0}), "at least 1"),
+            (
+                json!({"days": 30, "scrub_fields": ["author.email"]}),
+                "JSON Pointer",
+            ),
+            (
+                json!({"days": 30, "scrub_on_save": true}),
+                "needs scrub_fields",
+            ),
+            (json!({"days": 30, "keep": true}), "unknown field"),
+            (json!({"scrub_fields": ["/a"]}), "missing field"),
+        ] {
+            let err = policy(json!({"x-ares-retention": declared})).unwrap_err();
+            assert!(
+                matches!(&err, AppError::SchemaError(msg) if msg.contains(needle)),
+                "{err}"
+            );
+        }
+    }
+
+    #[test]
+    fn scrubs_pointer_targets_inside_nested_structures() {
+        let mut data = json!({
+            "title": "Launch notes",
+            "author": {"name": "Ada", "contact": {"email": "ada@example.com", "phone": "555"}},
+            "comments": [
+                {"text": "Nice", "by": {"email": "bob@example.com"}},
+                {"text": "Agreed", "by": {"email": "eve@example.com"}}
+            ]
+        });
+        let pointers = [
+            "/author/contact/email".to_string(),
+            "/comments/1/by/email".to_string(),
+            "/author/missing".to_string(),
+            "/comments/7/by/email".to_string(),
+        ];
+
+        assert_eq!(scrub(&mut data, &pointers), 2);
+        assert_eq!(
+            data,
+            json!({
+                "title": "Launch notes",
+                "author": {"name": "Ada", "contact": {"email": null, "phone": "555"}},
+                "comments": [
+                    {"text": "Nice", "by": {"email": "bob@example.com"}},
+                    {"text": "Agreed", "by": {"email": null}}
+                ]
+            })
+        );
+        // Scrubbing again finds nothing left to null.
+        assert_eq!(scrub(&mut data, &pointers), 0);
+    }
+
+    #[test]
+    fn observer_only_scrubs_schemas_marked_for_it() {
+        let original = json!({"author": {"email": "ada@example.com"}});
+        let mut retention = RetentionPolicy {
+            days: 30,
+            scrub_fields: vec!["/author/email".to_string()],
+            scrub_on_save: false,
+        };
+
+        let mut data = original.clone();
+        assert_eq!(ScrubObserver.before_save(None, &mut data), 0);
+        assert_eq!(ScrubObserver.before_save(Some(&retention), &mut data), 0);
+        assert_eq!(data, original);
+
+        retention.scrub_on_save = true;
+        assert_eq!(ScrubObserver.before_save(Some(&retention), &mut data), 1);
+        assert_eq!(data, json!({"author": {"email": null}}));
+    }
+}
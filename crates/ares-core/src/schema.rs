@@ -1,12 +1,16 @@
 use std::collections::HashMap;
 use std::path::{Path, PathBuf};
+use std::time::SystemTime;
 
 use crate::error::AppError;
 
 /// Validate that a JSON value is a valid JSON Schema.
 ///
 /// Uses meta-schema validation to check conformance against the JSON Schema
-/// specification. Returns an error with details if the schema is invalid.
+/// specification. Returns [`AppError::ValidationError`] listing every
+/// violation (capped at [`MAX_VALIDATION_ERRORS`]) if the schema is invalid,
+/// or [`AppError::SchemaError`] if it is not an object, declares an unknown
+/// `$schema`, or declares a malformed `x-ares-retention` policy.
 pub fn validate_schema(value: &serde_json::Value) -> Result<(), AppError> {
     if !value.is_object() {
         return Err(AppError::SchemaError(
@@ -14,16 +18,46 @@ pub fn validate_schema(value: &serde_json::Value) -> Result<(), AppError> {
         ));
     }
 
-    jsonschema::meta::options()
-        .validate(value)
+    let validator = jsonschema::meta::validator_for(value)
         .map_err(|e| AppError::SchemaError(format!("Invalid JSON Schema: {e}")))?;
+    let errors = collect_errors(validator.iter_errors(value));
+    if !errors.is_empty() {
+        return Err(AppError::ValidationError(errors));
+    }
 
+    crate::retention::RetentionPolicy::from_schema(value)?;
     Ok(())
 }
 
 /// Maximum number of validation failures reported in a single error message.
 const MAX_VALIDATION_ERRORS: usize = 10;
 
+/// Format validation failures with their location, capped at
+/// [`MAX_VALIDATION_ERRORS`] entries plus an "omitted" marker.
+fn collect_errors<'a>(
+    errors: impl Iterator<Item = jsonschema::ValidationError<'a>>,
+) -> Vec<String> {
+    // Collect one extra so we can tell whether the list was truncated.
+    let mut errors: Vec<String> = errors
+        .take(MAX_VALIDATION_ERRORS + 1)
+        .map(|err| {
+            let path = err.instance_path();
+            if path.as_str().is_empty() {
+                err.to_string()
+            } else {
+                format!("{err} (at `{path}`)")
+            }
+        })
+        .collect();
+
+    if errors.len() > MAX_VALIDATION_ERRORS {
+        errors.truncate(MAX_VALIDATION_ERRORS);
+        errors.push("… (additional errors omitted)".to_string());
+    }
+
+    errors
+}
+
 /// Validate that an extracted JSON `value` conforms to `schema`.
 ///
 /// Unlike [`validate_schema`], which checks that the schema document itself is a
@@ -38,35 +72,29 @@ pub fn validate_extracted_output(
     schema: &serde_json::Value,
     value: &serde_json::Value,
 ) -> Result<(), AppError> {
-    let validator = jsonschema::validator_for(schema)
-        .map_err(|e| AppError::SchemaError(format!("Invalid JSON Schema: {e}")))?;
-
-    // Collect one extra so we can tell whether the list was truncated.
-    let mut errors: Vec<String> = validator
-        .iter_errors(value)
-        .take(MAX_VALIDATION_ERRORS + 1)
-        .map(|err| {
-            let path = err.instance_path();
-            if path.as_str().is_empty() {
-                err.to_string()
-            } else {
-                format!("{err} (at `{path}`)")
-            }
-        })
-        .collect();
-
+    let errors = extracted_output_errors(schema, value)?;
     if errors.is_empty() {
         return Ok(());
     }
 
-    if errors.len() > MAX_VALIDATION_ERRORS {
-        errors.truncate(MAX_VALIDATION_ERRORS);
-        errors.push("… (additional errors omitted)".to_string());
-    }
-
     Err(AppError::ExtractionValidationError(errors.join("; ")))
 }
 
+/// List the ways an extracted JSON `value` fails to conform to `schema`.
+///
+/// Same checks as [`validate_extracted_output`], but returns the individual
+/// failures (empty when the value conforms) instead of folding them into an
+/// error. Capped at [`MAX_VALIDATION_ERRORS`] entries plus an "omitted" marker.
+pub fn extracted_output_errors(
+    schema: &serde_json::Value,
+    value: &serde_json::Value,
+) -> Result<Vec<String>, AppError> {
+    let validator = jsonschema::validator_for(schema)
+        .map_err(|e| AppError::SchemaError(format!("Invalid JSON Schema: {e}")))?;
+
+    Ok(collect_errors(validator.iter_errors(value)))
+}
+
 /// A fully resolved schema: path, canonical name, and parsed JSON.
 #[derive(Debug, Clone)]
 pub struct ResolvedSchema {
@@ -81,6 +109,25 @@ pub struct SchemaEntry {
     pub name: String,
     pub latest_version: String,
     pub versions: Vec<String>,
+    /// Creation time of the oldest version file, when the file system
+    /// records one.
+    pub created_at: Option<SystemTime>,
+    /// Most recent modification time across the version files.
+    pub updated_at: Option<SystemTime>,
+}
+
+impl SchemaEntry {
+    /// Number of versions of this schema.
+    pub fn version_count(&self) -> usize {
+        self.versions.len()
+    }
+}
+
+/// A schema version file with its file system timestamps.
+struct VersionFile {
+    version: String,
+    created_at: Option<SystemTime>,
+    updated_at: Option<SystemTime>,
 }
 
 /// Resolves schema references (file paths or `name@version` strings) to
@@ -211,11 +258,13 @@ impl SchemaResolver {
         let mut entries = Vec::new();
 
         for (name, latest_version) in &registry {
-            let versions = self.list_versions(name)?;
+            let files = self.list_versions(name)?;
             entries.push(SchemaEntry {
                 name: name.clone(),
                 latest_version: latest_version.clone(),
-                versions,
+                created_at: files.iter().filter_map(|f| f.created_at).min(),
+                updated_at: files.iter().filter_map(|f| f.updated_at).max(),
+                versions: files.into_iter().map(|f| f.version).collect(),
             });
         }
 
@@ -223,8 +272,21 @@ impl SchemaResolver {
         Ok(entries)
     }
 
-    /// List all version files for a given schema name.
-    fn list_versions(&self, name: &str) -> Result<Vec<String>, AppError> {
+    /// Number of registered schemas.
+    pub fn schema_count(&self) -> Result<usize, AppError> {
+        Ok(self.load_registry()?.len())
+    }
+
+    /// Number of versions across all registered schemas.
+    pub fn total_version_count(&self) -> Result<usize, AppError> {
+        self.load_registry()?
+            .keys()
+            .map(|name| Ok(self.list_versions(name)?.len()))
+            .sum()
+    }
+
+    /// List all version files for a given schema name, oldest version first.
+    fn list_versions(&self, name: &str) -> Result<Vec<VersionFile>, AppError> {
         let schema_dir = self.schemas_dir.join(name);
         if !schema_dir.is_dir() {
             return Ok(vec![]);
@@ -246,11 +308,16 @@ impl SchemaResolver {
             if path.extension().is_some_and(|ext| ext == "json")
                 && let Some(stem) = path.file_stem().and_then(|s| s.to_str())
             {
-                versions.push(stem.to_string());
+                let metadata = entry.metadata().ok();
+                versions.push(VersionFile {
+                    version: stem.to_string(),
+                    created_at: metadata.as_ref().and_then(|m| m.created().ok()),
+                    updated_at: metadata.as_ref().and_then(|m| m.modified().ok()),
+                });
             }
         }
 
-        versions.sort_by(|a, b| version_cmp(a, b));
+        versions.sort_by(|a, b| version_cmp(&a.version, &b.version));
         Ok(versions)
     }
 
@@ -328,18 +395,10 @@ impl SchemaResolver {
             registry.remove(name);
         } else if registry.get(name).is_some_and(|latest| latest == version) {
             // Deleted version was the latest — point to the highest remaining
-            registry.insert(name.to_string(), remaining.last().unwrap().clone());
+            registry.insert(name.to_string(), remaining.last().unwrap().version.clone());
         }
 
-        let registry_path = self.schemas_dir.join("registry.json");
-        let registry_json = serde_json::to_string_pretty(&registry)
-            .map_err(|e| AppError::SchemaError(e.to_string()))?;
-        std::fs::write(&registry_path, format!("{registry_json}\n")).map_err(|e| {
-            AppError::SchemaError(format!(
-                "Failed to write schema registry {}: {e}",
-                registry_path.display()
-            ))
-        })?;
+        self.write_registry(&registry)?;
 
         // Clean up empty directory (non-fatal)
         let _ = std::fs::remove_dir(self.schemas_dir.join(name));
@@ -392,17 +451,46 @@ impl SchemaResolver {
         if should_update {
             registry.insert(name.to_string(), version.to_string());
         }
+        self.write_registry(&registry)?;
+
+        Ok(())
+    }
+
+    /// Point the registry's latest version for `name` at an existing version.
+    ///
+    /// Unlike [`create_schema`](Self::create_schema), this may move `latest`
+    /// backwards, e.g. to roll back a bad release.
+    pub fn set_latest(&self, name: &str, version: &str) -> Result<(), AppError> {
+        if name.is_empty() || version.is_empty() {
+            return Err(AppError::SchemaError(
+                "Schema name and version must not be empty".to_string(),
+            ));
+        }
+
+        let schema_path = self.schemas_dir.join(name).join(format!("{version}.json"));
+        if !schema_path.exists() {
+            return Err(AppError::SchemaNotFound {
+                name: name.to_string(),
+                version: version.to_string(),
+            });
+        }
+
+        let mut registry = self.load_registry()?;
+        registry.insert(name.to_string(), version.to_string());
+        self.write_registry(&registry)
+    }
+
+    /// Write `registry` back to `registry.json`.
+    fn write_registry(&self, registry: &HashMap<String, String>) -> Result<(), AppError> {
         let registry_path = self.schemas_dir.join("registry.json");
-        let registry_json = serde_json::to_string_pretty(&registry)
+        let registry_json = serde_json::to_string_pretty(registry)
             .map_err(|e| AppError::SchemaError(e.to_string()))?;
         std::fs::write(&registry_path, format!("{registry_json}\n")).map_err(|e| {
             AppError::SchemaError(format!(
                 "Failed to write schema registry {}: {e}",
                 registry_path.display()
             ))
-        })?;
-
-        Ok(())
+        })
     }
 }
 
@@ -671,6 +759,64 @@ mod tests {
         assert_eq!(entries[0].latest_version, "2.0.0");
         // Versions should be sorted
         assert_eq!(entries[0].versions, vec!["1.0.0", "1.1.0", "2.0.0"]);
+        assert_eq!(entries[0].version_count(), 3);
+    }
+
+    #[test]
+    fn test_schema_and_version_counts() {
+        let tmp = TempDir::new().unwrap();
+        let schemas_dir = tmp.path().join("schemas");
+        std::fs::create_dir_all(&schemas_dir).unwrap();
+
+        let resolver = SchemaResolver::new(&schemas_dir);
+        assert_eq!(resolver.schema_count().unwrap(), 0);
+        assert_eq!(resolver.total_version_count().unwrap(), 0);
+
+        let schema = serde_json::json!({"type": "object"});
+        resolver.create_schema("blog", "1.0.0", &schema).unwrap();
+        resolver.create_schema("blog", "1.1.0", &schema).unwrap();
+        resolver.create_schema("product", "2.0.0", &schema).unwrap();
+        assert_eq!(resolver.schema_count().unwrap(), 2);
+        assert_eq!(resolver.total_version_count().unwrap(), 3);
+
+        std::fs::write(schemas_dir.join("registry.json"), "not json").unwrap();
+        assert!(resolver.schema_count().is_err());
+        assert!(resolver.total_version_count().is_err());
+    }
+
+    #[test]
+    fn test_list_schemas_reports_file_timestamps() {
+        let tmp = TempDir::new().unwrap();
+        let schemas_dir = tmp.path().join("schemas");
+        std::fs::create_dir_all(&schemas_dir).unwrap();
+
+        let resolver = SchemaResolver::new(&schemas_dir);
+        let schema = serde_json::json!({"type": "object"});
+        resolver.create_schema("blog", "1.0.0", &schema).unwrap();
+        resolver.create_schema("blog", "2.0.0", &schema).unwrap();
+
+        let old = SystemTime::UNIX_EPOCH + std::time::Duration::from_secs(1_700_000_000);
+        std::fs::File::options()
+            .write(true)
+            .open(schemas_dir.join("blog/1.0.0.json"))
+            .unwrap()
+            .set_modified(old)
+            .unwrap();
+
+        let entry = &resolver.list_schemas().unwrap()[0];
+        let updated_at = entry.updated_at.expect("modification time");
+        assert!(updated_at > old);
+        assert_eq!(
+            updated_at,
+            std::fs::metadata(schemas_dir.join("blog/2.0.0.json"))
+                .unwrap()
+                .modified()
+                .unwrap()
+        );
+        // Creation time is not recorded on every file system.
+        if let Some(created_at) = entry.created_at {
+            assert!(created_at <= updated_at);
+        }
     }
 
     #[test]
@@ -829,6 +975,39 @@ mod tests {
         assert_eq!(registry.get("blog").unwrap(), "2.0.0");
     }
 
+    #[test]
+    fn test_set_latest_can_roll_back() {
+        let tmp = TempDir::new().unwrap();
+        let schemas_dir = tmp.path().join("schemas");
+        std::fs::create_dir_all(&schemas_dir).unwrap();
+
+        let resolver = SchemaResolver::new(&schemas_dir);
+        let schema = serde_json::json!({"type": "object"});
+
+        resolver.create_schema("blog", "1.0.0", &schema).unwrap();
+        resolver.create_schema("blog", "2.0.0", &schema).unwrap();
+        resolver.set_latest("blog", "1.0.0").unwrap();
+
+        let registry = resolver.load_registry().unwrap();
+        assert_eq!(registry.get("blog").unwrap(), "1.0.0");
+    }
+
+    #[test]
+    fn test_set_latest_requires_existing_version() {
+        let tmp = TempDir::new().unwrap();
+        let schemas_dir = tmp.path().join("schemas");
+        std::fs::create_dir_all(&schemas_dir).unwrap();
+
+        let resolver = SchemaResolver::new(&schemas_dir);
+        resolver
+            .create_schema("blog", "1.0.0", &serde_json::json!({"type": "object"}))
+            .unwrap();
+
+        let err = resolver.set_latest("blog", "3.0.0").unwrap_err();
+        assert!(matches!(err, AppError::SchemaNotFound { .. }));
+        assert_eq!(resolver.load_registry().unwrap()["blog"], "1.0.0");
+    }
+
     // -----------------------------------------------------------------------
     // validate_schema tests
     // -----------------------------------------------------------------------
@@ -874,8 +1053,41 @@ mod tests {
             "type": "not_a_valid_type"
         });
         let err = validate_schema(&schema).unwrap_err();
-        assert!(matches!(err, AppError::SchemaError(_)));
-        assert!(err.to_string().contains("Invalid JSON Schema"));
+        let AppError::ValidationError(messages) = err else {
+            panic!("expected ValidationError, got {err:?}");
+        };
+        assert!(!messages.is_empty());
+        assert!(
+            messages.iter().all(|m| m.contains("(at `/type`)")),
+            "{messages:?}"
+        );
+    }
+
+    #[test]
+    fn test_validate_schema_reports_every_violation() {
+        let schema = serde_json::json!({
+            "type": "object",
+            "properties": {
+                "title": { "type": 42 },
+                "count": { "minimum": "zero" }
+            },
+            "required": "title"
+        });
+        let AppError::ValidationError(messages) = validate_schema(&schema).unwrap_err() else {
+            panic!("expected ValidationError");
+        };
+        for path in [
+            "/properties/title/type",
+            "/properties/count/minimum",
+            "/required",
+        ] {
+            assert!(
+                messages
+                    .iter()
+                    .any(|m| m.contains(&format!("(at `{path}`)"))),
+                "missing {path} in {messages:?}"
+            );
+        }
     }
 
     #[test]
@@ -885,7 +1097,18 @@ mod tests {
             "properties": "not an object"
         });
         let err = validate_schema(&schema).unwrap_err();
+        assert!(matches!(err, AppError::ValidationError(_)));
+    }
+
+    #[test]
+    fn test_validate_schema_rejects_bad_retention() {
+        let schema = serde_json::json!({
+            "type": "object",
+            "x-ares-retention": {"days": 30, "scrub_fields": ["email"]}
+        });
+        let err = validate_schema(&schema).unwrap_err();
         assert!(matches!(err, AppError::SchemaError(_)));
+        assert!(err.to_string().contains("x-ares-retention"), "{err}");
     }
 
     #[test]
@@ -900,7 +1123,7 @@ mod tests {
         let err = resolver
             .create_schema("test", "1.0.0", &invalid)
             .unwrap_err();
-        assert!(matches!(err, AppError::SchemaError(_)));
+        assert!(matches!(err, AppError::ValidationError(_)));
         // File should not have been written
         assert!(!schemas_dir.join("test/1.0.0.json").exists());
     }
@@ -919,7 +1142,7 @@ mod tests {
         let err = resolver
             .update_schema("test", "1.0.0", &invalid)
             .unwrap_err();
-        assert!(matches!(err, AppError::SchemaError(_)));
+        assert!(matches!(err, AppError::ValidationError(_)));
 
         // Original content should be preserved
         let resolved = resolver.resolve("test@1.0.0").unwrap();
@@ -995,6 +1218,20 @@ mod tests {
         assert!(matches!(err, AppError::SchemaError(_)));
     }
 
+    #[test]
+    fn test_output_errors_lists_each_failure() {
+        let value = serde_json::json!({ "count": "x", "extra": 1 });
+        let errors = extracted_output_errors(&person_schema(), &value).unwrap();
+        assert_eq!(errors.len(), 3);
+
+        let valid = serde_json::json!({ "title": "Hello" });
+        assert!(
+            extracted_output_errors(&person_schema(), &valid)
+                .unwrap()
+                .is_empty()
+        );
+    }
+
     #[test]
     fn test_delete_only_version_removes_registry_entry() {
         let tmp = TempDir::new().unwrap();
@@ -1,20 +1,43 @@
 use std::sync::Arc;
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+use futures::stream::{FuturesUnordered, Stream, StreamExt};
+use uuid::Uuid;
 
 use crate::cache::{ContentCache, ExtractionCache};
+use crate::diff::diff_json;
 use crate::error::AppError;
-use crate::models::{NewExtraction, ScrapeResult, compute_hash};
-use crate::traits::{Cleaner, ExtractionStore, Extractor, Fetcher};
+use crate::fetch::FetchOptions;
+use crate::models::{
+    ChangeEvent, Extraction, ExtractionEvent, ExtractionOutcome, NewExtraction, SaveOutcome,
+    ScrapeResult, compute_hash, compute_json_hash, compute_simhash, simhash_distance,
+};
+use crate::retention::{RetentionPolicy, ScrubObserver};
+use crate::schema::ResolvedSchema;
+use crate::snapshot::SnapshotKind;
+use crate::traits::{
+    ChangeNotifier, Cleaner, ExtractionEventPublisher, ExtractionStore, Extractor, Fetcher,
+    NullNotifier, NullPublisher, NullSnapshotStore, NullStore, SnapshotStore,
+};
+
+/// How many pages [`ScrapeService::scrape_batch_parallel`] scrapes at once
+/// unless overridden with [`ScrapeService::with_batch_concurrency`].
+pub const DEFAULT_BATCH_CONCURRENCY: usize = 8;
 
 /// Orchestrates the full scrape pipeline: fetch → clean → extract → hash → compare → save.
 ///
 /// Generic over all external dependencies via traits, enabling dependency injection
 /// and testability without real HTTP or LLM calls.
-pub struct ScrapeService<F, C, E, S>
+pub struct ScrapeService<F, C, E, S, N = NullNotifier, P = NullPublisher, A = NullSnapshotStore>
 where
     F: Fetcher,
     C: Cleaner,
     E: Extractor,
     S: ExtractionStore,
+    N: ChangeNotifier,
+    P: ExtractionEventPublisher,
+    A: SnapshotStore,
 {
     fetcher: F,
     cleaner: C,
@@ -23,10 +46,22 @@ where
     model_name: String,
     provider: String,
     skip_unchanged: bool,
+    use_upsert: bool,
+    store_content: bool,
     validate: bool,
     max_content_chars: Option<usize>,
+    fetch_options: FetchOptions,
+    batch_concurrency: usize,
     content_cache: Option<ContentCache>,
     extraction_cache: Option<ExtractionCache>,
+    notifier: N,
+    publisher: P,
+    snapshots: A,
+    snapshot_kinds: Vec<SnapshotKind>,
+    dedup_window: Option<Duration>,
+    reuse_duplicates: bool,
+    content_similarity_threshold: Option<u32>,
+    schema_file: Option<ResolvedSchema>,
 }
 
 impl<F, C, E, S> ScrapeService<F, C, E, S>
@@ -37,36 +72,191 @@ where
     S: ExtractionStore,
 {
     /// Create a new ScrapeService without persistence.
+    ///
+    /// Unlike [`ScrapeServiceBuilder::build`], nothing is checked; prefer the
+    /// builder in new code.
     pub fn new(fetcher: F, cleaner: C, extractor: E, model_name: String) -> Self {
-        Self {
-            fetcher,
-            cleaner,
-            extractor,
-            store: None,
-            model_name,
-            provider: "openai".to_string(),
-            skip_unchanged: false,
-            validate: true,
-            max_content_chars: None,
-            content_cache: None,
-            extraction_cache: None,
-        }
+        Self::from_parts(fetcher, cleaner, extractor, None, model_name)
     }
 
     /// Create a new ScrapeService with database persistence.
     pub fn with_store(fetcher: F, cleaner: C, extractor: E, store: S, model_name: String) -> Self {
+        Self::from_parts(fetcher, cleaner, extractor, Some(store), model_name)
+    }
+
+    fn from_parts(
+        fetcher: F,
+        cleaner: C,
+        extractor: E,
+        store: Option<S>,
+        model_name: String,
+    ) -> Self {
         Self {
             fetcher,
             cleaner,
             extractor,
-            store: Some(store),
+            store,
             model_name,
             provider: "openai".to_string(),
             skip_unchanged: false,
+            use_upsert: false,
+            store_content: false,
             validate: true,
             max_content_chars: None,
+            fetch_options: FetchOptions::default(),
+            batch_concurrency: DEFAULT_BATCH_CONCURRENCY,
             content_cache: None,
             extraction_cache: None,
+            notifier: NullNotifier,
+            publisher: NullPublisher,
+            snapshots: NullSnapshotStore,
+            snapshot_kinds: Vec::new(),
+            dedup_window: None,
+            reuse_duplicates: false,
+            content_similarity_threshold: None,
+            schema_file: None,
+        }
+    }
+}
+
+impl<F, C, E, S, N, P, A> ScrapeService<F, C, E, S, N, P, A>
+where
+    F: Fetcher,
+    C: Cleaner,
+    E: Extractor,
+    S: ExtractionStore,
+    N: ChangeNotifier,
+    P: ExtractionEventPublisher,
+    A: SnapshotStore,
+{
+    fn replace_store<S2: ExtractionStore>(
+        self,
+        store: Option<S2>,
+    ) -> ScrapeService<F, C, E, S2, N, P, A> {
+        ScrapeService {
+            fetcher: self.fetcher,
+            cleaner: self.cleaner,
+            extractor: self.extractor,
+            store,
+            model_name: self.model_name,
+            provider: self.provider,
+            skip_unchanged: self.skip_unchanged,
+            use_upsert: self.use_upsert,
+            store_content: self.store_content,
+            validate: self.validate,
+            max_content_chars: self.max_content_chars,
+            fetch_options: self.fetch_options,
+            batch_concurrency: self.batch_concurrency,
+            content_cache: self.content_cache,
+            extraction_cache: self.extraction_cache,
+            notifier: self.notifier,
+            publisher: self.publisher,
+            snapshots: self.snapshots,
+            snapshot_kinds: self.snapshot_kinds,
+            dedup_window: self.dedup_window,
+            reuse_duplicates: self.reuse_duplicates,
+            content_similarity_threshold: self.content_similarity_threshold,
+            schema_file: self.schema_file,
+        }
+    }
+
+    /// Tell `notifier` whenever a saved extraction's data differs from the
+    /// previous extraction of the same page. Requires a store.
+    pub fn with_notifier<N2: ChangeNotifier>(
+        self,
+        notifier: N2,
+    ) -> ScrapeService<F, C, E, S, N2, P, A> {
+        ScrapeService {
+            fetcher: self.fetcher,
+            cleaner: self.cleaner,
+            extractor: self.extractor,
+            store: self.store,
+            model_name: self.model_name,
+            provider: self.provider,
+            skip_unchanged: self.skip_unchanged,
+            use_upsert: self.use_upsert,
+            store_content: self.store_content,
+            validate: self.validate,
+            max_content_chars: self.max_content_chars,
+            fetch_options: self.fetch_options,
+            batch_concurrency: self.batch_concurrency,
+            content_cache: self.content_cache,
+            extraction_cache: self.extraction_cache,
+            notifier,
+            publisher: self.publisher,
+            snapshots: self.snapshots,
+            snapshot_kinds: self.snapshot_kinds,
+            dedup_window: self.dedup_window,
+            reuse_duplicates: self.reuse_duplicates,
+            content_similarity_threshold: self.content_similarity_threshold,
+            schema_file: self.schema_file,
+        }
+    }
+
+    /// Publish an [`ExtractionEvent`] for every saved extraction. Requires a
+    /// store.
+    pub fn with_publisher<P2: ExtractionEventPublisher>(
+        self,
+        publisher: P2,
+    ) -> ScrapeService<F, C, E, S, N, P2, A> {
+        ScrapeService {
+            fetcher: self.fetcher,
+            cleaner: self.cleaner,
+            extractor: self.extractor,
+            store: self.store,
+            model_name: self.model_name,
+            provider: self.provider,
+            skip_unchanged: self.skip_unchanged,
+            use_upsert: self.use_upsert,
+            store_content: self.store_content,
+            validate: self.validate,
+            max_content_chars: self.max_content_chars,
+            fetch_options: self.fetch_options,
+            batch_concurrency: self.batch_concurrency,
+            content_cache: self.content_cache,
+            extraction_cache: self.extraction_cache,
+            notifier: self.notifier,
+            publisher,
+            snapshots: self.snapshots,
+            snapshot_kinds: self.snapshot_kinds,
+            dedup_window: self.dedup_window,
+            reuse_duplicates: self.reuse_duplicates,
+            content_similarity_threshold: self.content_similarity_threshold,
+            schema_file: self.schema_file,
+        }
+    }
+
+    /// Archive the `kinds` of source content behind every saved extraction
+    /// in `store`, recording the keys on the extraction. Requires a store.
+    pub fn with_snapshots<A2: SnapshotStore>(
+        self,
+        store: A2,
+        kinds: &[SnapshotKind],
+    ) -> ScrapeService<F, C, E, S, N, P, A2> {
+        ScrapeService {
+            fetcher: self.fetcher,
+            cleaner: self.cleaner,
+            extractor: self.extractor,
+            store: self.store,
+            model_name: self.model_name,
+            provider: self.provider,
+            skip_unchanged: self.skip_unchanged,
+            use_upsert: self.use_upsert,
+            store_content: self.store_content,
+            validate: self.validate,
+            max_content_chars: self.max_content_chars,
+            fetch_options: self.fetch_options,
+            batch_concurrency: self.batch_concurrency,
+            content_cache: self.content_cache,
+            extraction_cache: self.extraction_cache,
+            notifier: self.notifier,
+            publisher: self.publisher,
+            snapshots: store,
+            snapshot_kinds: kinds.to_vec(),
+            dedup_window: self.dedup_window,
+            reuse_duplicates: self.reuse_duplicates,
+            content_similarity_threshold: self.content_similarity_threshold,
+            schema_file: self.schema_file,
         }
     }
 
@@ -76,6 +266,20 @@ where
         self
     }
 
+    /// When enabled, persist via [`ExtractionStore::upsert`] so re-runs that
+    /// produce the same data update one record instead of adding snapshots.
+    pub fn with_upsert(mut self, upsert: bool) -> Self {
+        self.use_upsert = upsert;
+        self
+    }
+
+    /// When enabled, keep the cleaned content sent to the extractor with
+    /// each saved extraction, via [`ExtractionStore::save_content`].
+    pub fn with_store_content(mut self, store: bool) -> Self {
+        self.store_content = store;
+        self
+    }
+
     /// Set the provider name recorded in extraction run metadata (e.g.
     /// `openai`, `anthropic`, `local`). Defaults to `openai`.
     pub fn with_provider(mut self, provider: impl Into<String>) -> Self {
@@ -92,6 +296,15 @@ where
         self
     }
 
+    /// Also validate extracted output against `schema_file`, e.g. the file
+    /// the schema passed to [`scrape`](Self::scrape) was created from, in case
+    /// that copy is stale. Checked along with the other output validation, so
+    /// a mismatch also fails before anything is persisted.
+    pub fn with_schema_file(mut self, schema_file: Option<ResolvedSchema>) -> Self {
+        self.schema_file = schema_file;
+        self
+    }
+
     /// Cap the cleaned content (in characters) sent to the extractor.
     ///
     /// Real pages can clean to tens of KB of Markdown; bounding the input keeps
@@ -104,6 +317,22 @@ where
         self
     }
 
+    /// Per-page fetch options: passed to [`Fetcher::fetch_with`], with
+    /// `content_selector` applied when cleaning and `llm_timeout_secs` as an
+    /// upper bound on the extraction call.
+    pub fn with_fetch_options(mut self, options: FetchOptions) -> Self {
+        self.fetch_options = options;
+        self
+    }
+
+    /// Maximum number of pages [`scrape_batch_parallel`](Self::scrape_batch_parallel)
+    /// has in flight at once (at least 1). Defaults to
+    /// [`DEFAULT_BATCH_CONCURRENCY`].
+    pub fn with_batch_concurrency(mut self, concurrency: usize) -> Self {
+        self.batch_concurrency = concurrency.max(1);
+        self
+    }
+
     /// Enable in-memory caching for fetched content and LLM extraction results.
     pub fn with_caches(
         mut self,
@@ -115,6 +344,34 @@ where
         self
     }
 
+    /// Mark pages whose cleaned content was already extracted for another
+    /// URL with the same schema within `window` (mirrors, tracking-parameter
+    /// variants) as duplicates of that extraction. `None` (default) turns
+    /// the check off. Requires a store.
+    pub fn with_dedup_window(mut self, window: Option<Duration>) -> Self {
+        self.dedup_window = window;
+        self
+    }
+
+    /// When enabled, a duplicate page skips the LLM call and reuses the
+    /// extracted data of the extraction it duplicates. Requires a dedup
+    /// window.
+    pub fn with_reuse_duplicates(mut self, reuse: bool) -> Self {
+        self.reuse_duplicates = reuse;
+        self
+    }
+
+    /// Treat content whose simhash differs from the previous extraction of
+    /// the page in fewer than `threshold` of its 64 bits as unchanged: skip
+    /// the LLM call and reuse that extraction's data. Catches pages whose
+    /// content hash changes on every fetch (timestamps, rotated "related
+    /// articles") without their substance changing. `None` (default) turns
+    /// the check off. Requires a store.
+    pub fn with_content_similarity_threshold(mut self, threshold: Option<u32>) -> Self {
+        self.content_similarity_threshold = threshold;
+        self
+    }
+
     /// Run the full scrape pipeline for a URL + schema.
     ///
     /// 1. Fetch HTML from URL
@@ -123,33 +380,131 @@ where
     /// 4. Compute content and data hashes
     /// 5. Compare with previous extraction (if store available)
     /// 6. Persist result (if store available)
+    #[tracing::instrument(name = "scrape", skip_all, fields(url = %url, schema_name = %schema_name))]
     pub async fn scrape(
         &self,
         url: &str,
         schema: &serde_json::Value,
         schema_name: &str,
     ) -> Result<ScrapeResult, AppError> {
-        // 1. Fetch (with optional content cache)
-        let html: Arc<str> = if let Some(cache) = &self.content_cache {
+        // 1. Fetch (with optional content cache). The cache is keyed by URL
+        // alone, so pages fetched with credentials or custom headers bypass
+        // it rather than leak to (or from) another caller's fetch.
+        let content_cache = self
+            .content_cache
+            .as_ref()
+            .filter(|_| !self.fetch_options.personalizes_fetch());
+        let html: Arc<str> = if let Some(cache) = content_cache {
             if let Some(cached) = cache.get(url).await {
                 tracing::info!("Using cached content for {} ({} bytes)", url, cached.len());
                 cached
             } else {
                 tracing::info!("Fetching {}", url);
-                let html: Arc<str> = self.fetcher.fetch(url).await?.into();
+                let html: Arc<str> = self
+                    .fetcher
+                    .fetch_with(url, &self.fetch_options)
+                    .await?
+                    .into();
                 tracing::info!("Fetched {} bytes of HTML", html.len());
                 cache.insert(url, Arc::clone(&html)).await;
                 html
             }
         } else {
             tracing::info!("Fetching {}", url);
-            let html: Arc<str> = self.fetcher.fetch(url).await?.into();
+            let html: Arc<str> = self
+                .fetcher
+                .fetch_with(url, &self.fetch_options)
+                .await?
+                .into();
             tracing::info!("Fetched {} bytes of HTML", html.len());
             html
         };
 
+        self.process(url, html, schema, schema_name).await
+    }
+
+    /// Run the pipeline on HTML the caller already has, skipping the fetch.
+    ///
+    /// `url` is only a label: it is never requested, but it keys the stored
+    /// extraction exactly as in [`scrape`](Self::scrape), so change detection
+    /// works across submissions for the same label. The content cache is
+    /// neither read nor written.
+    #[tracing::instrument(name = "scrape_html", skip_all, fields(url = %url, schema_name = %schema_name))]
+    pub async fn scrape_html(
+        &self,
+        html: &str,
+        url: &str,
+        schema: &serde_json::Value,
+        schema_name: &str,
+    ) -> Result<ScrapeResult, AppError> {
+        tracing::info!("Using {} bytes of caller-supplied HTML", html.len());
+        self.process(url, html.into(), schema, schema_name).await
+    }
+
+    /// Scrape every URL concurrently, yielding each URL with its result as
+    /// soon as it finishes.
+    ///
+    /// Results arrive in completion order, not input order, so a caller can
+    /// print them as they come. At most `batch_concurrency` pages are in
+    /// flight; the rest start as earlier ones complete. A failed URL yields
+    /// its error and doesn't stop the batch.
+    pub fn scrape_batch_parallel<'a>(
+        &'a self,
+        urls: impl IntoIterator<Item = String> + 'a,
+        schema: &'a serde_json::Value,
+        schema_name: &'a str,
+    ) -> impl Stream<Item = (String, Result<ScrapeResult, AppError>)> + 'a {
+        let scrape_one = move |url: String| async move {
+            let result = self.scrape(&url, schema, schema_name).await;
+            (url, result)
+        };
+
+        futures::stream::unfold(
+            (urls.into_iter(), FuturesUnordered::new()),
+            move |(mut pending, mut in_flight)| async move {
+                while in_flight.len() < self.batch_concurrency {
+                    match pending.next() {
+                        Some(url) => in_flight.push(scrape_one(url)),
+                        None => break,
+                    }
+                }
+                let item = in_flight.next().await?;
+                Some((item, (pending, in_flight)))
+            },
+        )
+    }
+
+    /// Call the extractor, bounded by `llm_timeout_secs` when set.
+    async fn extract(
+        &self,
+        markdown: &str,
+        schema: &serde_json::Value,
+    ) -> Result<ExtractionOutcome, AppError> {
+        match self.fetch_options.llm_timeout() {
+            Some(limit) => tokio::time::timeout(limit, self.extractor.extract(markdown, schema))
+                .await
+                .map_err(|_| AppError::Timeout(limit.as_secs()))?,
+            None => self.extractor.extract(markdown, schema).await,
+        }
+    }
+
+    /// Steps 2–6 of the pipeline, shared by [`scrape`](Self::scrape) and
+    /// [`scrape_html`](Self::scrape_html).
+    async fn process(
+        &self,
+        url: &str,
+        html: Arc<str>,
+        schema: &serde_json::Value,
+        schema_name: &str,
+    ) -> Result<ScrapeResult, AppError> {
+        // A malformed retention policy fails here, before the LLM call.
+        let retention = RetentionPolicy::from_schema(schema)?;
+
         // 2. Clean
-        let markdown = self.cleaner.clean(&html)?;
+        let markdown = match &self.fetch_options.content_selector {
+            Some(selector) => self.cleaner.clean_within(&html, selector)?,
+            None => self.cleaner.clean(&html)?,
+        };
         tracing::info!(
             "Cleaned to {} bytes of Markdown ({}% reduction)",
             markdown.len(),
@@ -178,39 +533,94 @@ where
 
         // 3. Hash content and schema (before extraction, needed for extraction cache key)
         let content_hash = compute_hash(&markdown);
-        let schema_hash = compute_hash(&schema.to_string());
+        let content_simhash = compute_simhash(&markdown);
+        let schema_hash = compute_hash(schema.to_string());
+        let prompt_hash = self.extractor.fingerprint().map(compute_hash);
+
+        // 3a. Tag the content with its language, so results can be filtered by
+        // it and mismatches with the schema's output language are visible.
+        let detected_language = crate::language::detect_language(&markdown);
+
+        // 3b. Optionally look for the same content already extracted for
+        // another URL.
+        let duplicate = self.find_duplicate(url, &content_hash, schema_name).await?;
+
+        // 3c. Optionally look for the page's previous extraction having
+        // near-identical content, made with the same schema and prompt.
+        let similar = self
+            .find_similar(
+                url,
+                &content_simhash,
+                schema_name,
+                &schema_hash,
+                prompt_hash.as_deref(),
+            )
+            .await?;
+        // Reused data keeps the simhash of the content it was extracted from,
+        // so small changes can't add up to drift unnoticed.
+        let source_simhash = match &similar {
+            Some(previous) => previous.content_simhash.clone(),
+            None => Some(content_simhash.clone()),
+        };
 
         // 4. Extract (with optional extraction cache). Latency and token usage
-        // are captured only on a real LLM call; cache hits report neither.
-        let (extracted, latency_ms, usage) = if let Some(cache) = &self.extraction_cache {
-            if let Some(cached) = cache
-                .get(&content_hash, schema_name, &schema_hash, &self.model_name)
-                .await
+        // are captured only on a real LLM call; cache hits and reused
+        // duplicates report neither. The recorded model is the one that
+        // answered, when the provider says.
+        let mut model = self.model_name.clone();
+        let (mut extracted, latency_ms, usage) = match (&duplicate, &similar) {
+            (_, Some(previous)) => {
+                tracing::info!(previous = %previous.id, "Reusing the previous extraction's data");
+                model = previous.model.clone();
+                (previous.extracted_data.clone(), None, None)
+            }
+            // Scrubbed data is missing fields the schema asks for, so it is
+            // only ever linked, not copied.
+            (Some((original, found)), None)
+                if self.reuse_duplicates && found.scrubbed_at.is_none() =>
             {
-                tracing::info!("Using cached extraction for model {}", self.model_name);
-                (cached, None, None)
-            } else {
-                tracing::info!("Extracting with model {} ...", self.model_name);
-                let started = std::time::Instant::now();
-                let outcome = self.extractor.extract(&markdown, schema).await?;
-                let latency_ms = started.elapsed().as_millis();
-                cache
-                    .insert(
-                        &content_hash,
-                        schema_name,
-                        &schema_hash,
-                        &self.model_name,
-                        outcome.value.clone(),
-                    )
-                    .await;
-                (outcome.value, Some(latency_ms), outcome.usage)
+                tracing::info!(%original, "Reusing the duplicated extraction's data");
+                model = found.model.clone();
+                (found.extracted_data.clone(), None, None)
+            }
+            _ => {
+                if let Some(cache) = &self.extraction_cache {
+                    if let Some(cached) = cache
+                        .get(&content_hash, schema_name, &schema_hash, &self.model_name)
+                        .await
+                    {
+                        tracing::info!("Using cached extraction for model {}", self.model_name);
+                        (cached, None, None)
+                    } else {
+                        tracing::info!("Extracting with model {} ...", self.model_name);
+                        let started = std::time::Instant::now();
+                        let outcome = self.extract(&markdown, schema).await?;
+                        let latency_ms = started.elapsed().as_millis();
+                        cache
+                            .insert(
+                                &content_hash,
+                                schema_name,
+                                &schema_hash,
+                                &self.model_name,
+                                outcome.value.clone(),
+                            )
+                            .await;
+                        if let Some(answered) = outcome.model {
+                            model = answered;
+                        }
+                        (outcome.value, Some(latency_ms), outcome.usage)
+                    }
+                } else {
+                    tracing::info!("Extracting with model {} ...", self.model_name);
+                    let started = std::time::Instant::now();
+                    let outcome = self.extract(&markdown, schema).await?;
+                    let latency_ms = started.elapsed().as_millis();
+                    if let Some(answered) = outcome.model {
+                        model = answered;
+                    }
+                    (outcome.value, Some(latency_ms), outcome.usage)
+                }
             }
-        } else {
-            tracing::info!("Extracting with model {} ...", self.model_name);
-            let started = std::time::Instant::now();
-            let outcome = self.extractor.extract(&markdown, schema).await?;
-            let latency_ms = started.elapsed().as_millis();
-            (outcome.value, Some(latency_ms), outcome.usage)
         };
 
         // 4b. Validate extracted output against the schema before hashing/saving.
@@ -221,6 +631,17 @@ where
         // schema validation alone can't catch.
         if self.validate {
             crate::schema::validate_extracted_output(schema, &extracted)?;
+            if let Some(file) = &self.schema_file {
+                let errors = crate::schema::extracted_output_errors(&file.schema, &extracted)?;
+                if !errors.is_empty() {
+                    return Err(AppError::ExtractionValidationError(format!(
+                        "extracted data does not match schema {} ({}): {}",
+                        file.name,
+                        file.path.display(),
+                        errors.join("; ")
+                    )));
+                }
+            }
 
             let ungrounded = crate::groundedness::ungrounded_fields(&markdown, &extracted);
             if !ungrounded.is_empty() {
@@ -231,160 +652,635 @@ where
             }
         }
 
+        // 4c. Null out personal data the schema never wants stored. Done
+        // before hashing so the hash, diffs, and events all see what is saved.
+        let scrubbed = ScrubObserver.before_save(retention.as_ref(), &mut extracted);
+        if scrubbed > 0 {
+            tracing::debug!(scrubbed, "Scrubbed fields before save");
+        }
+
         // 5. Hash extracted data
-        let data_hash = compute_hash(&extracted.to_string());
+        let data_hash = compute_json_hash(&extracted);
         tracing::info!(
             content_hash = %&content_hash[..8],
             data_hash = %&data_hash[..8],
             latency_ms = ?latency_ms,
             usage = ?usage,
+            detected_language = ?detected_language,
             "Extraction complete"
         );
 
-        // Run metadata recorded alongside the extraction. Schema version is the
-        // part after `@` in a `name@version` reference (None for bare names or a
-        // trailing `@`). Latency/usage are `None` on cache hits. Conversions are
-        // fallible to avoid silent wraparound on absurd values.
-        let schema_version = schema_name
-            .rsplit_once('@')
-            .map(|(_, v)| v.to_string())
-            .filter(|v| !v.is_empty());
-        let latency_ms_i64 = latency_ms.and_then(|l| i64::try_from(l).ok());
-        let prompt_tokens = usage.and_then(|u| i32::try_from(u.prompt_tokens).ok());
-        let completion_tokens = usage.and_then(|u| i32::try_from(u.completion_tokens).ok());
+        let mut result = ScrapeResult {
+            extracted_data: extracted,
+            content_hash,
+            content_simhash,
+            data_hash,
+            changed: true,
+            extraction_id: None,
+            saved: None,
+            latency_ms,
+            usage,
+            duplicate_of: duplicate.map(|(original, _)| original),
+            detected_language,
+            raw_html: Some(html.clone()),
+        };
 
         // 5 & 6. Compare + Persist
-        let (changed, extraction_id) = if let Some(store) = &self.store {
-            let previous = store.get_latest(url, schema_name).await?;
-            let changed = match &previous {
-                Some(prev) => prev.data_hash != data_hash,
+        if let Some(store) = &self.store {
+            let mut previous = store.get_latest(url, schema_name).await?;
+            result.changed = match &previous {
+                Some(prev) => prev.data_hash != result.data_hash,
                 None => true,
             };
 
-            if self.skip_unchanged && !changed {
-                let prev_id = previous.map(|p| p.id);
-                tracing::info!(?prev_id, "Data unchanged — skipping save");
-                (false, prev_id)
+            let kept = if self.skip_unchanged && !result.changed {
+                let candidate = NewExtraction {
+                    provider: self.provider.clone(),
+                    prompt_hash: prompt_hash.clone(),
+                    schema_hash: Some(schema_hash.clone()),
+                    content_simhash: source_simhash.clone(),
+                    retention: retention.clone(),
+                    ..result.to_extraction(url, schema_name, &model)
+                };
+                let kept = store.keep_if_unchanged(&candidate).await?;
+                if kept.is_none() {
+                    // The latest extraction changed after it was read above,
+                    // so ours is saved like any other change, against the
+                    // extraction that replaced it.
+                    result.changed = true;
+                    previous = store.get_latest(url, schema_name).await?;
+                    tracing::info!("Data changed concurrently — saving new extraction");
+                }
+                kept
+            } else {
+                None
+            };
+
+            if let Some(kept) = kept {
+                if let SaveOutcome::Unchanged {
+                    existing_id,
+                    last_seen_bumped,
+                } = kept
+                {
+                    tracing::info!(%existing_id, last_seen_bumped, "Data unchanged — skipping save");
+                }
+                result.extraction_id = Some(kept.extraction_id());
+                result.saved = Some(kept);
             } else {
                 let new_extraction = NewExtraction {
-                    url: url.to_string(),
-                    schema_name: schema_name.to_string(),
-                    extracted_data: extracted.clone(),
-                    raw_content_hash: content_hash.clone(),
-                    data_hash: data_hash.clone(),
-                    model: self.model_name.clone(),
                     provider: self.provider.clone(),
-                    schema_version: schema_version.clone(),
-                    latency_ms: latency_ms_i64,
-                    prompt_tokens,
-                    completion_tokens,
+                    prompt_hash: prompt_hash.clone(),
+                    schema_hash: Some(schema_hash.clone()),
+                    content_simhash: source_simhash.clone(),
+                    html_snapshot_key: self.archive(SnapshotKind::Html, &html).await,
+                    markdown_snapshot_key: self.archive(SnapshotKind::Markdown, &markdown).await,
+                    retention: retention.clone(),
+                    ..result.to_extraction(url, schema_name, &model)
+                };
+
+                let id = if self.use_upsert {
+                    store.upsert(&new_extraction).await?
+                } else {
+                    let id = store.save(&new_extraction).await?;
+                    result.saved = Some(SaveOutcome::Inserted(id));
+                    id
                 };
 
-                let id = store.save(&new_extraction).await?;
+                if self.store_content {
+                    self.save_content(store, id, &markdown).await;
+                }
 
-                if changed {
-                    if previous.is_some() {
+                let changes = match &previous {
+                    Some(prev) if result.changed => {
+                        diff_json(&prev.extracted_data, &result.extracted_data)
+                    }
+                    _ => Vec::new(),
+                };
+                match &previous {
+                    Some(prev) if result.changed => {
                         tracing::info!(%id, "Data CHANGED — saved new extraction");
-                    } else {
-                        tracing::info!(%id, "First extraction — saved");
+                        let change = ChangeEvent {
+                            url: url.to_string(),
+                            schema_name: schema_name.to_string(),
+                            previous_extraction_id: prev.id,
+                            extraction_id: id,
+                            changes: changes.clone(),
+                            detected_at: Utc::now(),
+                        };
+                        self.notify(&change).await;
                     }
-                } else {
-                    tracing::info!(%id, "Data unchanged — saved snapshot");
+                    None => tracing::info!(%id, "First extraction — saved"),
+                    Some(_) => tracing::info!(%id, "Data unchanged — saved snapshot"),
                 }
 
-                (changed, Some(id))
+                let event = ExtractionEvent {
+                    extraction_id: id,
+                    previous_extraction_id: previous.as_ref().map(|prev| prev.id),
+                    extraction: new_extraction,
+                    changed: result.changed,
+                    changes,
+                    saved_at: Utc::now(),
+                };
+                self.publish(&event).await;
+
+                result.extraction_id = Some(id);
             }
+        }
+
+        Ok(result)
+    }
+
+    /// The extraction of another URL whose content `content_hash` duplicates,
+    /// with the extraction it was found through, when a dedup window is set.
+    ///
+    /// The earliest match in the window is the original, unless it is this
+    /// URL's own extraction; then this URL is the original, or shares its
+    /// original if that extraction was itself a duplicate.
+    async fn find_duplicate(
+        &self,
+        url: &str,
+        content_hash: &str,
+        schema_name: &str,
+    ) -> Result<Option<(Uuid, Extraction)>, AppError> {
+        let (Some(store), Some(window)) = (&self.store, self.dedup_window) else {
+            return Ok(None);
+        };
+        let since = chrono::Duration::from_std(window)
+            .ok()
+            .and_then(|window| Utc::now().checked_sub_signed(window))
+            .unwrap_or(DateTime::<Utc>::MIN_UTC);
+        let Some(found) = store
+            .find_by_content_hash(schema_name, content_hash, since)
+            .await?
+        else {
+            return Ok(None);
+        };
+        let original = if found.url == url {
+            found.duplicate_of
         } else {
-            (true, None)
+            Some(found.duplicate_of.unwrap_or(found.id))
         };
-
-        Ok(ScrapeResult {
-            extracted_data: extracted,
-            content_hash,
-            data_hash,
-            changed,
-            extraction_id,
-            latency_ms,
-            usage,
-            raw_html: Some(html),
-        })
+        if let Some(original) = original {
+            tracing::info!(%original, found_url = %found.url, "Content duplicates another URL");
+        }
+        Ok(original.map(|id| (id, found)))
     }
-}
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use crate::models::compute_hash;
-    use crate::testutil::*;
-    use crate::traits::NullStore;
+    /// The latest extraction of `url` with `schema_name`, when its content's
+    /// simhash is within the similarity threshold of `simhash`, it was made
+    /// with the same schema and prompt, and no retention policy has scrubbed
+    /// its data.
+    async fn find_similar(
+        &self,
+        url: &str,
+        simhash: &str,
+        schema_name: &str,
+        schema_hash: &str,
+        prompt_hash: Option<&str>,
+    ) -> Result<Option<Extraction>, AppError> {
+        let (Some(store), Some(threshold)) = (&self.store, self.content_similarity_threshold)
+        else {
+            return Ok(None);
+        };
+        let Some(previous) = store.get_latest(url, schema_name).await? else {
+            return Ok(None);
+        };
+        if previous.schema_hash.as_deref() != Some(schema_hash)
+            || previous.prompt_hash.as_deref() != prompt_hash
+            || previous.scrubbed_at.is_some()
+        {
+            return Ok(None);
+        }
+        let distance = previous
+            .content_simhash
+            .as_deref()
+            .and_then(|previous| simhash_distance(previous, simhash));
+        match distance {
+            Some(distance) if distance < threshold => {
+                tracing::info!(
+                    previous = %previous.id,
+                    distance,
+                    "Content similar to the previous extraction"
+                );
+                Ok(Some(previous))
+            }
+            _ => Ok(None),
+        }
+    }
 
-    fn test_schema() -> serde_json::Value {
-        serde_json::json!({"type": "object", "properties": {"title": {"type": "string"}}})
+    /// Send `change` to the notifier. A failed notification is logged and
+    /// otherwise ignored: the extraction is already saved.
+    async fn notify(&self, change: &ChangeEvent) {
+        if let Err(e) = self.notifier.notify(change).await {
+            tracing::warn!(
+                extraction_id = %change.extraction_id,
+                error = %e,
+                "Change notification failed"
+            );
+        }
     }
 
-    #[tokio::test]
-    async fn happy_path_without_store() {
-        let extracted = serde_json::json!({"title": "Hello"});
-        let svc = ScrapeService::<_, _, _, NullStore>::new(
-            MockFetcher::new("<html>hello</html>"),
-            MockCleaner::passthrough(),
-            MockExtractor::new(extracted.clone()),
-            "test-model".into(),
-        );
+    /// Store the cleaned content of extraction `id`. Like archiving, this is
+    /// best-effort: the extraction is already saved.
+    async fn save_content(&self, store: &S, id: Uuid, content: &str) {
+        if let Err(e) = store.save_content(id, content).await {
+            tracing::warn!(%id, error = %e, "Cleaned content not stored");
+        }
+    }
 
-        let result = svc
-            .scrape("https://example.com", &test_schema(), "test")
-            .await
-            .unwrap();
+    /// Store `content` as a `kind` snapshot, if that kind is archived, and
+    /// return its key. Archiving is best-effort: on failure the extraction
+    /// is saved without the key.
+    async fn archive(&self, kind: SnapshotKind, content: &str) -> Option<String> {
+        if !self.snapshot_kinds.contains(&kind) {
+            return None;
+        }
+        let key = kind.key(&compute_hash(content));
+        match self.snapshots.put(&key, content.as_bytes()).await {
+            Ok(()) => Some(key),
+            Err(e) => {
+                tracing::warn!(%key, error = %e, "Snapshot not archived");
+                None
+            }
+        }
+    }
 
-        assert_eq!(result.extracted_data, extracted);
-        assert!(result.changed);
-        assert!(result.extraction_id.is_none());
-        assert_eq!(result.content_hash.len(), 64);
-        assert_eq!(result.data_hash.len(), 64);
+    /// Publish `event`. Publishing is best-effort: a failure is logged and
+    /// otherwise ignored, like a failed notification.
+    async fn publish(&self, event: &ExtractionEvent) {
+        if let Err(e) = self.publisher.publish(event).await {
+            tracing::warn!(
+                extraction_id = %event.extraction_id,
+                error = %e,
+                "Extraction event not published"
+            );
+        }
     }
+}
 
-    #[tokio::test]
-    async fn happy_path_with_store_first_extraction() {
-        let extracted = serde_json::json!({"title": "Hello"});
-        let store = MockStore::empty();
-        let svc = ScrapeService::with_store(
-            MockFetcher::new("<html>hello</html>"),
-            MockCleaner::passthrough(),
-            MockExtractor::new(extracted.clone()),
-            store.clone(),
-            "test-model".into(),
-        );
+/// What [`ScrapeServiceBuilder::build`] returns.
+type BuildResult<F, C, E, S, N, P, A> = Result<ScrapeService<F, C, E, S, N, P, A>, AppError>;
 
-        let result = svc
-            .scrape("https://example.com", &test_schema(), "test")
-            .await
-            .unwrap();
+/// Builds a [`ScrapeService`] step by step.
+///
+/// The fetcher, cleaner and extractor are required by
+/// [`new`](Self::new); everything else is optional. [`build`](Self::build)
+/// rejects combinations that cannot work, such as skipping unchanged data
+/// without a store to compare against, with [`AppError::ConfigError`].
+///
+/// ```
+/// use ares_core::ScrapeServiceBuilder;
+/// use ares_core::testutil::{MockCleaner, MockExtractor, MockFetcher, MockStore};
+///
+/// let service = ScrapeServiceBuilder::new(
+///     MockFetcher::new("<h1>Hello</h1>"),
+///     MockCleaner::passthrough(),
+///     MockExtractor::new(serde_json::json!({ "title": "Hello" })),
+/// )
+/// .with_model("gpt-4o-mini")
+/// .with_store(MockStore::empty())
+/// .with_skip_unchanged(true)
+/// .build()
+/// .unwrap();
+/// # let _ = service;
+/// ```
+pub struct ScrapeServiceBuilder<
+    F,
+    C,
+    E,
+    S = NullStore,
+    N = NullNotifier,
+    P = NullPublisher,
+    A = NullSnapshotStore,
+> where
+    F: Fetcher,
+    C: Cleaner,
+    E: Extractor,
+    S: ExtractionStore,
+    N: ChangeNotifier,
+    P: ExtractionEventPublisher,
+    A: SnapshotStore,
+{
+    service: ScrapeService<F, C, E, S, N, P, A>,
+    /// As requested; the service itself clamps to at least 1.
+    batch_concurrency: usize,
+    has_notifier: bool,
+    has_publisher: bool,
+    has_snapshots: bool,
+}
 
-        assert!(result.changed);
-        assert!(result.extraction_id.is_some());
-        assert_eq!(store.saved.lock().unwrap().len(), 1);
+impl<F, C, E> ScrapeServiceBuilder<F, C, E>
+where
+    F: Fetcher,
+    C: Cleaner,
+    E: Extractor,
+{
+    pub fn new(fetcher: F, cleaner: C, extractor: E) -> Self {
+        Self {
+            service: ScrapeService::from_parts(fetcher, cleaner, extractor, None, String::new()),
+            batch_concurrency: DEFAULT_BATCH_CONCURRENCY,
+            has_notifier: false,
+            has_publisher: false,
+            has_snapshots: false,
+        }
     }
+}
 
-    #[tokio::test]
-    async fn with_store_same_data_hash_reports_unchanged() {
-        let extracted = serde_json::json!({"title": "Hello"});
-        let data_hash = compute_hash(&extracted.to_string());
-        let prev = make_test_extraction(&data_hash);
-        let store = MockStore::with_latest(prev);
-
-        let svc = ScrapeService::with_store(
-            MockFetcher::new("<html>hello</html>"),
-            MockCleaner::passthrough(),
-            MockExtractor::new(extracted),
-            store.clone(),
-            "test-model".into(),
-        );
+impl<F, C, E, S, N, P, A> ScrapeServiceBuilder<F, C, E, S, N, P, A>
+where
+    F: Fetcher,
+    C: Cleaner,
+    E: Extractor,
+    S: ExtractionStore,
+    N: ChangeNotifier,
+    P: ExtractionEventPublisher,
+    A: SnapshotStore,
+{
+    /// Persist extractions in `store` and compare each with the previous one.
+    pub fn with_store<S2: ExtractionStore>(
+        self,
+        store: S2,
+    ) -> ScrapeServiceBuilder<F, C, E, S2, N, P, A> {
+        ScrapeServiceBuilder {
+            service: self.service.replace_store(Some(store)),
+            batch_concurrency: self.batch_concurrency,
+            has_notifier: self.has_notifier,
+            has_publisher: self.has_publisher,
+            has_snapshots: self.has_snapshots,
+        }
+    }
 
-        let result = svc
-            .scrape("https://example.com", &test_schema(), "test")
-            .await
-            .unwrap();
+    /// The model name recorded on extractions. Required.
+    pub fn with_model(mut self, model_name: impl Into<String>) -> Self {
+        self.service.model_name = model_name.into();
+        self
+    }
+
+    /// See [`ScrapeService::with_provider`].
+    pub fn with_provider(mut self, provider: impl Into<String>) -> Self {
+        self.service = self.service.with_provider(provider);
+        self
+    }
+
+    /// See [`ScrapeService::with_skip_unchanged`]. Requires a store.
+    pub fn with_skip_unchanged(mut self, skip: bool) -> Self {
+        self.service = self.service.with_skip_unchanged(skip);
+        self
+    }
+
+    /// See [`ScrapeService::with_upsert`]. Requires a store.
+    pub fn with_upsert(mut self, upsert: bool) -> Self {
+        self.service = self.service.with_upsert(upsert);
+        self
+    }
+
+    /// See [`ScrapeService::with_store_content`]. Requires a store.
+    pub fn with_store_content(mut self, store: bool) -> Self {
+        self.service = self.service.with_store_content(store);
+        self
+    }
+
+    /// See [`ScrapeService::with_validation`].
+    pub fn with_validation(mut self, validate: bool) -> Self {
+        self.service = self.service.with_validation(validate);
+        self
+    }
+
+    /// See [`ScrapeService::with_schema_file`].
+    pub fn with_schema_file(mut self, schema_file: Option<ResolvedSchema>) -> Self {
+        self.service = self.service.with_schema_file(schema_file);
+        self
+    }
+
+    /// See [`ScrapeService::with_max_content_chars`]. The cap must be
+    /// positive.
+    pub fn with_max_content_chars(mut self, max: Option<usize>) -> Self {
+        self.service = self.service.with_max_content_chars(max);
+        self
+    }
+
+    /// See [`ScrapeService::with_fetch_options`].
+    pub fn with_fetch_options(mut self, options: FetchOptions) -> Self {
+        self.service = self.service.with_fetch_options(options);
+        self
+    }
+
+    /// See [`ScrapeService::with_batch_concurrency`]. Must be at least 1.
+    pub fn with_batch_concurrency(mut self, concurrency: usize) -> Self {
+        self.batch_concurrency = concurrency;
+        self
+    }
+
+    /// See [`ScrapeService::with_dedup_window`]. Requires a store; the window
+    /// must be positive.
+    pub fn with_dedup_window(mut self, window: Option<Duration>) -> Self {
+        self.service = self.service.with_dedup_window(window);
+        self
+    }
+
+    /// See [`ScrapeService::with_reuse_duplicates`]. Requires a dedup window.
+    pub fn with_reuse_duplicates(mut self, reuse: bool) -> Self {
+        self.service = self.service.with_reuse_duplicates(reuse);
+        self
+    }
+
+    /// See [`ScrapeService::with_content_similarity_threshold`]. Requires a
+    /// store; the threshold must be between 1 and 64.
+    pub fn with_content_similarity_threshold(mut self, threshold: Option<u32>) -> Self {
+        self.service = self.service.with_content_similarity_threshold(threshold);
+        self
+    }
+
+    /// See [`ScrapeService::with_caches`].
+    pub fn with_caches(
+        mut self,
+        content: Option<ContentCache>,
+        extraction: Option<ExtractionCache>,
+    ) -> Self {
+        self.service = self.service.with_caches(content, extraction);
+        self
+    }
+
+    /// See [`ScrapeService::with_notifier`]. Requires a store.
+    pub fn with_notifier<N2: ChangeNotifier>(
+        self,
+        notifier: N2,
+    ) -> ScrapeServiceBuilder<F, C, E, S, N2, P, A> {
+        ScrapeServiceBuilder {
+            service: self.service.with_notifier(notifier),
+            batch_concurrency: self.batch_concurrency,
+            has_notifier: true,
+            has_publisher: self.has_publisher,
+            has_snapshots: self.has_snapshots,
+        }
+    }
+
+    /// See [`ScrapeService::with_publisher`]. Requires a store.
+    pub fn with_publisher<P2: ExtractionEventPublisher>(
+        self,
+        publisher: P2,
+    ) -> ScrapeServiceBuilder<F, C, E, S, N, P2, A> {
+        ScrapeServiceBuilder {
+            service: self.service.with_publisher(publisher),
+            batch_concurrency: self.batch_concurrency,
+            has_notifier: self.has_notifier,
+            has_publisher: true,
+            has_snapshots: self.has_snapshots,
+        }
+    }
+
+    /// See [`ScrapeService::with_snapshots`]. Requires a store.
+    pub fn with_snapshots<A2: SnapshotStore>(
+        self,
+        store: A2,
+        kinds: &[SnapshotKind],
+    ) -> ScrapeServiceBuilder<F, C, E, S, N, P, A2> {
+        ScrapeServiceBuilder {
+            service: self.service.with_snapshots(store, kinds),
+            batch_concurrency: self.batch_concurrency,
+            has_notifier: self.has_notifier,
+            has_publisher: self.has_publisher,
+            has_snapshots: true,
+        }
+    }
+
+    /// The service, or a [`AppError::ConfigError`] naming the first option
+    /// that cannot work as configured.
+    pub fn build(self) -> BuildResult<F, C, E, S, N, P, A> {
+        let service = self.service;
+        if service.model_name.trim().is_empty() {
+            return Err(AppError::ConfigError(
+                "ScrapeService needs a model name (with_model)".into(),
+            ));
+        }
+        if service.max_content_chars == Some(0) {
+            return Err(AppError::ConfigError(
+                "max_content_chars must be positive; use None for no cap".into(),
+            ));
+        }
+        if self.batch_concurrency == 0 {
+            return Err(AppError::ConfigError(
+                "batch_concurrency must be at least 1".into(),
+            ));
+        }
+        if service.dedup_window.is_some_and(|window| window.is_zero()) {
+            return Err(AppError::ConfigError(
+                "dedup_window must be positive; use None to disable dedup".into(),
+            ));
+        }
+        if service.reuse_duplicates && service.dedup_window.is_none() {
+            return Err(AppError::ConfigError(
+                "reuse_duplicates requires a dedup window (with_dedup_window)".into(),
+            ));
+        }
+        if service
+            .content_similarity_threshold
+            .is_some_and(|threshold| !(1..=64).contains(&threshold))
+        {
+            return Err(AppError::ConfigError(
+                "content_similarity_threshold must be between 1 and 64; use None to disable it"
+                    .into(),
+            ));
+        }
+        if service.store.is_none() {
+            let needs_store = [
+                ("skip_unchanged", service.skip_unchanged),
+                ("upsert", service.use_upsert),
+                ("store_content", service.store_content),
+                ("a notifier", self.has_notifier),
+                ("a publisher", self.has_publisher),
+                ("snapshots", self.has_snapshots),
+                ("a dedup window", service.dedup_window.is_some()),
+                (
+                    "a content similarity threshold",
+                    service.content_similarity_threshold.is_some(),
+                ),
+            ];
+            if let Some((option, _)) = needs_store.iter().find(|(_, set)| *set) {
+                return Err(AppError::ConfigError(format!(
+                    "ScrapeService with {option} requires a store (with_store)"
+                )));
+            }
+        }
+        Ok(service.with_batch_concurrency(self.batch_concurrency))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{compute_hash, compute_json_hash};
+    use crate::snapshot::SnapshotKind;
+    use crate::testutil::*;
+    use crate::traits::NullStore;
+
+    fn test_schema() -> serde_json::Value {
+        serde_json::json!({"type": "object", "properties": {"title": {"type": "string"}}})
+    }
+
+    #[tokio::test]
+    async fn happy_path_without_store() {
+        let extracted = serde_json::json!({"title": "Hello"});
+        let svc = ScrapeService::<_, _, _, NullStore>::new(
+            MockFetcher::new("<html>hello</html>"),
+            MockCleaner::passthrough(),
+            MockExtractor::new(extracted.clone()),
+            "test-model".into(),
+        );
+
+        let result = svc
+            .scrape("https://example.com", &test_schema(), "test")
+            .await
+            .unwrap();
+
+        assert_eq!(result.extracted_data, extracted);
+        assert!(result.changed);
+        assert!(result.extraction_id.is_none());
+        assert_eq!(result.content_hash.len(), 64);
+        assert_eq!(result.data_hash.len(), 64);
+    }
+
+    #[tokio::test]
+    async fn happy_path_with_store_first_extraction() {
+        let extracted = serde_json::json!({"title": "Hello"});
+        let store = MockStore::empty();
+        let svc = ScrapeService::with_store(
+            MockFetcher::new("<html>hello</html>"),
+            MockCleaner::passthrough(),
+            MockExtractor::new(extracted.clone()),
+            store.clone(),
+            "test-model".into(),
+        );
+
+        let result = svc
+            .scrape("https://example.com", &test_schema(), "test")
+            .await
+            .unwrap();
+
+        assert!(result.changed);
+        assert!(result.extraction_id.is_some());
+        assert_eq!(store.saved.lock().unwrap().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn with_store_same_data_hash_reports_unchanged() {
+        let extracted = serde_json::json!({"title": "Hello"});
+        let data_hash = compute_json_hash(&extracted);
+        let prev = make_test_extraction(&data_hash);
+        let store = MockStore::with_latest(prev);
+
+        let svc = ScrapeService::with_store(
+            MockFetcher::new("<html>hello</html>"),
+            MockCleaner::passthrough(),
+            MockExtractor::new(extracted),
+            store.clone(),
+            "test-model".into(),
+        );
+
+        let result = svc
+            .scrape("https://example.com", &test_schema(), "test")
+            .await
+            .unwrap();
 
         assert!(!result.changed);
         assert!(result.extraction_id.is_some());
@@ -392,6 +1288,238 @@ mod tests {
         assert_eq!(store.saved.lock().unwrap().len(), 1);
     }
 
+    #[tokio::test]
+    async fn changed_data_notifies_with_the_field_diff() {
+        let prev = make_test_extraction("old_hash_that_wont_match");
+        let prev_id = prev.id;
+        let notifier = MockNotifier::new();
+        let svc = ScrapeService::with_store(
+            MockFetcher::new("<html>hello</html>"),
+            MockCleaner::passthrough(),
+            MockExtractor::new(serde_json::json!({"title": "New Title"})),
+            MockStore::with_latest(prev),
+            "test-model".into(),
+        )
+        .with_notifier(notifier.clone());
+
+        let result = svc
+            .scrape("https://example.com", &test_schema(), "test")
+            .await
+            .unwrap();
+
+        let events = notifier.events.lock().unwrap();
+        assert_eq!(events.len(), 1);
+        let event = &events[0];
+        assert_eq!(event.url, "https://example.com");
+        assert_eq!(event.schema_name, "test");
+        assert_eq!(event.previous_extraction_id, prev_id);
+        assert_eq!(Some(event.extraction_id), result.extraction_id);
+        assert_eq!(
+            event.changes,
+            [crate::diff::JsonChange::Changed {
+                path: "title".into(),
+                old: serde_json::json!("Test"),
+                new: serde_json::json!("New Title"),
+            }]
+        );
+    }
+
+    #[tokio::test]
+    async fn unchanged_and_first_extractions_do_not_notify() {
+        let extracted = serde_json::json!({"title": "Hello"});
+        let data_hash = compute_json_hash(&extracted);
+        for store in [
+            MockStore::empty(),
+            MockStore::with_latest(make_test_extraction(&data_hash)),
+        ] {
+            let notifier = MockNotifier::new();
+            ScrapeService::with_store(
+                MockFetcher::new("<html>hello</html>"),
+                MockCleaner::passthrough(),
+                MockExtractor::new(extracted.clone()),
+                store,
+                "test-model".into(),
+            )
+            .with_notifier(notifier.clone())
+            .scrape("https://example.com", &test_schema(), "test")
+            .await
+            .unwrap();
+            assert!(notifier.events.lock().unwrap().is_empty());
+        }
+    }
+
+    #[tokio::test]
+    async fn failed_notification_does_not_fail_the_scrape() {
+        let store = MockStore::with_latest(make_test_extraction("old_hash_that_wont_match"));
+        let notifier = MockNotifier::failing();
+        let result = ScrapeService::with_store(
+            MockFetcher::new("<html>hello</html>"),
+            MockCleaner::passthrough(),
+            MockExtractor::new(serde_json::json!({"title": "New Title"})),
+            store.clone(),
+            "test-model".into(),
+        )
+        .with_notifier(notifier.clone())
+        .scrape("https://example.com", &test_schema(), "test")
+        .await
+        .unwrap();
+
+        assert!(result.changed);
+        assert_eq!(notifier.events.lock().unwrap().len(), 1);
+        assert_eq!(store.saved.lock().unwrap().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn saved_extractions_are_published_with_the_diff() {
+        let prev = make_test_extraction("old_hash_that_wont_match");
+        let prev_id = prev.id;
+        let publisher = MockPublisher::new();
+        let result = ScrapeService::with_store(
+            MockFetcher::new("<html>hello</html>"),
+            MockCleaner::passthrough(),
+            MockExtractor::new(serde_json::json!({"title": "New Title"})),
+            MockStore::with_latest(prev),
+            "test-model".into(),
+        )
+        .with_publisher(publisher.clone())
+        .scrape("https://example.com", &test_schema(), "test")
+        .await
+        .unwrap();
+
+        let events = publisher.events.lock().unwrap();
+        assert_eq!(events.len(), 1);
+        let event = &events[0];
+        assert_eq!(Some(event.extraction_id), result.extraction_id);
+        assert_eq!(event.previous_extraction_id, Some(prev_id));
+        assert_eq!(event.extraction.schema_name, "test");
+        assert_eq!(event.extraction.extracted_data["title"], "New Title");
+        assert!(event.changed);
+        assert_eq!(event.changes.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn failed_publish_does_not_fail_the_scrape() {
+        let publisher = MockPublisher::failing();
+        let store = MockStore::empty();
+        let result = ScrapeService::with_store(
+            MockFetcher::new("<html>hello</html>"),
+            MockCleaner::passthrough(),
+            MockExtractor::new(serde_json::json!({"title": "Hello"})),
+            store.clone(),
+            "test-model".into(),
+        )
+        .with_publisher(publisher.clone())
+        .scrape("https://example.com", &test_schema(), "test")
+        .await
+        .unwrap();
+
+        assert!(result.extraction_id.is_some());
+        assert_eq!(store.saved.lock().unwrap().len(), 1);
+        let events = publisher.events.lock().unwrap();
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].previous_extraction_id, None);
+        assert!(events[0].changes.is_empty());
+    }
+
+    #[tokio::test]
+    async fn cleaned_content_is_stored_only_when_enabled() {
+        let service = |store: MockStore| {
+            ScrapeService::with_store(
+                MockFetcher::new("<html>hello</html>"),
+                MockCleaner::passthrough(),
+                MockExtractor::new(serde_json::json!({"title": "Hello"})),
+                store,
+                "test-model".into(),
+            )
+        };
+
+        let store = MockStore::empty();
+        let result = service(store.clone())
+            .with_store_content(true)
+            .scrape("https://example.com", &test_schema(), "test")
+            .await
+            .unwrap();
+        assert_eq!(
+            *store.contents.lock().unwrap(),
+            [(
+                result.extraction_id.unwrap(),
+                "<html>hello</html>".to_string()
+            )]
+        );
+
+        let store = MockStore::empty();
+        service(store.clone())
+            .scrape("https://example.com", &test_schema(), "test")
+            .await
+            .unwrap();
+        assert!(store.contents.lock().unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn snapshots_are_archived_and_keyed_on_the_extraction() {
+        let snapshots = MockSnapshotStore::new();
+        let store = MockStore::empty();
+        ScrapeService::with_store(
+            MockFetcher::new("<html>hello</html>"),
+            MockCleaner::passthrough(),
+            MockExtractor::new(serde_json::json!({"title": "Hello"})),
+            store.clone(),
+            "test-model".into(),
+        )
+        .with_snapshots(snapshots.clone(), &SnapshotKind::ALL)
+        .scrape("https://example.com", &test_schema(), "test")
+        .await
+        .unwrap();
+
+        let saved = store.saved.lock().unwrap();
+        let html_key = saved[0].html_snapshot_key.clone().unwrap();
+        let markdown_key = saved[0].markdown_snapshot_key.clone().unwrap();
+        assert_eq!(
+            html_key,
+            SnapshotKind::Html.key(&compute_hash("<html>hello</html>"))
+        );
+        assert!(markdown_key.starts_with("markdown/"), "{markdown_key}");
+        let objects = snapshots.objects.lock().unwrap();
+        assert_eq!(objects[&html_key], b"<html>hello</html>");
+        assert_eq!(objects[&markdown_key], b"<html>hello</html>");
+    }
+
+    #[tokio::test]
+    async fn snapshots_are_limited_to_the_chosen_kinds_and_best_effort() {
+        let snapshots = MockSnapshotStore::new();
+        let store = MockStore::empty();
+        ScrapeService::with_store(
+            MockFetcher::new("<html>hello</html>"),
+            MockCleaner::passthrough(),
+            MockExtractor::new(serde_json::json!({"title": "Hello"})),
+            store.clone(),
+            "test-model".into(),
+        )
+        .with_snapshots(snapshots.clone(), &[SnapshotKind::Markdown])
+        .scrape("https://example.com", &test_schema(), "test")
+        .await
+        .unwrap();
+        assert_eq!(store.saved.lock().unwrap()[0].html_snapshot_key, None);
+        assert_eq!(snapshots.objects.lock().unwrap().len(), 1);
+
+        let store = MockStore::empty();
+        let result = ScrapeService::with_store(
+            MockFetcher::new("<html>hello</html>"),
+            MockCleaner::passthrough(),
+            MockExtractor::new(serde_json::json!({"title": "Hello"})),
+            store.clone(),
+            "test-model".into(),
+        )
+        .with_snapshots(MockSnapshotStore::failing(), &SnapshotKind::ALL)
+        .scrape("https://example.com", &test_schema(), "test")
+        .await
+        .unwrap();
+        assert!(result.extraction_id.is_some());
+        let saved = store.saved.lock().unwrap();
+        assert_eq!(saved[0].html_snapshot_key, None);
+        assert_eq!(saved[0].markdown_snapshot_key, None);
+    }
+
     #[tokio::test]
     async fn with_store_different_data_hash_reports_changed() {
         let prev = make_test_extraction("old_hash_that_wont_match");
@@ -471,7 +1599,7 @@ mod tests {
     #[tokio::test]
     async fn skip_unchanged_skips_save() {
         let extracted = serde_json::json!({"title": "Hello"});
-        let data_hash = compute_hash(&extracted.to_string());
+        let data_hash = compute_json_hash(&extracted);
         let prev = make_test_extraction(&data_hash);
         let prev_id = prev.id;
         let store = MockStore::with_latest(prev);
@@ -492,20 +1620,63 @@ mod tests {
 
         assert!(!result.changed);
         assert_eq!(result.extraction_id, Some(prev_id));
-        assert_eq!(store.saved.lock().unwrap().len(), 0);
+        assert_eq!(
+            result.saved,
+            Some(SaveOutcome::Unchanged {
+                existing_id: prev_id,
+                last_seen_bumped: false,
+            })
+        );
+        assert_eq!(store.save_count(), 0);
+    }
+
+    #[tokio::test]
+    async fn skip_unchanged_saves_data_changed_concurrently() {
+        let extracted = serde_json::json!({"title": "Hello"});
+        let data_hash = compute_json_hash(&extracted);
+        let store = MockStore::with_latest(make_test_extraction(&data_hash))
+            .with_concurrent_write(make_test_extraction("other-hash"));
+        let notifier = MockNotifier::new();
+
+        let svc = ScrapeService::with_store(
+            MockFetcher::new("<html>hello</html>"),
+            MockCleaner::passthrough(),
+            MockExtractor::new(extracted),
+            store.clone(),
+            "test-model".into(),
+        )
+        .with_skip_unchanged(true)
+        .with_store_content(true)
+        .with_notifier(notifier.clone());
+
+        let result = svc
+            .scrape("https://example.com", &test_schema(), "test")
+            .await
+            .unwrap();
+
+        assert!(result.changed);
+        assert!(result.extraction_id.is_some());
+        assert_eq!(
+            result.saved,
+            result.extraction_id.map(SaveOutcome::Inserted)
+        );
+        assert_eq!(store.save_count(), 1);
+        assert_eq!(store.contents.lock().unwrap().len(), 1);
+        assert_eq!(notifier.events.lock().unwrap().len(), 1);
     }
 
     #[tokio::test]
     async fn skip_unchanged_false_still_saves_snapshot() {
         let extracted = serde_json::json!({"title": "Hello"});
-        let data_hash = compute_hash(&extracted.to_string());
+        let data_hash = compute_json_hash(&extracted);
         let prev = make_test_extraction(&data_hash);
         let store = MockStore::with_latest(prev);
+        let extractor = MockExtractor::new(extracted);
 
         let svc = ScrapeService::with_store(
             MockFetcher::new("<html>hello</html>"),
             MockCleaner::passthrough(),
-            MockExtractor::new(extracted),
+            extractor.clone(),
             store.clone(),
             "test-model".into(),
         )
@@ -518,7 +1689,40 @@ mod tests {
 
         assert!(!result.changed);
         assert!(result.extraction_id.is_some());
-        assert_eq!(store.saved.lock().unwrap().len(), 1);
+        assert_eq!(
+            result.saved,
+            result.extraction_id.map(SaveOutcome::Inserted)
+        );
+        assert_eq!(store.save_count(), 1);
+        assert_eq!(extractor.call_count(), 1);
+    }
+
+    #[tokio::test]
+    async fn upsert_updates_existing_record_instead_of_saving() {
+        let extracted = serde_json::json!({"title": "Hello"});
+        let data_hash = compute_json_hash(&extracted);
+        let prev = make_test_extraction(&data_hash);
+        let prev_id = prev.id;
+        let store = MockStore::with_latest(prev);
+
+        let svc = ScrapeService::with_store(
+            MockFetcher::new("<html>hello</html>"),
+            MockCleaner::passthrough(),
+            MockExtractor::new(extracted),
+            store.clone(),
+            "test-model".into(),
+        )
+        .with_upsert(true);
+
+        let result = svc
+            .scrape("https://example.com", &test_schema(), "test")
+            .await
+            .unwrap();
+
+        assert!(!result.changed);
+        assert_eq!(result.extraction_id, Some(prev_id));
+        assert_eq!(store.upserted.lock().unwrap().len(), 1);
+        assert_eq!(store.saved.lock().unwrap().len(), 0);
     }
 
     #[tokio::test]
@@ -634,17 +1838,138 @@ mod tests {
             .unwrap();
 
         let saved = store.saved.lock().unwrap();
-        assert_eq!(saved.len(), 1);
-        let ne = &saved[0];
-        assert_eq!(ne.provider, "anthropic");
-        assert_eq!(ne.model, "claude-haiku-4-5");
-        // schema_version is parsed from the `name@version` schema_name.
-        assert_eq!(ne.schema_version.as_deref(), Some("1.0.0"));
-        // A real extractor call records latency.
-        assert!(ne.latency_ms.is_some());
-        // MockExtractor reports no usage → token counts are None.
-        assert!(ne.prompt_tokens.is_none());
-        assert!(ne.completion_tokens.is_none());
+        assert_eq!(saved.len(), 1);
+        let ne = &saved[0];
+        assert_eq!(ne.provider, "anthropic");
+        assert_eq!(ne.model, "claude-haiku-4-5");
+        // schema_version is parsed from the `name@version` schema_name.
+        assert_eq!(ne.schema_version.as_deref(), Some("1.0.0"));
+        // A real extractor call records latency.
+        assert!(ne.latency_ms.is_some());
+        // MockExtractor reports no usage → token counts are None.
+        assert!(ne.prompt_tokens.is_none());
+        assert!(ne.completion_tokens.is_none());
+    }
+
+    #[tokio::test]
+    async fn detected_language_is_recorded_on_the_result_and_row() {
+        let store = MockStore::empty();
+        let svc = ScrapeService::with_store(
+            MockFetcher::new(
+                "Der Stadtrat hat am Dienstagabend nach einer langen Debatte über den öffentlichen Nahverkehr und die Finanzierung der Schulen den neuen Haushalt beschlossen. Die Bürger waren mit dem Ergebnis zufrieden.",
+            ),
+            MockCleaner::passthrough(),
+            MockExtractor::new(serde_json::json!({"title": "Budget approved"})),
+            store.clone(),
+            "gpt-4o-mini".into(),
+        );
+
+        let result = svc
+            .scrape("https://example.de", &test_schema(), "blog")
+            .await
+            .unwrap();
+        assert_eq!(result.detected_language.as_deref(), Some("deu"));
+        assert_eq!(
+            store.saved.lock().unwrap()[0].detected_language.as_deref(),
+            Some("deu")
+        );
+
+        // Too little text to tell: nothing is recorded rather than a guess.
+        let result = ScrapeService::<_, _, _, NullStore>::new(
+            MockFetcher::new("<html>hello</html>"),
+            MockCleaner::passthrough(),
+            MockExtractor::new(serde_json::json!({"title": "Hello"})),
+            "gpt-4o-mini".into(),
+        )
+        .scrape("https://example.com", &test_schema(), "blog")
+        .await
+        .unwrap();
+        assert_eq!(result.detected_language, None);
+    }
+
+    #[tokio::test]
+    async fn retention_policy_is_recorded_and_scrub_on_save_applied() {
+        let schema = serde_json::json!({
+            "type": "object",
+            "x-ares-retention": {
+                "days": 30,
+                "scrub_fields": ["/author/email"],
+                "scrub_on_save": true
+            }
+        });
+        let store = MockStore::empty();
+        let svc = ScrapeService::with_store(
+            MockFetcher::new("<html>hello</html>"),
+            MockCleaner::passthrough(),
+            MockExtractor::new(serde_json::json!({
+                "title": "Hello",
+                "author": {"name": "Ada", "email": "ada@example.com"}
+            })),
+            store.clone(),
+            "gpt-4o-mini".into(),
+        );
+
+        let result = svc
+            .scrape("https://example.com", &schema, "blog")
+            .await
+            .unwrap();
+
+        let scrubbed = serde_json::json!({
+            "title": "Hello",
+            "author": {"name": "Ada", "email": null}
+        });
+        assert_eq!(result.extracted_data, scrubbed);
+        assert_eq!(result.data_hash, compute_json_hash(&scrubbed));
+        let saved = store.saved.lock().unwrap();
+        assert_eq!(saved[0].extracted_data, scrubbed);
+        assert_eq!(
+            saved[0].retention,
+            RetentionPolicy::from_schema(&schema).unwrap()
+        );
+    }
+
+    #[tokio::test]
+    async fn malformed_retention_fails_before_extraction() {
+        let extractor = MockExtractor::new(serde_json::json!({"title": "Hello"}));
+        let svc = ScrapeService::<_, _, _, NullStore>::new(
+            MockFetcher::new("<html>hello</html>"),
+            MockCleaner::passthrough(),
+            extractor.clone(),
+            "gpt-4o-mini".into(),
+        );
+
+        let schema = serde_json::json!({"type": "object", "x-ares-retention": {"days": 0}});
+        let err = svc
+            .scrape("https://example.com", &schema, "blog")
+            .await
+            .unwrap_err();
+        assert!(matches!(err, AppError::SchemaError(_)), "{err}");
+        assert_eq!(extractor.call_count(), 0);
+    }
+
+    #[tokio::test]
+    async fn records_answering_model_and_prompt_hash() {
+        let store = MockStore::empty();
+        let svc = ScrapeService::with_store(
+            MockFetcher::new("<html>hello</html>"),
+            MockCleaner::passthrough(),
+            MockExtractor::new(serde_json::json!({"title": "Hello"}))
+                .with_fingerprint("Extract the title.")
+                .with_reported_model("gpt-4o-mini-2024-07-18"),
+            store.clone(),
+            "gpt-4o-mini".into(),
+        );
+
+        svc.scrape("https://example.com", &test_schema(), "blog")
+            .await
+            .unwrap();
+
+        let saved = store.saved.lock().unwrap();
+        assert_eq!(saved[0].model, "gpt-4o-mini-2024-07-18");
+        assert_eq!(
+            saved[0].prompt_hash.as_deref(),
+            Some(compute_hash("Extract the title.").as_str())
+        );
     }
 
     #[tokio::test]
@@ -706,10 +2031,10 @@ mod tests {
         let content_cache = crate::cache::ContentCache::new(&config);
         let extraction_cache = crate::cache::ExtractionCache::new(&config);
 
-        // MockFetcher with only ONE response — second call would return default
         let extracted = serde_json::json!({"title": "Hello"});
+        let fetcher = MockFetcher::new("<html>hello</html>");
         let svc = ScrapeService::<_, _, _, NullStore>::new(
-            MockFetcher::new("<html>hello</html>"),
+            fetcher.clone(),
             MockCleaner::passthrough(),
             MockExtractor::with_responses(vec![Ok(extracted.clone()), Ok(extracted.clone())]),
             "test-model".into(),
@@ -723,12 +2048,13 @@ mod tests {
             .unwrap();
         assert_eq!(r1.extracted_data, extracted);
 
-        // Second scrape — should use content cache (MockFetcher has no more responses)
+        // Second scrape — should use content cache
         let r2 = svc
             .scrape("https://example.com", &test_schema(), "test")
             .await
             .unwrap();
         assert_eq!(r2.extracted_data, extracted);
+        assert_eq!(fetcher.fetch_count(), 1);
         // Same content means same content hash
         assert_eq!(r1.content_hash, r2.content_hash);
 
@@ -738,21 +2064,65 @@ mod tests {
         assert!(r2.usage.is_none(), "cache hit must not report usage");
     }
 
+    #[tokio::test]
+    async fn content_cache_skipped_for_authenticated_fetches() {
+        let config = test_cache_config();
+        let content_cache = crate::cache::ContentCache::new(&config);
+        let extracted = serde_json::json!({"title": "Hello"});
+        let fetcher = MockFetcher::new("<html>hello</html>");
+        let service = |options: FetchOptions| {
+            ScrapeService::<_, _, _, NullStore>::new(
+                fetcher.clone(),
+                MockCleaner::passthrough(),
+                MockExtractor::new(extracted.clone()),
+                "test-model".into(),
+            )
+            .with_caches(Some(content_cache.clone()), None)
+            .with_fetch_options(options)
+        };
+
+        let authed = service(FetchOptions {
+            auth: Some(crate::fetch::FetchAuth::Bearer {
+                token: "s3cret".into(),
+            }),
+            ..FetchOptions::default()
+        });
+        let with_headers = service(FetchOptions {
+            headers: [("Cookie".to_string(), "session=1".to_string())].into(),
+            ..FetchOptions::default()
+        });
+        let anonymous = service(FetchOptions::default());
+
+        for svc in [&authed, &with_headers, &authed, &anonymous] {
+            svc.scrape("https://example.com", &test_schema(), "test")
+                .await
+                .unwrap();
+        }
+        // Every personalised fetch went to the network, and none of them
+        // seeded the cache for the anonymous one.
+        assert_eq!(fetcher.fetch_count(), 4);
+        anonymous
+            .scrape("https://example.com", &test_schema(), "test")
+            .await
+            .unwrap();
+        assert_eq!(fetcher.fetch_count(), 4);
+    }
+
     #[tokio::test]
     async fn extraction_cache_avoids_second_llm_call() {
         let config = test_cache_config();
         let content_cache = crate::cache::ContentCache::new(&config);
         let extraction_cache = crate::cache::ExtractionCache::new(&config);
 
-        // MockExtractor with only ONE response — second call would return default
         let extracted = serde_json::json!({"title": "Hello"});
+        let extractor = MockExtractor::new(extracted.clone());
         let svc = ScrapeService::<_, _, _, NullStore>::new(
             MockFetcher::with_responses(vec![
                 Ok("<html>hello</html>".into()),
                 Ok("<html>hello</html>".into()),
             ]),
             MockCleaner::passthrough(),
-            MockExtractor::new(extracted.clone()),
+            extractor.clone(),
             "test-model".into(),
         )
         .with_caches(Some(content_cache), Some(extraction_cache));
@@ -771,6 +2141,7 @@ mod tests {
             .await
             .unwrap();
         assert_eq!(r2.extracted_data, extracted);
+        assert_eq!(extractor.call_count(), 1);
 
         // Extraction cache hit on the second run → no latency recorded.
         assert!(r1.latency_ms.is_some());
@@ -780,11 +2151,12 @@ mod tests {
     #[tokio::test]
     async fn no_cache_calls_fetcher_every_time() {
         let extracted = serde_json::json!({"title": "Hello"});
+        let fetcher = MockFetcher::with_responses(vec![
+            Ok("<html>first</html>".into()),
+            Ok("<html>second</html>".into()),
+        ]);
         let svc = ScrapeService::<_, _, _, NullStore>::new(
-            MockFetcher::with_responses(vec![
-                Ok("<html>first</html>".into()),
-                Ok("<html>second</html>".into()),
-            ]),
+            fetcher.clone(),
             MockCleaner::passthrough(),
             MockExtractor::with_responses(vec![
                 Ok(extracted.clone()),
@@ -810,5 +2182,634 @@ mod tests {
         // Both runs hit the real extractor → both record latency.
         assert!(r1.latency_ms.is_some());
         assert!(r2.latency_ms.is_some());
+        assert_eq!(fetcher.fetch_count(), 2);
+    }
+
+    #[tokio::test]
+    async fn batch_parallel_yields_every_url_including_failures() {
+        let fetcher = MockFetcher::with_responses(vec![
+            Ok("<html>a</html>".into()),
+            Err(AppError::HttpError("HTTP 404".into())),
+            Ok("<html>c</html>".into()),
+        ]);
+        let svc = ScrapeService::<_, _, _, NullStore>::new(
+            fetcher.clone(),
+            MockCleaner::passthrough(),
+            MockExtractor::new(serde_json::json!({"title": "Hello"})),
+            "test-model".into(),
+        )
+        .with_batch_concurrency(1);
+
+        let urls = ["https://a.com", "https://b.com", "https://c.com"].map(String::from);
+        let results: Vec<_> = svc
+            .scrape_batch_parallel(urls.clone(), &test_schema(), "test")
+            .collect()
+            .await;
+
+        // With one page in flight, completion order is input order.
+        let order: Vec<_> = results.iter().map(|(url, _)| url.clone()).collect();
+        assert_eq!(order, urls);
+        assert!(results[0].1.is_ok());
+        assert!(matches!(results[1].1, Err(AppError::HttpError(_))));
+        assert!(results[2].1.is_ok());
+        assert_eq!(fetcher.fetch_count(), 3);
+    }
+
+    #[tokio::test]
+    async fn batch_parallel_yields_fast_pages_first() {
+        let svc = ScrapeService::<_, _, _, NullStore>::new(
+            DelayedFetcher,
+            MockCleaner::passthrough(),
+            MockExtractor::with_responses(vec![]),
+            "test-model".into(),
+        );
+
+        let urls = ["https://slow.com", "https://fast.com"].map(String::from);
+        let order: Vec<_> = svc
+            .scrape_batch_parallel(urls, &test_schema(), "test")
+            .map(|(url, _)| url)
+            .collect()
+            .await;
+
+        assert_eq!(order, ["https://fast.com", "https://slow.com"]);
+    }
+
+    /// Fetcher that takes longer for URLs containing "slow".
+    #[derive(Clone)]
+    struct DelayedFetcher;
+
+    impl Fetcher for DelayedFetcher {
+        async fn fetch(&self, url: &str) -> Result<String, AppError> {
+            let delay = if url.contains("slow") { 50 } else { 0 };
+            tokio::time::sleep(std::time::Duration::from_millis(delay)).await;
+            Ok(format!("<html>{url}</html>"))
+        }
+    }
+
+    #[tokio::test]
+    async fn scrape_html_skips_fetch_and_keys_by_label_url() {
+        let store = MockStore::empty();
+        let svc = ScrapeService::with_store(
+            // Any fetch would fail the test.
+            MockFetcher::with_error(AppError::NetworkError("should not fetch".into())),
+            MockCleaner::passthrough(),
+            MockExtractor::new(serde_json::json!({"title": "Hello"})),
+            store.clone(),
+            "test-model".into(),
+        );
+
+        let result = svc
+            .scrape_html(
+                "<html>hello</html>",
+                "https://example.com/label",
+                &test_schema(),
+                "test",
+            )
+            .await
+            .unwrap();
+
+        assert!(result.changed);
+        assert_eq!(result.content_hash, compute_hash("<html>hello</html>"));
+        let saved = store.saved.lock().unwrap();
+        assert_eq!(saved.len(), 1);
+        assert_eq!(saved[0].url, "https://example.com/label");
+        assert_eq!(saved[0].schema_name, "test");
+    }
+
+    #[tokio::test]
+    async fn scrape_html_detects_changes_across_submissions() {
+        let store = MockStore::tracking();
+        let svc = ScrapeService::with_store(
+            MockFetcher::with_error(AppError::NetworkError("should not fetch".into())),
+            MockCleaner::passthrough(),
+            MockExtractor::with_responses(vec![
+                Ok(serde_json::json!({"title": "Hello"})),
+                Ok(serde_json::json!({"title": "Hello"})),
+                Ok(serde_json::json!({"title": "World"})),
+            ]),
+            store.clone(),
+            "test-model".into(),
+        );
+        let url = "https://example.com/label";
+
+        let first = svc
+            .scrape_html("<html>hello</html>", url, &test_schema(), "test")
+            .await
+            .unwrap();
+        let same = svc
+            .scrape_html("<html>hello</html>", url, &test_schema(), "test")
+            .await
+            .unwrap();
+        let changed = svc
+            .scrape_html("<html>world</html>", url, &test_schema(), "test")
+            .await
+            .unwrap();
+
+        assert!(first.changed);
+        assert!(!same.changed);
+        assert!(changed.changed);
+        assert_eq!(store.saved.lock().unwrap().len(), 3);
+    }
+
+    #[tokio::test]
+    async fn content_selector_cleans_within_selection() {
+        let svc = ScrapeService::<_, _, _, NullStore>::new(
+            MockFetcher::new("<html>hello</html>"),
+            MockCleaner::passthrough(),
+            MockExtractor::new(serde_json::json!({"title": "Hello"})),
+            "test-model".into(),
+        )
+        .with_fetch_options(FetchOptions {
+            content_selector: Some("main".into()),
+            ..FetchOptions::default()
+        });
+
+        // The mock cleaner doesn't support selectors, so reaching it proves
+        // the selector path was taken instead of a plain clean.
+        let err = svc
+            .scrape("https://example.com", &test_schema(), "test")
+            .await
+            .unwrap_err();
+        assert!(matches!(err, AppError::CleanerError(msg) if msg.contains("not supported")));
+    }
+
+    /// An extraction of `url` made from `html` `age` ago.
+    fn prior_extraction(url: &str, html: &str, age: chrono::Duration) -> Extraction {
+        Extraction {
+            url: url.to_string(),
+            schema_name: "test".to_string(),
+            extracted_data: serde_json::json!({"title": "From the mirror"}),
+            content_hash: compute_hash(html),
+            created_at: Utc::now() - age,
+            ..make_test_extraction("prior")
+        }
+    }
+
+    /// A service scraping `<h1>Hello</h1>` that dedups within a day.
+    fn dedup_service(
+        store: MockStore,
+        extractor: MockExtractor,
+        reuse: bool,
+    ) -> ScrapeService<MockFetcher, MockCleaner, MockExtractor, MockStore> {
+        ScrapeServiceBuilder::new(
+            MockFetcher::new("<h1>Hello</h1>"),
+            MockCleaner::passthrough(),
+            extractor,
+        )
+        .with_model("test-model")
+        .with_store(store)
+        .with_dedup_window(Some(Duration::from_secs(24 * 3600)))
+        .with_reuse_duplicates(reuse)
+        .build()
+        .unwrap()
+    }
+
+    #[tokio::test]
+    async fn duplicate_content_of_another_url_is_linked_and_saved() {
+        let prior = prior_extraction(
+            "https://mirror.example.com",
+            "<h1>Hello</h1>",
+            chrono::Duration::hours(1),
+        );
+        let store = MockStore::empty().with_prior(prior.clone());
+        let extractor = MockExtractor::new(serde_json::json!({"title": "Hello"}));
+        let svc = dedup_service(store.clone(), extractor.clone(), false);
+
+        let result = svc
+            .scrape("https://example.com?utm_source=x", &test_schema(), "test")
+            .await
+            .unwrap();
+
+        assert_eq!(result.duplicate_of, Some(prior.id));
+        assert_eq!(extractor.call_count(), 1);
+        assert_eq!(result.extracted_data, serde_json::json!({"title": "Hello"}));
+        let saved = store.saved.lock().unwrap();
+        assert_eq!(saved[0].duplicate_of, Some(prior.id));
+    }
+
+    #[tokio::test]
+    async fn reused_duplicates_skip_the_llm_and_copy_the_data() {
+        let mut prior = prior_extraction(
+            "https://mirror.example.com",
+            "<h1>Hello</h1>",
+            chrono::Duration::hours(1),
+        );
+        prior.model = "original-model".to_string();
+        let store = MockStore::empty().with_prior(prior.clone());
+        let extractor = MockExtractor::new(serde_json::json!({"title": "Hello"}));
+        let svc = dedup_service(store.clone(), extractor.clone(), true);
+
+        let result = svc
+            .scrape("https://example.com", &test_schema(), "test")
+            .await
+            .unwrap();
+
+        assert_eq!(extractor.call_count(), 0);
+        assert_eq!(result.extracted_data, prior.extracted_data);
+        assert_eq!(result.duplicate_of, Some(prior.id));
+        assert!(result.latency_ms.is_none() && result.usage.is_none());
+        let saved = store.saved.lock().unwrap();
+        assert_eq!(saved[0].extracted_data, prior.extracted_data);
+        assert_eq!(saved[0].model, "original-model");
+        assert_eq!(saved[0].duplicate_of, Some(prior.id));
+    }
+
+    #[tokio::test]
+    async fn scrubbed_duplicates_are_linked_but_not_reused() {
+        let mut prior = prior_extraction(
+            "https://mirror.example.com",
+            "<h1>Hello</h1>",
+            chrono::Duration::hours(1),
+        );
+        prior.scrubbed_at = Some(Utc::now());
+        let store = MockStore::empty().with_prior(prior.clone());
+        let extractor = MockExtractor::new(serde_json::json!({"title": "Hello"}));
+        let svc = dedup_service(store.clone(), extractor.clone(), true);
+
+        let result = svc
+            .scrape("https://example.com", &test_schema(), "test")
+            .await
+            .unwrap();
+
+        assert_eq!(extractor.call_count(), 1);
+        assert_eq!(result.extracted_data, serde_json::json!({"title": "Hello"}));
+        assert_eq!(result.duplicate_of, Some(prior.id));
+    }
+
+    #[tokio::test]
+    async fn duplicates_link_to_the_original_not_to_another_duplicate() {
+        let original = Uuid::new_v4();
+        let mut prior = prior_extraction(
+            "https://mirror.example.com",
+            "<h1>Hello</h1>",
+            chrono::Duration::hours(1),
+        );
+        prior.duplicate_of = Some(original);
+        let store = MockStore::empty().with_prior(prior);
+        let svc = dedup_service(
+            store,
+            MockExtractor::new(serde_json::json!({"title": "Hello"})),
+            false,
+        );
+
+        let result = svc
+            .scrape("https://example.com", &test_schema(), "test")
+            .await
+            .unwrap();
+
+        assert_eq!(result.duplicate_of, Some(original));
+    }
+
+    #[tokio::test]
+    async fn same_url_other_content_or_old_matches_are_not_duplicates() {
+        let html = "<h1>Hello</h1>";
+        let cases = [
+            prior_extraction("https://example.com", html, chrono::Duration::hours(1)),
+            prior_extraction(
+                "https://mirror.example.com",
+                "<h1>Other</h1>",
+                chrono::Duration::hours(1),
+            ),
+            prior_extraction(
+                "https://mirror.example.com",
+                html,
+                chrono::Duration::days(2),
+            ),
+        ];
+        for prior in cases {
+            let extractor = MockExtractor::new(serde_json::json!({"title": "Hello"}));
+            let svc = dedup_service(
+                MockStore::empty().with_prior(prior),
+                extractor.clone(),
+                true,
+            );
+
+            let result = svc
+                .scrape("https://example.com", &test_schema(), "test")
+                .await
+                .unwrap();
+
+            assert_eq!(result.duplicate_of, None);
+            assert_eq!(extractor.call_count(), 1);
+        }
+    }
+
+    fn builder() -> ScrapeServiceBuilder<MockFetcher, MockCleaner, MockExtractor> {
+        ScrapeServiceBuilder::new(
+            MockFetcher::new("<html>hello</html>"),
+            MockCleaner::passthrough(),
+            MockExtractor::new(serde_json::json!({"title": "Hello"})),
+        )
+        .with_model("test-model")
+    }
+
+    fn config_error<T>(result: Result<T, AppError>) -> String {
+        match result {
+            Err(AppError::ConfigError(msg)) => msg,
+            Err(e) => panic!("expected a config error, got {e}"),
+            Ok(_) => panic!("expected a config error"),
+        }
+    }
+
+    #[tokio::test]
+    async fn builder_without_store_scrapes_without_saving() {
+        let svc = builder()
+            .with_provider("anthropic")
+            .with_validation(false)
+            .with_max_content_chars(Some(1000))
+            .with_batch_concurrency(2)
+            .build()
+            .unwrap();
+        assert_eq!(svc.provider, "anthropic");
+        assert_eq!(svc.batch_concurrency, 2);
+
+        let result = svc
+            .scrape("https://example.com", &test_schema(), "test")
+            .await
+            .unwrap();
+        assert!(result.changed);
+        assert!(result.extraction_id.is_none());
+    }
+
+    #[tokio::test]
+    async fn builder_with_store_and_every_store_option() {
+        let store = MockStore::empty();
+        let notifier = MockNotifier::new();
+        let publisher = MockPublisher::new();
+        let snapshots = MockSnapshotStore::new();
+        let svc = builder()
+            .with_store(store.clone())
+            .with_skip_unchanged(true)
+            .with_upsert(true)
+            .with_store_content(true)
+            .with_notifier(notifier.clone())
+            .with_publisher(publisher.clone())
+            .with_snapshots(snapshots.clone(), &[SnapshotKind::Html])
+            .build()
+            .unwrap();
+
+        let result = svc
+            .scrape("https://example.com", &test_schema(), "test")
+            .await
+            .unwrap();
+        assert!(result.extraction_id.is_some());
+        assert_eq!(store.upserted.lock().unwrap().len(), 1);
+        assert_eq!(store.contents.lock().unwrap().len(), 1);
+        assert_eq!(publisher.events.lock().unwrap().len(), 1);
+        assert_eq!(snapshots.objects.lock().unwrap().len(), 1);
+    }
+
+    #[test]
+    fn builder_requires_a_model() {
+        let msg = config_error(builder().with_model(" ").build());
+        assert!(msg.contains("model name"), "{msg}");
+    }
+
+    #[test]
+    fn builder_rejects_store_options_without_a_store() {
+        let cases = [
+            (
+                "skip_unchanged",
+                config_error(builder().with_skip_unchanged(true).build()),
+            ),
+            ("upsert", config_error(builder().with_upsert(true).build())),
+            (
+                "store_content",
+                config_error(builder().with_store_content(true).build()),
+            ),
+            (
+                "notifier",
+                config_error(builder().with_notifier(MockNotifier::new()).build()),
+            ),
+            (
+                "publisher",
+                config_error(builder().with_publisher(MockPublisher::new()).build()),
+            ),
+            (
+                "snapshots",
+                config_error(
+                    builder()
+                        .with_snapshots(MockSnapshotStore::new(), &SnapshotKind::ALL)
+                        .build(),
+                ),
+            ),
+            (
+                "dedup window",
+                config_error(
+                    builder()
+                        .with_dedup_window(Some(Duration::from_secs(60)))
+                        .build(),
+                ),
+            ),
+        ];
+        for (option, msg) in cases {
+            assert!(
+                msg.contains(option) && msg.contains("requires a store"),
+                "{msg}"
+            );
+        }
+
+        // Turning the options off again is fine.
+        assert!(builder().with_skip_unchanged(false).build().is_ok());
+    }
+
+    #[test]
+    fn builder_rejects_zero_limits() {
+        let msg = config_error(builder().with_max_content_chars(Some(0)).build());
+        assert!(msg.contains("max_content_chars"), "{msg}");
+        let msg = config_error(builder().with_batch_concurrency(0).build());
+        assert!(msg.contains("batch_concurrency"), "{msg}");
+        let msg = config_error(builder().with_dedup_window(Some(Duration::ZERO)).build());
+        assert!(msg.contains("dedup_window"), "{msg}");
+    }
+
+    /// A few hundred words of page text, optionally stamped with `footer`.
+    fn long_page(footer: &str) -> String {
+        let body: Vec<String> = (0..300).map(|i| format!("paragraph{i}")).collect();
+        format!("{} {footer}", body.join(" "))
+    }
+
+    #[tokio::test]
+    async fn similar_content_reuses_the_previous_extraction() {
+        let fetcher = MockFetcher::with_responses(vec![
+            Ok(long_page("Updated 10:01")),
+            Ok(long_page("Updated 10:02")),
+        ]);
+        let extractor = MockExtractor::new(serde_json::json!({"title": "Hello"}));
+        let store = MockStore::tracking();
+        let svc = ScrapeServiceBuilder::new(fetcher, MockCleaner::passthrough(), extractor.clone())
+            .with_model("test-model")
+            .with_store(store.clone())
+            .with_content_similarity_threshold(Some(8))
+            .build()
+            .unwrap();
+
+        let first = svc
+            .scrape("https://example.com", &test_schema(), "test")
+            .await
+            .unwrap();
+        let second = svc
+            .scrape("https://example.com", &test_schema(), "test")
+            .await
+            .unwrap();
+
+        assert_eq!(extractor.call_count(), 1);
+        assert_ne!(first.content_hash, second.content_hash);
+        assert!(!second.changed);
+        assert_eq!(second.extracted_data, first.extracted_data);
+        assert!(second.latency_ms.is_none() && second.usage.is_none());
+        // The reused data keeps the simhash of the content it came from.
+        let saved = store.saved.lock().unwrap();
+        assert_eq!(
+            saved[1].content_simhash.as_deref(),
+            Some(first.content_simhash.as_str())
+        );
+    }
+
+    #[tokio::test]
+    async fn similarity_compares_with_the_content_the_data_came_from() {
+        // Each page is close to the one before, but the third has drifted
+        // too far from the first, which the reused data was extracted from.
+        let drifted = |n: usize| {
+            let body: Vec<String> = (0..300)
+                .map(|i| {
+                    if i < n * 40 {
+                        format!("changed{i}")
+                    } else {
+                        format!("paragraph{i}")
+                    }
+                })
+                .collect();
+            body.join(" ")
+        };
+        let pages = [drifted(0), drifted(1), drifted(2)];
+        let threshold = simhash_distance(&compute_simhash(&pages[0]), &compute_simhash(&pages[1]))
+            .unwrap()
+            .max(
+                simhash_distance(&compute_simhash(&pages[1]), &compute_simhash(&pages[2])).unwrap(),
+            )
+            + 1;
+        let far =
+            simhash_distance(&compute_simhash(&pages[0]), &compute_simhash(&pages[2])).unwrap();
+        assert!(far >= threshold, "pages 0 and 2 must be dissimilar");
+
+        let fetcher = MockFetcher::with_responses(pages.iter().cloned().map(Ok).collect());
+        let extractor = MockExtractor::new(serde_json::json!({"title": "Hello"}));
+        let svc = ScrapeServiceBuilder::new(fetcher, MockCleaner::passthrough(), extractor.clone())
+            .with_model("test-model")
+            .with_store(MockStore::tracking())
+            .with_content_similarity_threshold(Some(threshold))
+            .build()
+            .unwrap();
+
+        for _ in 0..3 {
+            svc.scrape("https://example.com", &test_schema(), "test")
+                .await
+                .unwrap();
+        }
+        assert_eq!(extractor.call_count(), 2);
+    }
+
+    #[tokio::test]
+    async fn similar_content_with_scrubbed_data_is_extracted_again() {
+        let page = long_page("Updated 10:02");
+        let previous = Extraction {
+            content_simhash: Some(compute_simhash(&long_page("Updated 10:01"))),
+            schema_hash: Some(compute_hash(test_schema().to_string())),
+            scrubbed_at: Some(Utc::now()),
+            ..make_test_extraction("scrubbed")
+        };
+        let extractor = MockExtractor::new(serde_json::json!({"title": "Hello"}));
+        let svc = ScrapeServiceBuilder::new(
+            MockFetcher::new(&page),
+            MockCleaner::passthrough(),
+            extractor.clone(),
+        )
+        .with_model("test-model")
+        .with_store(MockStore::with_latest(previous))
+        .with_content_similarity_threshold(Some(8))
+        .build()
+        .unwrap();
+
+        svc.scrape("https://example.com", &test_schema(), "test")
+            .await
+            .unwrap();
+        assert_eq!(extractor.call_count(), 1);
+    }
+
+    #[tokio::test]
+    async fn similar_content_under_another_schema_is_extracted_again() {
+        let fetcher = MockFetcher::with_responses(vec![
+            Ok(long_page("Updated 10:01")),
+            Ok(long_page("Updated 10:02")),
+        ]);
+        let extractor = MockExtractor::with_responses(vec![
+            Ok(serde_json::json!({"title": "Hello"})),
+            Ok(serde_json::json!({"title": "Hello"})),
+        ]);
+        let svc = ScrapeServiceBuilder::new(fetcher, MockCleaner::passthrough(), extractor.clone())
+            .with_model("test-model")
+            .with_store(MockStore::tracking())
+            .with_content_similarity_threshold(Some(8))
+            .build()
+            .unwrap();
+
+        svc.scrape("https://example.com", &test_schema(), "test")
+            .await
+            .unwrap();
+        let mut schema = test_schema();
+        schema["required"] = serde_json::json!(["title"]);
+        svc.scrape("https://example.com", &schema, "test")
+            .await
+            .unwrap();
+        assert_eq!(extractor.call_count(), 2);
+    }
+
+    #[tokio::test]
+    async fn dissimilar_content_is_extracted_again() {
+        let fetcher = MockFetcher::with_responses(vec![
+            Ok(long_page("")),
+            Ok("A completely different page about something else.".to_string()),
+        ]);
+        let extractor = MockExtractor::new(serde_json::json!({"title": "Hello"}));
+        let svc = ScrapeServiceBuilder::new(fetcher, MockCleaner::passthrough(), extractor.clone())
+            .with_model("test-model")
+            .with_store(MockStore::tracking())
+            .with_content_similarity_threshold(Some(8))
+            .build()
+            .unwrap();
+
+        for _ in 0..2 {
+            svc.scrape("https://example.com", &test_schema(), "test")
+                .await
+                .unwrap();
+        }
+        assert_eq!(extractor.call_count(), 2);
+    }
+
+    #[test]
+    fn builder_validates_the_similarity_threshold() {
+        for threshold in [0, 65] {
+            let msg = config_error(
+                builder()
+                    .with_store(MockStore::empty())
+                    .with_content_similarity_threshold(Some(threshold))
+                    .build(),
+            );
+            assert!(msg.contains("content_similarity_threshold"), "{msg}");
+        }
+        let msg = config_error(builder().with_content_similarity_threshold(Some(8)).build());
+        assert!(msg.contains("content similarity threshold"), "{msg}");
+    }
+
+    #[test]
+    fn builder_rejects_reusing_duplicates_without_a_window() {
+        let msg = config_error(
+            builder()
+                .with_store(MockStore::empty())
+                .with_reuse_duplicates(true)
+                .build(),
+        );
+        assert!(msg.contains("dedup window"), "{msg}");
     }
 }
@@ -0,0 +1,135 @@
+//! Snapshots: the source content behind an extraction, archived outside
+//! the database.
+//!
+//! A [`SnapshotStore`](crate::traits::SnapshotStore) holds objects under
+//! content-addressed keys ([`SnapshotKind::key`]), so a page scraped
+//! unchanged a hundred times is stored once. Extraction rows record the keys
+//! of their snapshots.
+
+use std::fmt;
+use std::str::FromStr;
+
+use serde::{Deserialize, Serialize};
+
+/// What a snapshot holds.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SnapshotKind {
+    /// The fetched HTML, before cleaning.
+    Html,
+    /// The cleaned Markdown sent to the extractor.
+    Markdown,
+}
+
+impl SnapshotKind {
+    pub const ALL: [SnapshotKind; 2] = [SnapshotKind::Html, SnapshotKind::Markdown];
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            SnapshotKind::Html => "html",
+            SnapshotKind::Markdown => "markdown",
+        }
+    }
+
+    /// MIME type of the stored content.
+    pub fn content_type(self) -> &'static str {
+        match self {
+            SnapshotKind::Html => "text/html; charset=utf-8",
+            SnapshotKind::Markdown => "text/markdown; charset=utf-8",
+        }
+    }
+
+    fn extension(self) -> &'static str {
+        match self {
+            SnapshotKind::Html => "html",
+            SnapshotKind::Markdown => "md",
+        }
+    }
+
+    /// The key of content with SHA-256 `hash`, e.g. `html/3f/3f9a….html`.
+    /// The two-character directory keeps filesystem stores from putting
+    /// every snapshot in one directory.
+    pub fn key(self, hash: &str) -> String {
+        let shard = hash.get(..2).unwrap_or(hash);
+        format!("{}/{shard}/{hash}.{}", self.as_str(), self.extension())
+    }
+}
+
+impl fmt::Display for SnapshotKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for SnapshotKind {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_ascii_lowercase().as_str() {
+            "html" => Ok(SnapshotKind::Html),
+            "markdown" | "md" => Ok(SnapshotKind::Markdown),
+            other => Err(format!(
+                "unknown snapshot kind '{other}' (expected html or markdown)"
+            )),
+        }
+    }
+}
+
+/// Parse a comma-separated list of kinds, e.g. `html,markdown`.
+pub fn parse_snapshot_kinds(spec: &str) -> Result<Vec<SnapshotKind>, String> {
+    let mut kinds = Vec::new();
+    for part in spec.split(',').filter(|part| !part.trim().is_empty()) {
+        let kind = part.parse()?;
+        if !kinds.contains(&kind) {
+            kinds.push(kind);
+        }
+    }
+    Ok(kinds)
+}
+
+/// Whether `key` is safe to use as a relative object path: non-empty
+/// segments of `[A-Za-z0-9._-]`, none of them `.` or `..`.
+pub fn is_valid_key(key: &str) -> bool {
+    !key.is_empty()
+        && key.split('/').all(|segment| {
+            !segment.is_empty()
+                && segment != "."
+                && segment != ".."
+                && segment
+                    .chars()
+                    .all(|c| c.is_ascii_alphanumeric() || matches!(c, '.' | '_' | '-'))
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn keys_are_sharded_by_hash_prefix() {
+        assert_eq!(SnapshotKind::Html.key("3f9a"), "html/3f/3f9a.html");
+        assert_eq!(SnapshotKind::Markdown.key("3f9a"), "markdown/3f/3f9a.md");
+        assert!(is_valid_key(&SnapshotKind::Html.key(&"a".repeat(64))));
+    }
+
+    #[test]
+    fn parses_kind_lists() {
+        assert_eq!(
+            parse_snapshot_kinds("html, markdown,html").unwrap(),
+            SnapshotKind::ALL
+        );
+        assert_eq!(
+            parse_snapshot_kinds("md").unwrap(),
+            [SnapshotKind::Markdown]
+        );
+        assert!(parse_snapshot_kinds("").unwrap().is_empty());
+        assert!(parse_snapshot_kinds("pdf").is_err());
+    }
+
+    #[test]
+    fn rejects_keys_that_escape_the_store() {
+        for key in ["", "/etc/passwd", "html/../secret", "html//x", "a b", "./x"] {
+            assert!(!is_valid_key(key), "{key}");
+        }
+    }
+}
@@ -1,57 +1,135 @@
 //! Test utilities: mock implementations of all core traits.
 //!
-//! Handwritten mocks for dependency injection in unit tests.
-//! All mocks use `Arc<Mutex<_>>` for interior mutability, allowing
-//! test assertions on recorded calls.
-
-use std::sync::{Arc, Mutex};
-
-use chrono::Utc;
+//! Handwritten mocks for dependency injection in unit tests, available to
+//! other crates with the `test-util` feature. All mocks use `Arc<Mutex<_>>`
+//! for interior mutability, so clones share state and tests can assert on
+//! recorded calls. Mocks that return canned results take them as a queue
+//! through `with_responses`, one per call, falling back to a default once
+//! the queue is empty.
+//!
+//! # Example
+//!
+//! ```
+//! use ares_core::ScrapeService;
+//! use ares_core::testutil::{MockCleaner, MockExtractor, MockFetcher, MockStore};
+//!
+//! # #[tokio::main]
+//! # async fn main() {
+//! let fetcher = MockFetcher::new("<h1>Hello</h1>");
+//! let store = MockStore::empty();
+//! let service = ScrapeService::with_store(
+//!     fetcher.clone(),
+//!     MockCleaner::passthrough(),
+//!     MockExtractor::new(serde_json::json!({ "title": "Hello" })),
+//!     store.clone(),
+//!     "test-model".to_string(),
+//! );
+//!
+//! let schema = serde_json::json!({ "type": "object" });
+//! let result = service
+//!     .scrape("https://example.com", &schema, "page")
+//!     .await
+//!     .unwrap();
+//! assert_eq!(result.extracted_data["title"], "Hello");
+//! assert_eq!(fetcher.recorded_urls(), ["https://example.com"]);
+//! assert_eq!(store.save_count(), 1);
+//! # }
+//! ```
+
+use std::collections::HashMap;
+use std::io;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex, MutexGuard, PoisonError};
+
+use chrono::{DateTime, NaiveDate, Utc};
+use tracing_subscriber::fmt::MakeWriter;
 use uuid::Uuid;
 
+use crate::budget::{DailyUsage, UsageStore};
+use crate::circuit_breaker::{CircuitBreakerStats, CircuitState};
 use crate::error::AppError;
+use crate::event_log::{NewWorkerEvent, WorkerEventStore};
+use crate::fetch::FetchOptions;
 use crate::job::{CreateScrapeJobRequest, JobStatus, ScrapeJob};
 use crate::job_queue::JobQueue;
-use crate::models::{Extraction, ExtractionOutcome, NewExtraction};
+use crate::models::{
+    ChangeEvent, Extraction, ExtractionEvent, ExtractionOutcome, NewExtraction, SaveOutcome,
+};
 use crate::traits::{
-    Cleaner, ExtractionStore, Extractor, ExtractorFactory, Fetcher, LinkDiscoverer,
+    ChangeNotifier, Cleaner, ExtractionEventPublisher, ExtractionStore, Extractor,
+    ExtractorFactory, Fetcher, LinkDiscoverer, SnapshotStore,
 };
 
+/// Lock `mutex`, recovering the data if a panicking test poisoned it, so
+/// one failed assertion doesn't turn every later call into a lock panic.
+fn lock<T>(mutex: &Mutex<T>) -> MutexGuard<'_, T> {
+    mutex.lock().unwrap_or_else(PoisonError::into_inner)
+}
+
 // ---------------------------------------------------------------------------
 // MockFetcher
 // ---------------------------------------------------------------------------
 
 /// Mock fetcher that returns a configurable response.
+///
+/// ```
+/// use ares_core::error::AppError;
+/// use ares_core::testutil::MockFetcher;
+/// use ares_core::traits::Fetcher;
+///
+/// # #[tokio::main]
+/// # async fn main() {
+/// let fetcher = MockFetcher::with_responses(vec![
+///     Err(AppError::Timeout(30)),
+///     Ok("<p>retried</p>".into()),
+/// ]);
+/// assert!(fetcher.fetch("https://example.com").await.is_err());
+/// assert_eq!(fetcher.fetch("https://example.com").await.unwrap(), "<p>retried</p>");
+/// assert_eq!(fetcher.fetch_count(), 2);
+/// # }
+/// ```
 #[derive(Clone)]
 pub struct MockFetcher {
     /// Queue of responses. Each call pops the first element.
     /// If empty, returns a default HTML string.
     responses: Arc<Mutex<Vec<Result<String, AppError>>>>,
+    fetch_count: Arc<AtomicUsize>,
+    urls: Arc<Mutex<Vec<String>>>,
 }
 
 impl MockFetcher {
     pub fn new(html: &str) -> Self {
-        Self {
-            responses: Arc::new(Mutex::new(vec![Ok(html.to_string())])),
-        }
+        Self::with_responses(vec![Ok(html.to_string())])
     }
 
     pub fn with_error(error: AppError) -> Self {
-        Self {
-            responses: Arc::new(Mutex::new(vec![Err(error)])),
-        }
+        Self::with_responses(vec![Err(error)])
     }
 
     pub fn with_responses(responses: Vec<Result<String, AppError>>) -> Self {
         Self {
             responses: Arc::new(Mutex::new(responses)),
+            fetch_count: Arc::new(AtomicUsize::new(0)),
+            urls: Arc::new(Mutex::new(Vec::new())),
         }
     }
+
+    /// Number of fetches made through this fetcher or any of its clones.
+    pub fn fetch_count(&self) -> usize {
+        self.fetch_count.load(Ordering::SeqCst)
+    }
+
+    /// The URLs fetched through this fetcher or any of its clones, in order.
+    pub fn recorded_urls(&self) -> Vec<String> {
+        lock(&self.urls).clone()
+    }
 }
 
 impl Fetcher for MockFetcher {
-    async fn fetch(&self, _url: &str) -> Result<String, AppError> {
-        let mut responses = self.responses.lock().unwrap();
+    async fn fetch(&self, url: &str) -> Result<String, AppError> {
+        self.fetch_count.fetch_add(1, Ordering::SeqCst);
+        lock(&self.urls).push(url.to_string());
+        let mut responses = lock(&self.responses);
         if responses.is_empty() {
             Ok("<html><body>default</body></html>".to_string())
         } else {
@@ -64,36 +142,40 @@ impl Fetcher for MockFetcher {
 // MockCleaner
 // ---------------------------------------------------------------------------
 
-/// Mock cleaner that applies a simple transformation.
+/// Mock cleaner that returns its input unchanged, unless given responses.
 #[derive(Clone)]
 pub struct MockCleaner {
-    error: Arc<Mutex<Option<AppError>>>,
+    /// Queue of responses. Each call pops the first element.
+    /// If empty, returns the input as-is.
+    responses: Arc<Mutex<Vec<Result<String, AppError>>>>,
 }
 
 impl MockCleaner {
     /// Creates a cleaner that returns the input unchanged.
     pub fn passthrough() -> Self {
-        Self {
-            error: Arc::new(Mutex::new(None)),
-        }
+        Self::with_responses(Vec::new())
     }
 
-    /// Creates a cleaner that returns an error.
+    /// Creates a cleaner whose first call returns an error.
     pub fn with_error(error: AppError) -> Self {
+        Self::with_responses(vec![Err(error)])
+    }
+
+    pub fn with_responses(responses: Vec<Result<String, AppError>>) -> Self {
         Self {
-            error: Arc::new(Mutex::new(Some(error))),
+            responses: Arc::new(Mutex::new(responses)),
         }
     }
 }
 
 impl Cleaner for MockCleaner {
     fn clean(&self, html: &str) -> Result<String, AppError> {
-        let mut err = self.error.lock().unwrap();
-        if let Some(e) = err.take() {
-            return Err(e);
+        let mut responses = lock(&self.responses);
+        if responses.is_empty() {
+            Ok(html.to_string())
+        } else {
+            responses.remove(0)
         }
-        // Simulate cleaning: just return the input as-is
-        Ok(html.to_string())
     }
 }
 
@@ -105,26 +187,46 @@ impl Cleaner for MockCleaner {
 #[derive(Clone)]
 pub struct MockExtractor {
     responses: Arc<Mutex<Vec<Result<serde_json::Value, AppError>>>>,
+    call_count: Arc<AtomicUsize>,
+    fingerprint: Option<String>,
+    reported_model: Option<String>,
 }
 
 impl MockExtractor {
     pub fn new(data: serde_json::Value) -> Self {
-        Self {
-            responses: Arc::new(Mutex::new(vec![Ok(data)])),
-        }
+        Self::with_responses(vec![Ok(data)])
     }
 
     pub fn with_error(error: AppError) -> Self {
-        Self {
-            responses: Arc::new(Mutex::new(vec![Err(error)])),
-        }
+        Self::with_responses(vec![Err(error)])
     }
 
     pub fn with_responses(responses: Vec<Result<serde_json::Value, AppError>>) -> Self {
         Self {
             responses: Arc::new(Mutex::new(responses)),
+            call_count: Arc::new(AtomicUsize::new(0)),
+            fingerprint: None,
+            reported_model: None,
         }
     }
+
+    /// Report `fingerprint` from [`Extractor::fingerprint`].
+    pub fn with_fingerprint(mut self, fingerprint: impl Into<String>) -> Self {
+        self.fingerprint = Some(fingerprint.into());
+        self
+    }
+
+    /// Report every extraction as answered by `model`, as a provider
+    /// resolving an alias or falling back would.
+    pub fn with_reported_model(mut self, model: impl Into<String>) -> Self {
+        self.reported_model = Some(model.into());
+        self
+    }
+
+    /// Number of `extract` calls made on this extractor or any of its clones.
+    pub fn call_count(&self) -> usize {
+        self.call_count.load(Ordering::SeqCst)
+    }
 }
 
 impl Extractor for MockExtractor {
@@ -133,13 +235,22 @@ impl Extractor for MockExtractor {
         _content: &str,
         _schema: &serde_json::Value,
     ) -> Result<ExtractionOutcome, AppError> {
-        let mut responses = self.responses.lock().unwrap();
+        self.call_count.fetch_add(1, Ordering::SeqCst);
+        let mut responses = lock(&self.responses);
         let value = if responses.is_empty() {
             serde_json::json!({"default": true})
         } else {
             responses.remove(0)?
         };
-        Ok(ExtractionOutcome::new(value))
+        let outcome = ExtractionOutcome::new(value);
+        Ok(match &self.reported_model {
+            Some(model) => outcome.with_model(model.clone()),
+            None => outcome,
+        })
+    }
+
+    fn fingerprint(&self) -> Option<String> {
+        self.fingerprint.clone()
     }
 }
 
@@ -147,26 +258,41 @@ impl Extractor for MockExtractor {
 // MockExtractorFactory
 // ---------------------------------------------------------------------------
 
-/// Mock factory that always creates a MockExtractor with the given JSON response.
+/// Mock factory creating [`MockExtractor`]s.
+///
+/// Built with [`new`](Self::new), every created extractor returns the same
+/// JSON; built with [`with_responses`](Self::with_responses), the created
+/// extractors share one queue of responses, so each job gets the next one.
 #[derive(Clone)]
 pub struct MockExtractorFactory {
     /// The JSON value every created extractor will return.
-    data: Arc<Mutex<serde_json::Value>>,
+    data: serde_json::Value,
+    /// Extractor handed out (as a clone) by every `create` call, if any.
+    shared: Option<MockExtractor>,
     create_error: Arc<Mutex<Option<AppError>>>,
 }
 
 impl MockExtractorFactory {
     pub fn new(data: serde_json::Value) -> Self {
         Self {
-            data: Arc::new(Mutex::new(data)),
+            data,
+            shared: None,
             create_error: Arc::new(Mutex::new(None)),
         }
     }
 
+    pub fn with_responses(responses: Vec<Result<serde_json::Value, AppError>>) -> Self {
+        Self {
+            shared: Some(MockExtractor::with_responses(responses)),
+            ..Self::new(serde_json::Value::Null)
+        }
+    }
+
+    /// Factory whose first `create` call returns `error`.
     pub fn with_create_error(error: AppError) -> Self {
         Self {
-            data: Arc::new(Mutex::new(serde_json::Value::Null)),
             create_error: Arc::new(Mutex::new(Some(error))),
+            ..Self::new(serde_json::Value::Null)
         }
     }
 }
@@ -175,12 +301,13 @@ impl ExtractorFactory for MockExtractorFactory {
     type Extractor = MockExtractor;
 
     fn create(&self, _model: &str, _base_url: &str) -> Result<MockExtractor, AppError> {
-        let mut err = self.create_error.lock().unwrap();
-        if let Some(e) = err.take() {
+        if let Some(e) = lock(&self.create_error).take() {
             return Err(e);
         }
-        let data = self.data.lock().unwrap().clone();
-        Ok(MockExtractor::new(data))
+        Ok(match &self.shared {
+            Some(extractor) => extractor.clone(),
+            None => MockExtractor::new(self.data.clone()),
+        })
     }
 }
 
@@ -188,12 +315,25 @@ impl ExtractorFactory for MockExtractorFactory {
 // MockStore
 // ---------------------------------------------------------------------------
 
-/// Mock store that records saves and returns configurable latest/history.
+/// Mock store that records saves and upserts and returns configurable
+/// latest/history.
 #[derive(Clone)]
 pub struct MockStore {
     pub saved: Arc<Mutex<Vec<NewExtraction>>>,
+    pub upserted: Arc<Mutex<Vec<NewExtraction>>>,
+    /// Cleaned content stored with `save_content`, by extraction ID.
+    pub contents: Arc<Mutex<Vec<(Uuid, String)>>>,
     latest: Arc<Mutex<Option<Extraction>>>,
+    /// Earlier extractions `find_by_content_hash` searches, oldest first.
+    prior: Arc<Mutex<Vec<Extraction>>>,
     save_error: Arc<Mutex<Option<AppError>>>,
+    /// Every `save` call, including failed ones.
+    save_count: Arc<AtomicUsize>,
+    /// When set, each save becomes the latest extraction.
+    track_latest: bool,
+    /// Becomes the latest extraction on the next `keep_if_unchanged`, as if
+    /// written concurrently.
+    concurrent_write: Arc<Mutex<Option<Extraction>>>,
 }
 
 impl MockStore {
@@ -201,8 +341,14 @@ impl MockStore {
     pub fn empty() -> Self {
         Self {
             saved: Arc::new(Mutex::new(Vec::new())),
+            upserted: Arc::new(Mutex::new(Vec::new())),
+            contents: Arc::new(Mutex::new(Vec::new())),
             latest: Arc::new(Mutex::new(None)),
             save_error: Arc::new(Mutex::new(None)),
+            save_count: Arc::new(AtomicUsize::new(0)),
+            prior: Arc::new(Mutex::new(Vec::new())),
+            track_latest: false,
+            concurrent_write: Arc::new(Mutex::new(None)),
         }
     }
 
@@ -210,8 +356,14 @@ impl MockStore {
     pub fn with_latest(extraction: Extraction) -> Self {
         Self {
             saved: Arc::new(Mutex::new(Vec::new())),
+            upserted: Arc::new(Mutex::new(Vec::new())),
+            contents: Arc::new(Mutex::new(Vec::new())),
             latest: Arc::new(Mutex::new(Some(extraction))),
             save_error: Arc::new(Mutex::new(None)),
+            save_count: Arc::new(AtomicUsize::new(0)),
+            prior: Arc::new(Mutex::new(Vec::new())),
+            track_latest: false,
+            concurrent_write: Arc::new(Mutex::new(None)),
         }
     }
 
@@ -219,29 +371,143 @@ impl MockStore {
     pub fn with_save_error(error: AppError) -> Self {
         Self {
             saved: Arc::new(Mutex::new(Vec::new())),
+            upserted: Arc::new(Mutex::new(Vec::new())),
+            contents: Arc::new(Mutex::new(Vec::new())),
             latest: Arc::new(Mutex::new(None)),
             save_error: Arc::new(Mutex::new(Some(error))),
+            save_count: Arc::new(AtomicUsize::new(0)),
+            prior: Arc::new(Mutex::new(Vec::new())),
+            track_latest: false,
+            concurrent_write: Arc::new(Mutex::new(None)),
+        }
+    }
+
+    /// Empty store whose saves become the latest extraction, for change
+    /// detection across several runs.
+    pub fn tracking() -> Self {
+        Self {
+            track_latest: true,
+            ..Self::empty()
         }
     }
+
+    /// Add an earlier extraction, possibly of another URL, for
+    /// `find_by_content_hash` to find.
+    pub fn with_prior(self, extraction: Extraction) -> Self {
+        lock(&self.prior).push(extraction);
+        self
+    }
+
+    /// Replace the latest extraction with `extraction` just before the next
+    /// `keep_if_unchanged` check, so it finds the data changed.
+    pub fn with_concurrent_write(self, extraction: Extraction) -> Self {
+        *lock(&self.concurrent_write) = Some(extraction);
+        self
+    }
+
+    /// Number of `save` calls on this store or any of its clones, including
+    /// those that returned an error.
+    pub fn save_count(&self) -> usize {
+        self.save_count.load(Ordering::SeqCst)
+    }
 }
 
 impl ExtractionStore for MockStore {
     async fn save(&self, extraction: &NewExtraction) -> Result<Uuid, AppError> {
-        let mut err = self.save_error.lock().unwrap();
+        self.save_count.fetch_add(1, Ordering::SeqCst);
+        let mut err = lock(&self.save_error);
         if let Some(e) = err.take() {
             return Err(e);
         }
         let id = Uuid::new_v4();
-        self.saved.lock().unwrap().push(extraction.clone());
+        lock(&self.saved).push(extraction.clone());
+        if self.track_latest {
+            *lock(&self.latest) = Some(Extraction {
+                id,
+                url: extraction.url.clone(),
+                schema_name: extraction.schema_name.clone(),
+                extracted_data: extraction.extracted_data.clone(),
+                content_hash: extraction.raw_content_hash.clone(),
+                content_simhash: extraction.content_simhash.clone(),
+                data_hash: extraction.data_hash.clone(),
+                model: extraction.model.clone(),
+                provider: extraction.provider.clone(),
+                prompt_hash: extraction.prompt_hash.clone(),
+                schema_hash: extraction.schema_hash.clone(),
+                schema_version: extraction.schema_version.clone(),
+                latency_ms: extraction.latency_ms,
+                prompt_tokens: extraction.prompt_tokens,
+                completion_tokens: extraction.completion_tokens,
+                html_snapshot_key: extraction.html_snapshot_key.clone(),
+                markdown_snapshot_key: extraction.markdown_snapshot_key.clone(),
+                duplicate_of: extraction.duplicate_of,
+                detected_language: extraction.detected_language.clone(),
+                golden: false,
+                scrubbed_at: None,
+                created_at: Utc::now(),
+            });
+        }
         Ok(id)
     }
 
+    /// Returns the latest extraction's ID when its data hash matches,
+    /// mirroring an in-place update; otherwise a fresh ID.
+    async fn upsert(&self, extraction: &NewExtraction) -> Result<Uuid, AppError> {
+        let mut err = lock(&self.save_error);
+        if let Some(e) = err.take() {
+            return Err(e);
+        }
+        lock(&self.upserted).push(extraction.clone());
+        let existing = lock(&self.latest)
+            .as_ref()
+            .filter(|prev| prev.data_hash == extraction.data_hash)
+            .map(|prev| prev.id);
+        Ok(existing.unwrap_or_else(Uuid::new_v4))
+    }
+
+    async fn keep_if_unchanged(
+        &self,
+        extraction: &NewExtraction,
+    ) -> Result<Option<SaveOutcome>, AppError> {
+        if let Some(written) = lock(&self.concurrent_write).take() {
+            *lock(&self.latest) = Some(written);
+        }
+        Ok(lock(&self.latest)
+            .as_ref()
+            .filter(|latest| latest.data_hash == extraction.data_hash)
+            .map(|latest| SaveOutcome::Unchanged {
+                existing_id: latest.id,
+                last_seen_bumped: false,
+            }))
+    }
+
+    async fn save_content(&self, extraction_id: Uuid, content: &str) -> Result<(), AppError> {
+        lock(&self.contents).push((extraction_id, content.to_string()));
+        Ok(())
+    }
+
     async fn get_latest(
         &self,
         _url: &str,
         _schema_name: &str,
     ) -> Result<Option<Extraction>, AppError> {
-        Ok(self.latest.lock().unwrap().clone())
+        Ok(lock(&self.latest).clone())
+    }
+
+    async fn find_by_content_hash(
+        &self,
+        schema_name: &str,
+        content_hash: &str,
+        since: DateTime<Utc>,
+    ) -> Result<Option<Extraction>, AppError> {
+        Ok(lock(&self.prior)
+            .iter()
+            .find(|e| {
+                e.schema_name == schema_name
+                    && e.content_hash == content_hash
+                    && e.created_at >= since
+            })
+            .cloned())
     }
 
     async fn get_history(
@@ -279,7 +545,135 @@ impl MockLinkDiscoverer {
 
 impl LinkDiscoverer for MockLinkDiscoverer {
     fn discover_links(&self, _html: &str, _base_url: &str) -> Result<Vec<String>, AppError> {
-        Ok(self.links.lock().unwrap().clone())
+        Ok(lock(&self.links).clone())
+    }
+}
+
+// ---------------------------------------------------------------------------
+// MockNotifier
+// ---------------------------------------------------------------------------
+
+/// Mock change notifier that records every event it is sent.
+#[derive(Clone, Default)]
+pub struct MockNotifier {
+    pub events: Arc<Mutex<Vec<ChangeEvent>>>,
+    /// The webhook override in effect for each recorded event.
+    pub webhooks: Arc<Mutex<Vec<Option<String>>>>,
+    /// Webhook URL set through [`ChangeNotifier::with_webhook_override`].
+    pub webhook_override: Option<String>,
+    fail: bool,
+}
+
+impl MockNotifier {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// A notifier whose every call fails (after recording the event).
+    pub fn failing() -> Self {
+        Self {
+            fail: true,
+            ..Self::default()
+        }
+    }
+}
+
+impl ChangeNotifier for MockNotifier {
+    async fn notify(&self, change: &ChangeEvent) -> Result<(), AppError> {
+        lock(&self.events).push(change.clone());
+        lock(&self.webhooks).push(self.webhook_override.clone());
+        if self.fail {
+            return Err(AppError::HttpError(
+                "HTTP 500 from notification sink".into(),
+            ));
+        }
+        Ok(())
+    }
+
+    fn with_webhook_override(&self, url: &str) -> Self {
+        Self {
+            webhook_override: Some(url.to_string()),
+            ..self.clone()
+        }
+    }
+}
+
+// ---------------------------------------------------------------------------
+// MockPublisher
+// ---------------------------------------------------------------------------
+
+/// Mock extraction event publisher that records every event it is sent.
+#[derive(Clone, Default)]
+pub struct MockPublisher {
+    pub events: Arc<Mutex<Vec<ExtractionEvent>>>,
+    fail: bool,
+}
+
+impl MockPublisher {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// A publisher whose every call fails (after recording the event).
+    pub fn failing() -> Self {
+        Self {
+            fail: true,
+            ..Self::default()
+        }
+    }
+}
+
+impl ExtractionEventPublisher for MockPublisher {
+    async fn publish(&self, event: &ExtractionEvent) -> Result<(), AppError> {
+        lock(&self.events).push(event.clone());
+        if self.fail {
+            return Err(AppError::NetworkError("event bus unreachable".into()));
+        }
+        Ok(())
+    }
+}
+
+// ---------------------------------------------------------------------------
+// MockSnapshotStore
+// ---------------------------------------------------------------------------
+
+/// Mock snapshot store keeping objects in memory.
+#[derive(Clone, Default)]
+pub struct MockSnapshotStore {
+    pub objects: Arc<Mutex<HashMap<String, Vec<u8>>>>,
+    fail: bool,
+}
+
+impl MockSnapshotStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// A store whose every write fails.
+    pub fn failing() -> Self {
+        Self {
+            fail: true,
+            ..Self::default()
+        }
+    }
+}
+
+impl SnapshotStore for MockSnapshotStore {
+    async fn put(&self, key: &str, content: &[u8]) -> Result<(), AppError> {
+        if self.fail {
+            return Err(AppError::NetworkError("snapshot store unreachable".into()));
+        }
+        lock(&self.objects).insert(key.to_string(), content.to_vec());
+        Ok(())
+    }
+
+    async fn get(&self, key: &str) -> Result<Option<Vec<u8>>, AppError> {
+        Ok(lock(&self.objects).get(key).cloned())
+    }
+
+    async fn delete(&self, key: &str) -> Result<(), AppError> {
+        lock(&self.objects).remove(key);
+        Ok(())
     }
 }
 
@@ -315,7 +709,7 @@ impl Default for MockRobotsChecker {
 
 impl crate::traits::RobotsChecker for MockRobotsChecker {
     async fn is_allowed(&self, url: &str) -> bool {
-        let blocked = self.blocked_urls.lock().unwrap();
+        let blocked = lock(&self.blocked_urls);
         !blocked.iter().any(|b| url.contains(b))
     }
 }
@@ -327,6 +721,9 @@ impl crate::traits::RobotsChecker for MockRobotsChecker {
 /// Recorded failure: (job_id, error_message, next_retry_at).
 pub type FailedJobRecord = (Uuid, String, Option<chrono::DateTime<Utc>>);
 
+/// Recorded deferral: (job_id, reason, until).
+pub type DeferredJobRecord = (Uuid, String, DateTime<Utc>);
+
 /// Recorded completion: (job_id, extraction_id).
 pub type CompletedJobRecord = (Uuid, Option<Uuid>);
 
@@ -336,9 +733,14 @@ pub struct MockJobQueue {
     pub jobs: Arc<Mutex<Vec<ScrapeJob>>>,
     claim_error: Arc<Mutex<Option<AppError>>>,
     pub failed_jobs: Arc<Mutex<Vec<FailedJobRecord>>>,
+    pub deferred_jobs: Arc<Mutex<Vec<DeferredJobRecord>>>,
     pub completed_jobs: Arc<Mutex<Vec<CompletedJobRecord>>>,
     pub released_workers: Arc<Mutex<Vec<String>>>,
     pub visited_urls: Arc<Mutex<Vec<(Uuid, String)>>>,
+    /// Every circuit-breaker state published by a worker, in order.
+    pub saved_breakers: Arc<Mutex<Vec<CircuitBreakerStats>>>,
+    /// Pending reset requests, keyed by breaker name.
+    pub breaker_resets: Arc<Mutex<HashMap<String, DateTime<Utc>>>>,
 }
 
 impl MockJobQueue {
@@ -347,9 +749,12 @@ impl MockJobQueue {
             jobs: Arc::new(Mutex::new(Vec::new())),
             claim_error: Arc::new(Mutex::new(None)),
             failed_jobs: Arc::new(Mutex::new(Vec::new())),
+            deferred_jobs: Arc::new(Mutex::new(Vec::new())),
             completed_jobs: Arc::new(Mutex::new(Vec::new())),
             released_workers: Arc::new(Mutex::new(Vec::new())),
             visited_urls: Arc::new(Mutex::new(Vec::new())),
+            saved_breakers: Arc::new(Mutex::new(Vec::new())),
+            breaker_resets: Arc::new(Mutex::new(HashMap::new())),
         }
     }
 
@@ -357,24 +762,28 @@ impl MockJobQueue {
     pub fn with_job(job: ScrapeJob) -> Self {
         Self {
             jobs: Arc::new(Mutex::new(vec![job])),
-            claim_error: Arc::new(Mutex::new(None)),
-            failed_jobs: Arc::new(Mutex::new(Vec::new())),
-            completed_jobs: Arc::new(Mutex::new(Vec::new())),
-            released_workers: Arc::new(Mutex::new(Vec::new())),
-            visited_urls: Arc::new(Mutex::new(Vec::new())),
+            ..Self::empty()
+        }
+    }
+
+    pub fn with_jobs(jobs: Vec<ScrapeJob>) -> Self {
+        Self {
+            jobs: Arc::new(Mutex::new(jobs)),
+            ..Self::empty()
         }
     }
 
     pub fn with_claim_error(error: AppError) -> Self {
         Self {
-            jobs: Arc::new(Mutex::new(Vec::new())),
             claim_error: Arc::new(Mutex::new(Some(error))),
-            failed_jobs: Arc::new(Mutex::new(Vec::new())),
-            completed_jobs: Arc::new(Mutex::new(Vec::new())),
-            released_workers: Arc::new(Mutex::new(Vec::new())),
-            visited_urls: Arc::new(Mutex::new(Vec::new())),
+            ..Self::empty()
         }
     }
+
+    /// Simulate an operator requesting a reset of the named breaker.
+    pub fn request_breaker_reset(&self, name: &str) {
+        lock(&self.breaker_resets).insert(name.to_string(), Utc::now());
+    }
 }
 
 impl JobQueue for MockJobQueue {
@@ -403,19 +812,26 @@ impl JobQueue for MockJobQueue {
             max_depth: request.max_depth,
             max_pages: request.max_pages,
             allowed_domains: request.allowed_domains,
+            fetch_options: request.fetch_options,
+            notify_url: request.notify_url,
+            tags: request.tags,
         };
-        self.jobs.lock().unwrap().push(job.clone());
+        lock(&self.jobs).push(job.clone());
         Ok(job)
     }
 
     async fn claim_job(&self, worker_id: &str) -> Result<Option<ScrapeJob>, AppError> {
-        let mut err = self.claim_error.lock().unwrap();
+        let mut err = lock(&self.claim_error);
         if let Some(e) = err.take() {
             return Err(e);
         }
 
-        let mut jobs = self.jobs.lock().unwrap();
-        if let Some(pos) = jobs.iter().position(|j| j.status == JobStatus::Pending) {
+        let mut jobs = lock(&self.jobs);
+        // Like the real queue, jobs scheduled for a later retry aren't claimable.
+        let now = Utc::now();
+        if let Some(pos) = jobs.iter().position(|j| {
+            j.status == JobStatus::Pending && j.next_retry_at.is_none_or(|at| at <= now)
+        }) {
             jobs[pos].status = JobStatus::Running;
             jobs[pos].worker_id = Some(worker_id.to_string());
             jobs[pos].started_at = Some(Utc::now());
@@ -430,12 +846,9 @@ impl JobQueue for MockJobQueue {
         job_id: Uuid,
         extraction_id: Option<Uuid>,
     ) -> Result<(), AppError> {
-        self.completed_jobs
-            .lock()
-            .unwrap()
-            .push((job_id, extraction_id));
+        lock(&self.completed_jobs).push((job_id, extraction_id));
 
-        let mut jobs = self.jobs.lock().unwrap();
+        let mut jobs = lock(&self.jobs);
         if let Some(job) = jobs.iter_mut().find(|j| j.id == job_id) {
             job.status = JobStatus::Completed;
             job.extraction_id = extraction_id;
@@ -450,19 +863,20 @@ impl JobQueue for MockJobQueue {
         error: &str,
         next_retry_at: Option<chrono::DateTime<Utc>>,
     ) -> Result<(), AppError> {
-        self.failed_jobs
-            .lock()
-            .unwrap()
-            .push((job_id, error.to_string(), next_retry_at));
+        lock(&self.failed_jobs).push((job_id, error.to_string(), next_retry_at));
 
-        let mut jobs = self.jobs.lock().unwrap();
-        if let Some(job) = jobs.iter_mut().find(|j| j.id == job_id) {
+        let mut jobs = lock(&self.jobs);
+        if let Some(job) = jobs
+            .iter_mut()
+            .find(|j| j.id == job_id && j.status != JobStatus::Completed)
+        {
             if next_retry_at.is_some() {
                 job.status = JobStatus::Pending;
                 job.retry_count += 1;
                 job.next_retry_at = next_retry_at;
             } else {
                 job.status = JobStatus::Failed;
+                job.completed_at = Some(Utc::now());
             }
             job.error_message = Some(error.to_string());
             job.worker_id = None;
@@ -470,29 +884,68 @@ impl JobQueue for MockJobQueue {
         Ok(())
     }
 
+    async fn defer_job(
+        &self,
+        job_id: Uuid,
+        reason: &str,
+        until: DateTime<Utc>,
+    ) -> Result<(), AppError> {
+        lock(&self.deferred_jobs).push((job_id, reason.to_string(), until));
+
+        let mut jobs = lock(&self.jobs);
+        if let Some(job) = jobs
+            .iter_mut()
+            .find(|j| j.id == job_id && j.status != JobStatus::Completed)
+        {
+            job.status = JobStatus::Pending;
+            job.next_retry_at = Some(until);
+            job.error_message = Some(reason.to_string());
+            job.worker_id = None;
+        }
+        Ok(())
+    }
+
     async fn cancel_job(&self, job_id: Uuid) -> Result<(), AppError> {
-        let mut jobs = self.jobs.lock().unwrap();
+        let mut jobs = lock(&self.jobs);
         if let Some(job) = jobs.iter_mut().find(|j| j.id == job_id) {
             job.status = JobStatus::Cancelled;
         }
         Ok(())
     }
 
+    async fn bulk_cancel(&self, job_ids: &[Uuid]) -> Result<u64, AppError> {
+        let mut jobs = lock(&self.jobs);
+        let mut count = 0u64;
+        for job in jobs.iter_mut() {
+            if job_ids.contains(&job.id)
+                && !matches!(job.status, JobStatus::Completed | JobStatus::Cancelled)
+            {
+                job.status = JobStatus::Cancelled;
+                count += 1;
+            }
+        }
+        Ok(count)
+    }
+
     async fn get_job(&self, job_id: Uuid) -> Result<Option<ScrapeJob>, AppError> {
-        let jobs = self.jobs.lock().unwrap();
+        let jobs = lock(&self.jobs);
         Ok(jobs.iter().find(|j| j.id == job_id).cloned())
     }
 
     async fn list_jobs(
         &self,
         status: Option<JobStatus>,
+        worker_id: Option<&str>,
+        tag: Option<&str>,
         limit: usize,
         offset: usize,
     ) -> Result<Vec<ScrapeJob>, AppError> {
-        let jobs = self.jobs.lock().unwrap();
+        let jobs = lock(&self.jobs);
         let filtered: Vec<_> = jobs
             .iter()
             .filter(|j| status.is_none_or(|s| j.status == s))
+            .filter(|j| worker_id.is_none_or(|w| j.worker_id.as_deref() == Some(w)))
+            .filter(|j| tag.is_none_or(|t| j.tags.iter().any(|tag| tag == t)))
             .skip(offset)
             .take(limit)
             .cloned()
@@ -501,7 +954,7 @@ impl JobQueue for MockJobQueue {
     }
 
     async fn retry_job(&self, job_id: Uuid) -> Result<Option<ScrapeJob>, AppError> {
-        let mut jobs = self.jobs.lock().unwrap();
+        let mut jobs = lock(&self.jobs);
         if let Some(job) = jobs.iter_mut().find(|j| j.id == job_id)
             && matches!(job.status, JobStatus::Failed | JobStatus::Cancelled)
         {
@@ -519,8 +972,29 @@ impl JobQueue for MockJobQueue {
         Ok(None)
     }
 
+    async fn update_job_schema(
+        &self,
+        job_id: Uuid,
+        new_schema: serde_json::Value,
+    ) -> Result<(), AppError> {
+        let mut jobs = lock(&self.jobs);
+        match jobs
+            .iter_mut()
+            .find(|j| j.id == job_id && j.status == JobStatus::Pending)
+        {
+            Some(job) => {
+                job.schema = new_schema;
+                job.updated_at = Utc::now();
+                Ok(())
+            }
+            None => Err(AppError::Conflict(format!(
+                "Job {job_id} is not pending; only pending jobs can change schema"
+            ))),
+        }
+    }
+
     async fn release_job(&self, job_id: Uuid) -> Result<(), AppError> {
-        let mut jobs = self.jobs.lock().unwrap();
+        let mut jobs = lock(&self.jobs);
         if let Some(job) = jobs.iter_mut().find(|j| j.id == job_id) {
             job.status = JobStatus::Pending;
             job.worker_id = None;
@@ -529,12 +1003,9 @@ impl JobQueue for MockJobQueue {
     }
 
     async fn release_worker_jobs(&self, worker_id: &str) -> Result<u64, AppError> {
-        self.released_workers
-            .lock()
-            .unwrap()
-            .push(worker_id.to_string());
+        lock(&self.released_workers).push(worker_id.to_string());
 
-        let mut jobs = self.jobs.lock().unwrap();
+        let mut jobs = lock(&self.jobs);
         let mut count = 0u64;
         for job in jobs.iter_mut() {
             if job.worker_id.as_deref() == Some(worker_id) && job.status == JobStatus::Running {
@@ -547,12 +1018,20 @@ impl JobQueue for MockJobQueue {
     }
 
     async fn count_by_status(&self, status: JobStatus) -> Result<i64, AppError> {
-        let jobs = self.jobs.lock().unwrap();
+        let jobs = lock(&self.jobs);
         Ok(jobs.iter().filter(|j| j.status == status).count() as i64)
     }
 
+    async fn count_by_worker(&self, worker_id: &str) -> Result<i64, AppError> {
+        let jobs = lock(&self.jobs);
+        Ok(jobs
+            .iter()
+            .filter(|j| j.worker_id.as_deref() == Some(worker_id) && j.status == JobStatus::Running)
+            .count() as i64)
+    }
+
     async fn mark_url_visited(&self, session_id: Uuid, url: &str) -> Result<bool, AppError> {
-        let mut visited = self.visited_urls.lock().unwrap();
+        let mut visited = lock(&self.visited_urls);
         if visited.iter().any(|(s, u)| *s == session_id && u == url) {
             Ok(false)
         } else {
@@ -562,9 +1041,21 @@ impl JobQueue for MockJobQueue {
     }
 
     async fn count_visited_urls(&self, session_id: Uuid) -> Result<i64, AppError> {
-        let visited = self.visited_urls.lock().unwrap();
+        let visited = lock(&self.visited_urls);
         Ok(visited.iter().filter(|(s, _)| *s == session_id).count() as i64)
     }
+
+    async fn save_circuit_breaker(&self, stats: &CircuitBreakerStats) -> Result<(), AppError> {
+        lock(&self.saved_breakers).push(stats.clone());
+        Ok(())
+    }
+
+    async fn circuit_breaker_reset_requested_at(
+        &self,
+        name: &str,
+    ) -> Result<Option<DateTime<Utc>>, AppError> {
+        Ok(lock(&self.breaker_resets).get(name).copied())
+    }
 }
 
 // ---------------------------------------------------------------------------
@@ -575,6 +1066,22 @@ impl JobQueue for MockJobQueue {
 #[derive(Default)]
 pub struct MockReporter {
     pub events: Arc<Mutex<Vec<String>>>,
+    /// Details of each `JobCompleted` event.
+    pub completions: Arc<Mutex<Vec<JobCompletion>>>,
+    /// Each circuit breaker change, as `(name, state)`.
+    pub circuit_changes: Arc<Mutex<Vec<(String, CircuitState)>>>,
+}
+
+/// The fields of a `JobCompleted` event, as recorded by [`MockReporter`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct JobCompletion {
+    pub job_id: Uuid,
+    pub extraction_id: Option<Uuid>,
+    pub duration: std::time::Duration,
+    pub changed: Option<bool>,
+    pub content_bytes: usize,
+    pub model: String,
+    pub detected_language: Option<String>,
 }
 
 impl MockReporter {
@@ -595,7 +1102,175 @@ impl crate::worker::WorkerReporter for MockReporter {
             crate::worker::WorkerEvent::ShuttingDown { .. } => "ShuttingDown",
             crate::worker::WorkerEvent::Stopped { .. } => "Stopped",
         };
-        self.events.lock().unwrap().push(label.to_string());
+        lock(&self.events).push(label.to_string());
+        if let crate::worker::WorkerEvent::JobCompleted {
+            job_id,
+            extraction_id,
+            duration,
+            changed,
+            content_bytes,
+            model,
+            detected_language,
+        } = event
+        {
+            lock(&self.completions).push(JobCompletion {
+                job_id,
+                extraction_id,
+                duration,
+                changed,
+                content_bytes,
+                model: model.to_string(),
+                detected_language: detected_language.map(str::to_string),
+            });
+        }
+    }
+
+    fn on_circuit_breaker_change(&self, name: &str, state: CircuitState) {
+        lock(&self.circuit_changes).push((name.to_string(), state));
+    }
+}
+
+// ---------------------------------------------------------------------------
+// MockEventStore
+// ---------------------------------------------------------------------------
+
+/// Mock worker event store that records saved events. Saves can be paused,
+/// to simulate a slow database, or made to fail.
+#[derive(Clone)]
+pub struct MockEventStore {
+    pub events: Arc<Mutex<Vec<NewWorkerEvent>>>,
+    paused: Arc<tokio::sync::watch::Sender<bool>>,
+    fail: bool,
+    save_calls: Arc<AtomicUsize>,
+}
+
+impl Default for MockEventStore {
+    fn default() -> Self {
+        Self {
+            events: Arc::new(Mutex::new(Vec::new())),
+            paused: Arc::new(tokio::sync::watch::Sender::new(false)),
+            fail: false,
+            save_calls: Arc::new(AtomicUsize::new(0)),
+        }
+    }
+}
+
+impl MockEventStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Store whose saves wait until [`resume`](Self::resume) is called.
+    pub fn paused() -> Self {
+        let store = Self::new();
+        store.paused.send_replace(true);
+        store
+    }
+
+    /// Store whose saves always fail.
+    pub fn failing() -> Self {
+        Self {
+            fail: true,
+            ..Self::new()
+        }
+    }
+
+    pub fn resume(&self) {
+        self.paused.send_replace(false);
+    }
+
+    /// Number of `save_events` calls, including failed ones.
+    pub fn save_calls(&self) -> usize {
+        self.save_calls.load(Ordering::SeqCst)
+    }
+}
+
+impl WorkerEventStore for MockEventStore {
+    async fn save_events(&self, events: &[NewWorkerEvent]) -> Result<(), AppError> {
+        self.save_calls.fetch_add(1, Ordering::SeqCst);
+        let _ = self.paused.subscribe().wait_for(|paused| !paused).await;
+        if self.fail {
+            return Err(AppError::DatabaseError("connection refused".into()));
+        }
+        lock(&self.events).extend_from_slice(events);
+        Ok(())
+    }
+}
+
+// ---------------------------------------------------------------------------
+// MockUsageStore
+// ---------------------------------------------------------------------------
+
+/// In-memory per-day usage counters.
+#[derive(Clone, Default)]
+pub struct MockUsageStore {
+    pub days: Arc<Mutex<HashMap<NaiveDate, DailyUsage>>>,
+}
+
+impl MockUsageStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl UsageStore for MockUsageStore {
+    async fn usage_on(&self, day: NaiveDate) -> Result<DailyUsage, AppError> {
+        Ok(lock(&self.days)
+            .get(&day)
+            .copied()
+            .unwrap_or_else(|| DailyUsage::empty(day)))
+    }
+
+    async fn add_usage(&self, increment: &DailyUsage) -> Result<DailyUsage, AppError> {
+        let mut days = lock(&self.days);
+        let usage = days
+            .entry(increment.day)
+            .or_insert_with(|| DailyUsage::empty(increment.day));
+        usage.llm_calls += increment.llm_calls;
+        usage.tokens += increment.tokens;
+        usage.cost_usd += increment.cost_usd;
+        Ok(*usage)
+    }
+}
+
+// ---------------------------------------------------------------------------
+// CapturedLogs
+// ---------------------------------------------------------------------------
+
+/// Log writer that collects everything written through it, for tests that
+/// assert on log output. Use it as a `tracing_subscriber` writer; clones
+/// share the buffer.
+#[derive(Clone, Default)]
+pub struct CapturedLogs(Arc<Mutex<Vec<u8>>>);
+
+impl CapturedLogs {
+    /// Everything written so far.
+    pub fn output(&self) -> String {
+        String::from_utf8(lock(&self.0).clone()).expect("log output is UTF-8")
+    }
+
+    /// The lines written so far.
+    pub fn lines(&self) -> Vec<String> {
+        self.output().lines().map(str::to_string).collect()
+    }
+}
+
+impl io::Write for CapturedLogs {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        lock(&self.0).extend_from_slice(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+impl<'a> MakeWriter<'a> for CapturedLogs {
+    type Writer = CapturedLogs;
+
+    fn make_writer(&'a self) -> Self::Writer {
+        self.clone()
     }
 }
 
@@ -629,6 +1304,9 @@ pub fn make_test_job() -> ScrapeJob {
         max_depth: 0,
         max_pages: 100,
         allowed_domains: Vec::new(),
+        fetch_options: FetchOptions::default(),
+        notify_url: None,
+        tags: Vec::new(),
     }
 }
 
@@ -640,13 +1318,22 @@ pub fn make_test_extraction(data_hash: &str) -> Extraction {
         schema_name: "test_schema".to_string(),
         extracted_data: serde_json::json!({"title": "Test"}),
         content_hash: "abc123".to_string(),
+        content_simhash: None,
         data_hash: data_hash.to_string(),
         model: "test-model".to_string(),
         provider: "openai".to_string(),
+        prompt_hash: None,
+        schema_hash: None,
         schema_version: None,
         latency_ms: None,
         prompt_tokens: None,
         completion_tokens: None,
+        html_snapshot_key: None,
+        markdown_snapshot_key: None,
+        duplicate_of: None,
+        detected_language: None,
+        golden: false,
+        scrubbed_at: None,
         created_at: Utc::now(),
     }
 }
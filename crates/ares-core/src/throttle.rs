@@ -34,6 +34,7 @@ use tokio::sync::Mutex;
 use url::Url;
 
 use crate::error::AppError;
+use crate::fetch::FetchOptions;
 use crate::traits::Fetcher;
 
 /// Configuration for the throttled fetcher.
@@ -84,6 +85,20 @@ impl Default for ThrottleConfig {
     }
 }
 
+/// Extract the domain key from a URL (scheme://host:port).
+///
+/// This is the key throttling is tracked under, and the `domain` that
+/// jobs and extractions are grouped by in per-domain statistics.
+pub fn domain_key(url_str: &str) -> Option<String> {
+    let url = Url::parse(url_str).ok()?;
+    let host = url.host_str()?;
+    let port = url
+        .port_or_known_default()
+        .map(|p| format!(":{p}"))
+        .unwrap_or_default();
+    Some(format!("{}://{}{}", url.scheme(), host, port))
+}
+
 /// A [`Fetcher`] wrapper that enforces per-domain throttling.
 ///
 /// Tracks the last request time for each domain (scheme + host + port)
@@ -108,15 +123,28 @@ impl<F: Fetcher> ThrottledFetcher<F> {
         }
     }
 
-    /// Extract the domain key from a URL (scheme://host:port).
-    fn domain_key(url_str: &str) -> Option<String> {
-        let url = Url::parse(url_str).ok()?;
-        let host = url.host_str()?;
-        let port = url
-            .port_or_known_default()
-            .map(|p| format!(":{p}"))
-            .unwrap_or_default();
-        Some(format!("{}://{}{}", url.scheme(), host, port))
+    /// Forget the last request to `domain`, so the next request to it is
+    /// not delayed. `domain` is either a URL, clearing its
+    /// scheme/host/port key, or a bare host, clearing every key for it.
+    #[cfg_attr(not(test), allow(dead_code))]
+    pub async fn clear_domain(&self, domain: &str) {
+        let mut map = self.last_request.lock().await;
+        match domain_key(domain) {
+            Some(key) => {
+                map.remove(&key);
+            }
+            None => map.retain(|key, _| {
+                Url::parse(key)
+                    .ok()
+                    .is_none_or(|url| url.host_str() != Some(domain))
+            }),
+        }
+    }
+
+    /// Forget the last request to every domain.
+    #[cfg_attr(not(test), allow(dead_code))]
+    pub async fn clear_all(&self) {
+        self.last_request.lock().await.clear();
     }
 
     /// Wait until the per-domain delay has elapsed, then record the
@@ -151,11 +179,18 @@ impl<F: Fetcher> ThrottledFetcher<F> {
 
 impl<F: Fetcher> Fetcher for ThrottledFetcher<F> {
     async fn fetch(&self, url: &str) -> Result<String, AppError> {
-        if let Some(domain) = Self::domain_key(url) {
+        if let Some(domain) = domain_key(url) {
             self.wait_for_domain(&domain).await;
         }
         self.inner.fetch(url).await
     }
+
+    async fn fetch_with(&self, url: &str, options: &FetchOptions) -> Result<String, AppError> {
+        if let Some(domain) = domain_key(url) {
+            self.wait_for_domain(&domain).await;
+        }
+        self.inner.fetch_with(url, options).await
+    }
 }
 
 // ---------------------------------------------------------------------------
@@ -187,25 +222,22 @@ mod tests {
     #[test]
     fn domain_key_extracts_correctly() {
         assert_eq!(
-            ThrottledFetcher::<MockFetcher>::domain_key("https://example.com/path?q=1"),
+            domain_key("https://example.com/path?q=1"),
             Some("https://example.com:443".to_string())
         );
         assert_eq!(
-            ThrottledFetcher::<MockFetcher>::domain_key("http://example.com:8080/page"),
+            domain_key("http://example.com:8080/page"),
             Some("http://example.com:8080".to_string())
         );
         assert_eq!(
-            ThrottledFetcher::<MockFetcher>::domain_key("http://example.com"),
+            domain_key("http://example.com"),
             Some("http://example.com:80".to_string())
         );
     }
 
     #[test]
     fn domain_key_returns_none_for_invalid_url() {
-        assert_eq!(
-            ThrottledFetcher::<MockFetcher>::domain_key("not-a-url"),
-            None
-        );
+        assert_eq!(domain_key("not-a-url"), None);
     }
 
     #[test]
@@ -259,6 +291,45 @@ mod tests {
         );
     }
 
+    #[tokio::test]
+    async fn cleared_domains_are_not_delayed() {
+        let inner = MockFetcher::new("<html>ok</html>");
+        let config = ThrottleConfig::new(Duration::from_secs(5));
+        let fetcher = ThrottledFetcher::new(inner, config);
+
+        let start = Instant::now();
+        fetcher.fetch("http://example.com/page1").await.unwrap();
+        fetcher.clear_domain("http://example.com/").await;
+        fetcher.fetch("http://example.com/page2").await.unwrap();
+        fetcher.clear_domain("example.com").await;
+        fetcher.fetch("http://example.com/page3").await.unwrap();
+        fetcher.fetch("http://other.com/page1").await.unwrap();
+        fetcher.clear_all().await;
+        fetcher.fetch("http://other.com/page2").await.unwrap();
+
+        assert!(
+            start.elapsed() < Duration::from_secs(1),
+            "Cleared domains should not be throttled, elapsed: {:?}",
+            start.elapsed()
+        );
+        assert_eq!(fetcher.last_request.lock().await.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn clearing_a_host_keeps_other_domains() {
+        let fetcher = ThrottledFetcher::new(
+            MockFetcher::new("<html>ok</html>"),
+            ThrottleConfig::new(Duration::ZERO),
+        );
+        fetcher.fetch("http://example.com/").await.unwrap();
+        fetcher.fetch("https://example.com/").await.unwrap();
+        fetcher.fetch("http://other.com/").await.unwrap();
+
+        fetcher.clear_domain("example.com").await;
+        let map = fetcher.last_request.lock().await;
+        assert_eq!(map.keys().collect::<Vec<_>>(), vec!["http://other.com:80"]);
+    }
+
     #[tokio::test]
     async fn throttle_passes_through_fetch_result() {
         let inner = MockFetcher::new("<html>hello</html>");
@@ -1,18 +1,44 @@
 use std::future::Future;
 
+use chrono::{DateTime, Utc};
 use uuid::Uuid;
 
 use crate::error::AppError;
-use crate::models::{Extraction, ExtractionOutcome, NewExtraction};
+use crate::fetch::FetchOptions;
+use crate::models::{
+    ChangeEvent, Extraction, ExtractionEvent, ExtractionOutcome, NewExtraction, SaveOutcome,
+};
 
 /// Fetches raw HTML content from a URL.
 pub trait Fetcher: Send + Sync + Clone {
     fn fetch(&self, url: &str) -> impl Future<Output = Result<String, AppError>> + Send;
+
+    /// Fetch honouring per-page [`FetchOptions`].
+    ///
+    /// The default ignores the options; fetchers override it for the ones
+    /// they support (a timeout override, a selector to wait for).
+    fn fetch_with(
+        &self,
+        url: &str,
+        options: &FetchOptions,
+    ) -> impl Future<Output = Result<String, AppError>> + Send {
+        let _ = options;
+        self.fetch(url)
+    }
 }
 
 /// Converts raw HTML into clean Markdown text.
 pub trait Cleaner: Send + Sync + Clone {
     fn clean(&self, html: &str) -> Result<String, AppError>;
+
+    /// Like [`clean`](Self::clean), keeping only the elements matching the
+    /// CSS `selector`. Cleaners that can't select return an error.
+    fn clean_within(&self, html: &str, selector: &str) -> Result<String, AppError> {
+        let _ = (html, selector);
+        Err(AppError::CleanerError(
+            "content selectors are not supported by this cleaner".to_string(),
+        ))
+    }
 }
 
 /// Extracts structured JSON data from text content using an LLM.
@@ -24,6 +50,13 @@ pub trait Extractor: Send + Sync + Clone {
         content: &str,
         schema: &serde_json::Value,
     ) -> impl Future<Output = Result<ExtractionOutcome, AppError>> + Send;
+
+    /// The effective prompt configuration (system prompt, instructions) this
+    /// extractor sends, hashed into each extraction's `prompt_hash`. `None`
+    /// (the default) when the extractor has no prompt to report.
+    fn fingerprint(&self) -> Option<String> {
+        None
+    }
 }
 
 /// Factory for creating Extractor instances with specific model/base_url.
@@ -44,6 +77,54 @@ pub trait ExtractionStore: Send + Sync + Clone {
         extraction: &NewExtraction,
     ) -> impl Future<Output = Result<Uuid, AppError>> + Send;
 
+    /// Insert an extraction, or update the existing record with the same URL,
+    /// schema, and data hash. Returns that record's UUID.
+    ///
+    /// Defaults to [`save`](Self::save) for stores without in-place updates.
+    fn upsert(
+        &self,
+        extraction: &NewExtraction,
+    ) -> impl Future<Output = Result<Uuid, AppError>> + Send {
+        self.save(extraction)
+    }
+
+    /// Keep the latest extraction of `extraction`'s URL and schema instead of
+    /// saving `extraction`, if their data hashes match. `None` means the data
+    /// changed (or nothing was saved yet), and the caller saves `extraction`
+    /// as usual.
+    ///
+    /// The default checks [`get_latest`](Self::get_latest) and never bumps a
+    /// last-seen time. Stores that can should check and bump in one step, so
+    /// a concurrent write can't slip between.
+    fn keep_if_unchanged(
+        &self,
+        extraction: &NewExtraction,
+    ) -> impl Future<Output = Result<Option<SaveOutcome>, AppError>> + Send {
+        async move {
+            let latest = self
+                .get_latest(&extraction.url, &extraction.schema_name)
+                .await?;
+            Ok(latest
+                .filter(|latest| latest.data_hash == extraction.data_hash)
+                .map(|latest| SaveOutcome::Unchanged {
+                    existing_id: latest.id,
+                    last_seen_bumped: false,
+                }))
+        }
+    }
+
+    /// Store the cleaned content an extraction was made from.
+    ///
+    /// Defaults to discarding it, for stores that don't keep content.
+    fn save_content(
+        &self,
+        extraction_id: Uuid,
+        content: &str,
+    ) -> impl Future<Output = Result<(), AppError>> + Send {
+        let _ = (extraction_id, content);
+        async { Ok(()) }
+    }
+
     /// Get the most recent extraction for a URL + schema pair.
     fn get_latest(
         &self,
@@ -59,6 +140,21 @@ pub trait ExtractionStore: Send + Sync + Clone {
         limit: usize,
         offset: usize,
     ) -> impl Future<Output = Result<Vec<Extraction>, AppError>> + Send;
+
+    /// The earliest extraction with `schema_name`, of any URL, made at or
+    /// after `since` from content hashing to `content_hash`.
+    ///
+    /// Defaults to `None`, for stores that can't search by content; they
+    /// never report duplicates.
+    fn find_by_content_hash(
+        &self,
+        schema_name: &str,
+        content_hash: &str,
+        since: DateTime<Utc>,
+    ) -> impl Future<Output = Result<Option<Extraction>, AppError>> + Send {
+        let _ = (schema_name, content_hash, since);
+        async { Ok(None) }
+    }
 }
 
 /// A no-op ExtractionStore for use when persistence is not needed.
@@ -112,3 +208,81 @@ impl RobotsChecker for NoRobotsChecker {
         true
     }
 }
+
+/// Tells someone that a page's extracted data changed.
+///
+/// Called by [`ScrapeService`](crate::scrape::ScrapeService) after saving an
+/// extraction whose data differs from the previous one. Errors are logged
+/// and never fail the scrape.
+pub trait ChangeNotifier: Send + Sync + Clone {
+    fn notify(&self, change: &ChangeEvent) -> impl Future<Output = Result<(), AppError>> + Send;
+
+    /// A notifier for a job that sends its changes to the webhook at `url`
+    /// instead of the configured one. The default ignores the override.
+    fn with_webhook_override(&self, url: &str) -> Self {
+        let _ = url;
+        self.clone()
+    }
+}
+
+/// Publishes every saved extraction, e.g. to a message bus.
+///
+/// Called by [`ScrapeService`](crate::scrape::ScrapeService) after each
+/// successful save. Publishing is best-effort: errors are logged and never
+/// fail the scrape, so implementations should retry briefly themselves.
+pub trait ExtractionEventPublisher: Send + Sync + Clone {
+    fn publish(&self, event: &ExtractionEvent)
+    -> impl Future<Output = Result<(), AppError>> + Send;
+}
+
+/// A no-op ExtractionEventPublisher for when no bus is configured.
+#[derive(Debug, Clone)]
+pub struct NullPublisher;
+
+impl ExtractionEventPublisher for NullPublisher {
+    async fn publish(&self, _event: &ExtractionEvent) -> Result<(), AppError> {
+        Ok(())
+    }
+}
+
+/// Archives the source content behind extractions, e.g. in object
+/// storage, under keys from [`SnapshotKind::key`](crate::snapshot::SnapshotKind::key).
+pub trait SnapshotStore: Send + Sync + Clone {
+    /// Store `content` under `key`, replacing any object already there.
+    fn put(&self, key: &str, content: &[u8]) -> impl Future<Output = Result<(), AppError>> + Send;
+
+    /// The object under `key`, or `None` if there is none.
+    fn get(&self, key: &str) -> impl Future<Output = Result<Option<Vec<u8>>, AppError>> + Send;
+
+    /// Remove the object under `key`; removing a missing object succeeds.
+    fn delete(&self, key: &str) -> impl Future<Output = Result<(), AppError>> + Send;
+}
+
+/// A no-op SnapshotStore for when snapshots are off. Stores nothing, so
+/// reads find nothing.
+#[derive(Debug, Clone)]
+pub struct NullSnapshotStore;
+
+impl SnapshotStore for NullSnapshotStore {
+    async fn put(&self, _key: &str, _content: &[u8]) -> Result<(), AppError> {
+        Ok(())
+    }
+
+    async fn get(&self, _key: &str) -> Result<Option<Vec<u8>>, AppError> {
+        Ok(None)
+    }
+
+    async fn delete(&self, _key: &str) -> Result<(), AppError> {
+        Ok(())
+    }
+}
+
+/// A no-op ChangeNotifier for when nobody needs telling.
+#[derive(Debug, Clone)]
+pub struct NullNotifier;
+
+impl ChangeNotifier for NullNotifier {
+    async fn notify(&self, _change: &ChangeEvent) -> Result<(), AppError> {
+        Ok(())
+    }
+}
@@ -0,0 +1,276 @@
+//! Regression checks against golden extractions.
+//!
+//! An extraction marked golden is a known-good result for its page. After a
+//! prompt or model change, [`verify_goldens`] re-extracts every golden page
+//! and diffs the new data against the golden data, so a regression shows up
+//! before the change ships. Pages are replayed from their archived HTML when
+//! a snapshot exists, so the check measures the extractor rather than drift
+//! in the live site; pages without one are fetched again.
+
+use serde::Serialize;
+use uuid::Uuid;
+
+use crate::diff::{JsonChange, diff_json};
+use crate::models::Extraction;
+use crate::scrape::ScrapeService;
+use crate::traits::{Cleaner, Extractor, Fetcher, NullStore, SnapshotStore};
+
+/// The outcome of re-extracting one golden page.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct GoldenCheck {
+    pub url: String,
+    /// The golden extraction compared against.
+    pub golden_id: Uuid,
+    /// Whether the page was replayed from its HTML snapshot rather than
+    /// fetched.
+    pub replayed: bool,
+    /// Differences from the golden data to the new data; empty when they
+    /// match.
+    pub changes: Vec<JsonChange>,
+    /// Why the page couldn't be re-extracted, if it couldn't.
+    pub error: Option<String>,
+}
+
+impl GoldenCheck {
+    pub fn passed(&self) -> bool {
+        self.error.is_none() && self.changes.is_empty()
+    }
+}
+
+/// Every golden page of a schema, checked.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct VerifyReport {
+    pub schema_name: String,
+    pub checks: Vec<GoldenCheck>,
+}
+
+impl VerifyReport {
+    /// Whether every page matched its golden data. A schema without golden
+    /// pages passes.
+    pub fn passed(&self) -> bool {
+        self.checks.iter().all(GoldenCheck::passed)
+    }
+
+    /// Pages that didn't match, or couldn't be re-extracted.
+    pub fn failed(&self) -> usize {
+        self.checks.iter().filter(|check| !check.passed()).count()
+    }
+}
+
+/// Re-extract each of `goldens` with `service` and compare the result with
+/// its golden data.
+///
+/// `service` has no store, so nothing is saved. HTML is read from
+/// `snapshots` under each golden's `html_snapshot_key` when archived; a
+/// failure to read it falls back to fetching. Pages are checked one at a
+/// time, and a page that fails doesn't stop the rest.
+pub async fn verify_goldens<F, C, E, A>(
+    service: &ScrapeService<F, C, E, NullStore>,
+    snapshots: &A,
+    goldens: &[Extraction],
+    schema: &serde_json::Value,
+    schema_name: &str,
+) -> VerifyReport
+where
+    F: Fetcher,
+    C: Cleaner,
+    E: Extractor,
+    A: SnapshotStore,
+{
+    let mut checks = Vec::with_capacity(goldens.len());
+    for golden in goldens {
+        let html = archived_html(snapshots, golden).await;
+        let replayed = html.is_some();
+        let result = match &html {
+            Some(html) => {
+                service
+                    .scrape_html(html, &golden.url, schema, schema_name)
+                    .await
+            }
+            None => service.scrape(&golden.url, schema, schema_name).await,
+        };
+
+        let check = match result {
+            Ok(result) => GoldenCheck {
+                url: golden.url.clone(),
+                golden_id: golden.id,
+                replayed,
+                changes: diff_json(&golden.extracted_data, &result.extracted_data),
+                error: None,
+            },
+            Err(e) => GoldenCheck {
+                url: golden.url.clone(),
+                golden_id: golden.id,
+                replayed,
+                changes: Vec::new(),
+                error: Some(e.to_string()),
+            },
+        };
+        if !check.passed() {
+            tracing::warn!(url = %check.url, golden_id = %check.golden_id, "Golden check failed");
+        }
+        checks.push(check);
+    }
+
+    VerifyReport {
+        schema_name: schema_name.to_string(),
+        checks,
+    }
+}
+
+/// The HTML `golden` was extracted from, if it was archived and is still
+/// readable.
+async fn archived_html<A: SnapshotStore>(snapshots: &A, golden: &Extraction) -> Option<String> {
+    let key = golden.html_snapshot_key.as_deref()?;
+    match snapshots.get(key).await {
+        Ok(Some(bytes)) => Some(String::from_utf8_lossy(&bytes).into_owned()),
+        Ok(None) => None,
+        Err(e) => {
+            tracing::warn!(%key, "Failed to read HTML snapshot, fetching the page instead: {e}");
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::AppError;
+    use crate::scrape::ScrapeServiceBuilder;
+    use crate::testutil::{
+        MockCleaner, MockExtractor, MockFetcher, MockSnapshotStore, make_test_extraction,
+    };
+
+    fn golden(url: &str, data: serde_json::Value) -> Extraction {
+        Extraction {
+            url: url.to_string(),
+            extracted_data: data,
+            ..make_test_extraction("golden")
+        }
+    }
+
+    fn service(
+        fetcher: MockFetcher,
+        extractor: MockExtractor,
+    ) -> ScrapeService<MockFetcher, MockCleaner, MockExtractor, NullStore> {
+        ScrapeServiceBuilder::new(fetcher, MockCleaner::passthrough(), extractor)
+            .with_model("test-model")
+            .with_store(NullStore)
+            .build()
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn matching_data_passes_and_changed_data_fails_with_its_diff() {
+        let service = service(
+            MockFetcher::new("<h1>Hello</h1>"),
+            MockExtractor::with_responses(vec![
+                Ok(serde_json::json!({"title": "Hello", "price": 10})),
+                Ok(serde_json::json!({"title": "Hello", "price": 10})),
+            ]),
+        );
+        let goldens = [
+            golden(
+                "https://example.com/a",
+                serde_json::json!({"title": "Hello", "price": 10}),
+            ),
+            golden(
+                "https://example.com/b",
+                serde_json::json!({"title": "Hello", "price": 12}),
+            ),
+        ];
+
+        let report = verify_goldens(
+            &service,
+            &MockSnapshotStore::new(),
+            &goldens,
+            &serde_json::json!({"type": "object"}),
+            "test",
+        )
+        .await;
+
+        assert!(!report.passed());
+        assert_eq!(report.failed(), 1);
+        assert!(report.checks[0].passed());
+        assert!(!report.checks[0].replayed);
+        assert_eq!(
+            report.checks[1].changes,
+            vec![JsonChange::Changed {
+                path: "price".to_string(),
+                old: serde_json::json!(12),
+                new: serde_json::json!(10),
+            }]
+        );
+    }
+
+    #[tokio::test]
+    async fn archived_html_is_replayed_instead_of_fetched() {
+        let snapshots = MockSnapshotStore::new();
+        snapshots
+            .put("html/golden.html", b"<h1>Archived</h1>")
+            .await
+            .unwrap();
+        let service = service(
+            MockFetcher::with_error(AppError::HttpError("site is down".into())),
+            MockExtractor::new(serde_json::json!({"title": "Archived"})),
+        );
+        let goldens = [Extraction {
+            html_snapshot_key: Some("html/golden.html".to_string()),
+            ..golden(
+                "https://example.com/a",
+                serde_json::json!({"title": "Archived"}),
+            )
+        }];
+
+        let report = verify_goldens(
+            &service,
+            &snapshots,
+            &goldens,
+            &serde_json::json!({"type": "object"}),
+            "test",
+        )
+        .await;
+
+        assert!(report.passed());
+        assert!(report.checks[0].replayed);
+    }
+
+    #[tokio::test]
+    async fn a_page_that_cannot_be_extracted_fails_without_stopping_the_rest() {
+        let service = service(
+            MockFetcher::with_responses(vec![
+                Err(AppError::HttpError("404".into())),
+                Ok("<h1>B</h1>".to_string()),
+            ]),
+            MockExtractor::new(serde_json::json!({"title": "B"})),
+        );
+        let goldens = [
+            golden("https://example.com/a", serde_json::json!({"title": "A"})),
+            golden("https://example.com/b", serde_json::json!({"title": "B"})),
+        ];
+
+        let report = verify_goldens(
+            &service,
+            &MockSnapshotStore::new(),
+            &goldens,
+            &serde_json::json!({"type": "object"}),
+            "test",
+        )
+        .await;
+
+        assert_eq!(report.failed(), 1);
+        assert!(report.checks[0].error.as_deref().unwrap().contains("404"));
+        assert!(report.checks[0].changes.is_empty());
+        assert!(report.checks[1].passed());
+    }
+
+    #[test]
+    fn an_empty_golden_set_passes() {
+        let report = VerifyReport {
+            schema_name: "test".to_string(),
+            checks: Vec::new(),
+        };
+        assert!(report.passed());
+        assert_eq!(report.failed(), 0);
+    }
+}
@@ -1,18 +1,31 @@
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex, MutexGuard, PoisonError};
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+use serde::Serialize;
 use tokio_util::sync::CancellationToken;
 use url::Url;
 use uuid::Uuid;
 
 use crate::cache::{ContentCache, ExtractionCache};
-use crate::circuit_breaker::{CircuitBreaker, CircuitBreakerError};
+use crate::circuit_breaker::{CircuitBreaker, CircuitBreakerError, CircuitState};
 use crate::error::AppError;
 use crate::job::{CreateScrapeJobRequest, ScrapeJob, WorkerConfig};
 use crate::job_queue::JobQueue;
-use crate::scrape::ScrapeService;
+use crate::models::{Extraction, NewExtraction, SaveOutcome};
+use crate::schema::{ResolvedSchema, SchemaResolver};
+use crate::scrape::{ScrapeService, ScrapeServiceBuilder};
+use crate::snapshot::SnapshotKind;
 use crate::traits::{
-    Cleaner, ExtractionStore, ExtractorFactory, Fetcher, LinkDiscoverer, RobotsChecker,
+    ChangeNotifier, Cleaner, ExtractionEventPublisher, ExtractionStore, ExtractorFactory, Fetcher,
+    LinkDiscoverer, NullNotifier, NullPublisher, NullSnapshotStore, RobotsChecker, SnapshotStore,
 };
 
 /// Events emitted by the worker for monitoring/logging.
+///
+/// Variants gain fields as reporters need more detail, so matches outside
+/// this crate should use `..` in their patterns.
 #[derive(Debug, Clone)]
 pub enum WorkerEvent<'a> {
     Started {
@@ -24,14 +37,27 @@ pub enum WorkerEvent<'a> {
     },
     JobStarted {
         job_id: Uuid,
+        /// [`ScrapeJob::display_name`] of the job.
+        job_name: &'a str,
         url: &'a str,
     },
     JobCompleted {
         job_id: Uuid,
         extraction_id: Option<Uuid>,
+        /// Time from the job starting to its completion.
+        duration: Duration,
+        /// Whether the extracted data differs from the previous extraction
+        /// of the page, when known.
+        changed: Option<bool>,
+        /// Size of the fetched page.
+        content_bytes: usize,
+        model: &'a str,
+        /// ISO 639-3 code of the page content's language, when detected.
+        detected_language: Option<&'a str>,
     },
+    /// `job.next_retry_at` is set when a retry is scheduled.
     JobFailed {
-        job_id: Uuid,
+        job: &'a ScrapeJob,
         error: &'a str,
         will_retry: bool,
     },
@@ -45,10 +71,20 @@ pub enum WorkerEvent<'a> {
 }
 
 /// Trait for receiving worker events (decoupled logging).
+///
+/// Every method has a no-op default, so reporters implement only what they
+/// need. Newer notifications get their own method rather than a
+/// [`WorkerEvent`] variant, so adding one doesn't touch existing reporters.
 pub trait WorkerReporter: Send + Sync {
     fn report(&self, event: WorkerEvent<'_>) {
         let _ = event;
     }
+
+    /// The worker's circuit breaker `name` moved to `state`, through failures,
+    /// recovery, or an operator reset.
+    fn on_circuit_breaker_change(&self, name: &str, state: CircuitState) {
+        let _ = (name, state);
+    }
 }
 
 /// Reporter that uses the `tracing` crate.
@@ -65,24 +101,58 @@ impl WorkerReporter for TracingWorkerReporter {
                 tracing::debug!("Polling for jobs");
             }
             WorkerEvent::JobClaimed { job } => {
-                tracing::info!(job_id = %job.id, url = %job.url, "Job claimed");
+                tracing::info!(
+                    job_id = %job.id,
+                    job_name = %job.display_name(),
+                    url = %job.url,
+                    "Job claimed"
+                );
             }
-            WorkerEvent::JobStarted { job_id, url } => {
-                tracing::info!(%job_id, %url, "Processing job");
+            WorkerEvent::JobStarted {
+                job_id,
+                job_name,
+                url,
+            } => {
+                tracing::info!(%job_id, %job_name, %url, "Processing job");
             }
             WorkerEvent::JobCompleted {
                 job_id,
                 extraction_id,
+                duration,
+                changed,
+                content_bytes,
+                model,
+                detected_language,
             } => {
-                tracing::info!(%job_id, ?extraction_id, "Job completed");
+                let duration_ms = duration.as_millis() as u64;
+                tracing::info!(
+                    %job_id,
+                    ?extraction_id,
+                    duration_ms,
+                    ?changed,
+                    content_bytes,
+                    %model,
+                    ?detected_language,
+                    "Job completed"
+                );
             }
             WorkerEvent::JobFailed {
-                job_id,
+                job,
                 error,
                 will_retry,
-            } => {
-                tracing::warn!(%job_id, %error, %will_retry, "Job failed");
-            }
+            } => match job.time_to_retry().filter(|_| will_retry) {
+                Some(retry_in) => {
+                    let retry_in_secs = retry_in.as_secs();
+                    tracing::warn!(
+                        job_id = %job.id,
+                        %error,
+                        %will_retry,
+                        retry_in_secs,
+                        "Job failed, retrying in {retry_in_secs}s"
+                    );
+                }
+                None => tracing::warn!(job_id = %job.id, %error, %will_retry, "Job failed"),
+            },
             WorkerEvent::ShuttingDown {
                 worker_id,
                 jobs_released,
@@ -96,9 +166,254 @@ impl WorkerReporter for TracingWorkerReporter {
     }
 }
 
+/// How processing a single job ended.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum JobOutcome {
+    Completed,
+    /// Failed, with a retry scheduled.
+    Retrying,
+    /// Failed permanently.
+    Failed,
+}
+
+/// Counts of the jobs a [`WorkerService::run`] call processed.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+pub struct WorkerRunSummary {
+    pub processed: u64,
+    pub completed: u64,
+    pub retrying: u64,
+    pub failed: u64,
+}
+
+impl WorkerRunSummary {
+    fn record(&mut self, outcome: JobOutcome) {
+        self.processed += 1;
+        match outcome {
+            JobOutcome::Completed => self.completed += 1,
+            JobOutcome::Retrying => self.retrying += 1,
+            JobOutcome::Failed => self.failed += 1,
+        }
+    }
+}
+
+/// Live view of a worker, updated by its run loop and read by health checks.
+///
+/// Clones share the same state, so a handle taken with
+/// [`WorkerService::status`] keeps tracking the worker while it runs.
+#[derive(Debug, Clone)]
+pub struct WorkerStatus {
+    inner: Arc<Mutex<WorkerStatusSnapshot>>,
+}
+
+/// Point-in-time copy of a [`WorkerStatus`].
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct WorkerStatusSnapshot {
+    pub worker_id: String,
+    /// Whether the run loop is active.
+    pub running: bool,
+    pub started_at: Option<DateTime<Utc>>,
+    /// When the queue last answered a claim, with or without a job.
+    pub last_poll_at: Option<DateTime<Utc>>,
+    /// The job being processed, if any.
+    pub current_job: Option<Uuid>,
+    /// When the worker claimed [`current_job`](Self::current_job).
+    pub current_job_started_at: Option<DateTime<Utc>>,
+    pub circuit_breaker: CircuitState,
+    /// Jobs processed since the worker started.
+    pub jobs: WorkerRunSummary,
+}
+
+impl WorkerStatusSnapshot {
+    /// `true` while the loop is running and either has polled the queue
+    /// within `max_poll_age` or is busy with a job claimed within
+    /// `max_job_age`. A job's fetch and LLM timeouts, not the poll interval,
+    /// bound how long it may run, so `max_job_age` should cover both plus
+    /// some slack; a job older than that means the loop is stuck.
+    pub fn is_polling(
+        &self,
+        max_poll_age: Duration,
+        max_job_age: Duration,
+        now: DateTime<Utc>,
+    ) -> bool {
+        if !self.running {
+            return false;
+        }
+        let within = |at: DateTime<Utc>, max_age: Duration| {
+            now - at <= chrono::Duration::from_std(max_age).unwrap_or(chrono::Duration::MAX)
+        };
+        if self.current_job.is_some() {
+            return self
+                .current_job_started_at
+                .is_some_and(|at| within(at, max_job_age));
+        }
+        self.last_poll_at.is_some_and(|at| within(at, max_poll_age))
+    }
+}
+
+impl WorkerStatus {
+    fn new(worker_id: &str) -> Self {
+        Self {
+            inner: Arc::new(Mutex::new(WorkerStatusSnapshot {
+                worker_id: worker_id.to_string(),
+                running: false,
+                started_at: None,
+                last_poll_at: None,
+                current_job: None,
+                current_job_started_at: None,
+                circuit_breaker: CircuitState::Closed,
+                jobs: WorkerRunSummary::default(),
+            })),
+        }
+    }
+
+    pub fn snapshot(&self) -> WorkerStatusSnapshot {
+        self.lock().clone()
+    }
+
+    fn update(&self, f: impl FnOnce(&mut WorkerStatusSnapshot)) {
+        f(&mut self.lock());
+    }
+
+    fn lock(&self) -> MutexGuard<'_, WorkerStatusSnapshot> {
+        self.inner
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+/// What the worker last exchanged with the queue about its circuit breaker.
+#[derive(Debug, Default)]
+struct BreakerSync {
+    /// Whether the queue has been checked at least once. Reset requests made
+    /// before the worker started are not applied to its fresh breaker.
+    primed: bool,
+    /// The most recent reset request already applied.
+    applied_reset: Option<DateTime<Utc>>,
+    /// The last state published, to skip redundant writes.
+    published: Option<(CircuitState, u32, u32, Option<String>)>,
+    /// The last state handed to the reporter.
+    reported: Option<CircuitState>,
+}
+
+/// The scrape service a worker runs a job with, saving through a
+/// [`JobCompletingStore`], or why it couldn't be built.
+type JobScrapeServiceResult<F, C, E, Q, S, N, P, A> =
+    Result<ScrapeService<F, C, E, JobCompletingStore<Q, S>, N, P, A>, AppError>;
+
+/// The store a job's scrape saves through: saving the extraction also
+/// completes the job, via [`JobQueue::complete_job_with_extraction`], so a
+/// job is not left running with its extraction saved.
+#[derive(Clone)]
+struct JobCompletingStore<Q, S> {
+    queue: Q,
+    store: S,
+    job_id: Uuid,
+    /// The extraction the job was completed with, once saved.
+    completed_with: Arc<Mutex<Option<Uuid>>>,
+}
+
+impl<Q: JobQueue, S: ExtractionStore> JobCompletingStore<Q, S> {
+    fn new(queue: Q, store: S, job_id: Uuid) -> Self {
+        Self {
+            queue,
+            store,
+            job_id,
+            completed_with: Arc::default(),
+        }
+    }
+
+    /// Whether saving an extraction already completed the job.
+    fn job_completed(&self) -> bool {
+        self.completed_with
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .is_some()
+    }
+
+    async fn save_and_complete(
+        &self,
+        extraction: &NewExtraction,
+        upsert: bool,
+    ) -> Result<Uuid, AppError> {
+        let id = self
+            .queue
+            .complete_job_with_extraction(self.job_id, &self.store, extraction, upsert)
+            .await?;
+        *self
+            .completed_with
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner) = Some(id);
+        Ok(id)
+    }
+}
+
+impl<Q: JobQueue, S: ExtractionStore> ExtractionStore for JobCompletingStore<Q, S> {
+    async fn save(&self, extraction: &NewExtraction) -> Result<Uuid, AppError> {
+        self.save_and_complete(extraction, false).await
+    }
+
+    async fn upsert(&self, extraction: &NewExtraction) -> Result<Uuid, AppError> {
+        self.save_and_complete(extraction, true).await
+    }
+
+    // Left to the inner store so its check stays atomic; a kept extraction
+    // saves nothing, so the worker completes the job afterwards.
+    async fn keep_if_unchanged(
+        &self,
+        extraction: &NewExtraction,
+    ) -> Result<Option<SaveOutcome>, AppError> {
+        self.store.keep_if_unchanged(extraction).await
+    }
+
+    async fn save_content(&self, extraction_id: Uuid, content: &str) -> Result<(), AppError> {
+        self.store.save_content(extraction_id, content).await
+    }
+
+    async fn get_latest(
+        &self,
+        url: &str,
+        schema_name: &str,
+    ) -> Result<Option<Extraction>, AppError> {
+        self.store.get_latest(url, schema_name).await
+    }
+
+    async fn get_history(
+        &self,
+        url: &str,
+        schema_name: &str,
+        limit: usize,
+        offset: usize,
+    ) -> Result<Vec<Extraction>, AppError> {
+        self.store
+            .get_history(url, schema_name, limit, offset)
+            .await
+    }
+
+    async fn find_by_content_hash(
+        &self,
+        schema_name: &str,
+        content_hash: &str,
+        since: DateTime<Utc>,
+    ) -> Result<Option<Extraction>, AppError> {
+        self.store
+            .find_by_content_hash(schema_name, content_hash, since)
+            .await
+    }
+}
+
 /// Worker that polls the job queue and processes scrape jobs.
-pub struct WorkerService<Q, F, C, EF, S, LD, RC>
-where
+pub struct WorkerService<
+    Q,
+    F,
+    C,
+    EF,
+    S,
+    LD,
+    RC,
+    N = NullNotifier,
+    P = NullPublisher,
+    A = NullSnapshotStore,
+> where
     Q: JobQueue,
     F: Fetcher,
     C: Cleaner,
@@ -106,6 +421,9 @@ where
     S: ExtractionStore,
     LD: LinkDiscoverer,
     RC: RobotsChecker,
+    N: ChangeNotifier,
+    P: ExtractionEventPublisher,
+    A: SnapshotStore,
 {
     queue: Q,
     fetcher: F,
@@ -118,6 +436,12 @@ where
     config: WorkerConfig,
     content_cache: Option<ContentCache>,
     extraction_cache: Option<ExtractionCache>,
+    breaker_sync: Mutex<BreakerSync>,
+    status: WorkerStatus,
+    notifier: N,
+    publisher: P,
+    snapshots: A,
+    snapshot_kinds: Vec<SnapshotKind>,
 }
 
 impl<Q, F, C, EF, S, LD, RC> WorkerService<Q, F, C, EF, S, LD, RC>
@@ -142,6 +466,7 @@ where
         circuit_breaker: CircuitBreaker,
         config: WorkerConfig,
     ) -> Self {
+        let status = WorkerStatus::new(&config.worker_id);
         Self {
             queue,
             fetcher,
@@ -154,6 +479,108 @@ where
             config,
             content_cache: None,
             extraction_cache: None,
+            breaker_sync: Mutex::new(BreakerSync::default()),
+            status,
+            notifier: NullNotifier,
+            publisher: NullPublisher,
+            snapshots: NullSnapshotStore,
+            snapshot_kinds: Vec::new(),
+        }
+    }
+}
+
+impl<Q, F, C, EF, S, LD, RC, N, P, A> WorkerService<Q, F, C, EF, S, LD, RC, N, P, A>
+where
+    Q: JobQueue,
+    F: Fetcher,
+    C: Cleaner,
+    EF: ExtractorFactory,
+    S: ExtractionStore,
+    LD: LinkDiscoverer,
+    RC: RobotsChecker,
+    N: ChangeNotifier,
+    P: ExtractionEventPublisher,
+    A: SnapshotStore,
+{
+    /// Tell `notifier` when a job's extracted data changed. A job's
+    /// `notify_url` replaces the notifier's webhook for that job.
+    pub fn with_notifier<N2: ChangeNotifier>(
+        self,
+        notifier: N2,
+    ) -> WorkerService<Q, F, C, EF, S, LD, RC, N2, P, A> {
+        WorkerService {
+            queue: self.queue,
+            fetcher: self.fetcher,
+            cleaner: self.cleaner,
+            extractor_factory: self.extractor_factory,
+            store: self.store,
+            link_discoverer: self.link_discoverer,
+            robots_checker: self.robots_checker,
+            circuit_breaker: self.circuit_breaker,
+            config: self.config,
+            content_cache: self.content_cache,
+            extraction_cache: self.extraction_cache,
+            breaker_sync: self.breaker_sync,
+            status: self.status,
+            notifier,
+            publisher: self.publisher,
+            snapshots: self.snapshots,
+            snapshot_kinds: self.snapshot_kinds,
+        }
+    }
+
+    /// Publish an [`ExtractionEvent`](crate::models::ExtractionEvent) for
+    /// every extraction a job saves.
+    pub fn with_publisher<P2: ExtractionEventPublisher>(
+        self,
+        publisher: P2,
+    ) -> WorkerService<Q, F, C, EF, S, LD, RC, N, P2, A> {
+        WorkerService {
+            queue: self.queue,
+            fetcher: self.fetcher,
+            cleaner: self.cleaner,
+            extractor_factory: self.extractor_factory,
+            store: self.store,
+            link_discoverer: self.link_discoverer,
+            robots_checker: self.robots_checker,
+            circuit_breaker: self.circuit_breaker,
+            config: self.config,
+            content_cache: self.content_cache,
+            extraction_cache: self.extraction_cache,
+            breaker_sync: self.breaker_sync,
+            status: self.status,
+            notifier: self.notifier,
+            publisher,
+            snapshots: self.snapshots,
+            snapshot_kinds: self.snapshot_kinds,
+        }
+    }
+
+    /// Archive the `kinds` of source content behind every extraction a job
+    /// saves; see [`ScrapeService::with_snapshots`].
+    pub fn with_snapshots<A2: SnapshotStore>(
+        self,
+        store: A2,
+        kinds: &[SnapshotKind],
+    ) -> WorkerService<Q, F, C, EF, S, LD, RC, N, P, A2> {
+        WorkerService {
+            queue: self.queue,
+            fetcher: self.fetcher,
+            cleaner: self.cleaner,
+            extractor_factory: self.extractor_factory,
+            store: self.store,
+            link_discoverer: self.link_discoverer,
+            robots_checker: self.robots_checker,
+            circuit_breaker: self.circuit_breaker,
+            config: self.config,
+            content_cache: self.content_cache,
+            extraction_cache: self.extraction_cache,
+            breaker_sync: self.breaker_sync,
+            status: self.status,
+            notifier: self.notifier,
+            publisher: self.publisher,
+            snapshots: store,
+            snapshot_kinds: kinds.to_vec(),
         }
     }
 
@@ -168,28 +595,73 @@ where
         self
     }
 
-    /// Run the worker loop until cancellation.
+    /// Validate extracted data against the schema files in `dir`; see
+    /// [`WorkerConfig::schemas_dir`].
+    pub fn with_schemas_dir(mut self, dir: impl Into<PathBuf>) -> Self {
+        self.config.schemas_dir = Some(dir.into());
+        self
+    }
+
+    /// A handle on this worker's live status, for health checks.
+    pub fn status(&self) -> WorkerStatus {
+        self.status.clone()
+    }
+
+    /// Run the worker loop until cancellation, or until the queue is drained
+    /// ([`WorkerConfig::run_once`]) or [`WorkerConfig::max_jobs`] jobs have
+    /// been processed.
     pub async fn run<WR: WorkerReporter>(
         &self,
         cancel_token: CancellationToken,
         reporter: &WR,
-    ) -> Result<(), AppError> {
+    ) -> Result<WorkerRunSummary, AppError> {
         reporter.report(WorkerEvent::Started {
             worker_id: &self.config.worker_id,
         });
+        self.status.update(|s| {
+            s.running = true;
+            s.started_at = Some(Utc::now());
+        });
 
+        let mut summary = WorkerRunSummary::default();
         loop {
             if cancel_token.is_cancelled() {
                 break;
             }
+            if let Some(max_jobs) = self.config.max_jobs
+                && summary.processed >= max_jobs
+            {
+                break;
+            }
 
+            self.sync_circuit_breaker(reporter).await;
             reporter.report(WorkerEvent::Polling);
 
-            match self.queue.claim_job(&self.config.worker_id).await {
+            let claimed = self.queue.claim_job(&self.config.worker_id).await;
+            if let Ok(job) = &claimed {
+                let job_id = job.as_ref().map(|j| j.id);
+                self.status.update(|s| {
+                    let now = Utc::now();
+                    s.last_poll_at = Some(now);
+                    s.current_job = job_id;
+                    s.current_job_started_at = job_id.map(|_| now);
+                });
+            }
+
+            match claimed {
                 Ok(Some(job)) => {
                     reporter.report(WorkerEvent::JobClaimed { job: &job });
-                    self.process_job(&job, reporter).await;
+                    let outcome = self.process_job(&job, reporter).await;
+                    self.report_circuit_breaker_change(reporter);
+                    summary.record(outcome);
+                    self.status.update(|s| {
+                        s.current_job = None;
+                        s.current_job_started_at = None;
+                        s.jobs.record(outcome);
+                        s.circuit_breaker = self.circuit_breaker.state();
+                    });
                 }
+                Ok(None) if self.config.run_once => break,
                 Ok(None) => {
                     tokio::select! {
                         () = tokio::time::sleep(self.config.poll_interval) => {}
@@ -220,44 +692,165 @@ where
         reporter.report(WorkerEvent::Stopped {
             worker_id: &self.config.worker_id,
         });
+        self.status.update(|s| s.running = false);
+
+        Ok(summary)
+    }
+
+    /// Apply any reset an operator requested through the queue, report a state
+    /// change, then publish the circuit breaker's state if it changed since
+    /// the last call.
+    async fn sync_circuit_breaker<WR: WorkerReporter>(&self, reporter: &WR) {
+        let name = self.circuit_breaker.name();
+        let requested = match self.queue.circuit_breaker_reset_requested_at(name).await {
+            Ok(requested) => requested,
+            Err(e) => {
+                tracing::warn!(circuit = %name, error = %e, "Failed to check for circuit breaker reset");
+                None
+            }
+        };
 
-        Ok(())
+        {
+            let mut sync = self.lock_breaker_sync();
+            if requested > sync.applied_reset {
+                if sync.primed {
+                    self.circuit_breaker.reset();
+                }
+                sync.applied_reset = requested;
+            }
+            sync.primed = true;
+        }
+        self.report_circuit_breaker_change(reporter);
+
+        let stats = {
+            let mut sync = self.lock_breaker_sync();
+            let stats = self.circuit_breaker.stats();
+            self.status.update(|s| s.circuit_breaker = stats.state);
+            let key = (
+                stats.state,
+                stats.failure_count,
+                stats.success_count,
+                stats.last_error.clone(),
+            );
+            if sync.published.as_ref() == Some(&key) {
+                return;
+            }
+            sync.published = Some(key);
+            stats
+        };
+
+        if let Err(e) = self.queue.save_circuit_breaker(&stats).await {
+            tracing::warn!(circuit = %name, error = %e, "Failed to publish circuit breaker state");
+            self.lock_breaker_sync().published = None;
+        }
+    }
+
+    /// Tell `reporter` if the circuit breaker's state differs from the last
+    /// one it saw. The first call only records the starting state.
+    fn report_circuit_breaker_change<WR: WorkerReporter>(&self, reporter: &WR) {
+        let state = self.circuit_breaker.state();
+        let previous = self.lock_breaker_sync().reported.replace(state);
+        if previous.is_some_and(|previous| previous != state) {
+            reporter.on_circuit_breaker_change(self.circuit_breaker.name(), state);
+        }
+    }
+
+    fn lock_breaker_sync(&self) -> MutexGuard<'_, BreakerSync> {
+        self.breaker_sync
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    /// The job's schema file in [`WorkerConfig::schemas_dir`], if one is
+    /// configured, for the scrape to validate its output against. A schema
+    /// name without a version resolves to its latest version. Jobs whose
+    /// schema is not in the directory (e.g. created from a file elsewhere)
+    /// are only checked against the schema stored with the job.
+    fn schema_file(&self, job: &ScrapeJob) -> Option<ResolvedSchema> {
+        let dir = self.config.schemas_dir.as_ref()?;
+        let schema_ref = if job.schema_name.contains('@') {
+            job.schema_name.clone()
+        } else {
+            format!("{}@latest", job.schema_name)
+        };
+        match SchemaResolver::new(dir).resolve(&schema_ref) {
+            Ok(resolved) => Some(resolved),
+            Err(e) => {
+                tracing::warn!(
+                    job_id = %job.id,
+                    schema = %schema_ref,
+                    error = %e,
+                    "Schema file not found; skipping schema file validation"
+                );
+                None
+            }
+        }
+    }
+
+    /// The scrape service for `job`, configured from the worker's settings,
+    /// saving through `store`.
+    fn scrape_service(
+        &self,
+        job: &ScrapeJob,
+        extractor: EF::Extractor,
+        store: JobCompletingStore<Q, S>,
+    ) -> JobScrapeServiceResult<F, C, EF::Extractor, Q, S, N, P, A> {
+        ScrapeServiceBuilder::new(self.fetcher.clone(), self.cleaner.clone(), extractor)
+            .with_model(job.model.clone())
+            .with_store(store)
+            .with_skip_unchanged(self.config.skip_unchanged)
+            .with_upsert(self.config.upsert)
+            .with_store_content(self.config.store_content)
+            .with_provider(self.config.provider.clone())
+            .with_fetch_options(job.fetch_options.clone())
+            .with_schema_file(self.schema_file(job))
+            .with_caches(self.content_cache.clone(), self.extraction_cache.clone())
+            .with_notifier(match &job.notify_url {
+                Some(url) => self.notifier.with_webhook_override(url),
+                None => self.notifier.clone(),
+            })
+            .with_publisher(self.publisher.clone())
+            .with_snapshots(self.snapshots.clone(), &self.snapshot_kinds)
+            .build()
     }
 
     /// Process a single job. Public for testing purposes.
-    pub async fn process_job<WR: WorkerReporter>(&self, job: &ScrapeJob, reporter: &WR) {
+    #[tracing::instrument(name = "job", skip_all, fields(job_id = %job.id, worker_id = %self.config.worker_id))]
+    pub async fn process_job<WR: WorkerReporter>(
+        &self,
+        job: &ScrapeJob,
+        reporter: &WR,
+    ) -> JobOutcome {
         reporter.report(WorkerEvent::JobStarted {
             job_id: job.id,
+            job_name: &job.display_name(),
             url: &job.url,
         });
-
-        // Create extractor for this job's model/base_url
-        let extractor = match self.extractor_factory.create(&job.model, &job.base_url) {
-            Ok(e) => e,
+        let started = std::time::Instant::now();
+
+        // Create an extractor for this job's model/base_url, and a
+        // ScrapeService around it
+        let store = JobCompletingStore::new(self.queue.clone(), self.store.clone(), job.id);
+        let service = match self
+            .extractor_factory
+            .create(&job.model, &job.base_url)
+            .and_then(|extractor| self.scrape_service(job, extractor, store.clone()))
+        {
+            Ok(service) => service,
             Err(e) => {
                 let error_msg = e.to_string();
                 reporter.report(WorkerEvent::JobFailed {
-                    job_id: job.id,
+                    job,
                     error: &error_msg,
                     will_retry: false,
                 });
-                let _ = self.queue.fail_job(job.id, &error_msg, None).await;
-                return;
+                if let Err(e) = self.queue.fail_job(job.id, &error_msg, None).await {
+                    tracing::error!(job_id = %job.id, error = %e, "Failed to mark job as failed");
+                }
+                return JobOutcome::Failed;
             }
         };
 
-        // Build ScrapeService for this job
-        let service = ScrapeService::with_store(
-            self.fetcher.clone(),
-            self.cleaner.clone(),
-            extractor,
-            self.store.clone(),
-            job.model.clone(),
-        )
-        .with_skip_unchanged(self.config.skip_unchanged)
-        .with_provider(self.config.provider.clone())
-        .with_caches(self.content_cache.clone(), self.extraction_cache.clone());
-
         // Wrap in circuit breaker
         let result = self
             .circuit_breaker
@@ -273,11 +866,19 @@ where
                 reporter.report(WorkerEvent::JobCompleted {
                     job_id: job.id,
                     extraction_id: scrape_result.extraction_id,
+                    duration: started.elapsed(),
+                    changed: Some(scrape_result.changed),
+                    content_bytes: scrape_result.raw_html.as_ref().map_or(0, |html| html.len()),
+                    model: &job.model,
+                    detected_language: scrape_result.detected_language.as_deref(),
                 });
-                if let Err(e) = self
-                    .queue
-                    .complete_job(job.id, scrape_result.extraction_id)
-                    .await
+                // Saving the extraction completed the job, unless nothing was
+                // saved (e.g. unchanged data with skip_unchanged).
+                if !store.job_completed()
+                    && let Err(e) = self
+                        .queue
+                        .complete_job(job.id, scrape_result.extraction_id)
+                        .await
                 {
                     tracing::error!(job_id = %job.id, error = %e, "Failed to mark job completed");
                 }
@@ -374,7 +975,10 @@ where
                                         .with_crawl_config(
                                             job.max_pages,
                                             job.allowed_domains.clone(),
-                                        );
+                                        )
+                                        .with_fetch_options(job.fetch_options.clone())
+                                        .with_notify_url(job.notify_url.clone())
+                                        .with_tags(job.tags.clone());
 
                                         if let Err(e) = self.queue.create_job(request).await {
                                             tracing::error!(
@@ -404,6 +1008,25 @@ where
                         }
                     }
                 }
+                JobOutcome::Completed
+            }
+            // A spent budget isn't the job's fault: run it again once the
+            // budget resets, without using up one of its retries.
+            Err(CircuitBreakerError::Inner(e @ AppError::BudgetExceeded { resets_at, .. })) => {
+                let error_msg = e.to_string();
+                reporter.report(WorkerEvent::JobFailed {
+                    job: &ScrapeJob {
+                        next_retry_at: Some(resets_at),
+                        ..job.clone()
+                    },
+                    error: &error_msg,
+                    will_retry: true,
+                });
+
+                if let Err(e) = self.queue.defer_job(job.id, &error_msg, resets_at).await {
+                    tracing::error!(job_id = %job.id, error = %e, "Failed to defer job");
+                }
+                JobOutcome::Retrying
             }
             Err(circuit_err) => {
                 let (error_msg, is_retryable) = match &circuit_err {
@@ -421,21 +1044,25 @@ where
                 };
 
                 let can_retry = job.can_retry() && is_retryable;
+                let next_retry =
+                    can_retry.then(|| job.calculate_next_retry(&self.config.retry_config));
                 reporter.report(WorkerEvent::JobFailed {
-                    job_id: job.id,
+                    job: &ScrapeJob {
+                        next_retry_at: next_retry,
+                        ..job.clone()
+                    },
                     error: &error_msg,
                     will_retry: can_retry,
                 });
 
-                let next_retry = if can_retry {
-                    Some(job.calculate_next_retry(&self.config.retry_config))
-                } else {
-                    None
-                };
-
                 if let Err(e) = self.queue.fail_job(job.id, &error_msg, next_retry).await {
                     tracing::error!(job_id = %job.id, error = %e, "Failed to mark job as failed");
                 }
+                if can_retry {
+                    JobOutcome::Retrying
+                } else {
+                    JobOutcome::Failed
+                }
             }
         }
     }
@@ -445,6 +1072,7 @@ where
 mod tests {
     use super::*;
     use crate::circuit_breaker::CircuitBreakerConfig;
+    use crate::fetch::FetcherSelector;
     use crate::job::{RetryConfig, WorkerConfig};
     use crate::testutil::*;
     use std::time::Duration;
@@ -455,7 +1083,12 @@ mod tests {
             poll_interval: Duration::from_millis(10),
             retry_config: RetryConfig::default(),
             skip_unchanged: false,
+            upsert: false,
+            store_content: false,
             provider: "openai".to_string(),
+            max_jobs: None,
+            run_once: false,
+            schemas_dir: None,
         }
     }
 
@@ -491,6 +1124,16 @@ mod tests {
         let events = reporter.events.lock().unwrap();
         assert!(events.contains(&"JobStarted".to_string()));
         assert!(events.contains(&"JobCompleted".to_string()));
+
+        let completions = reporter.completions.lock().unwrap();
+        assert_eq!(completions.len(), 1);
+        assert_eq!(completions[0].job_id, job.id);
+        assert_eq!(completions[0].extraction_id, completed[0].1);
+        assert_eq!(completions[0].changed, Some(true));
+        assert_eq!(completions[0].content_bytes, "<html>hi</html>".len());
+        assert_eq!(completions[0].model, job.model);
+        // Too little content to tell its language.
+        assert_eq!(completions[0].detected_language, None);
     }
 
     #[tokio::test]
@@ -553,6 +1196,85 @@ mod tests {
         );
     }
 
+    #[tokio::test]
+    async fn blocked_fetch_fails_permanently_with_the_hint() {
+        let job = make_test_job();
+        let queue = MockJobQueue::with_job(job.clone());
+        let reporter = MockReporter::new();
+
+        let worker = WorkerService::new(
+            queue.clone(),
+            MockFetcher::with_error(AppError::Blocked {
+                url: job.url.clone(),
+                reason: "Cloudflare JS challenge (HTTP 503)".into(),
+                hint: "retry with --browser, or configure a proxy".into(),
+            }),
+            MockCleaner::passthrough(),
+            MockExtractorFactory::new(serde_json::json!({})),
+            MockStore::empty(),
+            MockLinkDiscoverer::new(),
+            MockRobotsChecker::new(),
+            test_cb(),
+            test_config(),
+        );
+
+        worker.process_job(&job, &reporter).await;
+
+        let failed = queue.failed_jobs.lock().unwrap();
+        assert_eq!(failed.len(), 1);
+        assert!(
+            failed[0].1.contains("retry with --browser"),
+            "{}",
+            failed[0].1
+        );
+        assert!(failed[0].2.is_none());
+    }
+
+    #[tokio::test]
+    async fn exceeded_budget_defers_the_job_to_midnight_utc() {
+        use crate::budget::{BudgetGuard, BudgetLimits, BudgetedExtractorFactory, next_reset};
+
+        let mut job = make_test_job();
+        job.retry_count = job.max_retries;
+        let queue = MockJobQueue::with_job(job.clone());
+        let reporter = MockReporter::new();
+        let factory = MockExtractorFactory::new(serde_json::json!({"title": "Test"}));
+        let guard = BudgetGuard::new(
+            MockUsageStore::new(),
+            BudgetLimits::default().with_max_calls(0),
+        );
+
+        let worker = WorkerService::new(
+            queue.clone(),
+            MockFetcher::new("<html>hi</html>"),
+            MockCleaner::passthrough(),
+            BudgetedExtractorFactory::new(factory, guard),
+            MockStore::empty(),
+            MockLinkDiscoverer::new(),
+            MockRobotsChecker::new(),
+            test_cb(),
+            test_config(),
+        );
+
+        let outcome = worker.process_job(&job, &reporter).await;
+
+        assert!(matches!(outcome, JobOutcome::Retrying));
+        assert!(queue.failed_jobs.lock().unwrap().is_empty());
+        let deferred = queue.deferred_jobs.lock().unwrap();
+        assert_eq!(deferred.len(), 1);
+        assert!(
+            deferred[0].1.contains("Daily budget exceeded"),
+            "{}",
+            deferred[0].1
+        );
+        assert_eq!(deferred[0].2, next_reset(Utc::now()));
+        // Deferring doesn't use up a retry, even of a job that has none left.
+        let stored = queue.jobs.lock().unwrap()[0].clone();
+        assert_eq!(stored.status, crate::job::JobStatus::Pending);
+        assert_eq!(stored.retry_count, job.retry_count);
+        assert_eq!(worker.circuit_breaker.state(), CircuitState::Closed);
+    }
+
     #[tokio::test]
     async fn process_job_circuit_open_retries() {
         let job = make_test_job();
@@ -589,6 +1311,177 @@ mod tests {
         );
     }
 
+    #[tokio::test]
+    async fn breaker_state_is_published_and_reset_on_request() {
+        let job = make_test_job();
+        let queue = MockJobQueue::with_job(job.clone());
+        let reporter = MockReporter::new();
+
+        let cb_config = CircuitBreakerConfig {
+            failure_threshold: 1,
+            recovery_timeout: Duration::from_secs(60),
+            ..Default::default()
+        };
+        let cb = CircuitBreaker::new("test", cb_config);
+
+        let worker = WorkerService::new(
+            queue.clone(),
+            MockFetcher::with_error(AppError::NetworkError("timeout".into())),
+            MockCleaner::passthrough(),
+            MockExtractorFactory::new(serde_json::json!({})),
+            MockStore::empty(),
+            MockLinkDiscoverer::new(),
+            MockRobotsChecker::new(),
+            cb.clone(),
+            test_config(),
+        );
+
+        // A reset requested before the worker started is not replayed.
+        queue.request_breaker_reset("test");
+        worker.sync_circuit_breaker(&reporter).await;
+        worker.process_job(&job, &reporter).await;
+        assert_eq!(cb.state(), CircuitState::Open);
+
+        worker.sync_circuit_breaker(&reporter).await;
+        worker.sync_circuit_breaker(&reporter).await;
+        {
+            let saved = queue.saved_breakers.lock().unwrap();
+            let states: Vec<_> = saved.iter().map(|s| s.state).collect();
+            assert_eq!(states, [CircuitState::Closed, CircuitState::Open]);
+            assert_eq!(
+                saved[1].last_error.as_deref(),
+                Some("Network error: timeout")
+            );
+        }
+
+        queue.request_breaker_reset("test");
+        worker.sync_circuit_breaker(&reporter).await;
+        assert_eq!(cb.state(), CircuitState::Closed);
+
+        let saved = queue.saved_breakers.lock().unwrap();
+        assert_eq!(saved.len(), 3);
+        assert_eq!(saved[2].state, CircuitState::Closed);
+        assert_eq!(saved[2].failure_count, 0);
+    }
+
+    #[tokio::test]
+    async fn breaker_changes_are_reported_once_each() {
+        let job = make_test_job();
+        let queue = MockJobQueue::with_job(job.clone());
+        let reporter = MockReporter::new();
+
+        let cb_config = CircuitBreakerConfig {
+            failure_threshold: 1,
+            recovery_timeout: Duration::from_secs(60),
+            ..Default::default()
+        };
+        let worker = WorkerService::new(
+            queue.clone(),
+            MockFetcher::with_error(AppError::NetworkError("timeout".into())),
+            MockCleaner::passthrough(),
+            MockExtractorFactory::new(serde_json::json!({})),
+            MockStore::empty(),
+            MockLinkDiscoverer::new(),
+            MockRobotsChecker::new(),
+            CircuitBreaker::new("test", cb_config),
+            WorkerConfig {
+                max_jobs: Some(1),
+                ..test_config()
+            },
+        );
+
+        worker
+            .run(CancellationToken::new(), &reporter)
+            .await
+            .unwrap();
+        assert_eq!(
+            *reporter.circuit_changes.lock().unwrap(),
+            [("test".to_string(), CircuitState::Open)]
+        );
+
+        queue.request_breaker_reset("test");
+        worker.sync_circuit_breaker(&reporter).await;
+        worker.sync_circuit_breaker(&reporter).await;
+        assert_eq!(
+            *reporter.circuit_changes.lock().unwrap(),
+            [
+                ("test".to_string(), CircuitState::Open),
+                ("test".to_string(), CircuitState::Closed),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn schema_file_validation_fails_hallucinated_output() {
+        let tmp = tempfile::tempdir().unwrap();
+        let schema_path = tmp.path().join("blog").join("1.0.0.json");
+        std::fs::create_dir_all(schema_path.parent().unwrap()).unwrap();
+        std::fs::write(
+            &schema_path,
+            r#"{"type": "object", "required": ["title", "price"]}"#,
+        )
+        .unwrap();
+
+        let mut job = make_test_job();
+        job.schema_name = "blog@1.0.0".to_string();
+        let queue = MockJobQueue::with_job(job.clone());
+        let reporter = MockReporter::new();
+
+        let store = MockStore::empty();
+
+        // The schema stored with the job accepts the output; the file does not.
+        let worker = WorkerService::new(
+            queue.clone(),
+            MockFetcher::new("<html>hi</html>"),
+            MockCleaner::passthrough(),
+            MockExtractorFactory::new(serde_json::json!({"title": "Test"})),
+            store.clone(),
+            MockLinkDiscoverer::new(),
+            MockRobotsChecker::new(),
+            test_cb(),
+            test_config(),
+        )
+        .with_schemas_dir(tmp.path());
+
+        let outcome = worker.process_job(&job, &reporter).await;
+
+        assert_eq!(outcome, JobOutcome::Failed);
+        assert!(queue.completed_jobs.lock().unwrap().is_empty());
+        let failed = queue.failed_jobs.lock().unwrap();
+        assert_eq!(failed.len(), 1);
+        assert!(failed[0].1.contains("blog@1.0.0"));
+        assert!(failed[0].1.contains("\"price\" is a required property"));
+        assert!(failed[0].2.is_none(), "validation failures are not retried");
+        assert!(
+            store.saved.lock().unwrap().is_empty(),
+            "output failing the schema file is never saved"
+        );
+    }
+
+    #[tokio::test]
+    async fn schema_file_validation_skips_schemas_not_in_the_dir() {
+        let tmp = tempfile::tempdir().unwrap();
+        let job = make_test_job();
+        let queue = MockJobQueue::with_job(job.clone());
+        let reporter = MockReporter::new();
+
+        let worker = WorkerService::new(
+            queue.clone(),
+            MockFetcher::new("<html>hi</html>"),
+            MockCleaner::passthrough(),
+            MockExtractorFactory::new(serde_json::json!({"title": "Test"})),
+            MockStore::empty(),
+            MockLinkDiscoverer::new(),
+            MockRobotsChecker::new(),
+            test_cb(),
+            test_config(),
+        )
+        .with_schemas_dir(tmp.path());
+
+        let outcome = worker.process_job(&job, &reporter).await;
+        assert_eq!(outcome, JobOutcome::Completed);
+    }
+
     #[tokio::test]
     async fn process_job_factory_error_fails_without_retry() {
         let job = make_test_job();
@@ -687,6 +1580,208 @@ mod tests {
         assert!(events.contains(&"Stopped".to_string()));
     }
 
+    #[tokio::test]
+    async fn run_once_drains_queue_and_returns_counts() {
+        let jobs: Vec<_> = (0..3).map(|_| make_test_job()).collect();
+        let queue = MockJobQueue::with_jobs(jobs);
+        let reporter = MockReporter::new();
+
+        let worker = WorkerService::new(
+            queue.clone(),
+            MockFetcher::with_responses(vec![
+                Ok("<html>one</html>".into()),
+                Err(AppError::HttpError(
+                    "HTTP 404 for https://example.com".into(),
+                )),
+                Ok("<html>three</html>".into()),
+            ]),
+            MockCleaner::passthrough(),
+            MockExtractorFactory::new(serde_json::json!({"title": "Test"})),
+            MockStore::empty(),
+            MockLinkDiscoverer::new(),
+            MockRobotsChecker::new(),
+            test_cb(),
+            test_config().with_run_once(true),
+        );
+
+        // Never cancelled: the loop must end on its own once the queue is empty.
+        let summary = tokio::time::timeout(
+            Duration::from_secs(5),
+            worker.run(CancellationToken::new(), &reporter),
+        )
+        .await
+        .expect("run_once worker should exit when the queue is empty")
+        .unwrap();
+
+        assert_eq!(
+            summary,
+            WorkerRunSummary {
+                processed: 3,
+                completed: 2,
+                retrying: 0,
+                failed: 1,
+            }
+        );
+        assert!(
+            reporter
+                .events
+                .lock()
+                .unwrap()
+                .contains(&"Stopped".to_string())
+        );
+    }
+
+    #[tokio::test]
+    async fn max_jobs_stops_after_limit() {
+        let jobs: Vec<_> = (0..3).map(|_| make_test_job()).collect();
+        let queue = MockJobQueue::with_jobs(jobs);
+        let reporter = MockReporter::new();
+
+        let worker = WorkerService::new(
+            queue.clone(),
+            MockFetcher::with_responses(vec![]),
+            MockCleaner::passthrough(),
+            MockExtractorFactory::new(serde_json::json!({"title": "Test"})),
+            MockStore::empty(),
+            MockLinkDiscoverer::new(),
+            MockRobotsChecker::new(),
+            test_cb(),
+            test_config().with_max_jobs(2),
+        );
+
+        let summary = tokio::time::timeout(
+            Duration::from_secs(5),
+            worker.run(CancellationToken::new(), &reporter),
+        )
+        .await
+        .expect("worker should exit after max_jobs")
+        .unwrap();
+
+        assert_eq!(summary.processed, 2);
+        assert_eq!(summary.completed, 2);
+        let jobs = queue.jobs.lock().unwrap();
+        let pending = jobs
+            .iter()
+            .filter(|j| j.status == crate::job::JobStatus::Pending)
+            .count();
+        assert_eq!(pending, 1, "the third job is left for another worker");
+    }
+
+    #[tokio::test]
+    async fn status_tracks_the_run_loop() {
+        let jobs: Vec<_> = (0..2).map(|_| make_test_job()).collect();
+        let queue = MockJobQueue::with_jobs(jobs);
+        let reporter = MockReporter::new();
+
+        let worker = WorkerService::new(
+            queue,
+            MockFetcher::with_responses(vec![
+                Ok("<html>one</html>".into()),
+                Err(AppError::HttpError(
+                    "HTTP 404 for https://example.com".into(),
+                )),
+            ]),
+            MockCleaner::passthrough(),
+            MockExtractorFactory::new(serde_json::json!({"title": "Test"})),
+            MockStore::empty(),
+            MockLinkDiscoverer::new(),
+            MockRobotsChecker::new(),
+            test_cb(),
+            test_config(),
+        );
+        let status = worker.status();
+        assert!(!status.snapshot().running);
+
+        let cancel = CancellationToken::new();
+        let watcher = async {
+            // Wait until both jobs are done and the loop is idle-polling.
+            let snapshot = loop {
+                let snapshot = status.snapshot();
+                if snapshot.jobs.processed == 2 && snapshot.current_job.is_none() {
+                    break snapshot;
+                }
+                tokio::time::sleep(Duration::from_millis(5)).await;
+            };
+            cancel.cancel();
+            snapshot
+        };
+        let (summary, snapshot) = tokio::time::timeout(Duration::from_secs(5), async {
+            tokio::join!(worker.run(cancel.clone(), &reporter), watcher)
+        })
+        .await
+        .expect("worker should stop once cancelled");
+        summary.unwrap();
+
+        assert!(snapshot.is_polling(Duration::from_secs(60), Duration::from_secs(60), Utc::now()));
+        let json = serde_json::to_value(&snapshot).unwrap();
+        assert_eq!(json["worker_id"], "test-worker");
+        assert_eq!(json["running"], true);
+        assert_eq!(json["current_job"], serde_json::Value::Null);
+        assert_eq!(json["current_job_started_at"], serde_json::Value::Null);
+        assert_eq!(json["circuit_breaker"], "closed");
+        assert_eq!(
+            json["jobs"],
+            serde_json::json!({"processed": 2, "completed": 1, "retrying": 0, "failed": 1})
+        );
+        assert!(json["started_at"].is_string());
+        assert!(json["last_poll_at"].is_string());
+
+        let stopped = status.snapshot();
+        assert!(!stopped.running);
+        assert!(!stopped.is_polling(Duration::from_secs(60), Duration::from_secs(60), Utc::now()));
+    }
+
+    #[test]
+    fn stale_poll_is_not_polling_unless_a_recent_job_is_running() {
+        let poll = Duration::from_secs(15);
+        let job = Duration::from_secs(300);
+        let now = Utc::now();
+        let mut snapshot = WorkerStatus::new("w").snapshot();
+        snapshot.running = true;
+        assert!(!snapshot.is_polling(poll, job, now));
+
+        snapshot.last_poll_at = Some(now - chrono::Duration::seconds(10));
+        assert!(snapshot.is_polling(poll, job, now));
+
+        snapshot.last_poll_at = Some(now - chrono::Duration::seconds(20));
+        assert!(!snapshot.is_polling(poll, job, now));
+
+        snapshot.current_job = Some(Uuid::new_v4());
+        snapshot.current_job_started_at = snapshot.last_poll_at;
+        assert!(snapshot.is_polling(poll, job, now));
+
+        // A job running past its allowance means the loop is stuck.
+        snapshot.current_job_started_at = Some(now - chrono::Duration::seconds(301));
+        assert!(!snapshot.is_polling(poll, job, now));
+    }
+
+    #[tokio::test]
+    async fn run_once_leaves_jobs_scheduled_for_retry() {
+        let queue = MockJobQueue::with_job(make_test_job());
+        let reporter = MockReporter::new();
+
+        let worker = WorkerService::new(
+            queue.clone(),
+            MockFetcher::with_error(AppError::NetworkError("timeout".into())),
+            MockCleaner::passthrough(),
+            MockExtractorFactory::new(serde_json::json!({})),
+            MockStore::empty(),
+            MockLinkDiscoverer::new(),
+            MockRobotsChecker::new(),
+            test_cb(),
+            test_config().with_run_once(true),
+        );
+
+        let summary = worker
+            .run(CancellationToken::new(), &reporter)
+            .await
+            .unwrap();
+
+        assert_eq!(summary.processed, 1);
+        assert_eq!(summary.retrying, 1);
+        assert_eq!(summary.failed, 0);
+    }
+
     #[tokio::test]
     async fn retryable_error_but_max_retries_exceeded() {
         let mut job = make_test_job();
@@ -777,6 +1872,78 @@ mod tests {
         assert!(failed[0].1.contains("disk full"));
     }
 
+    #[tokio::test]
+    async fn browser_job_on_worker_without_browser_fails_permanently() {
+        let mut job = make_test_job();
+        job.fetch_options.use_browser = true;
+        let queue = MockJobQueue::with_job(job.clone());
+        let reporter = MockReporter::new();
+
+        let fetcher: FetcherSelector<_, MockFetcher> =
+            FetcherSelector::new(MockFetcher::new("<html>hi</html>"));
+        let worker = WorkerService::new(
+            queue.clone(),
+            fetcher,
+            MockCleaner::passthrough(),
+            MockExtractorFactory::new(serde_json::json!({"title": "Test"})),
+            MockStore::empty(),
+            MockLinkDiscoverer::new(),
+            MockRobotsChecker::new(),
+            test_cb(),
+            test_config(),
+        );
+
+        worker.process_job(&job, &reporter).await;
+
+        let failed = queue.failed_jobs.lock().unwrap();
+        assert_eq!(failed.len(), 1);
+        assert!(
+            failed[0].1.contains("no browser fetcher"),
+            "{}",
+            failed[0].1
+        );
+        assert!(failed[0].2.is_none(), "Should not retry on the same worker");
+    }
+
+    #[tokio::test]
+    async fn worker_picks_fetcher_per_job() {
+        let plain = make_test_job();
+        let mut rendered = make_test_job();
+        rendered.id = Uuid::new_v4();
+        rendered.fetch_options.use_browser = true;
+        let queue = MockJobQueue::with_job(plain.clone());
+        let reporter = MockReporter::new();
+        let store = MockStore::empty();
+
+        let fetcher = FetcherSelector::new(MockFetcher::new("<p>http</p>"))
+            .with_browser(MockFetcher::new("<p>browser</p>"));
+        let worker = WorkerService::new(
+            queue.clone(),
+            fetcher,
+            MockCleaner::passthrough(),
+            MockExtractorFactory::new(serde_json::json!({"title": "Test"})),
+            store.clone(),
+            MockLinkDiscoverer::new(),
+            MockRobotsChecker::new(),
+            test_cb(),
+            test_config(),
+        );
+
+        worker.process_job(&plain, &reporter).await;
+        worker.process_job(&rendered, &reporter).await;
+
+        assert_eq!(queue.completed_jobs.lock().unwrap().len(), 2);
+        let saved = store.saved.lock().unwrap();
+        let hashes: Vec<_> = saved.iter().map(|e| e.raw_content_hash.clone()).collect();
+        assert_eq!(
+            hashes,
+            [
+                crate::compute_hash("<p>http</p>"),
+                crate::compute_hash("<p>browser</p>")
+            ]
+        );
+    }
+
     // --- Crawl-specific tests ---
 
     fn make_crawl_job(
@@ -798,7 +1965,8 @@ mod tests {
     #[tokio::test]
     async fn crawl_job_enqueues_child_jobs() {
         let session_id = Uuid::new_v4();
-        let job = make_crawl_job(session_id, 0, 2, 100, vec!["example.com".to_string()]);
+        let mut job = make_crawl_job(session_id, 0, 2, 100, vec!["example.com".to_string()]);
+        job.fetch_options.fetch_timeout_secs = Some(45);
         let queue = MockJobQueue::with_job(job.clone());
         let reporter = MockReporter::new();
 
@@ -829,6 +1997,8 @@ mod tests {
         assert_eq!(child_jobs[0].depth, 1);
         assert_eq!(child_jobs[0].max_depth, 2);
         assert_eq!(child_jobs[0].crawl_session_id, Some(session_id));
+        // Children are fetched the same way as their parent.
+        assert_eq!(child_jobs[0].fetch_options, job.fetch_options);
     }
 
     #[tokio::test]
@@ -868,6 +2038,71 @@ mod tests {
         assert!(urls.contains(&"https://sub.example.com/page3"));
     }
 
+    #[tokio::test]
+    async fn job_notify_url_overrides_the_webhook_and_is_inherited() {
+        let session_id = Uuid::new_v4();
+        let mut job = make_crawl_job(session_id, 0, 1, 100, vec!["example.com".to_string()]);
+        job.notify_url = Some("https://hooks.example.com/job".into());
+        job.tags = vec!["run-42".into()];
+        let queue = MockJobQueue::with_job(job.clone());
+        let notifier = MockNotifier::new();
+
+        let worker = WorkerService::new(
+            queue.clone(),
+            MockFetcher::new("<html>hi</html>"),
+            MockCleaner::passthrough(),
+            MockExtractorFactory::new(serde_json::json!({"title": "New Title"})),
+            MockStore::with_latest(make_test_extraction("old_hash")),
+            MockLinkDiscoverer::with_links(vec!["https://example.com/page1".to_string()]),
+            MockRobotsChecker::new(),
+            test_cb(),
+            test_config(),
+        )
+        .with_notifier(notifier.clone());
+
+        worker.process_job(&job, &MockReporter::new()).await;
+
+        assert_eq!(notifier.events.lock().unwrap().len(), 1);
+        assert_eq!(
+            *notifier.webhooks.lock().unwrap(),
+            [Some("https://hooks.example.com/job".to_string())]
+        );
+        let jobs = queue.jobs.lock().unwrap();
+        let child = jobs
+            .iter()
+            .find(|j| j.parent_job_id == Some(job.id))
+            .unwrap();
+        assert_eq!(child.notify_url, job.notify_url);
+        assert_eq!(child.tags, job.tags);
+    }
+
+    #[tokio::test]
+    async fn saved_job_extractions_are_published() {
+        let session_id = Uuid::new_v4();
+        let job = make_crawl_job(session_id, 2, 2, 100, vec!["example.com".to_string()]);
+        let publisher = MockPublisher::new();
+
+        let worker = WorkerService::new(
+            MockJobQueue::with_job(job.clone()),
+            MockFetcher::new("<html>hi</html>"),
+            MockCleaner::passthrough(),
+            MockExtractorFactory::new(serde_json::json!({"title": "Test"})),
+            MockStore::empty(),
+            MockLinkDiscoverer::new(),
+            MockRobotsChecker::new(),
+            test_cb(),
+            test_config(),
+        )
+        .with_publisher(publisher.clone());
+
+        worker.process_job(&job, &MockReporter::new()).await;
+
+        let events = publisher.events.lock().unwrap();
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].extraction.url, job.url);
+        assert_eq!(events[0].extraction.schema_name, job.schema_name);
+    }
+
     #[tokio::test]
     async fn crawl_job_respects_max_depth() {
         let session_id = Uuid::new_v4();
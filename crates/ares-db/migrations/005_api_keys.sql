@@ -0,0 +1,21 @@
+-- Per-consumer API keys with scopes, so individual callers can be revoked
+-- without rotating the shared ARES_ADMIN_TOKEN (which remains a bootstrap
+-- admin key). Only the SHA-256 hash of each key is stored.
+
+CREATE TABLE IF NOT EXISTS api_keys (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    name VARCHAR(255) NOT NULL,
+    key_hash VARCHAR(64) NOT NULL UNIQUE,
+
+    -- Granted scopes: scrape, jobs:write, schemas:write, admin
+    scopes TEXT[] NOT NULL DEFAULT '{}',
+
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    revoked_at TIMESTAMPTZ,
+    last_used_at TIMESTAMPTZ
+);
+
+-- Active key lookup during authentication
+CREATE INDEX IF NOT EXISTS idx_api_keys_active
+ON api_keys(key_hash)
+WHERE revoked_at IS NULL;
@@ -0,0 +1,18 @@
+-- Ares: in-place extraction updates (upsert mode)
+--
+-- Upserting an extraction refreshes the existing row for the same
+-- (url, schema_name, data_hash) instead of appending a new snapshot.
+-- Snapshot saves legitimately repeat a data hash (unchanged re-scrapes,
+-- A -> B -> A reverts), so uniqueness is enforced only on upserted rows.
+
+ALTER TABLE extractions
+    ADD COLUMN IF NOT EXISTS updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    ADD COLUMN IF NOT EXISTS upserted   BOOLEAN     NOT NULL DEFAULT FALSE;
+
+UPDATE extractions SET updated_at = created_at;
+
+CREATE UNIQUE INDEX IF NOT EXISTS idx_extractions_upsert_key
+    ON extractions(url, schema_name, data_hash) WHERE upserted;
+
+CREATE INDEX IF NOT EXISTS idx_extractions_url_schema_updated
+    ON extractions(url, schema_name, updated_at DESC);
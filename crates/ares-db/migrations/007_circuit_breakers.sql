@@ -0,0 +1,17 @@
+-- Circuit-breaker state published by workers, so the server can show and
+-- reset breakers that live in separate worker processes. Workers reset their
+-- local breaker whenever reset_requested_at moves forward.
+
+CREATE TABLE IF NOT EXISTS circuit_breakers (
+    name VARCHAR(255) PRIMARY KEY,
+
+    -- closed, open, half-open
+    state VARCHAR(20) NOT NULL DEFAULT 'closed',
+    failure_count INTEGER NOT NULL DEFAULT 0,
+    success_count INTEGER NOT NULL DEFAULT 0,
+    last_error TEXT,
+    half_open_at TIMESTAMPTZ,
+
+    reset_requested_at TIMESTAMPTZ,
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
@@ -0,0 +1,4 @@
+-- Per-job fetch options (browser, timeouts, selectors), stored as JSON so new
+-- options don't need a migration. '{}' means "use the worker's defaults".
+ALTER TABLE scrape_jobs
+ADD COLUMN IF NOT EXISTS fetch_options JSONB NOT NULL DEFAULT '{}';
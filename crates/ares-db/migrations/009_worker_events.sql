@@ -0,0 +1,17 @@
+-- Durable record of each job attempt, written by workers as they claim,
+-- start, complete, or fail jobs. A BIGSERIAL id keeps events written in the
+-- same statement (and so with the same created_at) in order.
+
+CREATE TABLE IF NOT EXISTS worker_events (
+    id BIGSERIAL PRIMARY KEY,
+    worker_id VARCHAR(255) NOT NULL,
+    job_id UUID,
+
+    -- job_claimed, job_started, job_completed, job_failed
+    event_type VARCHAR(50) NOT NULL,
+    detail JSONB NOT NULL DEFAULT '{}',
+
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_worker_events_job ON worker_events(job_id, id DESC);
@@ -0,0 +1,4 @@
+-- Per-job change notification webhook, overriding the worker's. NULL means
+-- "use the worker's notifiers".
+ALTER TABLE scrape_jobs
+ADD COLUMN IF NOT EXISTS notify_url TEXT;
@@ -0,0 +1,13 @@
+-- Keys of the source content archived in the snapshot store (raw HTML and
+-- cleaned Markdown). NULL means that content was not archived, or its
+-- snapshot has been pruned.
+ALTER TABLE extractions
+    ADD COLUMN IF NOT EXISTS html_snapshot_key     TEXT,
+    ADD COLUMN IF NOT EXISTS markdown_snapshot_key TEXT;
+
+-- Snapshots are content-addressed and shared between extractions; pruning
+-- looks up whether a key is still referenced.
+CREATE INDEX IF NOT EXISTS idx_extractions_html_snapshot_key
+    ON extractions(html_snapshot_key) WHERE html_snapshot_key IS NOT NULL;
+CREATE INDEX IF NOT EXISTS idx_extractions_markdown_snapshot_key
+    ON extractions(markdown_snapshot_key) WHERE markdown_snapshot_key IS NOT NULL;
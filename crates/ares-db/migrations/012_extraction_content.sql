@@ -0,0 +1,8 @@
+-- The cleaned Markdown an extraction was made from, when content storage is
+-- on (ARES_STORE_CONTENT). Kept out of `extractions` so listing and history
+-- queries never read it.
+CREATE TABLE IF NOT EXISTS extraction_content (
+    extraction_id   UUID PRIMARY KEY REFERENCES extractions(id) ON DELETE CASCADE,
+    cleaned_content TEXT NOT NULL,
+    created_at      TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
@@ -0,0 +1,4 @@
+-- Free-form labels for grouping jobs, e.g. by the pipeline run that created
+-- them. Empty for untagged jobs.
+ALTER TABLE scrape_jobs
+ADD COLUMN IF NOT EXISTS tags TEXT[] NOT NULL DEFAULT '{}';
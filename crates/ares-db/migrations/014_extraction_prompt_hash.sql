@@ -0,0 +1,5 @@
+-- SHA-256 of the effective extraction prompt, so history comparisons can be
+-- limited to runs made with the same prompt. NULL for older rows and for
+-- extractors that don't report a prompt.
+ALTER TABLE extractions
+ADD COLUMN IF NOT EXISTS prompt_hash VARCHAR(64);
@@ -0,0 +1,10 @@
+-- When an extraction's data was last seen by a scrape. Skip-unchanged saves
+-- bump this on the latest row instead of inserting a duplicate snapshot.
+ALTER TABLE extractions
+ADD COLUMN IF NOT EXISTS last_seen_at TIMESTAMPTZ;
+
+UPDATE extractions SET last_seen_at = updated_at WHERE last_seen_at IS NULL;
+
+ALTER TABLE extractions
+ALTER COLUMN last_seen_at SET DEFAULT NOW(),
+ALTER COLUMN last_seen_at SET NOT NULL;
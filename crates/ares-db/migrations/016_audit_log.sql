@@ -0,0 +1,25 @@
+-- Who created or deleted schemas, cancelled or retried jobs, managed API
+-- keys, or pruned data through the HTTP API. Entries are never updated and
+-- are kept by `ares db prune`, which only removes operational history.
+
+CREATE TABLE IF NOT EXISTS audit_log (
+    id BIGSERIAL PRIMARY KEY,
+
+    -- The acting API key, or the fingerprint of the bootstrap admin token
+    api_key_id UUID,
+    token_fingerprint VARCHAR(16),
+
+    -- e.g. schema.create, job.cancel, api_key.revoke
+    action VARCHAR(50) NOT NULL,
+    resource_type VARCHAR(50) NOT NULL,
+    resource_id VARCHAR(512),
+    request_id VARCHAR(255),
+
+    -- Redacted summary of the request, never its full body
+    summary JSONB NOT NULL DEFAULT '{}',
+
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_audit_log_created ON audit_log(created_at DESC, id DESC);
+CREATE INDEX IF NOT EXISTS idx_audit_log_action ON audit_log(action, created_at DESC);
@@ -0,0 +1,10 @@
+-- LLM usage per UTC day, checked against the daily budget before each call.
+-- Workers and API servers add to the same row with atomic upserts.
+
+CREATE TABLE IF NOT EXISTS usage_counters (
+    day DATE PRIMARY KEY,
+    llm_calls BIGINT NOT NULL DEFAULT 0,
+    tokens BIGINT NOT NULL DEFAULT 0,
+    cost_usd DOUBLE PRECISION NOT NULL DEFAULT 0,
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
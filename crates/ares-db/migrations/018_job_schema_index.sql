@@ -0,0 +1,8 @@
+-- Claiming for workers that only take some schemas (`ares worker
+-- --schema-filter`): `claim_job` filters pending jobs with
+-- `schema_name LIKE $2`. varchar_pattern_ops lets prefix patterns such as
+-- 'blog%' use the index whatever the database collation.
+
+CREATE INDEX IF NOT EXISTS idx_scrape_jobs_schema_status
+ON scrape_jobs(schema_name varchar_pattern_ops, status, created_at ASC)
+WHERE status = 'pending';
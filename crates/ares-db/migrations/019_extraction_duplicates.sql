@@ -0,0 +1,11 @@
+-- Cross-URL duplicate detection: an extraction whose cleaned content was
+-- already extracted for another URL (a mirror, or the same page behind
+-- tracking parameters) points at that original. Deleting the original
+-- unlinks its duplicates rather than deleting them.
+ALTER TABLE extractions
+ADD COLUMN IF NOT EXISTS duplicate_of UUID REFERENCES extractions(id) ON DELETE SET NULL;
+
+-- `find_by_content_hash` looks up the earliest extraction of a schema with a
+-- given content hash within a time window.
+CREATE INDEX IF NOT EXISTS idx_extractions_schema_content_hash
+ON extractions(schema_name, raw_content_hash, created_at ASC);
@@ -0,0 +1,35 @@
+-- Per-domain statistics: jobs and extractions record the domain of their
+-- URL (scheme://host:port, as keyed by the fetch throttle) so failures can
+-- be grouped by site.
+ALTER TABLE scrape_jobs ADD COLUMN IF NOT EXISTS domain TEXT;
+ALTER TABLE extractions ADD COLUMN IF NOT EXISTS domain TEXT;
+
+-- Backfill existing rows. Mirrors `ares_core::domain_key`: lowercased
+-- scheme and host, and the explicit port or the scheme's default one.
+-- URLs that don't parse are left NULL.
+CREATE FUNCTION pg_temp.url_domain_key(url TEXT) RETURNS TEXT
+LANGUAGE SQL IMMUTABLE AS $$
+    SELECT lower(parts.m[1]) || '://' || lower(parts.m[2])
+        || COALESCE(':' || COALESCE(
+            parts.m[3],
+            CASE lower(parts.m[1]) WHEN 'https' THEN '443' WHEN 'http' THEN '80' END
+        ), '')
+    FROM (
+        SELECT regexp_match(
+            url,
+            '^([A-Za-z][A-Za-z0-9+.-]*)://(?:[^@/?#]*@)?(\[[^]]*\]|[^/:?#]+)(?::([0-9]+))?'
+        ) AS m
+    ) AS parts
+$$;
+
+UPDATE scrape_jobs SET domain = pg_temp.url_domain_key(url) WHERE domain IS NULL;
+UPDATE extractions SET domain = pg_temp.url_domain_key(url) WHERE domain IS NULL;
+
+DROP FUNCTION pg_temp.url_domain_key(TEXT);
+
+-- `StatsRepository::domain_stats` groups jobs and extractions by domain.
+CREATE INDEX IF NOT EXISTS idx_scrape_jobs_domain
+ON scrape_jobs(domain, status);
+
+CREATE INDEX IF NOT EXISTS idx_extractions_domain
+ON extractions(domain, created_at DESC);
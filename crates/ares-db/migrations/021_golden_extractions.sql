@@ -0,0 +1,10 @@
+-- Golden extractions: known-good results that `ares verify` and
+-- `POST /v1/verify` re-extract and diff against to catch regressions after
+-- a prompt or model change.
+ALTER TABLE extractions
+ADD COLUMN IF NOT EXISTS golden BOOLEAN NOT NULL DEFAULT FALSE;
+
+-- `list_golden` reads the newest golden row per URL of a schema.
+CREATE INDEX IF NOT EXISTS idx_extractions_golden
+ON extractions(schema_name, url, created_at DESC)
+WHERE golden;
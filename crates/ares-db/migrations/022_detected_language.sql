@@ -0,0 +1,9 @@
+-- Language of the cleaned content each extraction was made from, as an
+-- ISO 639-3 code (`eng`, `deu`, ...). NULL when it couldn't be detected
+-- reliably, and for rows saved before detection existed.
+ALTER TABLE extractions
+ADD COLUMN IF NOT EXISTS detected_language TEXT;
+
+-- History queries can filter a page's extractions by language.
+CREATE INDEX IF NOT EXISTS idx_extractions_url_schema_language
+ON extractions(url, schema_name, detected_language);
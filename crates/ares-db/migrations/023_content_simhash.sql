@@ -0,0 +1,5 @@
+-- Simhash of each extraction's cleaned content (16 hex chars), so a scrape
+-- can treat near-identical content (a changed timestamp, rotated "related"
+-- links) as unchanged and skip the LLM call. NULL for older rows.
+ALTER TABLE extractions
+ADD COLUMN IF NOT EXISTS content_simhash TEXT;
@@ -0,0 +1,13 @@
+-- Retention policy of the schema each extraction was made with
+-- (`x-ares-retention`). Rows older than `retention_days` are scrubbed, with
+-- the JSON Pointers in `retention_scrub_fields` nulled out of their data, or
+-- deleted when no fields are listed. NULL keeps the row forever.
+ALTER TABLE extractions
+ADD COLUMN IF NOT EXISTS retention_days INTEGER,
+ADD COLUMN IF NOT EXISTS retention_scrub_fields TEXT[],
+ADD COLUMN IF NOT EXISTS scrubbed_at TIMESTAMPTZ;
+
+-- Pruning only looks at rows with a policy that haven't been scrubbed yet.
+CREATE INDEX IF NOT EXISTS idx_extractions_retention
+ON extractions(created_at)
+WHERE retention_days IS NOT NULL AND scrubbed_at IS NULL;
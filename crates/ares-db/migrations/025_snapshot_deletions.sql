@@ -0,0 +1,6 @@
+-- Snapshot objects whose keys were released from extractions but could not
+-- be deleted from the snapshot store. The next cleanup retries them.
+CREATE TABLE IF NOT EXISTS snapshot_deletions (
+    key TEXT PRIMARY KEY,
+    failed_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
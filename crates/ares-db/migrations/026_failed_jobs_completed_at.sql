@@ -0,0 +1,6 @@
+-- Permanently failed jobs now record when they finished in completed_at, so
+-- stats can window them without updated_at, which later edits also bump.
+-- Older failed jobs get their last update as the closest estimate.
+UPDATE scrape_jobs
+SET completed_at = updated_at
+WHERE status = 'failed' AND completed_at IS NULL;
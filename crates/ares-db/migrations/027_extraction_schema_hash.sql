@@ -0,0 +1,5 @@
+-- SHA-256 of the JSON schema each extraction was made with, so a scrape only
+-- reuses a previous extraction's data under the same schema. NULL for older
+-- rows.
+ALTER TABLE extractions
+ADD COLUMN IF NOT EXISTS schema_hash TEXT;
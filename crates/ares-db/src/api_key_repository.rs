@@ -0,0 +1,139 @@
+use chrono::{DateTime, Utc};
+use sqlx::{PgPool, Pool, Postgres};
+use uuid::Uuid;
+
+use ares_core::api_key::{ApiKey, ApiKeyScope, generate_api_key, hash_api_key};
+use ares_core::error::AppError;
+
+/// PostgreSQL-backed store for scoped API keys.
+#[derive(Clone)]
+pub struct ApiKeyRepository {
+    pool: Pool<Postgres>,
+}
+
+// -- Internal row type for sqlx deserialization --
+
+#[derive(sqlx::FromRow)]
+struct ApiKeyRow {
+    id: Uuid,
+    name: String,
+    key_hash: String,
+    scopes: Vec<String>,
+    created_at: DateTime<Utc>,
+    revoked_at: Option<DateTime<Utc>>,
+    last_used_at: Option<DateTime<Utc>>,
+}
+
+impl TryFrom<ApiKeyRow> for ApiKey {
+    type Error = AppError;
+
+    fn try_from(row: ApiKeyRow) -> Result<Self, AppError> {
+        let scopes = row
+            .scopes
+            .iter()
+            .map(|s| {
+                s.parse().map_err(|_| {
+                    AppError::DatabaseError(format!("Invalid API key scope in database: '{s}'"))
+                })
+            })
+            .collect::<Result<Vec<ApiKeyScope>, AppError>>()?;
+        Ok(ApiKey {
+            id: row.id,
+            name: row.name,
+            key_hash: row.key_hash,
+            scopes,
+            created_at: row.created_at,
+            revoked_at: row.revoked_at,
+            last_used_at: row.last_used_at,
+        })
+    }
+}
+
+impl ApiKeyRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    /// Create a new key with the given scopes.
+    ///
+    /// Returns the stored record together with the plaintext key value — the
+    /// only time it is available, since only its hash is persisted.
+    pub async fn create(
+        &self,
+        name: &str,
+        scopes: &[ApiKeyScope],
+    ) -> Result<(ApiKey, String), AppError> {
+        let key = generate_api_key();
+        let scopes: Vec<&str> = scopes.iter().map(ApiKeyScope::as_str).collect();
+
+        let row = sqlx::query_as::<_, ApiKeyRow>(
+            r#"
+            INSERT INTO api_keys (name, key_hash, scopes)
+            VALUES ($1, $2, $3)
+            RETURNING *
+            "#,
+        )
+        .bind(name)
+        .bind(hash_api_key(&key))
+        .bind(&scopes)
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok((row.try_into()?, key))
+    }
+
+    /// List keys, newest first. Revoked keys are included only when requested.
+    pub async fn list(&self, include_revoked: bool) -> Result<Vec<ApiKey>, AppError> {
+        let rows = sqlx::query_as::<_, ApiKeyRow>(
+            r#"
+            SELECT * FROM api_keys
+            WHERE $1 OR revoked_at IS NULL
+            ORDER BY created_at DESC
+            "#,
+        )
+        .bind(include_revoked)
+        .fetch_all(&self.pool)
+        .await?;
+
+        rows.into_iter().map(ApiKey::try_from).collect()
+    }
+
+    /// Look up a non-revoked key by the SHA-256 hash of its value.
+    pub async fn find_active_by_hash(&self, key_hash: &str) -> Result<Option<ApiKey>, AppError> {
+        let row = sqlx::query_as::<_, ApiKeyRow>(
+            r#"SELECT * FROM api_keys WHERE key_hash = $1 AND revoked_at IS NULL"#,
+        )
+        .bind(key_hash)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        row.map(ApiKey::try_from).transpose()
+    }
+
+    /// Revoke a key. Returns `false` if the key does not exist or was
+    /// already revoked.
+    pub async fn revoke(&self, id: Uuid) -> Result<bool, AppError> {
+        let result = sqlx::query(
+            r#"
+            UPDATE api_keys
+            SET revoked_at = NOW()
+            WHERE id = $1 AND revoked_at IS NULL
+            "#,
+        )
+        .bind(id)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// Record that a key was just used to authenticate a request.
+    pub async fn touch_last_used(&self, id: Uuid) -> Result<(), AppError> {
+        sqlx::query(r#"UPDATE api_keys SET last_used_at = NOW() WHERE id = $1"#)
+            .bind(id)
+            .execute(&self.pool)
+            .await?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,121 @@
+use chrono::{DateTime, Utc};
+use sqlx::{PgPool, Pool, Postgres};
+use uuid::Uuid;
+
+use ares_core::audit::{AuditAction, AuditEntry, NewAuditEntry};
+use ares_core::error::AppError;
+
+/// PostgreSQL-backed audit log of administrative API actions.
+#[derive(Clone)]
+pub struct AuditRepository {
+    pool: Pool<Postgres>,
+}
+
+// -- Internal row type for sqlx deserialization --
+
+#[derive(sqlx::FromRow)]
+struct AuditRow {
+    id: i64,
+    api_key_id: Option<Uuid>,
+    token_fingerprint: Option<String>,
+    action: String,
+    resource_type: String,
+    resource_id: Option<String>,
+    request_id: Option<String>,
+    summary: serde_json::Value,
+    created_at: DateTime<Utc>,
+}
+
+impl From<AuditRow> for AuditEntry {
+    fn from(row: AuditRow) -> Self {
+        AuditEntry {
+            id: row.id,
+            api_key_id: row.api_key_id,
+            token_fingerprint: row.token_fingerprint,
+            action: row.action,
+            resource_type: row.resource_type,
+            resource_id: row.resource_id,
+            request_id: row.request_id,
+            summary: row.summary,
+            created_at: row.created_at,
+        }
+    }
+}
+
+impl AuditRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    /// Append an entry.
+    pub async fn insert(&self, entry: &NewAuditEntry) -> Result<(), AppError> {
+        sqlx::query(
+            r#"
+            INSERT INTO audit_log
+                (api_key_id, token_fingerprint, action, resource_type, resource_id,
+                 request_id, summary)
+            VALUES ($1, $2, $3, $4, $5, $6, $7)
+            "#,
+        )
+        .bind(entry.api_key_id)
+        .bind(&entry.token_fingerprint)
+        .bind(entry.action.as_str())
+        .bind(entry.action.resource_type())
+        .bind(&entry.resource_id)
+        .bind(&entry.request_id)
+        .bind(&entry.summary)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    /// Entries recorded at or after `since`, optionally only for `action`,
+    /// newest first.
+    pub async fn list(
+        &self,
+        since: Option<DateTime<Utc>>,
+        action: Option<AuditAction>,
+        limit: usize,
+        offset: usize,
+    ) -> Result<Vec<AuditEntry>, AppError> {
+        let rows = sqlx::query_as::<_, AuditRow>(
+            r#"
+            SELECT * FROM audit_log
+            WHERE ($1::timestamptz IS NULL OR created_at >= $1)
+              AND ($2::varchar IS NULL OR action = $2)
+            ORDER BY created_at DESC, id DESC
+            LIMIT $3 OFFSET $4
+            "#,
+        )
+        .bind(since)
+        .bind(action.map(|a| a.as_str()))
+        .bind(i64::try_from(limit).unwrap_or(i64::MAX))
+        .bind(i64::try_from(offset).unwrap_or(i64::MAX))
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(rows.into_iter().map(AuditEntry::from).collect())
+    }
+
+    /// Number of entries [`list`](Self::list) pages through.
+    pub async fn count(
+        &self,
+        since: Option<DateTime<Utc>>,
+        action: Option<AuditAction>,
+    ) -> Result<i64, AppError> {
+        let (count,): (i64,) = sqlx::query_as(
+            r#"
+            SELECT COUNT(*) FROM audit_log
+            WHERE ($1::timestamptz IS NULL OR created_at >= $1)
+              AND ($2::varchar IS NULL OR action = $2)
+            "#,
+        )
+        .bind(since)
+        .bind(action.map(|a| a.as_str()))
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(count)
+    }
+}
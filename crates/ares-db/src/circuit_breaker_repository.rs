@@ -0,0 +1,164 @@
+use chrono::{DateTime, Utc};
+use sqlx::{PgPool, Pool, Postgres};
+
+use ares_core::circuit_breaker::{CircuitBreakerRecord, CircuitBreakerStats, CircuitState};
+use ares_core::error::AppError;
+
+/// PostgreSQL-backed store for circuit-breaker state shared between workers
+/// and the server.
+#[derive(Clone)]
+pub struct CircuitBreakerRepository {
+    pool: Pool<Postgres>,
+}
+
+// -- Internal row type for sqlx deserialization --
+
+#[derive(sqlx::FromRow)]
+struct CircuitBreakerRow {
+    name: String,
+    state: String,
+    failure_count: i32,
+    success_count: i32,
+    last_error: Option<String>,
+    half_open_at: Option<DateTime<Utc>>,
+    reset_requested_at: Option<DateTime<Utc>>,
+    updated_at: DateTime<Utc>,
+}
+
+impl TryFrom<CircuitBreakerRow> for CircuitBreakerRecord {
+    type Error = AppError;
+
+    fn try_from(row: CircuitBreakerRow) -> Result<Self, AppError> {
+        let state: CircuitState = row.state.parse().map_err(|_| {
+            AppError::DatabaseError(format!(
+                "Invalid circuit breaker state in database: '{}'",
+                row.state
+            ))
+        })?;
+        let time_until_half_open = match (state, row.half_open_at) {
+            (CircuitState::Open, Some(at)) => Some((at - Utc::now()).to_std().unwrap_or_default()),
+            _ => None,
+        };
+
+        Ok(CircuitBreakerRecord {
+            stats: CircuitBreakerStats {
+                name: row.name,
+                state,
+                failure_count: u32::try_from(row.failure_count).unwrap_or_default(),
+                success_count: u32::try_from(row.success_count).unwrap_or_default(),
+                last_error: row.last_error,
+                time_until_half_open,
+            },
+            updated_at: row.updated_at,
+            reset_requested_at: row.reset_requested_at,
+        })
+    }
+}
+
+impl CircuitBreakerRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    /// Insert or overwrite the reported state of a breaker.
+    ///
+    /// A pending reset request is kept, so a worker that reports just after
+    /// an operator reset still picks the request up on its next poll.
+    pub async fn save(&self, stats: &CircuitBreakerStats) -> Result<(), AppError> {
+        let half_open_at = stats
+            .time_until_half_open
+            .and_then(|d| chrono::Duration::from_std(d).ok())
+            .map(|d| Utc::now() + d);
+
+        sqlx::query(
+            r#"
+            INSERT INTO circuit_breakers (
+                name, state, failure_count, success_count, last_error, half_open_at
+            )
+            VALUES ($1, $2, $3, $4, $5, $6)
+            ON CONFLICT (name) DO UPDATE SET
+                state = EXCLUDED.state,
+                failure_count = EXCLUDED.failure_count,
+                success_count = EXCLUDED.success_count,
+                last_error = EXCLUDED.last_error,
+                half_open_at = EXCLUDED.half_open_at,
+                updated_at = NOW()
+            "#,
+        )
+        .bind(&stats.name)
+        .bind(stats.state.as_str())
+        .bind(i32::try_from(stats.failure_count).unwrap_or(i32::MAX))
+        .bind(i32::try_from(stats.success_count).unwrap_or(i32::MAX))
+        .bind(&stats.last_error)
+        .bind(half_open_at)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    /// List all reported breakers, ordered by name.
+    pub async fn list(&self) -> Result<Vec<CircuitBreakerRecord>, AppError> {
+        let rows = sqlx::query_as::<_, CircuitBreakerRow>(
+            r#"SELECT * FROM circuit_breakers ORDER BY name"#,
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        rows.into_iter()
+            .map(CircuitBreakerRecord::try_from)
+            .collect()
+    }
+
+    /// Look up a single breaker by name.
+    pub async fn get(&self, name: &str) -> Result<Option<CircuitBreakerRecord>, AppError> {
+        let row = sqlx::query_as::<_, CircuitBreakerRow>(
+            r#"SELECT * FROM circuit_breakers WHERE name = $1"#,
+        )
+        .bind(name)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        row.map(CircuitBreakerRecord::try_from).transpose()
+    }
+
+    /// Request a reset: the stored state is closed immediately and workers
+    /// reset their own breaker on their next poll.
+    ///
+    /// Returns `None` if no breaker with that name has been reported.
+    pub async fn request_reset(
+        &self,
+        name: &str,
+    ) -> Result<Option<CircuitBreakerRecord>, AppError> {
+        let row = sqlx::query_as::<_, CircuitBreakerRow>(
+            r#"
+            UPDATE circuit_breakers
+            SET state = 'closed',
+                failure_count = 0,
+                success_count = 0,
+                last_error = NULL,
+                half_open_at = NULL,
+                reset_requested_at = NOW(),
+                updated_at = NOW()
+            WHERE name = $1
+            RETURNING *
+            "#,
+        )
+        .bind(name)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        row.map(CircuitBreakerRecord::try_from).transpose()
+    }
+
+    /// When a reset of the named breaker was last requested.
+    pub async fn reset_requested_at(&self, name: &str) -> Result<Option<DateTime<Utc>>, AppError> {
+        let requested: Option<(Option<DateTime<Utc>>,)> =
+            sqlx::query_as(r#"SELECT reset_requested_at FROM circuit_breakers WHERE name = $1"#)
+                .bind(name)
+                .fetch_optional(&self.pool)
+                .await?;
+
+        Ok(requested.and_then(|(at,)| at))
+    }
+}
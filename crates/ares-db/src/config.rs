@@ -1,25 +1,60 @@
+use std::time::Duration;
+
 use ares_core::AppError;
 
+/// How long to wait for a connection before giving up, unless overridden.
+const DEFAULT_CONNECT_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Connection attempts made at startup before giving up, unless overridden.
+const DEFAULT_CONNECT_RETRIES: u32 = 3;
+
 /// Configuration for the database connection pool.
 #[derive(Debug, Clone)]
 pub struct DatabaseConfig {
     pub url: String,
     pub max_connections: u32,
+    /// Connections opened eagerly at startup and kept open while idle.
+    pub min_connections: u32,
+    /// Upper bound on opening (or acquiring) a single connection.
+    pub connect_timeout: Duration,
+    /// Attempts [`Database::connect`](crate::Database::connect) makes before
+    /// failing, with exponential backoff in between.
+    pub connect_retries: u32,
 }
 
 impl DatabaseConfig {
+    /// Configuration for `url` with the default pool sizes (max 5, min 1),
+    /// a 30s connect timeout, and 3 connection attempts.
+    pub fn new(url: impl Into<String>) -> Self {
+        Self {
+            url: url.into(),
+            max_connections: 5,
+            min_connections: 1,
+            connect_timeout: DEFAULT_CONNECT_TIMEOUT,
+            connect_retries: DEFAULT_CONNECT_RETRIES,
+        }
+    }
+
     /// Read configuration from environment variables.
     ///
     /// - `DATABASE_URL` (required)
     /// - `DATABASE_MAX_CONNECTIONS` (optional, defaults to 5)
+    /// - `DATABASE_MIN_CONNECTIONS` (optional, defaults to 1, at most the max)
+    /// - `DATABASE_CONNECT_TIMEOUT` (optional, seconds, defaults to 30)
+    /// - `DATABASE_CONNECT_RETRIES` (optional, attempts, defaults to 3)
     pub fn from_env() -> Result<Self, AppError> {
-        let url = std::env::var("DATABASE_URL").map_err(|_| {
+        Self::from_lookup(|key| std::env::var(key).ok())
+    }
+
+    /// Like [`DatabaseConfig::from_env`], reading variables through `get`.
+    pub fn from_lookup(get: impl Fn(&str) -> Option<String>) -> Result<Self, AppError> {
+        let url = get("DATABASE_URL").ok_or_else(|| {
             AppError::ConfigError("DATABASE_URL not set. Required for database operations.".into())
         })?;
 
-        let max_connections = match std::env::var("DATABASE_MAX_CONNECTIONS") {
-            Err(_) => 5,
-            Ok(raw) => {
+        let max_connections = match get("DATABASE_MAX_CONNECTIONS") {
+            None => 5,
+            Some(raw) => {
                 let parsed: u32 = raw.parse().map_err(|_| {
                     AppError::ConfigError(format!(
                         "Invalid DATABASE_MAX_CONNECTIONS '{raw}': must be a positive integer"
@@ -34,9 +69,164 @@ impl DatabaseConfig {
             }
         };
 
+        let min_connections = match get("DATABASE_MIN_CONNECTIONS") {
+            None => 1.min(max_connections),
+            Some(raw) => raw.parse().map_err(|_| {
+                AppError::ConfigError(format!(
+                    "Invalid DATABASE_MIN_CONNECTIONS '{raw}': must be a non-negative integer"
+                ))
+            })?,
+        };
+        if min_connections > max_connections {
+            return Err(AppError::ConfigError(format!(
+                "DATABASE_MIN_CONNECTIONS ({min_connections}) must not exceed \
+                 DATABASE_MAX_CONNECTIONS ({max_connections})"
+            )));
+        }
+
+        let connect_timeout = match get("DATABASE_CONNECT_TIMEOUT") {
+            None => DEFAULT_CONNECT_TIMEOUT,
+            Some(raw) => match raw.parse::<u64>() {
+                Ok(secs) if secs > 0 => Duration::from_secs(secs),
+                _ => {
+                    return Err(AppError::ConfigError(format!(
+                        "Invalid DATABASE_CONNECT_TIMEOUT '{raw}': must be a positive number of seconds"
+                    )));
+                }
+            },
+        };
+
+        let connect_retries = match get("DATABASE_CONNECT_RETRIES") {
+            None => DEFAULT_CONNECT_RETRIES,
+            Some(raw) => match raw.parse::<u32>() {
+                Ok(attempts) if attempts > 0 => attempts,
+                _ => {
+                    return Err(AppError::ConfigError(format!(
+                        "Invalid DATABASE_CONNECT_RETRIES '{raw}': must be a positive integer"
+                    )));
+                }
+            },
+        };
+
         Ok(Self {
             url,
             max_connections,
+            min_connections,
+            connect_timeout,
+            connect_retries,
         })
     }
+
+    /// The URL with its password replaced by `[REDACTED]`, for logs and
+    /// diagnostics. A URL that doesn't parse is masked entirely, since it
+    /// can't be checked for a password.
+    pub fn redacted_url(&self) -> String {
+        match url::Url::parse(&self.url) {
+            // `password()` is the password as written in the URL, so it can
+            // be replaced in place without re-encoding the rest.
+            Ok(parsed) => match parsed.password() {
+                Some(password) => self
+                    .url
+                    .replacen(&format!(":{password}@"), ":[REDACTED]@", 1),
+                None => self.url.clone(),
+            },
+            Err(_) => "[REDACTED]".to_string(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use super::*;
+
+    fn lookup(vars: &[(&str, &str)]) -> Result<DatabaseConfig, AppError> {
+        let vars: HashMap<String, String> = vars
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect();
+        DatabaseConfig::from_lookup(|key| vars.get(key).cloned())
+    }
+
+    #[test]
+    fn connect_settings_default_and_parse() {
+        let config = lookup(&[("DATABASE_URL", "postgres://localhost/ares")]).unwrap();
+        assert_eq!(config.connect_timeout, Duration::from_secs(30));
+        assert_eq!(config.connect_retries, 3);
+
+        let config = lookup(&[
+            ("DATABASE_URL", "postgres://localhost/ares"),
+            ("DATABASE_CONNECT_TIMEOUT", "5"),
+            ("DATABASE_CONNECT_RETRIES", "1"),
+        ])
+        .unwrap();
+        assert_eq!(config.connect_timeout, Duration::from_secs(5));
+        assert_eq!(config.connect_retries, 1);
+
+        for (key, raw) in [
+            ("DATABASE_CONNECT_TIMEOUT", "0"),
+            ("DATABASE_CONNECT_TIMEOUT", "soon"),
+            ("DATABASE_CONNECT_RETRIES", "0"),
+        ] {
+            let err =
+                lookup(&[("DATABASE_URL", "postgres://localhost/ares"), (key, raw)]).unwrap_err();
+            assert!(err.to_string().contains(key), "{err}");
+        }
+    }
+
+    #[test]
+    fn min_connections_default_parse_and_bound() {
+        let url = ("DATABASE_URL", "postgres://localhost/ares");
+        assert_eq!(lookup(&[url]).unwrap().min_connections, 1);
+
+        let config = lookup(&[
+            url,
+            ("DATABASE_MAX_CONNECTIONS", "10"),
+            ("DATABASE_MIN_CONNECTIONS", "10"),
+        ])
+        .unwrap();
+        assert_eq!(config.min_connections, 10);
+        let config = lookup(&[url, ("DATABASE_MIN_CONNECTIONS", "0")]).unwrap();
+        assert_eq!(config.min_connections, 0);
+
+        for raw in ["-1", "two", ""] {
+            let err = lookup(&[url, ("DATABASE_MIN_CONNECTIONS", raw)]).unwrap_err();
+            assert!(
+                err.to_string()
+                    .contains(&format!("Invalid DATABASE_MIN_CONNECTIONS '{raw}'")),
+                "{err}"
+            );
+        }
+
+        let err = lookup(&[
+            url,
+            ("DATABASE_MAX_CONNECTIONS", "4"),
+            ("DATABASE_MIN_CONNECTIONS", "5"),
+        ])
+        .unwrap_err();
+        assert!(
+            err.to_string().contains(
+                "DATABASE_MIN_CONNECTIONS (5) must not exceed DATABASE_MAX_CONNECTIONS (4)"
+            ),
+            "{err}"
+        );
+        // The default max of 5 bounds an explicit min too.
+        let err = lookup(&[url, ("DATABASE_MIN_CONNECTIONS", "6")]).unwrap_err();
+        assert!(err.to_string().contains("must not exceed"), "{err}");
+    }
+
+    #[test]
+    fn redacted_url_masks_only_the_password() {
+        let redacted = |url: &str| DatabaseConfig::new(url).redacted_url();
+        assert_eq!(
+            redacted("postgres://ares:hunter22@db:5432/ares"),
+            "postgres://ares:[REDACTED]@db:5432/ares"
+        );
+        assert_eq!(
+            redacted("postgres://localhost/ares"),
+            "postgres://localhost/ares"
+        );
+        assert_eq!(redacted("not a url p@ss"), "[REDACTED]");
+    }
 }
@@ -1,10 +1,58 @@
+use std::time::Duration;
+
 use ares_core::AppError;
-use sqlx::PgPool;
+use chrono::{DateTime, Utc};
+use futures::future::BoxFuture;
+use serde::Serialize;
+use sqlx::migrate::Migrator;
 use sqlx::postgres::PgPoolOptions;
+use sqlx::{PgPool, PgTransaction};
 
+use crate::api_key_repository::ApiKeyRepository;
+use crate::audit_repository::AuditRepository;
+use crate::circuit_breaker_repository::CircuitBreakerRepository;
 use crate::config::DatabaseConfig;
 use crate::job_repository::ScrapeJobRepository;
 use crate::repository::ExtractionRepository;
+use crate::stats_repository::StatsRepository;
+use crate::usage_repository::UsageRepository;
+use crate::worker_event_repository::WorkerEventRepository;
+
+/// Migrations bundled from `./migrations` at compile time.
+static MIGRATOR: Migrator = sqlx::migrate!("./migrations");
+
+/// Pause before the second connection attempt; doubled after each failure.
+const CONNECT_INITIAL_BACKOFF: Duration = Duration::from_millis(500);
+
+/// A bundled migration and, once applied, when.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct MigrationStatus {
+    pub version: i64,
+    pub description: String,
+    /// When the migration was applied; `None` while it is pending.
+    pub installed_on: Option<DateTime<Utc>>,
+}
+
+impl MigrationStatus {
+    pub fn is_applied(&self) -> bool {
+        self.installed_on.is_some()
+    }
+}
+
+/// Row counts of the tables that grow with use.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct TableCounts {
+    pub extractions: i64,
+    pub scrape_jobs: i64,
+}
+
+/// Rows deleted by [`Database::prune`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+pub struct PruneReport {
+    pub scrape_jobs: u64,
+    pub worker_events: u64,
+    pub crawl_visited_urls: u64,
+}
 
 /// Central database facade — owns the connection pool, runs migrations,
 /// and vends repository instances.
@@ -15,30 +63,246 @@ pub struct Database {
 
 impl Database {
     /// Connect to PostgreSQL with the given configuration.
+    ///
+    /// Up to `connect_retries` attempts are made, doubling the pause between
+    /// them, so a database that is still starting up doesn't abort the caller.
+    /// The pool is warmed up by opening `min_connections` connections
+    /// concurrently, so the first burst of traffic doesn't have to.
     pub async fn connect(config: &DatabaseConfig) -> Result<Self, AppError> {
-        let pool = PgPoolOptions::new()
-            .max_connections(config.max_connections)
-            .connect(&config.url)
-            .await
-            .map_err(|e| AppError::DatabaseError(format!("Failed to connect: {e}")))?;
+        let attempts = config.connect_retries.max(1);
+        let mut backoff = CONNECT_INITIAL_BACKOFF;
+        let mut attempt = 1;
+        let pool = loop {
+            let result = PgPoolOptions::new()
+                .max_connections(config.max_connections)
+                .min_connections(config.min_connections)
+                .acquire_timeout(config.connect_timeout)
+                .connect(&config.url)
+                .await;
+            match result {
+                Ok(pool) => break pool,
+                Err(e) if attempt < attempts => {
+                    tracing::warn!(
+                        attempt,
+                        attempts,
+                        retry_in_ms = backoff.as_millis() as u64,
+                        "Database connection failed: {e}"
+                    );
+                    tokio::time::sleep(backoff).await;
+                    backoff *= 2;
+                    attempt += 1;
+                }
+                Err(e) => {
+                    return Err(AppError::DatabaseError(format!(
+                        "Failed to connect after {attempts} attempt(s): {e}"
+                    )));
+                }
+            }
+        };
+
+        // Hold every connection until all are acquired so each one is distinct.
+        let warm =
+            futures::future::try_join_all((0..config.min_connections).map(|_| pool.acquire()))
+                .await
+                .map_err(|e| AppError::DatabaseError(format!("Failed to warm up pool: {e}")))?;
+        tracing::debug!(connections = warm.len(), "Database pool warmed up");
+        drop(warm);
 
         Ok(Self { pool })
     }
 
+    /// Connect like [`Database::connect`], overriding the configured
+    /// `min_connections` (capped at `max_connections`).
+    pub async fn with_min_connections(
+        config: &DatabaseConfig,
+        min_connections: u32,
+    ) -> Result<Self, AppError> {
+        let config = DatabaseConfig {
+            min_connections: min_connections.min(config.max_connections),
+            ..config.clone()
+        };
+        Self::connect(&config).await
+    }
+
     /// Create a `Database` from an existing pool (useful for testing).
     pub fn from_pool(pool: PgPool) -> Self {
         Self { pool }
     }
 
+    /// Run `f` in a transaction, committing it when `f` returns `Ok`. On an
+    /// error, or if the future is dropped before finishing, the transaction
+    /// is rolled back.
+    ///
+    /// Write through [`TransactionalExtractionRepository`] and
+    /// [`TransactionalJobRepository`] built over the transaction:
+    ///
+    /// ```no_run
+    /// # use ares_db::{Database, TransactionalExtractionRepository, TransactionalJobRepository};
+    /// # async fn example(db: Database, job_id: uuid::Uuid, extraction: ares_core::models::NewExtraction) -> Result<(), ares_core::AppError> {
+    /// let id = db
+    ///     .transaction(|tx| {
+    ///         Box::pin(async move {
+    ///             let id = TransactionalExtractionRepository::new(tx).save(&extraction).await?;
+    ///             TransactionalJobRepository::new(tx).complete_job(job_id, Some(id)).await?;
+    ///             Ok(id)
+    ///         })
+    ///     })
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// [`TransactionalExtractionRepository`]: crate::TransactionalExtractionRepository
+    /// [`TransactionalJobRepository`]: crate::TransactionalJobRepository
+    pub async fn transaction<T, F>(&self, f: F) -> Result<T, AppError>
+    where
+        F: for<'t> FnOnce(&'t mut PgTransaction<'static>) -> BoxFuture<'t, Result<T, AppError>>,
+    {
+        let mut tx = self.pool.begin().await?;
+        let value = f(&mut tx).await?;
+        tx.commit().await?;
+        Ok(value)
+    }
+
     /// Run all pending migrations.
     pub async fn migrate(&self) -> Result<(), AppError> {
-        sqlx::migrate!("./migrations")
+        MIGRATOR
             .run(&self.pool)
             .await
             .map_err(|e| AppError::DatabaseError(format!("Migration failed: {e}")))?;
         Ok(())
     }
 
+    /// Run pending migrations when `auto_migrate` is set; otherwise fail if
+    /// any are pending, leaving the schema untouched (`ARES_AUTO_MIGRATE=false`).
+    pub async fn ensure_migrated(&self, auto_migrate: bool) -> Result<(), AppError> {
+        if auto_migrate {
+            return self.migrate().await;
+        }
+
+        let pending: Vec<String> = self
+            .migration_status()
+            .await?
+            .into_iter()
+            .filter(|m| !m.is_applied())
+            .map(|m| m.version.to_string())
+            .collect();
+        if pending.is_empty() {
+            return Ok(());
+        }
+        Err(AppError::DatabaseError(format!(
+            "{} pending migration(s) ({}) and ARES_AUTO_MIGRATE=false; \
+             run `ares db migrate` first",
+            pending.len(),
+            pending.join(", ")
+        )))
+    }
+
+    /// Whether every bundled migration has been applied successfully.
+    pub async fn migrations_applied(&self) -> Result<bool, AppError> {
+        Ok(self
+            .migration_status()
+            .await?
+            .iter()
+            .all(MigrationStatus::is_applied))
+    }
+
+    /// Every bundled migration in version order, with when it was applied.
+    pub async fn migration_status(&self) -> Result<Vec<MigrationStatus>, AppError> {
+        let (exists,): (bool,) =
+            sqlx::query_as("SELECT to_regclass('_sqlx_migrations') IS NOT NULL")
+                .fetch_one(&self.pool)
+                .await?;
+        let applied: Vec<(i64, DateTime<Utc>)> = if exists {
+            sqlx::query_as("SELECT version, installed_on FROM _sqlx_migrations WHERE success")
+                .fetch_all(&self.pool)
+                .await?
+        } else {
+            Vec::new()
+        };
+
+        Ok(MIGRATOR
+            .iter()
+            .filter(|m| !m.migration_type.is_down_migration())
+            .map(|m| MigrationStatus {
+                version: m.version,
+                description: m.description.to_string(),
+                installed_on: applied
+                    .iter()
+                    .find(|(version, _)| *version == m.version)
+                    .map(|(_, installed_on)| *installed_on),
+            })
+            .collect())
+    }
+
+    /// Current row counts of `extractions` and `scrape_jobs`.
+    pub async fn table_counts(&self) -> Result<TableCounts, AppError> {
+        let (extractions, scrape_jobs): (i64, i64) = sqlx::query_as(
+            "SELECT (SELECT COUNT(*) FROM extractions), (SELECT COUNT(*) FROM scrape_jobs)",
+        )
+        .fetch_one(&self.pool)
+        .await?;
+        Ok(TableCounts {
+            extractions,
+            scrape_jobs,
+        })
+    }
+
+    /// Delete finished jobs, worker events, and crawl bookkeeping last
+    /// touched before `cutoff`, in one transaction.
+    ///
+    /// Jobs of a crawl session are only deleted together, once every job in
+    /// the session is finished and older than `cutoff`, so `parent_job_id`
+    /// references never dangle. Extractions are history and are kept.
+    pub async fn prune(&self, cutoff: DateTime<Utc>) -> Result<PruneReport, AppError> {
+        let mut tx = self.pool.begin().await?;
+
+        let scrape_jobs = sqlx::query(
+            r#"
+            DELETE FROM scrape_jobs j
+            WHERE j.status IN ('completed', 'failed', 'cancelled')
+              AND j.updated_at < $1
+              AND NOT EXISTS (
+                  SELECT 1 FROM scrape_jobs s
+                  WHERE s.crawl_session_id = j.crawl_session_id
+                    AND (s.status NOT IN ('completed', 'failed', 'cancelled')
+                         OR s.updated_at >= $1)
+              )
+            "#,
+        )
+        .bind(cutoff)
+        .execute(&mut *tx)
+        .await?
+        .rows_affected();
+
+        let worker_events = sqlx::query("DELETE FROM worker_events WHERE created_at < $1")
+            .bind(cutoff)
+            .execute(&mut *tx)
+            .await?
+            .rows_affected();
+
+        let crawl_visited_urls = sqlx::query(
+            r#"
+            DELETE FROM crawl_visited_urls v
+            WHERE v.created_at < $1
+              AND NOT EXISTS (
+                  SELECT 1 FROM scrape_jobs j WHERE j.crawl_session_id = v.session_id
+              )
+            "#,
+        )
+        .bind(cutoff)
+        .execute(&mut *tx)
+        .await?
+        .rows_affected();
+
+        tx.commit().await?;
+        Ok(PruneReport {
+            scrape_jobs,
+            worker_events,
+            crawl_visited_urls,
+        })
+    }
+
     /// Get an [`ExtractionRepository`] backed by this pool.
     pub fn extraction_repo(&self) -> ExtractionRepository {
         ExtractionRepository::new(self.pool.clone())
@@ -49,6 +313,36 @@ impl Database {
         ScrapeJobRepository::new(self.pool.clone())
     }
 
+    /// Get an [`ApiKeyRepository`] backed by this pool.
+    pub fn api_key_repo(&self) -> ApiKeyRepository {
+        ApiKeyRepository::new(self.pool.clone())
+    }
+
+    /// Get an [`AuditRepository`] backed by this pool.
+    pub fn audit_repo(&self) -> AuditRepository {
+        AuditRepository::new(self.pool.clone())
+    }
+
+    /// Get a [`CircuitBreakerRepository`] backed by this pool.
+    pub fn circuit_breaker_repo(&self) -> CircuitBreakerRepository {
+        CircuitBreakerRepository::new(self.pool.clone())
+    }
+
+    /// Get a [`StatsRepository`] backed by this pool.
+    pub fn stats_repo(&self) -> StatsRepository {
+        StatsRepository::new(self.pool.clone())
+    }
+
+    /// Get a [`UsageRepository`] backed by this pool.
+    pub fn usage_repo(&self) -> UsageRepository {
+        UsageRepository::new(self.pool.clone())
+    }
+
+    /// Get a [`WorkerEventRepository`] backed by this pool.
+    pub fn worker_event_repo(&self) -> WorkerEventRepository {
+        WorkerEventRepository::new(self.pool.clone())
+    }
+
     /// Get a reference to the underlying pool.
     pub fn pool(&self) -> &PgPool {
         &self.pool
@@ -1,23 +1,109 @@
+use std::collections::HashMap;
+use std::time::Duration;
+
 use chrono::{DateTime, Utc};
-use sqlx::{PgPool, Pool, Postgres};
+use sqlx::query_builder::Separated;
+use sqlx::{PgExecutor, PgPool, PgTransaction, Pool, Postgres, QueryBuilder};
 use uuid::Uuid;
 
+use ares_core::circuit_breaker::CircuitBreakerStats;
 use ares_core::error::AppError;
 use ares_core::job::{CreateScrapeJobRequest, JobStatus, ScrapeJob};
 use ares_core::job_queue::JobQueue;
+use ares_core::models::NewExtraction;
+use ares_core::traits::ExtractionStore;
+
+use crate::circuit_breaker_repository::CircuitBreakerRepository;
+use crate::database::Database;
+use crate::repository::TransactionalExtractionRepository;
+
+/// `LIKE` pattern matching every schema name.
+const ANY_SCHEMA: &str = "%";
 
 /// PostgreSQL-backed job queue using `SELECT FOR UPDATE SKIP LOCKED`.
 #[derive(Clone)]
 pub struct ScrapeJobRepository {
     pool: Pool<Postgres>,
+    /// `LIKE` pattern a job's schema name must match to be claimed.
+    schema_pattern: String,
 }
 
 impl ScrapeJobRepository {
     pub fn new(pool: PgPool) -> Self {
-        Self { pool }
+        Self {
+            pool,
+            schema_pattern: ANY_SCHEMA.to_string(),
+        }
+    }
+
+    /// Only claim jobs whose schema name matches `filter`, where `*` matches
+    /// any run of characters (e.g. `blog*`). Other calls are unaffected.
+    pub fn with_schema_filter(mut self, filter: &str) -> Self {
+        self.schema_pattern = like_pattern(filter);
+        self
     }
 }
 
+/// `glob` as a `LIKE` pattern: `*` becomes `%`, and the characters `LIKE`
+/// treats specially are escaped.
+fn like_pattern(glob: &str) -> String {
+    let mut pattern = String::with_capacity(glob.len());
+    for c in glob.chars() {
+        match c {
+            '*' => pattern.push('%'),
+            '%' | '_' | '\\' => {
+                pattern.push('\\');
+                pattern.push(c);
+            }
+            c => pattern.push(c),
+        }
+    }
+    pattern
+}
+
+/// Job writes made inside a transaction, so they commit or roll back
+/// together with the transaction's other writes. See
+/// [`Database::transaction`].
+pub struct TransactionalJobRepository<'t> {
+    tx: &'t mut PgTransaction<'static>,
+}
+
+impl<'t> TransactionalJobRepository<'t> {
+    pub fn new(tx: &'t mut PgTransaction<'static>) -> Self {
+        Self { tx }
+    }
+
+    /// Like [`JobQueue::complete_job`].
+    pub async fn complete_job(
+        &mut self,
+        job_id: Uuid,
+        extraction_id: Option<Uuid>,
+    ) -> Result<(), AppError> {
+        complete_job(&mut **self.tx, job_id, extraction_id).await
+    }
+}
+
+async fn complete_job<'e>(
+    executor: impl PgExecutor<'e>,
+    job_id: Uuid,
+    extraction_id: Option<Uuid>,
+) -> Result<(), AppError> {
+    sqlx::query(
+        r#"
+        UPDATE scrape_jobs
+        SET status = 'completed', completed_at = NOW(), updated_at = NOW(),
+            extraction_id = $2, error_message = NULL, worker_id = NULL
+        WHERE id = $1
+        "#,
+    )
+    .bind(job_id)
+    .bind(extraction_id)
+    .execute(executor)
+    .await?;
+
+    Ok(())
+}
+
 // -- Internal row type for sqlx deserialization --
 
 #[derive(sqlx::FromRow)]
@@ -45,15 +131,16 @@ struct ScrapeJobRow {
     max_depth: i32,
     max_pages: i32,
     allowed_domains: serde_json::Value,
+    fetch_options: serde_json::Value,
+    notify_url: Option<String>,
+    tags: Vec<String>,
 }
 
 impl TryFrom<ScrapeJobRow> for ScrapeJob {
     type Error = AppError;
 
     fn try_from(row: ScrapeJobRow) -> Result<Self, AppError> {
-        let status = row.status.parse().map_err(|_| {
-            AppError::DatabaseError(format!("Invalid job status in database: '{}'", row.status))
-        })?;
+        let status = JobStatus::from_db_str(&row.status)?;
         Ok(ScrapeJob {
             id: row.id,
             url: row.url,
@@ -86,69 +173,181 @@ impl TryFrom<ScrapeJobRow> for ScrapeJob {
             allowed_domains: serde_json::from_value(row.allowed_domains).map_err(|e| {
                 AppError::DatabaseError(format!("Invalid allowed_domains JSON: {e}"))
             })?,
+            fetch_options: serde_json::from_value(row.fetch_options)
+                .map_err(|e| AppError::DatabaseError(format!("Invalid fetch_options JSON: {e}")))?,
+            notify_url: row.notify_url,
+            tags: row.tags,
         })
     }
 }
 
+const INSERT_JOBS: &str = r#"
+    INSERT INTO scrape_jobs (
+        url, schema_name, schema, model, base_url, max_retries,
+        crawl_session_id, parent_job_id, depth, max_depth,
+        max_pages, allowed_domains, fetch_options, notify_url, tags, domain
+    )
+    "#;
+
+/// Jobs per `INSERT` in [`ScrapeJobRepository::create_jobs`], keeping the 16
+/// bind parameters per job well under Postgres' limit of 65535.
+const CREATE_JOBS_CHUNK: usize = 1000;
+
+/// The column values of a new job, converted for binding.
+struct NewJobRow<'a> {
+    request: &'a CreateScrapeJobRequest,
+    max_retries: i32,
+    depth: i32,
+    max_depth: i32,
+    max_pages: i32,
+    allowed_domains: serde_json::Value,
+    fetch_options: serde_json::Value,
+    domain: Option<String>,
+}
+
+impl<'a> TryFrom<&'a CreateScrapeJobRequest> for NewJobRow<'a> {
+    type Error = AppError;
+
+    fn try_from(request: &'a CreateScrapeJobRequest) -> Result<Self, AppError> {
+        Ok(Self {
+            request,
+            max_retries: request.max_retries.unwrap_or(3) as i32,
+            depth: i32::try_from(request.depth).map_err(|_| {
+                AppError::DatabaseError(format!("depth out of range: {}", request.depth))
+            })?,
+            max_depth: i32::try_from(request.max_depth).map_err(|_| {
+                AppError::DatabaseError(format!("max_depth out of range: {}", request.max_depth))
+            })?,
+            max_pages: i32::try_from(request.max_pages).map_err(|_| {
+                AppError::DatabaseError(format!("max_pages out of range: {}", request.max_pages))
+            })?,
+            allowed_domains: serde_json::to_value(&request.allowed_domains).map_err(|e| {
+                AppError::DatabaseError(format!("Failed to serialize allowed_domains: {e}"))
+            })?,
+            fetch_options: serde_json::to_value(&request.fetch_options).map_err(|e| {
+                AppError::DatabaseError(format!("Failed to serialize fetch_options: {e}"))
+            })?,
+            domain: ares_core::domain_key(&request.url),
+        })
+    }
+}
+
+impl<'a> NewJobRow<'a> {
+    /// Bind this job as one `VALUES` tuple of [`INSERT_JOBS`].
+    fn push(mut values: Separated<'_, 'a, Postgres, &'static str>, row: Self) {
+        let request = row.request;
+        values
+            .push_bind(&request.url)
+            .push_bind(&request.schema_name)
+            .push_bind(&request.schema)
+            .push_bind(&request.model)
+            .push_bind(&request.base_url)
+            .push_bind(row.max_retries)
+            .push_bind(request.crawl_session_id)
+            .push_bind(request.parent_job_id)
+            .push_bind(row.depth)
+            .push_bind(row.max_depth)
+            .push_bind(row.max_pages)
+            .push_bind(row.allowed_domains)
+            .push_bind(row.fetch_options)
+            .push_bind(&request.notify_url)
+            .push_bind(&request.tags)
+            .push_bind(row.domain);
+    }
+}
+
 impl ScrapeJobRepository {
-    /// Count jobs, optionally filtered by status.
-    pub async fn count_jobs(&self, status: Option<JobStatus>) -> Result<i64, AppError> {
-        let (count,): (i64,) = if let Some(status) = status {
-            sqlx::query_as(r#"SELECT COUNT(*) FROM scrape_jobs WHERE status = $1"#)
-                .bind(status.as_str())
-                .fetch_one(&self.pool)
-                .await
-        } else {
-            sqlx::query_as(r#"SELECT COUNT(*) FROM scrape_jobs"#)
-                .fetch_one(&self.pool)
-                .await
-        }
-        .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+    /// Count jobs, optionally filtered by status, by the worker holding
+    /// them, and by a tag they carry.
+    pub async fn count_jobs(
+        &self,
+        status: Option<JobStatus>,
+        worker_id: Option<&str>,
+        tag: Option<&str>,
+    ) -> Result<i64, AppError> {
+        let mut query = QueryBuilder::<Postgres>::new("SELECT COUNT(*) FROM scrape_jobs");
+        push_job_filters(&mut query, status, worker_id, tag);
+        let (count,): (i64,) = query.build_query_as().fetch_one(&self.pool).await?;
 
         Ok(count)
     }
-}
 
-impl JobQueue for ScrapeJobRepository {
-    async fn create_job(&self, request: CreateScrapeJobRequest) -> Result<ScrapeJob, AppError> {
-        let row = sqlx::query_as::<_, ScrapeJobRow>(
+    /// Running jobs per worker. Workers with nothing running are absent.
+    pub async fn count_running_by_worker(&self) -> Result<HashMap<String, i64>, AppError> {
+        let rows: Vec<(String, i64)> = sqlx::query_as(
             r#"
-            INSERT INTO scrape_jobs (
-                url, schema_name, schema, model, base_url, max_retries,
-                crawl_session_id, parent_job_id, depth, max_depth,
-                max_pages, allowed_domains
-            )
-            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12)
-            RETURNING *
+            SELECT worker_id, COUNT(*)
+            FROM scrape_jobs
+            WHERE status = 'running' AND worker_id IS NOT NULL
+            GROUP BY worker_id
             "#,
         )
-        .bind(&request.url)
-        .bind(&request.schema_name)
-        .bind(&request.schema)
-        .bind(&request.model)
-        .bind(&request.base_url)
-        .bind(request.max_retries.unwrap_or(3) as i32)
-        .bind(request.crawl_session_id)
-        .bind(request.parent_job_id)
-        .bind(i32::try_from(request.depth).map_err(|_| {
-            AppError::DatabaseError(format!("depth out of range: {}", request.depth))
-        })?)
-        .bind(i32::try_from(request.max_depth).map_err(|_| {
-            AppError::DatabaseError(format!("max_depth out of range: {}", request.max_depth))
-        })?)
-        .bind(i32::try_from(request.max_pages).map_err(|_| {
-            AppError::DatabaseError(format!("max_pages out of range: {}", request.max_pages))
-        })?)
-        .bind(serde_json::to_value(&request.allowed_domains).map_err(|e| {
-            AppError::DatabaseError(format!("Failed to serialize allowed_domains: {e}"))
-        })?)
-        .fetch_one(&self.pool)
-        .await
-        .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(rows.into_iter().collect())
+    }
+}
+
+/// Append the `WHERE` clause shared by [`JobQueue::list_jobs`] and
+/// [`ScrapeJobRepository::count_jobs`].
+fn push_job_filters(
+    query: &mut QueryBuilder<'_, Postgres>,
+    status: Option<JobStatus>,
+    worker_id: Option<&str>,
+    tag: Option<&str>,
+) {
+    query.push(" WHERE TRUE");
+    if let Some(status) = status {
+        query.push(" AND status = ");
+        query.push_bind(status.as_str());
+    }
+    if let Some(worker_id) = worker_id {
+        query.push(" AND worker_id = ");
+        query.push_bind(worker_id.to_string());
+    }
+    if let Some(tag) = tag {
+        query.push(" AND ");
+        query.push_bind(tag.to_string());
+        query.push(" = ANY(tags)");
+    }
+}
+
+impl JobQueue for ScrapeJobRepository {
+    async fn create_job(&self, request: CreateScrapeJobRequest) -> Result<ScrapeJob, AppError> {
+        let row = NewJobRow::try_from(&request)?;
+        let mut query = QueryBuilder::<Postgres>::new(INSERT_JOBS);
+        query.push_values([row], NewJobRow::push);
+        query.push(" RETURNING *");
+        let row: ScrapeJobRow = query.build_query_as().fetch_one(&self.pool).await?;
 
         row.try_into()
     }
 
+    async fn create_jobs(
+        &self,
+        requests: Vec<CreateScrapeJobRequest>,
+    ) -> Result<Vec<ScrapeJob>, AppError> {
+        let mut tx = self.pool.begin().await?;
+        let mut jobs = Vec::with_capacity(requests.len());
+        for chunk in requests.chunks(CREATE_JOBS_CHUNK) {
+            let rows = chunk
+                .iter()
+                .map(NewJobRow::try_from)
+                .collect::<Result<Vec<_>, _>>()?;
+            let mut query = QueryBuilder::<Postgres>::new(INSERT_JOBS);
+            query.push_values(rows, NewJobRow::push);
+            query.push(" RETURNING *");
+            let rows: Vec<ScrapeJobRow> = query.build_query_as().fetch_all(&mut *tx).await?;
+            for row in rows {
+                jobs.push(row.try_into()?);
+            }
+        }
+        tx.commit().await?;
+
+        Ok(jobs)
+    }
+
     async fn claim_job(&self, worker_id: &str) -> Result<Option<ScrapeJob>, AppError> {
         let row = sqlx::query_as::<_, ScrapeJobRow>(
             r#"
@@ -156,7 +355,8 @@ impl JobQueue for ScrapeJobRepository {
             SET status = 'running', worker_id = $1, started_at = NOW(), updated_at = NOW()
             WHERE id = (
                 SELECT id FROM scrape_jobs
-                WHERE status = 'pending'
+                WHERE schema_name LIKE $2
+                  AND status = 'pending'
                   AND (next_retry_at IS NULL OR next_retry_at <= NOW())
                 ORDER BY next_retry_at NULLS FIRST, created_at ASC
                 FOR UPDATE SKIP LOCKED
@@ -166,9 +366,9 @@ impl JobQueue for ScrapeJobRepository {
             "#,
         )
         .bind(worker_id)
+        .bind(&self.schema_pattern)
         .fetch_optional(&self.pool)
-        .await
-        .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+        .await?;
 
         row.map(ScrapeJob::try_from).transpose()
     }
@@ -178,21 +378,35 @@ impl JobQueue for ScrapeJobRepository {
         job_id: Uuid,
         extraction_id: Option<Uuid>,
     ) -> Result<(), AppError> {
-        sqlx::query(
-            r#"
-            UPDATE scrape_jobs
-            SET status = 'completed', completed_at = NOW(), updated_at = NOW(),
-                extraction_id = $2, error_message = NULL, worker_id = NULL
-            WHERE id = $1
-            "#,
-        )
-        .bind(job_id)
-        .bind(extraction_id)
-        .execute(&self.pool)
-        .await
-        .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+        complete_job(&self.pool, job_id, extraction_id).await
+    }
 
-        Ok(())
+    /// Saves through this queue's own database rather than `store`, in the
+    /// same transaction as completing the job.
+    async fn complete_job_with_extraction<S: ExtractionStore>(
+        &self,
+        job_id: Uuid,
+        _store: &S,
+        extraction: &NewExtraction,
+        upsert: bool,
+    ) -> Result<Uuid, AppError> {
+        let extraction = extraction.clone();
+        Database::from_pool(self.pool.clone())
+            .transaction(|tx| {
+                Box::pin(async move {
+                    let mut extractions = TransactionalExtractionRepository::new(tx);
+                    let id = if upsert {
+                        extractions.upsert(&extraction).await?
+                    } else {
+                        extractions.save(&extraction).await?
+                    };
+                    TransactionalJobRepository::new(tx)
+                        .complete_job(job_id, Some(id))
+                        .await?;
+                    Ok(id)
+                })
+            })
+            .await
     }
 
     async fn fail_job(
@@ -202,7 +416,8 @@ impl JobQueue for ScrapeJobRepository {
         next_retry_at: Option<DateTime<Utc>>,
     ) -> Result<(), AppError> {
         // If next_retry_at is set, reset to pending for retry.
-        // Otherwise mark as permanently failed.
+        // Otherwise mark as permanently failed. A completed job already has
+        // its extraction saved, so a late failure must not reopen it.
         sqlx::query(
             r#"
             UPDATE scrape_jobs
@@ -213,16 +428,44 @@ impl JobQueue for ScrapeJobRepository {
                 error_message = $2,
                 updated_at = NOW(),
                 worker_id = NULL,
-                started_at = CASE WHEN $3::timestamptz IS NOT NULL THEN NULL ELSE started_at END
-            WHERE id = $1
+                started_at = CASE WHEN $3::timestamptz IS NOT NULL THEN NULL ELSE started_at END,
+                completed_at = CASE WHEN $3::timestamptz IS NOT NULL THEN NULL ELSE NOW() END
+            WHERE id = $1 AND status <> 'completed'
             "#,
         )
         .bind(job_id)
         .bind(error)
         .bind(next_retry_at)
         .execute(&self.pool)
-        .await
-        .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+        .await?;
+
+        Ok(())
+    }
+
+    async fn defer_job(
+        &self,
+        job_id: Uuid,
+        reason: &str,
+        until: DateTime<Utc>,
+    ) -> Result<(), AppError> {
+        sqlx::query(
+            r#"
+            UPDATE scrape_jobs
+            SET
+                status = 'pending',
+                next_retry_at = $3,
+                error_message = $2,
+                updated_at = NOW(),
+                worker_id = NULL,
+                started_at = NULL
+            WHERE id = $1 AND status <> 'completed'
+            "#,
+        )
+        .bind(job_id)
+        .bind(reason)
+        .bind(until)
+        .execute(&self.pool)
+        .await?;
 
         Ok(())
     }
@@ -237,18 +480,31 @@ impl JobQueue for ScrapeJobRepository {
         )
         .bind(job_id)
         .execute(&self.pool)
-        .await
-        .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+        .await?;
 
         Ok(())
     }
 
+    async fn bulk_cancel(&self, job_ids: &[Uuid]) -> Result<u64, AppError> {
+        let result = sqlx::query(
+            r#"
+            UPDATE scrape_jobs
+            SET status = 'cancelled', updated_at = NOW(), worker_id = NULL
+            WHERE id = ANY($1::uuid[]) AND status NOT IN ('completed', 'cancelled')
+            "#,
+        )
+        .bind(job_ids)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(result.rows_affected())
+    }
+
     async fn get_job(&self, job_id: Uuid) -> Result<Option<ScrapeJob>, AppError> {
         let row = sqlx::query_as::<_, ScrapeJobRow>(r#"SELECT * FROM scrape_jobs WHERE id = $1"#)
             .bind(job_id)
             .fetch_optional(&self.pool)
-            .await
-            .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+            .await?;
 
         row.map(ScrapeJob::try_from).transpose()
     }
@@ -256,37 +512,18 @@ impl JobQueue for ScrapeJobRepository {
     async fn list_jobs(
         &self,
         status: Option<JobStatus>,
+        worker_id: Option<&str>,
+        tag: Option<&str>,
         limit: usize,
         offset: usize,
     ) -> Result<Vec<ScrapeJob>, AppError> {
-        let rows = if let Some(status) = status {
-            sqlx::query_as::<_, ScrapeJobRow>(
-                r#"
-                SELECT * FROM scrape_jobs
-                WHERE status = $1
-                ORDER BY created_at DESC, id DESC
-                LIMIT $2 OFFSET $3
-                "#,
-            )
-            .bind(status.as_str())
-            .bind(limit as i64)
-            .bind(offset as i64)
-            .fetch_all(&self.pool)
-            .await
-        } else {
-            sqlx::query_as::<_, ScrapeJobRow>(
-                r#"
-                SELECT * FROM scrape_jobs
-                ORDER BY created_at DESC, id DESC
-                LIMIT $1 OFFSET $2
-                "#,
-            )
-            .bind(limit as i64)
-            .bind(offset as i64)
-            .fetch_all(&self.pool)
-            .await
-        }
-        .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+        let mut query = QueryBuilder::<Postgres>::new("SELECT * FROM scrape_jobs");
+        push_job_filters(&mut query, status, worker_id, tag);
+        query.push(" ORDER BY created_at DESC, id DESC LIMIT ");
+        query.push_bind(limit as i64);
+        query.push(" OFFSET ");
+        query.push_bind(offset as i64);
+        let rows: Vec<ScrapeJobRow> = query.build_query_as().fetch_all(&self.pool).await?;
 
         rows.into_iter()
             .map(ScrapeJob::try_from)
@@ -312,12 +549,36 @@ impl JobQueue for ScrapeJobRepository {
         )
         .bind(job_id)
         .fetch_optional(&self.pool)
-        .await
-        .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+        .await?;
 
         row.map(ScrapeJob::try_from).transpose()
     }
 
+    async fn update_job_schema(
+        &self,
+        job_id: Uuid,
+        new_schema: serde_json::Value,
+    ) -> Result<(), AppError> {
+        let result = sqlx::query(
+            r#"
+            UPDATE scrape_jobs
+            SET schema = $2, updated_at = NOW()
+            WHERE id = $1 AND status = 'pending'
+            "#,
+        )
+        .bind(job_id)
+        .bind(new_schema)
+        .execute(&self.pool)
+        .await?;
+
+        if result.rows_affected() == 0 {
+            return Err(AppError::Conflict(format!(
+                "Job {job_id} is not pending; only pending jobs can change schema"
+            )));
+        }
+        Ok(())
+    }
+
     async fn release_job(&self, job_id: Uuid) -> Result<(), AppError> {
         sqlx::query(
             r#"
@@ -328,8 +589,7 @@ impl JobQueue for ScrapeJobRepository {
         )
         .bind(job_id)
         .execute(&self.pool)
-        .await
-        .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+        .await?;
 
         Ok(())
     }
@@ -344,8 +604,7 @@ impl JobQueue for ScrapeJobRepository {
         )
         .bind(worker_id)
         .execute(&self.pool)
-        .await
-        .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+        .await?;
 
         Ok(result.rows_affected())
     }
@@ -355,8 +614,18 @@ impl JobQueue for ScrapeJobRepository {
             sqlx::query_as(r#"SELECT COUNT(*) FROM scrape_jobs WHERE status = $1"#)
                 .bind(status.as_str())
                 .fetch_one(&self.pool)
-                .await
-                .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+                .await?;
+
+        Ok(count)
+    }
+
+    async fn count_by_worker(&self, worker_id: &str) -> Result<i64, AppError> {
+        let (count,): (i64,) = sqlx::query_as(
+            r#"SELECT COUNT(*) FROM scrape_jobs WHERE worker_id = $1 AND status = 'running'"#,
+        )
+        .bind(worker_id)
+        .fetch_one(&self.pool)
+        .await?;
 
         Ok(count)
     }
@@ -373,8 +642,7 @@ impl JobQueue for ScrapeJobRepository {
         .bind(session_id)
         .bind(url_hash)
         .execute(&self.pool)
-        .await
-        .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+        .await?;
 
         Ok(result.rows_affected() > 0)
     }
@@ -384,11 +652,25 @@ impl JobQueue for ScrapeJobRepository {
             sqlx::query_as(r#"SELECT COUNT(*) FROM crawl_visited_urls WHERE session_id = $1"#)
                 .bind(session_id)
                 .fetch_one(&self.pool)
-                .await
-                .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+                .await?;
 
         Ok(count)
     }
+
+    async fn save_circuit_breaker(&self, stats: &CircuitBreakerStats) -> Result<(), AppError> {
+        CircuitBreakerRepository::new(self.pool.clone())
+            .save(stats)
+            .await
+    }
+
+    async fn circuit_breaker_reset_requested_at(
+        &self,
+        name: &str,
+    ) -> Result<Option<DateTime<Utc>>, AppError> {
+        CircuitBreakerRepository::new(self.pool.clone())
+            .reset_requested_at(name)
+            .await
+    }
 }
 
 impl ScrapeJobRepository {
@@ -402,8 +684,7 @@ impl ScrapeJobRepository {
         )
         .bind(session_id)
         .fetch_all(&self.pool)
-        .await
-        .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+        .await?;
 
         rows.into_iter()
             .map(ScrapeJob::try_from)
@@ -426,9 +707,74 @@ impl ScrapeJobRepository {
         )
         .bind(session_id)
         .fetch_all(&self.pool)
-        .await
-        .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+        .await?;
 
         Ok(rows)
     }
+
+    /// Jobs in the terminal `status` last updated more than `older_than`
+    /// ago, oldest first, for review before [`delete_jobs`](Self::delete_jobs).
+    pub async fn get_jobs_for_cleanup(
+        &self,
+        older_than: Duration,
+        status: JobStatus,
+    ) -> Result<Vec<ScrapeJob>, AppError> {
+        if !status.is_terminal() {
+            return Err(AppError::InvalidInput(format!(
+                "Only completed, failed, or cancelled jobs can be cleaned up, not {status}"
+            )));
+        }
+
+        let rows = sqlx::query_as::<_, ScrapeJobRow>(
+            r#"
+            SELECT * FROM scrape_jobs
+            WHERE status = $1 AND updated_at < NOW() - $2::interval
+            ORDER BY updated_at ASC, id ASC
+            "#,
+        )
+        .bind(status.as_str())
+        .bind(older_than)
+        .fetch_all(&self.pool)
+        .await?;
+
+        rows.into_iter()
+            .map(ScrapeJob::try_from)
+            .collect::<Result<Vec<_>, _>>()
+    }
+
+    /// Delete the given jobs, returning how many were deleted. Pending and
+    /// running jobs are never deleted, nor are crawl jobs whose children
+    /// are kept.
+    pub async fn delete_jobs(&self, job_ids: &[Uuid]) -> Result<u64, AppError> {
+        let result = sqlx::query(
+            r#"
+            DELETE FROM scrape_jobs j
+            WHERE j.id = ANY($1::uuid[])
+              AND j.status IN ('completed', 'failed', 'cancelled')
+              AND NOT EXISTS (
+                  SELECT 1 FROM scrape_jobs c
+                  WHERE c.parent_job_id = j.id
+                    AND NOT (c.id = ANY($1::uuid[])
+                             AND c.status IN ('completed', 'failed', 'cancelled'))
+              )
+            "#,
+        )
+        .bind(job_ids)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(result.rows_affected())
+    }
+
+    /// Delete the finished jobs tagged `tag`, under the same rules as
+    /// [`delete_jobs`](Self::delete_jobs). Returns how many were deleted.
+    pub async fn delete_jobs_by_tag(&self, tag: &str) -> Result<u64, AppError> {
+        let job_ids: Vec<Uuid> =
+            sqlx::query_scalar(r#"SELECT id FROM scrape_jobs WHERE $1 = ANY(tags)"#)
+                .bind(tag)
+                .fetch_all(&self.pool)
+                .await?;
+
+        self.delete_jobs(&job_ids).await
+    }
 }
@@ -1,11 +1,25 @@
 //! Database layer — connection pool, migrations, and repositories.
 
+pub mod api_key_repository;
+pub mod audit_repository;
+pub mod circuit_breaker_repository;
 pub mod config;
 pub mod database;
 pub mod job_repository;
 pub mod repository;
+pub mod stats_repository;
+pub mod usage_repository;
+pub mod worker_event_repository;
 
+pub use api_key_repository::ApiKeyRepository;
+pub use audit_repository::AuditRepository;
+pub use circuit_breaker_repository::CircuitBreakerRepository;
 pub use config::DatabaseConfig;
-pub use database::Database;
-pub use job_repository::ScrapeJobRepository;
-pub use repository::ExtractionRepository;
+pub use database::{Database, MigrationStatus, PruneReport, TableCounts};
+pub use job_repository::{ScrapeJobRepository, TransactionalJobRepository};
+pub use repository::{
+    DeleteReport, ExtractionRepository, RetentionReport, TransactionalExtractionRepository,
+};
+pub use stats_repository::{DomainStats, StatsRepository};
+pub use usage_repository::UsageRepository;
+pub use worker_event_repository::WorkerEventRepository;
@@ -1,9 +1,37 @@
+use std::collections::HashMap;
+
 use ares_core::error::AppError;
-use ares_core::models::{Extraction, NewExtraction};
+use ares_core::models::{Extraction, HistoryEntry, NewExtraction, SaveOutcome};
 use chrono::{DateTime, Utc};
-use sqlx::{PgPool, Pool, Postgres};
+use sqlx::{PgExecutor, PgPool, PgTransaction, Pool, Postgres};
 use uuid::Uuid;
 
+/// Extractions expired by [`ExtractionRepository::apply_retention`].
+#[derive(Debug, Clone, Default, PartialEq, Eq, serde::Serialize)]
+pub struct RetentionReport {
+    /// Rows deleted because their policy lists no fields to scrub.
+    pub deleted: u64,
+    /// Rows whose scrub fields were nulled out.
+    pub scrubbed: u64,
+    /// Snapshot keys of expired rows that no extraction references any more.
+    /// The caller deletes those objects from the snapshot store.
+    pub released_snapshots: Vec<String>,
+}
+
+/// Expired rows [`ExtractionRepository::apply_retention`] handles per
+/// transaction.
+const RETENTION_BATCH_SIZE: i64 = 500;
+
+/// Extractions removed by [`ExtractionRepository::delete_by_url`] or
+/// [`ExtractionRepository::delete_by_schema`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct DeleteReport {
+    pub deleted: u64,
+    /// Snapshot keys of deleted rows that no extraction references any more.
+    /// The caller deletes those objects from the snapshot store.
+    pub released_snapshots: Vec<String>,
+}
+
 /// Repository for extraction persistence in PostgreSQL.
 #[derive(Clone)]
 pub struct ExtractionRepository {
@@ -16,35 +44,113 @@ impl ExtractionRepository {
     }
 
     /// Save a new extraction result. Returns the generated UUID.
+    #[tracing::instrument(level = "debug", skip_all, fields(db.system = "postgresql", db.operation = "INSERT"))]
     pub async fn save(&self, extraction: &NewExtraction) -> Result<Uuid, AppError> {
-        let row: (Uuid,) = sqlx::query_as(
+        save_extraction(&self.pool, extraction).await
+    }
+
+    /// Insert an extraction, or refresh the existing upserted row with the
+    /// same URL, schema, and data hash. Returns the row's UUID.
+    ///
+    /// Unlike [`save`](Self::save), repeated runs producing the same data
+    /// touch one row (bumping `updated_at`) instead of adding snapshots.
+    /// Rows written by `save` are never matched.
+    #[tracing::instrument(level = "debug", skip_all, fields(db.system = "postgresql", db.operation = "INSERT"))]
+    pub async fn upsert(&self, extraction: &NewExtraction) -> Result<Uuid, AppError> {
+        upsert_extraction(&self.pool, extraction).await
+    }
+
+    /// If `extraction`'s data hash matches the latest extraction for the same
+    /// URL and schema, bump that row's `last_seen_at` instead of saving.
+    ///
+    /// Returns `None` when the data changed (or nothing was saved yet); the
+    /// caller then saves `extraction` as usual. The check and the bump are
+    /// one statement, so a row replaced in between is never bumped.
+    #[tracing::instrument(level = "debug", skip_all, fields(db.system = "postgresql", db.operation = "UPDATE"))]
+    pub async fn keep_if_unchanged(
+        &self,
+        extraction: &NewExtraction,
+    ) -> Result<Option<SaveOutcome>, AppError> {
+        let existing_id: Option<Uuid> = sqlx::query_scalar(
             r#"
-            INSERT INTO extractions
-                (url, schema_name, extracted_data, raw_content_hash, data_hash, model,
-                 provider, schema_version, latency_ms, prompt_tokens, completion_tokens)
-            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11)
+            WITH latest AS (
+                SELECT id, data_hash
+                FROM extractions
+                WHERE url = $1 AND schema_name = $2
+                ORDER BY updated_at DESC, created_at DESC, id DESC
+                LIMIT 1
+            )
+            UPDATE extractions SET last_seen_at = NOW()
+            WHERE id IN (SELECT id FROM latest WHERE data_hash = $3)
             RETURNING id
             "#,
         )
         .bind(&extraction.url)
         .bind(&extraction.schema_name)
-        .bind(&extraction.extracted_data)
-        .bind(&extraction.raw_content_hash)
         .bind(&extraction.data_hash)
-        .bind(&extraction.model)
-        .bind(&extraction.provider)
-        .bind(&extraction.schema_version)
-        .bind(extraction.latency_ms)
-        .bind(extraction.prompt_tokens)
-        .bind(extraction.completion_tokens)
-        .fetch_one(&self.pool)
-        .await
-        .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+        .fetch_optional(&self.pool)
+        .await?;
 
-        Ok(row.0)
+        Ok(existing_id.map(|existing_id| SaveOutcome::Unchanged {
+            existing_id,
+            last_seen_bumped: true,
+        }))
     }
 
-    /// Get the most recent extraction for a URL + schema pair.
+    /// Store the cleaned content extraction `extraction_id` was made from,
+    /// replacing any stored before (an upsert can save the same extraction
+    /// again).
+    #[tracing::instrument(level = "debug", skip_all, fields(db.system = "postgresql", db.operation = "INSERT"))]
+    pub async fn save_content(&self, extraction_id: Uuid, content: &str) -> Result<(), AppError> {
+        sqlx::query(
+            r#"
+            INSERT INTO extraction_content (extraction_id, cleaned_content)
+            VALUES ($1, $2)
+            ON CONFLICT (extraction_id)
+            DO UPDATE SET cleaned_content = EXCLUDED.cleaned_content, created_at = NOW()
+            "#,
+        )
+        .bind(extraction_id)
+        .bind(content)
+        .execute(&self.pool)
+        .await?;
+        Ok(())
+    }
+
+    /// The cleaned content stored for extraction `extraction_id`, if any.
+    #[tracing::instrument(level = "debug", skip_all, fields(db.system = "postgresql", db.operation = "SELECT"))]
+    pub async fn get_content(&self, extraction_id: Uuid) -> Result<Option<String>, AppError> {
+        let row: Option<(String,)> = sqlx::query_as(
+            "SELECT cleaned_content FROM extraction_content WHERE extraction_id = $1",
+        )
+        .bind(extraction_id)
+        .fetch_optional(&self.pool)
+        .await?;
+        Ok(row.map(|(content,)| content))
+    }
+
+    /// Get an extraction by ID.
+    #[tracing::instrument(level = "debug", skip_all, fields(db.system = "postgresql", db.operation = "SELECT"))]
+    pub async fn get(&self, id: Uuid) -> Result<Option<Extraction>, AppError> {
+        let row = sqlx::query_as::<_, ExtractionRow>(
+            r#"
+            SELECT id, url, schema_name, extracted_data, raw_content_hash, content_simhash, data_hash, model,
+                   provider, schema_version, latency_ms, prompt_tokens, completion_tokens,
+                   html_snapshot_key, markdown_snapshot_key, prompt_hash, schema_hash, duplicate_of, detected_language, golden, scrubbed_at, created_at
+            FROM extractions
+            WHERE id = $1
+            "#,
+        )
+        .bind(id)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(row.map(Into::into))
+    }
+
+    /// Get the most recently written (saved or upserted) extraction for a
+    /// URL + schema pair.
+    #[tracing::instrument(level = "debug", skip_all, fields(db.system = "postgresql", db.operation = "SELECT"))]
     pub async fn get_latest(
         &self,
         url: &str,
@@ -52,24 +158,54 @@ impl ExtractionRepository {
     ) -> Result<Option<Extraction>, AppError> {
         let row = sqlx::query_as::<_, ExtractionRow>(
             r#"
-            SELECT id, url, schema_name, extracted_data, raw_content_hash, data_hash, model,
-                   provider, schema_version, latency_ms, prompt_tokens, completion_tokens, created_at
+            SELECT id, url, schema_name, extracted_data, raw_content_hash, content_simhash, data_hash, model,
+                   provider, schema_version, latency_ms, prompt_tokens, completion_tokens,
+                   html_snapshot_key, markdown_snapshot_key, prompt_hash, schema_hash, duplicate_of, detected_language, golden, scrubbed_at, created_at
             FROM extractions
             WHERE url = $1 AND schema_name = $2
-            ORDER BY created_at DESC, id DESC
+            ORDER BY updated_at DESC, created_at DESC, id DESC
             LIMIT 1
             "#,
         )
         .bind(url)
         .bind(schema_name)
         .fetch_optional(&self.pool)
-        .await
-        .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+        .await?;
+
+        Ok(row.map(Into::into))
+    }
+
+    /// The earliest extraction with `schema_name`, of any URL, made at or
+    /// after `since` from content hashing to `content_hash`.
+    #[tracing::instrument(level = "debug", skip_all, fields(db.system = "postgresql", db.operation = "SELECT"))]
+    pub async fn find_by_content_hash(
+        &self,
+        schema_name: &str,
+        content_hash: &str,
+        since: DateTime<Utc>,
+    ) -> Result<Option<Extraction>, AppError> {
+        let row = sqlx::query_as::<_, ExtractionRow>(
+            r#"
+            SELECT id, url, schema_name, extracted_data, raw_content_hash, content_simhash, data_hash, model,
+                   provider, schema_version, latency_ms, prompt_tokens, completion_tokens,
+                   html_snapshot_key, markdown_snapshot_key, prompt_hash, schema_hash, duplicate_of, detected_language, golden, scrubbed_at, created_at
+            FROM extractions
+            WHERE schema_name = $1 AND raw_content_hash = $2 AND created_at >= $3
+            ORDER BY created_at ASC, id ASC
+            LIMIT 1
+            "#,
+        )
+        .bind(schema_name)
+        .bind(content_hash)
+        .bind(since)
+        .fetch_optional(&self.pool)
+        .await?;
 
         Ok(row.map(Into::into))
     }
 
     /// Get extraction history for a URL + schema pair, newest first.
+    #[tracing::instrument(level = "debug", skip_all, fields(db.system = "postgresql", db.operation = "SELECT"))]
     pub async fn get_history(
         &self,
         url: &str,
@@ -79,11 +215,58 @@ impl ExtractionRepository {
     ) -> Result<Vec<Extraction>, AppError> {
         let rows = sqlx::query_as::<_, ExtractionRow>(
             r#"
-            SELECT id, url, schema_name, extracted_data, raw_content_hash, data_hash, model,
-                   provider, schema_version, latency_ms, prompt_tokens, completion_tokens, created_at
+            SELECT id, url, schema_name, extracted_data, raw_content_hash, content_simhash, data_hash, model,
+                   provider, schema_version, latency_ms, prompt_tokens, completion_tokens,
+                   html_snapshot_key, markdown_snapshot_key, prompt_hash, schema_hash, duplicate_of, detected_language, golden, scrubbed_at, created_at
             FROM extractions
             WHERE url = $1 AND schema_name = $2
-            ORDER BY created_at DESC, id DESC
+            ORDER BY updated_at DESC, created_at DESC, id DESC
+            LIMIT $3 OFFSET $4
+            "#,
+        )
+        .bind(url)
+        .bind(schema_name)
+        .bind(limit as i64)
+        .bind(offset as i64)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(rows.into_iter().map(Into::into).collect())
+    }
+
+    /// Like [`get_history`](Self::get_history), with each extraction
+    /// compared to the one before it. The comparison runs over the whole
+    /// history, so the oldest row of a page is still compared with its true
+    /// predecessor.
+    ///
+    /// `language` keeps only extractions whose content was detected in that
+    /// language (an ISO 639-3 code such as `eng`); their predecessors are
+    /// still taken from the unfiltered history.
+    #[tracing::instrument(level = "debug", skip_all, fields(db.system = "postgresql", db.operation = "SELECT"))]
+    pub async fn get_history_entries(
+        &self,
+        url: &str,
+        schema_name: &str,
+        language: Option<&str>,
+        limit: usize,
+        offset: usize,
+    ) -> Result<Vec<HistoryEntry>, AppError> {
+        let rows = sqlx::query_as::<_, HistoryRow>(
+            r#"
+            SELECT id, url, schema_name, extracted_data, raw_content_hash, content_simhash, data_hash, model,
+                   provider, schema_version, latency_ms, prompt_tokens, completion_tokens,
+                   html_snapshot_key, markdown_snapshot_key, prompt_hash, schema_hash, duplicate_of, detected_language, golden, scrubbed_at, created_at,
+                   previous_extraction_id, changed
+            FROM (
+                SELECT *,
+                       LAG(id) OVER w AS previous_extraction_id,
+                       (LAG(data_hash) OVER w) IS DISTINCT FROM data_hash AS changed
+                FROM extractions
+                WHERE url = $1 AND schema_name = $2
+                WINDOW w AS (ORDER BY updated_at, created_at, id)
+            ) history
+            WHERE $5::TEXT IS NULL OR detected_language = $5
+            ORDER BY updated_at DESC, created_at DESC, id DESC
             LIMIT $3 OFFSET $4
             "#,
         )
@@ -91,33 +274,395 @@ impl ExtractionRepository {
         .bind(schema_name)
         .bind(limit as i64)
         .bind(offset as i64)
+        .bind(language)
         .fetch_all(&self.pool)
-        .await
-        .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+        .await?;
 
         Ok(rows.into_iter().map(Into::into).collect())
     }
 
-    /// Count extractions for a URL + schema pair.
-    pub async fn count_history(&self, url: &str, schema_name: &str) -> Result<i64, AppError> {
+    /// Count extractions for a URL + schema pair, optionally only those in
+    /// `language`.
+    pub async fn count_history(
+        &self,
+        url: &str,
+        schema_name: &str,
+        language: Option<&str>,
+    ) -> Result<i64, AppError> {
         let (count,): (i64,) = sqlx::query_as(
-            r#"SELECT COUNT(*) FROM extractions WHERE url = $1 AND schema_name = $2"#,
+            r#"
+            SELECT COUNT(*) FROM extractions
+            WHERE url = $1 AND schema_name = $2
+              AND ($3::TEXT IS NULL OR detected_language = $3)
+            "#,
         )
         .bind(url)
         .bind(schema_name)
+        .bind(language)
         .fetch_one(&self.pool)
-        .await
-        .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+        .await?;
 
         Ok(count)
     }
 
+    /// When each of `urls` was last extracted with `schema_name`. URLs
+    /// never extracted with it are absent.
+    #[tracing::instrument(level = "debug", skip_all, fields(db.system = "postgresql", db.operation = "SELECT"))]
+    pub async fn latest_extracted_at(
+        &self,
+        urls: &[String],
+        schema_name: &str,
+    ) -> Result<HashMap<String, DateTime<Utc>>, AppError> {
+        let rows: Vec<(String, DateTime<Utc>)> = sqlx::query_as(
+            r#"
+            SELECT url, MAX(updated_at)
+            FROM extractions
+            WHERE schema_name = $1 AND url = ANY($2)
+            GROUP BY url
+            "#,
+        )
+        .bind(schema_name)
+        .bind(urls)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(rows.into_iter().collect())
+    }
+
+    /// Delete every extraction of `url` (e.g. for an erasure request).
+    ///
+    /// Jobs that produced them are kept, with their `extraction_id` cleared.
+    #[tracing::instrument(level = "debug", skip_all, fields(db.system = "postgresql", db.operation = "DELETE"))]
+    pub async fn delete_by_url(&self, url: &str) -> Result<DeleteReport, AppError> {
+        self.delete_where("url", url).await
+    }
+
+    /// Delete every extraction made with the schema `schema_name`, in any
+    /// version.
+    ///
+    /// Jobs that produced them are kept, with their `extraction_id` cleared.
+    #[tracing::instrument(level = "debug", skip_all, fields(db.system = "postgresql", db.operation = "DELETE"))]
+    pub async fn delete_by_schema(&self, schema_name: &str) -> Result<DeleteReport, AppError> {
+        self.delete_where("schema_name", schema_name).await
+    }
+
+    /// Delete the extractions whose `column` equals `value`, detaching the
+    /// jobs that reference them first (which changes the jobs, so their
+    /// `updated_at` moves too). `column` is never user input.
+    async fn delete_where(
+        &self,
+        column: &'static str,
+        value: &str,
+    ) -> Result<DeleteReport, AppError> {
+        let mut tx = self.pool.begin().await?;
+
+        sqlx::query(&format!(
+            "UPDATE scrape_jobs SET extraction_id = NULL, updated_at = NOW()
+             WHERE extraction_id IN (SELECT id FROM extractions WHERE {column} = $1)"
+        ))
+        .bind(value)
+        .execute(&mut *tx)
+        .await?;
+
+        let deleted: Vec<(Option<String>, Option<String>)> = sqlx::query_as(&format!(
+            "DELETE FROM extractions WHERE {column} = $1
+             RETURNING html_snapshot_key, markdown_snapshot_key"
+        ))
+        .bind(value)
+        .fetch_all(&mut *tx)
+        .await?;
+        let count = deleted.len() as u64;
+        let keys = deleted
+            .into_iter()
+            .flat_map(|(html, markdown)| [html, markdown])
+            .flatten()
+            .collect();
+        let released_snapshots = unreferenced_snapshot_keys(&mut *tx, keys).await?;
+
+        tx.commit().await?;
+        Ok(DeleteReport {
+            deleted: count,
+            released_snapshots,
+        })
+    }
+
+    /// Remember snapshot keys whose objects could not be deleted from the
+    /// snapshot store, so a later cleanup retries them (see
+    /// [`take_failed_snapshot_deletions`](Self::take_failed_snapshot_deletions)).
+    #[tracing::instrument(level = "debug", skip_all, fields(db.system = "postgresql", db.operation = "INSERT"))]
+    pub async fn record_failed_snapshot_deletions(&self, keys: &[String]) -> Result<(), AppError> {
+        if keys.is_empty() {
+            return Ok(());
+        }
+        sqlx::query(
+            r#"
+            INSERT INTO snapshot_deletions (key)
+            SELECT unnest($1::text[])
+            ON CONFLICT (key) DO UPDATE SET failed_at = NOW()
+            "#,
+        )
+        .bind(keys)
+        .execute(&self.pool)
+        .await?;
+        Ok(())
+    }
+
+    /// Forget the recorded failed snapshot deletions and return their keys,
+    /// without those an extraction has referenced again since. The caller
+    /// retries deleting them, recording any that fail again.
+    #[tracing::instrument(level = "debug", skip_all, fields(db.system = "postgresql", db.operation = "DELETE"))]
+    pub async fn take_failed_snapshot_deletions(&self) -> Result<Vec<String>, AppError> {
+        let mut tx = self.pool.begin().await?;
+        let keys: Vec<(String,)> = sqlx::query_as("DELETE FROM snapshot_deletions RETURNING key")
+            .fetch_all(&mut *tx)
+            .await?;
+        let keys = keys.into_iter().map(|(key,)| key).collect();
+        let orphaned = unreferenced_snapshot_keys(&mut *tx, keys).await?;
+        tx.commit().await?;
+        Ok(orphaned)
+    }
+
+    /// Detach the snapshots of extractions last written before `cutoff`,
+    /// clearing their keys, and return the keys no extraction references
+    /// any more. The caller deletes those objects from the snapshot store,
+    /// recording any it fails to delete with
+    /// [`record_failed_snapshot_deletions`](Self::record_failed_snapshot_deletions).
+    ///
+    /// Snapshots are content-addressed, so a key shared with a newer
+    /// extraction is kept.
+    #[tracing::instrument(level = "debug", skip_all, fields(db.system = "postgresql", db.operation = "UPDATE"))]
+    pub async fn release_snapshots(&self, cutoff: DateTime<Utc>) -> Result<Vec<String>, AppError> {
+        let mut tx = self.pool.begin().await?;
+
+        let released: Vec<(Option<String>, Option<String>)> = sqlx::query_as(
+            r#"
+            UPDATE extractions e
+            SET html_snapshot_key = NULL, markdown_snapshot_key = NULL
+            FROM (
+                SELECT id, html_snapshot_key, markdown_snapshot_key
+                FROM extractions
+                WHERE updated_at < $1
+                  AND (html_snapshot_key IS NOT NULL OR markdown_snapshot_key IS NOT NULL)
+                FOR UPDATE
+            ) old
+            WHERE e.id = old.id
+            RETURNING old.html_snapshot_key, old.markdown_snapshot_key
+            "#,
+        )
+        .bind(cutoff)
+        .fetch_all(&mut *tx)
+        .await?;
+
+        let keys = released
+            .into_iter()
+            .flat_map(|(html, markdown)| [html, markdown])
+            .flatten()
+            .collect();
+        let orphaned = unreferenced_snapshot_keys(&mut *tx, keys).await?;
+
+        tx.commit().await?;
+        Ok(orphaned)
+    }
+
+    /// Apply the retention policy stored with each extraction (see
+    /// [`ares_core::RetentionPolicy`]) as of `now`.
+    ///
+    /// Expired rows with scrub fields have those JSON Pointers nulled out of
+    /// their data in place and are marked scrubbed, so they are only
+    /// rewritten once; their `data_hash` is left as it was, so the next scrape
+    /// of unchanged data doesn't count as a change. Their stored content and
+    /// snapshots still hold the scrubbed values, so the content is deleted
+    /// and the snapshots detached. Expired rows without scrub fields are
+    /// deleted, detaching the jobs that reference them first (and bumping
+    /// their `updated_at`, as [`delete_by_url`](Self::delete_by_url) does).
+    ///
+    /// Rows are handled in batches of 500, each in its own transaction, so a
+    /// large backlog never locks every expired row at once. A failure leaves
+    /// the batches before it applied.
+    ///
+    /// The report lists the detached snapshot keys no extraction references
+    /// any more, as [`release_snapshots`](Self::release_snapshots) does.
+    #[tracing::instrument(level = "debug", skip_all, fields(db.system = "postgresql", db.operation = "UPDATE"))]
+    pub async fn apply_retention(&self, now: DateTime<Utc>) -> Result<RetentionReport, AppError> {
+        let mut report = RetentionReport::default();
+        loop {
+            let (scrubbed, released) = self.scrub_expired_batch(now).await?;
+            report.scrubbed += scrubbed;
+            report.released_snapshots.extend(released);
+            if scrubbed < RETENTION_BATCH_SIZE as u64 {
+                break;
+            }
+        }
+        loop {
+            let (deleted, released) = self.delete_expired_batch(now).await?;
+            report.deleted += deleted;
+            report.released_snapshots.extend(released);
+            if deleted < RETENTION_BATCH_SIZE as u64 {
+                break;
+            }
+        }
+        report.released_snapshots.sort();
+        Ok(report)
+    }
+
+    /// Scrub up to [`RETENTION_BATCH_SIZE`] expired rows that have scrub
+    /// fields. Returns how many were scrubbed and the snapshot keys released.
+    async fn scrub_expired_batch(
+        &self,
+        now: DateTime<Utc>,
+    ) -> Result<(u64, Vec<String>), AppError> {
+        let mut tx = self.pool.begin().await?;
+
+        let expired: Vec<ExpiredRow> = sqlx::query_as(
+            r#"
+            SELECT id, extracted_data, retention_scrub_fields,
+                   html_snapshot_key, markdown_snapshot_key
+            FROM extractions
+            WHERE retention_days IS NOT NULL
+              AND scrubbed_at IS NULL
+              AND cardinality(retention_scrub_fields) > 0
+              AND created_at < $1 - make_interval(days => retention_days)
+            LIMIT $2
+            FOR UPDATE SKIP LOCKED
+            "#,
+        )
+        .bind(now)
+        .bind(RETENTION_BATCH_SIZE)
+        .fetch_all(&mut *tx)
+        .await?;
+
+        let scrubbed = expired.len() as u64;
+        let mut scrubbed_ids = Vec::with_capacity(expired.len());
+        let mut keys = Vec::new();
+        for mut row in expired {
+            ares_core::scrub(&mut row.extracted_data, &row.retention_scrub_fields);
+            sqlx::query(
+                r#"
+                UPDATE extractions
+                SET extracted_data = $2, scrubbed_at = $3,
+                    html_snapshot_key = NULL, markdown_snapshot_key = NULL
+                WHERE id = $1
+                "#,
+            )
+            .bind(row.id)
+            .bind(&row.extracted_data)
+            .bind(now)
+            .execute(&mut *tx)
+            .await?;
+            scrubbed_ids.push(row.id);
+            keys.extend(
+                [row.html_snapshot_key, row.markdown_snapshot_key]
+                    .into_iter()
+                    .flatten(),
+            );
+        }
+        sqlx::query("DELETE FROM extraction_content WHERE extraction_id = ANY($1)")
+            .bind(&scrubbed_ids)
+            .execute(&mut *tx)
+            .await?;
+
+        let released = unreferenced_snapshot_keys(&mut *tx, keys).await?;
+        tx.commit().await?;
+        Ok((scrubbed, released))
+    }
+
+    /// Delete up to [`RETENTION_BATCH_SIZE`] expired rows without scrub
+    /// fields. Returns how many were deleted and the snapshot keys released.
+    async fn delete_expired_batch(
+        &self,
+        now: DateTime<Utc>,
+    ) -> Result<(u64, Vec<String>), AppError> {
+        let mut tx = self.pool.begin().await?;
+
+        let ids: Vec<Uuid> = sqlx::query_scalar(
+            r#"
+            SELECT id FROM extractions
+            WHERE retention_days IS NOT NULL
+              AND COALESCE(cardinality(retention_scrub_fields), 0) = 0
+              AND created_at < $1 - make_interval(days => retention_days)
+            LIMIT $2
+            FOR UPDATE SKIP LOCKED
+            "#,
+        )
+        .bind(now)
+        .bind(RETENTION_BATCH_SIZE)
+        .fetch_all(&mut *tx)
+        .await?;
+
+        sqlx::query(
+            "UPDATE scrape_jobs SET extraction_id = NULL, updated_at = NOW()
+             WHERE extraction_id = ANY($1)",
+        )
+        .bind(&ids)
+        .execute(&mut *tx)
+        .await?;
+        // Their content goes with them (ON DELETE CASCADE); their snapshot
+        // keys are read as they go, so the objects can be released too.
+        let deleted: Vec<(Option<String>, Option<String>)> = sqlx::query_as(
+            "DELETE FROM extractions WHERE id = ANY($1)
+             RETURNING html_snapshot_key, markdown_snapshot_key",
+        )
+        .bind(&ids)
+        .fetch_all(&mut *tx)
+        .await?;
+        let deleted_count = deleted.len() as u64;
+        let keys = deleted
+            .into_iter()
+            .flat_map(|(html, markdown)| [html, markdown])
+            .flatten()
+            .collect();
+
+        let released = unreferenced_snapshot_keys(&mut *tx, keys).await?;
+        tx.commit().await?;
+        Ok((deleted_count, released))
+    }
+
+    /// Mark an extraction as golden, or clear the mark. Returns the updated
+    /// extraction, or `None` if there is no extraction with that ID.
+    pub async fn set_golden(&self, id: Uuid, golden: bool) -> Result<Option<Extraction>, AppError> {
+        let row = sqlx::query_as::<_, ExtractionRow>(
+            r#"
+            UPDATE extractions SET golden = $2
+            WHERE id = $1
+            RETURNING id, url, schema_name, extracted_data, raw_content_hash, content_simhash, data_hash, model,
+                      provider, schema_version, latency_ms, prompt_tokens, completion_tokens,
+                      html_snapshot_key, markdown_snapshot_key, prompt_hash, schema_hash, duplicate_of, detected_language, golden,
+                      scrubbed_at, created_at
+            "#,
+        )
+        .bind(id)
+        .bind(golden)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(row.map(Into::into))
+    }
+
+    /// The golden extractions of `schema_name`: the newest one per URL,
+    /// ordered by URL.
+    pub async fn list_golden(&self, schema_name: &str) -> Result<Vec<Extraction>, AppError> {
+        let rows = sqlx::query_as::<_, ExtractionRow>(
+            r#"
+            SELECT DISTINCT ON (url)
+                   id, url, schema_name, extracted_data, raw_content_hash, content_simhash, data_hash, model,
+                   provider, schema_version, latency_ms, prompt_tokens, completion_tokens,
+                   html_snapshot_key, markdown_snapshot_key, prompt_hash, schema_hash, duplicate_of, detected_language, golden,
+                   scrubbed_at, created_at
+            FROM extractions
+            WHERE schema_name = $1 AND golden
+            ORDER BY url, created_at DESC, id DESC
+            "#,
+        )
+        .bind(schema_name)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(rows.into_iter().map(Into::into).collect())
+    }
+
     /// Check database connectivity (used by the HTTP `/health` endpoint).
     pub async fn health_check(&self) -> Result<(), AppError> {
-        sqlx::query("SELECT 1")
-            .execute(&self.pool)
-            .await
-            .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+        sqlx::query("SELECT 1").execute(&self.pool).await?;
         Ok(())
     }
 
@@ -128,8 +673,9 @@ impl ExtractionRepository {
     ) -> Result<Vec<Extraction>, AppError> {
         let rows = sqlx::query_as::<_, ExtractionRow>(
             r#"
-            SELECT e.id, e.url, e.schema_name, e.extracted_data, e.raw_content_hash, e.data_hash, e.model,
-                   e.provider, e.schema_version, e.latency_ms, e.prompt_tokens, e.completion_tokens, e.created_at
+            SELECT e.id, e.url, e.schema_name, e.extracted_data, e.raw_content_hash, e.content_simhash, e.data_hash, e.model,
+                   e.provider, e.schema_version, e.latency_ms, e.prompt_tokens, e.completion_tokens,
+                   e.html_snapshot_key, e.markdown_snapshot_key, e.prompt_hash, e.schema_hash, e.duplicate_of, e.detected_language, e.golden, e.scrubbed_at, e.created_at
             FROM extractions e
             JOIN scrape_jobs j ON e.id = j.extraction_id
             WHERE j.crawl_session_id = $1
@@ -138,15 +684,186 @@ impl ExtractionRepository {
         )
         .bind(session_id)
         .fetch_all(&self.pool)
-        .await
-        .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+        .await?;
 
         Ok(rows.into_iter().map(Into::into).collect())
     }
 }
 
+/// Extraction writes made inside a transaction, so they commit or roll back
+/// together with the transaction's other writes. See
+/// [`Database::transaction`](crate::Database::transaction).
+pub struct TransactionalExtractionRepository<'t> {
+    tx: &'t mut PgTransaction<'static>,
+}
+
+impl<'t> TransactionalExtractionRepository<'t> {
+    pub fn new(tx: &'t mut PgTransaction<'static>) -> Self {
+        Self { tx }
+    }
+
+    /// Like [`ExtractionRepository::save`].
+    pub async fn save(&mut self, extraction: &NewExtraction) -> Result<Uuid, AppError> {
+        save_extraction(&mut **self.tx, extraction).await
+    }
+
+    /// Like [`ExtractionRepository::upsert`].
+    pub async fn upsert(&mut self, extraction: &NewExtraction) -> Result<Uuid, AppError> {
+        upsert_extraction(&mut **self.tx, extraction).await
+    }
+}
+
+async fn save_extraction<'e>(
+    executor: impl PgExecutor<'e>,
+    extraction: &NewExtraction,
+) -> Result<Uuid, AppError> {
+    let row: (Uuid,) = sqlx::query_as(
+        r#"
+        INSERT INTO extractions
+            (url, schema_name, extracted_data, raw_content_hash, data_hash, model,
+             provider, schema_version, latency_ms, prompt_tokens, completion_tokens,
+             html_snapshot_key, markdown_snapshot_key, prompt_hash, duplicate_of, domain,
+             detected_language, content_simhash, retention_days, retention_scrub_fields,
+             schema_hash)
+        VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14, $15, $16, $17, $18,
+                $19, $20, $21)
+        RETURNING id
+        "#,
+    )
+    .bind(&extraction.url)
+    .bind(&extraction.schema_name)
+    .bind(&extraction.extracted_data)
+    .bind(&extraction.raw_content_hash)
+    .bind(&extraction.data_hash)
+    .bind(&extraction.model)
+    .bind(&extraction.provider)
+    .bind(&extraction.schema_version)
+    .bind(extraction.latency_ms)
+    .bind(extraction.prompt_tokens)
+    .bind(extraction.completion_tokens)
+    .bind(&extraction.html_snapshot_key)
+    .bind(&extraction.markdown_snapshot_key)
+    .bind(&extraction.prompt_hash)
+    .bind(extraction.duplicate_of)
+    .bind(ares_core::domain_key(&extraction.url))
+    .bind(&extraction.detected_language)
+    .bind(&extraction.content_simhash)
+    .bind(retention_days(extraction))
+    .bind(retention_scrub_fields(extraction))
+    .bind(&extraction.schema_hash)
+    .fetch_one(executor)
+    .await?;
+
+    Ok(row.0)
+}
+
+async fn upsert_extraction<'e>(
+    executor: impl PgExecutor<'e>,
+    extraction: &NewExtraction,
+) -> Result<Uuid, AppError> {
+    let row: (Uuid,) = sqlx::query_as(
+        r#"
+        INSERT INTO extractions
+            (url, schema_name, extracted_data, raw_content_hash, data_hash, model,
+             provider, schema_version, latency_ms, prompt_tokens, completion_tokens,
+             html_snapshot_key, markdown_snapshot_key, prompt_hash, duplicate_of, domain,
+             detected_language, content_simhash, retention_days, retention_scrub_fields,
+             schema_hash, upserted)
+        VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14, $15, $16, $17, $18,
+                $19, $20, $21, TRUE)
+        ON CONFLICT (url, schema_name, data_hash) WHERE upserted
+        DO UPDATE SET
+            updated_at = NOW(),
+            last_seen_at = NOW(),
+            html_snapshot_key =
+                COALESCE(EXCLUDED.html_snapshot_key, extractions.html_snapshot_key),
+            markdown_snapshot_key =
+                COALESCE(EXCLUDED.markdown_snapshot_key, extractions.markdown_snapshot_key)
+        RETURNING id
+        "#,
+    )
+    .bind(&extraction.url)
+    .bind(&extraction.schema_name)
+    .bind(&extraction.extracted_data)
+    .bind(&extraction.raw_content_hash)
+    .bind(&extraction.data_hash)
+    .bind(&extraction.model)
+    .bind(&extraction.provider)
+    .bind(&extraction.schema_version)
+    .bind(extraction.latency_ms)
+    .bind(extraction.prompt_tokens)
+    .bind(extraction.completion_tokens)
+    .bind(&extraction.html_snapshot_key)
+    .bind(&extraction.markdown_snapshot_key)
+    .bind(&extraction.prompt_hash)
+    .bind(extraction.duplicate_of)
+    .bind(ares_core::domain_key(&extraction.url))
+    .bind(&extraction.detected_language)
+    .bind(&extraction.content_simhash)
+    .bind(retention_days(extraction))
+    .bind(retention_scrub_fields(extraction))
+    .bind(&extraction.schema_hash)
+    .fetch_one(executor)
+    .await?;
+
+    Ok(row.0)
+}
+
+/// `keys`, deduplicated, without those an extraction still references.
+/// Snapshots are content-addressed, so one released by an extraction may
+/// still belong to another.
+async fn unreferenced_snapshot_keys<'e>(
+    executor: impl PgExecutor<'e>,
+    mut keys: Vec<String>,
+) -> Result<Vec<String>, AppError> {
+    keys.sort();
+    keys.dedup();
+
+    let (orphaned,): (Vec<String>,) = sqlx::query_as(
+        r#"
+        SELECT COALESCE(array_agg(key), '{}')
+        FROM unnest($1::text[]) AS key
+        WHERE NOT EXISTS (
+            SELECT 1 FROM extractions
+            WHERE html_snapshot_key = key OR markdown_snapshot_key = key
+        )
+        "#,
+    )
+    .bind(&keys)
+    .fetch_one(executor)
+    .await?;
+    Ok(orphaned)
+}
+
+/// The `retention_days` column for `extraction`.
+fn retention_days(extraction: &NewExtraction) -> Option<i32> {
+    extraction
+        .retention
+        .as_ref()
+        .map(|policy| i32::try_from(policy.days).unwrap_or(i32::MAX))
+}
+
+/// The `retention_scrub_fields` column for `extraction`.
+fn retention_scrub_fields(extraction: &NewExtraction) -> Option<&[String]> {
+    extraction
+        .retention
+        .as_ref()
+        .map(|policy| policy.scrub_fields.as_slice())
+}
+
 // -- Internal row type for sqlx deserialization --
 
+/// An expired row whose scrub fields [`ExtractionRepository::apply_retention`]
+/// nulls out.
+#[derive(sqlx::FromRow)]
+struct ExpiredRow {
+    id: Uuid,
+    extracted_data: serde_json::Value,
+    retention_scrub_fields: Vec<String>,
+    html_snapshot_key: Option<String>,
+    markdown_snapshot_key: Option<String>,
+}
+
 #[derive(sqlx::FromRow)]
 struct ExtractionRow {
     id: Uuid,
@@ -154,6 +871,7 @@ struct ExtractionRow {
     schema_name: String,
     extracted_data: serde_json::Value,
     raw_content_hash: String,
+    content_simhash: Option<String>,
     data_hash: String,
     model: String,
     provider: String,
@@ -161,6 +879,14 @@ struct ExtractionRow {
     latency_ms: Option<i64>,
     prompt_tokens: Option<i32>,
     completion_tokens: Option<i32>,
+    html_snapshot_key: Option<String>,
+    markdown_snapshot_key: Option<String>,
+    prompt_hash: Option<String>,
+    schema_hash: Option<String>,
+    duplicate_of: Option<Uuid>,
+    detected_language: Option<String>,
+    golden: bool,
+    scrubbed_at: Option<DateTime<Utc>>,
     created_at: DateTime<Utc>,
 }
 
@@ -172,18 +898,45 @@ impl From<ExtractionRow> for Extraction {
             schema_name: row.schema_name,
             extracted_data: row.extracted_data,
             content_hash: row.raw_content_hash,
+            content_simhash: row.content_simhash,
             data_hash: row.data_hash,
             model: row.model,
             provider: row.provider,
+            prompt_hash: row.prompt_hash,
+            schema_hash: row.schema_hash,
             schema_version: row.schema_version,
             latency_ms: row.latency_ms,
             prompt_tokens: row.prompt_tokens,
             completion_tokens: row.completion_tokens,
+            html_snapshot_key: row.html_snapshot_key,
+            markdown_snapshot_key: row.markdown_snapshot_key,
+            duplicate_of: row.duplicate_of,
+            detected_language: row.detected_language,
+            golden: row.golden,
+            scrubbed_at: row.scrubbed_at,
             created_at: row.created_at,
         }
     }
 }
 
+#[derive(sqlx::FromRow)]
+struct HistoryRow {
+    #[sqlx(flatten)]
+    extraction: ExtractionRow,
+    previous_extraction_id: Option<Uuid>,
+    changed: bool,
+}
+
+impl From<HistoryRow> for HistoryEntry {
+    fn from(row: HistoryRow) -> Self {
+        HistoryEntry {
+            extraction: row.extraction.into(),
+            previous_extraction_id: row.previous_extraction_id,
+            changed: row.changed,
+        }
+    }
+}
+
 // -- Trait implementation --
 
 impl ares_core::traits::ExtractionStore for ExtractionRepository {
@@ -191,6 +944,21 @@ impl ares_core::traits::ExtractionStore for ExtractionRepository {
         ExtractionRepository::save(self, extraction).await
     }
 
+    async fn upsert(&self, extraction: &NewExtraction) -> Result<Uuid, AppError> {
+        ExtractionRepository::upsert(self, extraction).await
+    }
+
+    async fn keep_if_unchanged(
+        &self,
+        extraction: &NewExtraction,
+    ) -> Result<Option<SaveOutcome>, AppError> {
+        ExtractionRepository::keep_if_unchanged(self, extraction).await
+    }
+
+    async fn save_content(&self, extraction_id: Uuid, content: &str) -> Result<(), AppError> {
+        ExtractionRepository::save_content(self, extraction_id, content).await
+    }
+
     async fn get_latest(
         &self,
         url: &str,
@@ -208,4 +976,13 @@ impl ares_core::traits::ExtractionStore for ExtractionRepository {
     ) -> Result<Vec<Extraction>, AppError> {
         ExtractionRepository::get_history(self, url, schema_name, limit, offset).await
     }
+
+    async fn find_by_content_hash(
+        &self,
+        schema_name: &str,
+        content_hash: &str,
+        since: DateTime<Utc>,
+    ) -> Result<Option<Extraction>, AppError> {
+        ExtractionRepository::find_by_content_hash(self, schema_name, content_hash, since).await
+    }
 }
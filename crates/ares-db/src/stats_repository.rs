@@ -0,0 +1,137 @@
+use std::collections::BTreeMap;
+
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use sqlx::{PgPool, Pool, Postgres};
+
+use ares_core::error::AppError;
+
+/// Job outcomes and extractions of one domain over a time window.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct DomainStats {
+    /// `scheme://host:port`, as keyed by [`ares_core::domain_key`].
+    pub domain: String,
+    /// Jobs that completed within the window.
+    pub jobs_succeeded: i64,
+    /// Jobs that failed permanently within the window. Failures that were
+    /// retried are not counted.
+    pub jobs_failed: i64,
+    /// Mean time from claim to completion of the window's successful jobs.
+    pub avg_duration_secs: Option<f64>,
+    /// When a job for the domain last completed, inside the window or not.
+    pub last_success_at: Option<DateTime<Utc>>,
+    /// Extractions saved within the window.
+    pub extractions: i64,
+}
+
+impl DomainStats {
+    /// Share of the window's finished jobs that failed, or `None` when none
+    /// finished.
+    pub fn failure_rate(&self) -> Option<f64> {
+        let finished = self.jobs_succeeded + self.jobs_failed;
+        (finished > 0).then(|| self.jobs_failed as f64 / finished as f64)
+    }
+
+    fn empty(domain: String) -> Self {
+        Self {
+            domain,
+            jobs_succeeded: 0,
+            jobs_failed: 0,
+            avg_duration_secs: None,
+            last_success_at: None,
+            extractions: 0,
+        }
+    }
+}
+
+/// Read-only aggregate queries for dashboards.
+#[derive(Clone)]
+pub struct StatsRepository {
+    pool: Pool<Postgres>,
+}
+
+// -- Internal row types for sqlx deserialization --
+
+#[derive(sqlx::FromRow)]
+struct DomainJobsRow {
+    domain: String,
+    jobs_succeeded: i64,
+    jobs_failed: i64,
+    avg_duration_secs: Option<f64>,
+    last_success_at: Option<DateTime<Utc>>,
+}
+
+#[derive(sqlx::FromRow)]
+struct DomainExtractionsRow {
+    domain: String,
+    extractions: i64,
+}
+
+impl StatsRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    /// Per-domain job outcomes and extraction counts since `since`, for
+    /// every domain with a finished job or an extraction in that window.
+    /// Sorted by failed jobs, most first, then by domain.
+    pub async fn domain_stats(&self, since: DateTime<Utc>) -> Result<Vec<DomainStats>, AppError> {
+        let jobs = sqlx::query_as::<_, DomainJobsRow>(
+            r#"
+            SELECT
+                domain,
+                COUNT(*) FILTER (WHERE status = 'completed' AND completed_at >= $1)
+                    AS jobs_succeeded,
+                COUNT(*) FILTER (WHERE status = 'failed' AND completed_at >= $1)
+                    AS jobs_failed,
+                AVG(EXTRACT(EPOCH FROM completed_at - started_at)::DOUBLE PRECISION)
+                    FILTER (WHERE status = 'completed' AND completed_at >= $1)
+                    AS avg_duration_secs,
+                MAX(completed_at) FILTER (WHERE status = 'completed') AS last_success_at
+            FROM scrape_jobs
+            WHERE domain IS NOT NULL
+            GROUP BY domain
+            HAVING COUNT(*) FILTER (
+                WHERE status IN ('completed', 'failed') AND completed_at >= $1
+            ) > 0
+            "#,
+        )
+        .bind(since)
+        .fetch_all(&self.pool)
+        .await?;
+
+        let extractions = sqlx::query_as::<_, DomainExtractionsRow>(
+            r#"
+            SELECT domain, COUNT(*) AS extractions
+            FROM extractions
+            WHERE domain IS NOT NULL AND created_at >= $1
+            GROUP BY domain
+            "#,
+        )
+        .bind(since)
+        .fetch_all(&self.pool)
+        .await?;
+
+        let mut by_domain: BTreeMap<String, DomainStats> = BTreeMap::new();
+        for row in jobs {
+            let stats = by_domain
+                .entry(row.domain.clone())
+                .or_insert_with(|| DomainStats::empty(row.domain));
+            stats.jobs_succeeded = row.jobs_succeeded;
+            stats.jobs_failed = row.jobs_failed;
+            stats.avg_duration_secs = row.avg_duration_secs;
+            stats.last_success_at = row.last_success_at;
+        }
+        for row in extractions {
+            by_domain
+                .entry(row.domain.clone())
+                .or_insert_with(|| DomainStats::empty(row.domain))
+                .extractions = row.extractions;
+        }
+
+        let mut stats: Vec<DomainStats> = by_domain.into_values().collect();
+        // BTreeMap order is by domain; a stable sort keeps it within ties.
+        stats.sort_by_key(|s| std::cmp::Reverse(s.jobs_failed));
+        Ok(stats)
+    }
+}
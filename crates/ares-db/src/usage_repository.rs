@@ -0,0 +1,101 @@
+use chrono::NaiveDate;
+use sqlx::{PgPool, Pool, Postgres};
+
+use ares_core::budget::{DailyUsage, UsageStore};
+use ares_core::error::AppError;
+
+/// PostgreSQL-backed daily LLM usage counters.
+#[derive(Clone)]
+pub struct UsageRepository {
+    pool: Pool<Postgres>,
+}
+
+// -- Internal row type for sqlx deserialization --
+
+#[derive(sqlx::FromRow)]
+struct UsageRow {
+    day: NaiveDate,
+    llm_calls: i64,
+    tokens: i64,
+    cost_usd: f64,
+}
+
+impl From<UsageRow> for DailyUsage {
+    fn from(row: UsageRow) -> Self {
+        DailyUsage {
+            day: row.day,
+            llm_calls: u64::try_from(row.llm_calls).unwrap_or(0),
+            tokens: u64::try_from(row.tokens).unwrap_or(0),
+            cost_usd: row.cost_usd,
+        }
+    }
+}
+
+impl UsageRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    /// Usage counted on `day`; empty when nothing was recorded.
+    pub async fn get(&self, day: NaiveDate) -> Result<DailyUsage, AppError> {
+        let row = sqlx::query_as::<_, UsageRow>(
+            "SELECT day, llm_calls, tokens, cost_usd FROM usage_counters WHERE day = $1",
+        )
+        .bind(day)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(row.map_or_else(|| DailyUsage::empty(day), DailyUsage::from))
+    }
+
+    /// Add `increment` to its day's counters in one statement, so concurrent
+    /// callers never lose an update. Returns the new totals.
+    pub async fn increment(&self, increment: &DailyUsage) -> Result<DailyUsage, AppError> {
+        let row = sqlx::query_as::<_, UsageRow>(
+            r#"
+            INSERT INTO usage_counters (day, llm_calls, tokens, cost_usd)
+            VALUES ($1, $2, $3, $4)
+            ON CONFLICT (day) DO UPDATE SET
+                llm_calls = usage_counters.llm_calls + EXCLUDED.llm_calls,
+                tokens = usage_counters.tokens + EXCLUDED.tokens,
+                cost_usd = usage_counters.cost_usd + EXCLUDED.cost_usd,
+                updated_at = NOW()
+            RETURNING day, llm_calls, tokens, cost_usd
+            "#,
+        )
+        .bind(increment.day)
+        .bind(i64::try_from(increment.llm_calls).unwrap_or(i64::MAX))
+        .bind(i64::try_from(increment.tokens).unwrap_or(i64::MAX))
+        .bind(increment.cost_usd)
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(row.into())
+    }
+
+    /// Days with recorded usage on or after `since`, newest first.
+    pub async fn list_since(&self, since: NaiveDate) -> Result<Vec<DailyUsage>, AppError> {
+        let rows = sqlx::query_as::<_, UsageRow>(
+            r#"
+            SELECT day, llm_calls, tokens, cost_usd FROM usage_counters
+            WHERE day >= $1
+            ORDER BY day DESC
+            "#,
+        )
+        .bind(since)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(rows.into_iter().map(DailyUsage::from).collect())
+    }
+}
+
+impl UsageStore for UsageRepository {
+    async fn usage_on(&self, day: NaiveDate) -> Result<DailyUsage, AppError> {
+        self.get(day).await
+    }
+
+    async fn add_usage(&self, increment: &DailyUsage) -> Result<DailyUsage, AppError> {
+        self.increment(increment).await
+    }
+}
@@ -0,0 +1,103 @@
+use chrono::{DateTime, Utc};
+use sqlx::{PgPool, Pool, Postgres};
+use uuid::Uuid;
+
+use ares_core::error::AppError;
+use ares_core::event_log::{NewWorkerEvent, WorkerEventRecord, WorkerEventStore};
+
+/// PostgreSQL-backed log of worker job events.
+#[derive(Clone)]
+pub struct WorkerEventRepository {
+    pool: Pool<Postgres>,
+}
+
+// -- Internal row type for sqlx deserialization --
+
+#[derive(sqlx::FromRow)]
+struct WorkerEventRow {
+    id: i64,
+    worker_id: String,
+    job_id: Option<Uuid>,
+    event_type: String,
+    detail: serde_json::Value,
+    created_at: DateTime<Utc>,
+}
+
+impl From<WorkerEventRow> for WorkerEventRecord {
+    fn from(row: WorkerEventRow) -> Self {
+        WorkerEventRecord {
+            id: row.id,
+            worker_id: row.worker_id,
+            job_id: row.job_id,
+            event_type: row.event_type,
+            detail: row.detail,
+            created_at: row.created_at,
+        }
+    }
+}
+
+impl WorkerEventRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    /// Insert `events` in one statement, keeping their order.
+    pub async fn insert(&self, events: &[NewWorkerEvent]) -> Result<(), AppError> {
+        if events.is_empty() {
+            return Ok(());
+        }
+        let worker_ids: Vec<&str> = events.iter().map(|e| e.worker_id.as_str()).collect();
+        let job_ids: Vec<Option<Uuid>> = events.iter().map(|e| e.job_id).collect();
+        let event_types: Vec<&str> = events.iter().map(|e| e.event_type.as_str()).collect();
+        let details: Vec<&serde_json::Value> = events.iter().map(|e| &e.detail).collect();
+
+        sqlx::query(
+            r#"
+            INSERT INTO worker_events (worker_id, job_id, event_type, detail)
+            SELECT worker_id, job_id, event_type, detail
+            FROM UNNEST($1::varchar[], $2::uuid[], $3::varchar[], $4::jsonb[])
+                WITH ORDINALITY AS e(worker_id, job_id, event_type, detail, n)
+            ORDER BY n
+            "#,
+        )
+        .bind(worker_ids)
+        .bind(job_ids)
+        .bind(event_types)
+        .bind(details)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    /// The `limit` most recent events for a job, oldest first.
+    pub async fn list_for_job(
+        &self,
+        job_id: Uuid,
+        limit: usize,
+    ) -> Result<Vec<WorkerEventRecord>, AppError> {
+        let rows = sqlx::query_as::<_, WorkerEventRow>(
+            r#"
+            SELECT * FROM (
+                SELECT * FROM worker_events
+                WHERE job_id = $1
+                ORDER BY id DESC
+                LIMIT $2
+            ) recent
+            ORDER BY id
+            "#,
+        )
+        .bind(job_id)
+        .bind(i64::try_from(limit).unwrap_or(i64::MAX))
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(rows.into_iter().map(WorkerEventRecord::from).collect())
+    }
+}
+
+impl WorkerEventStore for WorkerEventRepository {
+    async fn save_events(&self, events: &[NewWorkerEvent]) -> Result<(), AppError> {
+        self.insert(events).await
+    }
+}
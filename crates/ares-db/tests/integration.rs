@@ -1,5 +1,13 @@
 mod integration {
+    pub mod api_key_tests;
+    pub mod audit_tests;
+    pub mod circuit_breaker_tests;
     pub mod common;
     pub mod extraction_tests;
     pub mod job_queue_tests;
+    pub mod maintenance_tests;
+    pub mod stats_tests;
+    pub mod usage_tests;
+    pub mod worker_event_tests;
+    pub mod worker_integration;
 }
@@ -0,0 +1,73 @@
+use ares_core::api_key::{ApiKeyScope, hash_api_key};
+use ares_db::ApiKeyRepository;
+
+use crate::integration::common::setup_test_db;
+
+#[tokio::test]
+async fn create_and_find_by_hash() {
+    let (pool, _container) = setup_test_db().await;
+    let repo = ApiKeyRepository::new(pool);
+
+    let (created, key) = repo
+        .create(
+            "ingest-service",
+            &[ApiKeyScope::Scrape, ApiKeyScope::JobsWrite],
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(created.name, "ingest-service");
+    assert_eq!(created.key_hash, hash_api_key(&key));
+    assert!(created.last_used_at.is_none());
+
+    let found = repo
+        .find_active_by_hash(&hash_api_key(&key))
+        .await
+        .unwrap()
+        .expect("key should be found");
+    assert_eq!(found.id, created.id);
+    assert_eq!(
+        found.scopes,
+        vec![ApiKeyScope::Scrape, ApiKeyScope::JobsWrite]
+    );
+}
+
+#[tokio::test]
+async fn revoked_key_is_not_found() {
+    let (pool, _container) = setup_test_db().await;
+    let repo = ApiKeyRepository::new(pool);
+
+    let (created, key) = repo.create("temp", &[ApiKeyScope::Admin]).await.unwrap();
+
+    assert!(repo.revoke(created.id).await.unwrap());
+    // Second revoke is a no-op.
+    assert!(!repo.revoke(created.id).await.unwrap());
+
+    assert!(
+        repo.find_active_by_hash(&hash_api_key(&key))
+            .await
+            .unwrap()
+            .is_none()
+    );
+    assert!(repo.list(false).await.unwrap().is_empty());
+
+    let all = repo.list(true).await.unwrap();
+    assert_eq!(all.len(), 1);
+    assert!(all[0].is_revoked());
+}
+
+#[tokio::test]
+async fn touch_last_used_sets_timestamp() {
+    let (pool, _container) = setup_test_db().await;
+    let repo = ApiKeyRepository::new(pool);
+
+    let (created, key) = repo.create("svc", &[ApiKeyScope::Scrape]).await.unwrap();
+    repo.touch_last_used(created.id).await.unwrap();
+
+    let found = repo
+        .find_active_by_hash(&hash_api_key(&key))
+        .await
+        .unwrap()
+        .unwrap();
+    assert!(found.last_used_at.is_some());
+}
@@ -0,0 +1,67 @@
+use chrono::{Duration, Utc};
+use uuid::Uuid;
+
+use ares_core::audit::{AuditAction, NewAuditEntry};
+use ares_db::AuditRepository;
+
+use crate::integration::common::setup_test_db;
+
+fn entry(action: AuditAction, resource_id: &str) -> NewAuditEntry {
+    NewAuditEntry {
+        api_key_id: Some(Uuid::new_v4()),
+        token_fingerprint: None,
+        action,
+        resource_id: Some(resource_id.to_string()),
+        request_id: Some("req-1".to_string()),
+        summary: serde_json::json!({ "resource": resource_id }),
+    }
+}
+
+#[tokio::test]
+async fn entries_are_listed_newest_first_and_filtered() {
+    let (pool, _container) = setup_test_db().await;
+    let repo = AuditRepository::new(pool);
+
+    repo.insert(&entry(AuditAction::SchemaCreate, "blog@1.0.0"))
+        .await
+        .unwrap();
+    repo.insert(&entry(AuditAction::JobCancel, "job-1"))
+        .await
+        .unwrap();
+    repo.insert(&entry(AuditAction::SchemaDelete, "blog@1.0.0"))
+        .await
+        .unwrap();
+
+    let all = repo.list(None, None, 10, 0).await.unwrap();
+    let actions: Vec<_> = all.iter().map(|e| e.action.as_str()).collect();
+    assert_eq!(actions, ["schema.delete", "job.cancel", "schema.create"]);
+    assert_eq!(all[1].resource_type, "job");
+    assert_eq!(all[1].resource_id.as_deref(), Some("job-1"));
+    assert_eq!(all[1].request_id.as_deref(), Some("req-1"));
+    assert_eq!(all[1].summary["resource"], "job-1");
+    assert_eq!(repo.count(None, None).await.unwrap(), 3);
+
+    let cancels = repo
+        .list(None, Some(AuditAction::JobCancel), 10, 0)
+        .await
+        .unwrap();
+    assert_eq!(cancels.len(), 1);
+    assert_eq!(
+        repo.count(None, Some(AuditAction::SchemaCreate))
+            .await
+            .unwrap(),
+        1
+    );
+
+    let page = repo.list(None, None, 1, 1).await.unwrap();
+    assert_eq!(page[0].action, "job.cancel");
+
+    let future = Utc::now() + Duration::hours(1);
+    assert!(
+        repo.list(Some(future), None, 10, 0)
+            .await
+            .unwrap()
+            .is_empty()
+    );
+    assert_eq!(repo.count(Some(future), None).await.unwrap(), 0);
+}
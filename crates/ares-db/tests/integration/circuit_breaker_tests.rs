@@ -0,0 +1,85 @@
+use std::time::Duration;
+
+use ares_core::circuit_breaker::{CircuitBreakerStats, CircuitState};
+use ares_core::job_queue::JobQueue;
+use ares_db::{CircuitBreakerRepository, ScrapeJobRepository};
+
+use crate::integration::common::setup_test_db;
+
+fn open_stats(name: &str) -> CircuitBreakerStats {
+    CircuitBreakerStats {
+        name: name.to_string(),
+        state: CircuitState::Open,
+        failure_count: 5,
+        success_count: 0,
+        last_error: Some("LLM API error (503): unavailable".to_string()),
+        time_until_half_open: Some(Duration::from_secs(30)),
+    }
+}
+
+#[tokio::test]
+async fn save_overwrites_reported_state() {
+    let (pool, _container) = setup_test_db().await;
+    let repo = CircuitBreakerRepository::new(pool);
+
+    repo.save(&open_stats("llm")).await.unwrap();
+    let record = repo
+        .get("llm")
+        .await
+        .unwrap()
+        .expect("breaker should exist");
+    assert_eq!(record.stats.state, CircuitState::Open);
+    assert_eq!(record.stats.failure_count, 5);
+    let remaining = record.stats.time_until_half_open.unwrap();
+    assert!(remaining > Duration::from_secs(25) && remaining <= Duration::from_secs(30));
+
+    let mut half_open = open_stats("llm");
+    half_open.state = CircuitState::HalfOpen;
+    half_open.time_until_half_open = None;
+    repo.save(&half_open).await.unwrap();
+
+    let all = repo.list().await.unwrap();
+    assert_eq!(all.len(), 1);
+    assert_eq!(all[0].stats.state, CircuitState::HalfOpen);
+    assert!(all[0].stats.time_until_half_open.is_none());
+}
+
+#[tokio::test]
+async fn request_reset_closes_and_survives_later_saves() {
+    let (pool, _container) = setup_test_db().await;
+    let repo = CircuitBreakerRepository::new(pool.clone());
+    let queue = ScrapeJobRepository::new(pool);
+
+    assert!(repo.request_reset("llm").await.unwrap().is_none());
+
+    queue
+        .save_circuit_breaker(&open_stats("llm"))
+        .await
+        .unwrap();
+    assert!(
+        queue
+            .circuit_breaker_reset_requested_at("llm")
+            .await
+            .unwrap()
+            .is_none()
+    );
+
+    let reset = repo.request_reset("llm").await.unwrap().unwrap();
+    assert_eq!(reset.stats.state, CircuitState::Closed);
+    assert_eq!(reset.stats.failure_count, 0);
+    assert!(reset.stats.last_error.is_none());
+    let requested = reset.reset_requested_at.expect("reset should be recorded");
+
+    // A worker reporting its stale state does not clear the request.
+    queue
+        .save_circuit_breaker(&open_stats("llm"))
+        .await
+        .unwrap();
+    assert_eq!(
+        queue
+            .circuit_breaker_reset_requested_at("llm")
+            .await
+            .unwrap(),
+        Some(requested)
+    );
+}
@@ -1,88 +1,28 @@
+use ares_db::Database;
 use sqlx::PgPool;
 use sqlx::postgres::PgPoolOptions;
 use testcontainers::core::{ContainerPort, WaitFor};
 use testcontainers::runners::AsyncRunner;
 use testcontainers::{ContainerAsync, GenericImage, ImageExt};
 
-/// SQL migration statements, executed one at a time.
-const MIGRATIONS: &[&str] = &[
-    // 001_init.sql
-    r#"CREATE TABLE IF NOT EXISTS extractions (
-        id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
-        url VARCHAR NOT NULL,
-        schema_name VARCHAR NOT NULL,
-        extracted_data JSONB NOT NULL,
-        raw_content_hash VARCHAR(64) NOT NULL,
-        data_hash VARCHAR(64) NOT NULL,
-        model VARCHAR(100) NOT NULL,
-        created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
-    )"#,
-    r#"CREATE INDEX IF NOT EXISTS idx_extractions_url
-        ON extractions(url, created_at DESC)"#,
-    r#"CREATE INDEX IF NOT EXISTS idx_extractions_url_schema
-        ON extractions(url, schema_name, created_at DESC)"#,
-    // 002_scrape_jobs.sql
-    r#"CREATE TABLE IF NOT EXISTS scrape_jobs (
-        id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
-        url VARCHAR NOT NULL,
-        schema_name VARCHAR NOT NULL,
-        schema JSONB NOT NULL,
-        model VARCHAR(100) NOT NULL,
-        base_url VARCHAR NOT NULL DEFAULT 'https://api.openai.com/v1',
-        status VARCHAR(20) NOT NULL DEFAULT 'pending',
-        created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
-        updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
-        started_at TIMESTAMPTZ,
-        completed_at TIMESTAMPTZ,
-        retry_count INTEGER NOT NULL DEFAULT 0,
-        max_retries INTEGER NOT NULL DEFAULT 3,
-        next_retry_at TIMESTAMPTZ,
-        error_message TEXT,
-        extraction_id UUID REFERENCES extractions(id),
-        worker_id VARCHAR(255),
-        CONSTRAINT chk_scrape_jobs_status CHECK (
-            status IN ('pending', 'running', 'completed', 'failed', 'cancelled')
-        )
-    )"#,
-    r#"CREATE INDEX IF NOT EXISTS idx_scrape_jobs_pending ON scrape_jobs(created_at) WHERE status = 'pending'"#,
-    r#"CREATE INDEX IF NOT EXISTS idx_scrape_jobs_retry ON scrape_jobs(next_retry_at) WHERE status = 'pending' AND next_retry_at IS NOT NULL"#,
-    r#"CREATE INDEX IF NOT EXISTS idx_scrape_jobs_worker ON scrape_jobs(worker_id) WHERE status = 'running'"#,
-    r#"CREATE INDEX IF NOT EXISTS idx_scrape_jobs_status ON scrape_jobs(status, created_at DESC)"#,
-    r#"CREATE INDEX IF NOT EXISTS idx_scrape_jobs_url ON scrape_jobs(url, created_at DESC)"#,
-    // 003_crawl_support.sql
-    r#"ALTER TABLE scrape_jobs
-       ADD COLUMN IF NOT EXISTS crawl_session_id UUID,
-       ADD COLUMN IF NOT EXISTS parent_job_id UUID REFERENCES scrape_jobs(id),
-       ADD COLUMN IF NOT EXISTS depth INTEGER NOT NULL DEFAULT 0,
-       ADD COLUMN IF NOT EXISTS max_depth INTEGER NOT NULL DEFAULT 0"#,
-    r#"CREATE TABLE IF NOT EXISTS crawl_visited_urls (
-        session_id UUID NOT NULL,
-        url_hash VARCHAR(64) NOT NULL,
-        created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
-        PRIMARY KEY (session_id, url_hash)
-    )"#,
-    r#"CREATE INDEX IF NOT EXISTS idx_scrape_jobs_crawl_session
-       ON scrape_jobs(crawl_session_id, created_at DESC)"#,
-    r#"CREATE INDEX IF NOT EXISTS idx_scrape_jobs_parent
-       ON scrape_jobs(parent_job_id)"#,
-    // 003_crawl_support.sql (crawl config columns)
-    r#"ALTER TABLE scrape_jobs
-       ADD COLUMN IF NOT EXISTS max_pages INTEGER NOT NULL DEFAULT 100,
-       ADD COLUMN IF NOT EXISTS allowed_domains JSONB NOT NULL DEFAULT '[]'"#,
-    // 004_extraction_metadata.sql
-    r#"ALTER TABLE extractions
-       ADD COLUMN IF NOT EXISTS provider          VARCHAR(50) NOT NULL DEFAULT 'openai',
-       ADD COLUMN IF NOT EXISTS schema_version    VARCHAR(50),
-       ADD COLUMN IF NOT EXISTS latency_ms        BIGINT,
-       ADD COLUMN IF NOT EXISTS prompt_tokens     INTEGER,
-       ADD COLUMN IF NOT EXISTS completion_tokens INTEGER"#,
-];
-
-/// Spins up a PostgreSQL container and returns a connected pool.
+/// Spins up a PostgreSQL container and returns a connected pool with the
+/// schema in place.
 ///
 /// The `ContainerAsync` must be kept in scope for the test duration —
 /// dropping it will stop the container.
 pub async fn setup_test_db() -> (PgPool, ContainerAsync<GenericImage>) {
+    let (pool, container) = setup_empty_db().await;
+    Database::from_pool(pool.clone())
+        .migrate()
+        .await
+        .expect("Failed to run migrations");
+
+    (pool, container)
+}
+
+/// Like [`setup_test_db`], but without any tables, for testing the bundled
+/// migrations themselves.
+pub async fn setup_empty_db() -> (PgPool, ContainerAsync<GenericImage>) {
     let container = GenericImage::new("postgres", "16")
         .with_exposed_port(ContainerPort::Tcp(5432))
         .with_wait_for(WaitFor::message_on_stderr(
@@ -122,13 +62,5 @@ pub async fn setup_test_db() -> (PgPool, ContainerAsync<GenericImage>) {
         }
     };
 
-    // Run migrations one statement at a time
-    for migration in MIGRATIONS {
-        sqlx::query(migration)
-            .execute(&pool)
-            .await
-            .expect("Failed to run migration");
-    }
-
     (pool, container)
 }
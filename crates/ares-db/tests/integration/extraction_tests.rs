@@ -1,5 +1,9 @@
-use ares_core::models::NewExtraction;
-use ares_db::ExtractionRepository;
+use ares_core::job::CreateScrapeJobRequest;
+use ares_core::job_queue::JobQueue;
+use ares_core::models::{NewExtraction, SaveOutcome};
+use ares_core::retention::RetentionPolicy;
+use ares_db::{ExtractionRepository, ScrapeJobRepository};
+use uuid::Uuid;
 
 use crate::integration::common::setup_test_db;
 
@@ -13,13 +17,21 @@ async fn save_and_retrieve_extraction() {
         schema_name: "blog".into(),
         extracted_data: serde_json::json!({"title": "Hello World"}),
         raw_content_hash: "abc123".repeat(10),
+        content_simhash: Some("0123456789abcdef".into()),
         data_hash: "def456".repeat(10),
         model: "gpt-4o-mini".into(),
         provider: "anthropic".into(),
+        prompt_hash: Some("0a1b".repeat(16)),
+        schema_hash: Some("5c4e".repeat(16)),
         schema_version: Some("1.0.0".into()),
         latency_ms: Some(1234),
         prompt_tokens: Some(900),
         completion_tokens: Some(42),
+        html_snapshot_key: Some("html/ab/abc.html".into()),
+        markdown_snapshot_key: None,
+        duplicate_of: None,
+        detected_language: Some("eng".into()),
+        retention: None,
     };
 
     let id = repo.save(&extraction).await.unwrap();
@@ -42,10 +54,19 @@ async fn save_and_retrieve_extraction() {
 
     // Run metadata round-trips.
     assert_eq!(latest.provider, "anthropic");
+    assert_eq!(latest.prompt_hash, Some("0a1b".repeat(16)));
     assert_eq!(latest.schema_version.as_deref(), Some("1.0.0"));
     assert_eq!(latest.latency_ms, Some(1234));
     assert_eq!(latest.prompt_tokens, Some(900));
     assert_eq!(latest.completion_tokens, Some(42));
+    assert_eq!(latest.detected_language.as_deref(), Some("eng"));
+    assert_eq!(latest.content_simhash.as_deref(), Some("0123456789abcdef"));
+    assert_eq!(latest.schema_hash, extraction.schema_hash);
+    assert_eq!(
+        latest.html_snapshot_key.as_deref(),
+        Some("html/ab/abc.html")
+    );
+    assert_eq!(latest.markdown_snapshot_key, None);
 }
 
 #[tokio::test]
@@ -145,6 +166,368 @@ async fn get_history_returns_ordered_with_limit() {
     assert_eq!(page2[1].extracted_data["index"], 1);
 }
 
+#[tokio::test]
+async fn get_history_entries_compare_across_page_boundaries() {
+    let (pool, _container) = setup_test_db().await;
+    let repo = ExtractionRepository::new(pool);
+
+    // Oldest first.
+    let mut ids = Vec::new();
+    for data_hash in ["a", "a", "b", "b", "a"] {
+        let id = repo
+            .save(&NewExtraction {
+                data_hash: data_hash.into(),
+                ..extraction("https://example.com", "blog")
+            })
+            .await
+            .unwrap();
+        ids.push(id);
+        tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+    }
+    // Another page's history doesn't interleave.
+    repo.save(&NewExtraction {
+        data_hash: "a".into(),
+        ..extraction("https://example.com/other", "blog")
+    })
+    .await
+    .unwrap();
+
+    let all = repo
+        .get_history_entries("https://example.com", "blog", None, 10, 0)
+        .await
+        .unwrap();
+    let changed: Vec<bool> = all.iter().map(|e| e.changed).collect();
+    assert_eq!(changed, [true, false, true, false, true]);
+    let previous: Vec<Option<Uuid>> = all.iter().map(|e| e.previous_extraction_id).collect();
+    assert_eq!(
+        previous,
+        [Some(ids[3]), Some(ids[2]), Some(ids[1]), Some(ids[0]), None]
+    );
+
+    // The oldest row of a page is still compared with the row past it.
+    let page = repo
+        .get_history_entries("https://example.com", "blog", None, 2, 1)
+        .await
+        .unwrap();
+    assert_eq!(page.len(), 2);
+    assert_eq!(page[0].extraction.id, ids[3]);
+    assert!(!page[0].changed);
+    assert_eq!(page[1].extraction.id, ids[2]);
+    assert!(page[1].changed);
+    assert_eq!(page[1].previous_extraction_id, Some(ids[1]));
+}
+
+#[tokio::test]
+async fn history_filters_by_detected_language() {
+    let (pool, _container) = setup_test_db().await;
+    let repo = ExtractionRepository::new(pool);
+
+    // Oldest first.
+    let mut ids = Vec::new();
+    for (data_hash, language) in [("a", Some("deu")), ("b", Some("eng")), ("c", None)] {
+        let id = repo
+            .save(&NewExtraction {
+                data_hash: data_hash.into(),
+                detected_language: language.map(Into::into),
+                ..extraction("https://example.com", "blog")
+            })
+            .await
+            .unwrap();
+        ids.push(id);
+        tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+    }
+
+    let english = repo
+        .get_history_entries("https://example.com", "blog", Some("eng"), 10, 0)
+        .await
+        .unwrap();
+    assert_eq!(english.len(), 1);
+    assert_eq!(english[0].extraction.id, ids[1]);
+    assert_eq!(
+        english[0].extraction.detected_language.as_deref(),
+        Some("eng")
+    );
+    // Its predecessor comes from the whole history, whatever its language.
+    assert_eq!(english[0].previous_extraction_id, Some(ids[0]));
+
+    assert_eq!(
+        repo.count_history("https://example.com", "blog", Some("eng"))
+            .await
+            .unwrap(),
+        1
+    );
+    assert_eq!(
+        repo.count_history("https://example.com", "blog", Some("fra"))
+            .await
+            .unwrap(),
+        0
+    );
+    assert_eq!(
+        repo.count_history("https://example.com", "blog", None)
+            .await
+            .unwrap(),
+        3
+    );
+}
+
+#[tokio::test]
+async fn upsert_updates_matching_row_and_leaves_snapshots_alone() {
+    let (pool, _container) = setup_test_db().await;
+    let repo = ExtractionRepository::new(pool);
+
+    let a = NewExtraction {
+        url: "https://example.com".into(),
+        schema_name: "blog".into(),
+        extracted_data: serde_json::json!({"title": "A"}),
+        raw_content_hash: "hash-a".into(),
+        data_hash: "dhash-a".into(),
+        model: "model".into(),
+        ..Default::default()
+    };
+    let b = NewExtraction {
+        extracted_data: serde_json::json!({"title": "B"}),
+        data_hash: "dhash-b".into(),
+        ..a.clone()
+    };
+
+    // A plain snapshot with the same hash is never matched by upsert.
+    let snapshot_id = repo.save(&a).await.unwrap();
+
+    let first = repo.upsert(&a).await.unwrap();
+    assert_ne!(first, snapshot_id);
+    let second = repo.upsert(&a).await.unwrap();
+    assert_eq!(first, second);
+    assert_eq!(
+        repo.count_history("https://example.com", "blog", None)
+            .await
+            .unwrap(),
+        2
+    );
+
+    // A -> B -> A: the refreshed A row becomes the latest again.
+    tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+    let b_id = repo.upsert(&b).await.unwrap();
+    assert_ne!(b_id, first);
+    tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+    assert_eq!(repo.upsert(&a).await.unwrap(), first);
+
+    let latest = repo
+        .get_latest("https://example.com", "blog")
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(latest.id, first);
+    assert_eq!(
+        repo.count_history("https://example.com", "blog", None)
+            .await
+            .unwrap(),
+        3
+    );
+}
+
+async fn last_seen_at(pool: &sqlx::PgPool, id: Uuid) -> chrono::DateTime<chrono::Utc> {
+    let (seen,) = sqlx::query_as("SELECT last_seen_at FROM extractions WHERE id = $1")
+        .bind(id)
+        .fetch_one(pool)
+        .await
+        .unwrap();
+    seen
+}
+
+#[tokio::test]
+async fn keep_if_unchanged_bumps_the_latest_matching_row() {
+    let (pool, _container) = setup_test_db().await;
+    let repo = ExtractionRepository::new(pool.clone());
+    let a = extraction("https://example.com", "blog");
+
+    assert_eq!(repo.keep_if_unchanged(&a).await.unwrap(), None);
+    let first = repo.save(&a).await.unwrap();
+    let seen = last_seen_at(&pool, first).await;
+
+    tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+    for _ in 0..2 {
+        assert_eq!(
+            repo.keep_if_unchanged(&a).await.unwrap(),
+            Some(SaveOutcome::Unchanged {
+                existing_id: first,
+                last_seen_bumped: true,
+            })
+        );
+    }
+    assert_eq!(
+        repo.count_history("https://example.com", "blog", None)
+            .await
+            .unwrap(),
+        1
+    );
+    assert!(last_seen_at(&pool, first).await > seen);
+}
+
+#[tokio::test]
+async fn keep_if_unchanged_compares_with_the_latest_only() {
+    let (pool, _container) = setup_test_db().await;
+    let repo = ExtractionRepository::new(pool.clone());
+    let a = extraction("https://example.com", "blog");
+    let b = NewExtraction {
+        extracted_data: serde_json::json!({"title": "B"}),
+        data_hash: "dhash-b".into(),
+        ..a.clone()
+    };
+
+    let first = repo.save(&a).await.unwrap();
+    let seen = last_seen_at(&pool, first).await;
+    tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+    assert_eq!(repo.keep_if_unchanged(&b).await.unwrap(), None);
+    repo.save(&b).await.unwrap();
+
+    // A -> B -> A: A is no longer the latest, so it isn't kept.
+    assert_eq!(repo.keep_if_unchanged(&a).await.unwrap(), None);
+    assert_eq!(last_seen_at(&pool, first).await, seen);
+
+    // Other schemas for the same URL are compared separately.
+    let other = NewExtraction {
+        schema_name: "other".into(),
+        ..a.clone()
+    };
+    assert_eq!(repo.keep_if_unchanged(&other).await.unwrap(), None);
+}
+
+fn extraction(url: &str, schema_name: &str) -> NewExtraction {
+    NewExtraction {
+        url: url.into(),
+        schema_name: schema_name.into(),
+        extracted_data: serde_json::json!({"title": "Hello"}),
+        raw_content_hash: "chash".into(),
+        data_hash: "dhash".into(),
+        model: "model".into(),
+        ..Default::default()
+    }
+}
+
+#[tokio::test]
+async fn delete_by_url_removes_only_that_url() {
+    let (pool, _container) = setup_test_db().await;
+    let repo = ExtractionRepository::new(pool.clone());
+    let jobs = ScrapeJobRepository::new(pool);
+
+    let erased = repo
+        .save(&extraction("https://me.example", "blog"))
+        .await
+        .unwrap();
+    repo.save(&extraction("https://me.example", "profile"))
+        .await
+        .unwrap();
+    repo.save(&extraction("https://other.example", "blog"))
+        .await
+        .unwrap();
+
+    // A job that produced an erased extraction survives, detached from it.
+    let job = jobs
+        .create_job(CreateScrapeJobRequest::new(
+            "https://me.example",
+            "blog",
+            serde_json::json!({"type": "object"}),
+            "gpt-4o-mini",
+            "https://api.openai.com/v1",
+        ))
+        .await
+        .unwrap();
+    jobs.complete_job(job.id, Some(erased)).await.unwrap();
+    let completed = jobs.get_job(job.id).await.unwrap().unwrap();
+
+    assert_eq!(
+        repo.delete_by_url("https://me.example")
+            .await
+            .unwrap()
+            .deleted,
+        2
+    );
+    assert_eq!(
+        repo.delete_by_url("https://me.example")
+            .await
+            .unwrap()
+            .deleted,
+        0
+    );
+
+    assert_eq!(
+        repo.count_history("https://me.example", "blog", None)
+            .await
+            .unwrap(),
+        0
+    );
+    assert_eq!(
+        repo.count_history("https://other.example", "blog", None)
+            .await
+            .unwrap(),
+        1
+    );
+    let job = jobs.get_job(job.id).await.unwrap().unwrap();
+    assert_eq!(job.extraction_id, None);
+    // Detaching changed the job, so its ETag must change too.
+    assert!(job.updated_at > completed.updated_at);
+}
+
+#[tokio::test]
+async fn delete_by_schema_removes_only_that_schema() {
+    let (pool, _container) = setup_test_db().await;
+    let repo = ExtractionRepository::new(pool);
+
+    repo.save(&extraction("https://a.example", "legacy"))
+        .await
+        .unwrap();
+    repo.save(&extraction("https://b.example", "legacy"))
+        .await
+        .unwrap();
+    repo.save(&extraction("https://a.example", "blog"))
+        .await
+        .unwrap();
+
+    assert_eq!(repo.delete_by_schema("legacy").await.unwrap().deleted, 2);
+    assert_eq!(
+        repo.count_history("https://a.example", "legacy", None)
+            .await
+            .unwrap(),
+        0
+    );
+    assert_eq!(
+        repo.count_history("https://a.example", "blog", None)
+            .await
+            .unwrap(),
+        1
+    );
+}
+
+#[tokio::test]
+async fn latest_extracted_at_covers_only_extracted_urls() {
+    let (pool, _container) = setup_test_db().await;
+    let repo = ExtractionRepository::new(pool);
+
+    for _ in 0..2 {
+        repo.save(&extraction("https://a.example", "blog"))
+            .await
+            .unwrap();
+    }
+    repo.save(&extraction("https://b.example", "other"))
+        .await
+        .unwrap();
+
+    let urls = [
+        "https://a.example".to_string(),
+        "https://b.example".to_string(),
+        "https://c.example".to_string(),
+    ];
+    let latest = repo.latest_extracted_at(&urls, "blog").await.unwrap();
+
+    assert_eq!(latest.len(), 1);
+    let newest = repo
+        .get_latest("https://a.example", "blog")
+        .await
+        .unwrap()
+        .unwrap();
+    assert!(latest["https://a.example"] >= newest.created_at);
+}
+
 #[tokio::test]
 async fn health_check_succeeds() {
     let (pool, _container) = setup_test_db().await;
@@ -152,3 +535,407 @@ async fn health_check_succeeds() {
 
     repo.health_check().await.unwrap();
 }
+
+#[tokio::test]
+async fn release_snapshots_returns_only_unreferenced_keys() {
+    let (pool, _container) = setup_test_db().await;
+    let repo = ExtractionRepository::new(pool);
+
+    let with_keys = |html: &str, markdown: &str| NewExtraction {
+        html_snapshot_key: Some(html.into()),
+        markdown_snapshot_key: Some(markdown.into()),
+        ..extraction("https://example.com", "blog")
+    };
+    let old = repo
+        .save(&with_keys("html/aa/a.html", "markdown/bb/b.md"))
+        .await
+        .unwrap();
+    tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+    let cutoff = chrono::Utc::now();
+    tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+    // The newer extraction shares the old one's HTML snapshot.
+    let new = repo
+        .save(&with_keys("html/aa/a.html", "markdown/cc/c.md"))
+        .await
+        .unwrap();
+
+    let released = repo.release_snapshots(cutoff).await.unwrap();
+    assert_eq!(released, ["markdown/bb/b.md"]);
+
+    let old = repo.get(old).await.unwrap().unwrap();
+    assert_eq!(old.html_snapshot_key, None);
+    assert_eq!(old.markdown_snapshot_key, None);
+    let new = repo.get(new).await.unwrap().unwrap();
+    assert_eq!(new.html_snapshot_key.as_deref(), Some("html/aa/a.html"));
+
+    assert!(repo.release_snapshots(cutoff).await.unwrap().is_empty());
+}
+
+#[tokio::test]
+async fn delete_by_url_releases_unreferenced_snapshots() {
+    let (pool, _container) = setup_test_db().await;
+    let repo = ExtractionRepository::new(pool);
+
+    let with_keys = |url: &str, html: &str, markdown: &str| NewExtraction {
+        html_snapshot_key: Some(html.into()),
+        markdown_snapshot_key: Some(markdown.into()),
+        ..extraction(url, "blog")
+    };
+    repo.save(&with_keys(
+        "https://me.example",
+        "html/aa/a.html",
+        "markdown/bb/b.md",
+    ))
+    .await
+    .unwrap();
+    // Another page with the same HTML keeps that snapshot alive.
+    repo.save(&with_keys(
+        "https://other.example",
+        "html/aa/a.html",
+        "markdown/cc/c.md",
+    ))
+    .await
+    .unwrap();
+
+    let report = repo.delete_by_url("https://me.example").await.unwrap();
+    assert_eq!(report.deleted, 1);
+    assert_eq!(report.released_snapshots, ["markdown/bb/b.md"]);
+}
+
+#[tokio::test]
+async fn failed_snapshot_deletions_are_handed_out_again() {
+    let (pool, _container) = setup_test_db().await;
+    let repo = ExtractionRepository::new(pool);
+
+    repo.record_failed_snapshot_deletions(&["html/aa/a.html".into(), "markdown/bb/b.md".into()])
+        .await
+        .unwrap();
+    // An extraction referencing one of them again keeps it.
+    repo.save(&NewExtraction {
+        html_snapshot_key: Some("html/aa/a.html".into()),
+        ..extraction("https://example.com", "blog")
+    })
+    .await
+    .unwrap();
+
+    assert_eq!(
+        repo.take_failed_snapshot_deletions().await.unwrap(),
+        ["markdown/bb/b.md"]
+    );
+    assert!(
+        repo.take_failed_snapshot_deletions()
+            .await
+            .unwrap()
+            .is_empty()
+    );
+}
+
+#[tokio::test]
+async fn apply_retention_scrubs_or_deletes_expired_rows() {
+    let (pool, _container) = setup_test_db().await;
+    let repo = ExtractionRepository::new(pool.clone());
+    let jobs = ScrapeJobRepository::new(pool);
+    let retained = |url: &str, data: serde_json::Value, days: u32, fields: &[&str]| NewExtraction {
+        extracted_data: data,
+        retention: Some(RetentionPolicy {
+            days,
+            scrub_fields: fields.iter().map(|f| f.to_string()).collect(),
+            scrub_on_save: false,
+        }),
+        ..extraction(url, "people")
+    };
+
+    let scrubbed = repo
+        .save(&retained(
+            "https://example.com/a",
+            serde_json::json!({
+                "title": "Hello",
+                "author": {"name": "Ada", "contact": {"email": "ada@example.com"}},
+                "replies": [{"email": "bob@example.com"}, {"email": "eve@example.com"}]
+            }),
+            30,
+            &["/author/contact/email", "/replies/1/email"],
+        ))
+        .await
+        .unwrap();
+    let deleted = repo
+        .save(&retained(
+            "https://example.com/b",
+            serde_json::json!({"title": "Bye"}),
+            30,
+            &[],
+        ))
+        .await
+        .unwrap();
+    let job = jobs
+        .create_job(CreateScrapeJobRequest::new(
+            "https://example.com/b",
+            "people",
+            serde_json::json!({"type": "object"}),
+            "gpt-4o-mini",
+            "https://api.openai.com/v1",
+        ))
+        .await
+        .unwrap();
+    jobs.complete_job(job.id, Some(deleted)).await.unwrap();
+    let completed = jobs.get_job(job.id).await.unwrap().unwrap();
+    let fresh = repo
+        .save(&retained(
+            "https://example.com/c",
+            serde_json::json!({"author": {"email": "zoe@example.com"}}),
+            90,
+            &["/author/email"],
+        ))
+        .await
+        .unwrap();
+    let forever = repo
+        .save(&extraction("https://example.com/d", "people"))
+        .await
+        .unwrap();
+
+    let now = chrono::Utc::now() + chrono::Duration::days(31);
+    let report = repo.apply_retention(now).await.unwrap();
+    assert_eq!(report.scrubbed, 1);
+    assert_eq!(report.deleted, 1);
+
+    let row = repo.get(scrubbed).await.unwrap().unwrap();
+    assert_eq!(
+        row.extracted_data,
+        serde_json::json!({
+            "title": "Hello",
+            "author": {"name": "Ada", "contact": {"email": null}},
+            "replies": [{"email": "bob@example.com"}, {"email": null}]
+        })
+    );
+    assert_eq!(row.data_hash, "dhash");
+    assert!(row.scrubbed_at.is_some());
+    assert!(repo.get(deleted).await.unwrap().is_none());
+    let job = jobs.get_job(job.id).await.unwrap().unwrap();
+    assert_eq!(job.extraction_id, None);
+    assert!(job.updated_at > completed.updated_at);
+    assert_eq!(
+        repo.get(fresh).await.unwrap().unwrap().extracted_data,
+        serde_json::json!({"author": {"email": "zoe@example.com"}})
+    );
+    assert!(repo.get(forever).await.unwrap().is_some());
+
+    // Scrubbed rows are only rewritten once.
+    let report = repo.apply_retention(now).await.unwrap();
+    assert_eq!((report.scrubbed, report.deleted), (0, 0));
+}
+
+#[tokio::test]
+async fn apply_retention_drops_content_and_releases_snapshots() {
+    let (pool, _container) = setup_test_db().await;
+    let repo = ExtractionRepository::new(pool);
+    let expiring = |url: &str, fields: &[&str], html: &str, markdown: &str| NewExtraction {
+        retention: Some(RetentionPolicy {
+            days: 30,
+            scrub_fields: fields.iter().map(|f| f.to_string()).collect(),
+            scrub_on_save: false,
+        }),
+        html_snapshot_key: Some(html.into()),
+        markdown_snapshot_key: Some(markdown.into()),
+        ..extraction(url, "people")
+    };
+
+    let scrubbed = repo
+        .save(&expiring(
+            "https://example.com/a",
+            &["/title"],
+            "html/aa/a.html",
+            "markdown/bb/b.md",
+        ))
+        .await
+        .unwrap();
+    repo.save_content(scrubbed, "# Hello").await.unwrap();
+    let deleted = repo
+        .save(&expiring(
+            "https://example.com/b",
+            &[],
+            "html/dd/d.html",
+            "markdown/ee/e.md",
+        ))
+        .await
+        .unwrap();
+    repo.save_content(deleted, "# Bye").await.unwrap();
+    // A row that is kept shares the scrubbed row's HTML snapshot.
+    repo.save(&NewExtraction {
+        html_snapshot_key: Some("html/aa/a.html".into()),
+        ..extraction("https://example.com/c", "people")
+    })
+    .await
+    .unwrap();
+
+    let now = chrono::Utc::now() + chrono::Duration::days(31);
+    let report = repo.apply_retention(now).await.unwrap();
+    assert_eq!((report.scrubbed, report.deleted), (1, 1));
+    assert_eq!(
+        report.released_snapshots,
+        ["html/dd/d.html", "markdown/bb/b.md", "markdown/ee/e.md"]
+    );
+
+    let row = repo.get(scrubbed).await.unwrap().unwrap();
+    assert_eq!(row.html_snapshot_key, None);
+    assert_eq!(row.markdown_snapshot_key, None);
+    assert_eq!(repo.get_content(scrubbed).await.unwrap(), None);
+    assert_eq!(repo.get_content(deleted).await.unwrap(), None);
+}
+
+#[tokio::test]
+async fn apply_retention_works_through_more_rows_than_one_batch() {
+    let (pool, _container) = setup_test_db().await;
+    let repo = ExtractionRepository::new(pool.clone());
+    for i in 0..501 {
+        repo.save(&NewExtraction {
+            retention: Some(RetentionPolicy {
+                days: 30,
+                scrub_fields: Vec::new(),
+                scrub_on_save: false,
+            }),
+            ..extraction(&format!("https://example.com/{i}"), "people")
+        })
+        .await
+        .unwrap();
+    }
+
+    let now = chrono::Utc::now() + chrono::Duration::days(31);
+    let report = repo.apply_retention(now).await.unwrap();
+    assert_eq!(report.deleted, 501);
+    let (left,): (i64,) = sqlx::query_as("SELECT COUNT(*) FROM extractions")
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+    assert_eq!(left, 0);
+}
+
+#[tokio::test]
+async fn content_is_stored_apart_and_deleted_with_its_extraction() {
+    let (pool, _container) = setup_test_db().await;
+    let repo = ExtractionRepository::new(pool);
+
+    let id = repo
+        .save(&extraction("https://example.com", "blog"))
+        .await
+        .unwrap();
+    assert_eq!(repo.get_content(id).await.unwrap(), None);
+
+    repo.save_content(id, "# First").await.unwrap();
+    repo.save_content(id, "# Second").await.unwrap();
+    assert_eq!(
+        repo.get_content(id).await.unwrap().as_deref(),
+        Some("# Second")
+    );
+
+    repo.delete_by_url("https://example.com").await.unwrap();
+    assert_eq!(repo.get_content(id).await.unwrap(), None);
+}
+
+#[tokio::test]
+async fn find_by_content_hash_returns_the_earliest_match_in_the_window() {
+    let (pool, _container) = setup_test_db().await;
+    let repo = ExtractionRepository::new(pool);
+
+    let before = chrono::Utc::now();
+    let original = repo
+        .save(&extraction("https://example.com/page", "blog"))
+        .await
+        .unwrap();
+    let mirror = repo
+        .save(&NewExtraction {
+            duplicate_of: Some(original),
+            ..extraction("https://mirror.example.com/page", "blog")
+        })
+        .await
+        .unwrap();
+
+    let found = repo
+        .find_by_content_hash("blog", "chash", before)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(found.id, original);
+    assert_eq!(found.duplicate_of, None);
+    let mirror = repo.get(mirror).await.unwrap().unwrap();
+    assert_eq!(mirror.duplicate_of, Some(original));
+
+    assert!(
+        repo.find_by_content_hash("blog", "other", before)
+            .await
+            .unwrap()
+            .is_none()
+    );
+    assert!(
+        repo.find_by_content_hash("news", "chash", before)
+            .await
+            .unwrap()
+            .is_none()
+    );
+    let later = chrono::Utc::now() + chrono::Duration::seconds(1);
+    assert!(
+        repo.find_by_content_hash("blog", "chash", later)
+            .await
+            .unwrap()
+            .is_none()
+    );
+
+    // Erasing the original keeps its duplicates, unlinked.
+    repo.delete_by_url("https://example.com/page")
+        .await
+        .unwrap();
+    let mirror = repo.get(mirror.id).await.unwrap().unwrap();
+    assert_eq!(mirror.duplicate_of, None);
+}
+
+#[tokio::test]
+async fn list_golden_returns_the_newest_golden_row_per_url() {
+    let (pool, _container) = setup_test_db().await;
+    let repo = ExtractionRepository::new(pool);
+
+    let old_a = repo
+        .save(&extraction("https://a.example", "blog"))
+        .await
+        .unwrap();
+    let new_a = repo
+        .save(&extraction("https://a.example", "blog"))
+        .await
+        .unwrap();
+    let b = repo
+        .save(&extraction("https://b.example", "blog"))
+        .await
+        .unwrap();
+    let other = repo
+        .save(&extraction("https://a.example", "news"))
+        .await
+        .unwrap();
+    repo.save(&extraction("https://c.example", "blog"))
+        .await
+        .unwrap();
+
+    for id in [old_a, new_a, b, other] {
+        let marked = repo.set_golden(id, true).await.unwrap().unwrap();
+        assert!(marked.golden);
+    }
+
+    let goldens = repo.list_golden("blog").await.unwrap();
+    let ids: Vec<_> = goldens.iter().map(|e| e.id).collect();
+    assert_eq!(ids, vec![new_a, b]);
+
+    // Unmarking the newest falls back to the older golden row.
+    assert!(!repo.set_golden(new_a, false).await.unwrap().unwrap().golden);
+    let ids: Vec<_> = repo
+        .list_golden("blog")
+        .await
+        .unwrap()
+        .iter()
+        .map(|e| e.id)
+        .collect();
+    assert_eq!(ids, vec![old_a, b]);
+
+    assert!(
+        repo.set_golden(Uuid::new_v4(), true)
+            .await
+            .unwrap()
+            .is_none()
+    );
+}
@@ -1,6 +1,10 @@
+use std::time::Duration;
+
+use ares_core::FetchOptions;
 use ares_core::job::{CreateScrapeJobRequest, JobStatus};
 use ares_core::job_queue::JobQueue;
 use ares_db::ScrapeJobRepository;
+use uuid::Uuid;
 
 use crate::integration::common::setup_test_db;
 
@@ -42,6 +46,65 @@ async fn create_job_with_custom_max_retries() {
     assert_eq!(job.max_retries, 10);
 }
 
+#[tokio::test]
+async fn create_jobs_inserts_every_request_in_order() {
+    let (pool, _container) = setup_test_db().await;
+    let repo = ScrapeJobRepository::new(pool);
+
+    let requests: Vec<_> = (0..3)
+        .map(|i| {
+            CreateScrapeJobRequest::new(
+                format!("https://example.com/{i}"),
+                "blog",
+                serde_json::json!({"type": "object"}),
+                "gpt-4o-mini",
+                "https://api.openai.com/v1",
+            )
+            .with_max_retries(i)
+        })
+        .collect();
+    let jobs = repo.create_jobs(requests).await.unwrap();
+
+    assert_eq!(jobs.len(), 3);
+    for (i, job) in jobs.iter().enumerate() {
+        assert_eq!(job.url, format!("https://example.com/{i}"));
+        assert_eq!(job.max_retries, i as u32);
+        assert_eq!(job.status, JobStatus::Pending);
+    }
+    assert_eq!(
+        repo.count_jobs(Some(JobStatus::Pending), None, None)
+            .await
+            .unwrap(),
+        3
+    );
+    assert!(repo.create_jobs(Vec::new()).await.unwrap().is_empty());
+}
+
+#[tokio::test]
+async fn fetch_options_round_trip() {
+    let (pool, _container) = setup_test_db().await;
+    let repo = ScrapeJobRepository::new(pool);
+
+    let options = FetchOptions {
+        use_browser: true,
+        fetch_timeout_secs: Some(60),
+        wait_selector: Some("#app".into()),
+        headers: [("Accept-Language".to_string(), "it-IT".to_string())].into(),
+        ..FetchOptions::default()
+    };
+    let job = repo
+        .create_job(test_request().with_fetch_options(options.clone()))
+        .await
+        .unwrap();
+    let plain = repo.create_job(test_request()).await.unwrap();
+
+    assert_eq!(
+        repo.get_job(job.id).await.unwrap().unwrap().fetch_options,
+        options
+    );
+    assert!(plain.fetch_options.is_default());
+}
+
 #[tokio::test]
 async fn claim_job_sets_running_and_worker() {
     let (pool, _container) = setup_test_db().await;
@@ -117,6 +180,79 @@ async fn complete_job_sets_completed_status() {
     assert!(updated.worker_id.is_none());
 }
 
+#[tokio::test]
+async fn complete_job_with_extraction_saves_and_completes_together() {
+    let (pool, _container) = setup_test_db().await;
+    let extraction_repo = ares_db::ExtractionRepository::new(pool.clone());
+    let repo = ScrapeJobRepository::new(pool);
+
+    let job = repo.create_job(test_request()).await.unwrap();
+    repo.claim_job("worker-1").await.unwrap().unwrap();
+    let extraction = ares_core::models::NewExtraction {
+        url: "https://example.com".into(),
+        schema_name: "blog".into(),
+        extracted_data: serde_json::json!({"title": "Test"}),
+        raw_content_hash: "hash".into(),
+        data_hash: "dhash".into(),
+        model: "model".into(),
+        ..Default::default()
+    };
+
+    let extraction_id = repo
+        .complete_job_with_extraction(job.id, &extraction_repo, &extraction, false)
+        .await
+        .unwrap();
+
+    let updated = repo.get_job(job.id).await.unwrap().unwrap();
+    assert_eq!(updated.status, JobStatus::Completed);
+    assert_eq!(updated.extraction_id, Some(extraction_id));
+    assert!(extraction_repo.get(extraction_id).await.unwrap().is_some());
+}
+
+#[tokio::test]
+async fn transaction_rolls_back_every_write_on_error() {
+    let (pool, _container) = setup_test_db().await;
+    let db = ares_db::Database::from_pool(pool.clone());
+    let extraction_repo = ares_db::ExtractionRepository::new(pool.clone());
+    let repo = ScrapeJobRepository::new(pool);
+
+    let job = repo.create_job(test_request()).await.unwrap();
+    let extraction = ares_core::models::NewExtraction {
+        url: "https://example.com/rolled-back".into(),
+        schema_name: "blog".into(),
+        raw_content_hash: "hash".into(),
+        data_hash: "dhash".into(),
+        model: "model".into(),
+        ..Default::default()
+    };
+
+    // The extraction is saved, then completing the job fails on the
+    // extraction_id foreign key, so neither write survives.
+    let result = db
+        .transaction(|tx| {
+            Box::pin(async move {
+                ares_db::TransactionalExtractionRepository::new(tx)
+                    .save(&extraction)
+                    .await?;
+                ares_db::TransactionalJobRepository::new(tx)
+                    .complete_job(job.id, Some(Uuid::new_v4()))
+                    .await
+            })
+        })
+        .await;
+
+    assert!(result.is_err());
+    assert!(
+        extraction_repo
+            .get_latest("https://example.com/rolled-back", "blog")
+            .await
+            .unwrap()
+            .is_none()
+    );
+    let unchanged = repo.get_job(job.id).await.unwrap().unwrap();
+    assert_eq!(unchanged.status, JobStatus::Pending);
+}
+
 #[tokio::test]
 async fn fail_job_with_retry_resets_to_pending() {
     let (pool, _container) = setup_test_db().await;
@@ -138,6 +274,31 @@ async fn fail_job_with_retry_resets_to_pending() {
     assert!(updated.worker_id.is_none());
 }
 
+#[tokio::test]
+async fn defer_job_resets_to_pending_without_counting_a_retry() {
+    let (pool, _container) = setup_test_db().await;
+    let repo = ScrapeJobRepository::new(pool);
+
+    let job = repo.create_job(test_request()).await.unwrap();
+    repo.claim_job("worker-1").await.unwrap();
+
+    let until = chrono::Utc::now() + chrono::TimeDelta::hours(3);
+    repo.defer_job(job.id, "budget exceeded", until)
+        .await
+        .unwrap();
+
+    let updated = repo.get_job(job.id).await.unwrap().unwrap();
+    assert_eq!(updated.status, JobStatus::Pending);
+    assert_eq!(updated.retry_count, 0);
+    assert_eq!(
+        updated.next_retry_at.map(|at| at.timestamp()),
+        Some(until.timestamp())
+    );
+    assert_eq!(updated.error_message.as_deref(), Some("budget exceeded"));
+    assert!(updated.worker_id.is_none());
+    assert!(repo.claim_job("worker-1").await.unwrap().is_none());
+}
+
 #[tokio::test]
 async fn fail_job_without_retry_marks_failed() {
     let (pool, _container) = setup_test_db().await;
@@ -154,6 +315,28 @@ async fn fail_job_without_retry_marks_failed() {
     assert_eq!(updated.status, JobStatus::Failed);
     assert_eq!(updated.retry_count, 0); // Not incremented for permanent failure
     assert_eq!(updated.error_message.as_deref(), Some("permanent error"));
+    assert!(updated.completed_at.is_some());
+}
+
+#[tokio::test]
+async fn fail_job_ignores_completed() {
+    let (pool, _container) = setup_test_db().await;
+    let repo = ScrapeJobRepository::new(pool);
+
+    let job = repo.create_job(test_request()).await.unwrap();
+    repo.claim_job("worker-1").await.unwrap();
+    repo.complete_job(job.id, None).await.unwrap();
+
+    let next_retry = chrono::Utc::now() + chrono::TimeDelta::minutes(5);
+    repo.fail_job(job.id, "late error", Some(next_retry))
+        .await
+        .unwrap();
+    repo.fail_job(job.id, "late error", None).await.unwrap();
+
+    let updated = repo.get_job(job.id).await.unwrap().unwrap();
+    assert_eq!(updated.status, JobStatus::Completed);
+    assert_eq!(updated.retry_count, 0);
+    assert!(updated.error_message.is_none());
 }
 
 #[tokio::test]
@@ -185,6 +368,65 @@ async fn cancel_job_ignores_completed() {
     assert_eq!(updated.status, JobStatus::Completed);
 }
 
+#[tokio::test]
+async fn update_job_schema_only_changes_pending_jobs() {
+    let (pool, _container) = setup_test_db().await;
+    let repo = ScrapeJobRepository::new(pool);
+
+    let claimed = repo.create_job(test_request()).await.unwrap();
+    repo.claim_job("worker-1").await.unwrap();
+    let pending = repo.create_job(test_request()).await.unwrap();
+
+    let fixed = serde_json::json!({
+        "type": "object",
+        "properties": {"title": {"type": "string"}}
+    });
+    repo.update_job_schema(pending.id, fixed.clone())
+        .await
+        .unwrap();
+    let updated = repo.get_job(pending.id).await.unwrap().unwrap();
+    assert_eq!(updated.schema, fixed);
+    assert_eq!(updated.status, JobStatus::Pending);
+    assert!(updated.updated_at > pending.updated_at);
+
+    for id in [claimed.id, Uuid::new_v4()] {
+        let err = repo.update_job_schema(id, fixed.clone()).await.unwrap_err();
+        assert!(matches!(err, ares_core::AppError::Conflict(_)), "{err}");
+    }
+    let running = repo.get_job(claimed.id).await.unwrap().unwrap();
+    assert_eq!(running.schema, serde_json::json!({"type": "object"}));
+}
+
+#[tokio::test]
+async fn bulk_cancel_skips_terminal_and_unknown_jobs() {
+    let (pool, _container) = setup_test_db().await;
+    let repo = ScrapeJobRepository::new(pool);
+
+    let done = repo.create_job(test_request()).await.unwrap();
+    repo.claim_job("worker-1").await.unwrap();
+    repo.complete_job(done.id, None).await.unwrap();
+    let running = repo.create_job(test_request()).await.unwrap();
+    repo.claim_job("worker-1").await.unwrap();
+    let pending = repo.create_job(test_request()).await.unwrap();
+
+    let ids = [done.id, pending.id, running.id, Uuid::new_v4()];
+    assert_eq!(repo.bulk_cancel(&ids).await.unwrap(), 2);
+    assert_eq!(repo.bulk_cancel(&ids).await.unwrap(), 0);
+
+    let mut statuses = vec![];
+    for id in [done.id, pending.id, running.id] {
+        statuses.push(repo.get_job(id).await.unwrap().unwrap().status);
+    }
+    assert_eq!(
+        statuses,
+        [
+            JobStatus::Completed,
+            JobStatus::Cancelled,
+            JobStatus::Cancelled
+        ]
+    );
+}
+
 #[tokio::test]
 async fn release_worker_jobs_on_shutdown() {
     let (pool, _container) = setup_test_db().await;
@@ -215,21 +457,64 @@ async fn list_jobs_with_status_filter() {
     repo.claim_job("worker-1").await.unwrap();
 
     let pending = repo
-        .list_jobs(Some(JobStatus::Pending), 10, 0)
+        .list_jobs(Some(JobStatus::Pending), None, None, 10, 0)
         .await
         .unwrap();
     assert_eq!(pending.len(), 1);
 
     let running = repo
-        .list_jobs(Some(JobStatus::Running), 10, 0)
+        .list_jobs(Some(JobStatus::Running), None, None, 10, 0)
         .await
         .unwrap();
     assert_eq!(running.len(), 1);
 
-    let all = repo.list_jobs(None, 10, 0).await.unwrap();
+    let all = repo.list_jobs(None, None, None, 10, 0).await.unwrap();
     assert_eq!(all.len(), 2);
 }
 
+#[tokio::test]
+async fn list_jobs_with_worker_filter() {
+    let (pool, _container) = setup_test_db().await;
+    let repo = ScrapeJobRepository::new(pool);
+
+    for _ in 0..4 {
+        repo.create_job(test_request()).await.unwrap();
+    }
+    let claimed = repo.claim_job("worker-1").await.unwrap().unwrap();
+    repo.claim_job("worker-1").await.unwrap();
+    repo.claim_job("worker-2").await.unwrap();
+    repo.complete_job(claimed.id, None).await.unwrap();
+
+    let held = repo
+        .list_jobs(None, Some("worker-1"), None, 10, 0)
+        .await
+        .unwrap();
+    assert_eq!(held.len(), 2);
+    assert!(
+        held.iter()
+            .all(|job| job.worker_id.as_deref() == Some("worker-1"))
+    );
+
+    let running = repo
+        .list_jobs(Some(JobStatus::Running), Some("worker-1"), None, 10, 0)
+        .await
+        .unwrap();
+    assert_eq!(running.len(), 1);
+    assert_eq!(
+        repo.count_jobs(Some(JobStatus::Running), Some("worker-1"), None)
+            .await
+            .unwrap(),
+        1
+    );
+
+    assert!(
+        repo.list_jobs(None, Some("worker-3"), None, 10, 0)
+            .await
+            .unwrap()
+            .is_empty()
+    );
+}
+
 #[tokio::test]
 async fn list_jobs_with_offset() {
     let (pool, _container) = setup_test_db().await;
@@ -239,10 +524,10 @@ async fn list_jobs_with_offset() {
         repo.create_job(test_request()).await.unwrap();
     }
 
-    let page1 = repo.list_jobs(None, 2, 0).await.unwrap();
+    let page1 = repo.list_jobs(None, None, None, 2, 0).await.unwrap();
     assert_eq!(page1.len(), 2);
 
-    let page2 = repo.list_jobs(None, 2, 2).await.unwrap();
+    let page2 = repo.list_jobs(None, None, None, 2, 2).await.unwrap();
     assert_eq!(page2.len(), 1);
 
     // Pages should not overlap
@@ -250,6 +535,48 @@ async fn list_jobs_with_offset() {
     assert_ne!(page1[1].id, page2[0].id);
 }
 
+#[tokio::test]
+async fn list_and_delete_jobs_by_tag() {
+    let (pool, _container) = setup_test_db().await;
+    let repo = ScrapeJobRepository::new(pool);
+
+    let tagged = repo
+        .create_job(test_request().with_tags(vec!["run-42".into(), "nightly".into()]))
+        .await
+        .unwrap();
+    let running = repo
+        .create_job(test_request().with_tags(vec!["run-42".into()]))
+        .await
+        .unwrap();
+    let other = repo.create_job(test_request()).await.unwrap();
+    assert_eq!(tagged.tags, ["run-42", "nightly"]);
+    assert!(other.tags.is_empty());
+
+    let listed = repo
+        .list_jobs(None, None, Some("run-42"), 10, 0)
+        .await
+        .unwrap();
+    assert_eq!(listed.len(), 2);
+    assert_eq!(
+        repo.count_jobs(None, None, Some("run-42")).await.unwrap(),
+        2
+    );
+    assert_eq!(
+        repo.count_jobs(None, None, Some("nightly")).await.unwrap(),
+        1
+    );
+
+    repo.cancel_job(tagged.id).await.unwrap();
+    let claimed = repo.claim_job("worker-1").await.unwrap().unwrap();
+    assert_eq!(claimed.id, running.id);
+
+    // Only finished jobs are deleted.
+    assert_eq!(repo.delete_jobs_by_tag("run-42").await.unwrap(), 1);
+    assert!(repo.get_job(tagged.id).await.unwrap().is_none());
+    assert!(repo.get_job(running.id).await.unwrap().is_some());
+    assert!(repo.get_job(other.id).await.unwrap().is_some());
+}
+
 #[tokio::test]
 async fn count_by_status() {
     let (pool, _container) = setup_test_db().await;
@@ -262,3 +589,168 @@ async fn count_by_status() {
     assert_eq!(repo.count_by_status(JobStatus::Pending).await.unwrap(), 3);
     assert_eq!(repo.count_by_status(JobStatus::Running).await.unwrap(), 0);
 }
+
+#[tokio::test]
+async fn count_by_worker_counts_running_jobs() {
+    let (pool, _container) = setup_test_db().await;
+    let repo = ScrapeJobRepository::new(pool);
+
+    for _ in 0..4 {
+        repo.create_job(test_request()).await.unwrap();
+    }
+    repo.claim_job("worker-1").await.unwrap();
+    repo.claim_job("worker-1").await.unwrap();
+    let done = repo.claim_job("worker-2").await.unwrap().unwrap();
+    repo.complete_job(done.id, None).await.unwrap();
+    repo.claim_job("worker-2").await.unwrap();
+
+    assert_eq!(repo.count_by_worker("worker-1").await.unwrap(), 2);
+    assert_eq!(repo.count_by_worker("worker-2").await.unwrap(), 1);
+    assert_eq!(repo.count_by_worker("worker-3").await.unwrap(), 0);
+
+    let by_worker = repo.count_running_by_worker().await.unwrap();
+    assert_eq!(by_worker.len(), 2);
+    assert_eq!(by_worker["worker-1"], 2);
+    assert_eq!(by_worker["worker-2"], 1);
+}
+
+#[tokio::test]
+async fn cleanup_finds_and_deletes_old_terminal_jobs() {
+    let (pool, _container) = setup_test_db().await;
+    let repo = ScrapeJobRepository::new(pool.clone());
+
+    let old = repo.create_job(test_request()).await.unwrap();
+    let recent = repo.create_job(test_request()).await.unwrap();
+    let old_failed = repo.create_job(test_request()).await.unwrap();
+    let old_pending = repo.create_job(test_request()).await.unwrap();
+    for (id, status, days) in [
+        (old.id, "completed", 40),
+        (recent.id, "completed", 1),
+        (old_failed.id, "failed", 40),
+        (old_pending.id, "pending", 40),
+    ] {
+        sqlx::query(
+            "UPDATE scrape_jobs SET status = $2, updated_at = NOW() - make_interval(days => $3) \
+             WHERE id = $1",
+        )
+        .bind(id)
+        .bind(status)
+        .bind(days)
+        .execute(&pool)
+        .await
+        .unwrap();
+    }
+
+    let thirty_days = Duration::from_secs(30 * 24 * 60 * 60);
+    let jobs = repo
+        .get_jobs_for_cleanup(thirty_days, JobStatus::Completed)
+        .await
+        .unwrap();
+    assert_eq!(jobs.iter().map(|j| j.id).collect::<Vec<_>>(), [old.id]);
+    assert!(
+        repo.get_jobs_for_cleanup(thirty_days, JobStatus::Pending)
+            .await
+            .is_err()
+    );
+
+    let deleted = repo
+        .delete_jobs(&[old.id, old_failed.id, old_pending.id])
+        .await
+        .unwrap();
+    assert_eq!(deleted, 2);
+    assert!(repo.get_job(old.id).await.unwrap().is_none());
+    assert!(repo.get_job(old_failed.id).await.unwrap().is_none());
+    assert!(repo.get_job(old_pending.id).await.unwrap().is_some());
+    assert!(repo.get_job(recent.id).await.unwrap().is_some());
+}
+
+#[tokio::test]
+async fn schema_filtered_claims_only_take_matching_jobs() {
+    let (pool, _container) = setup_test_db().await;
+    let repo = ScrapeJobRepository::new(pool.clone());
+    let request = |schema_name: &str| {
+        CreateScrapeJobRequest::new(
+            "https://example.com",
+            schema_name,
+            serde_json::json!({"type": "object"}),
+            "gpt-4o-mini",
+            "https://api.openai.com/v1",
+        )
+    };
+    repo.create_job(request("news")).await.unwrap();
+    repo.create_job(request("blog_posts")).await.unwrap();
+    repo.create_job(request("blogXposts")).await.unwrap();
+
+    // `_` is literal, not a LIKE wildcard.
+    let literal = ScrapeJobRepository::new(pool.clone()).with_schema_filter("blog_posts");
+    let claimed = literal.claim_job("worker-1").await.unwrap().unwrap();
+    assert_eq!(claimed.schema_name, "blog_posts");
+    assert!(literal.claim_job("worker-1").await.unwrap().is_none());
+
+    let blogs = ScrapeJobRepository::new(pool.clone()).with_schema_filter("blog*");
+    let claimed = blogs.claim_job("worker-2").await.unwrap().unwrap();
+    assert_eq!(claimed.schema_name, "blogXposts");
+    assert!(blogs.claim_job("worker-2").await.unwrap().is_none());
+
+    // An unfiltered repository still takes everything else.
+    let claimed = repo.claim_job("worker-3").await.unwrap().unwrap();
+    assert_eq!(claimed.schema_name, "news");
+}
+
+/// The schema-filtered claim is served by `idx_scrape_jobs_schema_status`
+/// rather than a scan of every pending job. Against this data,
+/// `EXPLAIN ANALYZE` of the claim's subquery reports:
+///
+/// ```text
+/// Limit
+///   ->  LockRows
+///         ->  Sort  (Sort Key: next_retry_at NULLS FIRST, created_at)
+///               ->  Index Scan using idx_scrape_jobs_schema_status on scrape_jobs
+///                     Index Cond: ((schema_name ~>=~ 'blog'::text) AND (schema_name ~<~ 'bloh'::text))
+///                     Filter: (((schema_name)::text ~~ 'blog%'::text) AND ((next_retry_at IS NULL) OR (next_retry_at <= now())))
+/// ```
+#[tokio::test]
+async fn schema_filtered_claims_use_the_schema_index() {
+    let (pool, _container) = setup_test_db().await;
+
+    sqlx::query(
+        r#"
+        INSERT INTO scrape_jobs (url, schema_name, schema, model)
+        SELECT 'https://example.com/' || n,
+               CASE WHEN n % 500 = 0 THEN 'blog' ELSE 'news' END,
+               '{"type": "object"}', 'gpt-4o-mini'
+        FROM generate_series(1, 5000) AS n
+        "#,
+    )
+    .execute(&pool)
+    .await
+    .unwrap();
+    sqlx::query("ANALYZE scrape_jobs")
+        .execute(&pool)
+        .await
+        .unwrap();
+
+    let plan: Vec<(String,)> = sqlx::query_as(
+        r#"
+        EXPLAIN
+        SELECT id FROM scrape_jobs
+        WHERE schema_name LIKE 'blog%'
+          AND status = 'pending'
+          AND (next_retry_at IS NULL OR next_retry_at <= NOW())
+        ORDER BY next_retry_at NULLS FIRST, created_at ASC
+        FOR UPDATE SKIP LOCKED
+        LIMIT 1
+        "#,
+    )
+    .fetch_all(&pool)
+    .await
+    .unwrap();
+    let plan: Vec<_> = plan.into_iter().map(|(line,)| line).collect();
+
+    assert!(
+        plan.iter()
+            .any(|line| line.contains("idx_scrape_jobs_schema_status")),
+        "{}",
+        plan.join("\n")
+    );
+}
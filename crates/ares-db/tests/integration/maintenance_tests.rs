@@ -0,0 +1,175 @@
+use ares_core::event_log::NewWorkerEvent;
+use ares_core::job::CreateScrapeJobRequest;
+use ares_core::job_queue::JobQueue;
+use ares_db::{Database, PruneReport, TableCounts};
+use chrono::{Duration, Utc};
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::integration::common::{setup_empty_db, setup_test_db};
+
+fn test_request() -> CreateScrapeJobRequest {
+    CreateScrapeJobRequest::new(
+        "https://example.com",
+        "blog",
+        serde_json::json!({"type": "object"}),
+        "gpt-4o-mini",
+        "https://api.openai.com/v1",
+    )
+}
+
+/// Set a job's status and backdate it by `days`.
+async fn age_job(pool: &PgPool, id: Uuid, status: &str, days: i64) {
+    sqlx::query(
+        "UPDATE scrape_jobs SET status = $2, updated_at = NOW() - make_interval(days => $3) \
+         WHERE id = $1",
+    )
+    .bind(id)
+    .bind(status)
+    .bind(days as i32)
+    .execute(pool)
+    .await
+    .unwrap();
+}
+
+#[tokio::test]
+async fn migrate_applies_every_pending_migration() {
+    let (pool, _container) = setup_empty_db().await;
+    let db = Database::from_pool(pool);
+
+    let before = db.migration_status().await.unwrap();
+    assert!(!before.is_empty());
+    assert!(before.iter().all(|m| !m.is_applied()));
+    assert_eq!(before[0].version, 1);
+    assert_eq!(before[0].description, "init");
+    assert!(!db.migrations_applied().await.unwrap());
+
+    db.migrate().await.unwrap();
+
+    let after = db.migration_status().await.unwrap();
+    assert_eq!(after.len(), before.len());
+    assert!(after.iter().all(|m| m.is_applied()));
+    assert!(db.migrations_applied().await.unwrap());
+
+    // Running again is a no-op.
+    db.migrate().await.unwrap();
+    assert_eq!(db.migration_status().await.unwrap(), after);
+}
+
+#[tokio::test]
+async fn ensure_migrated_refuses_pending_migrations_without_auto_migrate() {
+    let (pool, _container) = setup_empty_db().await;
+    let db = Database::from_pool(pool);
+
+    let err = db.ensure_migrated(false).await.unwrap_err().to_string();
+    assert!(err.contains("pending migration(s)"), "{err}");
+    assert!(err.contains("ares db migrate"), "{err}");
+    // Nothing was applied.
+    assert!(!db.migrations_applied().await.unwrap());
+
+    db.ensure_migrated(true).await.unwrap();
+    assert!(db.migrations_applied().await.unwrap());
+    db.ensure_migrated(false).await.unwrap();
+}
+
+#[tokio::test]
+async fn status_reports_table_counts() {
+    let (pool, _container) = setup_empty_db().await;
+    let db = Database::from_pool(pool);
+    db.migrate().await.unwrap();
+
+    assert_eq!(
+        db.table_counts().await.unwrap(),
+        TableCounts {
+            extractions: 0,
+            scrape_jobs: 0,
+        }
+    );
+
+    db.job_repo().create_job(test_request()).await.unwrap();
+    db.job_repo().create_job(test_request()).await.unwrap();
+    assert_eq!(db.table_counts().await.unwrap().scrape_jobs, 2);
+}
+
+#[tokio::test]
+async fn prune_deletes_only_old_finished_rows() {
+    let (pool, _container) = setup_test_db().await;
+    let db = Database::from_pool(pool.clone());
+    let jobs = db.job_repo();
+
+    let old_completed = jobs.create_job(test_request()).await.unwrap().id;
+    age_job(&pool, old_completed, "completed", 40).await;
+    let old_failed = jobs.create_job(test_request()).await.unwrap().id;
+    age_job(&pool, old_failed, "failed", 40).await;
+    let recent_completed = jobs.create_job(test_request()).await.unwrap().id;
+    age_job(&pool, recent_completed, "completed", 1).await;
+    let old_pending = jobs.create_job(test_request()).await.unwrap().id;
+    age_job(&pool, old_pending, "pending", 40).await;
+
+    db.worker_event_repo()
+        .insert(&[NewWorkerEvent {
+            worker_id: "worker-1".to_string(),
+            job_id: Some(old_completed),
+            event_type: "job_completed".to_string(),
+            detail: serde_json::json!({}),
+        }])
+        .await
+        .unwrap();
+    sqlx::query("UPDATE worker_events SET created_at = NOW() - INTERVAL '40 days'")
+        .execute(&pool)
+        .await
+        .unwrap();
+
+    let report = db.prune(Utc::now() - Duration::days(30)).await.unwrap();
+    assert_eq!(
+        report,
+        PruneReport {
+            scrape_jobs: 2,
+            worker_events: 1,
+            crawl_visited_urls: 0,
+        }
+    );
+
+    assert!(jobs.get_job(old_completed).await.unwrap().is_none());
+    assert!(jobs.get_job(old_failed).await.unwrap().is_none());
+    assert!(jobs.get_job(recent_completed).await.unwrap().is_some());
+    assert!(jobs.get_job(old_pending).await.unwrap().is_some());
+}
+
+#[tokio::test]
+async fn prune_keeps_crawl_sessions_until_every_job_is_old() {
+    let (pool, _container) = setup_test_db().await;
+    let db = Database::from_pool(pool.clone());
+    let jobs = db.job_repo();
+    let session = Uuid::new_v4();
+
+    let root = jobs
+        .create_job(test_request().with_crawl_context(session, None, 0, 1))
+        .await
+        .unwrap()
+        .id;
+    let child = jobs
+        .create_job(test_request().with_crawl_context(session, Some(root), 1, 1))
+        .await
+        .unwrap()
+        .id;
+    age_job(&pool, root, "completed", 40).await;
+    age_job(&pool, child, "completed", 1).await;
+    sqlx::query(
+        "INSERT INTO crawl_visited_urls (session_id, url_hash, created_at) \
+         VALUES ($1, 'abc', NOW() - INTERVAL '40 days')",
+    )
+    .bind(session)
+    .execute(&pool)
+    .await
+    .unwrap();
+
+    let cutoff = Utc::now() - Duration::days(30);
+    assert_eq!(db.prune(cutoff).await.unwrap(), PruneReport::default());
+
+    age_job(&pool, child, "completed", 40).await;
+    let report = db.prune(cutoff).await.unwrap();
+    assert_eq!(report.scrape_jobs, 2);
+    assert_eq!(report.crawl_visited_urls, 1);
+    assert!(jobs.get_job(root).await.unwrap().is_none());
+}
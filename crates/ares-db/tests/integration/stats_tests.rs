@@ -0,0 +1,148 @@
+use chrono::{Duration, Utc};
+
+use ares_core::job::CreateScrapeJobRequest;
+use ares_core::job_queue::JobQueue;
+use ares_core::models::NewExtraction;
+use ares_db::{ExtractionRepository, ScrapeJobRepository, StatsRepository};
+
+use crate::integration::common::setup_test_db;
+
+fn job_for(url: &str) -> CreateScrapeJobRequest {
+    CreateScrapeJobRequest::new(
+        url,
+        "blog",
+        serde_json::json!({"type": "object"}),
+        "gpt-4o-mini",
+        "https://api.openai.com/v1",
+    )
+}
+
+fn extraction_for(url: &str) -> NewExtraction {
+    NewExtraction {
+        url: url.into(),
+        schema_name: "blog".into(),
+        extracted_data: serde_json::json!({"title": "Hello"}),
+        raw_content_hash: "a".repeat(64),
+        content_simhash: None,
+        data_hash: "b".repeat(64),
+        model: "gpt-4o-mini".into(),
+        provider: "openai".into(),
+        prompt_hash: None,
+        schema_hash: None,
+        schema_version: None,
+        latency_ms: None,
+        prompt_tokens: None,
+        completion_tokens: None,
+        html_snapshot_key: None,
+        markdown_snapshot_key: None,
+        duplicate_of: None,
+        detected_language: None,
+        retention: None,
+    }
+}
+
+#[tokio::test]
+async fn domain_stats_groups_job_outcomes_and_extractions_by_domain() {
+    let (pool, _container) = setup_test_db().await;
+    let jobs = ScrapeJobRepository::new(pool.clone());
+    let extractions = ExtractionRepository::new(pool.clone());
+    let stats = StatsRepository::new(pool);
+
+    jobs.create_jobs(vec![
+        job_for("https://Example.com/a"),
+        job_for("https://example.com:443/b"),
+        job_for("http://shop.test:8080/item"),
+    ])
+    .await
+    .unwrap();
+
+    for _ in 0..3 {
+        let job = jobs.claim_job("worker-1").await.unwrap().unwrap();
+        if job.url.ends_with("/b") {
+            jobs.fail_job(job.id, "layout changed", None).await.unwrap();
+        } else {
+            jobs.complete_job(job.id, None).await.unwrap();
+        }
+    }
+    // Still pending: not a finished job, so not counted anywhere.
+    jobs.create_job(job_for("https://idle.test/"))
+        .await
+        .unwrap();
+
+    extractions
+        .save(&extraction_for("https://example.com/a"))
+        .await
+        .unwrap();
+
+    let since = Utc::now() - Duration::hours(1);
+    let domains = stats.domain_stats(since).await.unwrap();
+    assert_eq!(domains.len(), 2);
+
+    // The domain with failures sorts first.
+    let example = &domains[0];
+    assert_eq!(example.domain, "https://example.com:443");
+    assert_eq!(example.jobs_succeeded, 1);
+    assert_eq!(example.jobs_failed, 1);
+    assert_eq!(example.failure_rate(), Some(0.5));
+    assert!(example.avg_duration_secs.is_some_and(|secs| secs >= 0.0));
+    assert!(example.last_success_at.is_some());
+    assert_eq!(example.extractions, 1);
+
+    let shop = &domains[1];
+    assert_eq!(shop.domain, "http://shop.test:8080");
+    assert_eq!(shop.jobs_succeeded, 1);
+    assert_eq!(shop.jobs_failed, 0);
+    assert_eq!(shop.extractions, 0);
+
+    // A window with no finished jobs or extractions reports no domains.
+    let later = stats
+        .domain_stats(Utc::now() + Duration::hours(1))
+        .await
+        .unwrap();
+    assert!(later.is_empty());
+}
+
+#[tokio::test]
+async fn domain_stats_windows_jobs_by_when_they_finished() {
+    let (pool, _container) = setup_test_db().await;
+    let jobs = ScrapeJobRepository::new(pool.clone());
+    let stats = StatsRepository::new(pool.clone());
+
+    jobs.create_jobs(vec![
+        job_for("https://example.com/a"),
+        job_for("https://example.com/b"),
+    ])
+    .await
+    .unwrap();
+    for _ in 0..2 {
+        let job = jobs.claim_job("worker-1").await.unwrap().unwrap();
+        if job.url.ends_with("/b") {
+            jobs.fail_job(job.id, "layout changed", None).await.unwrap();
+        } else {
+            jobs.complete_job(job.id, None).await.unwrap();
+        }
+    }
+
+    // Both finished two days ago; a recent unrelated edit bumps updated_at.
+    sqlx::query(
+        "UPDATE scrape_jobs SET completed_at = NOW() - INTERVAL '2 days', \
+         started_at = NOW() - INTERVAL '2 days', updated_at = NOW()",
+    )
+    .execute(&pool)
+    .await
+    .unwrap();
+
+    let recent = stats
+        .domain_stats(Utc::now() - Duration::hours(1))
+        .await
+        .unwrap();
+    assert!(recent.is_empty());
+
+    let week = stats
+        .domain_stats(Utc::now() - Duration::days(7))
+        .await
+        .unwrap();
+    assert_eq!(week.len(), 1);
+    assert_eq!(week[0].jobs_succeeded, 1);
+    assert_eq!(week[0].jobs_failed, 1);
+}
@@ -0,0 +1,58 @@
+use chrono::NaiveDate;
+
+use ares_core::budget::DailyUsage;
+use ares_db::UsageRepository;
+
+use crate::integration::common::setup_test_db;
+
+fn day(d: u32) -> NaiveDate {
+    NaiveDate::from_ymd_opt(2026, 3, d).unwrap()
+}
+
+fn one_call(day: NaiveDate, tokens: u64) -> DailyUsage {
+    DailyUsage {
+        day,
+        llm_calls: 1,
+        tokens,
+        cost_usd: 0.25,
+    }
+}
+
+#[tokio::test]
+async fn concurrent_increments_are_not_lost() {
+    let (pool, _container) = setup_test_db().await;
+    let repo = UsageRepository::new(pool);
+
+    let tasks: Vec<_> = (0..40)
+        .map(|_| {
+            let repo = repo.clone();
+            tokio::spawn(async move { repo.increment(&one_call(day(9), 10)).await.unwrap() })
+        })
+        .collect();
+    for task in tasks {
+        task.await.unwrap();
+    }
+
+    let usage = repo.get(day(9)).await.unwrap();
+    assert_eq!(usage.llm_calls, 40);
+    assert_eq!(usage.tokens, 400);
+    assert!((usage.cost_usd - 10.0).abs() < 1e-9);
+}
+
+#[tokio::test]
+async fn each_day_has_its_own_counters() {
+    let (pool, _container) = setup_test_db().await;
+    let repo = UsageRepository::new(pool);
+
+    repo.increment(&one_call(day(9), 100)).await.unwrap();
+    let next_day = repo.increment(&one_call(day(10), 7)).await.unwrap();
+
+    assert_eq!(next_day.llm_calls, 1);
+    assert_eq!(next_day.tokens, 7);
+    assert_eq!(repo.get(day(9)).await.unwrap().tokens, 100);
+    assert_eq!(repo.get(day(11)).await.unwrap(), DailyUsage::empty(day(11)));
+
+    let recent = repo.list_since(day(10)).await.unwrap();
+    assert_eq!(recent.len(), 1);
+    assert_eq!(recent[0].day, day(10));
+}
@@ -0,0 +1,52 @@
+use uuid::Uuid;
+
+use ares_core::event_log::{NewWorkerEvent, WorkerEventStore};
+use ares_db::WorkerEventRepository;
+
+use crate::integration::common::setup_test_db;
+
+fn event(job_id: Uuid, event_type: &str) -> NewWorkerEvent {
+    NewWorkerEvent {
+        worker_id: "worker-1".to_string(),
+        job_id: Some(job_id),
+        event_type: event_type.to_string(),
+        detail: serde_json::json!({ "step": event_type }),
+    }
+}
+
+#[tokio::test]
+async fn events_are_listed_per_job_in_order() {
+    let (pool, _container) = setup_test_db().await;
+    let repo = WorkerEventRepository::new(pool);
+    let job = Uuid::new_v4();
+    let other = Uuid::new_v4();
+
+    repo.save_events(&[
+        event(job, "job_claimed"),
+        event(other, "job_claimed"),
+        event(job, "job_started"),
+    ])
+    .await
+    .unwrap();
+    repo.save_events(&[event(job, "job_completed")])
+        .await
+        .unwrap();
+
+    let events = repo.list_for_job(job, 10).await.unwrap();
+    let types: Vec<_> = events.iter().map(|e| e.event_type.as_str()).collect();
+    assert_eq!(types, ["job_claimed", "job_started", "job_completed"]);
+    assert_eq!(events[1].detail["step"], "job_started");
+    assert!(events.iter().all(|e| e.worker_id == "worker-1"));
+
+    // The limit keeps the most recent events.
+    let last_two = repo.list_for_job(job, 2).await.unwrap();
+    let types: Vec<_> = last_two.iter().map(|e| e.event_type.as_str()).collect();
+    assert_eq!(types, ["job_started", "job_completed"]);
+
+    assert!(
+        repo.list_for_job(Uuid::new_v4(), 10)
+            .await
+            .unwrap()
+            .is_empty()
+    );
+}
@@ -0,0 +1,71 @@
+use std::time::Duration;
+
+use ares_core::circuit_breaker::{CircuitBreaker, CircuitBreakerConfig};
+use ares_core::job::{CreateScrapeJobRequest, JobStatus, WorkerConfig};
+use ares_core::job_queue::JobQueue;
+use ares_core::testutil::{
+    MockCleaner, MockExtractorFactory, MockFetcher, MockLinkDiscoverer, MockReporter,
+    MockRobotsChecker,
+};
+use ares_core::worker::WorkerService;
+use ares_db::{ExtractionRepository, ScrapeJobRepository};
+use tokio_util::sync::CancellationToken;
+
+use crate::integration::common::setup_test_db;
+
+#[tokio::test]
+async fn worker_claims_and_completes_a_real_job() {
+    let (pool, _container) = setup_test_db().await;
+    let jobs = ScrapeJobRepository::new(pool.clone());
+    let extractions = ExtractionRepository::new(pool);
+
+    let worker = WorkerService::new(
+        jobs.clone(),
+        MockFetcher::new("<html><h1>Hello</h1></html>"),
+        MockCleaner::passthrough(),
+        MockExtractorFactory::new(serde_json::json!({"title": "Hello"})),
+        extractions.clone(),
+        MockLinkDiscoverer::new(),
+        MockRobotsChecker::new(),
+        CircuitBreaker::new("test", CircuitBreakerConfig::default()),
+        WorkerConfig::default()
+            .with_worker_id("worker-1")
+            .with_poll_interval(Duration::from_millis(20)),
+    );
+    let reporter = MockReporter::new();
+    let cancel = CancellationToken::new();
+
+    let enqueue = async {
+        let job = jobs
+            .create_job(CreateScrapeJobRequest::new(
+                "https://example.com",
+                "blog",
+                serde_json::json!({
+                    "type": "object",
+                    "properties": {"title": {"type": "string"}}
+                }),
+                "gpt-4o-mini",
+                "https://api.openai.com/v1",
+            ))
+            .await
+            .unwrap();
+        tokio::time::sleep(Duration::from_millis(500)).await;
+        cancel.cancel();
+        job
+    };
+    let (summary, job) = tokio::join!(worker.run(cancel.clone(), &reporter), enqueue);
+    let summary = summary.unwrap();
+    assert_eq!(summary.completed, 1);
+
+    let job = jobs.get_job(job.id).await.unwrap().unwrap();
+    assert_eq!(job.status, JobStatus::Completed);
+    assert!(job.worker_id.is_none());
+
+    let latest = extractions
+        .get_latest("https://example.com", "blog")
+        .await
+        .unwrap()
+        .expect("the worker saved an extraction");
+    assert_eq!(job.extraction_id, Some(latest.id));
+    assert_eq!(latest.extracted_data["title"], "Hello");
+}